@@ -0,0 +1,370 @@
+//! Portable bundles of contiguous block headers, for bootstrapping light
+//! clients.
+//!
+//! A bundle contains a segment of the best chain, and the hash of a trusted
+//! checkpoint header in that segment. The checkpoint and the headers below it
+//! are trusted, and provide the context for verifying the headers above it.
+//! Bundles are verified without a database, by replaying the headers into a
+//! new `ZcashClient`.
+//!
+//! The bundle's start height is untrusted, so bundles are verified against
+//! the height and hash of the trusted checkpoint.
+//!
+//! Bundles have two encodings:
+//!   * a binary encoding, using the zcash serialization of block headers, and
+//!   * a JSON encoding, using the serde serialization of block headers.
+//!
+//! The binary encoding is:
+//!   * the magic bytes `ZHCB`
+//!   * the format version, a `u8`
+//!   * the network, a `u8`: 0 for Mainnet, 1 for Testnet
+//!   * the start height, as a little-endian `u32`
+//!   * the checkpoint hash, 32 bytes
+//!   * the cumulative work, as a big-endian `u128`
+//!   * the headers, as a compactsize count followed by each header
+
+use std::{convert::TryFrom, error, io};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use zebra_chain::{
+    block::{BlockHeader, BlockHeaderHash, Work},
+    serialization::{SerializationError, ZcashDeserialize, ZcashSerialize},
+    types::BlockHeight,
+    Network,
+};
+
+use crate::client::ZcashClient;
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// The magic bytes at the start of a binary header bundle.
+const BUNDLE_MAGIC: [u8; 4] = *b"ZHCB";
+
+/// The current binary header bundle format version.
+pub const BUNDLE_FORMAT_VERSION: u8 = 1;
+
+/// A contiguous segment of block headers, with the metadata needed to verify
+/// it against a trusted checkpoint.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeaderBundle {
+    /// The network of the headers.
+    pub network: Network,
+    /// The height of the first header.
+    pub start_height: BlockHeight,
+    /// The hash of the trusted checkpoint header, which must be one of
+    /// `headers`.
+    pub checkpoint: BlockHeaderHash,
+    /// The sum of the work of every header in the bundle.
+    ///
+    /// If the bundle starts at genesis, this is the cumulative work of the
+    /// chain.
+    pub cumulative_work: Work,
+    /// The block headers, in ascending height order.
+    pub headers: Vec<BlockHeader>,
+}
+
+impl HeaderBundle {
+    /// Create a bundle of `headers`, which starts at `start_height`, and
+    /// contains the `checkpoint` header.
+    pub fn new(
+        network: Network,
+        start_height: BlockHeight,
+        checkpoint: BlockHeaderHash,
+        headers: Vec<BlockHeader>,
+    ) -> Result<Self, Error> {
+        let cumulative_work = total_work(&headers)?;
+
+        Ok(Self {
+            network,
+            start_height,
+            checkpoint,
+            cumulative_work,
+            headers,
+        })
+    }
+
+    /// Returns the height of the last header, or `None` if the bundle is
+    /// empty.
+    ///
+    /// Returns an error if the height is out of range.
+    pub fn end_height(&self) -> Result<Option<BlockHeight>, Error> {
+        match self.headers.len().checked_sub(1) {
+            Some(offset) => self.height_at(offset).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the height of the header at `index`.
+    fn height_at(&self, index: usize) -> Result<BlockHeight, Error> {
+        u32::try_from(index)
+            .ok()
+            .and_then(|offset| self.start_height.0.checked_add(offset))
+            .map(BlockHeight)
+            .ok_or_else(|| {
+                format!(
+                    "bundle header {} is out of range, starting at {:?}",
+                    index, self.start_height
+                )
+                .into()
+            })
+    }
+
+    /// Verify every header in the bundle, starting from `trusted_checkpoint`,
+    /// and return a light client that has verified the bundle.
+    ///
+    /// `trusted_checkpoint` is the height and hash of a trusted block header.
+    /// The bundle must contain that header at that height, and enough headers
+    /// below it to check the difficulty adjustment of the next header. `now`
+    /// is the current time, which is used to reject headers from the future.
+    pub fn verify(
+        &self,
+        trusted_checkpoint: (BlockHeight, BlockHeaderHash),
+        trusting_depth: u32,
+        now: DateTime<Utc>,
+    ) -> Result<ZcashClient, Error> {
+        let (trusted_height, trusted_hash) = trusted_checkpoint;
+        if self.checkpoint != trusted_hash {
+            return Err(format!(
+                "bundle checkpoint {:?} is not the trusted checkpoint {:?}",
+                self.checkpoint, trusted_hash
+            )
+            .into());
+        }
+
+        let checkpoint_index = self
+            .headers
+            .iter()
+            .position(|block_header| BlockHeaderHash::from(block_header) == self.checkpoint)
+            .ok_or("bundle does not contain its checkpoint header")?;
+        let checkpoint_height = self.height_at(checkpoint_index)?;
+        if checkpoint_height != trusted_height {
+            return Err(format!(
+                "bundle checkpoint is at {:?}, but the trusted checkpoint is at {:?}",
+                checkpoint_height, trusted_height
+            )
+            .into());
+        }
+        self.end_height()?;
+
+        let cumulative_work = total_work(&self.headers)?;
+        if cumulative_work != self.cumulative_work {
+            return Err(format!(
+                "bundle has cumulative work {:?}, but its headers have {:?}",
+                self.cumulative_work, cumulative_work
+            )
+            .into());
+        }
+
+        let (trusted_headers, headers) = self.headers.split_at(checkpoint_index + 1);
+        let mut client = ZcashClient::new(
+            self.network,
+            trusting_depth,
+            checkpoint_height,
+            trusted_headers,
+        )?;
+        client.update_client(headers, now)?;
+
+        Ok(client)
+    }
+
+    /// Returns the JSON encoding of this bundle.
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Reads a bundle from its JSON encoding.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+/// Returns the sum of the work of `headers`.
+fn total_work(headers: &[BlockHeader]) -> Result<Work, Error> {
+    headers.iter().try_fold(Work::default(), |work, block_header| {
+        block_header
+            .bits
+            .to_work()
+            .and_then(|header_work| work.checked_add(header_work))
+            .ok_or_else(|| {
+                format!(
+                    "invalid work for block header {:?}",
+                    BlockHeaderHash::from(block_header)
+                )
+                .into()
+            })
+    })
+}
+
+impl ZcashSerialize for HeaderBundle {
+    fn zcash_serialize<W: io::Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        writer.write_all(&BUNDLE_MAGIC)?;
+        writer.write_all(&[BUNDLE_FORMAT_VERSION])?;
+        writer.write_all(&[match self.network {
+            Network::Mainnet => 0,
+            Network::Testnet => 1,
+        }])?;
+        writer.write_all(&self.start_height.0.to_le_bytes())?;
+        self.checkpoint.zcash_serialize(&mut writer)?;
+        writer.write_all(&self.cumulative_work.to_be_bytes())?;
+        self.headers.zcash_serialize(&mut writer)?;
+
+        Ok(())
+    }
+}
+
+impl ZcashDeserialize for HeaderBundle {
+    fn zcash_deserialize<R: io::Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != BUNDLE_MAGIC {
+            return Err(SerializationError::Parse("not a header bundle"));
+        }
+
+        let mut version_and_network = [0u8; 2];
+        reader.read_exact(&mut version_and_network)?;
+        if version_and_network[0] != BUNDLE_FORMAT_VERSION {
+            return Err(SerializationError::Parse(
+                "unsupported header bundle format version",
+            ));
+        }
+        let network = match version_and_network[1] {
+            0 => Network::Mainnet,
+            1 => Network::Testnet,
+            _ => return Err(SerializationError::Parse("unknown header bundle network")),
+        };
+
+        let mut start_height = [0u8; 4];
+        reader.read_exact(&mut start_height)?;
+        let checkpoint = BlockHeaderHash::zcash_deserialize(&mut reader)?;
+        let mut cumulative_work = [0u8; 16];
+        reader.read_exact(&mut cumulative_work)?;
+
+        Ok(Self {
+            network,
+            start_height: BlockHeight(u32::from_le_bytes(start_height)),
+            checkpoint,
+            cumulative_work: Work::from_be_bytes(cumulative_work),
+            headers: Vec::zcash_deserialize(&mut reader)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use zebra_chain::{block::Block, serialization::ZcashDeserializeInto};
+
+    fn genesis_and_block_1() -> Vec<BlockHeader> {
+        [
+            &zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..],
+            &zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..],
+        ]
+        .iter()
+        .map(|bytes| {
+            Block::zcash_deserialize(*bytes)
+                .expect("block test vector should deserialize")
+                .header
+        })
+        .collect()
+    }
+
+    fn genesis_bundle() -> HeaderBundle {
+        let headers = genesis_and_block_1();
+        HeaderBundle::new(
+            Network::Mainnet,
+            BlockHeight(0),
+            BlockHeaderHash::from(&headers[0]),
+            headers,
+        )
+        .expect("mainnet headers should have valid work")
+    }
+
+    #[test]
+    fn bundle_round_trip() {
+        zebra_test::init();
+
+        let bundle = genesis_bundle();
+        assert_eq!(bundle.end_height().unwrap(), Some(BlockHeight(1)));
+
+        let bytes = bundle.zcash_serialize_to_vec().unwrap();
+        assert_eq!(bytes[..4], BUNDLE_MAGIC);
+        let binary: HeaderBundle = bytes
+            .as_slice()
+            .zcash_deserialize_into()
+            .expect("serialized bundles should deserialize");
+        assert_eq!(binary, bundle);
+
+        let json = bundle.to_json().expect("bundles should serialize to JSON");
+        let json = HeaderBundle::from_json(&json).expect("JSON bundles should deserialize");
+        assert_eq!(json, bundle);
+    }
+
+    #[test]
+    fn verify_bundle() {
+        zebra_test::init();
+
+        let bundle = genesis_bundle();
+        let checkpoint = bundle.checkpoint;
+
+        let client = bundle
+            .verify((BlockHeight(0), checkpoint), 1, Utc::now())
+            .expect("the bundle should be valid");
+        assert_eq!(client.client_state().latest_height, BlockHeight(1));
+        assert!(client.trusted_consensus_state(BlockHeight(0)).is_some());
+
+        bundle
+            .verify((BlockHeight(0), BlockHeaderHash([0; 32])), 1, Utc::now())
+            .expect_err("bundles must match the trusted checkpoint");
+
+        let mut wrong_work = bundle.clone();
+        wrong_work.cumulative_work = Work::default();
+        wrong_work
+            .verify((BlockHeight(0), checkpoint), 1, Utc::now())
+            .expect_err("bundles must have the work of their headers");
+
+        let mut bad_solution = bundle.clone();
+        bad_solution.headers[1].nonce = [0; 32];
+        bad_solution
+            .verify((BlockHeight(0), checkpoint), 1, Utc::now())
+            .expect_err("every header in the bundle must be valid");
+
+        let mut missing_checkpoint = bundle.clone();
+        missing_checkpoint.headers.remove(0);
+        missing_checkpoint.start_height = BlockHeight(1);
+        missing_checkpoint.cumulative_work =
+            total_work(&missing_checkpoint.headers).expect("mainnet headers have valid work");
+        missing_checkpoint
+            .verify((BlockHeight(0), checkpoint), 1, Utc::now())
+            .expect_err("bundles must contain their checkpoint");
+    }
+
+    #[test]
+    fn verify_bundle_heights() {
+        zebra_test::init();
+
+        let bundle = genesis_bundle();
+        let checkpoint = bundle.checkpoint;
+
+        bundle
+            .verify((BlockHeight(1), checkpoint), 1, Utc::now())
+            .expect_err("bundles must match the trusted checkpoint height");
+
+        // The start height is untrusted, so it can't move the checkpoint
+        let mut wrong_start = bundle.clone();
+        wrong_start.start_height = BlockHeight(1);
+        wrong_start
+            .verify((BlockHeight(0), checkpoint), 1, Utc::now())
+            .expect_err("bundles must put the checkpoint at its trusted height");
+
+        let mut overflow = bundle;
+        overflow.start_height = BlockHeight(u32::MAX);
+        overflow
+            .end_height()
+            .expect_err("the end height is out of range");
+        overflow
+            .verify((BlockHeight(u32::MAX), checkpoint), 1, Utc::now())
+            .expect_err("headers above the maximum height should be rejected");
+    }
+}
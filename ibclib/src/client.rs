@@ -0,0 +1,765 @@
+//! A Zcash light client, using ICS-02 client and consensus states.
+//!
+//! The client starts from a trusted chain of block headers, then verifies
+//! each new block header using the block header consensus rules in
+//! `zebra_consensus::verify::header`. It doesn't need a running node, so it
+//! can verify headers that are supplied by a relayer.
+
+use std::{collections::BTreeMap, convert::TryFrom, error, slice};
+
+use chrono::{DateTime, Utc};
+use zebra_chain::{
+    block::{BlockHeader, BlockHeaderHash, CompactDifficulty},
+    merkle_tree::MerkleTreeRootHash,
+    note_commitment_tree::SaplingNoteTreeRootHash,
+    types::BlockHeight,
+    Network,
+};
+use zebra_consensus::verify::{difficulty::POW_ADJUSTMENT_BLOCK_SPAN, header::check_block_header};
+
+use crate::misbehaviour::Misbehaviour;
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// The state of a Zcash light client.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientState {
+    /// The network of the tracked chain.
+    pub network: Network,
+    /// The height of the latest verified block header.
+    pub latest_height: BlockHeight,
+    /// The number of block headers that must be verified on top of a block
+    /// header, before its consensus state is trusted.
+    pub trusting_depth: u32,
+    /// The height of the misbehaviour that froze the client, if any.
+    ///
+    /// Frozen clients don't accept any updates.
+    pub frozen_height: Option<BlockHeight>,
+}
+
+impl ClientState {
+    /// Returns true if the client has been frozen.
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_height.is_some()
+    }
+
+    /// Freeze the client because of misbehaviour at `height`.
+    ///
+    /// If the client is already frozen, keeps the lowest misbehaviour height.
+    pub fn freeze(&mut self, height: BlockHeight) {
+        self.frozen_height = Some(
+            self.frozen_height
+                .map_or(height, |frozen_height| frozen_height.min(height)),
+        );
+    }
+}
+
+/// The consensus state of a Zcash light client at a block height.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsensusState {
+    /// The hash of the block header.
+    pub hash: BlockHeaderHash,
+    /// The time of the block header.
+    pub time: DateTime<Utc>,
+    /// The difficulty threshold of the block header, which is needed to
+    /// verify the difficulty adjustment of later headers.
+    pub bits: CompactDifficulty,
+    /// The root of the block's transaction Merkle tree.
+    pub merkle_root: MerkleTreeRootHash,
+    /// The root of the Sapling note commitment tree after the block.
+    pub sapling_root: SaplingNoteTreeRootHash,
+}
+
+impl From<&BlockHeader> for ConsensusState {
+    fn from(block_header: &BlockHeader) -> Self {
+        Self {
+            hash: block_header.into(),
+            time: block_header.time,
+            bits: block_header.bits,
+            merkle_root: block_header.merkle_root_hash,
+            sapling_root: block_header.final_sapling_root_hash,
+        }
+    }
+}
+
+/// A Zcash light client, with its client state, and the consensus states of
+/// the block headers that it has verified.
+///
+/// Consensus states that are more than `trusting_depth` blocks below the
+/// latest height are pruned.
+#[derive(Clone, Debug)]
+pub struct ZcashClient {
+    client_state: ClientState,
+    consensus_states: BTreeMap<BlockHeight, ConsensusState>,
+}
+
+impl ZcashClient {
+    /// Create a client that trusts `trusted_headers`, a chain of block
+    /// headers that ends at `height`.
+    ///
+    /// The trusted headers must include the `POW_ADJUSTMENT_BLOCK_SPAN`
+    /// headers below the first untrusted header, or every header down to
+    /// genesis, so the client can check difficulty adjustments.
+    pub fn new(
+        network: Network,
+        trusting_depth: u32,
+        height: BlockHeight,
+        trusted_headers: &[BlockHeader],
+    ) -> Result<Self, Error> {
+        let required = (height.0 as usize)
+            .saturating_add(1)
+            .min(POW_ADJUSTMENT_BLOCK_SPAN);
+        if trusted_headers.len() < required {
+            return Err(format!(
+                "a client at {:?} needs at least {} trusted headers, but {} were supplied",
+                height,
+                required,
+                trusted_headers.len()
+            )
+            .into());
+        }
+
+        let first_height = u32::try_from(trusted_headers.len() - 1)
+            .ok()
+            .and_then(|offset| height.0.checked_sub(offset))
+            .map(BlockHeight)
+            .ok_or_else(|| {
+                format!(
+                    "{} trusted headers can't end at {:?}",
+                    trusted_headers.len(),
+                    height
+                )
+            })?;
+
+        let mut consensus_states = BTreeMap::new();
+        let mut previous_hash = None;
+        for (block_header, height) in trusted_headers
+            .iter()
+            .zip((first_height.0..=height.0).map(BlockHeight))
+        {
+            if let Some(previous_hash) = previous_hash {
+                if block_header.previous_block_hash != previous_hash {
+                    return Err(format!(
+                        "trusted header at {:?} does not link to the previous trusted header",
+                        height
+                    )
+                    .into());
+                }
+            }
+
+            let consensus_state = ConsensusState::from(block_header);
+            previous_hash = Some(consensus_state.hash);
+            consensus_states.insert(height, consensus_state);
+        }
+
+        let mut client = Self {
+            client_state: ClientState {
+                network,
+                latest_height: height,
+                trusting_depth,
+                frozen_height: None,
+            },
+            consensus_states,
+        };
+        client.prune();
+
+        Ok(client)
+    }
+
+    /// Returns the client state.
+    pub fn client_state(&self) -> &ClientState {
+        &self.client_state
+    }
+
+    /// Returns the consensus state at `height`, if the client has verified
+    /// that block header.
+    ///
+    /// Recent block headers can still be reorganised out of the best chain.
+    /// Use `trusted_consensus_state` to get consensus states that are deep
+    /// enough to be trusted.
+    pub fn consensus_state(&self, height: BlockHeight) -> Option<&ConsensusState> {
+        self.consensus_states.get(&height)
+    }
+
+    /// Returns the consensus state at `height`, if it is at least
+    /// `trusting_depth` blocks below the latest height.
+    pub fn trusted_consensus_state(&self, height: BlockHeight) -> Option<&ConsensusState> {
+        let depth = self.client_state.latest_height.0.checked_sub(height.0)?;
+        if depth < self.client_state.trusting_depth {
+            return None;
+        }
+
+        self.consensus_state(height)
+    }
+
+    /// Verify `headers`, which extend the latest verified block header, and
+    /// store their consensus states.
+    ///
+    /// `now` is the current time, which is used to reject headers from the
+    /// future. The update is atomic: if any header is invalid, none of the
+    /// headers are stored.
+    ///
+    /// Returns the new latest height.
+    pub fn update_client(
+        &mut self,
+        headers: &[BlockHeader],
+        now: DateTime<Utc>,
+    ) -> Result<BlockHeight, Error> {
+        if let Some(frozen_height) = self.client_state.frozen_height {
+            return Err(format!(
+                "the client was frozen by misbehaviour at {:?}",
+                frozen_height
+            )
+            .into());
+        }
+
+        let mut verified: Vec<(BlockHeight, ConsensusState)> = Vec::new();
+        for block_header in headers {
+            let (parent_height, parent_hash) = match verified.last() {
+                Some((height, consensus_state)) => (*height, consensus_state.hash),
+                None => {
+                    let height = self.client_state.latest_height;
+                    let consensus_state = self
+                        .consensus_state(height)
+                        .expect("the client has a consensus state at its latest height");
+                    (height, consensus_state.hash)
+                }
+            };
+
+            if block_header.previous_block_hash != parent_hash {
+                return Err(format!(
+                    "block header {:?} does not extend the latest verified block header {:?} at {:?}",
+                    BlockHeaderHash::from(block_header),
+                    parent_hash,
+                    parent_height
+                ).into());
+            }
+
+            // The ancestors are in reverse height order, starting with the
+            // parent
+            let height = parent_height
+                .0
+                .checked_add(1)
+                .map(BlockHeight)
+                .ok_or("block header height is out of range")?;
+            let context = verified
+                .iter()
+                .rev()
+                .map(|(_height, consensus_state)| consensus_state)
+                .chain(
+                    self.consensus_states
+                        .range(..=self.client_state.latest_height)
+                        .rev()
+                        .map(|(_height, consensus_state)| consensus_state),
+                )
+                .take(POW_ADJUSTMENT_BLOCK_SPAN)
+                .map(|consensus_state| (consensus_state.bits, consensus_state.time))
+                .collect();
+
+            check_block_header(
+                block_header,
+                self.client_state.network,
+                height,
+                context,
+                now,
+            )?;
+            verified.push((height, ConsensusState::from(block_header)));
+        }
+
+        for (height, consensus_state) in verified {
+            self.consensus_states.insert(height, consensus_state);
+            self.client_state.latest_height = height;
+        }
+        self.prune();
+
+        Ok(self.client_state.latest_height)
+    }
+
+    /// Remove consensus states that are more than `trusting_depth` blocks
+    /// below the latest height.
+    ///
+    /// The client also keeps enough consensus states to check the difficulty
+    /// adjustment of the next header.
+    fn prune(&mut self) {
+        let kept_depth = self
+            .client_state
+            .trusting_depth
+            .max(POW_ADJUSTMENT_BLOCK_SPAN as u32 - 1);
+        let lowest_height = self.client_state.latest_height.0.saturating_sub(kept_depth);
+
+        self.consensus_states = self.consensus_states.split_off(&BlockHeight(lowest_height));
+    }
+
+    /// Check `headers` for conflicts with the client's trusted consensus
+    /// states, and return the evidence of the first conflict.
+    ///
+    /// `headers` is a chain of block headers received by the syncer, or
+    /// supplied by a relayer. The first header must extend a verified
+    /// consensus state. Returns `None` if the headers agree with every
+    /// trusted consensus state at their heights.
+    pub fn detect_misbehaviour(
+        &self,
+        headers: &[BlockHeader],
+        now: DateTime<Utc>,
+    ) -> Result<Option<Misbehaviour>, Error> {
+        let first_header = match headers.first() {
+            Some(first_header) => first_header,
+            None => return Ok(None),
+        };
+
+        let parent_height = self
+            .consensus_states
+            .iter()
+            .find(|(_height, consensus_state)| {
+                consensus_state.hash == first_header.previous_block_hash
+            })
+            .map(|(height, _consensus_state)| *height)
+            .ok_or_else(|| {
+                format!(
+                    "block header {:?} does not extend a verified block header",
+                    BlockHeaderHash::from(first_header)
+                )
+            })?;
+
+        for (offset, block_header) in headers.iter().enumerate() {
+            let height = u32::try_from(offset)
+                .ok()
+                .and_then(|offset| parent_height.0.checked_add(offset))
+                .and_then(|height| height.checked_add(1))
+                .map(BlockHeight)
+                .ok_or("block header height is out of range")?;
+            let consensus_state = match self.consensus_state(height) {
+                Some(consensus_state) => consensus_state,
+                None => return Ok(None),
+            };
+            if consensus_state.hash == BlockHeaderHash::from(block_header) {
+                continue;
+            }
+
+            // The rest of the headers are on a different chain. Trusted
+            // consensus states are below the untrusted ones, so the later
+            // headers can't conflict with a trusted consensus state.
+            if self.trusted_consensus_state(height).is_none() {
+                return Ok(None);
+            }
+
+            let misbehaviour = Misbehaviour::ConflictingTrustedState {
+                height,
+                header: *block_header,
+            };
+            self.check_misbehaviour(&misbehaviour, now)?;
+            return Ok(Some(misbehaviour));
+        }
+
+        Ok(None)
+    }
+
+    /// Check that `misbehaviour` is valid evidence against this client.
+    ///
+    /// Each branch must be a valid chain of headers, which extends the
+    /// client's consensus state at the height below the misbehaviour. `now`
+    /// is the current time, which is used to reject headers from the future.
+    ///
+    /// Short forks happen naturally, so conflicting branches must each have
+    /// more than `trusting_depth` headers. A single header is only evidence
+    /// if it conflicts with a trusted consensus state.
+    pub fn check_misbehaviour(
+        &self,
+        misbehaviour: &Misbehaviour,
+        now: DateTime<Utc>,
+    ) -> Result<(), Error> {
+        let height = misbehaviour.height();
+
+        match misbehaviour {
+            Misbehaviour::ConflictingHeaders {
+                branch_1, branch_2, ..
+            } => {
+                let first_hashes = (
+                    branch_1.first().map(BlockHeaderHash::from),
+                    branch_2.first().map(BlockHeaderHash::from),
+                );
+                if first_hashes.0 == first_hashes.1 {
+                    return Err("conflicting branches must start with different headers".into());
+                }
+
+                for branch in [branch_1, branch_2].iter() {
+                    if branch.len() <= self.client_state.trusting_depth as usize {
+                        return Err(format!(
+                            "conflicting branches must have at least {} headers, but a branch has {}",
+                            self.client_state.trusting_depth as usize + 1,
+                            branch.len()
+                        )
+                        .into());
+                    }
+                    self.check_branch(height, branch, now)?;
+                }
+            }
+            Misbehaviour::ConflictingTrustedState { header, .. } => {
+                self.check_branch(height, slice::from_ref(header), now)?;
+
+                let trusted = self.trusted_consensus_state(height).ok_or_else(|| {
+                    format!("the client has no trusted consensus state at {:?}", height)
+                })?;
+                if trusted.hash == BlockHeaderHash::from(header) {
+                    return Err(format!(
+                        "misbehaviour header is the trusted block header at {:?}",
+                        height
+                    )
+                    .into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that `branch` is a valid chain of block headers starting at
+    /// `height`, which extends the client's consensus state at the height
+    /// below.
+    fn check_branch(
+        &self,
+        height: BlockHeight,
+        branch: &[BlockHeader],
+        now: DateTime<Utc>,
+    ) -> Result<(), Error> {
+        let parent_height = height
+            .0
+            .checked_sub(1)
+            .map(BlockHeight)
+            .ok_or("misbehaviour can't be at the genesis height")?;
+        let parent = self.consensus_state(parent_height).ok_or_else(|| {
+            format!(
+                "the client has no consensus state at {:?}, below the misbehaviour",
+                parent_height
+            )
+        })?;
+
+        let mut verified: Vec<ConsensusState> = Vec::new();
+        for (block_header, height) in branch.iter().zip((height.0..=u32::MAX).map(BlockHeight)) {
+            let parent_hash = verified.last().map_or(parent.hash, |state| state.hash);
+            if block_header.previous_block_hash != parent_hash {
+                return Err(format!(
+                    "misbehaviour header {:?} at {:?} does not extend the previous header",
+                    BlockHeaderHash::from(block_header),
+                    height
+                )
+                .into());
+            }
+
+            // The ancestors are in reverse height order, starting with the
+            // parent
+            let context = verified
+                .iter()
+                .rev()
+                .chain(
+                    self.consensus_states
+                        .range(..=parent_height)
+                        .rev()
+                        .map(|(_height, consensus_state)| consensus_state),
+                )
+                .take(POW_ADJUSTMENT_BLOCK_SPAN)
+                .map(|consensus_state| (consensus_state.bits, consensus_state.time))
+                .collect();
+
+            check_block_header(
+                block_header,
+                self.client_state.network,
+                height,
+                context,
+                now,
+            )?;
+            verified.push(ConsensusState::from(block_header));
+        }
+
+        if verified.len() < branch.len() {
+            return Err("misbehaviour branch height is out of range".into());
+        }
+
+        Ok(())
+    }
+
+    /// Check `misbehaviour`, then freeze the client.
+    ///
+    /// Frozen clients reject all updates. Evidence can be submitted to a
+    /// client that is already frozen.
+    pub fn check_misbehaviour_and_freeze(
+        &mut self,
+        misbehaviour: &Misbehaviour,
+        now: DateTime<Utc>,
+    ) -> Result<(), Error> {
+        self.check_misbehaviour(misbehaviour, now)?;
+
+        tracing::warn!(height = ?misbehaviour.height(), "freezing client because of misbehaviour");
+        self.client_state.freeze(misbehaviour.height());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use zebra_chain::{block::Block, serialization::ZcashDeserialize};
+
+    fn genesis_and_block_1() -> (BlockHeader, BlockHeader) {
+        let genesis =
+            Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])
+                .expect("block test vector should deserialize");
+        let block_1 = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])
+            .expect("block test vector should deserialize");
+
+        (genesis.header, block_1.header)
+    }
+
+    #[test]
+    fn update_client() {
+        zebra_test::init();
+
+        let (genesis, block_1) = genesis_and_block_1();
+        let mut client = ZcashClient::new(Network::Mainnet, 1, BlockHeight(0), &[genesis])
+            .expect("the genesis header should be trusted");
+        assert_eq!(client.trusted_consensus_state(BlockHeight(0)), None);
+
+        assert_eq!(
+            client
+                .update_client(&[block_1], Utc::now())
+                .expect("block 1 should be valid"),
+            BlockHeight(1)
+        );
+        assert_eq!(
+            client.consensus_state(BlockHeight(1)),
+            Some(&ConsensusState::from(&block_1))
+        );
+        assert_eq!(
+            client.trusted_consensus_state(BlockHeight(0)),
+            Some(&ConsensusState::from(&genesis))
+        );
+        assert_eq!(client.trusted_consensus_state(BlockHeight(1)), None);
+
+        // Block 1 doesn't extend the new latest header
+        client
+            .update_client(&[block_1], Utc::now())
+            .expect_err("headers that don't extend the latest header should be rejected");
+    }
+
+    #[test]
+    fn update_client_rejects_invalid_headers() {
+        zebra_test::init();
+
+        let (genesis, block_1) = genesis_and_block_1();
+        let mut client = ZcashClient::new(Network::Mainnet, 1, BlockHeight(0), &[genesis])
+            .expect("the genesis header should be trusted");
+
+        let mut bad_solution = block_1;
+        bad_solution.nonce = [0; 32];
+        client
+            .update_client(&[bad_solution], Utc::now())
+            .expect_err("headers with invalid equihash solutions should be rejected");
+
+        // Block 1 is valid, but the batch is rejected as a whole
+        client
+            .update_client(&[block_1, bad_solution], Utc::now())
+            .expect_err("batches with invalid headers should be rejected");
+        assert_eq!(client.client_state().latest_height, BlockHeight(0));
+        assert_eq!(client.consensus_state(BlockHeight(1)), None);
+
+        // Block 1 is from the future, according to a clock that is 3 hours
+        // before genesis
+        client
+            .update_client(&[block_1], genesis.time - chrono::Duration::hours(3))
+            .expect_err("headers from the future should be rejected");
+
+        // Frozen clients reject valid headers
+        client.client_state.frozen_height = Some(BlockHeight(0));
+        client
+            .update_client(&[block_1], Utc::now())
+            .expect_err("frozen clients should reject updates");
+    }
+
+    #[test]
+    fn misbehaviour_freezes_client() {
+        zebra_test::init();
+
+        let (genesis, block_1) = genesis_and_block_1();
+        let mut client = ZcashClient::new(Network::Mainnet, 0, BlockHeight(1), &[genesis, block_1])
+            .expect("linked headers down to genesis should be trusted");
+
+        assert_eq!(
+            client
+                .detect_misbehaviour(&[block_1], Utc::now())
+                .expect("headers on the trusted chain should be checked"),
+            None
+        );
+
+        // Pretend that the client was tricked into trusting a different
+        // header at height 1
+        client
+            .consensus_states
+            .get_mut(&BlockHeight(1))
+            .unwrap()
+            .hash = BlockHeaderHash([0; 32]);
+
+        let misbehaviour = client
+            .detect_misbehaviour(&[block_1], Utc::now())
+            .expect("headers that extend a trusted header should be checked")
+            .expect("block 1 conflicts with the trusted header");
+        assert_eq!(
+            misbehaviour,
+            Misbehaviour::ConflictingTrustedState {
+                height: BlockHeight(1),
+                header: block_1,
+            }
+        );
+
+        client
+            .check_misbehaviour_and_freeze(&misbehaviour, Utc::now())
+            .expect("the misbehaviour should be valid");
+        assert_eq!(client.client_state().frozen_height, Some(BlockHeight(1)));
+        client
+            .update_client(&[], Utc::now())
+            .expect_err("frozen clients should reject updates");
+    }
+
+    #[test]
+    fn misbehaviour_heights_are_checked() {
+        zebra_test::init();
+
+        let (genesis, block_1) = genesis_and_block_1();
+        let mut client = ZcashClient::new(Network::Mainnet, 0, BlockHeight(0), &[genesis])
+            .expect("the genesis header should be trusted");
+
+        // Pretend that the parent of block 1 is at the maximum height
+        let consensus_state = client
+            .consensus_states
+            .remove(&BlockHeight(0))
+            .expect("the genesis consensus state should exist");
+        client
+            .consensus_states
+            .insert(BlockHeight(u32::MAX), consensus_state);
+
+        client
+            .detect_misbehaviour(&[block_1], Utc::now())
+            .expect_err("headers above the maximum height should be rejected");
+    }
+
+    #[test]
+    fn invalid_misbehaviour_is_rejected() {
+        zebra_test::init();
+
+        let (genesis, block_1) = genesis_and_block_1();
+        let mut client = ZcashClient::new(Network::Mainnet, 0, BlockHeight(1), &[genesis, block_1])
+            .expect("linked headers down to genesis should be trusted");
+
+        let trusted_header = Misbehaviour::ConflictingTrustedState {
+            height: BlockHeight(1),
+            header: block_1,
+        };
+        client
+            .check_misbehaviour_and_freeze(&trusted_header, Utc::now())
+            .expect_err("trusted headers aren't misbehaviour");
+
+        let mut bad_solution = block_1;
+        bad_solution.nonce = [0; 32];
+        let conflicting_headers =
+            Misbehaviour::conflicting_headers(BlockHeight(1), vec![block_1], vec![bad_solution])
+                .expect("the headers have different hashes and the same parent");
+        client
+            .check_misbehaviour_and_freeze(&conflicting_headers, Utc::now())
+            .expect_err("misbehaviour headers must be valid");
+
+        let wrong_height = Misbehaviour::ConflictingTrustedState {
+            height: BlockHeight(2),
+            header: block_1,
+        };
+        client
+            .check_misbehaviour_and_freeze(&wrong_height, Utc::now())
+            .expect_err("misbehaviour headers must extend the height below");
+
+        assert!(!client.client_state().is_frozen());
+
+        client
+            .detect_misbehaviour(&[genesis], Utc::now())
+            .expect_err("headers must extend a verified header");
+    }
+
+    #[test]
+    fn shallow_conflicting_branches_are_rejected() {
+        zebra_test::init();
+
+        let (genesis, block_1) = genesis_and_block_1();
+        let mut client = ZcashClient::new(Network::Mainnet, 1, BlockHeight(1), &[genesis, block_1])
+            .expect("linked headers down to genesis should be trusted");
+
+        // A natural fork at height 1, which is shallower than the trusting
+        // depth
+        let mut orphan = block_1;
+        orphan.nonce = [0; 32];
+        let natural_fork =
+            Misbehaviour::conflicting_headers(BlockHeight(1), vec![block_1], vec![orphan])
+                .expect("the headers have different hashes and the same parent");
+
+        let error = client
+            .check_misbehaviour_and_freeze(&natural_fork, Utc::now())
+            .expect_err("natural forks aren't misbehaviour");
+        assert!(error.to_string().contains("at least"));
+        assert!(!client.client_state().is_frozen());
+    }
+
+    #[test]
+    fn new_client_checks_trusted_headers() {
+        zebra_test::init();
+
+        let (genesis, block_1) = genesis_and_block_1();
+
+        ZcashClient::new(Network::Mainnet, 1, BlockHeight(1), &[genesis, block_1])
+            .expect("linked headers down to genesis should be trusted");
+        ZcashClient::new(Network::Mainnet, 1, BlockHeight(1), &[block_1, genesis])
+            .expect_err("unlinked headers should be rejected");
+        ZcashClient::new(Network::Mainnet, 1, BlockHeight(1), &[block_1])
+            .expect_err("clients need enough headers to check difficulty adjustments");
+        ZcashClient::new(Network::Mainnet, 1, BlockHeight(0), &[genesis, block_1])
+            .expect_err("trusted headers can't start below genesis");
+        ZcashClient::new(
+            Network::Mainnet,
+            1,
+            BlockHeight(u32::MAX),
+            &[genesis; POW_ADJUSTMENT_BLOCK_SPAN],
+        )
+        .expect_err("unlinked headers at the maximum height should be rejected");
+    }
+
+    #[test]
+    fn old_consensus_states_are_pruned() {
+        zebra_test::init();
+
+        let (genesis, _block_1) = genesis_and_block_1();
+        let kept_depth = POW_ADJUSTMENT_BLOCK_SPAN as u32 + 10;
+        let mut client = ZcashClient::new(Network::Mainnet, kept_depth, BlockHeight(0), &[genesis])
+            .expect("the genesis header should be trusted");
+
+        // Pretend that the client has verified 100 block headers
+        for height in 1..=100 {
+            client
+                .consensus_states
+                .insert(BlockHeight(height), ConsensusState::from(&genesis));
+        }
+        client.client_state.latest_height = BlockHeight(100);
+        client.prune();
+
+        assert_eq!(
+            client.consensus_states.keys().next(),
+            Some(&BlockHeight(100 - kept_depth))
+        );
+        assert!(client
+            .trusted_consensus_state(BlockHeight(100 - kept_depth))
+            .is_some());
+        assert_eq!(client.consensus_state(BlockHeight(99 - kept_depth)), None);
+
+        // Difficulty adjustments need more consensus states than a small
+        // trusting depth
+        client.client_state.trusting_depth = 1;
+        client.prune();
+        assert_eq!(client.consensus_states.len(), POW_ADJUSTMENT_BLOCK_SPAN);
+    }
+}
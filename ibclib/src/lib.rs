@@ -2,5 +2,8 @@
 
 #![allow(dead_code)]
 
+pub mod bundle;
+pub mod client;
+pub mod misbehaviour;
 pub mod prelude;
 pub mod zcash;
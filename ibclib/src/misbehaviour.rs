@@ -0,0 +1,443 @@
+//! Misbehaviour evidence for a Zcash light client.
+//!
+//! A light client only follows the chain that relayers show it, so an
+//! attacker can try to make it accept a conflicting chain. Evidence of
+//! conflicting headers freezes the client, using
+//! `ZcashClient::check_misbehaviour_and_freeze`.
+//!
+//! Short forks happen naturally on Zcash, so two headers at the same height
+//! aren't evidence on their own. Evidence must conflict with a trusted
+//! consensus state, or contain two branches that are each deep enough to be
+//! trusted. The syncer finds those branches using `ReorgTracker`.
+//!
+//! Evidence has a canonical serialization, so it can be submitted to other
+//! chains, and compared byte-for-byte:
+//!   * a tag byte: 1 for `ConflictingHeaders`, 2 for `ConflictingTrustedState`
+//!   * the height, as a little-endian `u32`
+//!   * for `ConflictingHeaders`, each branch, as a `CompactSize` header count,
+//!     followed by the headers in their zcash serialization
+//!   * for `ConflictingTrustedState`, the header, in its zcash serialization
+
+use std::{io, mem};
+
+use zebra_chain::{
+    block::{BlockHeader, BlockHeaderHash},
+    serialization::{SerializationError, ZcashDeserialize, ZcashSerialize},
+    types::BlockHeight,
+};
+use zebra_state::ChainEvent;
+
+/// The serialization tag for `Misbehaviour::ConflictingHeaders`.
+const CONFLICTING_HEADERS_TAG: u8 = 1;
+
+/// The serialization tag for `Misbehaviour::ConflictingTrustedState`.
+const CONFLICTING_TRUSTED_STATE_TAG: u8 = 2;
+
+/// Evidence that a light client was shown conflicting block headers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Misbehaviour {
+    /// Two valid branches of block headers, which start at the same height
+    /// with different hashes.
+    ///
+    /// Both branches extend the same parent block header, and each branch
+    /// has at least `trusting_depth` headers above its first header. Branches
+    /// are ordered by the hash of their first header, so each pair of
+    /// branches has a single canonical encoding.
+    ConflictingHeaders {
+        /// The height of the first header in each branch
+        height: BlockHeight,
+        /// The branch with the lower first header hash, in ascending height
+        /// order
+        branch_1: Vec<BlockHeader>,
+        /// The branch with the higher first header hash, in ascending height
+        /// order
+        branch_2: Vec<BlockHeader>,
+    },
+    /// A valid block header with a different hash to the client's trusted
+    /// consensus state at the same height.
+    ConflictingTrustedState {
+        /// The height of the block header
+        height: BlockHeight,
+        /// The conflicting block header
+        header: BlockHeader,
+    },
+}
+
+impl Misbehaviour {
+    /// Returns evidence that `branch_1` and `branch_2` conflict at `height`,
+    /// or `None` if either branch is empty, or their first headers have the
+    /// same hash, or different parents.
+    ///
+    /// Used when the syncer reorganises the best chain, or a relayer receives
+    /// two chains of block headers. The branches still need to be checked
+    /// against a client.
+    pub fn conflicting_headers(
+        height: BlockHeight,
+        branch_1: Vec<BlockHeader>,
+        branch_2: Vec<BlockHeader>,
+    ) -> Option<Self> {
+        let first_1 = branch_1.first()?;
+        let first_2 = branch_2.first()?;
+        let hash_1 = BlockHeaderHash::from(first_1);
+        let hash_2 = BlockHeaderHash::from(first_2);
+
+        if hash_1 == hash_2 || first_1.previous_block_hash != first_2.previous_block_hash {
+            return None;
+        }
+
+        let (branch_1, branch_2) = if hash_1.0 < hash_2.0 {
+            (branch_1, branch_2)
+        } else {
+            (branch_2, branch_1)
+        };
+
+        Some(Misbehaviour::ConflictingHeaders {
+            height,
+            branch_1,
+            branch_2,
+        })
+    }
+
+    /// Returns the block headers in this evidence.
+    pub fn headers(&self) -> Vec<BlockHeader> {
+        match self {
+            Misbehaviour::ConflictingHeaders {
+                branch_1, branch_2, ..
+            } => branch_1.iter().chain(branch_2).copied().collect(),
+            Misbehaviour::ConflictingTrustedState { header, .. } => vec![*header],
+        }
+    }
+
+    /// Returns the height of the conflict.
+    pub fn height(&self) -> BlockHeight {
+        match self {
+            Misbehaviour::ConflictingHeaders { height, .. } => *height,
+            Misbehaviour::ConflictingTrustedState { height, .. } => *height,
+        }
+    }
+}
+
+/// A reorganisation that replaced a branch of the best chain with another
+/// branch, where both branches were deep enough to be trusted.
+///
+/// Returned by `ReorgTracker`. The syncer turns it into
+/// `Misbehaviour::ConflictingHeaders` evidence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeepReorg {
+    /// The height of the first header in each branch
+    pub height: BlockHeight,
+    /// The hashes of the first `trusting_depth + 1` disconnected headers, in
+    /// ascending height order
+    pub disconnected: Vec<BlockHeaderHash>,
+    /// The hashes of the first `trusting_depth + 1` connected headers, in
+    /// ascending height order
+    pub connected: Vec<BlockHeaderHash>,
+}
+
+/// Finds deep reorganisations in the chain events of a block header state.
+///
+/// Short reorganisations are natural forks, so they are ignored. A
+/// reorganisation is deep if both branches have at least `trusting_depth`
+/// headers above their first header.
+#[derive(Clone, Debug)]
+pub struct ReorgTracker {
+    trusting_depth: u32,
+    disconnected: Vec<(BlockHeight, BlockHeaderHash)>,
+    connected: Vec<(BlockHeight, BlockHeaderHash)>,
+}
+
+impl ReorgTracker {
+    /// Create a tracker for clients with `trusting_depth`.
+    pub fn new(trusting_depth: u32) -> Self {
+        Self {
+            trusting_depth,
+            disconnected: Vec::new(),
+            connected: Vec::new(),
+        }
+    }
+
+    /// Track `event`, and return the deep reorganisation that it completes,
+    /// if any.
+    ///
+    /// Events must be tracked in the order that the state published them.
+    pub fn track(&mut self, event: &ChainEvent) -> Option<DeepReorg> {
+        match *event {
+            ChainEvent::Disconnected { hash, height } => {
+                self.disconnected.push((height, hash));
+                None
+            }
+            ChainEvent::Connected { hash, height } => {
+                self.connected.push((height, hash));
+                None
+            }
+            ChainEvent::NewTip { .. } => {
+                // Disconnected headers are published in descending height
+                // order
+                let mut disconnected = mem::replace(&mut self.disconnected, Vec::new());
+                let connected = mem::replace(&mut self.connected, Vec::new());
+                disconnected.reverse();
+
+                let height = disconnected.first()?.0;
+                let connected: Vec<_> = connected
+                    .into_iter()
+                    .filter(|(connected_height, _hash)| *connected_height >= height)
+                    .collect();
+
+                let min_len = (self.trusting_depth as usize).saturating_add(1);
+                if disconnected.len() < min_len || connected.len() < min_len {
+                    return None;
+                }
+
+                // Longer branches aren't better evidence
+                Some(DeepReorg {
+                    height,
+                    disconnected: disconnected
+                        .into_iter()
+                        .take(min_len)
+                        .map(|(_height, hash)| hash)
+                        .collect(),
+                    connected: connected
+                        .into_iter()
+                        .take(min_len)
+                        .map(|(_height, hash)| hash)
+                        .collect(),
+                })
+            }
+        }
+    }
+}
+
+impl ZcashSerialize for Misbehaviour {
+    fn zcash_serialize<W: io::Write>(&self, mut writer: W) -> Result<(), io::Error> {
+        match self {
+            Misbehaviour::ConflictingHeaders {
+                height,
+                branch_1,
+                branch_2,
+            } => {
+                writer.write_all(&[CONFLICTING_HEADERS_TAG])?;
+                writer.write_all(&height.0.to_le_bytes())?;
+                branch_1.zcash_serialize(&mut writer)?;
+                branch_2.zcash_serialize(&mut writer)?;
+            }
+            Misbehaviour::ConflictingTrustedState { height, header } => {
+                writer.write_all(&[CONFLICTING_TRUSTED_STATE_TAG])?;
+                writer.write_all(&height.0.to_le_bytes())?;
+                header.zcash_serialize(&mut writer)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ZcashDeserialize for Misbehaviour {
+    fn zcash_deserialize<R: io::Read>(mut reader: R) -> Result<Self, SerializationError> {
+        let mut tag = [0u8; 1];
+        reader.read_exact(&mut tag)?;
+
+        let mut height = [0u8; 4];
+        reader.read_exact(&mut height)?;
+        let height = BlockHeight(u32::from_le_bytes(height));
+
+        match tag[0] {
+            CONFLICTING_HEADERS_TAG => {
+                let branch_1: Vec<BlockHeader> = Vec::zcash_deserialize(&mut reader)?;
+                let branch_2: Vec<BlockHeader> = Vec::zcash_deserialize(&mut reader)?;
+                let first_header = branch_1.first().copied();
+
+                // Reject non-canonical encodings
+                match Misbehaviour::conflicting_headers(height, branch_1, branch_2) {
+                    Some(misbehaviour)
+                        if misbehaviour.headers().first().copied() == first_header =>
+                    {
+                        Ok(misbehaviour)
+                    }
+                    _ => Err(SerializationError::Parse(
+                        "conflicting branches must have the same parent, and be in hash order",
+                    )),
+                }
+            }
+            CONFLICTING_TRUSTED_STATE_TAG => Ok(Misbehaviour::ConflictingTrustedState {
+                height,
+                header: BlockHeader::zcash_deserialize(&mut reader)?,
+            }),
+            _ => Err(SerializationError::Parse("unknown misbehaviour tag")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use zebra_chain::{block::Block, serialization::ZcashDeserializeInto};
+
+    fn block_1_and_conflict() -> (BlockHeader, BlockHeader) {
+        let block_1 = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])
+            .expect("block test vector should deserialize");
+        let mut conflict = block_1.header;
+        conflict.nonce = [0; 32];
+
+        (block_1.header, conflict)
+    }
+
+    #[test]
+    fn conflicting_headers_are_canonical() {
+        zebra_test::init();
+
+        let (block_1, conflict) = block_1_and_conflict();
+        let misbehaviour =
+            Misbehaviour::conflicting_headers(BlockHeight(1), vec![block_1], vec![conflict])
+                .expect("the headers have different hashes and the same parent");
+        assert_eq!(
+            Misbehaviour::conflicting_headers(BlockHeight(1), vec![conflict], vec![block_1]),
+            Some(misbehaviour.clone())
+        );
+        assert_eq!(
+            Misbehaviour::conflicting_headers(BlockHeight(1), vec![block_1], vec![block_1]),
+            None
+        );
+        assert_eq!(
+            Misbehaviour::conflicting_headers(BlockHeight(1), vec![block_1], Vec::new()),
+            None
+        );
+
+        let bytes = misbehaviour.zcash_serialize_to_vec().unwrap();
+        let round_trip: Misbehaviour = bytes
+            .as_slice()
+            .zcash_deserialize_into()
+            .expect("serialized misbehaviour should deserialize");
+        assert_eq!(round_trip, misbehaviour);
+
+        // Swap the branches, making the encoding non-canonical
+        let headers = misbehaviour.headers();
+        let swapped = Misbehaviour::ConflictingHeaders {
+            height: BlockHeight(1),
+            branch_1: vec![headers[1]],
+            branch_2: vec![headers[0]],
+        };
+        swapped
+            .zcash_serialize_to_vec()
+            .unwrap()
+            .as_slice()
+            .zcash_deserialize_into::<Misbehaviour>()
+            .expect_err("non-canonical misbehaviour should be rejected");
+    }
+
+    #[test]
+    fn conflicting_trusted_state_round_trip() {
+        zebra_test::init();
+
+        let (block_1, _conflict) = block_1_and_conflict();
+        let misbehaviour = Misbehaviour::ConflictingTrustedState {
+            height: BlockHeight(1),
+            header: block_1,
+        };
+
+        let bytes = misbehaviour.zcash_serialize_to_vec().unwrap();
+        assert_eq!(bytes[0], CONFLICTING_TRUSTED_STATE_TAG);
+        assert_eq!(bytes[1..5], 1u32.to_le_bytes());
+
+        let round_trip: Misbehaviour = bytes
+            .as_slice()
+            .zcash_deserialize_into()
+            .expect("serialized misbehaviour should deserialize");
+        assert_eq!(round_trip, misbehaviour);
+    }
+
+    fn hash(byte: u8) -> BlockHeaderHash {
+        BlockHeaderHash([byte; 32])
+    }
+
+    #[test]
+    fn reorg_tracker_ignores_natural_forks() {
+        zebra_test::init();
+
+        let mut tracker = ReorgTracker::new(1);
+        let events = [
+            ChainEvent::Connected {
+                hash: hash(1),
+                height: BlockHeight(1),
+            },
+            ChainEvent::NewTip {
+                hash: hash(1),
+                height: BlockHeight(1),
+            },
+            // A one block fork at height 1
+            ChainEvent::Disconnected {
+                hash: hash(1),
+                height: BlockHeight(1),
+            },
+            ChainEvent::Connected {
+                hash: hash(11),
+                height: BlockHeight(1),
+            },
+            ChainEvent::Connected {
+                hash: hash(12),
+                height: BlockHeight(2),
+            },
+            ChainEvent::NewTip {
+                hash: hash(12),
+                height: BlockHeight(2),
+            },
+        ];
+
+        for event in events.iter() {
+            assert_eq!(tracker.track(event), None);
+        }
+    }
+
+    #[test]
+    fn reorg_tracker_finds_deep_reorgs() {
+        zebra_test::init();
+
+        let mut tracker = ReorgTracker::new(1);
+        let events = [
+            ChainEvent::Disconnected {
+                hash: hash(2),
+                height: BlockHeight(2),
+            },
+            ChainEvent::Disconnected {
+                hash: hash(1),
+                height: BlockHeight(1),
+            },
+            ChainEvent::Connected {
+                hash: hash(11),
+                height: BlockHeight(1),
+            },
+            ChainEvent::Connected {
+                hash: hash(12),
+                height: BlockHeight(2),
+            },
+            ChainEvent::Connected {
+                hash: hash(13),
+                height: BlockHeight(3),
+            },
+        ];
+        for event in events.iter() {
+            assert_eq!(tracker.track(event), None);
+        }
+
+        assert_eq!(
+            tracker.track(&ChainEvent::NewTip {
+                hash: hash(13),
+                height: BlockHeight(3),
+            }),
+            Some(DeepReorg {
+                height: BlockHeight(1),
+                disconnected: vec![hash(1), hash(2)],
+                connected: vec![hash(11), hash(12)],
+            })
+        );
+
+        // The tracker starts again after each new tip
+        assert_eq!(
+            tracker.track(&ChainEvent::NewTip {
+                hash: hash(13),
+                height: BlockHeight(3),
+            }),
+            None
+        );
+    }
+}
@@ -8,12 +8,22 @@ use std::{
 
 type Error = Box<dyn error::Error + Send + Sync + 'static>;
 
+/// The default number of blocks that must be above a block header, before
+/// `IBCRequest::get_finalized` returns it.
+///
+/// Headers that are shallower than this depth can still be reorganised out of
+/// the best chain. Configured by `finality_depth` in the `[ibc]` section of
+/// `zebrad.toml`.
+pub const DEFAULT_FINALITY_DEPTH: u32 = 24;
+
 #[derive(Clone, Copy)]
 pub struct IBCStorage<S>
 where
     Self: Send + Sync + 'static,
 {
     pub state: S,
+    /// The minimum depth of the headers returned by `get_finalized`.
+    pub finality_depth: u32,
 }
 
 #[derive(Clone, Copy)]
@@ -29,8 +39,18 @@ pub trait IBCRequest<Hash, Height> {
 //  type HeightResponse;
     type HeaderHeightResponse;
     type HashHeightResponse;
+    type HeadersHeightsResponse;
     fn get(&self, query: impl Into<IBCQuery<Hash, Height>>) -> Self::HeaderHeightResponse;
     fn get_tip(&self) -> Self::HashHeightResponse;
+    /// Like `get`, but returns `None` for headers that are missing, on a side
+    /// chain, or have fewer than `finality_depth` blocks above them in the
+    /// best chain.
+    fn get_finalized(&self, query: impl Into<IBCQuery<Hash, Height>>) -> Self::HeaderHeightResponse;
+    /// Get the hash and height of the best chain header that has `depth`
+    /// blocks above it, or `None` if the best chain is shorter than `depth`.
+    fn get_finalized_tip(&self, depth: u32) -> Self::HashHeightResponse;
+    fn get_range(&self, start: Height, end: Height) -> Self::HeadersHeightsResponse;
+    fn get_batch(&self, queries: Vec<IBCQuery<Hash, Height>>) -> Self::HeadersHeightsResponse;
 }
 
 /*
@@ -4,11 +4,15 @@ use zebra_chain::{
         BlockHeader,
         BlockHeaderHash,
     },
+    merkle_tree::{MerkleProof, MerkleTree},
+    transaction::TransactionHash,
     types::BlockHeight,
+    Network,
 };
 use zebra_state::{
+    ChainEventStream,
     QueryType,
-//  RequestBlock,
+    RequestBlock,
     RequestBlockHeader,
     Response,
 };
@@ -21,7 +25,11 @@ use std::{
 use futures_util::FutureExt;
 use tower::{Service, ServiceExt};
 
-use crate::prelude::*;
+use crate::{
+    bundle::HeaderBundle,
+    misbehaviour::{DeepReorg, Misbehaviour},
+    prelude::*,
+};
 
 type Error = Box<dyn error::Error + Send + Sync + 'static>;
 
@@ -66,17 +74,134 @@ impl From<BlockHeight> for IBCQuery<BlockHeaderHash, BlockHeight> {
 
 impl<S> IBCStorage<S>
 where
-    S: Service<RequestBlockHeader, Response = Response, Error = Error> + Send + Clone + 'static,
-    S::Future: Send,
     Self: Send + Sync + 'static,
 {
     pub fn new(state: S) -> Self {
         Self {
             state,
+            finality_depth: DEFAULT_FINALITY_DEPTH,
+        }
+    }
+
+    /// Use `finality_depth` as the minimum depth of the headers returned by
+    /// `get_finalized`.
+    pub fn with_finality_depth(self, finality_depth: u32) -> Self {
+        Self {
+            finality_depth,
+            ..self
         }
     }
 }
 
+impl<S> IBCStorage<S>
+where
+    S: Service<RequestBlockHeader, Response = Response, Error = Error> + Send + Clone + 'static,
+    S::Future: Send,
+    Self: Send + Sync + 'static,
+{
+    /// Subscribe to the best chain changes in the state.
+    ///
+    /// The returned `Stream` yields the new tip, and the block headers that
+    /// are connected or disconnected, for every change after this call.
+    pub fn subscribe(&self) -> Pin<Box<dyn Future<Output = Result<ChainEventStream, Error>> + Send + 'static>> {
+        let mut state = self.state.clone();
+        async move {
+            let subscribe = state
+            .ready_and()
+            .await?
+            .call(RequestBlockHeader::Subscribe);
+
+            tracing::info!("Chain events subscription requested!");
+
+            match subscribe.await? {
+                Response::Subscription { chain_events } => Ok(chain_events.subscribe()),
+                _ => Err("Some error in subscribing to chain events".into()),
+            }
+        }.boxed()
+    }
+
+    /// Export the best chain block headers from `start` to `end` as a
+    /// portable bundle, using the header at `checkpoint` as its trusted
+    /// checkpoint.
+    pub fn get_bundle(&self, network: Network, start: BlockHeight, end: BlockHeight, checkpoint: BlockHeight) -> Pin<Box<dyn Future<Output = Result<HeaderBundle, Error>> + Send + 'static>> {
+        let get_range = self.get_range(start, end);
+        async move {
+            let headers = get_range.await?;
+
+            tracing::info!("Bundle of block headers from height {:?} to {:?} requested!", start, end);
+
+            let expected_len = end.0.checked_sub(start.0).and_then(|offset| offset.checked_add(1)).ok_or("the bundle range is invalid")?;
+            if headers.len() as u32 != expected_len {
+                return Err(format!("the best chain does not contain every block header from {:?} to {:?}", start, end).into());
+            }
+            let checkpoint = headers
+                .iter()
+                .find(|(_block_header, height)| *height == checkpoint)
+                .map(|(block_header, _height)| BlockHeaderHash::from(block_header.as_ref()))
+                .ok_or("the checkpoint height is not in the bundle range")?;
+
+            HeaderBundle::new(network, start, checkpoint, headers.into_iter().map(|(block_header, _height)| *block_header).collect())
+        }.boxed()
+    }
+
+    /// Get the block headers in `reorg`, and return them as misbehaviour
+    /// evidence.
+    ///
+    /// Fails if the state no longer has every header in both branches.
+    pub fn reorg_misbehaviour(&self, reorg: DeepReorg) -> Pin<Box<dyn Future<Output = Result<Misbehaviour, Error>> + Send + 'static>> {
+        let get_disconnected = self.get_batch(reorg.disconnected.iter().copied().map(IBCQuery::ByHash).collect());
+        let get_connected = self.get_batch(reorg.connected.iter().copied().map(IBCQuery::ByHash).collect());
+        async move {
+            let disconnected = get_disconnected.await?;
+            let connected = get_connected.await?;
+
+            tracing::info!("Misbehaviour evidence for a reorganisation at height {:?} requested!", reorg.height);
+
+            if disconnected.len() != reorg.disconnected.len() || connected.len() != reorg.connected.len() {
+                return Err("the state does not contain every block header in the reorganisation".into());
+            }
+
+            Misbehaviour::conflicting_headers(
+                reorg.height,
+                disconnected.into_iter().map(|(block_header, _height)| *block_header).collect(),
+                connected.into_iter().map(|(block_header, _height)| *block_header).collect(),
+            )
+            .ok_or_else(|| "the reorganised branches do not conflict".into())
+        }.boxed()
+    }
+}
+
+impl<S> IBCStorage<S>
+where
+    S: Service<RequestBlock, Response = Response, Error = Error> + Send + Clone + 'static,
+    S::Future: Send,
+    Self: Send + Sync + 'static,
+{
+    /// Get a proof that the transaction `txid` is in the best chain block at
+    /// `height`.
+    ///
+    /// The proof can be checked against the `merkle_root_hash` of the block
+    /// header at `height`. Returns `None` if the transaction is not in that
+    /// block. Block header states don't store transactions, so this call
+    /// needs a full block state.
+    pub fn get_merkle_proof(&self, txid: TransactionHash, height: BlockHeight) -> Pin<Box<dyn Future<Output = Result<Option<MerkleProof>, Error>> + Send + 'static>> {
+        let mut state = self.state.clone();
+        async move {
+            let get_block = state
+            .ready_and()
+            .await?
+            .call(RequestBlock::GetBlock { query: QueryType::ByHeight(height) });
+
+            tracing::info!("Merkle proof for transaction {:?} at height {:?} requested!", txid, height);
+
+            match get_block.await? {
+                Response::Block { block } => Ok(MerkleTree::from(block.as_ref()).prove(txid)),
+                _ => Err("block couldn't be found - either still syncing, or out of range".into()),
+            }
+        }.boxed()
+    }
+}
+
 impl<S> IBCRequest<BlockHeaderHash, BlockHeight> for IBCStorage<S>
 where
     S: Service<RequestBlockHeader, Response = Response, Error = Error> + Send + Clone + 'static,
@@ -89,6 +214,7 @@ where
 //  type HeightResponse = Pin<Box<dyn Future<Output = Result<Option<BlockHeight>, Error>> + Send + 'static>>;
     type HeaderHeightResponse = Pin<Box<dyn Future<Output = Result<Option<(Arc<BlockHeader>, BlockHeight)>, Error>> + Send + 'static>>;
     type HashHeightResponse = Pin<Box<dyn Future<Output = Result<Option<(BlockHeaderHash, BlockHeight)>, Error>> + Send + 'static>>;
+    type HeadersHeightsResponse = Pin<Box<dyn Future<Output = Result<Vec<(Arc<BlockHeader>, BlockHeight)>, Error>> + Send + 'static>>;
 
     fn get(&self, query: impl Into<IBCQuery<BlockHeaderHash, BlockHeight>>) -> Self::HeaderHeightResponse {
         let value = match query.into() {
@@ -144,4 +270,98 @@ where
             }
         }.boxed()
     }
+
+    fn get_finalized(&self, query: impl Into<IBCQuery<BlockHeaderHash, BlockHeight>>) -> Self::HeaderHeightResponse {
+        let query = match query.into() {
+            IBCQuery::ByHash(hash) => QueryType::ByHash(hash),
+            IBCQuery::ByHeight(height) => QueryType::ByHeight(height),
+        };
+        let mut state = self.state.clone();
+        let finality_depth = self.finality_depth;
+        async move {
+            // The state looks up the header and its depth together, so the
+            // best chain can't change between the two lookups
+            let get_block_header = state
+            .ready_and()
+            .await?
+            .call(RequestBlockHeader::GetBlockHeaderWithDepth { query: query.clone() });
+
+            tracing::info!("Finalized block header {:?} requested!", query);
+
+            // Missing, side chain, and shallow headers aren't finalized yet
+            match get_block_header.await? {
+                Response::BlockHeaderWithDepth(Some((block_header, block_height, Some(depth)))) if depth >= finality_depth => Ok(Some((block_header, block_height))),
+                Response::BlockHeaderWithDepth(_) => Ok(None),
+                _ => Err("Some error in requesting a finalized block header".into()),
+            }
+        }.boxed()
+    }
+
+    fn get_finalized_tip(&self, depth: u32) -> Self::HashHeightResponse {
+        let get_tip = self.get_tip();
+        let mut state = self.state.clone();
+        async move {
+            let tip_height = match get_tip.await? {
+                Some((_tip_hash, tip_height)) => tip_height,
+                None => return Ok(None),
+            };
+            let height = match tip_height.0.checked_sub(depth) {
+                Some(height) => BlockHeight(height),
+                None => return Ok(None),
+            };
+
+            let get_block_header = state
+            .ready_and()
+            .await?
+            .call(RequestBlockHeader::GetBlockHeader { query: QueryType::ByHeight(height) });
+
+            tracing::info!("Block header at depth {:?} below the tip requested!", depth);
+
+            match get_block_header.await? {
+                Response::BlockHeader { block_header, block_height } => Ok(Some((block_header.as_ref().into(), block_height))),
+                _ => Err("Some error in requesting the finalized tip of the current chain".into()),
+            }
+        }.boxed()
+    }
+
+    fn get_range(&self, start: BlockHeight, end: BlockHeight) -> Self::HeadersHeightsResponse {
+        let mut state = self.state.clone();
+        async move {
+            let get_range = state
+            .ready_and()
+            .await?
+            .call(RequestBlockHeader::GetBlockHeaderRange { start, end });
+
+            tracing::info!("Block headers from height {:?} to {:?} requested!", start, end);
+
+            match get_range.await? {
+                Response::BlockHeadersWithHeights { block_headers } => Ok(block_headers),
+                _ => Err("Some error in requesting a range of block headers".into()),
+            }
+        }.boxed()
+    }
+
+    fn get_batch(&self, queries: Vec<IBCQuery<BlockHeaderHash, BlockHeight>>) -> Self::HeadersHeightsResponse {
+        let mut state = self.state.clone();
+        let queries: Vec<QueryType> = queries
+            .into_iter()
+            .map(|query| match query {
+                IBCQuery::ByHash(hash) => QueryType::ByHash(hash),
+                IBCQuery::ByHeight(height) => QueryType::ByHeight(height),
+            })
+            .collect();
+        async move {
+            tracing::info!("Batch of {:?} block headers requested!", queries.len());
+
+            let get_batch = state
+            .ready_and()
+            .await?
+            .call(RequestBlockHeader::GetBlockHeaders(queries));
+
+            match get_batch.await? {
+                Response::BlockHeadersWithHeights { block_headers } => Ok(block_headers),
+                _ => Err("Some error in requesting a batch of block headers".into()),
+            }
+        }.boxed()
+    }
 }
@@ -0,0 +1,195 @@
+//! Tests for `IBCRequest::get_finalized` and `IBCRequest::get_finalized_tip`,
+//! using an in-memory block header state.
+
+use color_eyre::eyre::{eyre, Report};
+use std::sync::Arc;
+use tower::{Service, ServiceExt};
+use zebra_chain::{
+    block::{Block, BlockHeader, BlockHeaderHash},
+    serialization::ZcashDeserialize,
+    types::BlockHeight,
+};
+
+use ibclib::prelude::{IBCQuery, IBCRequest, IBCStorage};
+use zebra_state::{in_memory_headersonly, RequestBlockHeader};
+
+/// The height of the best tip in the test states.
+const TIP_HEIGHT: u32 = 5;
+
+type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Adds a best chain from the mainnet genesis block to `TIP_HEIGHT` to
+/// `state`, and a side chain header at height 1. Returns the best chain
+/// headers, and the side chain header.
+///
+/// The state doesn't check proof of work, so the headers above block 1 are
+/// copies of block 1, linked to their parents.
+async fn add_headers<S>(state: &mut S) -> Result<(Vec<BlockHeader>, BlockHeader), Report>
+where
+    S: Service<RequestBlockHeader, Response = zebra_state::Response, Error = Error>,
+{
+    let genesis = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?;
+    let block_1 = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])?;
+
+    let mut best_chain = vec![genesis.header, block_1.header];
+    while best_chain.len() <= TIP_HEIGHT as usize {
+        let parent = best_chain.last().expect("the chain is not empty");
+        let mut block_header = block_1.header;
+        block_header.previous_block_hash = BlockHeaderHash::from(parent);
+        best_chain.push(block_header);
+    }
+
+    // Ties go to the first chain, so this header stays on a side chain
+    let mut side_chain = block_1.header;
+    side_chain.nonce = [0; 32];
+
+    for block_header in best_chain.iter().chain(Some(&side_chain)) {
+        state
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(RequestBlockHeader::AddBlockHeader {
+                block_header: Arc::new(*block_header),
+            })
+            .await
+            .map_err(|e| eyre!(e))?;
+    }
+
+    Ok((best_chain, side_chain))
+}
+
+#[tokio::test]
+async fn get_finalized() -> Result<(), Report> {
+    zebra_test::init();
+
+    let mut state = in_memory_headersonly::init();
+    let (best_chain, side_chain) = add_headers(&mut state).await?;
+    let storage = IBCStorage::new(state).with_finality_depth(3);
+
+    // Exactly `finality_depth` blocks above the header
+    let (block_header, height) = storage
+        .get_finalized(BlockHeight(TIP_HEIGHT - 3))
+        .await
+        .map_err(|e| eyre!(e))?
+        .ok_or_else(|| eyre!("a header at the finality depth should be finalized"))?;
+    assert_eq!(*block_header, best_chain[(TIP_HEIGHT - 3) as usize]);
+    assert_eq!(height, BlockHeight(TIP_HEIGHT - 3));
+
+    let hash = BlockHeaderHash::from(&best_chain[(TIP_HEIGHT - 3) as usize]);
+    let (_block_header, height) = storage
+        .get_finalized(hash)
+        .await
+        .map_err(|e| eyre!(e))?
+        .ok_or_else(|| eyre!("a header at the finality depth should be finalized"))?;
+    assert_eq!(height, BlockHeight(TIP_HEIGHT - 3));
+
+    // One block less than `finality_depth`
+    assert_eq!(
+        storage
+            .get_finalized(BlockHeight(TIP_HEIGHT - 2))
+            .await
+            .map_err(|e| eyre!(e))?,
+        None,
+        "a header above the finality depth should not be finalized"
+    );
+    assert_eq!(
+        storage
+            .get_finalized(BlockHeaderHash::from(
+                &best_chain[(TIP_HEIGHT - 2) as usize],
+            ))
+            .await
+            .map_err(|e| eyre!(e))?,
+        None,
+        "a header above the finality depth should not be finalized"
+    );
+
+    // Side chain headers are deep enough, but aren't in the best chain
+    assert_eq!(
+        storage
+            .get_finalized(IBCQuery::ByHash(BlockHeaderHash::from(&side_chain)))
+            .await
+            .map_err(|e| eyre!(e))?,
+        None,
+        "side chain headers should not be finalized"
+    );
+    assert_eq!(
+        storage
+            .get_finalized(BlockHeaderHash([0; 32]))
+            .await
+            .map_err(|e| eyre!(e))?,
+        None,
+        "missing headers should not be finalized"
+    );
+    assert_eq!(
+        storage
+            .get_finalized(BlockHeight(TIP_HEIGHT + 1))
+            .await
+            .map_err(|e| eyre!(e))?,
+        None,
+        "heights above the tip should not be finalized"
+    );
+
+    // Every best chain header is final at depth 0, including the tip
+    let storage = storage.with_finality_depth(0);
+    let (block_header, height) = storage
+        .get_finalized(BlockHeight(TIP_HEIGHT))
+        .await
+        .map_err(|e| eyre!(e))?
+        .ok_or_else(|| eyre!("the tip should be finalized at depth 0"))?;
+    assert_eq!(*block_header, best_chain[TIP_HEIGHT as usize]);
+    assert_eq!(height, BlockHeight(TIP_HEIGHT));
+    assert_eq!(
+        storage
+            .get_finalized(BlockHeaderHash::from(&side_chain))
+            .await
+            .map_err(|e| eyre!(e))?,
+        None,
+        "side chain headers should not be finalized at depth 0"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_finalized_tip() -> Result<(), Report> {
+    zebra_test::init();
+
+    let mut state = in_memory_headersonly::init();
+    let (best_chain, _side_chain) = add_headers(&mut state).await?;
+    let storage = IBCStorage::new(state);
+
+    for depth in 0..=TIP_HEIGHT {
+        let height = TIP_HEIGHT - depth;
+        assert_eq!(
+            storage
+                .get_finalized_tip(depth)
+                .await
+                .map_err(|e| eyre!(e))?,
+            Some((
+                BlockHeaderHash::from(&best_chain[height as usize]),
+                BlockHeight(height)
+            )),
+            "the finalized tip at depth {} should be at height {}",
+            depth,
+            height
+        );
+    }
+
+    // The best chain is shallower than the depth
+    assert_eq!(
+        storage
+            .get_finalized_tip(TIP_HEIGHT + 1)
+            .await
+            .map_err(|e| eyre!(e))?,
+        None
+    );
+    assert_eq!(
+        storage
+            .get_finalized_tip(u32::MAX)
+            .await
+            .map_err(|e| eyre!(e))?,
+        None
+    );
+
+    Ok(())
+}
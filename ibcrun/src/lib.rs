@@ -2,5 +2,7 @@
 
 #![allow(dead_code)]
 
+pub mod node;
 pub mod prelude;
+pub mod server;
 pub mod zcash;
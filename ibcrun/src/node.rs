@@ -0,0 +1,247 @@
+//! An embeddable headers-only node.
+//!
+//! `NodeHandle::start` runs a headers-only node inside the calling process,
+//! on a tokio runtime supplied by the caller. Unlike `Config::run`, it
+//! doesn't take over the process, and the node can be shut down.
+//!
+//! If `listen_addr` is set in the `[ibc]` config section, the node also
+//! serves IBC queries using `crate::server::QueryServer`. The node only
+//! stores block headers, so its query server doesn't answer inclusion proof
+//! queries.
+//!
+//! The node watches its best chain for reorganisations that are deeper than
+//! the finality depth, and publishes them as misbehaviour evidence.
+//!
+//! The caller is responsible for setting up tracing.
+
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use color_eyre::eyre::{eyre, Report};
+use futures::future::{abortable, AbortHandle, Aborted, FutureExt};
+use tokio::{runtime::Handle, sync::watch, task::JoinHandle};
+
+use ibclib::{
+    misbehaviour::{Misbehaviour, ReorgTracker},
+    prelude::IBCStorage,
+};
+use crate::server::QueryServer;
+use zebra_network::{AddressBook, PeerSetHandle};
+use zebrad::{
+    commands::start_headersonly::{init_node, HeadersOnlyState, SyncStatus},
+    config::ZebradConfig,
+};
+
+/// A handle to a running headers-only node.
+pub struct NodeHandle {
+    storage: IBCStorage<HeadersOnlyState>,
+    abort_state: AbortHandle,
+    state_task: JoinHandle<Result<(), Aborted>>,
+    address_book: Arc<Mutex<AddressBook>>,
+    peer_set_handle: PeerSetHandle,
+    sync_status: watch::Receiver<SyncStatus>,
+    abort_sync: AbortHandle,
+    sync_task: JoinHandle<Result<Result<(), Report>, Aborted>>,
+    misbehaviour: watch::Receiver<Option<Misbehaviour>>,
+    abort_misbehaviour: AbortHandle,
+    misbehaviour_task: JoinHandle<Result<Result<(), Error>, Aborted>>,
+    query_server: Option<QueryServerTask>,
+}
+
+/// A running query server.
+struct QueryServerTask {
+    addr: SocketAddr,
+    abort: AbortHandle,
+    task: JoinHandle<Result<Result<(), Error>, Aborted>>,
+}
+
+type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+impl NodeHandle {
+    /// Start a headers-only node using `config`, and spawn its tasks on
+    /// `runtime`.
+    ///
+    /// The returned future doesn't need to be polled on `runtime`.
+    ///
+    /// If the query server can't bind to its `listen_addr`, the node is shut
+    /// down, and the bind error is returned.
+    pub async fn start(config: ZebradConfig, runtime: Handle) -> Result<Self, Report> {
+        let finality_depth = config.ibc.finality_depth;
+        let listen_addr = config.ibc.listen_addr;
+        let node = runtime
+            .spawn(async move { init_node(&config).await })
+            .await?;
+
+        let (sync, abort_sync) = abortable(node.sync);
+        let sync_task = runtime.spawn(sync);
+
+        let storage = IBCStorage::new(node.state).with_finality_depth(finality_depth);
+
+        let (misbehaviour_sender, misbehaviour) = watch::channel(None);
+        let (detect, abort_misbehaviour) = abortable(detect_misbehaviour(
+            storage.clone(),
+            finality_depth,
+            misbehaviour_sender,
+        ));
+        let misbehaviour_task = runtime.spawn(detect);
+
+        let mut node = Self {
+            storage,
+            abort_state: node.abort_state,
+            state_task: node.state_task,
+            address_book: node.address_book,
+            peer_set_handle: node.peer_set_handle,
+            sync_status: node.sync_status,
+            abort_sync,
+            sync_task,
+            misbehaviour,
+            abort_misbehaviour,
+            misbehaviour_task,
+            query_server: None,
+        };
+
+        if let Some(listen_addr) = listen_addr {
+            match node.start_query_server(listen_addr, &runtime).await {
+                Ok(query_server) => node.query_server = Some(query_server),
+                Err(e) => {
+                    // The other tasks are already running, and the state
+                    // database is open, so stop them before returning
+                    if let Err(shutdown_error) = node.shutdown().await {
+                        tracing::warn!(%shutdown_error, "syncer failed before shutdown");
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(node)
+    }
+
+    /// Bind the IBC query server to `listen_addr`, and spawn it on
+    /// `runtime`.
+    async fn start_query_server(
+        &self,
+        listen_addr: SocketAddr,
+        runtime: &Handle,
+    ) -> Result<QueryServerTask, Report> {
+        // Binding uses the tokio runtime, so we need to do it inside a task.
+        let server = QueryServer::new(self.storage.clone());
+        let (addr, serve) = runtime
+            .spawn(async move {
+                server
+                    .bind(&listen_addr)
+                    .map(|(addr, serve)| (addr, serve.boxed()))
+            })
+            .await?
+            .map_err(|e| eyre!(e))?;
+
+        let (serve, abort) = abortable(serve);
+        Ok(QueryServerTask {
+            addr,
+            abort,
+            task: runtime.spawn(serve),
+        })
+    }
+
+    /// Returns the address of the IBC query server, if it is running.
+    pub fn query_addr(&self) -> Option<SocketAddr> {
+        self.query_server.as_ref().map(|query_server| query_server.addr)
+    }
+
+    /// Returns the IBC interface to the node's block header state.
+    pub fn storage(&self) -> &IBCStorage<HeadersOnlyState> {
+        &self.storage
+    }
+
+    /// Returns the addresses of the peers that the node knows about.
+    pub fn address_book(&self) -> Arc<Mutex<AddressBook>> {
+        self.address_book.clone()
+    }
+
+    /// Returns a watch channel that is updated as the node syncs block
+    /// headers.
+    pub fn sync_status(&self) -> watch::Receiver<SyncStatus> {
+        self.sync_status.clone()
+    }
+
+    /// Returns a watch channel that is updated with misbehaviour evidence,
+    /// whenever the node reorganises blocks that were deeper than the
+    /// finality depth.
+    ///
+    /// Relayers can submit the evidence to the Zcash clients on other chains.
+    pub fn misbehaviour(&self) -> watch::Receiver<Option<Misbehaviour>> {
+        self.misbehaviour.clone()
+    }
+
+    /// Stop the node, and wait for its tasks to finish.
+    ///
+    /// Stops the syncer, misbehaviour detection, and the query server, then
+    /// the network's background tasks, then closes the state database. The
+    /// node's `cache_dir` can be reopened after this method returns.
+    ///
+    /// Peer connections close once the peer set is dropped. Watch channels
+    /// and address books returned by this handle stop being updated.
+    ///
+    /// Returns the syncer's error, if it failed before shutdown.
+    pub async fn shutdown(self) -> Result<(), Report> {
+        self.abort_sync.abort();
+        self.abort_misbehaviour.abort();
+        if let Ok(Err(e)) = self.misbehaviour_task.await? {
+            tracing::warn!(%e, "misbehaviour detection failed before shutdown");
+        }
+        if let Some(query_server) = self.query_server {
+            query_server.abort.abort();
+            if let Ok(Err(e)) = query_server.task.await? {
+                tracing::warn!(%e, "IBC query server failed before shutdown");
+            }
+        }
+        let sync_result = match self.sync_task.await? {
+            Ok(result) => result,
+            Err(Aborted) => Ok(()),
+        };
+
+        self.peer_set_handle.shutdown();
+
+        // Other tasks can still have handles to the state, so we stop its
+        // worker, which owns the database
+        drop(self.storage);
+        self.abort_state.abort();
+        let _ = self.state_task.await?;
+
+        sync_result
+    }
+}
+
+/// Watch the best chain in `storage` for reorganisations that are deeper than
+/// `trusting_depth`, and send their misbehaviour evidence to `sender`.
+async fn detect_misbehaviour(
+    storage: IBCStorage<HeadersOnlyState>,
+    trusting_depth: u32,
+    sender: watch::Sender<Option<Misbehaviour>>,
+) -> Result<(), Error> {
+    loop {
+        let mut chain_events = storage.subscribe().await?;
+        let mut tracker = ReorgTracker::new(trusting_depth);
+
+        // The stream ends if we fall too far behind, so subscribe again
+        while let Some(event) = chain_events.recv().await {
+            let reorg = match tracker.track(&event) {
+                Some(reorg) => reorg,
+                None => continue,
+            };
+
+            match storage.reorg_misbehaviour(reorg).await {
+                Ok(misbehaviour) => {
+                    tracing::warn!(
+                        height = ?misbehaviour.height(),
+                        "best chain reorganised below the finality depth"
+                    );
+                    sender.broadcast(Some(misbehaviour))?;
+                }
+                Err(e) => tracing::warn!(%e, "could not get evidence for a deep reorganisation"),
+            }
+        }
+    }
+}
@@ -0,0 +1,377 @@
+//! A local JSON over HTTP server for the `IBCRequest` queries.
+//!
+//! Relayers that can't link `ibclib` can query a node using these `GET`
+//! requests:
+//!   * `/tip`: the best chain tip
+//!   * `/finalized_tip`: the best chain header at the configured finality
+//!     depth
+//!   * `/finalized_tip/{depth}`: the best chain header at `depth`
+//!   * `/header/hash/{hash}`: a block header by hash
+//!   * `/header/height/{height}`: a best chain block header by height
+//!   * `/headers/{start}/{end}`: the best chain block headers from `start` to
+//!     `end`, inclusive, limited to `MAX_BLOCK_HEADER_RANGE_RESULTS` headers
+//!   * `/proof/{txid}/{height}`: a Merkle proof that the transaction `txid`
+//!     is in the best chain block at `height`, if the server has a block
+//!     state
+//!
+//! Hashes are hex-encoded, in their serialized byte order. Heights are
+//! integers. Responses are JSON:
+//!   * tips are `{"hash": hash, "height": height}`
+//!   * headers are `{"hash": hash, "height": height, "header": hex}`, where
+//!     `header` is the zcash serialization of the block header
+//!   * header ranges are arrays of headers
+//!   * proofs are `{"txid": hash, "height": height, "index": index,
+//!     "branch": [hash, ...]}`, which can be checked against the
+//!     `merkle_root_hash` of the block header at `height`
+//!   * errors are `{"error": message}`, with status 400 for invalid requests,
+//!     and 404 for missing data
+//!
+//! Inclusion proofs need a full block state, which is supplied using
+//! `QueryServer::with_blocks`. Servers without a block state, including the
+//! server run by the headers-only `crate::node::NodeHandle`, don't have the
+//! `/proof` query, and don't list it in their usage text.
+
+use std::{error, fmt, future::Future, net::SocketAddr, str::FromStr};
+
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use serde::Serialize;
+use tower::{buffer::Buffer, util::BoxService, Service};
+
+use ibclib::prelude::{IBCQuery, IBCRequest, IBCStorage};
+use zebra_chain::{
+    block::{BlockHeader, BlockHeaderHash},
+    serialization::ZcashSerialize,
+    transaction::TransactionHash,
+    types::BlockHeight,
+};
+use zebra_state::{RequestBlock, RequestBlockHeader, MAX_BLOCK_HEADER_RANGE_RESULTS};
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// A block state that can answer inclusion proof queries.
+///
+/// The state service is boxed, so the server has a single type, whether or
+/// not it has a block state.
+pub type BlockState = Buffer<BoxService<RequestBlock, zebra_state::Response, Error>, RequestBlock>;
+
+/// The usage text returned by `GET /`.
+const USAGE: &str = r#"
+This HTTP endpoint answers IBC block header queries with JSON.
+
+    curl localhost:8234/tip
+    curl localhost:8234/finalized_tip
+    curl localhost:8234/finalized_tip/{depth}
+    curl localhost:8234/header/hash/{hash}
+    curl localhost:8234/header/height/{height}
+    curl localhost:8234/headers/{start}/{end}
+"#;
+
+/// The usage text for the inclusion proof query, which is only served by
+/// servers with a block state.
+const PROOF_USAGE: &str = "    curl localhost:8234/proof/{txid}/{height}\n";
+
+/// The end of the usage text.
+const USAGE_SCHEMA: &str = "\nSee the `ibcrun::server` documentation for the response schema.\n";
+
+/// A JSON over HTTP server for the `IBCRequest` queries on a block header
+/// state.
+#[derive(Clone)]
+pub struct QueryServer<S>
+where
+    IBCStorage<S>: Send + Sync + 'static,
+{
+    storage: IBCStorage<S>,
+    blocks: Option<IBCStorage<BlockState>>,
+}
+
+/// A tip, in a JSON response.
+#[derive(Serialize)]
+struct TipJson {
+    hash: String,
+    height: u32,
+}
+
+/// A block header, in a JSON response.
+#[derive(Serialize)]
+struct HeaderJson {
+    hash: String,
+    height: u32,
+    header: String,
+}
+
+/// A Merkle proof, in a JSON response.
+#[derive(Serialize)]
+struct ProofJson {
+    txid: String,
+    height: u32,
+    index: u32,
+    branch: Vec<String>,
+}
+
+/// An error, in a JSON response.
+#[derive(Serialize)]
+struct ErrorJson {
+    error: String,
+}
+
+impl<S> QueryServer<S>
+where
+    S: Service<RequestBlockHeader, Response = zebra_state::Response, Error = Error>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+    S::Future: Send,
+{
+    /// Create a server that answers queries using `storage`.
+    pub fn new(storage: IBCStorage<S>) -> Self {
+        Self {
+            storage,
+            blocks: None,
+        }
+    }
+
+    /// Answer inclusion proof queries using the full block state `blocks`.
+    pub fn with_blocks(self, blocks: IBCStorage<BlockState>) -> Self {
+        Self {
+            blocks: Some(blocks),
+            ..self
+        }
+    }
+
+    /// Listen for queries on `addr`.
+    ///
+    /// Returns the bound address, and a future that serves queries until it
+    /// fails, or is dropped. Must be called from within a tokio runtime.
+    pub fn bind(
+        self,
+        addr: &SocketAddr,
+    ) -> Result<(SocketAddr, impl Future<Output = Result<(), Error>>), Error> {
+        let service = make_service_fn(move |_| {
+            let server = self.clone();
+            async move {
+                Ok::<_, hyper::Error>(service_fn(move |request| {
+                    let server = server.clone();
+                    async move { Ok::<_, hyper::Error>(server.respond(request).await) }
+                }))
+            }
+        });
+
+        let server = Server::try_bind(addr)?.serve(service);
+        let local_addr = server.local_addr();
+        tracing::info!(?local_addr, "serving IBC queries");
+
+        Ok((local_addr, async move { Ok(server.await?) }))
+    }
+
+    /// Returns the response to `request`.
+    async fn respond(&self, request: Request<Body>) -> Response<Body> {
+        if request.method() != Method::GET {
+            return error_response(StatusCode::METHOD_NOT_ALLOWED, "only GET is supported");
+        }
+
+        let path = request.uri().path().trim_matches('/').to_owned();
+        let segments: Vec<&str> = path.split('/').collect();
+
+        let result = match segments.as_slice() {
+            [""] => return Response::new(Body::from(self.usage())),
+            ["tip"] => self.tip().await,
+            ["finalized_tip"] => self.finalized_tip(self.storage.finality_depth).await,
+            ["finalized_tip", depth] => match parse(depth) {
+                Ok(depth) => self.finalized_tip(depth).await,
+                Err(e) => Err(e),
+            },
+            ["header", "hash", hash] => match parse::<BlockHeaderHash>(hash) {
+                Ok(hash) => self.header(hash.into()).await,
+                Err(e) => Err(e),
+            },
+            ["header", "height", height] => match parse(height) {
+                Ok(height) => self.header(BlockHeight(height).into()).await,
+                Err(e) => Err(e),
+            },
+            ["headers", start, end] => match (parse(start), parse(end)) {
+                (Ok(start), Ok(end)) => self.headers(BlockHeight(start), BlockHeight(end)).await,
+                (Err(e), _) | (_, Err(e)) => Err(e),
+            },
+            ["proof", txid, height] if self.blocks.is_some() => {
+                match (parse::<TransactionHash>(txid), parse(height)) {
+                    (Ok(txid), Ok(height)) => self.proof(txid, BlockHeight(height)).await,
+                    (Err(e), _) | (_, Err(e)) => Err(e),
+                }
+            }
+            _ => Err((StatusCode::NOT_FOUND, format!("unknown query: /{}", path))),
+        };
+
+        match result {
+            Ok(response) => response,
+            Err((status, message)) => error_response(status, message),
+        }
+    }
+
+    /// Returns the usage text for the queries this server answers.
+    fn usage(&self) -> String {
+        let mut usage = USAGE.to_owned();
+        if self.blocks.is_some() {
+            usage.push_str(PROOF_USAGE);
+        }
+        usage.push_str(USAGE_SCHEMA);
+
+        usage
+    }
+
+    async fn tip(&self) -> QueryResult {
+        match self.storage.get_tip().await.map_err(not_found)? {
+            Some((hash, height)) => Ok(tip_response(hash, height)),
+            None => Err((StatusCode::NOT_FOUND, "the state is empty".to_owned())),
+        }
+    }
+
+    async fn finalized_tip(&self, depth: u32) -> QueryResult {
+        match self
+            .storage
+            .get_finalized_tip(depth)
+            .await
+            .map_err(not_found)?
+        {
+            Some((hash, height)) => Ok(tip_response(hash, height)),
+            None => Err((
+                StatusCode::NOT_FOUND,
+                format!("the best chain is shorter than {} blocks", depth),
+            )),
+        }
+    }
+
+    async fn header(&self, query: IBCQuery<BlockHeaderHash, BlockHeight>) -> QueryResult {
+        match self.storage.get(query).await.map_err(not_found)? {
+            Some((block_header, height)) => Ok(json_response(&header_json(&block_header, height))),
+            None => Err((
+                StatusCode::NOT_FOUND,
+                "block header could not be found".to_owned(),
+            )),
+        }
+    }
+
+    async fn headers(&self, start: BlockHeight, end: BlockHeight) -> QueryResult {
+        if end < start {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("invalid range: end {} is below start {}", end.0, start.0),
+            ));
+        }
+        if end.0 - start.0 >= MAX_BLOCK_HEADER_RANGE_RESULTS {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "ranges are limited to {} headers",
+                    MAX_BLOCK_HEADER_RANGE_RESULTS
+                ),
+            ));
+        }
+
+        let headers: Vec<HeaderJson> = self
+            .storage
+            .get_range(start, end)
+            .await
+            .map_err(not_found)?
+            .iter()
+            .map(|(block_header, height)| header_json(block_header, *height))
+            .collect();
+
+        Ok(json_response(&headers))
+    }
+
+    async fn proof(&self, txid: TransactionHash, height: BlockHeight) -> QueryResult {
+        let blocks = self
+            .blocks
+            .as_ref()
+            .expect("proof queries are only routed to servers with blocks");
+
+        match blocks
+            .get_merkle_proof(txid, height)
+            .await
+            .map_err(not_found)?
+        {
+            Some(proof) => Ok(json_response(&ProofJson {
+                txid: hex::encode(&proof.txid.0),
+                height: height.0,
+                index: proof.index,
+                branch: proof.branch.iter().map(hex::encode).collect(),
+            })),
+            None => Err((
+                StatusCode::NOT_FOUND,
+                format!("transaction is not in the block at {:?}", height),
+            )),
+        }
+    }
+}
+
+/// The result of a query: a response, or an error status and message.
+type QueryResult = Result<Response<Body>, (StatusCode, String)>;
+
+/// Parse a path segment, or return a bad request error.
+fn parse<T>(segment: &str) -> Result<T, (StatusCode, String)>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    segment.parse().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("invalid path segment {:?}: {}", segment, e),
+        )
+    })
+}
+
+/// Returns a missing data error for a state error.
+///
+/// Requests are validated before they are sent to the state, and the state
+/// returns errors for missing headers, so most state errors are missing data.
+fn not_found(e: Error) -> (StatusCode, String) {
+    (StatusCode::NOT_FOUND, e.to_string())
+}
+
+fn tip_response(hash: BlockHeaderHash, height: BlockHeight) -> Response<Body> {
+    json_response(&TipJson {
+        hash: hex::encode(&hash.0),
+        height: height.0,
+    })
+}
+
+fn header_json(block_header: &BlockHeader, height: BlockHeight) -> HeaderJson {
+    let header = block_header
+        .zcash_serialize_to_vec()
+        .expect("block headers serialize into a Vec");
+
+    HeaderJson {
+        hash: hex::encode(&BlockHeaderHash::from(block_header).0),
+        height: height.0,
+        header: hex::encode(header),
+    }
+}
+
+fn json_response(value: &impl Serialize) -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::to_string(value).expect("responses serialize to JSON"),
+        ))
+        .expect("response with known status code cannot fail")
+}
+
+fn error_response(status: StatusCode, message: impl ToString) -> Response<Body> {
+    let error = ErrorJson {
+        error: message.to_string(),
+    };
+
+    Response::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::to_string(&error).expect("errors serialize to JSON"),
+        ))
+        .expect("response with known status code cannot fail")
+}
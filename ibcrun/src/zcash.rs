@@ -1,3 +1,7 @@
+//! Runs `zebrad` as if it was started from the command line.
+//!
+//! To embed a node in another application, use `crate::node::NodeHandle`.
+
 use zebrad::application::APPLICATION;
 // use zebrad::prelude::*;
 // use zebrad::prelude::Application as app;
@@ -0,0 +1,63 @@
+use color_eyre::eyre::{eyre, Report};
+use std::{collections::HashSet, net::TcpListener};
+use tempdir::TempDir;
+use tokio::runtime::Handle;
+
+use ibcrun::node::NodeHandle;
+use zebrad::config::ZebradConfig;
+
+/// Returns the config for a node that stores its state in `cache_dir`, and
+/// doesn't connect to any peers.
+fn config(cache_dir: &TempDir) -> Result<ZebradConfig, Report> {
+    let mut config = ZebradConfig::default();
+
+    config.network.initial_mainnet_peers = HashSet::new();
+    config.network.initial_testnet_peers = HashSet::new();
+    config.network.listen_addr = "127.0.0.1:0".parse()?;
+    config.state.cache_dir = cache_dir.path().to_owned();
+    config.state.ephemeral = false;
+    config.ibc.listen_addr = Some("127.0.0.1:0".parse()?);
+
+    Ok(config)
+}
+
+#[tokio::test]
+async fn shutdown_closes_state() -> Result<(), Report> {
+    zebra_test::init();
+
+    let cache_dir = TempDir::new("ibcrun-node")?;
+
+    let node = NodeHandle::start(config(&cache_dir)?, Handle::current()).await?;
+    assert!(node.query_addr().is_some());
+    node.shutdown().await?;
+
+    // The state database is locked while it is open, so the node can only
+    // start again if shutdown closed it
+    let node = NodeHandle::start(config(&cache_dir)?, Handle::current()).await?;
+    node.storage().subscribe().await.map_err(|e| eyre!(e))?;
+    node.shutdown().await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn query_server_bind_failure_stops_node() -> Result<(), Report> {
+    zebra_test::init();
+
+    let cache_dir = TempDir::new("ibcrun-node")?;
+
+    // Something else is already listening on the query server's address
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let mut busy_config = config(&cache_dir)?;
+    busy_config.ibc.listen_addr = Some(listener.local_addr()?);
+    assert!(NodeHandle::start(busy_config, Handle::current())
+        .await
+        .is_err());
+
+    // The failed start closed the state database, so the node can start
+    // again
+    let node = NodeHandle::start(config(&cache_dir)?, Handle::current()).await?;
+    node.shutdown().await?;
+
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use color_eyre::eyre::{eyre, Report};
+use hyper::{body, Client, StatusCode};
+use std::{net::SocketAddr, sync::Arc};
+use tower::{buffer::Buffer, util::BoxService, Service, ServiceExt};
+use zebra_chain::{
+    block::{Block, BlockHeaderHash},
+    serialization::{ZcashDeserialize, ZcashSerialize},
+    transaction::TransactionHash,
+};
+
+use ibclib::prelude::IBCStorage;
+use ibcrun::server::{BlockState, QueryServer};
+use zebra_state::{
+    in_memory, in_memory_headersonly, RequestBlock, RequestBlockHeader,
+    MAX_BLOCK_HEADER_RANGE_RESULTS,
+};
+
+/// Returns the mainnet genesis block and block 1.
+fn blocks() -> Result<Vec<Arc<Block>>, Report> {
+    Ok(vec![
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?.into(),
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])?.into(),
+    ])
+}
+
+/// Starts a query server for ephemeral in-memory states containing `blocks`,
+/// and returns its address.
+async fn start_server(blocks: &[Arc<Block>]) -> Result<SocketAddr, Report> {
+    let mut header_state = in_memory_headersonly::init();
+    let mut block_state: BlockState = Buffer::new(BoxService::new(in_memory::init()), 1);
+
+    for block in blocks {
+        header_state
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(RequestBlockHeader::AddBlockHeader {
+                block_header: Arc::new(block.header),
+            })
+            .await
+            .map_err(|e| eyre!(e))?;
+        block_state
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(RequestBlock::AddBlock {
+                block: block.clone(),
+            })
+            .await
+            .map_err(|e| eyre!(e))?;
+    }
+
+    let server = QueryServer::new(IBCStorage::new(header_state).with_finality_depth(1))
+        .with_blocks(IBCStorage::new(block_state));
+    let (addr, serve) = server.bind(&"127.0.0.1:0".parse()?).map_err(|e| eyre!(e))?;
+    tokio::spawn(serve);
+
+    Ok(addr)
+}
+
+/// Sends a `GET` request for `path` to the server at `addr`, and returns the
+/// response status and JSON body.
+async fn get(addr: SocketAddr, path: &str) -> Result<(StatusCode, serde_json::Value), Report> {
+    let uri = format!("http://{}{}", addr, path).parse()?;
+    let response = Client::new().get(uri).await?;
+    let status = response.status();
+    let body = body::to_bytes(response.into_body()).await?;
+
+    Ok((status, serde_json::from_slice(&body)?))
+}
+
+#[tokio::test]
+async fn header_queries() -> Result<(), Report> {
+    zebra_test::init();
+
+    let blocks = blocks()?;
+    let hashes: Vec<String> = blocks
+        .iter()
+        .map(|block| hex::encode(&BlockHeaderHash::from(&block.header).0))
+        .collect();
+    let addr = start_server(&blocks).await?;
+
+    let (status, tip) = get(addr, "/tip").await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tip["hash"], hashes[1]);
+    assert_eq!(tip["height"], 1);
+
+    let (status, finalized_tip) = get(addr, "/finalized_tip").await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(finalized_tip["hash"], hashes[0]);
+    assert_eq!(finalized_tip["height"], 0);
+
+    let (status, _) = get(addr, "/finalized_tip/2").await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, header) = get(addr, "/header/height/1").await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(header["hash"], hashes[1]);
+
+    let (status, header) = get(addr, &format!("/header/hash/{}", hashes[0])).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(header["height"], 0);
+    assert_eq!(
+        header["header"],
+        hex::encode(blocks[0].header.zcash_serialize_to_vec()?)
+    );
+
+    let (status, headers) = get(addr, "/headers/0/1").await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[0]["hash"], hashes[0]);
+    assert_eq!(headers[1]["hash"], hashes[1]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn proof_query() -> Result<(), Report> {
+    zebra_test::init();
+
+    let blocks = blocks()?;
+    let addr = start_server(&blocks).await?;
+
+    let coinbase = TransactionHash::from(blocks[1].transactions[0].as_ref());
+    let (status, proof) = get(addr, &format!("/proof/{}/1", hex::encode(&coinbase.0))).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(proof["txid"], hex::encode(&coinbase.0));
+    assert_eq!(proof["index"], 0);
+    // Block 1 only has a coinbase transaction, so its Merkle root is the
+    // coinbase transaction hash
+    assert_eq!(proof["branch"], serde_json::json!([]));
+    assert_eq!(blocks[1].header.merkle_root_hash.0, coinbase.0);
+
+    let (status, _) = get(addr, &format!("/proof/{}/0", hex::encode(&coinbase.0))).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn proof_query_needs_blocks() -> Result<(), Report> {
+    zebra_test::init();
+
+    let server = QueryServer::new(IBCStorage::new(in_memory_headersonly::init()));
+    let (addr, serve) = server.bind(&"127.0.0.1:0".parse()?).map_err(|e| eyre!(e))?;
+    tokio::spawn(serve);
+
+    let coinbase = TransactionHash::from(blocks()?[1].transactions[0].as_ref());
+    let (status, error) = get(addr, &format!("/proof/{}/1", hex::encode(&coinbase.0))).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(error["error"]
+        .as_str()
+        .expect("errors are strings")
+        .starts_with("unknown query"));
+
+    let response = Client::new()
+        .get(format!("http://{}/", addr).parse()?)
+        .await?;
+    let usage = body::to_bytes(response.into_body()).await?;
+    let usage = String::from_utf8(usage.to_vec())?;
+    assert!(usage.contains("/tip"));
+    assert!(!usage.contains("/proof"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn invalid_queries() -> Result<(), Report> {
+    zebra_test::init();
+
+    let addr = start_server(&blocks()?).await?;
+
+    let (status, error) = get(addr, "/header/height/tip").await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(error["error"].is_string());
+
+    let (status, _) = get(addr, "/header/height/5").await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Invalid ranges are bad requests, even if the headers are in the state
+    let (status, _) = get(addr, "/headers/1/0").await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = get(
+        addr,
+        &format!("/headers/0/{}", MAX_BLOCK_HEADER_RANGE_RESULTS),
+    )
+    .await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = get(addr, &format!("/headers/0/{}", u32::MAX)).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Valid ranges past the tip are missing data
+    let (status, _) = get(addr, "/headers/1/5").await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = get(addr, "/unknown").await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 //! Definitions of block datastructures.
 #![allow(clippy::unit_arg)]
 
+mod difficulty;
 mod hash;
 mod header;
 mod serialize;
@@ -17,6 +18,7 @@ use proptest_derive::Arbitrary;
 use crate::transaction::Transaction;
 use crate::types::BlockHeight;
 
+pub use difficulty::{CompactDifficulty, ExpandedDifficulty, Work};
 pub use hash::BlockHeaderHash;
 pub use header::BlockHeader;
 
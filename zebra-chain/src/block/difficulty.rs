@@ -0,0 +1,362 @@
+//! Block difficulty data structures and calculations
+//!
+//! The block difficulty "target threshold" is stored in the block header as a
+//! 32-bit `CompactDifficulty`. The `BlockHeaderHash` must be less than or equal
+//! to the `ExpandedDifficulty` threshold, when represented as a 256-bit integer
+//! in little-endian order.
+//!
+//! The block difficulty is also used to calculate the `Work` done by each
+//! block, which is used to select the best chain.
+
+use std::{cmp::Ordering, fmt};
+
+use primitive_types::U256;
+
+#[cfg(test)]
+use proptest_derive::Arbitrary;
+
+use crate::Network;
+
+use super::BlockHeaderHash;
+
+/// A 32-bit "compact bits" value, which represents the difficulty threshold for
+/// a block header.
+///
+/// Used for:
+///   - checking the `difficulty_threshold` value in the block header,
+///   - calculating the 256-bit `ExpandedDifficulty` threshold, for comparison
+///     with the block header hash, and
+///   - calculating the block work.
+///
+/// # Consensus
+///
+/// This is a floating-point encoding, with a 24-bit signed mantissa,
+/// an 8-bit exponent, an offset of 3, and a radix of 256.
+/// (IEEE 754 32-bit floating-point values use a separate sign bit, an implicit
+/// leading mantissa bit, an offset of 127, and a radix of 2.)
+///
+/// The precise bit pattern of a `CompactDifficulty` value is
+/// consensus-critical, because it is used for the `bits` field, which is
+/// hashed to produce the block header hash.
+///
+/// [Bitcoin-nBits](https://bitcoin.org/en/developer-reference#target-nbits)
+#[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(Arbitrary))]
+pub struct CompactDifficulty(pub u32);
+
+/// A 256-bit unsigned "expanded difficulty" value.
+///
+/// Used as a target threshold for the difficulty of a `BlockHeaderHash`.
+///
+/// Details:
+///
+/// The precise bit pattern of an `ExpandedDifficulty` value is not
+/// consensus-critical, because it is always converted to or from a
+/// `CompactDifficulty` before it is used in consensus rules.
+///
+/// Callers should avoid constructing `ExpandedDifficulty` zero values, because
+/// they are rejected by the consensus rules.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ExpandedDifficulty(U256);
+
+/// The work done by a block, or the cumulative work done by a chain of blocks.
+///
+/// The work of a block is the expected number of hashes a miner needs to try
+/// to find a header hash below its difficulty threshold. The chain with the
+/// most cumulative work is the best chain.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct Work(u128);
+
+impl fmt::Debug for CompactDifficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CompactDifficulty")
+            .field(&format_args!("{:#010x}", self.0))
+            .finish()
+    }
+}
+
+impl fmt::Debug for ExpandedDifficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = [0; 32];
+        self.0.to_big_endian(&mut buf);
+
+        f.debug_tuple("ExpandedDifficulty")
+            .field(&hex::encode(&buf))
+            .finish()
+    }
+}
+
+impl fmt::Debug for Work {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Work")
+            .field(&format_args!("{:#x}", self.0))
+            .finish()
+    }
+}
+
+impl CompactDifficulty {
+    /// CompactDifficulty exponent offset.
+    const OFFSET: i32 = 3;
+
+    /// CompactDifficulty floating-point precision.
+    const PRECISION: u32 = 24;
+
+    /// CompactDifficulty sign bit, part of the signed mantissa.
+    const SIGN_BIT: u32 = 1 << (CompactDifficulty::PRECISION - 1);
+
+    /// CompactDifficulty unsigned mantissa mask.
+    ///
+    /// Also the maximum unsigned mantissa value.
+    const UNSIGNED_MANTISSA_MASK: u32 = CompactDifficulty::SIGN_BIT - 1;
+
+    /// Calculate the ExpandedDifficulty for a compact representation.
+    ///
+    /// See `ToTarget()` in the Zcash Specification, and `CheckProofOfWork()` in
+    /// zcashd.
+    ///
+    /// Returns None for negative, zero, and overflow values. (zcashd rejects
+    /// these values, before comparing the hash.)
+    pub fn to_expanded(&self) -> Option<ExpandedDifficulty> {
+        // The constants for this floating-point representation.
+        // Alias the struct constants here, so the code is easier to read.
+        const OFFSET: i32 = CompactDifficulty::OFFSET;
+        const PRECISION: u32 = CompactDifficulty::PRECISION;
+        const SIGN_BIT: u32 = CompactDifficulty::SIGN_BIT;
+        const UNSIGNED_MANTISSA_MASK: u32 = CompactDifficulty::UNSIGNED_MANTISSA_MASK;
+
+        // The fractional part of the floating-point number
+        // x & (2^23 - 1)
+        let mantissa = self.0 & UNSIGNED_MANTISSA_MASK;
+
+        // The exponent for the multiplier in the floating-point number
+        // 256^(floor(x/(2^24)) - 3)
+        // The i32 conversion is safe, because we've just divided self by 2^24.
+        let exponent = ((self.0 >> PRECISION) as i32) - OFFSET;
+
+        // zcashd rejects zero and negative values without comparing the hash
+        if mantissa == 0 || self.0 & SIGN_BIT == SIGN_BIT {
+            return None;
+        }
+
+        // zcashd rejects values that overflow a 256-bit integer, using the
+        // exact rules from `arith_uint256::SetCompact()`
+        if exponent > 31
+            || (mantissa > 0xff && exponent > 30)
+            || (mantissa > 0xffff && exponent > 29)
+        {
+            return None;
+        }
+
+        // Now calculate the result: mantissa*base^exponent
+        // Underflowing values are shifted right, dropping their low bits.
+        let result = if exponent < 0 {
+            U256::from(mantissa >> (8 * (-exponent) as u32))
+        } else {
+            U256::from(mantissa) << (8 * exponent as usize)
+        };
+
+        // zcashd rejects any zero values left after an underflow
+        if result.is_zero() {
+            None
+        } else {
+            Some(ExpandedDifficulty(result))
+        }
+    }
+
+    /// Calculate the Work for a compact representation.
+    ///
+    /// See `to_expanded()` for details.
+    ///
+    /// Returns None if the corresponding ExpandedDifficulty is None.
+    /// Also returns None on Work overflow, which should be impossible on a
+    /// valid chain.
+    pub fn to_work(&self) -> Option<Work> {
+        let expanded = self.to_expanded()?;
+
+        // We need to compute `2^256 / (expanded + 1)`, but we can't represent
+        // 2^256, as it's too large for a u256. However, as 2^256 is at least as
+        // large as `expanded + 1`, it is equal to
+        // `((2^256 - expanded - 1) / (expanded + 1)) + 1`, or
+        let result = (!expanded.0 / (expanded.0 + 1)) + 1;
+        if result.bits() <= 128 {
+            Some(Work(result.low_u128()))
+        } else {
+            None
+        }
+    }
+}
+
+impl ExpandedDifficulty {
+    /// Returns the easiest target difficulty allowed on `network`.
+    ///
+    /// `PoWLimit` in the Zcash specification, and `powLimit` in zcashd.
+    ///
+    /// # Panics
+    ///
+    /// If the hard-coded limits can't be parsed.
+    pub fn target_difficulty_limit(network: Network) -> ExpandedDifficulty {
+        let limit: U256 = match network {
+            /* 2^243 - 1 */
+            Network::Mainnet => U256::from_big_endian(
+                &hex::decode("0007ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff")
+                    .expect("hard-coded limit is valid hex"),
+            ),
+            /* 2^251 - 1 */
+            Network::Testnet => U256::from_big_endian(
+                &hex::decode("07ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff")
+                    .expect("hard-coded limit is valid hex"),
+            ),
+        };
+
+        // `zcashd` converts the PoWLimit into a compact representation before
+        // using it to perform difficulty filter checks, so we do the same.
+        ExpandedDifficulty(limit)
+            .to_compact()
+            .to_expanded()
+            .expect("difficulty limits are valid expanded values")
+    }
+
+    /// Returns the ExpandedDifficulty for a block header hash.
+    ///
+    /// Zcash interprets block header hashes as 256-bit unsigned integers in
+    /// little-endian order, when comparing them with difficulty thresholds.
+    pub fn from_hash(hash: &BlockHeaderHash) -> ExpandedDifficulty {
+        ExpandedDifficulty(U256::from_little_endian(&hash.0))
+    }
+
+    /// Calculate the CompactDifficulty for an expanded difficulty.
+    ///
+    /// See `ToCompact()` in the Zcash Specification, and `GetCompact()` in
+    /// zcashd.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is zero.
+    ///
+    /// `ExpandedDifficulty` values are generated in two ways:
+    ///   * conversion from `CompactDifficulty` values, which rejects zeroes, and
+    ///   * difficulty adjustment calculations, which impose a non-zero minimum
+    ///     `target_difficulty_limit`.
+    ///
+    /// Neither of these methods yield zero values.
+    pub fn to_compact(&self) -> CompactDifficulty {
+        // The zcashd implementation supports negative and zero compact values.
+        // These values are rejected by the protocol rules. Zebra is designed so
+        // that invalid states are not representable. Therefore, this function
+        // does not produce negative compact values, and panics on zero compact
+        // values. (The negative compact value code in zcashd is unused.)
+        assert!(self.0 > 0.into(), "Zero difficulty values are invalid");
+
+        // The constants for this floating-point representation.
+        // Alias the constants here, so the code is easier to read.
+        const UNSIGNED_MANTISSA_MASK: u32 = CompactDifficulty::UNSIGNED_MANTISSA_MASK;
+        const OFFSET: i32 = CompactDifficulty::OFFSET;
+
+        // Calculate the final size, accounting for the sign bit.
+        // This is the size *after* applying the sign bit adjustment in `ToCompact()`.
+        let size = self.0.bits() / 8 + 1;
+
+        // Make sure the mantissa is non-negative, by shifting down values that
+        // would otherwise overflow into the sign bit
+        let mantissa = if self.0 <= UNSIGNED_MANTISSA_MASK.into() {
+            // Value is small, shift up if needed
+            self.0 << (8 * (3 - size))
+        } else {
+            // Value is large, shift down
+            self.0 >> (8 * (size - 3))
+        };
+
+        // This assertion also makes sure that size fits in its 8 bit compact field
+        assert!(
+            size < (31 + OFFSET) as _,
+            "256^size (256^{}) must fit in a u256, after the sign bit adjustment and offset",
+            size
+        );
+        let size = size as u32;
+
+        assert!(
+            mantissa <= UNSIGNED_MANTISSA_MASK.into(),
+            "mantissa {:x?} must fit in its compact field",
+            mantissa
+        );
+        let mantissa = mantissa.low_u32();
+
+        if mantissa > 0 {
+            CompactDifficulty(mantissa + (size << 24))
+        } else {
+            // This check catches invalid mantissas. Overflows and underflows
+            // should also be unreachable, but they aren't caught here.
+            unreachable!("converted CompactDifficulty values must be valid")
+        }
+    }
+
+    /// Returns the sum of `expanded_difficulties`, or None on overflow.
+    pub fn checked_sum(
+        expanded_difficulties: impl IntoIterator<Item = ExpandedDifficulty>,
+    ) -> Option<ExpandedDifficulty> {
+        expanded_difficulties
+            .into_iter()
+            .try_fold(U256::zero(), |acc, difficulty| {
+                acc.checked_add(difficulty.0)
+            })
+            .map(ExpandedDifficulty)
+    }
+
+    /// Returns `self / divisor`, or None if `divisor` is zero.
+    ///
+    /// The result is rounded down, like `arith_uint256` division in zcashd.
+    pub fn checked_div(&self, divisor: u64) -> Option<ExpandedDifficulty> {
+        if divisor == 0 {
+            None
+        } else {
+            Some(ExpandedDifficulty(self.0 / U256::from(divisor)))
+        }
+    }
+
+    /// Returns `self * multiplier`, or None on overflow.
+    pub fn checked_mul(&self, multiplier: u64) -> Option<ExpandedDifficulty> {
+        self.0
+            .checked_mul(U256::from(multiplier))
+            .map(ExpandedDifficulty)
+    }
+}
+
+impl PartialEq<ExpandedDifficulty> for BlockHeaderHash {
+    /// Is `self` equal to `other`?
+    ///
+    /// See `partial_cmp` for details.
+    fn eq(&self, other: &ExpandedDifficulty) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd<ExpandedDifficulty> for BlockHeaderHash {
+    /// `BlockHeaderHash`es are compared with `ExpandedDifficulty` thresholds by
+    /// converting the hash to a 256-bit integer in little-endian order.
+    fn partial_cmp(&self, other: &ExpandedDifficulty) -> Option<Ordering> {
+        ExpandedDifficulty::from_hash(self).partial_cmp(other)
+    }
+}
+
+impl Work {
+    /// Returns the work as a 128-bit unsigned integer.
+    pub fn as_u128(&self) -> u128 {
+        self.0
+    }
+
+    /// Returns the sum of `self` and `other`, or None on overflow.
+    pub fn checked_add(&self, other: Work) -> Option<Work> {
+        self.0.checked_add(other.0).map(Work)
+    }
+
+    /// Returns the big-endian byte representation of this work, which sorts
+    /// in the same order as the work values.
+    pub fn to_be_bytes(&self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+
+    /// Reads work from its big-endian byte representation.
+    pub fn from_be_bytes(bytes: [u8; 16]) -> Work {
+        Work(u128::from_be_bytes(bytes))
+    }
+}
@@ -5,7 +5,7 @@ use crate::note_commitment_tree::SaplingNoteTreeRootHash;
 use crate::serialization::{
     SerializationError, ZcashDeserialize, ZcashDeserializeInto, ZcashSerialize,
 };
-use crate::{sha256d_writer::Sha256dWriter, test::generate};
+use crate::{sha256d_writer::Sha256dWriter, test::generate, Network};
 use chrono::{TimeZone, Utc};
 use proptest::{
     arbitrary::{any, Arbitrary},
@@ -25,7 +25,7 @@ impl Arbitrary for BlockHeader {
             any::<SaplingNoteTreeRootHash>(),
             // time is interpreted as u32 in the spec, but rust timestamps are i64
             (0i64..(u32::MAX as i64)),
-            any::<u32>(),
+            any::<CompactDifficulty>(),
             any::<[u8; 32]>(),
             any::<EquihashSolution>(),
         )
@@ -190,6 +190,83 @@ fn block_limits_single_tx() {
     Block::zcash_deserialize(&data[..]).expect_err("block should not deserialize");
 }
 
+#[test]
+fn compact_difficulty_roundtrip() {
+    // Normalised compact values survive a round-trip through expanded values
+    for compact in &[
+        0x1d00_ffff,
+        0x1f07_ffff,
+        0x2007_ffff,
+        0x1c0f_ffff,
+        0x0312_3456,
+        0x0412_3456,
+        0x0500_9234,
+        0x0112_0000,
+        0x0200_8000,
+    ] {
+        let expanded = CompactDifficulty(*compact)
+            .to_expanded()
+            .expect("valid compact values should expand");
+        assert_eq!(expanded.to_compact(), CompactDifficulty(*compact));
+    }
+
+    // Zero, negative, and overflowing values are invalid
+    for compact in &[
+        0x0000_0000,
+        0x0080_0000,
+        0x01fe_dcba,
+        0x0492_3456,
+        0xff12_3456,
+        0x2300_0001,
+        0x2200_0100,
+        0x2101_0000,
+        0x0100_3456,
+    ] {
+        assert_eq!(CompactDifficulty(*compact).to_expanded(), None);
+    }
+}
+
+#[test]
+fn difficulty_limits() {
+    assert_eq!(
+        ExpandedDifficulty::target_difficulty_limit(Network::Mainnet).to_compact(),
+        CompactDifficulty(0x1f07_ffff)
+    );
+    assert_eq!(
+        ExpandedDifficulty::target_difficulty_limit(Network::Testnet).to_compact(),
+        CompactDifficulty(0x2007_ffff)
+    );
+
+    // The work for Bitcoin's minimum difficulty is 2^32 + 2^16 + 1
+    assert_eq!(
+        CompactDifficulty(0x1d00_ffff)
+            .to_work()
+            .expect("valid compact values have work")
+            .as_u128(),
+        0x1_0001_0001
+    );
+}
+
+#[test]
+fn block_test_vectors_difficulty() {
+    let limit = ExpandedDifficulty::target_difficulty_limit(Network::Mainnet);
+
+    for block_bytes in zebra_test::vectors::TEST_BLOCKS.iter() {
+        let block = block_bytes
+            .zcash_deserialize_into::<Block>()
+            .expect("block test vector should deserialize");
+        let hash = BlockHeaderHash::from(&block);
+        let threshold = block
+            .header
+            .bits
+            .to_expanded()
+            .expect("mainnet blocks have valid difficulty thresholds");
+
+        assert!(threshold <= limit);
+        assert!(hash <= threshold);
+    }
+}
+
 proptest! {
 
     #[test]
@@ -7,7 +7,6 @@
 #[macro_use]
 extern crate serde;
 
-mod merkle_tree;
 mod serde_helpers;
 mod sha256d_writer;
 
@@ -15,9 +14,11 @@ pub mod addresses;
 pub mod block;
 pub mod equihash_solution;
 pub mod keys;
+pub mod merkle_tree;
 pub mod note_commitment_tree;
 pub mod notes;
 pub mod nullifier;
+pub mod parameters;
 pub mod proofs;
 pub mod serialization;
 pub mod transaction;
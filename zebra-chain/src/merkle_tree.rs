@@ -1,48 +1,203 @@
 //! A binary hash tree of SHA256d (two rounds of SHA256) hashes for
 //! node values.
+//!
+//! Zcash inherits Bitcoin's transaction Merkle tree. The leaves are the
+//! transaction hashes, in block order. Each parent node is the SHA256d hash
+//! of its two children. If a level has an odd number of nodes, the last node
+//! is paired with itself.
+//!
+//! Pairing the last node with itself means that different lists of
+//! transactions can have the same root (CVE-2012-2459). For example, if a
+//! block has an odd number of transactions, adding a copy of its last
+//! transaction doesn't change the root. These trees have identical sibling
+//! nodes, so they are detected by `MerkleTree::is_mutated`.
 #![allow(clippy::unit_arg)]
 
-use std::{fmt, io};
+use std::{fmt, io::Write, iter::FromIterator};
 
 #[cfg(test)]
 use proptest_derive::Arbitrary;
 
-use crate::serialization::{SerializationError, ZcashDeserialize, ZcashSerialize};
+use crate::block::Block;
 use crate::sha256d_writer::Sha256dWriter;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionHash};
 
 /// A binary hash tree of SHA256d (two rounds of SHA256) hashes for
 /// node values.
-#[derive(Default)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct MerkleTree<T> {
-    _leaves: Vec<T>,
+    leaves: Vec<T>,
 }
 
-impl<Transaction> ZcashSerialize for MerkleTree<Transaction> {
-    fn zcash_serialize<W: io::Write>(&self, _writer: W) -> Result<(), io::Error> {
-        unimplemented!();
+impl<T> MerkleTree<T> {
+    /// Returns the leaves of the tree, in block order.
+    pub fn leaves(&self) -> &[T] {
+        &self.leaves
     }
 }
 
-impl<Transaction> ZcashDeserialize for MerkleTree<Transaction> {
-    fn zcash_deserialize<R: io::Read>(_reader: R) -> Result<Self, SerializationError> {
-        unimplemented!();
+impl<T> FromIterator<T> for MerkleTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self {
+            leaves: iter.into_iter().collect(),
+        }
     }
 }
 
+impl<'a> From<&'a Block> for MerkleTree<TransactionHash> {
+    fn from(block: &'a Block) -> Self {
+        block
+            .transactions
+            .iter()
+            .map(|transaction| TransactionHash::from(transaction.as_ref()))
+            .collect()
+    }
+}
+
+impl MerkleTree<TransactionHash> {
+    /// Returns the root hash of the tree.
+    ///
+    /// The root of an empty tree is all zeroes. Valid blocks always have a
+    /// coinbase transaction, so their trees are never empty.
+    pub fn root(&self) -> MerkleTreeRootHash {
+        let mut level: Vec<[u8; 32]> = self.leaves.iter().map(|leaf| leaf.0).collect();
+        if level.is_empty() {
+            return MerkleTreeRootHash([0; 32]);
+        }
+
+        while level.len() > 1 {
+            level = parent_level(&level);
+        }
+
+        MerkleTreeRootHash(level[0])
+    }
+
+    /// Returns true if any level of the tree has two identical sibling nodes.
+    ///
+    /// A mutated tree has the same root as a tree without some of its
+    /// duplicate leaves, so blocks with mutated trees must be rejected.
+    pub fn is_mutated(&self) -> bool {
+        let mut level: Vec<[u8; 32]> = self.leaves.iter().map(|leaf| leaf.0).collect();
+
+        while level.len() > 1 {
+            if level
+                .chunks(2)
+                .any(|pair| pair.len() == 2 && pair[0] == pair[1])
+            {
+                return true;
+            }
+            level = parent_level(&level);
+        }
+
+        false
+    }
+
+    /// Returns a proof that `txid` is a leaf of this tree, or `None` if it is
+    /// not in the tree.
+    pub fn prove(&self, txid: TransactionHash) -> Option<MerkleProof> {
+        let index = self.leaves.iter().position(|leaf| *leaf == txid)?;
+
+        let mut level: Vec<[u8; 32]> = self.leaves.iter().map(|leaf| leaf.0).collect();
+        let mut position = index;
+        let mut branch = Vec::new();
+        while level.len() > 1 {
+            let sibling = position ^ 1;
+            branch.push(*level.get(sibling).unwrap_or(&level[position]));
+
+            level = parent_level(&level);
+            position /= 2;
+        }
+
+        Some(MerkleProof {
+            txid,
+            index: index as u32,
+            branch,
+        })
+    }
+}
+
+/// A proof that a transaction is in a block, which can be checked against
+/// the block header's `merkle_root_hash`.
+///
+/// The proof contains the sibling of each node on the path from the
+/// transaction's leaf to the root, so its size is logarithmic in the number
+/// of transactions in the block.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// The hash of the proven transaction.
+    pub txid: TransactionHash,
+    /// The position of the transaction in the block.
+    pub index: u32,
+    /// The sibling hashes on the path from the leaf to the root, starting
+    /// with the leaf's sibling.
+    pub branch: Vec<[u8; 32]>,
+}
+
+impl MerkleProof {
+    /// Returns true if this proof shows that `txid` is at `index` in a tree
+    /// with root hash `root`.
+    pub fn verify(&self, root: MerkleTreeRootHash) -> bool {
+        // Every bit of the index must select a side in the branch
+        if self.branch.len() < 32 && u64::from(self.index) >> self.branch.len() != 0 {
+            return false;
+        }
+
+        let mut node = self.txid.0;
+        let mut position = self.index;
+        for sibling in &self.branch {
+            node = if position & 1 == 0 {
+                hash_pair(&node, sibling)
+            } else {
+                hash_pair(sibling, &node)
+            };
+            position >>= 1;
+        }
+
+        node == root.0
+    }
+}
+
+/// Returns the parents of the nodes in `level`, pairing the last node with
+/// itself if `level` has an odd length.
+fn parent_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    level
+        .chunks(2)
+        .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+        .collect()
+}
+
+/// Returns the SHA256d hash of the concatenation of `left` and `right`.
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hash_writer = Sha256dWriter::default();
+    hash_writer
+        .write_all(left)
+        .expect("Sha256dWriter is infallible");
+    hash_writer
+        .write_all(right)
+        .expect("Sha256dWriter is infallible");
+    hash_writer.finish()
+}
+
 /// A SHA-256d hash of the root node of a merkle tree of SHA256-d
 /// hashed transactions in a block.
 #[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(test, derive(Arbitrary))]
 pub struct MerkleTreeRootHash(pub [u8; 32]);
 
+impl From<MerkleTree<TransactionHash>> for MerkleTreeRootHash {
+    fn from(merkle_tree: MerkleTree<TransactionHash>) -> Self {
+        merkle_tree.root()
+    }
+}
+
 impl From<MerkleTree<Transaction>> for MerkleTreeRootHash {
     fn from(merkle_tree: MerkleTree<Transaction>) -> Self {
-        let mut hash_writer = Sha256dWriter::default();
         merkle_tree
-            .zcash_serialize(&mut hash_writer)
-            .expect("Sha256dWriter is infallible");
-        Self(hash_writer.finish())
+            .leaves
+            .iter()
+            .map(TransactionHash::from)
+            .collect::<MerkleTree<_>>()
+            .root()
     }
 }
 
@@ -53,3 +208,112 @@ impl fmt::Debug for MerkleTreeRootHash {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::serialization::ZcashDeserialize;
+
+    fn hash(byte: u8) -> TransactionHash {
+        TransactionHash([byte; 32])
+    }
+
+    #[test]
+    fn block_test_vector_roots() {
+        zebra_test::init();
+
+        for block_bytes in zebra_test::vectors::TEST_BLOCKS.iter() {
+            let block = Block::zcash_deserialize(&block_bytes[..])
+                .expect("block test vector should deserialize");
+            let merkle_tree = MerkleTree::from(&block);
+
+            assert_eq!(merkle_tree.root(), block.header.merkle_root_hash);
+
+            for txid in merkle_tree.leaves() {
+                let proof = merkle_tree
+                    .prove(*txid)
+                    .expect("block transactions should be in the tree");
+                assert!(proof.verify(block.header.merkle_root_hash));
+            }
+        }
+    }
+
+    #[test]
+    fn single_leaf_root_is_the_leaf() {
+        let merkle_tree: MerkleTree<_> = vec![hash(1)].into_iter().collect();
+
+        assert_eq!(merkle_tree.root(), MerkleTreeRootHash(hash(1).0));
+
+        let proof = merkle_tree.prove(hash(1)).expect("the leaf is in the tree");
+        assert!(proof.branch.is_empty());
+        assert!(proof.verify(merkle_tree.root()));
+    }
+
+    #[test]
+    fn odd_levels_duplicate_the_last_node() {
+        let odd: MerkleTree<_> = vec![hash(1), hash(2), hash(3)].into_iter().collect();
+        let even: MerkleTree<_> = vec![hash(1), hash(2), hash(3), hash(3)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(odd.root(), even.root());
+        assert!(!odd.is_mutated());
+        assert!(even.is_mutated());
+
+        let left = hash_pair(&hash(1).0, &hash(2).0);
+        let right = hash_pair(&hash(3).0, &hash(3).0);
+        assert_eq!(odd.root(), MerkleTreeRootHash(hash_pair(&left, &right)));
+
+        let proof = odd.prove(hash(3)).expect("the leaf is in the tree");
+        assert_eq!(proof.index, 2);
+        assert_eq!(proof.branch, vec![hash(3).0, left]);
+        assert!(proof.verify(odd.root()));
+    }
+
+    #[test]
+    fn identical_siblings_are_mutations() {
+        let distinct: MerkleTree<_> = (1..=5).map(hash).collect();
+        assert!(!distinct.is_mutated());
+
+        // Identical leaves are only mutations if they are siblings
+        let not_siblings: MerkleTree<_> = vec![hash(1), hash(2), hash(2), hash(3)]
+            .into_iter()
+            .collect();
+        assert!(!not_siblings.is_mutated());
+
+        // Duplicate subtrees have identical nodes on a higher level
+        let duplicate_subtree: MerkleTree<_> = vec![hash(1), hash(2), hash(1), hash(2)]
+            .into_iter()
+            .collect();
+        assert!(duplicate_subtree.is_mutated());
+    }
+
+    #[test]
+    fn invalid_proofs_are_rejected() {
+        let merkle_tree: MerkleTree<_> = (1..=5).map(hash).collect();
+        let root = merkle_tree.root();
+
+        assert_eq!(merkle_tree.prove(hash(6)), None);
+
+        let proof = merkle_tree.prove(hash(2)).expect("the leaf is in the tree");
+        assert!(proof.verify(root));
+        assert!(!proof.verify(MerkleTreeRootHash([0; 32])));
+
+        let mut wrong_txid = proof.clone();
+        wrong_txid.txid = hash(6);
+        assert!(!wrong_txid.verify(root));
+
+        let mut wrong_index = proof.clone();
+        wrong_index.index = 0;
+        assert!(!wrong_index.verify(root));
+
+        let mut out_of_range = proof.clone();
+        out_of_range.index += 1 << proof.branch.len();
+        assert!(!out_of_range.verify(root));
+
+        let mut wrong_branch = proof;
+        wrong_branch.branch[1] = [0; 32];
+        assert!(!wrong_branch.verify(root));
+    }
+}
@@ -0,0 +1,156 @@
+//! Consensus parameters for each Zcash network.
+//!
+//! The network upgrade activation heights are the values from the Zcash
+//! specification and `zcashd`'s `chainparams.cpp`.
+
+use crate::{block::BlockHeaderHash, types::BlockHeight, Network};
+
+#[cfg(test)]
+mod tests;
+
+/// A Zcash network upgrade.
+///
+/// Network upgrades can change the Zcash network protocol or consensus rules in
+/// incompatible ways.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum NetworkUpgrade {
+    /// The Zcash protocol before the Overwinter upgrade.
+    ///
+    /// We avoid using `Sprout`, because the specification says that Sprout
+    /// is the name of the pre-Sapling protocol, before and after Overwinter.
+    BeforeOverwinter,
+    /// The Zcash protocol after the Overwinter upgrade.
+    Overwinter,
+    /// The Zcash protocol after the Sapling upgrade.
+    Sapling,
+    /// The Zcash protocol after the Blossom upgrade.
+    Blossom,
+    /// The Zcash protocol after the Heartwood upgrade.
+    Heartwood,
+    /// The Zcash protocol after the Canopy upgrade.
+    Canopy,
+}
+
+/// Mainnet network upgrade activation heights, in ascending order.
+const MAINNET_ACTIVATION_HEIGHTS: &[(BlockHeight, NetworkUpgrade)] = &[
+    (BlockHeight(0), NetworkUpgrade::BeforeOverwinter),
+    (BlockHeight(347_500), NetworkUpgrade::Overwinter),
+    (BlockHeight(419_200), NetworkUpgrade::Sapling),
+    (BlockHeight(653_600), NetworkUpgrade::Blossom),
+    (BlockHeight(903_000), NetworkUpgrade::Heartwood),
+    (BlockHeight(1_046_400), NetworkUpgrade::Canopy),
+];
+
+/// Testnet network upgrade activation heights, in ascending order.
+const TESTNET_ACTIVATION_HEIGHTS: &[(BlockHeight, NetworkUpgrade)] = &[
+    (BlockHeight(0), NetworkUpgrade::BeforeOverwinter),
+    (BlockHeight(207_500), NetworkUpgrade::Overwinter),
+    (BlockHeight(280_000), NetworkUpgrade::Sapling),
+    (BlockHeight(584_000), NetworkUpgrade::Blossom),
+    (BlockHeight(903_800), NetworkUpgrade::Heartwood),
+    (BlockHeight(1_028_500), NetworkUpgrade::Canopy),
+];
+
+/// A consensus branch id, which identifies the consensus rules of a network
+/// upgrade.
+///
+/// Signature hashes commit to the branch id of the network upgrade that the
+/// transaction was signed for, so transactions can't be replayed across
+/// network upgrades.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct ConsensusBranchId(pub u32);
+
+/// The `previous_block_hash` field of the genesis block.
+///
+/// The genesis block is the only block without a parent, so it uses the
+/// all-zeroes hash instead.
+pub const GENESIS_PREVIOUS_BLOCK_HASH: BlockHeaderHash = BlockHeaderHash([0; 32]);
+
+/// The mainnet genesis block hash, in internal byte order.
+///
+/// `00040fe8ec8471911baa1db1266ea15dd06b4a8a5c453883c000b031973dce08` in
+/// display order.
+const MAINNET_GENESIS_HASH: BlockHeaderHash = BlockHeaderHash([
+    8, 206, 61, 151, 49, 176, 0, 192, 131, 56, 69, 92, 138, 74, 107, 208, 93, 161, 110, 38, 177,
+    29, 170, 27, 145, 113, 132, 236, 232, 15, 4, 0,
+]);
+
+/// The testnet genesis block hash, in internal byte order.
+///
+/// `05a60a92d99d85997cce3b87616c089f6124d7342af37106edc76126334a2c38` in
+/// display order.
+const TESTNET_GENESIS_HASH: BlockHeaderHash = BlockHeaderHash([
+    56, 44, 74, 51, 38, 97, 199, 237, 6, 113, 243, 42, 52, 215, 36, 97, 159, 8, 108, 97, 135, 59,
+    206, 124, 153, 133, 157, 217, 146, 10, 166, 5,
+]);
+
+/// Returns the hash of the genesis block on `network`.
+pub fn genesis_hash(network: Network) -> BlockHeaderHash {
+    match network {
+        Network::Mainnet => MAINNET_GENESIS_HASH,
+        Network::Testnet => TESTNET_GENESIS_HASH,
+    }
+}
+
+/// The target block spacing before Blossom, in seconds.
+pub const PRE_BLOSSOM_POW_TARGET_SPACING: i64 = 150;
+
+/// The target block spacing after Blossom, in seconds.
+pub const POST_BLOSSOM_POW_TARGET_SPACING: i64 = 75;
+
+impl NetworkUpgrade {
+    /// Returns the network upgrade activation heights for `network`, in
+    /// ascending height order.
+    pub fn activation_list(network: Network) -> &'static [(BlockHeight, NetworkUpgrade)] {
+        match network {
+            Network::Mainnet => MAINNET_ACTIVATION_HEIGHTS,
+            Network::Testnet => TESTNET_ACTIVATION_HEIGHTS,
+        }
+    }
+
+    /// Returns the network upgrade that is active at `height` on `network`.
+    pub fn current(network: Network, height: BlockHeight) -> NetworkUpgrade {
+        NetworkUpgrade::activation_list(network)
+            .iter()
+            .rev()
+            .find(|(activation_height, _)| *activation_height <= height)
+            .map(|(_, upgrade)| *upgrade)
+            .expect("every height has a current network upgrade, because BeforeOverwinter activates at 0")
+    }
+
+    /// Returns the activation height of this network upgrade on `network`.
+    pub fn activation_height(&self, network: Network) -> BlockHeight {
+        NetworkUpgrade::activation_list(network)
+            .iter()
+            .find(|(_, upgrade)| upgrade == self)
+            .map(|(height, _)| *height)
+            .expect("every network upgrade has an activation height on every network")
+    }
+
+    /// Returns the consensus branch id of this network upgrade.
+    ///
+    /// The branch id is the same on every network. Before Overwinter, the
+    /// branch id is zero, like the Sprout branch id in `zcashd`.
+    pub fn branch_id(&self) -> ConsensusBranchId {
+        match self {
+            NetworkUpgrade::BeforeOverwinter => ConsensusBranchId(0),
+            NetworkUpgrade::Overwinter => ConsensusBranchId(0x5ba8_1b19),
+            NetworkUpgrade::Sapling => ConsensusBranchId(0x76b8_09bb),
+            NetworkUpgrade::Blossom => ConsensusBranchId(0x2bb4_0e60),
+            NetworkUpgrade::Heartwood => ConsensusBranchId(0xf5b9_230b),
+            NetworkUpgrade::Canopy => ConsensusBranchId(0xe9ff_75a6),
+        }
+    }
+
+    /// Returns the target block spacing, in seconds, for the network upgrade
+    /// that is active at `height` on `network`.
+    ///
+    /// `PoWTargetSpacing(height)` in the Zcash specification.
+    pub fn target_spacing_for_height(network: Network, height: BlockHeight) -> i64 {
+        if NetworkUpgrade::current(network, height) >= NetworkUpgrade::Blossom {
+            POST_BLOSSOM_POW_TARGET_SPACING
+        } else {
+            PRE_BLOSSOM_POW_TARGET_SPACING
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use super::*;
+
+use NetworkUpgrade::*;
+
+#[test]
+fn activation_list_is_ordered() {
+    for network in &[Network::Mainnet, Network::Testnet] {
+        let list = NetworkUpgrade::activation_list(*network);
+        assert_eq!(list.first(), Some(&(BlockHeight(0), BeforeOverwinter)));
+        for pair in list.windows(2) {
+            assert!(pair[0].0 < pair[1].0);
+            assert!(pair[0].1 < pair[1].1);
+        }
+    }
+}
+
+#[test]
+fn current_network_upgrade() {
+    assert_eq!(
+        NetworkUpgrade::current(Network::Mainnet, BlockHeight(0)),
+        BeforeOverwinter
+    );
+    assert_eq!(
+        NetworkUpgrade::current(Network::Mainnet, BlockHeight(419_199)),
+        Overwinter
+    );
+    assert_eq!(
+        NetworkUpgrade::current(Network::Mainnet, BlockHeight(419_200)),
+        Sapling
+    );
+    assert_eq!(
+        NetworkUpgrade::current(Network::Testnet, BlockHeight(584_000)),
+        Blossom
+    );
+    assert_eq!(
+        Blossom.activation_height(Network::Mainnet),
+        BlockHeight(653_600)
+    );
+}
+
+#[test]
+fn target_spacing() {
+    assert_eq!(
+        NetworkUpgrade::target_spacing_for_height(Network::Mainnet, BlockHeight(653_599)),
+        PRE_BLOSSOM_POW_TARGET_SPACING
+    );
+    assert_eq!(
+        NetworkUpgrade::target_spacing_for_height(Network::Mainnet, BlockHeight(653_600)),
+        POST_BLOSSOM_POW_TARGET_SPACING
+    );
+    assert_eq!(
+        NetworkUpgrade::target_spacing_for_height(Network::Testnet, BlockHeight(583_999)),
+        PRE_BLOSSOM_POW_TARGET_SPACING
+    );
+}
+
+#[test]
+fn genesis_previous_block_hash() {
+    use crate::{block::Block, serialization::ZcashDeserialize};
+
+    let genesis = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])
+        .expect("genesis block test vector should deserialize");
+    assert_eq!(
+        genesis.header.previous_block_hash,
+        GENESIS_PREVIOUS_BLOCK_HASH
+    );
+
+    let block = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])
+        .expect("block test vector should deserialize");
+    assert_ne!(
+        block.header.previous_block_hash,
+        GENESIS_PREVIOUS_BLOCK_HASH
+    );
+}
+
+#[test]
+fn genesis_hashes() {
+    use crate::{block::Block, serialization::ZcashDeserialize};
+
+    let genesis = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])
+        .expect("genesis block test vector should deserialize");
+    let hash: BlockHeaderHash = (&genesis).into();
+
+    assert_eq!(hash, genesis_hash(Network::Mainnet));
+    assert_ne!(hash, genesis_hash(Network::Testnet));
+    assert_eq!(
+        genesis_hash(Network::Testnet),
+        "382c4a332661c7ed0671f32a34d724619f086c61873bce7c99859dd9920aa605"
+            .parse()
+            .expect("hash hex should parse")
+    );
+}
+
+#[test]
+fn branch_ids_are_unique() {
+    let upgrades = NetworkUpgrade::activation_list(Network::Mainnet);
+    for (i, (_, upgrade)) in upgrades.iter().enumerate() {
+        for (_, other) in &upgrades[i + 1..] {
+            assert_ne!(upgrade.branch_id(), other.branch_id());
+        }
+    }
+
+    assert_eq!(BeforeOverwinter.branch_id(), ConsensusBranchId(0));
+    assert_eq!(Sapling.branch_id(), ConsensusBranchId(0x76b8_09bb));
+}
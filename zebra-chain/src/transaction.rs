@@ -6,6 +6,7 @@ mod hash;
 mod joinsplit;
 mod serialize;
 mod shielded_data;
+mod sighash;
 mod transparent;
 
 #[cfg(test)]
@@ -14,6 +15,7 @@ mod tests;
 pub use hash::TransactionHash;
 pub use joinsplit::{JoinSplit, JoinSplitData};
 pub use shielded_data::{Output, ShieldedData, Spend};
+pub use sighash::{HashType, SigHash};
 pub use transparent::{CoinbaseData, OutPoint, TransparentInput, TransparentOutput};
 
 use crate::proofs::{Bctv14Proof, Groth16Proof};
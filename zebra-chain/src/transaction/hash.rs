@@ -16,12 +16,12 @@ use super::Transaction;
 ///
 /// TODO: I'm pretty sure this is also a SHA256d hash but I haven't
 /// confirmed it yet.
-#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[cfg_attr(test, derive(Arbitrary))]
 pub struct TransactionHash(pub [u8; 32]);
 
-impl From<Transaction> for TransactionHash {
-    fn from(transaction: Transaction) -> Self {
+impl<'a> From<&'a Transaction> for TransactionHash {
+    fn from(transaction: &'a Transaction) -> Self {
         let mut hash_writer = Sha256dWriter::default();
         transaction
             .zcash_serialize(&mut hash_writer)
@@ -30,6 +30,12 @@ impl From<Transaction> for TransactionHash {
     }
 }
 
+impl From<Transaction> for TransactionHash {
+    fn from(transaction: Transaction) -> Self {
+        (&transaction).into()
+    }
+}
+
 impl fmt::Debug for TransactionHash {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_tuple("TransactionHash")
@@ -17,8 +17,8 @@ use crate::types::Script;
 
 use super::*;
 
-const OVERWINTER_VERSION_GROUP_ID: u32 = 0x03C4_8270;
-const SAPLING_VERSION_GROUP_ID: u32 = 0x892F_2085;
+pub(super) const OVERWINTER_VERSION_GROUP_ID: u32 = 0x03C4_8270;
+pub(super) const SAPLING_VERSION_GROUP_ID: u32 = 0x892F_2085;
 
 const GENESIS_COINBASE_DATA: [u8; 77] = [
     4, 255, 255, 7, 31, 1, 4, 69, 90, 99, 97, 115, 104, 48, 98, 57, 99, 52, 101, 101, 102, 56, 98,
@@ -356,7 +356,8 @@ impl ZcashDeserialize for Output {
         Ok(Output {
             cv: reader.read_32_bytes()?,
             cmu: reader.read_32_bytes()?,
-            ephemeral_key: jubjub::AffinePoint::from_bytes(reader.read_32_bytes()?).unwrap(),
+            ephemeral_key: Option::from(jubjub::AffinePoint::from_bytes(reader.read_32_bytes()?))
+                .ok_or(SerializationError::Parse("invalid ephemeral key"))?,
             enc_ciphertext: notes::sapling::EncryptedCiphertext::zcash_deserialize(&mut reader)?,
             out_ciphertext: notes::sapling::OutCiphertext::zcash_deserialize(&mut reader)?,
             zkproof: Groth16Proof::zcash_deserialize(&mut reader)?,
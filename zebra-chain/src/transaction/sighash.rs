@@ -0,0 +1,565 @@
+//! Signature hashes, the digests that transparent and Sapling signatures sign.
+//!
+//! The signature hash algorithm depends on the transaction version:
+//!   * V1 and V2 transactions use the legacy algorithm inherited from Bitcoin,
+//!     which is a double-SHA256 of a modified copy of the transaction,
+//!   * V3 transactions use the [ZIP-143] algorithm, and
+//!   * V4 transactions use the [ZIP-243] algorithm.
+//!
+//! ZIP-143 and ZIP-243 signature hashes commit to the consensus branch id of
+//! the network upgrade, so signatures can't be replayed across upgrades.
+//!
+//! [ZIP-143]: https://zips.z.cash/zip-0143
+//! [ZIP-243]: https://zips.z.cash/zip-0243
+
+use std::{
+    fmt,
+    io::{self, Write},
+    ops::BitOr,
+};
+
+use blake2b_simd::{Params, State};
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use crate::{
+    parameters::ConsensusBranchId,
+    proofs::ZkSnarkProof,
+    serialization::{WriteZcashExt, ZcashSerialize},
+    sha256d_writer::Sha256dWriter,
+    types::{
+        amount::{Amount, NonNegative},
+        Script,
+    },
+};
+
+use super::{
+    serialize::{OVERWINTER_VERSION_GROUP_ID, SAPLING_VERSION_GROUP_ID},
+    JoinSplitData, OutPoint, ShieldedData, Transaction, TransactionHash, TransparentInput,
+    TransparentOutput,
+};
+
+const ZCASH_SIGHASH_PERSONALIZATION_PREFIX: &[u8; 12] = b"ZcashSigHash";
+const ZCASH_PREVOUTS_HASH_PERSONALIZATION: &[u8; 16] = b"ZcashPrevoutHash";
+const ZCASH_SEQUENCE_HASH_PERSONALIZATION: &[u8; 16] = b"ZcashSequencHash";
+const ZCASH_OUTPUTS_HASH_PERSONALIZATION: &[u8; 16] = b"ZcashOutputsHash";
+const ZCASH_JOINSPLITS_HASH_PERSONALIZATION: &[u8; 16] = b"ZcashJSplitsHash";
+const ZCASH_SHIELDED_SPENDS_HASH_PERSONALIZATION: &[u8; 16] = b"ZcashSSpendsHash";
+const ZCASH_SHIELDED_OUTPUTS_HASH_PERSONALIZATION: &[u8; 16] = b"ZcashSOutputHash";
+
+/// The legacy signature hash of invalid `SINGLE` signatures, and signatures
+/// for inputs that don't exist.
+///
+/// Bitcoin signs this value, rather than rejecting the signature, so we have
+/// to do the same.
+const LEGACY_ONE: [u8; 32] = [
+    1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+/// The `OP_CODESEPARATOR` opcode, which legacy signature hashes remove from
+/// the script code.
+const OP_CODESEPARATOR: u8 = 0xab;
+
+/// The hash type of a signature, which selects the parts of the transaction
+/// that the signature commits to.
+///
+/// Transparent signatures end with a hash type byte, which can have any
+/// value. Only the low 5 bits and the `ANYONECANPAY` bit select the signed
+/// parts of the transaction, but the whole value is included in the hash.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct HashType(pub u32);
+
+impl HashType {
+    /// Sign all the inputs and outputs.
+    pub const ALL: HashType = HashType(1);
+    /// Sign all the inputs, and none of the outputs.
+    pub const NONE: HashType = HashType(2);
+    /// Sign all the inputs, and the output with the same index as the signed
+    /// input.
+    pub const SINGLE: HashType = HashType(3);
+    /// Only sign the signed input, rather than all the inputs.
+    ///
+    /// Combined with one of the other hash types, e.g.
+    /// `HashType::ALL | HashType::ANYONECANPAY`.
+    pub const ANYONECANPAY: HashType = HashType(0x80);
+
+    /// Returns the hash type, without the `ANYONECANPAY` bit.
+    fn base(self) -> u32 {
+        self.0 & 0x1f
+    }
+
+    /// Returns true if the hash type has the `ANYONECANPAY` bit.
+    fn anyone_can_pay(self) -> bool {
+        self.0 & HashType::ANYONECANPAY.0 != 0
+    }
+}
+
+impl BitOr for HashType {
+    type Output = HashType;
+
+    fn bitor(self, rhs: HashType) -> HashType {
+        HashType(self.0 | rhs.0)
+    }
+}
+
+/// A signature hash, the digest that a transparent, JoinSplit, or Sapling
+/// signature signs.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct SigHash(pub [u8; 32]);
+
+impl AsRef<[u8]> for SigHash {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[..]
+    }
+}
+
+impl fmt::Debug for SigHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("SigHash")
+            .field(&hex::encode(&self.0))
+            .finish()
+    }
+}
+
+impl Transaction {
+    /// Returns the signature hash of this transaction, for a signature with
+    /// `hash_type`, under the network upgrade with consensus `branch_id`.
+    ///
+    /// Transparent signatures sign an `input`, which is the index of the
+    /// signed input, the script code that the signature is checked against,
+    /// and the value of the output that the input spends. JoinSplit and
+    /// Sapling signatures sign the whole transaction, and pass `None`.
+    ///
+    /// V1 and V2 transactions use the legacy algorithm, which doesn't commit
+    /// to `branch_id` or the spent value. If the input doesn't exist, or a
+    /// `SINGLE` signature has no matching output, the legacy signature hash
+    /// is the value one.
+    ///
+    /// Returns `None` if the `input` index is out of range for a V3 or V4
+    /// transaction.
+    pub fn sighash(
+        &self,
+        branch_id: ConsensusBranchId,
+        hash_type: HashType,
+        input: Option<(usize, &Script, Amount<NonNegative>)>,
+    ) -> Option<SigHash> {
+        let sighasher = SigHasher {
+            transaction: self,
+            hash_type,
+            input,
+        };
+
+        let sighash = match self {
+            Transaction::V1 { .. } | Transaction::V2 { .. } => sighasher.legacy(),
+            Transaction::V3 { .. } | Transaction::V4 { .. }
+                if sighasher
+                    .input_index()
+                    .map_or(false, |index| index >= self.inputs().count()) =>
+            {
+                return None
+            }
+            Transaction::V3 { .. } | Transaction::V4 { .. } => {
+                sighasher.zip143_or_zip243(branch_id)
+            }
+        };
+        Some(sighash.expect("hash writers are infallible"))
+    }
+}
+
+/// The parameters of a signature hash.
+struct SigHasher<'a> {
+    transaction: &'a Transaction,
+    hash_type: HashType,
+    input: Option<(usize, &'a Script, Amount<NonNegative>)>,
+}
+
+impl<'a> SigHasher<'a> {
+    /// Returns the index of the signed input, if any.
+    fn input_index(&self) -> Option<usize> {
+        self.input.map(|(index, _, _)| index)
+    }
+
+    /// Returns the legacy signature hash, for V1 and V2 transactions.
+    fn legacy(&self) -> Result<SigHash, io::Error> {
+        let inputs: Vec<_> = self.transaction.inputs().collect();
+        let outputs: Vec<_> = self.transaction.outputs().collect();
+        let input_index = self.input_index();
+        let base = self.hash_type.base();
+
+        if input_index.map_or(false, |index| index >= inputs.len())
+            || (base == HashType::SINGLE.0
+                && input_index.map_or(true, |index| index >= outputs.len()))
+        {
+            return Ok(SigHash(LEGACY_ONE));
+        }
+
+        let mut writer = Sha256dWriter::default();
+
+        let version = match self.transaction {
+            Transaction::V1 { .. } => 1,
+            _ => 2,
+        };
+        writer.write_u32::<LittleEndian>(version)?;
+
+        // Inputs: only the signed input has a script, and other inputs don't
+        // sign their sequence numbers unless all the outputs are signed.
+        let signed_inputs: Vec<usize> = if self.hash_type.anyone_can_pay() {
+            input_index.into_iter().collect()
+        } else {
+            (0..inputs.len()).collect()
+        };
+        writer.write_compactsize(signed_inputs.len() as u64)?;
+        for index in signed_inputs {
+            outpoint(inputs[index]).zcash_serialize(&mut writer)?;
+            match self.input {
+                Some((input_index, script_code, _)) if input_index == index => {
+                    let script_code = without_code_separators(script_code);
+                    writer.write_compactsize(script_code.len() as u64)?;
+                    writer.write_all(&script_code)?;
+                }
+                _ => writer.write_compactsize(0)?,
+            }
+            if Some(index) != input_index
+                && (base == HashType::NONE.0 || base == HashType::SINGLE.0)
+            {
+                writer.write_u32::<LittleEndian>(0)?;
+            } else {
+                writer.write_u32::<LittleEndian>(sequence(inputs[index]))?;
+            }
+        }
+
+        // Outputs: `SINGLE` signs the output with the same index as the
+        // signed input, and replaces the earlier outputs with null outputs.
+        match input_index {
+            _ if base == HashType::NONE.0 => writer.write_compactsize(0)?,
+            Some(index) if base == HashType::SINGLE.0 => {
+                writer.write_compactsize(index as u64 + 1)?;
+                for _ in 0..index {
+                    writer.write_i64::<LittleEndian>(-1)?;
+                    writer.write_compactsize(0)?;
+                }
+                outputs[index].zcash_serialize(&mut writer)?;
+            }
+            _ => {
+                writer.write_compactsize(outputs.len() as u64)?;
+                for output in outputs {
+                    output.zcash_serialize(&mut writer)?;
+                }
+            }
+        }
+
+        self.transaction.lock_time().zcash_serialize(&mut writer)?;
+
+        // JoinSplits: the JoinSplit signature signs this hash, so it is
+        // replaced with zeroes.
+        if let Transaction::V2 { joinsplit_data, .. } = self.transaction {
+            match joinsplit_data {
+                None => writer.write_compactsize(0)?,
+                Some(joinsplit_data) => {
+                    writer.write_compactsize(joinsplit_data.joinsplits().count() as u64)?;
+                    for joinsplit in joinsplit_data.joinsplits() {
+                        joinsplit.zcash_serialize(&mut writer)?;
+                    }
+                    writer.write_all(&<[u8; 32]>::from(joinsplit_data.pub_key)[..])?;
+                    writer.write_all(&[0; 64][..])?;
+                }
+            }
+        }
+
+        writer.write_u32::<LittleEndian>(self.hash_type.0)?;
+
+        Ok(SigHash(writer.finish()))
+    }
+
+    /// Returns the ZIP-143 signature hash for V3 transactions, or the ZIP-243
+    /// signature hash for V4 transactions.
+    ///
+    /// The signed input must be in range.
+    fn zip143_or_zip243(&self, branch_id: ConsensusBranchId) -> Result<SigHash, io::Error> {
+        let mut personal = [0; 16];
+        personal[..12].copy_from_slice(ZCASH_SIGHASH_PERSONALIZATION_PREFIX);
+        personal[12..].copy_from_slice(&branch_id.0.to_le_bytes());
+        let mut writer = blake2b_writer(&personal);
+
+        match self.transaction {
+            Transaction::V3 { .. } => {
+                writer.write_u32::<LittleEndian>(3 | (1 << 31))?;
+                writer.write_u32::<LittleEndian>(OVERWINTER_VERSION_GROUP_ID)?;
+            }
+            _ => {
+                writer.write_u32::<LittleEndian>(4 | (1 << 31))?;
+                writer.write_u32::<LittleEndian>(SAPLING_VERSION_GROUP_ID)?;
+            }
+        }
+
+        writer.write_all(&self.hash_prevouts()?)?;
+        writer.write_all(&self.hash_sequence()?)?;
+        writer.write_all(&self.hash_outputs()?)?;
+
+        match self.transaction {
+            Transaction::V3 { joinsplit_data, .. } => {
+                writer.write_all(&hash_joinsplits(joinsplit_data)?)?;
+            }
+            Transaction::V4 {
+                joinsplit_data,
+                shielded_data,
+                ..
+            } => {
+                writer.write_all(&hash_joinsplits(joinsplit_data)?)?;
+                writer.write_all(&hash_shielded_spends(shielded_data)?)?;
+                writer.write_all(&hash_shielded_outputs(shielded_data)?)?;
+            }
+            _ => unreachable!("only V3 and V4 transactions use ZIP-143 or ZIP-243"),
+        }
+
+        self.transaction.lock_time().zcash_serialize(&mut writer)?;
+        let expiry_height = self
+            .transaction
+            .expiry_height()
+            .expect("V3 and V4 transactions have an expiry height");
+        writer.write_u32::<LittleEndian>(expiry_height.0)?;
+
+        if let Transaction::V4 { value_balance, .. } = self.transaction {
+            writer.write_i64::<LittleEndian>((*value_balance).into())?;
+        }
+
+        writer.write_u32::<LittleEndian>(self.hash_type.0)?;
+
+        if let Some((index, script_code, value)) = self.input {
+            let input = self
+                .transaction
+                .inputs()
+                .nth(index)
+                .expect("the signed input index is in range");
+            outpoint(input).zcash_serialize(&mut writer)?;
+            script_code.zcash_serialize(&mut writer)?;
+            writer.write_u64::<LittleEndian>(value.into())?;
+            writer.write_u32::<LittleEndian>(sequence(input))?;
+        }
+
+        Ok(SigHash(finish(writer)))
+    }
+
+    /// Returns `hashPrevouts`, which commits to the outpoints of all the
+    /// inputs, unless the signature is `ANYONECANPAY`.
+    fn hash_prevouts(&self) -> Result<[u8; 32], io::Error> {
+        if self.hash_type.anyone_can_pay() {
+            return Ok([0; 32]);
+        }
+
+        let mut writer = blake2b_writer(ZCASH_PREVOUTS_HASH_PERSONALIZATION);
+        for input in self.transaction.inputs() {
+            outpoint(input).zcash_serialize(&mut writer)?;
+        }
+        Ok(finish(writer))
+    }
+
+    /// Returns `hashSequence`, which commits to the sequence numbers of all
+    /// the inputs, if the signature is `ALL` without `ANYONECANPAY`.
+    fn hash_sequence(&self) -> Result<[u8; 32], io::Error> {
+        let base = self.hash_type.base();
+        if self.hash_type.anyone_can_pay() || base == HashType::NONE.0 || base == HashType::SINGLE.0
+        {
+            return Ok([0; 32]);
+        }
+
+        let mut writer = blake2b_writer(ZCASH_SEQUENCE_HASH_PERSONALIZATION);
+        for input in self.transaction.inputs() {
+            writer.write_u32::<LittleEndian>(sequence(input))?;
+        }
+        Ok(finish(writer))
+    }
+
+    /// Returns `hashOutputs`, which commits to all the outputs, or the output
+    /// with the same index as a `SINGLE` signature's input.
+    ///
+    /// `NONE` signatures, and `SINGLE` signatures without a matching output,
+    /// don't commit to any outputs. But `ALL` signatures hash the outputs even
+    /// if there aren't any.
+    fn hash_outputs(&self) -> Result<[u8; 32], io::Error> {
+        let base = self.hash_type.base();
+        let outputs: Vec<&TransparentOutput> = if base == HashType::NONE.0 {
+            return Ok([0; 32]);
+        } else if base == HashType::SINGLE.0 {
+            match self
+                .input_index()
+                .and_then(|index| self.transaction.outputs().nth(index))
+            {
+                Some(output) => vec![output],
+                None => return Ok([0; 32]),
+            }
+        } else {
+            self.transaction.outputs().collect()
+        };
+
+        let mut writer = blake2b_writer(ZCASH_OUTPUTS_HASH_PERSONALIZATION);
+        for output in outputs {
+            output.zcash_serialize(&mut writer)?;
+        }
+        Ok(finish(writer))
+    }
+}
+
+/// Returns `hashJoinSplits`, which commits to the JoinSplits and the
+/// JoinSplit public key, but not the JoinSplit signature.
+fn hash_joinsplits<P: ZkSnarkProof>(
+    joinsplit_data: &Option<JoinSplitData<P>>,
+) -> Result<[u8; 32], io::Error> {
+    let joinsplit_data = match joinsplit_data {
+        Some(joinsplit_data) => joinsplit_data,
+        None => return Ok([0; 32]),
+    };
+
+    let mut writer = blake2b_writer(ZCASH_JOINSPLITS_HASH_PERSONALIZATION);
+    for joinsplit in joinsplit_data.joinsplits() {
+        joinsplit.zcash_serialize(&mut writer)?;
+    }
+    writer.write_all(&<[u8; 32]>::from(joinsplit_data.pub_key)[..])?;
+    Ok(finish(writer))
+}
+
+/// Returns `hashShieldedSpends`, which commits to the Sapling spends, but
+/// not their spend authorization signatures.
+fn hash_shielded_spends(shielded_data: &Option<ShieldedData>) -> Result<[u8; 32], io::Error> {
+    let spends: Vec<_> = shielded_data
+        .iter()
+        .flat_map(|shielded_data| shielded_data.spends())
+        .collect();
+    if spends.is_empty() {
+        return Ok([0; 32]);
+    }
+
+    let mut writer = blake2b_writer(ZCASH_SHIELDED_SPENDS_HASH_PERSONALIZATION);
+    for spend in spends {
+        writer.write_all(&spend.cv[..])?;
+        writer.write_all(&spend.anchor.0[..])?;
+        spend.nullifier.zcash_serialize(&mut writer)?;
+        writer.write_all(&<[u8; 32]>::from(spend.rk)[..])?;
+        spend.zkproof.zcash_serialize(&mut writer)?;
+    }
+    Ok(finish(writer))
+}
+
+/// Returns `hashShieldedOutputs`, which commits to the Sapling outputs.
+fn hash_shielded_outputs(shielded_data: &Option<ShieldedData>) -> Result<[u8; 32], io::Error> {
+    let outputs: Vec<_> = shielded_data
+        .iter()
+        .flat_map(|shielded_data| shielded_data.outputs())
+        .collect();
+    if outputs.is_empty() {
+        return Ok([0; 32]);
+    }
+
+    let mut writer = blake2b_writer(ZCASH_SHIELDED_OUTPUTS_HASH_PERSONALIZATION);
+    for output in outputs {
+        output.zcash_serialize(&mut writer)?;
+    }
+    Ok(finish(writer))
+}
+
+/// Returns a BLAKE2b-256 writer with `personal`ization.
+fn blake2b_writer(personal: &[u8]) -> State {
+    Params::new().hash_length(32).personal(personal).to_state()
+}
+
+/// Returns the hash of `writer`.
+fn finish(writer: State) -> [u8; 32] {
+    let mut hash = [0; 32];
+    hash.copy_from_slice(writer.finalize().as_bytes());
+    hash
+}
+
+/// Returns the outpoint that `input` spends.
+///
+/// Coinbase inputs are serialized with a null outpoint.
+fn outpoint(input: &TransparentInput) -> OutPoint {
+    match input {
+        TransparentInput::PrevOut { outpoint, .. } => *outpoint,
+        TransparentInput::Coinbase { .. } => OutPoint {
+            hash: TransactionHash([0; 32]),
+            index: 0xffff_ffff,
+        },
+    }
+}
+
+/// Returns the sequence number of `input`.
+fn sequence(input: &TransparentInput) -> u32 {
+    match input {
+        TransparentInput::PrevOut { sequence, .. } => *sequence,
+        TransparentInput::Coinbase { sequence, .. } => *sequence,
+    }
+}
+
+/// Returns `script_code`, without any `OP_CODESEPARATOR`s.
+///
+/// Pushed data is copied as-is, even if it contains `OP_CODESEPARATOR` bytes.
+fn without_code_separators(script_code: &Script) -> Vec<u8> {
+    let script = &script_code.0[..];
+    let mut stripped = Vec::with_capacity(script.len());
+
+    let mut position = 0;
+    while position < script.len() {
+        let opcode = script[position];
+        let data_len = match opcode {
+            0x01..=0x4b => usize::from(opcode),
+            // OP_PUSHDATA1, OP_PUSHDATA2 and OP_PUSHDATA4
+            0x4c..=0x4e => {
+                let size_len = 1 << (opcode - 0x4c);
+                let mut size = [0; 4];
+                let size_bytes = script.get(position + 1..position + 1 + size_len);
+                match size_bytes {
+                    Some(size_bytes) => size[..size_len].copy_from_slice(size_bytes),
+                    // Truncated push sizes are copied as-is
+                    None => size = [0xff; 4],
+                }
+                size_len.saturating_add(u32::from_le_bytes(size) as usize)
+            }
+            _ => 0,
+        };
+
+        let end = script
+            .len()
+            .min(position.saturating_add(1).saturating_add(data_len));
+        if opcode != OP_CODESEPARATOR {
+            stripped.extend_from_slice(&script[position..end]);
+        }
+        position = end;
+    }
+
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_types() {
+        assert_eq!(HashType::SINGLE | HashType::ANYONECANPAY, HashType(0x83));
+        assert_eq!((HashType::NONE | HashType::ANYONECANPAY).base(), 2);
+        assert!(HashType(0x81).anyone_can_pay());
+        assert!(!HashType(0x41).anyone_can_pay());
+    }
+
+    #[test]
+    fn code_separators() {
+        for &(script, expected) in &[
+            ("ab", ""),
+            ("51ab52abab", "5152"),
+            // A push of one OP_CODESEPARATOR byte
+            ("01ab", "01ab"),
+            ("4c02abababac", "4c02ababac"),
+            ("4d0100ab", "4d0100ab"),
+            // Truncated pushes
+            ("05abab", "05abab"),
+            ("4e01", "4e01"),
+        ] {
+            let script = Script(hex::decode(script).unwrap());
+            assert_eq!(
+                hex::encode(without_code_separators(&script)),
+                expected,
+                "{:?}",
+                script
+            );
+        }
+    }
+}
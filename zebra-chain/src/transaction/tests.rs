@@ -1,8 +1,13 @@
+use std::convert::TryInto;
+
 use proptest::{arbitrary::any, collection::vec, option, prelude::*};
+use secp256k1::{Message, PublicKey, Secp256k1, Signature};
+use zebra_test::vectors::SigHashVector;
 
 use crate::{
+    parameters::{ConsensusBranchId, NetworkUpgrade},
     serialization::{ZcashDeserialize, ZcashDeserializeInto, ZcashSerialize},
-    types::LockTime,
+    types::{amount::NonNegative, LockTime, Script},
 };
 
 use super::*;
@@ -115,6 +120,17 @@ proptest! {
 
         prop_assert_eq![tx, tx2];
     }
+
+    #[test]
+    fn sighash_commits_to_branch_id(tx in any::<Transaction>()) {
+        let overwinter = tx.sighash(NetworkUpgrade::Overwinter.branch_id(), HashType::ALL, None);
+        let sapling = tx.sighash(NetworkUpgrade::Sapling.branch_id(), HashType::ALL, None);
+
+        match tx {
+            Transaction::V1 { .. } | Transaction::V2 { .. } => prop_assert_eq![overwinter, sapling],
+            Transaction::V3 { .. } | Transaction::V4 { .. } => prop_assert_ne![overwinter, sapling],
+        }
+    }
 }
 
 #[test]
@@ -167,3 +183,362 @@ fn zip243_deserialize_and_round_trip() {
 
     assert_eq!(&zebra_test::vectors::ZIP243_3[..], &data3[..]);
 }
+
+/// Checks the signature hashes of the ZIP-143 or ZIP-243 test `vectors`.
+fn check_sighash_vectors(branch_id: ConsensusBranchId, vectors: &[SigHashVector]) {
+    for (i, vector) in vectors.iter().enumerate() {
+        let tx = Transaction::zcash_deserialize(&vector.tx[..])
+            .expect("signature hash test vector should deserialize");
+        let mut data = Vec::new();
+        tx.zcash_serialize(&mut data)
+            .expect("signature hash test vector should serialize");
+        assert_eq!(data, vector.tx);
+
+        let script_code = Script(vector.script_code.clone());
+        let amount = vector.amount.try_into().unwrap();
+        let input = vector.input.map(|index| (index, &script_code, amount));
+        assert_eq!(
+            tx.sighash(branch_id, HashType(vector.hash_type), input)
+                .unwrap()
+                .0,
+            vector.sighash,
+            "unexpected signature hash for test vector {}",
+            i + 1
+        );
+    }
+}
+
+#[test]
+fn zip143_sighash_vectors() {
+    check_sighash_vectors(
+        NetworkUpgrade::Overwinter.branch_id(),
+        &zebra_test::vectors::ZIP143_SIGHASHES,
+    );
+}
+
+#[test]
+fn zip243_sighash_vectors() {
+    check_sighash_vectors(
+        NetworkUpgrade::Sapling.branch_id(),
+        &zebra_test::vectors::ZIP243_SIGHASHES,
+    );
+}
+
+// Extra signature hash cases, for the hash types that the test vectors don't
+// cover.
+//
+// These hashes were computed with the Python reference implementation of
+// ZIP-143 and ZIP-243, on the first two test vector transactions. The
+// reference implementation reproduces the hashes of the full test vector
+// sets.
+
+/// The hash types for ZIP-143 and ZIP-243 signatures, in the order of the
+/// expected hashes in `check_sighashes`.
+const HASH_TYPES: [HashType; 6] = [
+    HashType::ALL,
+    HashType::NONE,
+    HashType::SINGLE,
+    HashType(0x81),
+    HashType(0x82),
+    HashType(0x83),
+];
+
+/// Checks the signature hashes of `tx` for each of `HASH_TYPES` against
+/// `expected`.
+fn check_sighashes(
+    tx: &Transaction,
+    branch_id: ConsensusBranchId,
+    input: Option<(usize, &Script, Amount<NonNegative>)>,
+    expected: [&str; 6],
+) {
+    for (&hash_type, &expected) in HASH_TYPES.iter().zip(expected.iter()) {
+        assert_eq!(
+            hex::encode(tx.sighash(branch_id, hash_type, input).unwrap()),
+            expected,
+            "unexpected signature hash for hash type {:?}",
+            hash_type
+        );
+    }
+}
+
+#[test]
+fn zip143_sighash() {
+    let branch_id = NetworkUpgrade::Overwinter.branch_id();
+
+    let tx1 = Transaction::zcash_deserialize(&zebra_test::vectors::ZIP143_1[..])
+        .expect("transaction test vector from ZIP143 should deserialize");
+    check_sighashes(
+        &tx1,
+        branch_id,
+        None,
+        [
+            "a1f1a4e5cd9bd522322d661edd2af1bf2a7019cfab94ece18f4ba935b0a19073",
+            "0eba7738938f5035368c1539eb64904fd9e2415ccfffebadd4a2922e68f39b1d",
+            "5425d9271415562db048390e07cd85e10c25efcc7e405691426bcb8ea0f623a9",
+            "97d25a66799acfbf27df35c0048d67d901329958c79fbdc748a72dea10ccd587",
+            "6876133faa33b4c62a0fd2a94c32b902296ed17b34b70c4aa39b3f998b2a03d3",
+            "f3c938263ae96591c7a82ad418fcfae4e763bef42a0bf406cd4462a4b8c90463",
+        ],
+    );
+
+    let mut tx2 = Transaction::zcash_deserialize(&zebra_test::vectors::ZIP143_2[..])
+        .expect("transaction test vector from ZIP143 should deserialize");
+    let script_code = Script(vec![0x53]);
+    let value = 365_293_780_364_847u64.try_into().unwrap();
+    check_sighashes(
+        &tx2,
+        branch_id,
+        Some((1, &script_code, value)),
+        [
+            "eba3e003e925f9344b7eecdbf64944a3909b5f38296775f2a096a351dd06e83d",
+            "a7734333dee1e925fd4cb834e1b8ccda4f7d1c496d83492908dbe89355f46751",
+            "23652e76cb13b85a0e3363bb5fca061fa791c40c533eccee899364e6e60bb4f7",
+            "fb8b5ad832b7d79d19d31a8ef9a873947ff7576427a810c2b3f23dd9d99bcfe7",
+            "27d4aaf32e0b6673763216d0c8e815ca1577fbbc86cded74b87ec8c868724913",
+            "488fe448fa2033a74c9c8b64ef4de32c67b523375d5d2535f12194cddbd7509e",
+        ],
+    );
+
+    // Inputs that don't exist have no signature hash
+    assert_eq!(
+        tx2.sighash(branch_id, HashType::ALL, Some((2, &script_code, value))),
+        None
+    );
+
+    // `SINGLE` signatures for inputs past the last output don't sign any
+    // outputs
+    if let Transaction::V3 {
+        ref mut outputs, ..
+    } = tx2
+    {
+        outputs.truncate(1);
+    }
+    assert_eq!(
+        hex::encode(
+            tx2.sighash(branch_id, HashType::SINGLE, Some((1, &script_code, value)))
+                .unwrap()
+        ),
+        "884d510e724ba65bcca1545c2e261551234e43d4b099a9bae3ebc6fa613dbce4"
+    );
+    assert_eq!(
+        hex::encode(
+            tx2.sighash(branch_id, HashType(0x83), Some((1, &script_code, value)))
+                .unwrap()
+        ),
+        "0e7dff36bd15091af4390b67b3f8c1e8b999e22d45e0233954f93c1d55c491d2"
+    );
+}
+
+#[test]
+fn zip243_sighash() {
+    let branch_id = NetworkUpgrade::Sapling.branch_id();
+
+    let tx1 = Transaction::zcash_deserialize(&zebra_test::vectors::ZIP243_1[..])
+        .expect("transaction test vector from ZIP243 should deserialize");
+    check_sighashes(
+        &tx1,
+        branch_id,
+        None,
+        [
+            "63d18534de5f2d1c9e169b73f9c783718adbef5c8a7d55b5e7a37affa1dd3ff3",
+            "0e405039a335e1abeb01db9418bbebcec216f0e58684f7321b38373adb8db715",
+            "250167a3b8dd034e222735d30507fb90aacfc35354e9bed428e0d9505c464670",
+            "cde354b311228a0072df08a6be1158531b0bf135381808557be028063609b2e2",
+            "ce7157cc3eaa926adadf1ced5957a6c77ca7334db6a08dbebebb2784790a2ae1",
+            "f1ad82356eb34ea22a15cffb1bd342df329a4937c9cf657bce5a81f9bb25657a",
+        ],
+    );
+
+    let mut tx2 = Transaction::zcash_deserialize(&zebra_test::vectors::ZIP243_2[..])
+        .expect("transaction test vector from ZIP243 should deserialize");
+    let script_code = Script(Vec::new());
+    let value = 652_655_344_020_909u64.try_into().unwrap();
+    check_sighashes(
+        &tx2,
+        branch_id,
+        Some((1, &script_code, value)),
+        [
+            "b9fd8dc3077c250aaf24cefcfde2c7777bdebbc065eb1e16eb7c4c1d2c360246",
+            "bbe6d84f57c56b29b914c694baaccb891297e961de3eb46c68e3c89c47b1a1db",
+            "ac339673d02a310ff5dbb49751fe01c554342a88681c044c000eeed27794fbc2",
+            "38f65416c17283119e49b305e752bc4283e811509ca80c5d1695df825e6e2224",
+            "170b288abbd8709f7e3958b85920742a3c66af16ab9b2a8c58258ed5f9a5e5f2",
+            "ec2f7f28e2a6fd77f76b3d3ec8ed2f73bf60592d734ba26ffdb5e54c04398700",
+        ],
+    );
+
+    // Inputs that don't exist have no signature hash
+    assert_eq!(
+        tx2.sighash(branch_id, HashType::ALL, Some((2, &script_code, value))),
+        None
+    );
+
+    // `SINGLE` signatures for inputs past the last output don't sign any
+    // outputs
+    if let Transaction::V4 {
+        ref mut outputs, ..
+    } = tx2
+    {
+        outputs.truncate(1);
+    }
+    assert_eq!(
+        hex::encode(
+            tx2.sighash(branch_id, HashType::SINGLE, Some((1, &script_code, value)))
+                .unwrap()
+        ),
+        "0810de9a9bae6ed292de270be151f6285c6c5737884c267947586d2065248f87"
+    );
+    assert_eq!(
+        hex::encode(
+            tx2.sighash(branch_id, HashType(0x83), Some((1, &script_code, value)))
+                .unwrap()
+        ),
+        "3ea9298ec19d4bb41be50abc7145012d53c96ec540a6ad4afaa5e64cd875bd6c"
+    );
+
+    // ZIP-243's test vector 3 is a mainnet transaction, which isn't in the
+    // generated test vector set
+    let tx3 = Transaction::zcash_deserialize(&zebra_test::vectors::ZIP243_3[..])
+        .expect("transaction test vector from ZIP243 should deserialize");
+    let script_code =
+        Script(hex::decode("76a914507173527b4c3318a2aecd793bf1cfed705950cf88ac").unwrap());
+    let value = 50_000_000u64.try_into().unwrap();
+    let sighash = tx3
+        .sighash(branch_id, HashType::ALL, Some((0, &script_code, value)))
+        .unwrap();
+    assert_eq!(
+        hex::encode(sighash),
+        "f3148f80dfab5e573d5edfe7a850f5fd39234f80b5429d3a57edcc11e34c585b"
+    );
+    check_sighashes(
+        &tx3,
+        branch_id,
+        Some((0, &script_code, value)),
+        [
+            "f3148f80dfab5e573d5edfe7a850f5fd39234f80b5429d3a57edcc11e34c585b",
+            "2f0a186e474c4c2f768c2f55529523a1f3d09d182740cdf0d21bb6752028d70d",
+            "0b2eb07bf9bf193ac2ca24a4e6874db4f40dbc04364d25d1b278646708cd9325",
+            "310c0c94e32f685d7656edf030bef15e443119cd0ea341dbe5bde9ffb342df67",
+            "5ccea8d6d22d2b81d16e05e8f04b226bf60326c025e9f8b4e32ce8736cb5a5ad",
+            "b1eb4b6da75563258c36706aa276f760b5c215910f681d467791d3b17ad20f2c",
+        ],
+    );
+
+    // As a mainnet transaction, its signature verifies against the signature
+    // hash.
+    assert!(verify_p2pkh_signature(&tx3, 0, sighash));
+}
+
+/// Bitcoin transaction `f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16`,
+/// which is also a valid V1 Zcash transaction.
+///
+/// It spends a pay-to-public-key output, with a signature using the legacy
+/// algorithm.
+const LEGACY_TX: &str = "0100000001c997a5e56e104102fa209c6a852dd90660a20b2d9c352423edce25857fcd3704000000004847304402204e45e16932b8af514961a1d3a1a25fdf3f4f7732e9d624c6c61548ab5fb8cd410220181522ec8eca07de4860a4acdd12909d831cc56cbbac4622082221a8768d1d0901ffffffff0200ca9a3b00000000434104ae1a62fe09c5f51b13905f07f06b99a2f7159b2225f374cd378d71302fa28414e7aab37397f554a7df5f142c21c1b7303b8a0626f1baded5c72a704f7e6cd84cac00286bee0000000043410411db93e1dcdb8a016b49840f8c53bc1eb68a382e97b1482ecad7b148a6909a5cb2e0eaddfb84ccf9744464f82e160bfa9b8b64f9d4c03f999b8643f656b412a3ac00000000";
+
+/// The public key of the output spent by `LEGACY_TX`.
+const LEGACY_PUBLIC_KEY: &str = "0411db93e1dcdb8a016b49840f8c53bc1eb68a382e97b1482ecad7b148a6909a5cb2e0eaddfb84ccf9744464f82e160bfa9b8b64f9d4c03f999b8643f656b412a3";
+
+#[test]
+fn legacy_sighash() {
+    // Legacy signature hashes don't commit to the branch id or the value
+    let branch_id = NetworkUpgrade::BeforeOverwinter.branch_id();
+    let value = 0u64.try_into().unwrap();
+
+    let tx: Transaction = hex::decode(LEGACY_TX)
+        .unwrap()
+        .zcash_deserialize_into()
+        .expect("legacy transaction should deserialize");
+    let public_key = hex::decode(LEGACY_PUBLIC_KEY).unwrap();
+
+    // The spent pay-to-public-key script
+    let mut script_code = vec![0x41];
+    script_code.extend(&public_key);
+    script_code.push(0xac);
+    let script_code = Script(script_code);
+
+    let sighash = tx
+        .sighash(branch_id, HashType::ALL, Some((0, &script_code, value)))
+        .unwrap();
+    assert_eq!(
+        hex::encode(sighash),
+        "7a05c6145f10101e9d6325494245adf1297d80f8f38d4d576d57cdba220bcb19"
+    );
+
+    let signature = match tx.inputs().next() {
+        Some(TransparentInput::PrevOut { script, .. }) => &script.0[1..script.0.len() - 1],
+        _ => panic!("legacy transaction should spend a previous output"),
+    };
+    assert!(verify_signature(signature, &public_key, sighash));
+
+    // `OP_CODESEPARATOR`s are removed from the script code
+    let mut separated_script_code = script_code.clone();
+    separated_script_code.0.insert(0, 0xab);
+    assert_eq!(
+        tx.sighash(
+            branch_id,
+            HashType::ALL,
+            Some((0, &separated_script_code, value))
+        ),
+        Some(sighash)
+    );
+
+    // Other hash types sign different parts of the transaction
+    for &hash_type in &[
+        HashType::NONE,
+        HashType::SINGLE,
+        HashType::ALL | HashType::ANYONECANPAY,
+        HashType(0x41),
+    ] {
+        assert_ne!(
+            tx.sighash(branch_id, hash_type, Some((0, &script_code, value))),
+            Some(sighash)
+        );
+    }
+
+    // Signatures for missing inputs, and `SINGLE` signatures without a
+    // matching output, sign the value one
+    let mut one = [0; 32];
+    one[0] = 1;
+    assert_eq!(
+        tx.sighash(branch_id, HashType::ALL, Some((1, &script_code, value))),
+        Some(SigHash(one))
+    );
+    let tx = Transaction::V1 {
+        inputs: tx.inputs().cloned().collect(),
+        outputs: Vec::new(),
+        lock_time: tx.lock_time(),
+    };
+    assert_eq!(
+        tx.sighash(branch_id, HashType::SINGLE, Some((0, &script_code, value))),
+        Some(SigHash(one))
+    );
+}
+
+/// Returns true if the pay-to-public-key-hash signature of input `index` in
+/// `tx` verifies against `sighash`.
+fn verify_p2pkh_signature(tx: &Transaction, index: usize, sighash: SigHash) -> bool {
+    let script_sig = match tx.inputs().nth(index) {
+        Some(TransparentInput::PrevOut { script, .. }) => &script.0,
+        _ => return false,
+    };
+
+    // The script sig pushes the signature, with a hash type byte, then the
+    // public key.
+    let signature_len = usize::from(script_sig[0]);
+    let signature = &script_sig[1..signature_len];
+    let public_key = &script_sig[signature_len + 2..];
+
+    verify_signature(signature, public_key, sighash)
+}
+
+/// Returns true if the DER-encoded `signature`, without a hash type byte,
+/// verifies against `sighash` and `public_key`.
+fn verify_signature(signature: &[u8], public_key: &[u8], sighash: SigHash) -> bool {
+    let secp = Secp256k1::verification_only();
+    let message = Message::from_slice(&sighash.0).unwrap();
+    let signature = Signature::from_der(signature).unwrap();
+    let public_key = PublicKey::from_slice(public_key).unwrap();
+
+    secp.verify(&message, &signature, &public_key).is_ok()
+}
@@ -3,8 +3,9 @@
 //! `verify::BlockVerifier` verifies blocks and their transactions, then adds them to
 //! `zebra_state::ZebraState`.
 //!
-//! `mempool::MempoolTransactionVerifier` verifies transactions, and adds them to
-//! `mempool::ZebraMempoolState`.
+//! `mempool::init` returns a verifier for transactions that aren't in blocks
+//! yet. Zebra doesn't store a mempool yet, so verified transactions are
+//! returned to the caller.
 //!
 //! Consensus handling is provided using `tower::Service`s, to support backpressure
 //! and batch verification.
@@ -10,6 +10,29 @@
 //! The mempool is provided via a `tower::Service`, to support backpressure and batch
 //! verification.
 
+use futures_util::FutureExt;
+use std::{
+    collections::HashMap,
+    error,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use thiserror::Error;
+use tower::{buffer::Buffer, Service, ServiceExt};
+
+use zebra_chain::{
+    parameters::NetworkUpgrade,
+    transaction::{Transaction, TransactionHash},
+    types::BlockHeight,
+    Network,
+};
+
+use zebra_script::Flags;
+
+use crate::verify::{chain::next_height, script::verify_transparent_inputs};
+
 /// Mempool state.
 ///
 /// New transactions are verified, checked against the chain state, then added to the
@@ -19,11 +42,380 @@
 #[derive(Default)]
 struct ZebraMempoolState {}
 
+/// An error from a mempool transaction check.
+///
+/// Verifier errors are boxed, so callers can use
+/// `downcast_ref::<MempoolError>()` to tell these errors apart from state
+/// failures.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum MempoolError {
+    /// Coinbase transactions are only valid in blocks.
+    #[error("coinbase transactions can only be mined in blocks")]
+    Coinbase,
+
+    /// The transaction doesn't have any transparent inputs, JoinSplits, or
+    /// Sapling spends.
+    #[error("transaction has no inputs")]
+    NoInputs,
+
+    /// The transaction doesn't have any transparent outputs, JoinSplits, or
+    /// Sapling outputs.
+    #[error("transaction has no outputs")]
+    NoOutputs,
+
+    /// The transaction version is not valid in the network upgrade of the
+    /// next block.
+    #[error("transaction version {version} is not valid in the {upgrade:?} network upgrade")]
+    WrongVersion {
+        /// The version of the transaction.
+        version: u32,
+        /// The network upgrade of the next block.
+        upgrade: NetworkUpgrade,
+    },
+
+    /// The transaction can't be mined in the next block, because it has
+    /// expired.
+    #[error("transaction expired at {expiry_height:?}, but the next block is at {height:?}")]
+    Expired {
+        /// The expiry height of the transaction.
+        expiry_height: BlockHeight,
+        /// The height of the next block.
+        height: BlockHeight,
+    },
+
+    /// The transaction is already in a block in the state.
+    #[error("transaction {hash:?} is already in the chain")]
+    AlreadyInChain {
+        /// The hash of the transaction.
+        hash: TransactionHash,
+    },
+}
+
+/// Check that `transaction` is not a coinbase transaction.
+pub(crate) fn coinbase_check(transaction: &Transaction) -> Result<(), MempoolError> {
+    if transaction.contains_coinbase_input() {
+        Err(MempoolError::Coinbase)
+    } else {
+        Ok(())
+    }
+}
+
+/// Check that `transaction` has at least one input, and at least one output.
+///
+/// JoinSplits count as inputs and outputs. Sapling spends count as inputs,
+/// and Sapling outputs count as outputs.[S 7.1][7.1]
+///
+/// [7.1]: https://zips.z.cash/protocol/protocol.pdf#txnencodingandconsensus
+pub(crate) fn inputs_and_outputs_check(transaction: &Transaction) -> Result<(), MempoolError> {
+    let (joinsplits, spends, shielded_outputs) = match transaction {
+        Transaction::V1 { .. } => (0, 0, 0),
+        Transaction::V2 { joinsplit_data, .. } | Transaction::V3 { joinsplit_data, .. } => (
+            joinsplit_data
+                .as_ref()
+                .map_or(0, |data| data.joinsplits().count()),
+            0,
+            0,
+        ),
+        Transaction::V4 {
+            joinsplit_data,
+            shielded_data,
+            ..
+        } => (
+            joinsplit_data
+                .as_ref()
+                .map_or(0, |data| data.joinsplits().count()),
+            shielded_data
+                .as_ref()
+                .map_or(0, |data| data.spends().count()),
+            shielded_data
+                .as_ref()
+                .map_or(0, |data| data.outputs().count()),
+        ),
+    };
+
+    if transaction.inputs().count() + joinsplits + spends == 0 {
+        Err(MempoolError::NoInputs)
+    } else if transaction.outputs().count() + joinsplits + shielded_outputs == 0 {
+        Err(MempoolError::NoOutputs)
+    } else {
+        Ok(())
+    }
+}
+
+/// Check that the version of `transaction` is valid in `upgrade`.
+///
+/// Version 1 and 2 transactions are only valid before Overwinter, version 3
+/// transactions are only valid in Overwinter, and version 4 transactions are
+/// valid from Sapling onwards.
+pub(crate) fn version_check(
+    transaction: &Transaction,
+    upgrade: NetworkUpgrade,
+) -> Result<(), MempoolError> {
+    let version = match transaction {
+        Transaction::V1 { .. } => 1,
+        Transaction::V2 { .. } => 2,
+        Transaction::V3 { .. } => 3,
+        Transaction::V4 { .. } => 4,
+    };
+    let valid = match upgrade {
+        NetworkUpgrade::BeforeOverwinter => version <= 2,
+        NetworkUpgrade::Overwinter => version == 3,
+        _ => version == 4,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(MempoolError::WrongVersion { version, upgrade })
+    }
+}
+
+/// Check that `transaction` can be mined in a block at `height`.
+///
+/// An expiry height of zero means that the transaction never expires.
+pub(crate) fn expiry_check(
+    transaction: &Transaction,
+    height: BlockHeight,
+) -> Result<(), MempoolError> {
+    match transaction.expiry_height() {
+        Some(expiry_height) if expiry_height.0 != 0 && height > expiry_height => {
+            Err(MempoolError::Expired {
+                expiry_height,
+                height,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Mempool transaction verification.
 ///
 /// New transactions are verified, checked against the chain state, then added to the
 /// mempool.
 ///
-/// `MempoolTransactionVerifier` is not yet implemented.
-#[derive(Default)]
-struct MempoolTransactionVerifier {}
+/// Zebra doesn't have a mempool state yet, so verified transactions are only
+/// returned to the caller.
+struct MempoolTransactionVerifier<S> {
+    /// The network that the transactions are for.
+    network: Network,
+    /// The underlying `ZebraState`, possibly wrapped in other services.
+    state_service: S,
+}
+
+/// The error type for the MempoolTransactionVerifier Service.
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+impl<S> Service<Arc<Transaction>> for MempoolTransactionVerifier<S>
+where
+    S: Service<zebra_state::RequestBlock, Response = zebra_state::Response, Error = Error>
+        + Send
+        + Clone
+        + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = TransactionHash;
+    type Error = Error;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Each transaction uses its own clone of the state service
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, transaction: Arc<Transaction>) -> Self::Future {
+        let network = self.network;
+        let mut state_service = self.state_service.clone();
+
+        async move {
+            let hash = TransactionHash::from(transaction.as_ref());
+
+            // Since errors cause an early exit, try to do the
+            // quick checks first.
+            coinbase_check(&transaction)?;
+            inputs_and_outputs_check(&transaction)?;
+
+            // Like zcashd, check for mined transactions before checking
+            // whether they are still valid
+            match state_service
+                .ready_and()
+                .await?
+                .call(zebra_state::RequestBlock::GetTransaction { hash })
+                .await?
+            {
+                zebra_state::Response::Transaction(None) => {}
+                zebra_state::Response::Transaction(Some(_)) => {
+                    return Err(MempoolError::AlreadyInChain { hash }.into())
+                }
+                _ => return Err("unexpected response to a transaction request".into()),
+            }
+
+            let height = match state_service
+                .ready_and()
+                .await?
+                .call(zebra_state::RequestBlock::GetTip)
+                .await?
+            {
+                zebra_state::Response::Tip { height, .. } => next_height(height),
+                _ => return Err("unexpected response to a tip request".into()),
+            };
+
+            let upgrade = NetworkUpgrade::current(network, height);
+            version_check(&transaction, upgrade)?;
+            expiry_check(&transaction, height)?;
+
+            // Like zcashd, mempool transactions must follow the standard
+            // script rules, as well as the consensus rules
+            verify_transparent_inputs(
+                &mut state_service,
+                &transaction,
+                &HashMap::new(),
+                upgrade.branch_id(),
+                Flags::STANDARD,
+            )
+            .await?;
+
+            Ok(hash)
+        }
+        .boxed()
+    }
+}
+
+/// Return a mempool transaction verification service for `network`, using
+/// the provided block state service.
+///
+/// The verifier checks transactions against the state's best chain tip, and
+/// rejects transactions that are already in the state. Transparent inputs
+/// must spend outputs in the state, because there is no mempool state yet.
+/// It returns the hash of each valid transaction.
+///
+/// The returned type is opaque to allow instrumentation or other wrappers, but
+/// can be boxed for storage. It is also `Clone` to allow sharing of a
+/// verification service.
+pub fn init<S>(
+    network: Network,
+    state_service: S,
+) -> impl Service<
+    Arc<Transaction>,
+    Response = TransactionHash,
+    Error = Error,
+    Future = impl Future<Output = Result<TransactionHash, Error>>,
+> + Send
+  + Clone
+  + 'static
+where
+    S: Service<zebra_state::RequestBlock, Response = zebra_state::Response, Error = Error>
+        + Send
+        + Clone
+        + 'static,
+    S::Future: Send + 'static,
+{
+    Buffer::new(
+        MempoolTransactionVerifier {
+            network,
+            state_service,
+        },
+        1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use color_eyre::eyre::{eyre, Report};
+
+    use zebra_chain::{block::Block, serialization::ZcashDeserialize};
+
+    /// Returns block 434873, which has non-coinbase transactions.
+    fn block_434873() -> Arc<Block> {
+        Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_434873_BYTES[..])
+            .expect("block should deserialize")
+    }
+
+    #[test]
+    fn transaction_checks() {
+        zebra_test::init();
+
+        let block = block_434873();
+        let coinbase = &block.transactions[0];
+        let transaction = block.transactions.last().unwrap();
+
+        assert_eq!(coinbase_check(coinbase), Err(MempoolError::Coinbase));
+        coinbase_check(transaction).expect("non-coinbase transactions should be valid");
+        inputs_and_outputs_check(transaction)
+            .expect("mainnet transactions should have inputs and outputs");
+
+        // Block 434873 is in the Sapling network upgrade
+        version_check(transaction, NetworkUpgrade::Sapling)
+            .expect("Sapling transactions should be valid in Sapling");
+        version_check(transaction, NetworkUpgrade::Blossom)
+            .expect("Sapling transactions should be valid after Sapling");
+        version_check(transaction, NetworkUpgrade::Overwinter)
+            .expect_err("Sapling transactions should be invalid in Overwinter");
+        version_check(transaction, NetworkUpgrade::BeforeOverwinter)
+            .expect_err("Sapling transactions should be invalid before Overwinter");
+
+        let mut transaction = transaction.as_ref().clone();
+        let expiry_height = BlockHeight(434_883);
+        match &mut transaction {
+            Transaction::V4 {
+                expiry_height: height,
+                ..
+            } => *height = expiry_height,
+            _ => panic!("block 434873 should contain Sapling transactions"),
+        }
+        expiry_check(&transaction, expiry_height)
+            .expect("transactions should be valid at their expiry height");
+        assert_eq!(
+            expiry_check(&transaction, next_height(expiry_height)),
+            Err(MempoolError::Expired {
+                expiry_height,
+                height: next_height(expiry_height),
+            })
+        );
+
+        // Transactions with an expiry height of zero never expire
+        match &mut transaction {
+            Transaction::V4 { expiry_height, .. } => *expiry_height = BlockHeight(0),
+            _ => unreachable!("the transaction version doesn't change"),
+        }
+        expiry_check(&transaction, BlockHeight(u32::MAX))
+            .expect("transactions without an expiry height should never expire");
+    }
+
+    #[tokio::test]
+    async fn already_in_chain() -> Result<(), Report> {
+        zebra_test::init();
+
+        let block = block_434873();
+        let mut state_service = zebra_state::in_memory::init();
+        state_service
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(zebra_state::RequestBlock::AddBlock {
+                block: block.clone(),
+            })
+            .await
+            .map_err(|e| eyre!(e))?;
+
+        let mut verifier = init(Network::Mainnet, state_service);
+        let transaction = block.transactions.last().unwrap().clone();
+        let hash = TransactionHash::from(transaction.as_ref());
+
+        let error = verifier
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(transaction)
+            .await
+            .expect_err("transactions in the state should be rejected");
+        assert_eq!(
+            error.downcast_ref::<MempoolError>(),
+            Some(&MempoolError::AlreadyInChain { hash })
+        );
+
+        Ok(())
+    }
+}
@@ -4,9 +4,11 @@
 //! verification.
 
 pub mod block;
+pub mod chain;
+pub mod difficulty;
 pub mod header;
 pub mod redjubjub;
-mod script;
+pub mod script;
 mod transaction;
 
 // pub use block::init as block_init;
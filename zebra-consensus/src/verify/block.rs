@@ -11,6 +11,7 @@
 use chrono::{DateTime, Duration, Utc};
 use futures_util::FutureExt;
 use std::{
+    collections::HashMap,
     error,
     future::Future,
     pin::Pin,
@@ -21,7 +22,18 @@ use tower::{buffer::Buffer, Service, ServiceExt};
 
 use zebra_chain::{
     block::{Block, BlockHeaderHash},
+    merkle_tree::MerkleTree,
+    parameters::{genesis_hash, NetworkUpgrade, GENESIS_PREVIOUS_BLOCK_HASH},
+    transaction::TransactionHash,
     types::BlockHeight,
+    Network,
+};
+use zebra_script::Flags;
+
+use super::{
+    chain::{median_time_past_check, next_height, ChainError},
+    difficulty::POW_MEDIAN_BLOCK_SPAN,
+    script::verify_transparent_inputs,
 };
 
 // use zebra_state::QueryType;
@@ -73,7 +85,106 @@ pub(crate) fn coinbase_check(block: &Block) -> Result<(), Error> {
     }
 }
 
+/// Check that the block header's `merkle_root_hash` is the root of the
+/// Merkle tree of the block's transactions, and that the tree isn't mutated.
+///
+/// Otherwise, the transactions could be replaced without changing the block
+/// header hash. A mutated tree has the same root as the tree without some of
+/// its duplicate transactions (CVE-2012-2459), so a peer could get an invalid
+/// block stored under a valid block's hash.
+pub(crate) fn merkle_root_check(block: &Block) -> Result<(), Error> {
+    let merkle_tree = MerkleTree::from(block);
+    let merkle_root = merkle_tree.root();
+
+    if merkle_root != block.header.merkle_root_hash {
+        Err(format!(
+            "block transactions have Merkle root {:?}, but the block header has {:?}",
+            merkle_root, block.header.merkle_root_hash
+        ))?
+    }
+
+    if merkle_tree.is_mutated() {
+        Err("block transactions have a mutated Merkle tree, with duplicate transactions")?
+    }
+
+    Ok(())
+}
+
+/// Returns the height of the parent of a block, and the times of up to
+/// `POW_MEDIAN_BLOCK_SPAN` of its ancestors, by walking back from
+/// `previous_block_hash` through `state_service`.
+///
+/// The times are in reverse height order, starting with the parent.
+///
+/// Returns `ChainError::Orphan` if the parent is not in the state.
+async fn ancestor_times<S>(
+    state_service: &mut S,
+    hash: BlockHeaderHash,
+    previous_block_hash: BlockHeaderHash,
+) -> Result<(BlockHeight, Vec<DateTime<Utc>>), Error>
+where
+    S: Service<zebra_state::RequestBlock, Response = zebra_state::Response, Error = Error>,
+{
+    // Missing blocks are state errors, so check for the parent first, to
+    // distinguish orphans from other state failures
+    match state_service
+        .ready_and()
+        .await?
+        .call(zebra_state::RequestBlock::GetDepth {
+            hash: previous_block_hash,
+        })
+        .await?
+    {
+        zebra_state::Response::Depth(Some(_)) => {}
+        zebra_state::Response::Depth(None) => {
+            return Err(ChainError::Orphan {
+                hash,
+                parent_hash: previous_block_hash,
+            }
+            .into())
+        }
+        _ => return Err("unexpected response to a parent block request".into()),
+    }
+
+    let parent_height = match state_service
+        .ready_and()
+        .await?
+        .call(zebra_state::RequestBlock::GetBlockHeight {
+            hash: previous_block_hash,
+        })
+        .await?
+    {
+        zebra_state::Response::BlockHeight { block_height } => block_height,
+        _ => return Err("unexpected response to a parent block height request".into()),
+    };
+
+    let ancestor_count = std::cmp::min(parent_height.0 as usize + 1, POW_MEDIAN_BLOCK_SPAN);
+    let mut times = Vec::with_capacity(ancestor_count);
+    let mut ancestor_hash = previous_block_hash;
+
+    while times.len() < ancestor_count {
+        let ancestor = match state_service
+            .ready_and()
+            .await?
+            .call(zebra_state::RequestBlock::GetBlock {
+                query: zebra_state::QueryType::ByHash(ancestor_hash),
+            })
+            .await?
+        {
+            zebra_state::Response::Block { block } => block,
+            _ => return Err("unexpected response to an ancestor block request".into()),
+        };
+
+        times.push(ancestor.header.time);
+        ancestor_hash = ancestor.header.previous_block_hash;
+    }
+
+    Ok((parent_height, times))
+}
+
 struct BlockVerifier<S> {
+    /// The network of the verified chain.
+    network: Network,
     /// The underlying `ZebraState`, possibly wrapped in other services.
     state_service: S,
 }
@@ -106,14 +217,11 @@ where
 
     fn call(&mut self, block: Arc<Block>) -> Self::Future {
         // TODO(jlusby): Error = Report, handle errors from state_service.
-        // TODO(teor):
-        //   - handle chain reorgs
-        //   - adjust state_service "unique block height" conditions
+        let network = self.network;
         let mut state_service = self.state_service.clone();
 
         let hash: BlockHeaderHash = block.as_ref().into();
         let hash_str = hex::encode(&hash.0);
-        let height = block.coinbase_height().unwrap();
 
         async move {
             // Since errors cause an early exit, try to do the
@@ -123,6 +231,59 @@ where
             node_time_check(block.header.time, now)?;
             block.header.is_equihash_solution_valid()?;
             coinbase_check(block.as_ref())?;
+            merkle_root_check(block.as_ref())?;
+
+            let height = block
+                .coinbase_height()
+                .expect("coinbase_check ensures the block has a coinbase height");
+
+            // The genesis block doesn't have a parent, so its height is 0.
+            // Other block heights must follow on from their parent's height.
+            let (expected_height, times) =
+                if block.header.previous_block_hash == GENESIS_PREVIOUS_BLOCK_HASH {
+                    if hash != genesis_hash(network) {
+                        return Err(ChainError::WrongGenesis { hash, network }.into());
+                    }
+                    (BlockHeight(0), Vec::new())
+                } else {
+                    let (parent_height, times) = ancestor_times(
+                        &mut state_service,
+                        hash,
+                        block.header.previous_block_hash,
+                    )
+                    .await?;
+                    (next_height(parent_height), times)
+                };
+
+            if height != expected_height {
+                return Err(ChainError::HeightMismatch {
+                    hash,
+                    coinbase_height: height,
+                    expected_height,
+                }
+                .into());
+            }
+
+            median_time_past_check(hash, height, block.header.time, times)?;
+
+            // Transparent inputs can spend the outputs of earlier
+            // transactions in the same block
+            let branch_id = NetworkUpgrade::current(network, height).branch_id();
+            let mut block_transactions = HashMap::new();
+            for transaction in &block.transactions {
+                verify_transparent_inputs(
+                    &mut state_service,
+                    transaction,
+                    &block_transactions,
+                    branch_id,
+                    Flags::CONSENSUS,
+                )
+                .await?;
+                block_transactions.insert(
+                    TransactionHash::from(transaction.as_ref()),
+                    transaction.clone(),
+                );
+            }
 
             // `Tower::Buffer` requires a 1:1 relationship between `poll()`s
             // and `call()`s, because it reserves a buffer slot in each
@@ -143,7 +304,8 @@ where
     }
 }
 
-/// Return a block verification service, using the provided state service.
+/// Return a block verification service for `network`, using the provided
+/// state service.
 ///
 /// The block verifier holds a state service of type `S`, used as context for
 /// block validation and to which newly verified blocks will be committed. This
@@ -157,6 +319,7 @@ where
 /// the result be shared) rather than constructing multiple verification services
 /// backed by the same state layer.
 pub fn init<S>(
+    network: Network,
     state_service: S,
 ) -> impl Service<
     Arc<Block>,
@@ -173,7 +336,13 @@ where
         + 'static,
     S::Future: Send + 'static,
 {
-    Buffer::new(BlockVerifier { state_service }, 1)
+    Buffer::new(
+        BlockVerifier {
+            network,
+            state_service,
+        },
+        1,
+    )
 }
 
 #[cfg(test)]
@@ -209,6 +378,52 @@ mod tests {
             .expect("the header time from a mainnet block should be valid");
     }
 
+    #[test]
+    fn merkle_root_check_test_vectors() {
+        zebra_test::init();
+
+        for block_bytes in zebra_test::vectors::TEST_BLOCKS.iter() {
+            let mut block = Block::zcash_deserialize(&block_bytes[..])
+                .expect("block test vector should deserialize");
+            merkle_root_check(&block).expect("the Merkle root of a mainnet block should be valid");
+
+            let last_transaction = block.transactions.last().unwrap().clone();
+            block.transactions.push(last_transaction);
+            merkle_root_check(&block)
+                .expect_err("blocks with extra transactions should be rejected");
+        }
+    }
+
+    #[test]
+    fn merkle_root_check_rejects_mutated_trees() {
+        zebra_test::init();
+
+        // Make a block with an odd number of distinct transactions
+        let mut block = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])
+            .expect("block test vector should deserialize");
+        for block_bytes in &[
+            &zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..],
+            &zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..],
+        ] {
+            let other_block = Block::zcash_deserialize(*block_bytes)
+                .expect("block test vector should deserialize");
+            block.transactions.extend(other_block.transactions);
+        }
+        assert_eq!(block.transactions.len(), 3);
+        block.header.merkle_root_hash = MerkleTree::from(&block).root();
+        merkle_root_check(&block).expect("the Merkle root should be valid");
+
+        // A single copy of the last transaction doesn't change the root, but
+        // it mutates the tree
+        let last_transaction = block.transactions.last().unwrap().clone();
+        block.transactions.push(last_transaction);
+        assert_eq!(
+            MerkleTree::from(&block).root(),
+            block.header.merkle_root_hash
+        );
+        merkle_root_check(&block).expect_err("blocks with mutated Merkle trees should be rejected");
+    }
+
     #[test]
     fn time_check_now() {
         // These checks are deteministic, because all the times are offset
@@ -332,11 +547,11 @@ mod tests {
         zebra_test::init();
 
         let block =
-            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])?;
+            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?;
         let hash: BlockHeaderHash = block.as_ref().into();
 
         let state_service = zebra_state::in_memory::init();
-        let mut block_verifier = super::init(state_service.clone());
+        let mut block_verifier = super::init(Network::Mainnet, state_service.clone());
 
         /// SPANDOC: Make sure the verifier service is ready
         let ready_verifier_service = block_verifier.ready_and().await.map_err(|e| eyre!(e))?;
@@ -346,7 +561,7 @@ mod tests {
             .await
             .map_err(|e| eyre!(e))?;
 
-        assert_eq!(verify_response, hash);
+        assert_eq!(verify_response, (hash, BlockHeight(0)));
 
         Ok(())
     }
@@ -361,11 +576,11 @@ mod tests {
         zebra_test::init();
 
         let block =
-            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])?;
+            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?;
         let hash: BlockHeaderHash = block.as_ref().into();
 
-        let state_service = zebra_state::in_memory::init();
-        let mut block_verifier = super::init(state_service.clone());
+        let mut state_service = zebra_state::in_memory::init();
+        let mut block_verifier = super::init(Network::Mainnet, state_service.clone());
 
         /// SPANDOC: Make sure the verifier service is ready
         let ready_verifier_service = block_verifier.ready_and().await.map_err(|e| eyre!(e))?;
@@ -375,7 +590,7 @@ mod tests {
             .await
             .map_err(|e| eyre!(e))?;
 
-        assert_eq!(verify_response, hash);
+        assert_eq!(verify_response, (hash, BlockHeight(0)));
 
         /// SPANDOC: Make sure the state service is ready
         let ready_state_service = state_service.ready_and().await.map_err(|e| eyre!(e))?;
@@ -407,11 +622,11 @@ mod tests {
         zebra_test::init();
 
         let block =
-            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])?;
+            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?;
         let hash: BlockHeaderHash = block.as_ref().into();
 
-        let state_service = zebra_state::in_memory::init();
-        let mut block_verifier = super::init(state_service.clone());
+        let mut state_service = zebra_state::in_memory::init();
+        let mut block_verifier = super::init(Network::Mainnet, state_service.clone());
 
         /// SPANDOC: Make sure the verifier service is ready (1/2)
         let ready_verifier_service = block_verifier.ready_and().await.map_err(|e| eyre!(e))?;
@@ -421,7 +636,7 @@ mod tests {
             .await
             .map_err(|e| eyre!(e))?;
 
-        assert_eq!(verify_response, hash);
+        assert_eq!(verify_response, (hash, BlockHeight(0)));
 
         /// SPANDOC: Make sure the state service is ready (1/2)
         let ready_state_service = state_service.ready_and().await.map_err(|e| eyre!(e))?;
@@ -483,7 +698,7 @@ mod tests {
             <Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])?;
 
         let mut state_service = zebra_state::in_memory::init();
-        let mut block_verifier = super::init(state_service.clone());
+        let mut block_verifier = super::init(Network::Mainnet, state_service.clone());
 
         // Modify the block's time
         // Changing the block header also invalidates the header hashes, but
@@ -532,12 +747,12 @@ mod tests {
 
         // Get a valid block
         let mut block =
-            Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])
+            Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])
                 .expect("block test vector should deserialize");
 
         // Service variables
         let state_service = zebra_state::in_memory::init();
-        let mut block_verifier = super::init(state_service.clone());
+        let mut block_verifier = super::init(Network::Mainnet, state_service.clone());
 
         let ready_verifier_service = block_verifier.ready_and().await.map_err(|e| eyre!(e))?;
 
@@ -561,6 +776,138 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn verify_fail_orphan_test() -> Result<(), Report> {
+        verify_fail_orphan().await
+    }
+
+    #[spandoc::spandoc]
+    async fn verify_fail_orphan() -> Result<(), Report> {
+        zebra_test::init();
+
+        // Block 1's parent isn't in the state, so it is an orphan
+        let block =
+            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])?;
+        let hash: BlockHeaderHash = block.as_ref().into();
+
+        let state_service = zebra_state::in_memory::init();
+        let mut block_verifier = super::init(Network::Mainnet, state_service.clone());
+
+        /// SPANDOC: Make sure the verifier service is ready
+        let ready_verifier_service = block_verifier.ready_and().await.map_err(|e| eyre!(e))?;
+        /// SPANDOC: Try to add the block, and expect an orphan error
+        let error = ready_verifier_service
+            .call(block.clone())
+            .await
+            .expect_err("blocks without a parent should be rejected");
+
+        assert_eq!(
+            error.downcast_ref::<ChainError>(),
+            Some(&ChainError::Orphan {
+                hash,
+                parent_hash: block.header.previous_block_hash,
+            })
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn verify_fail_height_mismatch_test() -> Result<(), Report> {
+        verify_fail_height_mismatch().await
+    }
+
+    #[spandoc::spandoc]
+    async fn verify_fail_height_mismatch() -> Result<(), Report> {
+        zebra_test::init();
+
+        let genesis =
+            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?;
+        let block =
+            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])?;
+        let hash: BlockHeaderHash = block.as_ref().into();
+
+        // Changing block 1's coinbase height would also change its Merkle
+        // root, so use a state that puts genesis at height 1 instead
+        let state_service = tower::service_fn(move |request: zebra_state::RequestBlock| {
+            let genesis = genesis.clone();
+            async move {
+                let response: Result<zebra_state::Response, Error> = match request {
+                    zebra_state::RequestBlock::GetDepth { .. } => {
+                        Ok(zebra_state::Response::Depth(Some(0)))
+                    }
+                    zebra_state::RequestBlock::GetBlockHeight { .. } => {
+                        Ok(zebra_state::Response::BlockHeight {
+                            block_height: BlockHeight(1),
+                        })
+                    }
+                    zebra_state::RequestBlock::GetBlock { .. } => {
+                        Ok(zebra_state::Response::Block { block: genesis })
+                    }
+                    _ => Err("unexpected state request".into()),
+                };
+                response
+            }
+        });
+        let mut block_verifier = super::init(Network::Mainnet, state_service);
+
+        /// SPANDOC: Try to add block 1, and expect a height mismatch
+        let error = block_verifier
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(block)
+            .await
+            .expect_err("blocks with the wrong coinbase height should be rejected");
+
+        assert_eq!(
+            error.downcast_ref::<ChainError>(),
+            Some(&ChainError::HeightMismatch {
+                hash,
+                coinbase_height: BlockHeight(1),
+                expected_height: BlockHeight(2),
+            })
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn verify_fail_wrong_genesis_test() -> Result<(), Report> {
+        verify_fail_wrong_genesis().await
+    }
+
+    #[spandoc::spandoc]
+    async fn verify_fail_wrong_genesis() -> Result<(), Report> {
+        zebra_test::init();
+
+        // The mainnet genesis block isn't a valid testnet genesis block
+        let block =
+            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?;
+        let hash: BlockHeaderHash = block.as_ref().into();
+
+        let state_service = zebra_state::in_memory::init();
+        let mut block_verifier = super::init(Network::Testnet, state_service.clone());
+
+        /// SPANDOC: Make sure the verifier service is ready
+        let ready_verifier_service = block_verifier.ready_and().await.map_err(|e| eyre!(e))?;
+        /// SPANDOC: Try to add the block, and expect a wrong genesis error
+        let error = ready_verifier_service
+            .call(block)
+            .await
+            .expect_err("the mainnet genesis block should be rejected on testnet");
+
+        assert_eq!(
+            error.downcast_ref::<ChainError>(),
+            Some(&ChainError::WrongGenesis {
+                hash,
+                network: Network::Testnet,
+            })
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[spandoc::spandoc]
     async fn coinbase() -> Result<(), Report> {
@@ -568,7 +915,7 @@ mod tests {
 
         // Service variables
         let state_service = zebra_state::in_memory::init();
-        let mut block_verifier = super::init(state_service.clone());
+        let mut block_verifier = super::init(Network::Mainnet, state_service.clone());
 
         // Get a header of a block
         let header =
@@ -0,0 +1,163 @@
+//! Chain context checks for block headers and blocks.
+//!
+//! These checks need the ancestors of the candidate block from the state:
+//!   - the parent block must already be in the state, and the candidate's
+//!     height is derived from the parent's height, and
+//!   - the candidate's time must be later than the median-time-past of its
+//!     ancestors.
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use zebra_chain::{block::BlockHeaderHash, types::BlockHeight, Network};
+
+use super::difficulty::{median_time, POW_MEDIAN_BLOCK_SPAN};
+
+/// An error from a chain context check.
+///
+/// Verifier errors are boxed, so callers can use `downcast_ref::<ChainError>()`
+/// to tell these errors apart from other verification failures.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ChainError {
+    /// The parent of the block is not in the state.
+    ///
+    /// The block might be valid, but it can't be verified until its parent
+    /// has been verified.
+    #[error("block {hash:?} is an orphan: its parent {parent_hash:?} is not in the state")]
+    Orphan {
+        /// The hash of the orphan block.
+        hash: BlockHeaderHash,
+        /// The `previous_block_hash` of the orphan block.
+        parent_hash: BlockHeaderHash,
+    },
+
+    /// The block's time is not later than the median-time-past of its
+    /// ancestors.
+    #[error(
+        "block {hash:?} at {height:?} has time {time}, which is not later than the median-time-past {median_time_past}"
+    )]
+    TimeTooEarly {
+        /// The hash of the block.
+        hash: BlockHeaderHash,
+        /// The height of the block.
+        height: BlockHeight,
+        /// The block's time.
+        time: DateTime<Utc>,
+        /// The median time of the block's ancestors.
+        median_time_past: DateTime<Utc>,
+    },
+
+    /// The block doesn't have a parent, but it isn't the genesis block for the
+    /// network.
+    #[error("block {hash:?} has no parent, but it is not the {network:?} genesis block")]
+    WrongGenesis {
+        /// The hash of the block.
+        hash: BlockHeaderHash,
+        /// The network of the verifier.
+        network: Network,
+    },
+
+    /// The height in the block's coinbase transaction doesn't follow on from
+    /// its parent's height.
+    #[error(
+        "block {hash:?} has coinbase height {coinbase_height:?}, but the height after its parent is {expected_height:?}"
+    )]
+    HeightMismatch {
+        /// The hash of the block.
+        hash: BlockHeaderHash,
+        /// The height in the block's coinbase transaction.
+        coinbase_height: BlockHeight,
+        /// The height derived from the block's parent.
+        expected_height: BlockHeight,
+    },
+}
+
+/// Check that `time` is later than the median-time-past of the block's
+/// ancestors.
+///
+/// "The block's nTime field MUST be greater than the median-time-past of
+/// that block"[S 7.6][7.6]
+///
+/// `ancestor_times` are the times of the block's ancestors, in reverse height
+/// order, starting with its parent. Only the first `POW_MEDIAN_BLOCK_SPAN`
+/// times are used. The genesis block doesn't have any ancestors, so it
+/// always passes this check.
+///
+/// [7.6]: https://zips.z.cash/protocol/protocol.pdf#blockheader
+pub(crate) fn median_time_past_check(
+    hash: BlockHeaderHash,
+    height: BlockHeight,
+    time: DateTime<Utc>,
+    ancestor_times: impl IntoIterator<Item = DateTime<Utc>>,
+) -> Result<(), ChainError> {
+    let ancestor_times: Vec<_> = ancestor_times
+        .into_iter()
+        .take(POW_MEDIAN_BLOCK_SPAN)
+        .collect();
+
+    if ancestor_times.is_empty() {
+        return Ok(());
+    }
+
+    let median_time_past = median_time(ancestor_times);
+    if time > median_time_past {
+        Ok(())
+    } else {
+        Err(ChainError::TimeTooEarly {
+            hash,
+            height,
+            time,
+            median_time_past,
+        })
+    }
+}
+
+/// Returns the height after `parent_height`.
+pub(crate) fn next_height(parent_height: BlockHeight) -> BlockHeight {
+    BlockHeight(parent_height.0 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+
+    #[test]
+    fn median_time_past() {
+        zebra_test::init();
+
+        let hash = BlockHeaderHash([0xab; 32]);
+        let height = BlockHeight(100);
+        let times: Vec<_> = (0..12).map(|secs| Utc.timestamp(1_000 + secs, 0)).collect();
+
+        // Times are in reverse height order, so only the 11 latest times are
+        // used, and the median is the sixth one
+        let ancestor_times: Vec<_> = times.iter().rev().cloned().collect();
+        let median_time_past = Utc.timestamp(1_006, 0);
+
+        median_time_past_check(
+            hash,
+            height,
+            median_time_past + chrono::Duration::seconds(1),
+            ancestor_times.clone(),
+        )
+        .expect("times after the median-time-past should be valid");
+
+        assert_eq!(
+            median_time_past_check(hash, height, median_time_past, ancestor_times.clone()),
+            Err(ChainError::TimeTooEarly {
+                hash,
+                height,
+                time: median_time_past,
+                median_time_past,
+            })
+        );
+
+        median_time_past_check(hash, height, Utc.timestamp(0, 0), ancestor_times)
+            .expect_err("times before the median-time-past should be invalid");
+
+        median_time_past_check(hash, BlockHeight(0), Utc.timestamp(0, 0), Vec::new())
+            .expect("blocks without ancestors should be valid");
+    }
+}
@@ -0,0 +1,405 @@
+//! Block difficulty adjustment calculations for contextual validation.
+//!
+//! This module implements `ThresholdBits(height)` from [section 7.6.3][7.6.3]
+//! of the Zcash specification, which is `GetNextWorkRequired()` in zcashd:
+//!   - the averaging window of the last 17 difficulty thresholds,
+//!   - the median-time-past of the first and last blocks in that window,
+//!   - damping and the maximum upward and downward adjustments, and
+//!   - the testnet minimum difficulty rule.
+//!
+//! The calculations only depend on the candidate block header and the
+//! difficulty thresholds and times of its ancestors, so they can be used by
+//! light clients that don't have a `zebra_state` service.
+//!
+//! [7.6.3]: https://zips.z.cash/protocol/protocol.pdf#diffadjustment
+
+use chrono::{DateTime, Duration, Utc};
+use std::cmp::{max, min};
+
+use zebra_chain::{
+    block::{BlockHeader, CompactDifficulty, ExpandedDifficulty},
+    parameters::NetworkUpgrade,
+    types::BlockHeight,
+    Network,
+};
+
+/// The averaging window for difficulty threshold arithmetic mean calculations.
+///
+/// `PoWAveragingWindow` in the Zcash specification.
+pub const POW_AVERAGING_WINDOW: usize = 17;
+
+/// The median block span for time median calculations.
+///
+/// `PoWMedianBlockSpan` in the Zcash specification.
+pub const POW_MEDIAN_BLOCK_SPAN: usize = 11;
+
+/// The number of previous blocks needed to calculate the difficulty threshold
+/// for a block.
+pub const POW_ADJUSTMENT_BLOCK_SPAN: usize = POW_AVERAGING_WINDOW + POW_MEDIAN_BLOCK_SPAN;
+
+/// The damping factor for median timespan variance.
+///
+/// `PoWDampingFactor` in the Zcash specification.
+pub const POW_DAMPING_FACTOR: i64 = 4;
+
+/// The maximum upward adjustment percentage for median timespan variance.
+///
+/// `PoWMaxAdjustUp * 100` in the Zcash specification.
+pub const POW_MAX_ADJUST_UP_PERCENT: i64 = 16;
+
+/// The maximum downward adjustment percentage for median timespan variance.
+///
+/// `PoWMaxAdjustDown * 100` in the Zcash specification.
+pub const POW_MAX_ADJUST_DOWN_PERCENT: i64 = 32;
+
+/// The first testnet height where the minimum difficulty rule applies.
+///
+/// zcashd applies the rule when the *previous* block's height is at least
+/// 299187.
+pub const TESTNET_MINIMUM_DIFFICULTY_START_HEIGHT: BlockHeight = BlockHeight(299_188);
+
+/// If a testnet block's time is more than this multiple of the target spacing
+/// after its parent's time, it can be mined at the minimum difficulty.
+pub const TESTNET_MINIMUM_DIFFICULTY_GAP_MULTIPLIER: i64 = 6;
+
+/// The difficulty adjustment context for a candidate block header.
+///
+/// Contains the candidate block's height, time, and network, and the
+/// difficulty thresholds and times of its ancestors.
+#[derive(Clone, Debug)]
+pub struct AdjustedDifficulty {
+    /// The candidate block's time.
+    candidate_time: DateTime<Utc>,
+    /// The candidate block's height.
+    candidate_height: BlockHeight,
+    /// The network the candidate block is on.
+    network: Network,
+    /// The `bits` fields of the previous `POW_ADJUSTMENT_BLOCK_SPAN` blocks,
+    /// in reverse height order, starting with the parent.
+    relevant_difficulty_thresholds: Vec<CompactDifficulty>,
+    /// The `time` fields of the previous `POW_ADJUSTMENT_BLOCK_SPAN` blocks,
+    /// in reverse height order, starting with the parent.
+    relevant_times: Vec<DateTime<Utc>>,
+}
+
+impl AdjustedDifficulty {
+    /// Returns the difficulty adjustment context for `candidate_header`, at
+    /// `candidate_height` on `network`.
+    ///
+    /// `context` is the `(bits, time)` of the previous blocks, in reverse
+    /// height order, starting with the parent of `candidate_header`. Only the
+    /// first `POW_ADJUSTMENT_BLOCK_SPAN` items are used. If the chain is
+    /// shorter than `POW_ADJUSTMENT_BLOCK_SPAN`, `context` should contain
+    /// every block back to the genesis block.
+    pub fn new_from_header<C>(
+        candidate_header: &BlockHeader,
+        candidate_height: BlockHeight,
+        network: Network,
+        context: C,
+    ) -> AdjustedDifficulty
+    where
+        C: IntoIterator<Item = (CompactDifficulty, DateTime<Utc>)>,
+    {
+        let (relevant_difficulty_thresholds, relevant_times) =
+            context.into_iter().take(POW_ADJUSTMENT_BLOCK_SPAN).unzip();
+
+        AdjustedDifficulty {
+            candidate_time: candidate_header.time,
+            candidate_height,
+            network,
+            relevant_difficulty_thresholds,
+            relevant_times,
+        }
+    }
+
+    /// Returns the candidate block's height.
+    pub fn candidate_height(&self) -> BlockHeight {
+        self.candidate_height
+    }
+
+    /// Returns the difficulty threshold that the candidate block's `bits`
+    /// field must be equal to.
+    ///
+    /// `ThresholdBits(height)` in the Zcash specification, including the
+    /// testnet minimum difficulty rule.
+    pub fn expected_difficulty_threshold(&self) -> CompactDifficulty {
+        if self.is_testnet_min_difficulty_block() {
+            ExpandedDifficulty::target_difficulty_limit(self.network).to_compact()
+        } else {
+            self.threshold_bits()
+        }
+    }
+
+    /// Is the candidate block a testnet minimum difficulty block?
+    ///
+    /// After `TESTNET_MINIMUM_DIFFICULTY_START_HEIGHT`, testnet blocks with a
+    /// time more than `TESTNET_MINIMUM_DIFFICULTY_GAP_MULTIPLIER` target
+    /// spacings after their parent's time use the minimum difficulty.
+    fn is_testnet_min_difficulty_block(&self) -> bool {
+        if self.network != Network::Testnet
+            || self.candidate_height < TESTNET_MINIMUM_DIFFICULTY_START_HEIGHT
+        {
+            return false;
+        }
+
+        let previous_block_time = match self.relevant_times.first() {
+            Some(time) => *time,
+            None => return false,
+        };
+
+        let target_spacing =
+            NetworkUpgrade::target_spacing_for_height(self.network, self.candidate_height);
+        let minimum_difficulty_gap =
+            Duration::seconds(target_spacing * TESTNET_MINIMUM_DIFFICULTY_GAP_MULTIPLIER);
+
+        self.candidate_time > previous_block_time + minimum_difficulty_gap
+    }
+
+    /// Returns the difficulty threshold from the averaging window, without
+    /// the testnet minimum difficulty rule.
+    ///
+    /// Blocks that don't have a full averaging window (and an extra block for
+    /// the median time of the first block) use the difficulty limit, like
+    /// zcashd.
+    fn threshold_bits(&self) -> CompactDifficulty {
+        let limit = ExpandedDifficulty::target_difficulty_limit(self.network);
+
+        if self.relevant_difficulty_thresholds.len() <= POW_AVERAGING_WINDOW {
+            return limit.to_compact();
+        }
+
+        let averaging_window_timespan =
+            NetworkUpgrade::target_spacing_for_height(self.network, self.candidate_height)
+                * POW_AVERAGING_WINDOW as i64;
+
+        let threshold = self
+            .mean_target_difficulty()
+            .checked_div(averaging_window_timespan as u64)
+            .expect("the averaging window timespan is not zero")
+            .checked_mul(self.median_timespan_bounded(averaging_window_timespan) as u64)
+            .expect("the bounded timespan is small enough to avoid overflow");
+
+        min(threshold, limit).to_compact()
+    }
+
+    /// Returns the arithmetic mean of the difficulty thresholds in the
+    /// averaging window.
+    ///
+    /// `MeanTarget(height)` in the Zcash specification.
+    fn mean_target_difficulty(&self) -> ExpandedDifficulty {
+        let averaging_window_thresholds = self
+            .relevant_difficulty_thresholds
+            .iter()
+            .take(POW_AVERAGING_WINDOW)
+            .map(|threshold| {
+                threshold
+                    .to_expanded()
+                    .expect("previous blocks have valid difficulty thresholds")
+            });
+
+        ExpandedDifficulty::checked_sum(averaging_window_thresholds)
+            .expect("the sum of 17 valid difficulty thresholds fits in 256 bits")
+            .checked_div(POW_AVERAGING_WINDOW as u64)
+            .expect("the averaging window is not zero")
+    }
+
+    /// Returns the damped and bounded median timespan of the averaging window,
+    /// in seconds.
+    ///
+    /// `ActualTimespanBounded(height)` in the Zcash specification.
+    fn median_timespan_bounded(&self, averaging_window_timespan: i64) -> i64 {
+        // The median time of the parent block, and of the block before the
+        // start of the averaging window
+        let newer_median_time = median_time(
+            self.relevant_times
+                .iter()
+                .take(POW_MEDIAN_BLOCK_SPAN)
+                .cloned(),
+        );
+        let older_median_time = median_time(
+            self.relevant_times
+                .iter()
+                .skip(POW_AVERAGING_WINDOW)
+                .take(POW_MEDIAN_BLOCK_SPAN)
+                .cloned(),
+        );
+        let actual_timespan = newer_median_time.timestamp() - older_median_time.timestamp();
+
+        // `ActualTimespanDamped(height)`: the division truncates towards zero,
+        // like C++ integer division in zcashd
+        let damped_timespan = averaging_window_timespan
+            + (actual_timespan - averaging_window_timespan) / POW_DAMPING_FACTOR;
+
+        let min_timespan = averaging_window_timespan * (100 - POW_MAX_ADJUST_UP_PERCENT) / 100;
+        let max_timespan = averaging_window_timespan * (100 + POW_MAX_ADJUST_DOWN_PERCENT) / 100;
+
+        min(max_timespan, max(min_timespan, damped_timespan))
+    }
+}
+
+/// Returns the median of `times`.
+///
+/// If there are an even number of times, returns the later of the two middle
+/// times, like `GetMedianTimePast()` in zcashd.
+///
+/// # Panics
+///
+/// If `times` is empty.
+pub fn median_time(times: impl IntoIterator<Item = DateTime<Utc>>) -> DateTime<Utc> {
+    let mut times: Vec<_> = times.into_iter().collect();
+    assert!(!times.is_empty(), "median time requires at least one time");
+
+    times.sort_unstable();
+    times[times.len() / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+
+    use zebra_chain::{
+        block::Block, parameters::PRE_BLOSSOM_POW_TARGET_SPACING, serialization::ZcashDeserialize,
+    };
+
+    /// A difficulty threshold that is harder than the mainnet limit.
+    const TEST_THRESHOLD: CompactDifficulty = CompactDifficulty(0x1d00_ffff);
+
+    /// Returns an `AdjustedDifficulty` for a chain of `TEST_THRESHOLD` blocks
+    /// spaced `spacing` seconds apart.
+    ///
+    /// The chain has `ancestors` blocks before the candidate block, which is
+    /// at `candidate_height`, and `spacing` seconds after its parent.
+    fn steady_chain(
+        network: Network,
+        candidate_height: BlockHeight,
+        ancestors: usize,
+        spacing: i64,
+    ) -> AdjustedDifficulty {
+        let candidate_time = Utc.timestamp(1_500_000_000, 0);
+        let context = (1..=ancestors as i64).map(|depth| {
+            (
+                TEST_THRESHOLD,
+                candidate_time - Duration::seconds(depth * spacing),
+            )
+        });
+
+        let block = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])
+            .expect("block test vector should deserialize");
+        let mut header = block.header;
+        header.time = candidate_time;
+
+        AdjustedDifficulty::new_from_header(&header, candidate_height, network, context)
+    }
+
+    #[test]
+    fn median_time_odd_and_even() {
+        let times = |secs: &[i64]| {
+            secs.iter()
+                .map(|s| Utc.timestamp(*s, 0))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(median_time(times(&[3, 1, 2])), Utc.timestamp(2, 0));
+        // zcashd uses the later of the two middle times
+        assert_eq!(median_time(times(&[4, 1, 3, 2])), Utc.timestamp(3, 0));
+        assert_eq!(median_time(times(&[7])), Utc.timestamp(7, 0));
+    }
+
+    #[test]
+    fn short_chain_uses_limit() {
+        zebra_test::init();
+
+        for network in &[Network::Mainnet, Network::Testnet] {
+            let limit = ExpandedDifficulty::target_difficulty_limit(*network).to_compact();
+
+            for ancestors in 0..=POW_AVERAGING_WINDOW {
+                let adjusted =
+                    steady_chain(*network, BlockHeight(ancestors as u32), ancestors, 150);
+                assert_eq!(adjusted.expected_difficulty_threshold(), limit);
+            }
+        }
+    }
+
+    #[test]
+    fn steady_chain_keeps_difficulty() {
+        zebra_test::init();
+
+        let spacing = PRE_BLOSSOM_POW_TARGET_SPACING;
+        let adjusted = steady_chain(Network::Mainnet, BlockHeight(100_000), 28, spacing);
+        let expected = adjusted
+            .expected_difficulty_threshold()
+            .to_expanded()
+            .expect("adjusted threshold is valid");
+        let mean = TEST_THRESHOLD.to_expanded().unwrap();
+
+        // The division rounds down, so the threshold can be slightly easier
+        // than the mean, but the compact encoding only keeps the high bits
+        assert!(expected <= mean);
+        assert!(expected > mean.checked_div(100).unwrap().checked_mul(99).unwrap());
+    }
+
+    #[test]
+    fn fast_and_slow_chains_are_bounded() {
+        zebra_test::init();
+
+        let window_timespan = PRE_BLOSSOM_POW_TARGET_SPACING * POW_AVERAGING_WINDOW as i64;
+        let bounded = |timespan: i64| {
+            TEST_THRESHOLD
+                .to_expanded()
+                .unwrap()
+                .checked_div(window_timespan as u64)
+                .unwrap()
+                .checked_mul(timespan as u64)
+                .unwrap()
+                .to_compact()
+        };
+
+        // Blocks one second apart hit the maximum upward adjustment
+        let fast = steady_chain(Network::Mainnet, BlockHeight(100_000), 28, 1);
+        assert_eq!(
+            fast.expected_difficulty_threshold(),
+            bounded(window_timespan * (100 - POW_MAX_ADJUST_UP_PERCENT) / 100)
+        );
+
+        // Blocks an hour apart hit the maximum downward adjustment
+        let slow = steady_chain(Network::Mainnet, BlockHeight(100_000), 28, 3600);
+        assert_eq!(
+            slow.expected_difficulty_threshold(),
+            bounded(window_timespan * (100 + POW_MAX_ADJUST_DOWN_PERCENT) / 100)
+        );
+    }
+
+    #[test]
+    fn testnet_minimum_difficulty() {
+        zebra_test::init();
+
+        let testnet_limit =
+            ExpandedDifficulty::target_difficulty_limit(Network::Testnet).to_compact();
+        let gap = PRE_BLOSSOM_POW_TARGET_SPACING * TESTNET_MINIMUM_DIFFICULTY_GAP_MULTIPLIER;
+
+        // A gap of exactly 6 target spacings isn't enough
+        let adjusted = steady_chain(Network::Testnet, BlockHeight(300_000), 28, gap);
+        assert_ne!(adjusted.expected_difficulty_threshold(), testnet_limit);
+
+        // But one more second is
+        let adjusted = steady_chain(Network::Testnet, BlockHeight(300_000), 28, gap + 1);
+        assert_eq!(adjusted.expected_difficulty_threshold(), testnet_limit);
+
+        // The rule doesn't apply before the start height, or on mainnet
+        let adjusted = steady_chain(
+            Network::Testnet,
+            BlockHeight(TESTNET_MINIMUM_DIFFICULTY_START_HEIGHT.0 - 1),
+            28,
+            gap + 1,
+        );
+        assert_ne!(adjusted.expected_difficulty_threshold(), testnet_limit);
+
+        let adjusted = steady_chain(Network::Mainnet, BlockHeight(300_000), 28, gap + 1);
+        assert_ne!(
+            adjusted.expected_difficulty_threshold(),
+            ExpandedDifficulty::target_difficulty_limit(Network::Mainnet).to_compact()
+        );
+    }
+}
@@ -20,8 +20,15 @@ use std::{
 use tower::{buffer::Buffer, Service, ServiceExt};
 
 use zebra_chain::{
-    block::{BlockHeader, BlockHeaderHash},
+    block::{BlockHeader, BlockHeaderHash, CompactDifficulty, ExpandedDifficulty},
+    parameters::{genesis_hash, GENESIS_PREVIOUS_BLOCK_HASH},
     types::BlockHeight,
+    Network,
+};
+
+use super::{
+    chain::{median_time_past_check, next_height, ChainError},
+    difficulty::{AdjustedDifficulty, POW_ADJUSTMENT_BLOCK_SPAN},
 };
 
 // use zebra_state::QueryType;
@@ -55,6 +62,190 @@ pub(crate) fn node_time_check(
     }
 }
 
+/// Check that the difficulty threshold in `block_header` is valid, and that
+/// `hash` is less than or equal to that threshold.
+///
+/// "The block MUST pass the difficulty filter... `ToTarget(nBits)` MUST be
+/// less than or equal to `PoWLimit`, and the block header hash interpreted
+/// as a little-endian integer MUST be less than or equal to
+/// `ToTarget(nBits)`."[S 7.6.2][7.6.2]
+///
+/// This check is context-free: it doesn't check that `bits` is the
+/// difficulty threshold expected at `height`.
+///
+/// [7.6.2]: https://zips.z.cash/protocol/protocol.pdf#difficulty
+pub(crate) fn difficulty_is_valid(
+    block_header: &BlockHeader,
+    network: Network,
+    height: &BlockHeight,
+    hash: &BlockHeaderHash,
+) -> Result<(), Error> {
+    let difficulty_threshold = block_header.bits.to_expanded().ok_or_else(|| {
+        format!(
+            "invalid difficulty threshold {:?} in block header {:?} {:?}",
+            block_header.bits, height, hash
+        )
+    })?;
+
+    if difficulty_threshold > ExpandedDifficulty::target_difficulty_limit(network) {
+        return Err(format!(
+            "difficulty threshold {:?} in block header {:?} {:?} is easier than the {:?} limit",
+            block_header.bits, height, hash, network
+        )
+        .into());
+    }
+
+    if *hash > difficulty_threshold {
+        return Err(format!(
+            "block header {:?} {:?} hash is greater than its difficulty threshold {:?}",
+            height, hash, block_header.bits
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Check that the difficulty threshold in `block_header` is the threshold
+/// calculated from its ancestors.
+///
+/// "`nBits` MUST be equal to `ThresholdBits(height)`"[S 7.6.3][7.6.3]
+///
+/// `context` is the `(bits, time)` of the ancestors of `block_header`, in
+/// reverse height order, starting with its parent.
+///
+/// [7.6.3]: https://zips.z.cash/protocol/protocol.pdf#diffadjustment
+pub(crate) fn difficulty_threshold_is_expected(
+    block_header: &BlockHeader,
+    network: Network,
+    height: BlockHeight,
+    context: Vec<(CompactDifficulty, DateTime<Utc>)>,
+) -> Result<(), Error> {
+    let expected_threshold =
+        AdjustedDifficulty::new_from_header(block_header, height, network, context)
+            .expected_difficulty_threshold();
+
+    if block_header.bits == expected_threshold {
+        Ok(())
+    } else {
+        Err(format!(
+            "block header {:?} difficulty threshold {:?} does not match the expected threshold {:?}",
+            height, block_header.bits, expected_threshold
+        )
+        .into())
+    }
+}
+
+/// Check `block_header` at `height` against the block header consensus rules,
+/// without using the state.
+///
+/// `context` is the `(bits, time)` of up to `POW_ADJUSTMENT_BLOCK_SPAN`
+/// ancestors of `block_header`, in reverse height order, starting with its
+/// parent. The genesis block doesn't have any ancestors. Callers must check
+/// that `block_header` links to its parent.
+///
+/// Light clients use this function to verify block headers from untrusted
+/// peers or relayers. Returns the hash of `block_header`.
+pub fn check_block_header(
+    block_header: &BlockHeader,
+    network: Network,
+    height: BlockHeight,
+    context: Vec<(CompactDifficulty, DateTime<Utc>)>,
+    now: DateTime<Utc>,
+) -> Result<BlockHeaderHash, Error> {
+    let hash: BlockHeaderHash = block_header.into();
+
+    // Since errors cause an early exit, try to do the quick checks first.
+    node_time_check(block_header.time, now)?;
+    block_header.is_equihash_solution_valid()?;
+
+    if height == BlockHeight(0) && hash != genesis_hash(network) {
+        return Err(ChainError::WrongGenesis { hash, network }.into());
+    }
+
+    difficulty_is_valid(block_header, network, &height, &hash)?;
+    median_time_past_check(
+        hash,
+        height,
+        block_header.time,
+        context.iter().map(|(_bits, time)| *time),
+    )?;
+    difficulty_threshold_is_expected(block_header, network, height, context)?;
+
+    Ok(hash)
+}
+
+/// Returns the height of the parent of a block header, and the `(bits, time)`
+/// of up to `POW_ADJUSTMENT_BLOCK_SPAN` of its ancestors, by walking back from
+/// `previous_block_hash` through `state_service`.
+///
+/// The ancestors are in reverse height order, starting with the parent.
+///
+/// Returns `ChainError::Orphan` if the parent is not in the state.
+async fn ancestor_context<S>(
+    state_service: &mut S,
+    hash: BlockHeaderHash,
+    previous_block_hash: BlockHeaderHash,
+) -> Result<(BlockHeight, Vec<(CompactDifficulty, DateTime<Utc>)>), Error>
+where
+    S: Service<zebra_state::RequestBlockHeader, Response = zebra_state::Response, Error = Error>,
+{
+    // Missing headers are state errors, so check for the parent first, to
+    // distinguish orphans from other state failures. The parent can be on a
+    // side chain.
+    match state_service
+        .ready_and()
+        .await?
+        .call(zebra_state::RequestBlockHeader::Contains {
+            hash: previous_block_hash,
+        })
+        .await?
+    {
+        zebra_state::Response::Contains(true) => {}
+        zebra_state::Response::Contains(false) => {
+            return Err(ChainError::Orphan {
+                hash,
+                parent_hash: previous_block_hash,
+            }
+            .into())
+        }
+        _ => return Err("unexpected response to a parent block header request".into()),
+    }
+
+    let mut parent_height = None;
+    let mut context = Vec::with_capacity(POW_ADJUSTMENT_BLOCK_SPAN);
+    let mut ancestor_hash = previous_block_hash;
+
+    while context.len() < POW_ADJUSTMENT_BLOCK_SPAN {
+        let response = state_service
+            .ready_and()
+            .await?
+            .call(zebra_state::RequestBlockHeader::GetBlockHeader {
+                query: zebra_state::QueryType::ByHash(ancestor_hash),
+            })
+            .await?;
+
+        let (ancestor, ancestor_height) = match response {
+            zebra_state::Response::BlockHeader {
+                block_header,
+                block_height,
+            } => (block_header, block_height),
+            _ => return Err("unexpected response to an ancestor block header request".into()),
+        };
+
+        parent_height.get_or_insert(ancestor_height);
+        context.push((ancestor.bits, ancestor.time));
+
+        if ancestor_height == BlockHeight(0) {
+            break;
+        }
+        ancestor_hash = ancestor.previous_block_hash;
+    }
+
+    let parent_height = parent_height.expect("the parent is always in the context");
+    Ok((parent_height, context))
+}
+
 /*
 /// [3.10]: https://zips.z.cash/protocol/protocol.pdf#coinbasetransactions
 pub(crate) fn coinbase_check(block: &Block) -> Result<(), Error> {
@@ -76,6 +267,8 @@ pub(crate) fn coinbase_check(block: &Block) -> Result<(), Error> {
 */
 
 struct BlockHeaderVerifier<S> {
+    /// The network the block headers are from.
+    network: Network,
     /// The underlying `ZebraState`, possibly wrapped in other services.
     state_service: S,
 }
@@ -87,7 +280,7 @@ type Error = Box<dyn error::Error + Send + Sync + 'static>;
 /// The BlockHeaderVerifier service implementation.
 ///
 /// After verification, blocks are added to the underlying state service.
-impl<S> Service<(Arc<BlockHeader>, BlockHeight)> for BlockHeaderVerifier<S>
+impl<S> Service<Arc<BlockHeader>> for BlockHeaderVerifier<S>
 where
     S: Service<zebra_state::RequestBlockHeader, Response = zebra_state::Response, Error = Error>
         + Send
@@ -106,40 +299,61 @@ where
         Poll::Ready(Ok(()))
     }
 
-    fn call(&mut self, header: (Arc<BlockHeader>, BlockHeight)) -> Self::Future {
+    fn call(&mut self, block_header: Arc<BlockHeader>) -> Self::Future {
         // TODO(jlusby): Error = Report, handle errors from state_service.
-        // TODO(teor):
-        //   - handle chain reorgs
-        //   - adjust state_service "unique block height" conditions
         let mut state_service = self.state_service.clone();
+        let network = self.network;
 
-        let hash: BlockHeaderHash = header.0.as_ref().into();
+        let hash: BlockHeaderHash = block_header.as_ref().into();
         let hash_str = hex::encode(&hash.0);
-        // let height = block.coinbase_height().unwrap();
-        let block_header = header.0;
-        let block_height = header.1;
 
         async move {
-            // Since errors cause an early exit, try to do the
-            // quick checks first.
-
-            let now = Utc::now();
-            node_time_check(block_header.time, now)?;
-            block_header.is_equihash_solution_valid()?;
             // coinbase_check(block.as_ref())?; // didn't applicable for block headers handling
 
+            // The genesis block doesn't have a parent, so its height is 0.
+            // Other block heights are derived from their parent's height.
+            let (block_height, context) =
+                if block_header.previous_block_hash == GENESIS_PREVIOUS_BLOCK_HASH {
+                    (BlockHeight(0), Vec::new())
+                } else {
+                    let (parent_height, context) = ancestor_context(
+                        &mut state_service,
+                        hash,
+                        block_header.previous_block_hash,
+                    )
+                    .await?;
+                    (next_height(parent_height), context)
+                };
+
+            check_block_header(&block_header, network, block_height, context, Utc::now())?;
+
             // `Tower::Buffer` requires a 1:1 relationship between `poll()`s
             // and `call()`s, because it reserves a buffer slot in each
             // `call()`.
             let add_block_header = state_service
                 .ready_and()
                 .await?
-                .call(zebra_state::RequestBlockHeader::AddBlockHeader { block_header, block_height });
+                .call(zebra_state::RequestBlockHeader::AddBlockHeader { block_header });
 
             tracing::info!("Block header with height {:?} and hash {:?} stored!", block_height, hash_str);
 
             match add_block_header.await? {
                 zebra_state::Response::Added { hash, height } => Ok((hash, height)),
+                zebra_state::Response::Reorganized {
+                    hash,
+                    height,
+                    disconnected,
+                    connected,
+                } => {
+                    tracing::info!(
+                        ?hash,
+                        ?height,
+                        disconnected = disconnected.len(),
+                        connected = connected.len(),
+                        "block header caused a chain reorganization"
+                    );
+                    Ok((hash, height))
+                }
                 _ => Err("adding block header to zebra-state failed".into()),
             }
         }
@@ -147,7 +361,8 @@ where
     }
 }
 
-/// Return a block header verification service, using the provided state service.
+/// Return a block header verification service for `network`, using the
+/// provided state service.
 ///
 /// The block header verifier holds a state service of type `S`, used as context for
 /// block header validation and to which newly verified block headers will be committed. This
@@ -161,9 +376,10 @@ where
 /// the result be shared) rather than constructing multiple verification services
 /// backed by the same state layer.
 pub fn init<S>(
+    network: Network,
     state_service: S,
 ) -> impl Service<
-    (Arc<BlockHeader>, BlockHeight),
+    Arc<BlockHeader>,
     Response = (BlockHeaderHash, BlockHeight),
     Error = Error,
     Future = impl Future<Output = Result<(BlockHeaderHash, BlockHeight), Error>>,
@@ -177,7 +393,13 @@ where
         + 'static,
     S::Future: Send + 'static,
 {
-    Buffer::new(BlockHeaderVerifier { state_service }, 1)
+    Buffer::new(
+        BlockHeaderVerifier {
+            network,
+            state_service,
+        },
+        1,
+    )
 }
 
 #[cfg(test)]
@@ -335,24 +557,30 @@ mod tests {
     async fn verify() -> Result<(), Report> {
         zebra_test::init();
 
-        let block =
-            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])?;
-        let block_header = block.header;
-        let block_height = block.coinbase_height().unwrap();
-        let hash: BlockHeaderHash = (&block_header).into();
-
         let state_service = zebra_state::in_memory_headersonly::init();
-        let mut block_header_verifier = super::init(state_service.clone());
-
-        /// SPANDOC: Make sure the verifier service is ready
-        let ready_verifier_service = block_header_verifier.ready_and().await.map_err(|e| eyre!(e))?;
-        /// SPANDOC: Verify the block header
-        let verify_response = ready_verifier_service
-            .call((Arc::new(block_header.clone()), block_height))
-            .await
-            .map_err(|e| eyre!(e))?;
-
-        assert_eq!(verify_response, (hash, block_height));
+        let mut block_header_verifier = super::init(Network::Mainnet, state_service.clone());
+
+        // The genesis block doesn't have any ancestors, and block 1 is
+        // checked against the difficulty threshold from the genesis block
+        for block_bytes in &[
+            &zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..],
+            &zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..],
+        ] {
+            let block = Arc::<Block>::zcash_deserialize(*block_bytes)?;
+            let block_header = block.header;
+            let block_height = block.coinbase_height().unwrap();
+            let hash: BlockHeaderHash = (&block_header).into();
+
+            /// SPANDOC: Make sure the verifier service is ready
+            let ready_verifier_service = block_header_verifier.ready_and().await.map_err(|e| eyre!(e))?;
+            /// SPANDOC: Verify the block header
+            let verify_response = ready_verifier_service
+                .call(Arc::new(block_header.clone()))
+                .await
+                .map_err(|e| eyre!(e))?;
+
+            assert_eq!(verify_response, (hash, block_height));
+        }
 
         Ok(())
     }
@@ -367,19 +595,19 @@ mod tests {
         zebra_test::init();
 
         let block =
-            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])?;
+            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?;
         let block_header = block.header;
         let block_height = block.coinbase_height().unwrap();
         let hash: BlockHeaderHash = (&block_header).into();
 
         let mut state_service = zebra_state::in_memory_headersonly::init();
-        let mut block_header_verifier = super::init(state_service.clone());
+        let mut block_header_verifier = super::init(Network::Mainnet, state_service.clone());
 
         /// SPANDOC: Make sure the verifier service is ready
         let ready_verifier_service = block_header_verifier.ready_and().await.map_err(|e| eyre!(e))?;
         /// SPANDOC: Verify the block header
         let verify_response = ready_verifier_service
-            .call((Arc::new(block_header.clone()), block_height))
+            .call(Arc::new(block_header.clone()))
             .await
             .map_err(|e| eyre!(e))?;
 
@@ -417,19 +645,19 @@ mod tests {
         zebra_test::init();
 
         let block =
-            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])?;
+            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?;
         let block_header = block.header;
         let block_height = block.coinbase_height().unwrap();
         let hash: BlockHeaderHash = (&block_header).into();
 
         let mut state_service = zebra_state::in_memory_headersonly::init();
-        let mut block_header_verifier = super::init(state_service.clone());
+        let mut block_header_verifier = super::init(Network::Mainnet, state_service.clone());
 
         /// SPANDOC: Make sure the verifier service is ready (1/2)
         let ready_verifier_service = block_header_verifier.ready_and().await.map_err(|e| eyre!(e))?;
         /// SPANDOC: Verify the block for the first time
         let verify_response = ready_verifier_service
-            .call((Arc::new(block_header.clone()), block_height))
+            .call(Arc::new(block_header.clone()))
             .await
             .map_err(|e| eyre!(e))?;
 
@@ -460,7 +688,7 @@ mod tests {
         // TODO(teor): ignore duplicate block verifies?
         // TODO(teor || jlusby): check error kind
         ready_verifier_service
-            .call((Arc::new(block_header.clone()), block_height))
+            .call(Arc::new(block_header.clone()))
             .await
             .unwrap_err();
 
@@ -498,10 +726,9 @@ mod tests {
         let block =
             <Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])?;
         let mut block_header = block.header;
-        let block_height = block.coinbase_height().unwrap();
 
         let mut state_service = zebra_state::in_memory_headersonly::init();
-        let mut block_header_verifier = super::init(state_service.clone());
+        let mut block_header_verifier = super::init(Network::Mainnet, state_service.clone());
 
         // Modify the block's time in block header
         // Changing the block header also invalidates the header hashes, but
@@ -521,7 +748,7 @@ mod tests {
         /// SPANDOC: Try to add the block, and expect failure
         // TODO(teor || jlusby): check error kind
         ready_verifier_service
-            .call((arc_block_header.clone(), block_height))
+            .call(arc_block_header.clone())
             .await
             .unwrap_err();
 
@@ -550,20 +777,19 @@ mod tests {
 
         // Get a valid block
         let block =
-            Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])
+            Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])
                 .expect("block test vector should deserialize");
         let mut block_header = block.header;
-        let block_height = block.coinbase_height().unwrap();
 
         // Service variables
         let state_service = zebra_state::in_memory_headersonly::init();
-        let mut block_header_verifier = super::init(state_service.clone());
+        let mut block_header_verifier = super::init(Network::Mainnet, state_service.clone());
 
         let ready_verifier_service = block_header_verifier.ready_and().await.map_err(|e| eyre!(e))?;
 
         // This should be ok
         ready_verifier_service
-            .call((Arc::new(block_header.clone()), block_height))
+            .call(Arc::new(block_header.clone()))
             .await
             .map_err(|e| eyre!(e))?;
 
@@ -574,13 +800,160 @@ mod tests {
 
         // Error: invalid equihash solution for BlockHeader
         ready_verifier_service
-            .call((Arc::new(block_header.clone()), block_height))
+            .call(Arc::new(block_header.clone()))
             .await
             .expect_err("expected the equihash solution to be invalid");
 
         Ok(())
     }
 
+    #[test]
+    fn difficulty_check() -> Result<(), Report> {
+        zebra_test::init();
+
+        let block =
+            Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])?;
+        let mut block_header = block.header;
+        let block_height = block.coinbase_height().unwrap();
+        let hash: BlockHeaderHash = (&block_header).into();
+
+        difficulty_is_valid(&block_header, Network::Mainnet, &block_height, &hash)
+            .map_err(|e| eyre!(e))?;
+
+        // The testnet limit is easier than the mainnet limit, so this check
+        // also accepts mainnet headers on testnet
+        difficulty_is_valid(&block_header, Network::Testnet, &block_height, &hash)
+            .map_err(|e| eyre!(e))?;
+
+        // A threshold that is easier than the mainnet limit is invalid, even
+        // though the hash is below it
+        block_header.bits = ExpandedDifficulty::target_difficulty_limit(Network::Testnet).to_compact();
+        difficulty_is_valid(&block_header, Network::Mainnet, &block_height, &hash)
+            .expect_err("thresholds easier than the limit should be invalid");
+
+        // A threshold that is harder than the hash is invalid
+        block_header.bits = CompactDifficulty(0x0300_0001);
+        difficulty_is_valid(&block_header, Network::Mainnet, &block_height, &hash)
+            .expect_err("hashes greater than the threshold should be invalid");
+
+        // An invalid compact encoding is invalid
+        block_header.bits = CompactDifficulty(0x0492_3456);
+        difficulty_is_valid(&block_header, Network::Mainnet, &block_height, &hash)
+            .expect_err("negative thresholds should be invalid");
+
+        Ok(())
+    }
+
+    #[test]
+    fn difficulty_threshold_check() -> Result<(), Report> {
+        zebra_test::init();
+
+        let genesis =
+            Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?;
+        let block = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])?;
+        let mut block_header = block.header;
+        let block_height = block.coinbase_height().unwrap();
+        let context = vec![(genesis.header.bits, genesis.header.time)];
+
+        difficulty_threshold_is_expected(
+            &block_header,
+            Network::Mainnet,
+            block_height,
+            context.clone(),
+        )
+        .map_err(|e| eyre!(e))?;
+
+        // Early blocks must use the limit, even if they are harder
+        block_header.bits = CompactDifficulty(0x1d00_ffff);
+        difficulty_threshold_is_expected(&block_header, Network::Mainnet, block_height, context)
+            .expect_err("early blocks should use the difficulty limit");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn verify_fail_orphan_test() -> Result<(), Report> {
+        verify_fail_orphan().await
+    }
+
+    #[spandoc::spandoc]
+    async fn verify_fail_orphan() -> Result<(), Report> {
+        zebra_test::init();
+
+        // Block 1's parent isn't in the state, so it is an orphan
+        let block =
+            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])?;
+        let block_header = block.header;
+        let hash: BlockHeaderHash = (&block_header).into();
+
+        let mut state_service = zebra_state::in_memory_headersonly::init();
+        let mut block_header_verifier = super::init(Network::Mainnet, state_service.clone());
+
+        /// SPANDOC: Make sure the verifier service is ready
+        let ready_verifier_service = block_header_verifier.ready_and().await.map_err(|e| eyre!(e))?;
+        /// SPANDOC: Try to add the block header, and expect an orphan error
+        let error = ready_verifier_service
+            .call(Arc::new(block_header))
+            .await
+            .expect_err("block headers without a parent should be rejected");
+
+        assert_eq!(
+            error.downcast_ref::<ChainError>(),
+            Some(&ChainError::Orphan {
+                hash,
+                parent_hash: block_header.previous_block_hash,
+            })
+        );
+
+        /// SPANDOC: Make sure the state service is ready
+        let ready_state_service = state_service.ready_and().await.map_err(|e| eyre!(e))?;
+        /// SPANDOC: Make sure the orphan isn't in the state
+        let state_response = ready_state_service
+            .call(zebra_state::RequestBlockHeader::Contains { hash })
+            .await
+            .map_err(|e| eyre!(e))?;
+        assert_eq!(state_response, zebra_state::Response::Contains(false));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn verify_fail_wrong_genesis_test() -> Result<(), Report> {
+        verify_fail_wrong_genesis().await
+    }
+
+    #[spandoc::spandoc]
+    async fn verify_fail_wrong_genesis() -> Result<(), Report> {
+        zebra_test::init();
+
+        // The mainnet genesis block isn't a valid testnet genesis block
+        let block =
+            Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?;
+        let block_header = block.header;
+        let hash: BlockHeaderHash = (&block_header).into();
+
+        let state_service = zebra_state::in_memory_headersonly::init();
+        let mut block_header_verifier = super::init(Network::Testnet, state_service);
+
+        /// SPANDOC: Make sure the verifier service is ready
+        let ready_verifier_service = block_header_verifier.ready_and().await.map_err(|e| eyre!(e))?;
+        /// SPANDOC: Try to add the block header, and expect a wrong genesis error
+        let error = ready_verifier_service
+            .call(Arc::new(block_header))
+            .await
+            .expect_err("the mainnet genesis block should be rejected on testnet");
+
+        assert_eq!(
+            error.downcast_ref::<ChainError>(),
+            Some(&ChainError::WrongGenesis {
+                hash,
+                network: Network::Testnet,
+            })
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[spandoc::spandoc]
     async fn coinbase() -> Result<(), Report> {
@@ -589,7 +962,8 @@ mod tests {
         // Service variables
         let state_service = zebra_state::in_memory::init();
 //      let mut block_verifier = super::init(state_service.clone());
-        let mut block_verifier = crate::verify::block::init(state_service.clone());
+        let mut block_verifier =
+            crate::verify::block::init(Network::Mainnet, state_service.clone());
 
         // Get a header of a block
         let header =
@@ -9,14 +9,407 @@
 //! Verification is provided via a `tower::Service`, to support backpressure and batch
 //! verification.
 //!
-//! This is an internal module. Use `verify::BlockVerifier` for blocks and their
-//! transactions, or `mempool::MempoolTransactionVerifier` for mempool transactions.
+//! Transparent scripts are evaluated by `zebra_script`, and signatures are
+//! checked against the spending transaction's signature hash by a
+//! `TransactionSignatureChecker`.
+//!
+//! The block and mempool verifiers check the scripts of every transparent
+//! input, using `verify_transparent_inputs`. Spent outputs are looked up in
+//! earlier transactions in the same block, then in any transaction in the
+//! state. The state doesn't track unspent outputs yet, so this doesn't check
+//! that spent outputs are unspent, or that they are in the best chain.
+
+use std::{
+    collections::HashMap,
+    error, fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use thiserror::Error;
+use tower::{Service, ServiceExt};
+
+use zebra_chain::{
+    parameters::ConsensusBranchId,
+    transaction::{OutPoint, Transaction, TransactionHash, TransparentInput, TransparentOutput},
+    types::Script,
+};
+use zebra_script::{
+    verify_script, Flags, ScriptError, SignatureChecker, TransactionSignatureChecker,
+};
+
+/// A request to verify a transparent input's `scriptSig`, against the
+/// `scriptPubKey` of the output it spends.
+#[derive(Clone)]
+pub struct Request {
+    /// The spending input's script.
+    pub script_sig: Script,
+    /// The spent output's script.
+    pub script_pubkey: Script,
+    /// Checks signatures and lock times against the spending transaction.
+    pub checker: Arc<dyn SignatureChecker + Send + Sync>,
+}
 
-/// Internal script verification service.
+impl fmt::Debug for Request {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Request")
+            .field("script_sig", &self.script_sig)
+            .field("script_pubkey", &self.script_pubkey)
+            .finish()
+    }
+}
+
+/// Transparent script verification service.
 ///
 /// After verification, the script future completes. State changes are handled by
 /// `BlockVerifier` or `MempoolTransactionVerifier`.
 ///
-/// `ScriptVerifier` is not yet implemented.
-#[derive(Default)]
-pub(crate) struct ScriptVerifier {}
+/// The default verifier checks the consensus rules that apply to every block.
+pub struct ScriptVerifier {
+    /// The rules that the interpreter checks.
+    flags: Flags,
+}
+
+impl ScriptVerifier {
+    /// Returns a script verifier that checks the rules in `flags`.
+    pub fn new(flags: Flags) -> Self {
+        ScriptVerifier { flags }
+    }
+}
+
+impl Default for ScriptVerifier {
+    fn default() -> Self {
+        ScriptVerifier::new(Flags::CONSENSUS)
+    }
+}
+
+impl Service<Request> for ScriptVerifier {
+    type Response = ();
+    type Error = ScriptError;
+    type Future = Pin<Box<dyn Future<Output = Result<(), ScriptError>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let result = verify_script(
+            &request.script_sig,
+            &request.script_pubkey,
+            self.flags,
+            request.checker.as_ref(),
+        );
+
+        if let Err(error) = result {
+            tracing::debug!(?error, ?request, "script verification failed");
+        }
+
+        Box::pin(async move { result })
+    }
+}
+
+/// An error from a transparent input check.
+///
+/// Verifier errors are boxed, so callers can use `downcast_ref::<InputError>()`
+/// to tell these errors apart from state failures.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum InputError {
+    /// The output spent by the input is not in the block or the state.
+    #[error("input {index} of transaction {hash:?} spends {outpoint:?}, which is not in the block or the state")]
+    MissingOutput {
+        /// The hash of the spending transaction.
+        hash: TransactionHash,
+        /// The index of the input in the spending transaction.
+        index: usize,
+        /// The spent output.
+        outpoint: OutPoint,
+    },
+
+    /// The input's script doesn't satisfy the script of the output it spends.
+    #[error("input {index} of transaction {hash:?} has an invalid script: {error}")]
+    Script {
+        /// The hash of the spending transaction.
+        hash: TransactionHash,
+        /// The index of the input in the spending transaction.
+        index: usize,
+        /// The script verification error.
+        error: ScriptError,
+    },
+}
+
+/// The error type for transparent input checks.
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// Returns the output spent by `outpoint`.
+///
+/// The output is looked up in `block_transactions`, which are the earlier
+/// transactions in the block, keyed by hash. Then it is looked up in the
+/// transactions in `state_service`.
+///
+/// Returns `Ok(None)` if the output is not in the block or the state.
+async fn spent_output<S>(
+    state_service: &mut S,
+    block_transactions: &HashMap<TransactionHash, Arc<Transaction>>,
+    outpoint: &OutPoint,
+) -> Result<Option<TransparentOutput>, Error>
+where
+    S: Service<zebra_state::RequestBlock, Response = zebra_state::Response, Error = Error>,
+{
+    let transaction = match block_transactions.get(&outpoint.hash) {
+        Some(transaction) => transaction.clone(),
+        None => match state_service
+            .ready_and()
+            .await?
+            .call(zebra_state::RequestBlock::GetTransaction {
+                hash: outpoint.hash,
+            })
+            .await?
+        {
+            zebra_state::Response::Transaction(Some((transaction, _, _))) => transaction,
+            zebra_state::Response::Transaction(None) => return Ok(None),
+            _ => return Err("unexpected response to a transaction request".into()),
+        },
+    };
+
+    Ok(transaction.outputs().nth(outpoint.index as usize).cloned())
+}
+
+/// Verifies the scripts of every transparent input in `transaction`, using
+/// the rules in `flags`.
+///
+/// `branch_id` is the consensus branch id of the network upgrade that the
+/// transaction is mined in. Inputs can spend the outputs of
+/// `block_transactions`, which are the earlier transactions in the same
+/// block, keyed by hash, or the outputs of any transaction in
+/// `state_service`.
+///
+/// Returns `InputError::MissingOutput` if a spent output is not in the block
+/// or the state, and `InputError::Script` if an input's script is invalid.
+pub(crate) async fn verify_transparent_inputs<S>(
+    state_service: &mut S,
+    transaction: &Arc<Transaction>,
+    block_transactions: &HashMap<TransactionHash, Arc<Transaction>>,
+    branch_id: ConsensusBranchId,
+    flags: Flags,
+) -> Result<(), Error>
+where
+    S: Service<zebra_state::RequestBlock, Response = zebra_state::Response, Error = Error>,
+{
+    let hash = TransactionHash::from(transaction.as_ref());
+
+    for (index, input) in transaction.inputs().enumerate() {
+        let (outpoint, script_sig) = match input {
+            TransparentInput::PrevOut {
+                outpoint, script, ..
+            } => (outpoint, script),
+            TransparentInput::Coinbase { .. } => continue,
+        };
+
+        let output = spent_output(state_service, block_transactions, outpoint)
+            .await?
+            .ok_or(InputError::MissingOutput {
+                hash,
+                index,
+                outpoint: *outpoint,
+            })?;
+
+        let request = Request {
+            script_sig: script_sig.clone(),
+            script_pubkey: output.pk_script,
+            checker: Arc::new(TransactionSignatureChecker::new(
+                transaction.clone(),
+                index,
+                output.value,
+                branch_id,
+            )),
+        };
+        ScriptVerifier::new(flags)
+            .oneshot(request)
+            .await
+            .map_err(|error| InputError::Script { hash, index, error })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use color_eyre::eyre::{eyre, Report};
+
+    use zebra_chain::{block::Block, serialization::ZcashDeserialize};
+
+    /// A checker that doesn't accept any signatures or lock times.
+    struct RejectAll;
+
+    impl SignatureChecker for RejectAll {
+        fn check_signature(&self, _: &[u8], _: &[u8], _: &Script) -> bool {
+            false
+        }
+
+        fn check_lock_time(&self, _: i64) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn verify_scripts() -> Result<(), Report> {
+        zebra_test::init();
+
+        // OP_1 satisfies OP_DUP OP_VERIFY
+        let request = Request {
+            script_sig: Script(vec![0x51]),
+            script_pubkey: Script(vec![0x76, 0x69]),
+            checker: Arc::new(RejectAll),
+        };
+        ScriptVerifier::default().oneshot(request.clone()).await?;
+
+        // P2SH is a consensus rule, so OP_NOP is not allowed in the scriptSig
+        // of a P2SH output
+        let mut script_pubkey = vec![0xa9, 0x14];
+        script_pubkey.extend(&hex::decode("da1745e9b549bd0bfa1a569971c77eba30cd5a4b")?);
+        script_pubkey.push(0x87);
+        let request = Request {
+            script_sig: Script(vec![0x61, 0x01, 0x51]),
+            script_pubkey: Script(script_pubkey),
+            ..request
+        };
+        assert_eq!(
+            ScriptVerifier::default().oneshot(request).await,
+            Err(ScriptError::SigPushOnly)
+        );
+
+        Ok(())
+    }
+
+    /// Returns mainnet block 434873.
+    ///
+    /// Transaction 8 spends output 1 of transaction 7, and transaction 7
+    /// spends an output from an earlier block.
+    fn block_434873() -> Arc<Block> {
+        Arc::<Block>::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_434873_BYTES[..])
+            .expect("block should deserialize")
+    }
+
+    /// The consensus branch id of the Sapling network upgrade.
+    const SAPLING_BRANCH_ID: ConsensusBranchId = ConsensusBranchId(0x76b8_09bb);
+
+    #[tokio::test]
+    async fn verify_block_inputs() -> Result<(), Report> {
+        zebra_test::init();
+
+        let block = block_434873();
+        let transactions = &block.transactions;
+        let hash = |index: usize| TransactionHash::from(transactions[index].as_ref());
+        let mut state_service = zebra_state::in_memory::init();
+
+        let mut block_transactions = HashMap::new();
+        block_transactions.insert(hash(7), transactions[7].clone());
+        verify_transparent_inputs(
+            &mut state_service,
+            &transactions[8],
+            &block_transactions,
+            SAPLING_BRANCH_ID,
+            Flags::STANDARD,
+        )
+        .await
+        .map_err(|e| eyre!(e))?;
+
+        // The spent output must be in the block or the state
+        let error = verify_transparent_inputs(
+            &mut state_service,
+            &transactions[7],
+            &block_transactions,
+            SAPLING_BRANCH_ID,
+            Flags::CONSENSUS,
+        )
+        .await
+        .expect_err("outputs from earlier blocks should not be in the empty state");
+        let outpoint = match transactions[7].inputs().next() {
+            Some(TransparentInput::PrevOut { outpoint, .. }) => *outpoint,
+            _ => panic!("transaction should spend a transparent output"),
+        };
+        assert_eq!(
+            error.downcast_ref::<InputError>(),
+            Some(&InputError::MissingOutput {
+                hash: hash(7),
+                index: 0,
+                outpoint,
+            })
+        );
+
+        // The script must satisfy the spent output's script
+        let mut block_transactions = HashMap::new();
+        block_transactions.insert(hash(7), transactions[6].clone());
+        let error = verify_transparent_inputs(
+            &mut state_service,
+            &transactions[8],
+            &block_transactions,
+            SAPLING_BRANCH_ID,
+            Flags::CONSENSUS,
+        )
+        .await
+        .expect_err("scripts should not satisfy the wrong output");
+        assert_eq!(
+            error.downcast_ref::<InputError>(),
+            Some(&InputError::Script {
+                hash: hash(8),
+                index: 0,
+                error: ScriptError::EqualVerify,
+            })
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn verify_state_inputs() -> Result<(), Report> {
+        zebra_test::init();
+
+        let block = block_434873();
+        let mut state_service = zebra_state::in_memory::init();
+        state_service
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(zebra_state::RequestBlock::AddBlock {
+                block: block.clone(),
+            })
+            .await
+            .map_err(|e| eyre!(e))?;
+
+        // Spent outputs can be in any transaction in the state
+        verify_transparent_inputs(
+            &mut state_service,
+            &block.transactions[9],
+            &HashMap::new(),
+            SAPLING_BRANCH_ID,
+            Flags::STANDARD,
+        )
+        .await
+        .map_err(|e| eyre!(e))?;
+
+        // Signatures commit to the branch id
+        let error = verify_transparent_inputs(
+            &mut state_service,
+            &block.transactions[9],
+            &HashMap::new(),
+            ConsensusBranchId(0x5ba8_1b19),
+            Flags::STANDARD,
+        )
+        .await
+        .expect_err("signatures should not be valid for another branch id");
+        assert_eq!(
+            error.downcast_ref::<InputError>(),
+            Some(&InputError::Script {
+                hash: TransactionHash::from(block.transactions[9].as_ref()),
+                index: 0,
+                error: ScriptError::EvalFalse,
+            })
+        );
+
+        Ok(())
+    }
+}
@@ -129,20 +129,37 @@ impl AddressBook {
     /// recently seen to least recently seen.
     pub fn disconnected_peers<'a>(&'a self) -> impl Iterator<Item = MetaAddr> + 'a {
         let _guard = self.span.enter();
-        use std::net::{IpAddr, Ipv4Addr};
         use std::ops::Bound::{Excluded, Unbounded};
-        let cutoff_meta = MetaAddr {
+
+        self.by_time
+            .range((Excluded(AddressBook::cutoff_meta()), Unbounded))
+            .rev()
+            .cloned()
+    }
+
+    /// Return an iterator over peers that could potentially be connected,
+    /// ordered from most recently seen to least recently seen.
+    pub fn potentially_connected_peers<'a>(&'a self) -> impl Iterator<Item = MetaAddr> + 'a {
+        let _guard = self.span.enter();
+        use std::ops::Bound::{Excluded, Unbounded};
+
+        self.by_time
+            .range((Unbounded, Excluded(AddressBook::cutoff_meta())))
+            .cloned()
+    }
+
+    /// Returns a `MetaAddr` at the [`AddressBook::cutoff_time`], which sorts
+    /// before every disconnected peer, and after every potentially connected
+    /// peer.
+    fn cutoff_meta() -> MetaAddr {
+        use std::net::{IpAddr, Ipv4Addr};
+        MetaAddr {
             last_seen: AddressBook::cutoff_time(),
             // The ordering on MetaAddrs is newest-first, then arbitrary,
             // so any fields will do here.
             addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 0),
             services: PeerServices::default(),
-        };
-
-        self.by_time
-            .range((Excluded(cutoff_meta), Unbounded))
-            .rev()
-            .cloned()
+        }
     }
 
     /// Returns an iterator that drains entries from the address book, removing
@@ -31,6 +31,23 @@ pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
 /// messages from each of our peers.
 pub const TIMESTAMP_TRUNCATION_SECONDS: i64 = 30 * 60;
 
+/// The maximum number of block headers in a `headers` message.
+///
+/// This is `MAX_HEADERS_RESULTS` in zcashd, which is lower than Bitcoin's
+/// limit of 2000, because Zcash headers include an Equihash solution.
+pub const MAX_HEADERS_RESULTS: usize = 160;
+
+/// The maximum number of peers that each transaction is advertised to.
+///
+/// Zebra doesn't fully verify transactions yet, so it only advertises them to
+/// a few peers. Peers can disconnect or ban nodes that relay invalid
+/// transactions.
+pub const MAX_TRANSACTION_ADVERTISEMENT_PEERS: usize = 3;
+
+/// The maximum number of advertised transactions that each peer connection
+/// keeps, to answer `getdata` requests for them.
+pub const MAX_ADVERTISED_TRANSACTIONS: usize = 100;
+
 /// The User-Agent string provided by the node.
 pub const USER_AGENT: &str = "Zebra v2.0.0-alpha.0";
 
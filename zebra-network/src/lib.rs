@@ -62,7 +62,8 @@ mod timestamp_collector;
 pub use crate::{
     address_book::AddressBook,
     config::Config,
-    peer_set::init,
+    constants::{CURRENT_VERSION, MAX_HEADERS_RESULTS},
+    peer_set::{init, PeerSetHandle},
     policies::{RetryErrors, RetryLimit},
     protocol::external::codec::Builder,
     protocol::internal::{Request, Response},
@@ -70,5 +71,8 @@ pub use crate::{
 
 /// Types used in the definition of [`Request`] and [`Response`] messages.
 pub mod types {
-    pub use crate::{meta_addr::MetaAddr, protocol::types::PeerServices};
+    pub use crate::{
+        meta_addr::MetaAddr,
+        protocol::{external::types::Version, types::PeerServices},
+    };
 }
@@ -18,6 +18,11 @@ use super::{ErrorSlot, SharedPeerError};
 pub struct Client {
     pub(super) server_tx: mpsc::Sender<ClientRequest>,
     pub(super) error_slot: ErrorSlot,
+    /// Stops the connection's heartbeat task when the client is dropped.
+    ///
+    /// The heartbeat task holds a `server_tx`, so the connection only closes
+    /// after the client and the heartbeat task are both gone.
+    pub(super) shutdown_tx: Option<oneshot::Sender<()>>,
 }
 
 /// A message from the `peer::Client` to the `peer::Server`.
@@ -33,6 +38,14 @@ pub(super) struct ClientRequest {
     pub span: tracing::Span,
 }
 
+impl Drop for Client {
+    fn drop(&mut self) {
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(());
+        }
+    }
+}
+
 impl Service<Request> for Client {
     type Response = Response;
     type Error = SharedPeerError;
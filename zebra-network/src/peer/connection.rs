@@ -14,7 +14,7 @@
 // The danger is to the mind. The danger is unleashed only if you substantially
 // disturb this code. This code is best shunned and left encapsulated.
 
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 use std::sync::Arc;
 
 use futures::{
@@ -30,6 +30,7 @@ use tracing_futures::Instrument;
 use zebra_chain::{
     block::{Block, BlockHeaderHash},
     serialization::SerializationError,
+    transaction::{Transaction, TransactionHash},
 };
 
 use crate::{
@@ -53,6 +54,7 @@ pub(super) enum Handler {
         blocks: Vec<Arc<Block>>,
     },
     FindBlocks,
+    FindHeaders,
 }
 
 impl Handler {
@@ -107,6 +109,9 @@ impl Handler {
                     })
                     .collect(),
             ))),
+            (FindHeaders, Message::Headers(headers)) => {
+                Finished(Ok(Response::BlockHeaders(headers)))
+            }
             // By default, messages are not responses.
             (state, msg) => {
                 trace!(?msg, "did not interpret message as response");
@@ -145,6 +150,9 @@ pub struct Connection<S, Tx> {
     pub(super) error_slot: ErrorSlot,
     //pub(super) peer_rx: Rx,
     pub(super) peer_tx: Tx,
+    /// The transactions advertised to the remote peer, oldest first, which
+    /// are sent in response to its `getdata` requests.
+    pub(super) advertised_transactions: VecDeque<(TransactionHash, Arc<Transaction>)>,
 }
 
 impl<S, Tx> Connection<S, Tx>
@@ -393,6 +401,44 @@ where
                     tx,
                     span,
                 }),
+            (AwaitingRequest, FindHeaders { known_blocks, stop }) => self
+                .peer_tx
+                .send(Message::GetHeaders {
+                    block_locator_hashes: known_blocks,
+                    hash_stop: stop.unwrap_or(BlockHeaderHash([0; 32])),
+                })
+                .await
+                .map_err(|e| e.into())
+                .map(|()| AwaitingResponse {
+                    handler: Handler::FindHeaders,
+                    tx,
+                    span,
+                }),
+            // Peers don't acknowledge advertisements, so we respond as soon as
+            // the message is sent.
+            (AwaitingRequest, AdvertiseTransaction(transaction)) => {
+                let hash = TransactionHash::from(transaction.as_ref());
+                if !self
+                    .advertised_transactions
+                    .iter()
+                    .any(|(advertised, _)| *advertised == hash)
+                {
+                    if self.advertised_transactions.len() >= constants::MAX_ADVERTISED_TRANSACTIONS
+                    {
+                        self.advertised_transactions.pop_front();
+                    }
+                    self.advertised_transactions.push_back((hash, transaction));
+                }
+
+                self.peer_tx
+                    .send(Message::Inv(vec![hash.into()]))
+                    .await
+                    .map_err(|e| e.into())
+                    .map(|()| {
+                        let _ = tx.send(Ok(Response::Nil));
+                        AwaitingRequest
+                    })
+            }
         } {
             Ok(new_state) => {
                 self.state = new_state;
@@ -448,6 +494,49 @@ where
                 None
             }
             Message::GetAddr => Some(Request::Peers),
+            Message::GetBlocks {
+                block_locator_hashes,
+                hash_stop,
+            } => Some(Request::FindBlocks {
+                known_blocks: block_locator_hashes,
+                stop: stop_hash(hash_stop),
+            }),
+            Message::GetHeaders {
+                block_locator_hashes,
+                hash_stop,
+            } => Some(Request::FindHeaders {
+                known_blocks: block_locator_hashes,
+                stop: stop_hash(hash_stop),
+            }),
+            Message::GetData(items) => {
+                let mut hashes = HashSet::new();
+                let mut transaction_hashes = Vec::new();
+                for item in items {
+                    match item {
+                        InventoryHash::Block(hash) => {
+                            hashes.insert(hash);
+                        }
+                        InventoryHash::Tx(hash) => transaction_hashes.push(hash),
+                        _ => {}
+                    }
+                }
+
+                // Advertised transactions are sent by the connection, because
+                // Zebra doesn't have a mempool service
+                if !transaction_hashes.is_empty() {
+                    if let Err(e) = self.send_advertised_transactions(transaction_hashes).await {
+                        self.fail_with(e);
+                        return;
+                    }
+                }
+
+                if hashes.is_empty() {
+                    debug!("ignoring getdata message without any blocks");
+                    None
+                } else {
+                    Some(Request::BlocksByHash(hashes))
+                }
+            }
             _ => {
                 debug!("unhandled message type");
                 None
@@ -459,6 +548,32 @@ where
         }
     }
 
+    /// Sends the advertised transactions with `hashes` to the remote peer, and
+    /// a `notfound` message for any other transactions.
+    async fn send_advertised_transactions(
+        &mut self,
+        hashes: Vec<TransactionHash>,
+    ) -> Result<(), PeerError> {
+        let mut missing = Vec::new();
+        for hash in hashes {
+            let transaction = self
+                .advertised_transactions
+                .iter()
+                .find(|(advertised, _)| *advertised == hash)
+                .map(|(_, transaction)| transaction.clone());
+            match transaction {
+                Some(transaction) => self.peer_tx.send(Message::Tx(transaction)).await?,
+                None => missing.push(hash.into()),
+            }
+        }
+
+        if !missing.is_empty() {
+            self.peer_tx.send(Message::NotFound(missing)).await?;
+        }
+
+        Ok(())
+    }
+
     /// Given a `req` originating from the peer, drive it to completion and send
     /// any appropriate messages to the remote peer. If an error occurs while
     /// processing the request (e.g., the service is shedding load), then we call
@@ -506,6 +621,8 @@ where
                     self.fail_with(e.into());
                 }
             }
+            // An empty inv doesn't tell the peer anything
+            Response::BlockHeaderHashes(hashes) if hashes.is_empty() => {}
             Response::BlockHeaderHashes(hashes) => {
                 if let Err(e) = self
                     .peer_tx
@@ -518,3 +635,12 @@ where
         }
     }
 }
+
+/// Returns `None` for the all-zeroes `hash_stop`, which means "no stop hash".
+fn stop_hash(hash_stop: BlockHeaderHash) -> Option<BlockHeaderHash> {
+    if hash_stop == BlockHeaderHash([0; 32]) {
+        None
+    } else {
+        Some(hash_stop)
+    }
+}
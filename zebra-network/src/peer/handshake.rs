@@ -1,5 +1,5 @@
 use std::{
-    collections::HashSet,
+    collections::{HashSet, VecDeque},
     future::Future,
     net::SocketAddr,
     pin::Pin,
@@ -8,7 +8,10 @@ use std::{
 };
 
 use chrono::Utc;
-use futures::{channel::mpsc, prelude::*};
+use futures::{
+    channel::{mpsc, oneshot},
+    prelude::*,
+};
 use tokio::net::TcpStream;
 use tokio_util::codec::Framed;
 use tower::Service;
@@ -209,11 +212,13 @@ where
             // These channels should not be cloned more than they are
             // in this block, see constants.rs for more.
             let (server_tx, server_rx) = mpsc::channel(0);
+            let (shutdown_tx, shutdown_rx) = oneshot::channel();
             let slot = ErrorSlot::default();
 
             let client = Client {
                 server_tx: server_tx.clone(),
                 error_slot: slot.clone(),
+                shutdown_tx: Some(shutdown_tx),
             };
 
             let (peer_tx, peer_rx) = stream.split();
@@ -265,6 +270,7 @@ where
                 error_slot: slot,
                 peer_tx,
                 request_timer: None,
+                advertised_transactions: VecDeque::new(),
             };
 
             tokio::spawn(
@@ -277,16 +283,22 @@ where
             let heartbeat_span = tracing::debug_span!(parent: connection_span, "heartbeat");
             tokio::spawn(
                 async move {
-                    use futures::channel::oneshot;
+                    use futures::future::{select, Either};
 
                     use super::client::ClientRequest;
 
                     let mut server_tx = server_tx;
+                    let mut shutdown_rx = shutdown_rx;
 
                     let mut interval_stream = tokio::time::interval(constants::HEARTBEAT_INTERVAL);
 
                     loop {
-                        interval_stream.tick().await;
+                        // Stop when the client is dropped, so the connection
+                        // can close
+                        let tick = Box::pin(interval_stream.tick());
+                        if let Either::Left(_) = select(&mut shutdown_rx, tick).await {
+                            return;
+                        }
 
                         // We discard the server handle because our
                         // heartbeat `Ping`s are a special case, and we
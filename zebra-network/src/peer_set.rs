@@ -1,4 +1,5 @@
 mod candidate_set;
+mod control;
 mod initialize;
 mod set;
 mod unready_service;
@@ -6,4 +7,5 @@ mod unready_service;
 use candidate_set::CandidateSet;
 use set::PeerSet;
 
+pub use control::PeerSetHandle;
 pub use initialize::init;
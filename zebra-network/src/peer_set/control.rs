@@ -0,0 +1,158 @@
+//! A control channel for manually adding peers to, and evicting peers from,
+//! the peer set.
+//!
+//! Control requests are converted into peer set changes, and sent to the
+//! peer set using the same channel as newly connected peers.
+
+use std::net::SocketAddr;
+
+use futures::{channel::mpsc, future::AbortHandle, sink::SinkExt, stream::StreamExt};
+use tower::{discover::Change, Service, ServiceExt};
+
+use crate::{peer, BoxedStdError};
+
+type PeerChange = Result<Change<SocketAddr, peer::Client>, BoxedStdError>;
+
+/// The number of control requests that can be queued before senders wait.
+const CONTROL_BUFFER_SIZE: usize = 10;
+
+/// A request to change the peers in the peer set.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(super) enum ControlRequest {
+    /// Connect to a peer, and add it to the peer set.
+    Add(SocketAddr),
+    /// Remove a peer from the peer set, closing its connection.
+    Evict(SocketAddr),
+}
+
+/// A handle for manually adding peers to, and evicting peers from, the peer
+/// set, and for shutting it down.
+///
+/// Used by operator commands, like the `addnode` and `disconnectnode` RPCs.
+#[derive(Clone, Debug)]
+pub struct PeerSetHandle {
+    tx: mpsc::Sender<ControlRequest>,
+    background_tasks: Vec<AbortHandle>,
+}
+
+impl PeerSetHandle {
+    /// Connect to the peer at `addr`, and add it to the peer set.
+    ///
+    /// Returns when the request has been queued. The connection is made in
+    /// the background, and failures are logged.
+    pub async fn add_peer(&self, addr: SocketAddr) -> Result<(), BoxedStdError> {
+        self.tx.clone().send(ControlRequest::Add(addr)).await?;
+        Ok(())
+    }
+
+    /// Remove the peer at `addr` from the peer set, closing its connection.
+    ///
+    /// Returns when the request has been queued. The peer can be reconnected
+    /// later, if it is a candidate for new connections.
+    pub async fn evict_peer(&self, addr: SocketAddr) -> Result<(), BoxedStdError> {
+        self.tx.clone().send(ControlRequest::Evict(addr)).await?;
+        Ok(())
+    }
+
+    /// Stop the peer set's background tasks, which connect to initial
+    /// peers, listen for inbound connections, crawl for new peers, and
+    /// handle control requests.
+    ///
+    /// The peer set fails once its background tasks have exited. Its peer
+    /// connections close after every handle to the peer set is dropped.
+    pub fn shutdown(&self) {
+        for task in self.background_tasks.iter() {
+            task.abort();
+        }
+    }
+}
+
+/// Returns a new peer set handle, and the receiver for its control requests.
+///
+/// `background_tasks` are aborted by `PeerSetHandle::shutdown`.
+pub(super) fn channel(
+    background_tasks: Vec<AbortHandle>,
+) -> (PeerSetHandle, mpsc::Receiver<ControlRequest>) {
+    let (tx, rx) = mpsc::channel(CONTROL_BUFFER_SIZE);
+    (
+        PeerSetHandle {
+            tx,
+            background_tasks,
+        },
+        rx,
+    )
+}
+
+/// Handle the control requests from `requests`, using `connector` to connect
+/// to new peers, then send the resulting peer set changes over `tx`.
+///
+/// Runs until every `PeerSetHandle` has been dropped.
+#[instrument(skip(requests, connector, tx))]
+pub(super) async fn handle_control_requests<C>(
+    mut requests: mpsc::Receiver<ControlRequest>,
+    mut connector: C,
+    mut tx: mpsc::Sender<PeerChange>,
+) -> Result<(), BoxedStdError>
+where
+    C: Service<SocketAddr, Response = Change<SocketAddr, peer::Client>, Error = BoxedStdError>,
+    C::Future: Send + 'static,
+{
+    while let Some(request) = requests.next().await {
+        match request {
+            ControlRequest::Add(addr) => {
+                info!(?addr, "connecting to manually added peer");
+                connector.ready_and().await?;
+                // Handshakes can be slow, so don't block other requests
+                let handshake = connector.call(addr);
+                let mut tx2 = tx.clone();
+                tokio::spawn(async move {
+                    match handshake.await {
+                        Ok(change) => {
+                            let _ = tx2.send(Ok(change)).await;
+                        }
+                        Err(error) => {
+                            info!(?addr, %error, "failed to connect to manually added peer")
+                        }
+                    }
+                });
+            }
+            ControlRequest::Evict(addr) => {
+                info!(?addr, "evicting peer");
+                tx.send(Ok(Change::Remove(addr))).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn evict_sends_remove() {
+        zebra_test::init();
+
+        let (handle, requests) = channel(Vec::new());
+        let (tx, mut changes) = mpsc::channel(1);
+        // Evicting doesn't use the connector
+        let connector = tower::service_fn(|_addr: SocketAddr| async {
+            Err::<Change<SocketAddr, peer::Client>, BoxedStdError>("no connections".into())
+        });
+        let task = tokio::spawn(handle_control_requests(requests, connector, tx));
+
+        let addr: SocketAddr = "127.0.0.1:8233".parse().unwrap();
+        handle.evict_peer(addr).await.unwrap();
+        match changes.next().await {
+            Some(Ok(Change::Remove(removed))) => assert_eq!(removed, addr),
+            _ => panic!("expected a peer removal"),
+        }
+
+        // Failed connections don't change the peer set
+        handle.add_peer(addr).await.unwrap();
+        drop(handle);
+        task.await.unwrap().unwrap();
+        assert!(changes.next().await.is_none());
+    }
+}
@@ -10,11 +10,14 @@ use std::{
 
 use futures::{
     channel::mpsc,
-    future::{self, Future, FutureExt},
+    future::{self, AbortHandle, Abortable, Future, FutureExt},
     sink::SinkExt,
     stream::{FuturesUnordered, StreamExt},
 };
-use tokio::net::{TcpListener, TcpStream};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
 use tower::{
     buffer::Buffer,
     discover::{Change, ServiceStream},
@@ -28,12 +31,17 @@ use crate::{
     Response,
 };
 
+use super::control::{self, PeerSetHandle};
 use super::CandidateSet;
 use super::PeerSet;
 
 type PeerChange = Result<Change<SocketAddr, peer::Client>, BoxedStdError>;
 
 /// Initialize a peer set with the given `config`, forwarding peer requests to the `inbound_service`.
+///
+/// Returns the peer set service, the address book of connected peers, and a
+/// handle for manually adding and evicting peers, and shutting down the peer
+/// set.
 pub async fn init<S>(
     config: Config,
     inbound_service: S,
@@ -47,6 +55,7 @@ pub async fn init<S>(
         + Clone
         + 'static,
     Arc<Mutex<AddressBook>>,
+    PeerSetHandle,
 )
 where
     S: Service<Request, Response = Response, Error = BoxedStdError> + Clone + Send + 'static,
@@ -91,17 +100,22 @@ where
     );
     let peer_set = Buffer::new(peer_set, config.peerset_request_buffer_size);
 
-    // Connect the tx end to the 3 peer sources:
+    // Connect the tx end to the 4 peer sources:
 
     // 1. Initial peers, specified in the config.
-    let add_guard = tokio::spawn(add_initial_peers(
-        config.initial_peers(),
+    let initial_peers = config.initial_peers();
+    let has_initial_peers = !initial_peers.is_empty();
+    let (add_task, abort_add) = future::abortable(add_initial_peers(
+        initial_peers,
         connector.clone(),
         peerset_tx.clone(),
     ));
+    let add_guard = spawn_background(add_task);
 
     // 2. Incoming peer connections, via a listener.
-    let listen_guard = tokio::spawn(listen(config.listen_addr, listener, peerset_tx.clone()));
+    let (listen_task, abort_listen) =
+        future::abortable(listen(config.listen_addr, listener, peerset_tx.clone()));
+    let listen_guard = spawn_background(listen_task);
 
     // 3. Outgoing peers we connect to in response to load.
     let mut candidates = CandidateSet::new(address_book.clone(), peer_set.clone());
@@ -111,27 +125,52 @@ where
     // need to ensure that its `addr` message is used by the crawler.
     // XXX this should go in CandidateSet::new, but we need init() -> Result<_,_>
 
-    info!("Sending initial request for peers");
-    let _ = candidates.update().await;
+    // Without initial peers, there's no one to ask, and the request would
+    // wait until a peer connects to us.
+    if has_initial_peers {
+        info!("Sending initial request for peers");
+        let _ = candidates.update().await;
+    }
 
     for _ in 0..config.peerset_initial_target_size {
         let _ = demand_tx.try_send(());
     }
 
-    let crawl_guard = tokio::spawn(crawl_and_dial(
+    let (crawl_task, abort_crawl) = future::abortable(crawl_and_dial(
         config.new_peer_interval,
         demand_tx,
         demand_rx,
         candidates,
-        connector,
-        peerset_tx,
+        connector.clone(),
+        peerset_tx.clone(),
+    ));
+    let crawl_guard = spawn_background(crawl_task);
+
+    // 4. Peers that are manually added or evicted, via the control channel.
+    let (abort_control, control_registration) = AbortHandle::new_pair();
+    let (peer_set_handle, control_rx) =
+        control::channel(vec![abort_add, abort_listen, abort_crawl, abort_control]);
+    let control_guard = spawn_background(Abortable::new(
+        control::handle_control_requests(control_rx, connector, peerset_tx),
+        control_registration,
     ));
 
     handle_tx
-        .send(vec![add_guard, listen_guard, crawl_guard])
+        .send(vec![add_guard, listen_guard, crawl_guard, control_guard])
         .unwrap();
 
-    (peer_set, address_book)
+    (peer_set, address_book, peer_set_handle)
+}
+
+/// Spawn `task`, a peer set background task.
+///
+/// Aborted tasks exit successfully, so the peer set only reports real
+/// errors.
+fn spawn_background<F>(task: Abortable<F>) -> JoinHandle<Result<(), BoxedStdError>>
+where
+    F: Future<Output = Result<(), BoxedStdError>> + Send + 'static,
+{
+    tokio::spawn(task.map(|result| result.unwrap_or(Ok(()))))
 }
 
 /// Use the provided `handshaker` to connect to `initial_peers`, then send
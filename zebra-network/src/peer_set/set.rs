@@ -3,6 +3,7 @@ use std::{
     convert::TryInto,
     fmt::Debug,
     future::Future,
+    iter,
     marker::PhantomData,
     pin::Pin,
     task::{Context, Poll},
@@ -23,6 +24,7 @@ use tower::{
 use tower_load::Load;
 
 use crate::{
+    constants,
     protocol::internal::{Request, Response},
     BoxedStdError,
 };
@@ -325,6 +327,29 @@ where
     }
 
     fn call(&mut self, req: Request) -> Self::Future {
+        match req {
+            // Transactions are only partially verified, so they are
+            // advertised to a few peers
+            Request::AdvertiseTransaction(_) => {
+                self.route_broadcast(req, constants::MAX_TRANSACTION_ADVERTISEMENT_PEERS)
+            }
+            _ => self.route_p2c(req),
+        }
+    }
+}
+
+impl<D> PeerSet<D>
+where
+    D: Discover + Unpin,
+    D::Key: Clone + Debug + ToString,
+    D::Service: Service<Request, Response = Response> + Load,
+    D::Error: Into<BoxedStdError>,
+    <D::Service as Service<Request>>::Error: Into<BoxedStdError> + 'static,
+    <D::Service as Service<Request>>::Future: Send + 'static,
+    <D::Service as Load>::Metric: Debug,
+{
+    /// Sends `req` to the preselected ready peer.
+    fn route_p2c(&mut self, req: Request) -> <Self as Service<Request>>::Future {
         let index = self
             .next_idx
             .take()
@@ -347,4 +372,36 @@ where
         use futures::future::TryFutureExt;
         fut.map_err(Into::into).boxed()
     }
+
+    /// Sends `req` to at most `max_peers` ready peers, and returns
+    /// `Response::Nil` once they have all responded.
+    ///
+    /// Peer errors are ignored, because broadcasts are best-effort.
+    fn route_broadcast(
+        &mut self,
+        req: Request,
+        max_peers: usize,
+    ) -> <Self as Service<Request>>::Future {
+        // The preselected peer is one of the ready peers
+        self.next_idx = None;
+
+        let services: Vec<_> = iter::from_fn(|| self.ready_services.pop())
+            .take(max_peers)
+            .collect();
+        let responses = FuturesUnordered::new();
+        for (key, mut svc) in services {
+            responses.push(svc.call(req.clone()).map(|result| result.is_ok()));
+            self.push_unready(key, svc);
+        }
+
+        async move {
+            let results: Vec<bool> = responses.collect().await;
+            let sent = results.iter().filter(|&&sent| sent).count();
+            let failed = results.len() - sent;
+            debug!(sent, failed, "broadcast request to peers");
+
+            Ok(Response::Nil)
+        }
+        .boxed()
+    }
 }
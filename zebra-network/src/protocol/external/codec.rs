@@ -9,7 +9,7 @@ use chrono::{TimeZone, Utc};
 use tokio_util::codec::{Decoder, Encoder};
 
 use zebra_chain::{
-    block::{Block, BlockHeaderHash},
+    block::{Block, BlockHeader, BlockHeaderHash},
     serialization::{
         ReadZcashExt, SerializationError as Error, WriteZcashExt, ZcashDeserialize, ZcashSerialize,
     },
@@ -220,7 +220,14 @@ impl Codec {
                 block_locator_hashes.zcash_serialize(&mut writer)?;
                 hash_stop.zcash_serialize(&mut writer)?;
             }
-            Message::Headers(headers) => headers.zcash_serialize(&mut writer)?,
+            Message::Headers(headers) => {
+                writer.write_compactsize(headers.len() as u64)?;
+                for header in headers {
+                    header.zcash_serialize(&mut writer)?;
+                    // Headers are sent without their transactions
+                    writer.write_compactsize(0)?;
+                }
+            }
             Message::Inv(hashes) => hashes.zcash_serialize(&mut writer)?,
             Message::GetData(hashes) => hashes.zcash_serialize(&mut writer)?,
             Message::NotFound(hashes) => hashes.zcash_serialize(&mut writer)?,
@@ -470,8 +477,23 @@ impl Codec {
     /// See [Zcash block header] for the enumeration of these fields.
     ///
     /// [Zcash block header](https://zips.z.cash/protocol/protocol.pdf#page=84)
+    ///
+    /// Each header is followed by a transaction count, which is always zero.
     fn read_headers<R: Read>(&self, mut reader: R) -> Result<Message, Error> {
-        Ok(Message::Headers(Vec::zcash_deserialize(&mut reader)?))
+        let len = reader.read_compactsize()? as usize;
+        if len > constants::MAX_HEADERS_RESULTS {
+            return Err(Error::Parse("headers message has too many headers"));
+        }
+
+        let mut headers = Vec::with_capacity(len);
+        for _ in 0..len {
+            headers.push(BlockHeader::zcash_deserialize(&mut reader)?.into());
+            if reader.read_compactsize()? != 0 {
+                return Err(Error::Parse("headers message has a non-zero transaction count"));
+            }
+        }
+
+        Ok(Message::Headers(headers))
     }
 
     fn read_getheaders<R: Read>(&self, mut reader: R) -> Result<Message, Error> {
@@ -481,7 +503,7 @@ impl Codec {
                 hash_stop: BlockHeaderHash::zcash_deserialize(&mut reader)?,
             })
         } else {
-            Err(Error::Parse("getblocks version did not match negotiation"))
+            Err(Error::Parse("getheaders version did not match negotiation"))
         }
     }
 
@@ -664,6 +686,46 @@ mod tests {
         });
     }
 
+    #[test]
+    fn headers_message_round_trip() {
+        let mut rt = Runtime::new().unwrap();
+
+        let block = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])
+            .expect("block test vector should deserialize");
+        let v = Message::Headers(vec![block.header.into(), block.header.into()]);
+
+        use tokio_util::codec::{FramedRead, FramedWrite};
+        let v_bytes = rt.block_on(async {
+            let mut bytes = Vec::new();
+            {
+                let mut fw = FramedWrite::new(&mut bytes, Codec::builder().finish());
+                fw.send(v.clone())
+                    .await
+                    .expect("message should be serialized");
+            }
+            bytes
+        });
+
+        // Each header is followed by a zero transaction count
+        let mut header_bytes = Vec::new();
+        block.header.zcash_serialize(&mut header_bytes).unwrap();
+        let body = &v_bytes[HEADER_LEN..];
+        assert_eq!(body.len(), 1 + 2 * (header_bytes.len() + 1));
+        assert_eq!(body[0], 2);
+        assert_eq!(&body[1..=header_bytes.len()], &header_bytes[..]);
+        assert_eq!(body[header_bytes.len() + 1], 0);
+
+        let v_parsed = rt.block_on(async {
+            let mut fr = FramedRead::new(Cursor::new(&v_bytes), Codec::builder().finish());
+            fr.next()
+                .await
+                .expect("a next message should be available")
+                .expect("that message should deserialize")
+        });
+
+        assert_eq!(v, v_parsed);
+    }
+
     #[test]
     fn decode_state_debug() {
         assert_eq!(format!("{:?}", DecodeState::Head), "DecodeState::Head");
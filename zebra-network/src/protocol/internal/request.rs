@@ -1,6 +1,6 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc};
 
-use zebra_chain::block::BlockHeaderHash;
+use zebra_chain::{block::BlockHeaderHash, transaction::Transaction};
 
 use super::super::types::Nonce;
 
@@ -37,4 +37,26 @@ pub enum Request {
         /// Optionally, the last header to request.
         stop: Option<BlockHeaderHash>,
     },
+
+    /// Request block headers of subsequent blocks in the chain, giving hashes
+    /// of known blocks.
+    ///
+    /// Peers respond with up to `MAX_HEADERS_RESULTS` headers. If
+    /// `known_blocks` is empty, they respond with the header for `stop`.
+    FindHeaders {
+        /// Hashes of known blocks, ordered from highest height to lowest height.
+        known_blocks: Vec<BlockHeaderHash>,
+        /// Optionally, the last header to request.
+        stop: Option<BlockHeaderHash>,
+    },
+
+    /// Advertise a transaction to peers, using an `inv` message.
+    ///
+    /// Zebra doesn't have a mempool, so each peer connection keeps the
+    /// transactions it advertises, and sends them in response to `getdata`
+    /// requests from that peer.
+    ///
+    /// The peer set sends this request to a few ready peers, and responds with
+    /// `Response::Nil`.
+    AdvertiseTransaction(Arc<Transaction>),
 }
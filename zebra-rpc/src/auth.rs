@@ -0,0 +1,443 @@
+//! Client authentication and authorization.
+//!
+//! Like `zcashd`, the RPC server uses HTTP basic authentication. Clients can
+//! authenticate using the random password in the `.cookie` file, which is
+//! written on startup and deleted on shutdown, or using the `user` and
+//! `password` in the config.
+//!
+//! Requests from localhost are always allowed. Requests from other clients
+//! are only allowed if their address is in one of the configured `allow_ips`
+//! ranges.
+
+use std::{
+    convert::TryFrom,
+    error, fmt, fs,
+    io::{self, Write},
+    net::{IpAddr, Ipv6Addr},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+
+use hyper::header::HeaderValue;
+use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// The user name for cookie authentication, which is the same as `zcashd`.
+pub const COOKIE_USER: &str = "__cookie__";
+
+/// The name of the cookie file.
+const COOKIE_FILE_NAME: &str = ".cookie";
+
+/// The groups of RPC methods that clients can be allowed to call.
+///
+/// Each group includes the methods of the groups before it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    /// Methods that query the chain state and peers
+    ReadOnly,
+    /// Methods that change the node's peers, or send transactions
+    Control,
+}
+
+/// A range of client IP addresses, written as an address, an address and
+/// prefix length, or an address and netmask.
+///
+/// For example: `10.1.2.3`, `10.0.0.0/8`, `10.0.0.0/255.0.0.0`, or
+/// `fd00::/8`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IpRange {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpRange {
+    /// Returns true if `ip` is in this range.
+    ///
+    /// IPv4-mapped IPv6 addresses are treated as IPv4 addresses.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.addr, canonical_ip(ip)) {
+            (IpAddr::V4(range), IpAddr::V4(ip)) => {
+                prefix_matches(&range.octets(), &ip.octets(), self.prefix_len)
+            }
+            (IpAddr::V6(range), IpAddr::V6(ip)) => {
+                prefix_matches(&range.octets(), &ip.octets(), self.prefix_len)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpRange {
+    type Err = Error;
+
+    fn from_str(range: &str) -> Result<Self, Self::Err> {
+        let mut parts = range.splitn(2, '/');
+        let addr: IpAddr = parts.next().unwrap_or_default().parse()?;
+        let max_len = match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+
+        let prefix_len = match parts.next() {
+            None => max_len,
+            Some(mask) => match mask.parse::<IpAddr>() {
+                Ok(mask) => netmask_prefix_len(addr, mask)
+                    .ok_or_else(|| format!("invalid netmask in IP range {:?}", range))?,
+                Err(_) => mask.parse()?,
+            },
+        };
+        if prefix_len > max_len {
+            return Err(format!("prefix length is too long in IP range {:?}", range).into());
+        }
+
+        Ok(Self { addr, prefix_len })
+    }
+}
+
+impl TryFrom<String> for IpRange {
+    type Error = Error;
+
+    fn try_from(range: String) -> Result<Self, Self::Error> {
+        range.parse()
+    }
+}
+
+impl fmt::Display for IpRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+impl From<IpRange> for String {
+    fn from(range: IpRange) -> Self {
+        range.to_string()
+    }
+}
+
+/// The reason that a request was rejected.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum AuthError {
+    /// The client address is not allowed
+    ForbiddenAddress,
+    /// The client credentials are missing or wrong
+    Unauthorized,
+}
+
+/// The clients and credentials that the RPC server accepts.
+#[derive(Clone)]
+pub struct Auth {
+    /// The accepted user names and passwords, and the permission that each
+    /// of them grants
+    credentials: Vec<(String, String, Permission)>,
+    /// The non-local client addresses that are allowed
+    allow_ips: Vec<IpRange>,
+    /// The cookie file, which is deleted when the last clone is dropped
+    _cookie: Arc<CookieFile>,
+}
+
+/// A cookie file written by `write_cookie`.
+struct CookieFile {
+    path: PathBuf,
+    password: String,
+}
+
+impl Drop for CookieFile {
+    /// Like `zcashd`, deletes the cookie file on shutdown.
+    ///
+    /// Another server may have replaced the cookie, so the file is only
+    /// deleted if it still contains this cookie.
+    fn drop(&mut self) {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) if contents == cookie_contents(&self.password) => {}
+            _ => return,
+        }
+
+        let path = &self.path;
+        match fs::remove_file(path) {
+            Ok(()) => tracing::info!(?path, "deleted RPC authentication cookie"),
+            Err(e) => tracing::warn!(?path, %e, "could not delete RPC authentication cookie"),
+        }
+    }
+}
+
+impl Auth {
+    /// Returns the authentication for `config`, and writes a new cookie file
+    /// to `cookie_dir`.
+    ///
+    /// The cookie file is deleted when the returned `Auth` and all its clones
+    /// are dropped.
+    ///
+    /// The cookie grants permission to call every method. If the config has
+    /// a user and password, they grant the configured `user_permission`.
+    pub fn new(config: &Config, cookie_dir: &Path) -> Result<Self, Error> {
+        let cookie = write_cookie(cookie_dir)?;
+        let mut credentials = vec![(
+            COOKIE_USER.to_owned(),
+            cookie.password.clone(),
+            Permission::Control,
+        )];
+
+        match (&config.user, &config.password) {
+            (Some(user), Some(password)) => {
+                credentials.push((user.clone(), password.clone(), config.user_permission))
+            }
+            (None, None) => {}
+            _ => return Err("the RPC user and password must be configured together".into()),
+        }
+
+        Ok(Self {
+            credentials,
+            allow_ips: config.allow_ips.clone(),
+            _cookie: Arc::new(cookie),
+        })
+    }
+
+    /// Returns the permission of a request from `client`, with the HTTP
+    /// `authorization` header.
+    pub(crate) fn authorize(
+        &self,
+        client: IpAddr,
+        authorization: Option<&HeaderValue>,
+    ) -> Result<Permission, AuthError> {
+        let local = canonical_ip(client).is_loopback();
+        if !local && !self.allow_ips.iter().any(|range| range.contains(client)) {
+            return Err(AuthError::ForbiddenAddress);
+        }
+
+        let (user, password) = authorization
+            .and_then(|header| header.to_str().ok())
+            .and_then(parse_basic_auth)
+            .ok_or(AuthError::Unauthorized)?;
+
+        // Check every credential, so the response time doesn't reveal which
+        // user names are valid
+        self.credentials
+            .iter()
+            .filter(|(expected_user, expected_password, _)| {
+                constant_time_eq(user.as_bytes(), expected_user.as_bytes())
+                    & constant_time_eq(password.as_bytes(), expected_password.as_bytes())
+            })
+            .map(|(_, _, permission)| *permission)
+            .max()
+            .ok_or(AuthError::Unauthorized)
+    }
+}
+
+/// Returns the path of the cookie file in `cookie_dir`.
+pub fn cookie_path(cookie_dir: &Path) -> PathBuf {
+    cookie_dir.join(COOKIE_FILE_NAME)
+}
+
+/// Writes a cookie file with a new random password to `cookie_dir`.
+///
+/// Like `zcashd`, the file contains `__cookie__:<password>`, and is only
+/// readable by the current user, even if it already existed.
+fn write_cookie(cookie_dir: &Path) -> Result<CookieFile, io::Error> {
+    let mut password = [0; 32];
+    thread_rng().fill_bytes(&mut password);
+    let password = hex::encode(password);
+
+    fs::create_dir_all(cookie_dir)?;
+    let path = cookie_path(cookie_dir);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path)?;
+    // The mode only applies to new files, so we also restrict existing files
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(cookie_contents(&password).as_bytes())?;
+    tracing::info!(?path, "wrote RPC authentication cookie");
+
+    Ok(CookieFile { path, password })
+}
+
+/// Returns the contents of a cookie file with `password`.
+fn cookie_contents(password: &str) -> String {
+    format!("{}:{}", COOKIE_USER, password)
+}
+
+/// Parses the user name and password in an HTTP basic authentication header.
+fn parse_basic_auth(authorization: &str) -> Option<(String, String)> {
+    if !authorization.starts_with("Basic ") {
+        return None;
+    }
+    let encoded = authorization["Basic ".len()..].trim();
+    let decoded = String::from_utf8(base64::decode(encoded).ok()?).ok()?;
+    let mut parts = decoded.splitn(2, ':');
+
+    Some((parts.next()?.to_owned(), parts.next()?.to_owned()))
+}
+
+/// Returns true if `a` and `b` are equal, in a time that only depends on
+/// their lengths.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// Returns `ip`, converting IPv4-mapped IPv6 addresses to IPv4.
+///
+/// Dual-stack listeners report IPv4 clients as IPv4-mapped addresses.
+fn canonical_ip(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) if is_ipv4_mapped(&v6) => {
+            let [.., a, b, c, d] = v6.octets();
+            IpAddr::from([a, b, c, d])
+        }
+        ip => ip,
+    }
+}
+
+fn is_ipv4_mapped(ip: &Ipv6Addr) -> bool {
+    matches!(ip.segments(), [0, 0, 0, 0, 0, 0xffff, _, _])
+}
+
+/// Returns true if the first `prefix_len` bits of `a` and `b` are equal.
+fn prefix_matches(a: &[u8], b: &[u8], prefix_len: u8) -> bool {
+    let prefix_len = usize::from(prefix_len);
+    let (bytes, bits) = (prefix_len / 8, prefix_len % 8);
+
+    if a[..bytes] != b[..bytes] {
+        return false;
+    }
+    if bits == 0 {
+        return true;
+    }
+
+    let mask = 0xff << (8 - bits);
+    a[bytes] & mask == b[bytes] & mask
+}
+
+/// Returns the prefix length of `mask`, or `None` if it is not a contiguous
+/// netmask for `addr`.
+fn netmask_prefix_len(addr: IpAddr, mask: IpAddr) -> Option<u8> {
+    let ones = match (addr, mask) {
+        (IpAddr::V4(_), IpAddr::V4(mask)) => {
+            let mask = u32::from(mask);
+            ((!mask).leading_zeros(), mask.count_ones())
+        }
+        (IpAddr::V6(_), IpAddr::V6(mask)) => {
+            let mask = u128::from(mask);
+            ((!mask).leading_zeros(), mask.count_ones())
+        }
+        _ => return None,
+    };
+
+    match ones {
+        (leading, count) if leading == count => u8::try_from(leading).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(range: &str) -> IpRange {
+        range.parse().expect("test ranges are valid")
+    }
+
+    fn ip(ip: &str) -> IpAddr {
+        ip.parse().expect("test addresses are valid")
+    }
+
+    #[test]
+    fn ip_ranges() {
+        assert!(range("10.1.2.3").contains(ip("10.1.2.3")));
+        assert!(!range("10.1.2.3").contains(ip("10.1.2.4")));
+
+        assert!(range("10.0.0.0/8").contains(ip("10.255.0.1")));
+        assert!(!range("10.0.0.0/8").contains(ip("11.0.0.1")));
+        assert!(range("10.0.0.0/255.0.0.0").contains(ip("10.255.0.1")));
+        assert!(range("192.168.0.0/20").contains(ip("192.168.15.1")));
+        assert!(!range("192.168.0.0/20").contains(ip("192.168.16.1")));
+        assert!(range("0.0.0.0/0").contains(ip("8.8.8.8")));
+
+        assert!(range("fd00::/8").contains(ip("fd12::1")));
+        assert!(!range("fd00::/8").contains(ip("fe80::1")));
+
+        // IPv4-mapped clients match IPv4 ranges, but not IPv6 ranges
+        assert!(range("10.0.0.0/8").contains(ip("::ffff:10.0.0.1")));
+        assert!(!range("10.0.0.0/8").contains(ip("fd00::1")));
+        assert!(!range("::/0").contains(ip("10.0.0.1")));
+    }
+
+    #[test]
+    fn invalid_ip_ranges() {
+        for &invalid in &[
+            "",
+            "10.0.0",
+            "10.0.0.0/33",
+            "fd00::/129",
+            "10.0.0.0/255.0.255.0",
+            "10.0.0.0/ffff::",
+            "localhost",
+        ] {
+            assert!(invalid.parse::<IpRange>().is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn ip_range_round_trip() {
+        let parsed = range("10.0.0.0/255.0.0.0");
+        assert_eq!(parsed.to_string(), "10.0.0.0/8");
+        assert_eq!(range(&parsed.to_string()), parsed);
+    }
+
+    #[test]
+    fn basic_auth_headers() {
+        assert_eq!(
+            parse_basic_auth("Basic dXNlcjpwYXNzOndvcmQ="),
+            Some(("user".to_owned(), "pass:word".to_owned()))
+        );
+        assert_eq!(parse_basic_auth("Basic dXNlcg=="), None);
+        assert_eq!(parse_basic_auth("Bearer dXNlcjpwYXNzd29yZA=="), None);
+        assert_eq!(parse_basic_auth("Basic !"), None);
+    }
+
+    #[test]
+    fn client_addresses() {
+        let auth = Auth {
+            credentials: vec![(
+                "user".to_owned(),
+                "password".to_owned(),
+                Permission::ReadOnly,
+            )],
+            allow_ips: vec![range("10.0.0.0/8")],
+            // There is no cookie file at this path, so nothing is deleted
+            _cookie: Arc::new(CookieFile {
+                path: PathBuf::from("nonexistent-cookie"),
+                password: String::new(),
+            }),
+        };
+        let header = HeaderValue::from_static("Basic dXNlcjpwYXNzd29yZA==");
+
+        for &allowed in &["127.0.0.1", "::1", "::ffff:127.0.0.1", "10.1.2.3"] {
+            assert_eq!(
+                auth.authorize(ip(allowed), Some(&header)),
+                Ok(Permission::ReadOnly)
+            );
+        }
+        for &forbidden in &["11.1.2.3", "fd00::1"] {
+            assert_eq!(
+                auth.authorize(ip(forbidden), Some(&header)),
+                Err(AuthError::ForbiddenAddress)
+            );
+        }
+    }
+}
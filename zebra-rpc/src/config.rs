@@ -0,0 +1,49 @@
+use std::net::SocketAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::auth::{IpRange, Permission};
+
+/// Configuration for the RPC server.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct Config {
+    /// The address on which the RPC server listens for JSON-RPC requests.
+    pub listen_addr: SocketAddr,
+
+    /// The user name for password authentication, like `zcashd`'s `rpcuser`.
+    ///
+    /// Clients can always authenticate using the `.cookie` file in the state
+    /// `cache_dir`, which is rewritten with a new password on startup.
+    pub user: Option<String>,
+
+    /// The password for `user`, like `zcashd`'s `rpcpassword`.
+    pub password: Option<String>,
+
+    /// The methods that `user` can call: `read_only` or `control`.
+    ///
+    /// Clients that authenticate using the cookie can call every method.
+    pub user_permission: Permission,
+
+    /// The non-local client addresses that are allowed to make requests,
+    /// like `zcashd`'s `rpcallowip`.
+    ///
+    /// Each range is an address, an address and prefix length, or an address
+    /// and netmask. Requests from localhost are always allowed.
+    pub allow_ips: Vec<IpRange>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            // The zcashd mainnet RPC port, on localhost
+            listen_addr: "127.0.0.1:8232"
+                .parse()
+                .expect("hard-coded address should parse"),
+            user: None,
+            password: None,
+            user_permission: Permission::Control,
+            allow_ips: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! Streams of chain events, which push new blocks and headers to clients as
+//! soon as the state has them.
+//!
+//! Clients subscribe by sending a `GET` request to `/events`, with an optional
+//! `filter` of comma-separated event types:
+//!   * `tips`: each new best chain tip (the default)
+//!   * `headers`: each block that is connected to the best chain, with its
+//!     header
+//!   * `blocks`: each block that is connected to the best chain, with the full
+//!     block
+//!   * `disconnected`: each block that is removed from the best chain by a
+//!     reorganisation
+//!
+//! For example, `GET /events?filter=headers,disconnected`.
+//!
+//! The response is a stream of newline-delimited JSON objects, which starts
+//! with the current tip, and lasts until the client disconnects. Headers and
+//! blocks are serialized hex, and hashes are byte-reversed hex, like the RPC
+//! methods.
+//!
+//! Each client has its own bounded buffer of unread events. If a client falls
+//! more than `MAX_UNREAD_CHAIN_EVENTS` events behind, its stream ends, so slow
+//! clients can't stall the node, or make it buffer an unbounded number of
+//! events. Clients can subscribe again, then catch up using
+//! `getbestblockhash` and `getblockhash`.
+
+use std::{error, str::FromStr};
+
+use futures::{
+    future,
+    stream::{self, Stream, StreamExt, TryStreamExt},
+};
+use serde_json::{json, Value};
+
+use zebra_chain::serialization::ZcashSerialize;
+use zebra_state::ChainEvent;
+
+use crate::{methods::display_hex, state::ChainState};
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// The path of the chain event stream.
+pub(crate) const EVENTS_PATH: &str = "/events";
+
+/// The chain events that a client subscribes to.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Filter {
+    tips: bool,
+    headers: bool,
+    blocks: bool,
+    disconnected: bool,
+}
+
+impl Filter {
+    /// Returns the filter in the `filter` parameter of `query`, or the
+    /// default filter if there is no `filter` parameter.
+    pub fn from_query(query: Option<&str>) -> Result<Self, String> {
+        let filter = query
+            .unwrap_or_default()
+            .split('&')
+            .filter_map(|param| {
+                let mut param = param.splitn(2, '=');
+                match (param.next(), param.next()) {
+                    (Some("filter"), value) => Some(value.unwrap_or_default()),
+                    _ => None,
+                }
+            })
+            .last();
+
+        match filter {
+            Some(filter) => filter.parse(),
+            None => Ok(Filter {
+                tips: true,
+                ..Filter::default()
+            }),
+        }
+    }
+
+    /// Returns true if the filter needs full blocks from the state.
+    pub fn needs_blocks(&self) -> bool {
+        self.blocks
+    }
+
+    /// Returns true if clients with this filter receive `event`.
+    fn includes(&self, event: &ChainEvent) -> bool {
+        match event {
+            ChainEvent::NewTip { .. } => self.tips,
+            ChainEvent::Connected { .. } => self.headers || self.blocks,
+            ChainEvent::Disconnected { .. } => self.disconnected,
+        }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(filter: &str) -> Result<Self, Self::Err> {
+        let mut parsed = Filter::default();
+
+        for event_type in filter.split(',') {
+            match event_type {
+                "tips" => parsed.tips = true,
+                "headers" => parsed.headers = true,
+                "blocks" => parsed.blocks = true,
+                "disconnected" => parsed.disconnected = true,
+                _ => {
+                    return Err(format!(
+                        "unknown event type {:?}, expected tips, headers, blocks or disconnected",
+                        event_type
+                    ))
+                }
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Subscribes to the chain events of `state`, and returns the events that
+/// match `filter`, as lines of JSON.
+///
+/// The stream fails if a connected block can't be read from the state.
+pub(crate) async fn subscribe(
+    state: ChainState,
+    filter: Filter,
+) -> Result<impl Stream<Item = Result<String, Error>> + Send + 'static, Error> {
+    let chain_events = state.subscribe().await?;
+
+    // Get the tip after subscribing, so clients don't miss any changes. Empty
+    // states don't have a tip.
+    let tip = state
+        .tip()
+        .await
+        .ok()
+        .map(|(hash, height)| ChainEvent::NewTip { hash, height });
+
+    Ok(stream::iter(tip)
+        .chain(chain_events)
+        .filter(move |event| future::ready(filter.includes(event)))
+        .then(move |event| {
+            let state = state.clone();
+            async move { event_with_data(&state, filter, &event).await }
+        })
+        .map_ok(|event| format!("{}\n", event))
+        .inspect_err(|e| tracing::info!(%e, "ending chain event stream")))
+}
+
+/// Returns the JSON for `event`, with the header and block data in `filter`.
+async fn event_with_data(
+    state: &ChainState,
+    filter: Filter,
+    event: &ChainEvent,
+) -> Result<Value, Error> {
+    let mut json = event_json(event);
+
+    if let ChainEvent::Connected { hash, .. } = event {
+        if filter.headers {
+            let (header, _) = state.header((*hash).into()).await?;
+            json["header"] = json!(hex::encode(header.zcash_serialize_to_vec()?));
+        }
+        if filter.blocks {
+            let block = state.block((*hash).into()).await?;
+            json["block"] = json!(hex::encode(block.zcash_serialize_to_vec()?));
+        }
+    }
+
+    Ok(json)
+}
+
+/// Returns the JSON for `event`, without any header or block data.
+fn event_json(event: &ChainEvent) -> Value {
+    let (event_type, hash, height) = match event {
+        ChainEvent::NewTip { hash, height } => ("tip", hash, height),
+        ChainEvent::Connected { hash, height } => ("connected", hash, height),
+        ChainEvent::Disconnected { hash, height } => ("disconnected", hash, height),
+    };
+
+    json!({
+        "type": event_type,
+        "hash": display_hex(&hash.0),
+        "height": height.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use zebra_chain::{block::BlockHeaderHash, types::BlockHeight};
+
+    #[test]
+    fn filters() {
+        let tips = Filter {
+            tips: true,
+            ..Filter::default()
+        };
+        assert_eq!(Filter::from_query(None), Ok(tips));
+        assert_eq!(Filter::from_query(Some("verbose=1")), Ok(tips));
+        assert_eq!(Filter::from_query(Some("filter=tips")), Ok(tips));
+
+        let filter = Filter::from_query(Some("filter=headers,disconnected")).unwrap();
+        assert!(filter.headers && filter.disconnected);
+        assert!(!filter.tips && !filter.needs_blocks());
+
+        assert!(Filter::from_query(Some("filter=")).is_err());
+        assert!(Filter::from_query(Some("filter=mempool")).is_err());
+    }
+
+    #[test]
+    fn filtered_events() {
+        let hash = BlockHeaderHash([1; 32]);
+        let height = BlockHeight(7);
+        let tip = ChainEvent::NewTip { hash, height };
+        let connected = ChainEvent::Connected { hash, height };
+        let disconnected = ChainEvent::Disconnected { hash, height };
+
+        let filter: Filter = "blocks".parse().unwrap();
+        assert!(!filter.includes(&tip));
+        assert!(filter.includes(&connected));
+        assert!(!filter.includes(&disconnected));
+
+        let filter: Filter = "tips,disconnected".parse().unwrap();
+        assert!(filter.includes(&tip));
+        assert!(!filter.includes(&connected));
+        assert!(filter.includes(&disconnected));
+
+        assert_eq!(
+            event_json(&disconnected),
+            json!({
+                "type": "disconnected",
+                "hash": "01".repeat(32),
+                "height": 7,
+            })
+        );
+    }
+}
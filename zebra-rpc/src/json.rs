@@ -0,0 +1,105 @@
+//! JSON-RPC 2.0 requests, responses and errors.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// JSON-RPC error codes.
+///
+/// The codes below -32000 are defined by JSON-RPC 2.0. The other codes
+/// match the errors that `zcashd` returns for the same failures.
+pub mod codes {
+    /// The request body is not valid JSON.
+    pub const PARSE_ERROR: i64 = -32700;
+    /// The request is not a valid JSON-RPC request object.
+    pub const INVALID_REQUEST: i64 = -32600;
+    /// The method does not exist.
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    /// The method parameters are invalid.
+    pub const INVALID_PARAMS: i64 = -32602;
+    /// A state error, or an unavailable feature.
+    pub const MISC_ERROR: i64 = -1;
+    /// A block or transaction could not be found.
+    pub const INVALID_ADDRESS_OR_KEY: i64 = -5;
+    /// A parameter value is out of range, or badly formatted.
+    pub const INVALID_PARAMETER: i64 = -8;
+    /// A raw transaction could not be decoded.
+    pub const DESERIALIZATION_ERROR: i64 = -22;
+    /// A transaction was rejected by the verifier.
+    pub const VERIFY_REJECTED: i64 = -26;
+    /// A transaction is already in the best chain.
+    pub const VERIFY_ALREADY_IN_CHAIN: i64 = -27;
+    /// The node is not connected to the peer.
+    pub const NODE_NOT_CONNECTED: i64 = -29;
+    /// The node has no peer-to-peer network.
+    pub const P2P_DISABLED: i64 = -31;
+}
+
+/// A JSON-RPC request.
+///
+/// `zcashd` clients often send `"jsonrpc": "1.0"`, or omit the version, so
+/// it is ignored.
+#[derive(Debug, Deserialize)]
+pub(crate) struct Request {
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+    #[serde(default)]
+    pub id: Value,
+}
+
+/// A JSON-RPC response, containing a result or an error.
+#[derive(Debug, Serialize)]
+pub(crate) struct Response {
+    jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+    id: Value,
+}
+
+impl Response {
+    /// Returns the response to the request with `id`.
+    pub fn new(id: Value, result: Result<Value, RpcError>) -> Self {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+
+        Self {
+            jsonrpc: "2.0",
+            result,
+            error,
+            id,
+        }
+    }
+}
+
+/// A JSON-RPC error object.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// Returns an error for a failed state request.
+    pub fn state(error: impl ToString) -> Self {
+        Self::new(codes::MISC_ERROR, error.to_string())
+    }
+
+    pub fn block_not_found() -> Self {
+        Self::new(codes::INVALID_ADDRESS_OR_KEY, "Block not found")
+    }
+
+    pub fn height_out_of_range() -> Self {
+        Self::new(codes::INVALID_PARAMETER, "Block height out of range")
+    }
+}
@@ -1,10 +1,99 @@
+//! A zcashd-compatible JSON-RPC server for Zebra. 🦓
+//!
+//! Tools written for `zcashd` query the chain using its JSON-RPC interface.
+//! This crate serves the read-only chain methods of that interface from the
+//! `zebra_state` services:
+//!   * `getblockcount`
+//!   * `getbestblockhash`
+//!   * `getblockhash height`
+//!   * `getblockheader "hash" ( verbose )`
+//!   * `getblock "hash|height" ( verbosity )`
+//!
+//! The raw transaction methods use the state's transaction index, and the
+//! `zebra_consensus` mempool checks:
+//!   * `decoderawtransaction "hexstring"`
+//!   * `getrawtransaction "txid" ( verbose )`
+//!   * `sendrawtransaction "hexstring"`
+//!
+//! It also serves the peer and network methods, using the `zebra_network`
+//! address book and peer set:
+//!   * `getpeerinfo`
+//!   * `getconnectioncount`
+//!   * `getnetworkinfo`
+//!   * `addnode "node" "add|remove|onetry"`
+//!   * `disconnectnode "address"`
+//!
+//! Requests are JSON-RPC 2.0 `POST`s, with positional `params`. Batches of
+//! requests are also supported. Like `zcashd`, hashes are displayed as
+//! byte-reversed hex, and request bodies are limited to 32 MiB.
+//!
+//! Clients can subscribe to chain events by sending a `GET` request to
+//! `/events?filter=<types>`, where `<types>` is a comma-separated list of
+//! `tips` (the default), `headers`, `blocks` or `disconnected`. The server
+//! pushes matching events to the client as newline-delimited JSON, until it
+//! disconnects, or falls more than `zebra_state::MAX_UNREAD_CHAIN_EVENTS`
+//! events behind.
+//!
+//! Clients authenticate like they do with `zcashd`, using the `.cookie` file
+//! in the state cache directory, or the configured user and password. Only
+//! localhost and the configured `allow_ips` ranges can connect. Users with
+//! `read_only` permission can't call `addnode`, `disconnectnode` or
+//! `sendrawtransaction`.
+//!
+//! Headers-only nodes don't store transactions, so they answer `getblock`,
+//! `getrawtransaction` and `sendrawtransaction` with an error, and reject
+//! `blocks` event subscriptions.
 #![doc(html_logo_url = "https://www.zfnd.org/images/zebra-icon.png")]
 #![doc(html_root_url = "https://doc.zebra.zfnd.org/zebra_rpc")]
+#![deny(missing_docs)]
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
-    }
+use std::{error, net::SocketAddr, path::Path};
+
+use zebra_chain::Network;
+
+mod auth;
+mod config;
+mod events;
+mod json;
+mod methods;
+mod server;
+mod state;
+
+pub use auth::{cookie_path, Auth, IpRange, Permission, COOKIE_USER};
+pub use config::Config;
+pub use json::codes;
+pub use methods::{PeerSet, Peers};
+pub use server::RpcServer;
+pub use state::{BlockState, ChainState, HeaderState};
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// Start an RPC server for `state` and `peers`, using `config`, and spawn it
+/// on the current tokio runtime.
+///
+/// Writes a new authentication cookie to `cookie_dir`, which is usually the
+/// state cache directory. The cookie is deleted when the server is dropped,
+/// which happens when the tokio runtime shuts down.
+///
+/// Returns the address that the server is listening on.
+pub fn init(
+    config: Config,
+    network: Network,
+    state: ChainState,
+    peers: Peers,
+    cookie_dir: &Path,
+) -> Result<SocketAddr, Error> {
+    let auth = Auth::new(&config, cookie_dir)?;
+    let (addr, server) = RpcServer::new(network, state)
+        .with_peers(peers)
+        .with_auth(auth)
+        .bind(&config.listen_addr)?;
+
+    tokio::spawn(async move {
+        if let Err(e) = server.await {
+            tracing::error!(%e, "RPC server failed");
+        }
+    });
+
+    Ok(addr)
 }
@@ -0,0 +1,471 @@
+//! The zcashd-compatible RPC methods.
+//!
+//! Each method checks its parameters, queries the state, and renders the
+//! result in the same JSON format as `zcashd`.
+
+mod network;
+mod transaction;
+
+pub use network::{PeerSet, Peers};
+
+use transaction::Mempool;
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use tower::{buffer::Buffer, util::BoxService};
+
+use zebra_chain::{
+    block::{BlockHeader, BlockHeaderHash, CompactDifficulty, ExpandedDifficulty},
+    serialization::ZcashSerialize,
+    transaction::TransactionHash,
+    types::BlockHeight,
+    Network,
+};
+use zebra_state::QueryType;
+
+use crate::{
+    auth::Permission,
+    json::{codes, RpcError},
+    state::ChainState,
+};
+
+/// The methods that change the node's peers, or send transactions.
+///
+/// Every other method only needs `Permission::ReadOnly`.
+const CONTROL_METHODS: &[&str] = &["addnode", "disconnectnode", "sendrawtransaction"];
+
+/// The RPC methods, and the services they query.
+#[derive(Clone)]
+pub(crate) struct Methods {
+    network: Network,
+    state: ChainState,
+    peers: Option<Peers>,
+    /// The transaction verifier for `sendrawtransaction`, if the state
+    /// stores full blocks
+    mempool: Option<Mempool>,
+}
+
+/// A verbose block header, in a `getblockheader` or `getblock` response.
+#[derive(Serialize)]
+struct BlockHeaderJson {
+    hash: String,
+    confirmations: i64,
+    height: u32,
+    version: u32,
+    merkleroot: String,
+    finalsaplingroot: String,
+    time: i64,
+    nonce: String,
+    solution: String,
+    bits: String,
+    difficulty: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previousblockhash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nextblockhash: Option<String>,
+}
+
+/// A verbose block, in a `getblock` response.
+#[derive(Serialize)]
+struct BlockJson {
+    #[serde(flatten)]
+    header: BlockHeaderJson,
+    size: usize,
+    tx: Vec<String>,
+}
+
+impl Methods {
+    pub fn new(network: Network, state: ChainState) -> Self {
+        let mempool = match &state {
+            ChainState::Blocks(state) => Some(Buffer::new(
+                BoxService::new(zebra_consensus::mempool::init(network, state.clone())),
+                1,
+            )),
+            ChainState::Headers(_) => None,
+        };
+
+        Self {
+            network,
+            state,
+            peers: None,
+            mempool,
+        }
+    }
+
+    /// Answer the peer and network methods using `peers`.
+    pub fn with_peers(self, peers: Peers) -> Self {
+        Self {
+            peers: Some(peers),
+            ..self
+        }
+    }
+
+    /// Call `method` with `params`, and return its result.
+    pub async fn call(&self, method: &str, params: Value) -> Result<Value, RpcError> {
+        let params = Params::new(params)?;
+
+        match method {
+            "getblockcount" => self.get_block_count(params).await,
+            "getbestblockhash" => self.get_best_block_hash(params).await,
+            "getblockhash" => self.get_block_hash(params).await,
+            "getblockheader" => self.get_block_header(params).await,
+            "getblock" => self.get_block(params).await,
+            "decoderawtransaction" => self.decode_raw_transaction(params).await,
+            "getrawtransaction" => self.get_raw_transaction(params).await,
+            "sendrawtransaction" => self.send_raw_transaction(params).await,
+            "getpeerinfo" => self.get_peer_info(params).await,
+            "getconnectioncount" => self.get_connection_count(params).await,
+            "getnetworkinfo" => self.get_network_info(params).await,
+            "addnode" => self.add_node(params).await,
+            "disconnectnode" => self.disconnect_node(params).await,
+            _ => Err(RpcError::new(
+                codes::METHOD_NOT_FOUND,
+                format!("Method not found: {}", method),
+            )),
+        }
+    }
+
+    /// `getblockcount`: returns the height of the best chain tip.
+    async fn get_block_count(&self, params: Params) -> Result<Value, RpcError> {
+        params.expect_at_most(0)?;
+        let (_, height) = self.state.tip().await.map_err(RpcError::state)?;
+
+        Ok(json!(height.0))
+    }
+
+    /// `getbestblockhash`: returns the hash of the best chain tip.
+    async fn get_best_block_hash(&self, params: Params) -> Result<Value, RpcError> {
+        params.expect_at_most(0)?;
+        let (hash, _) = self.state.tip().await.map_err(RpcError::state)?;
+
+        Ok(json!(display_hash(&hash)))
+    }
+
+    /// `getblockhash height`: returns the hash of the best chain block at
+    /// `height`.
+    async fn get_block_hash(&self, params: Params) -> Result<Value, RpcError> {
+        params.expect_at_most(1)?;
+        let height = self
+            .best_chain_height(params.required(0, "height")?)
+            .await?;
+
+        let (block_header, _) = self
+            .state
+            .header(height.into())
+            .await
+            .map_err(RpcError::state)?;
+
+        Ok(json!(display_hash(&BlockHeaderHash::from(
+            block_header.as_ref()
+        ))))
+    }
+
+    /// `getblockheader "hash" ( verbose )`: returns the block header with
+    /// `hash`, as a JSON object if `verbose` is true (the default), or as
+    /// serialized hex.
+    async fn get_block_header(&self, params: Params) -> Result<Value, RpcError> {
+        params.expect_at_most(2)?;
+        let hash = parse_hash(&params.required::<String>(0, "hash")?)?;
+        let verbose = params.optional(1, "verbose")?.unwrap_or(true);
+
+        if !self.state.contains(hash).await.map_err(RpcError::state)? {
+            return Err(RpcError::block_not_found());
+        }
+        let (block_header, height) = self
+            .state
+            .header(hash.into())
+            .await
+            .map_err(RpcError::state)?;
+
+        if verbose {
+            Ok(json!(self.header_json(&block_header, height).await?))
+        } else {
+            Ok(json!(serialized_hex(block_header.as_ref())?))
+        }
+    }
+
+    /// `getblock "hash|height" ( verbosity )`: returns the block with `hash`,
+    /// or the best chain block at `height`.
+    ///
+    /// With verbosity 0, the block is serialized hex. With verbosity 1 (the
+    /// default), the block is a JSON object, with its transactions as a list
+    /// of hashes.
+    async fn get_block(&self, params: Params) -> Result<Value, RpcError> {
+        params.expect_at_most(2)?;
+        let query = self
+            .block_query(&params.required::<Value>(0, "hash|height")?)
+            .await?;
+        let verbosity: u8 = params.optional(1, "verbosity")?.unwrap_or(1);
+        if verbosity > 1 {
+            return Err(RpcError::new(
+                codes::INVALID_PARAMETER,
+                "verbosity must be 0 or 1",
+            ));
+        }
+
+        if !self.state.stores_blocks() {
+            return Err(RpcError::new(
+                codes::MISC_ERROR,
+                "Block not available (this node only stores block headers)",
+            ));
+        }
+        let block = self.state.block(query).await.map_err(RpcError::state)?;
+
+        let bytes = block.zcash_serialize_to_vec().map_err(RpcError::state)?;
+        if verbosity == 0 {
+            return Ok(json!(hex::encode(bytes)));
+        }
+
+        let height = block
+            .coinbase_height()
+            .ok_or_else(|| RpcError::state("stored block has no coinbase height"))?;
+        Ok(json!(BlockJson {
+            header: self.header_json(&block.header, height).await?,
+            size: bytes.len(),
+            tx: block
+                .transactions
+                .iter()
+                .map(|transaction| display_hex(&TransactionHash::from(transaction.as_ref()).0))
+                .collect(),
+        }))
+    }
+
+    /// Returns `height` as a best chain height, or an error if it is negative,
+    /// or above the tip.
+    async fn best_chain_height(&self, height: i64) -> Result<BlockHeight, RpcError> {
+        let (_, tip_height) = self.state.tip().await.map_err(RpcError::state)?;
+
+        if height < 0 || height > i64::from(tip_height.0) {
+            return Err(RpcError::height_out_of_range());
+        }
+
+        Ok(BlockHeight(height as u32))
+    }
+
+    /// Returns the state query for a `getblock` hash or height parameter.
+    ///
+    /// Like `zcashd`, heights can be numbers or strings, and hashes are
+    /// checked before the block is looked up.
+    async fn block_query(&self, hash_or_height: &Value) -> Result<QueryType, RpcError> {
+        let height = match hash_or_height {
+            Value::String(hash) if hash.len() == 64 => {
+                let hash = parse_hash(hash)?;
+                if !self.state.contains(hash).await.map_err(RpcError::state)? {
+                    return Err(RpcError::block_not_found());
+                }
+                return Ok(hash.into());
+            }
+            Value::String(height) => height.parse::<i64>().ok(),
+            Value::Number(height) => height.as_i64(),
+            _ => None,
+        };
+
+        match height {
+            Some(height) => Ok(self.best_chain_height(height).await?.into()),
+            None => Err(RpcError::new(
+                codes::INVALID_PARAMETER,
+                format!("invalid block hash or height: {}", hash_or_height),
+            )),
+        }
+    }
+
+    /// Returns the verbose JSON for `block_header`, which is at `height`.
+    async fn header_json(
+        &self,
+        block_header: &BlockHeader,
+        height: BlockHeight,
+    ) -> Result<BlockHeaderJson, RpcError> {
+        let hash = BlockHeaderHash::from(block_header);
+        let depth = self.state.depth(hash).await.map_err(RpcError::state)?;
+
+        let nextblockhash = match depth {
+            Some(depth) if depth > 0 => {
+                let (next_header, _) = self
+                    .state
+                    .header(BlockHeight(height.0 + 1).into())
+                    .await
+                    .map_err(RpcError::state)?;
+                Some(display_hash(&BlockHeaderHash::from(next_header.as_ref())))
+            }
+            _ => None,
+        };
+
+        Ok(BlockHeaderJson {
+            hash: display_hash(&hash),
+            // Like zcashd, side chain blocks have -1 confirmations
+            confirmations: depth.map(|depth| i64::from(depth) + 1).unwrap_or(-1),
+            height: height.0,
+            version: block_header.version,
+            merkleroot: display_hex(&block_header.merkle_root_hash.0),
+            finalsaplingroot: display_hex(&block_header.final_sapling_root_hash.0),
+            time: block_header.time.timestamp(),
+            nonce: display_hex(&block_header.nonce),
+            solution: hex::encode(&block_header.solution.0[..]),
+            bits: format!("{:08x}", block_header.bits.0),
+            difficulty: difficulty(block_header.bits, self.network),
+            previousblockhash: if height.0 > 0 {
+                Some(display_hash(&block_header.previous_block_hash))
+            } else {
+                None
+            },
+            nextblockhash,
+        })
+    }
+}
+
+/// Returns the permission that is required to call `method`.
+pub(crate) fn permission(method: &str) -> Permission {
+    if CONTROL_METHODS.contains(&method) {
+        Permission::Control
+    } else {
+        Permission::ReadOnly
+    }
+}
+
+/// The positional parameters of a request.
+struct Params(Vec<Value>);
+
+impl Params {
+    fn new(params: Value) -> Result<Self, RpcError> {
+        match params {
+            Value::Null => Ok(Params(Vec::new())),
+            Value::Array(params) => Ok(Params(params)),
+            _ => Err(RpcError::new(
+                codes::INVALID_REQUEST,
+                "params must be an array",
+            )),
+        }
+    }
+
+    fn expect_at_most(&self, count: usize) -> Result<(), RpcError> {
+        if self.0.len() > count {
+            return Err(RpcError::new(
+                codes::INVALID_PARAMS,
+                format!(
+                    "expected at most {} parameters, but got {}",
+                    count,
+                    self.0.len()
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the parameter at `index`, or an error if it is missing or
+    /// invalid.
+    fn required<T: DeserializeOwned>(&self, index: usize, name: &str) -> Result<T, RpcError> {
+        self.optional(index, name)?.ok_or_else(|| {
+            RpcError::new(codes::INVALID_PARAMS, format!("missing parameter {}", name))
+        })
+    }
+
+    /// Returns the parameter at `index`, `None` if it is missing or null,
+    /// or an error if it is invalid.
+    fn optional<T: DeserializeOwned>(
+        &self,
+        index: usize,
+        name: &str,
+    ) -> Result<Option<T>, RpcError> {
+        match self.0.get(index) {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => T::deserialize(value).map(Some).map_err(|e| {
+                RpcError::new(
+                    codes::INVALID_PARAMS,
+                    format!("invalid parameter {}: {}", name, e),
+                )
+            }),
+        }
+    }
+}
+
+/// Returns `bytes` as byte-reversed hex, which is how zcashd displays hashes.
+pub(crate) fn display_hex(bytes: &[u8; 32]) -> String {
+    let mut bytes = *bytes;
+    bytes.reverse();
+    hex::encode(bytes)
+}
+
+fn display_hash(hash: &BlockHeaderHash) -> String {
+    display_hex(&hash.0)
+}
+
+/// Parses a byte-reversed hex hash.
+pub(crate) fn parse_display_hex(hex_hash: &str) -> Result<[u8; 32], RpcError> {
+    let mut bytes = [0; 32];
+    hex::decode_to_slice(hex_hash, &mut bytes[..]).map_err(|_| {
+        RpcError::new(
+            codes::INVALID_PARAMETER,
+            format!("hash must be 64 hex characters, not {:?}", hex_hash),
+        )
+    })?;
+    bytes.reverse();
+
+    Ok(bytes)
+}
+
+fn parse_hash(hex_hash: &str) -> Result<BlockHeaderHash, RpcError> {
+    parse_display_hex(hex_hash).map(BlockHeaderHash)
+}
+
+fn serialized_hex(value: &impl ZcashSerialize) -> Result<String, RpcError> {
+    Ok(hex::encode(
+        value.zcash_serialize_to_vec().map_err(RpcError::state)?,
+    ))
+}
+
+/// Returns the difficulty of `bits`, as a multiple of the minimum difficulty
+/// on `network`.
+///
+/// Uses the same floating-point calculation as zcashd.
+fn difficulty(bits: CompactDifficulty, network: Network) -> f64 {
+    let limit = ExpandedDifficulty::target_difficulty_limit(network)
+        .to_compact()
+        .0;
+
+    let mut shift = (bits.0 >> 24) & 0xff;
+    let limit_shift = (limit >> 24) & 0xff;
+    let mut difficulty = f64::from(limit & 0x00ff_ffff) / f64::from(bits.0 & 0x00ff_ffff);
+
+    while shift < limit_shift {
+        difficulty *= 256.0;
+        shift += 1;
+    }
+    while shift > limit_shift {
+        difficulty /= 256.0;
+        shift -= 1;
+    }
+
+    difficulty
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_hex_round_trip() {
+        let mut bytes = [0; 32];
+        bytes[0] = 0x08;
+        bytes[30] = 0x04;
+
+        let hex_hash = display_hex(&bytes);
+        assert!(hex_hash.starts_with("0004"));
+        assert!(hex_hash.ends_with("08"));
+        assert_eq!(parse_display_hex(&hex_hash), Ok(bytes));
+
+        assert!(parse_display_hex("00").is_err());
+    }
+
+    #[test]
+    fn minimum_difficulty_is_one() {
+        for &network in &[Network::Mainnet, Network::Testnet] {
+            let limit = ExpandedDifficulty::target_difficulty_limit(network).to_compact();
+            assert!((difficulty(limit, network) - 1.0).abs() < f64::EPSILON);
+        }
+
+        // 256 times harder than the mainnet limit
+        let harder = difficulty(CompactDifficulty(0x1e07_ffff), Network::Mainnet);
+        assert!((harder - 256.0).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,221 @@
+//! The zcashd-compatible network and peer RPC methods.
+//!
+//! Zebra's peer set doesn't keep per-connection statistics, so these methods
+//! report the peer metadata in the address book. Peers that have sent a
+//! message recently are treated as connected.
+
+use std::{
+    error,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tower::{buffer::Buffer, util::BoxService};
+
+use zebra_network::{
+    types::{MetaAddr, PeerServices},
+    AddressBook, PeerSetHandle, Request, Response, CURRENT_VERSION,
+};
+
+use super::{Methods, Params};
+use crate::json::{codes, RpcError};
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// The peer set, which the RPC server uses to send transactions to peers.
+pub type PeerSet = Buffer<BoxService<Request, Response, Error>, Request>;
+
+/// The network services used by the peer RPC methods.
+#[derive(Clone)]
+pub struct Peers {
+    /// The user agent that the node advertises to its peers
+    pub user_agent: String,
+    /// The peers that the node knows about
+    pub address_book: Arc<Mutex<AddressBook>>,
+    /// The control channel for adding and evicting peers
+    pub handle: PeerSetHandle,
+    /// The peer set, for sending transactions to peers
+    pub peer_set: PeerSet,
+}
+
+/// A connected peer, in a `getpeerinfo` response.
+#[derive(Serialize)]
+struct PeerInfoJson {
+    addr: String,
+    services: String,
+    lastrecv: i64,
+}
+
+impl From<MetaAddr> for PeerInfoJson {
+    fn from(meta: MetaAddr) -> Self {
+        Self {
+            addr: meta.addr.to_string(),
+            services: services_hex(meta.services),
+            lastrecv: meta.last_seen.timestamp(),
+        }
+    }
+}
+
+impl Methods {
+    /// `getpeerinfo`: returns the address, services, and last message time
+    /// of each connected peer.
+    pub(super) async fn get_peer_info(&self, params: Params) -> Result<Value, RpcError> {
+        params.expect_at_most(0)?;
+        let peers: Vec<PeerInfoJson> = self
+            .connected_peers()?
+            .into_iter()
+            .map(PeerInfoJson::from)
+            .collect();
+
+        Ok(json!(peers))
+    }
+
+    /// `getconnectioncount`: returns the number of connected peers.
+    pub(super) async fn get_connection_count(&self, params: Params) -> Result<Value, RpcError> {
+        params.expect_at_most(0)?;
+
+        Ok(json!(self.connected_peers()?.len()))
+    }
+
+    /// `getnetworkinfo`: returns the node's network protocol details.
+    pub(super) async fn get_network_info(&self, params: Params) -> Result<Value, RpcError> {
+        params.expect_at_most(0)?;
+        let connections = self.connected_peers()?.len();
+
+        Ok(json!({
+            "subversion": self.peers()?.user_agent,
+            "protocolversion": CURRENT_VERSION.0,
+            "localservices": services_hex(PeerServices::NODE_NETWORK),
+            "timeoffset": 0,
+            "connections": connections,
+            "networks": [],
+            "localaddresses": [],
+            "warnings": "",
+        }))
+    }
+
+    /// `addnode "node" "add|remove|onetry"`: connects to the peer at `node`.
+    ///
+    /// Zebra doesn't keep a list of added peers, so `add` and `onetry` both
+    /// make a single connection attempt, and `remove` is not supported.
+    pub(super) async fn add_node(&self, params: Params) -> Result<Value, RpcError> {
+        params.expect_at_most(2)?;
+        let addr = parse_peer_addr(&params.required::<String>(0, "node")?)?;
+        let command: String = params.required(1, "command")?;
+
+        match command.as_str() {
+            "add" | "onetry" => {
+                self.peers()?
+                    .handle
+                    .add_peer(addr)
+                    .await
+                    .map_err(RpcError::state)?;
+                Ok(Value::Null)
+            }
+            "remove" => Err(RpcError::new(
+                codes::INVALID_PARAMETER,
+                "Zebra doesn't keep a list of added nodes, use disconnectnode instead",
+            )),
+            _ => Err(RpcError::new(
+                codes::INVALID_PARAMETER,
+                format!("command must be add, remove or onetry, not {:?}", command),
+            )),
+        }
+    }
+
+    /// `disconnectnode "address"`: disconnects from the peer at `address`.
+    pub(super) async fn disconnect_node(&self, params: Params) -> Result<Value, RpcError> {
+        params.expect_at_most(1)?;
+        let addr = parse_peer_addr(&params.required::<String>(0, "address")?)?;
+        let peers = self.peers()?;
+
+        let connected = peers
+            .address_book
+            .lock()
+            .expect("mutex must be unpoisoned")
+            .is_potentially_connected(&addr);
+        if !connected {
+            return Err(RpcError::new(
+                codes::NODE_NOT_CONNECTED,
+                "Node not found in connected nodes",
+            ));
+        }
+
+        peers
+            .handle
+            .evict_peer(addr)
+            .await
+            .map_err(RpcError::state)?;
+
+        Ok(Value::Null)
+    }
+
+    pub(super) fn peers(&self) -> Result<&Peers, RpcError> {
+        self.peers.as_ref().ok_or_else(|| {
+            RpcError::new(
+                codes::P2P_DISABLED,
+                "Error: Peer-to-peer functionality missing or disabled",
+            )
+        })
+    }
+
+    /// Returns the address book entries of the connected peers.
+    fn connected_peers(&self) -> Result<Vec<MetaAddr>, RpcError> {
+        Ok(self
+            .peers()?
+            .address_book
+            .lock()
+            .expect("mutex must be unpoisoned")
+            .potentially_connected_peers()
+            .collect())
+    }
+}
+
+/// Returns `services` as 16 hex digits, like zcashd.
+fn services_hex(services: PeerServices) -> String {
+    format!("{:016x}", services.bits())
+}
+
+/// Parses a peer address, which must be an IP address and port.
+fn parse_peer_addr(addr: &str) -> Result<SocketAddr, RpcError> {
+    addr.parse().map_err(|_| {
+        RpcError::new(
+            codes::INVALID_PARAMETER,
+            format!("peer address must be an IP:port, not {:?}", addr),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn peer_info_json() {
+        let meta = MetaAddr {
+            addr: "127.0.0.1:8233".parse().unwrap(),
+            services: PeerServices::NODE_NETWORK,
+            last_seen: Utc.timestamp(1_573_680_222, 0),
+        };
+
+        assert_eq!(
+            json!(PeerInfoJson::from(meta)),
+            json!({
+                "addr": "127.0.0.1:8233",
+                "services": "0000000000000001",
+                "lastrecv": 1_573_680_222,
+            })
+        );
+    }
+
+    #[test]
+    fn peer_addresses_must_be_ips() {
+        assert!(parse_peer_addr("[::1]:8233").is_ok());
+        assert!(parse_peer_addr("127.0.0.1").is_err());
+        assert!(parse_peer_addr("dnsseed.z.cash:8233").is_err());
+    }
+}
@@ -0,0 +1,392 @@
+//! The zcashd-compatible raw transaction RPC methods.
+//!
+//! Zebra doesn't have a mempool yet, so `sendrawtransaction` checks the
+//! transaction against the best chain tip, then advertises it to a few ready
+//! peers. `getrawtransaction` only finds transactions in the best chain.
+//!
+//! Unlike `zcashd`, `sendrawtransaction` doesn't fully validate transactions:
+//! it doesn't check scripts, signatures, proofs, or spent outputs. Peers can
+//! disconnect or ban nodes that relay invalid transactions.
+
+use std::{error, sync::Arc};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tower::{buffer::Buffer, util::BoxService, ServiceExt};
+
+use zebra_chain::{
+    proofs::ZkSnarkProof,
+    serialization::{ReadZcashExt, ZcashDeserialize, ZcashSerialize},
+    transaction::{
+        JoinSplit, Output, Spend, Transaction, TransactionHash, TransparentInput, TransparentOutput,
+    },
+    types::{amount::Amount, LockTime},
+};
+use zebra_consensus::mempool::MempoolError;
+
+use super::{display_hex, parse_display_hex, serialized_hex, Methods, Params};
+use crate::json::{codes, RpcError};
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// The mempool transaction verifier, which checks transactions before they
+/// are sent to peers.
+pub(super) type Mempool =
+    Buffer<BoxService<Arc<Transaction>, TransactionHash, Error>, Arc<Transaction>>;
+
+/// The number of zatoshis in one ZEC.
+const COIN: i64 = 100_000_000;
+
+/// The version group ID of Overwinter transactions.
+const OVERWINTER_VERSION_GROUP_ID: u32 = 0x03C4_8270;
+
+/// The version group ID of Sapling transactions.
+const SAPLING_VERSION_GROUP_ID: u32 = 0x892F_2085;
+
+/// A decoded transaction, in a `decoderawtransaction` or verbose
+/// `getrawtransaction` response.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionJson {
+    txid: String,
+    overwintered: bool,
+    version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    versiongroupid: Option<String>,
+    locktime: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expiryheight: Option<u32>,
+    vin: Vec<Value>,
+    vout: Vec<Value>,
+    vjoinsplit: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value_balance: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value_balance_zat: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    v_shielded_spend: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    v_shielded_output: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    binding_sig: Option<String>,
+}
+
+impl Methods {
+    /// `decoderawtransaction "hexstring"`: returns the transaction in
+    /// `hexstring` as a JSON object.
+    pub(super) async fn decode_raw_transaction(&self, params: Params) -> Result<Value, RpcError> {
+        params.expect_at_most(1)?;
+        let transaction = parse_transaction(&params.required::<String>(0, "hexstring")?)?;
+
+        Ok(json!(transaction_json(&transaction)?))
+    }
+
+    /// `getrawtransaction "txid" ( verbose )`: returns the best chain
+    /// transaction with `txid`, as serialized hex, or as a JSON object if
+    /// `verbose` is non-zero.
+    ///
+    /// Like `zcashd`, `verbose` can be a number or a boolean.
+    pub(super) async fn get_raw_transaction(&self, params: Params) -> Result<Value, RpcError> {
+        params.expect_at_most(2)?;
+        let hash = TransactionHash(parse_display_hex(&params.required::<String>(0, "txid")?)?);
+        let verbose = match params.optional::<Value>(1, "verbose")? {
+            None => false,
+            Some(Value::Bool(verbose)) => verbose,
+            Some(Value::Number(verbose)) => verbose.as_i64() != Some(0),
+            Some(verbose) => {
+                return Err(RpcError::new(
+                    codes::INVALID_PARAMS,
+                    format!("invalid parameter verbose: {}", verbose),
+                ))
+            }
+        };
+
+        if !self.state.stores_blocks() {
+            return Err(RpcError::new(
+                codes::MISC_ERROR,
+                "Transaction not available (this node only stores block headers)",
+            ));
+        }
+        let (transaction, block_hash, height) = self
+            .state
+            .transaction(hash)
+            .await
+            .map_err(RpcError::state)?
+            .ok_or_else(|| {
+                RpcError::new(
+                    codes::INVALID_ADDRESS_OR_KEY,
+                    "No such mempool or blockchain transaction",
+                )
+            })?;
+
+        let bytes = transaction
+            .zcash_serialize_to_vec()
+            .map_err(RpcError::state)?;
+        if !verbose {
+            return Ok(json!(hex::encode(bytes)));
+        }
+
+        let depth = self
+            .state
+            .depth(block_hash)
+            .await
+            .map_err(RpcError::state)?
+            .unwrap_or(0);
+        let mut result = json!(transaction_json(&transaction)?);
+        result["hex"] = json!(hex::encode(bytes));
+        result["blockhash"] = json!(display_hex(&block_hash.0));
+        result["height"] = json!(height.0);
+        result["confirmations"] = json!(depth + 1);
+
+        Ok(result)
+    }
+
+    /// `sendrawtransaction "hexstring"`: checks the transaction in
+    /// `hexstring`, and advertises it to peers.
+    ///
+    /// The mempool checks don't include scripts, signatures, proofs, or spent
+    /// outputs, so callers must only send valid transactions.
+    ///
+    /// Returns the transaction hash.
+    pub(super) async fn send_raw_transaction(&self, params: Params) -> Result<Value, RpcError> {
+        // zcashd's allowhighfees parameter is ignored, because Zebra doesn't
+        // check fees yet
+        params.expect_at_most(2)?;
+        let transaction = Arc::new(parse_transaction(
+            &params.required::<String>(0, "hexstring")?,
+        )?);
+        let peers = self.peers()?;
+        let mempool = self.mempool.clone().ok_or_else(|| {
+            RpcError::new(
+                codes::MISC_ERROR,
+                "Transaction not verified (this node only stores block headers)",
+            )
+        })?;
+
+        let hash = mempool.oneshot(transaction.clone()).await.map_err(|e| {
+            match e.downcast_ref::<MempoolError>() {
+                Some(MempoolError::AlreadyInChain { .. }) => RpcError::new(
+                    codes::VERIFY_ALREADY_IN_CHAIN,
+                    "transaction already in block chain",
+                ),
+                Some(error) => RpcError::new(codes::VERIFY_REJECTED, error.to_string()),
+                None => RpcError::state(&e),
+            }
+        })?;
+
+        peers
+            .peer_set
+            .clone()
+            .oneshot(zebra_network::Request::AdvertiseTransaction(transaction))
+            .await
+            .map_err(RpcError::state)?;
+
+        Ok(json!(display_hex(&hash.0)))
+    }
+}
+
+/// Parses a hex-encoded transaction.
+fn parse_transaction(hex_transaction: &str) -> Result<Transaction, RpcError> {
+    let bytes = hex::decode(hex_transaction)
+        .map_err(|_| RpcError::new(codes::DESERIALIZATION_ERROR, "TX decode failed"))?;
+
+    // zcashd rejects trailing data
+    let mut reader = &bytes[..];
+    let transaction = Transaction::zcash_deserialize(&mut reader).map_err(|e| {
+        RpcError::new(
+            codes::DESERIALIZATION_ERROR,
+            format!("TX decode failed: {}", e),
+        )
+    })?;
+    if !reader.is_empty() {
+        return Err(RpcError::new(
+            codes::DESERIALIZATION_ERROR,
+            "TX decode failed: trailing data",
+        ));
+    }
+
+    Ok(transaction)
+}
+
+/// Returns the JSON for `transaction`, in the same format as `zcashd`.
+fn transaction_json(transaction: &Transaction) -> Result<TransactionJson, RpcError> {
+    let (version, versiongroupid) = match transaction {
+        Transaction::V1 { .. } => (1, None),
+        Transaction::V2 { .. } => (2, None),
+        Transaction::V3 { .. } => (3, Some(OVERWINTER_VERSION_GROUP_ID)),
+        Transaction::V4 { .. } => (4, Some(SAPLING_VERSION_GROUP_ID)),
+    };
+
+    let vjoinsplit = match transaction {
+        Transaction::V2 { joinsplit_data, .. } | Transaction::V3 { joinsplit_data, .. } => {
+            joinsplit_data
+                .iter()
+                .flat_map(|data| data.joinsplits())
+                .map(joinsplit_json)
+                .collect::<Result<_, _>>()?
+        }
+        Transaction::V4 { joinsplit_data, .. } => joinsplit_data
+            .iter()
+            .flat_map(|data| data.joinsplits())
+            .map(joinsplit_json)
+            .collect::<Result<_, _>>()?,
+        Transaction::V1 { .. } => Vec::new(),
+    };
+
+    let mut json = TransactionJson {
+        txid: display_hex(&TransactionHash::from(transaction).0),
+        overwintered: versiongroupid.is_some(),
+        version,
+        versiongroupid: versiongroupid.map(|id| format!("{:08x}", id)),
+        locktime: match transaction.lock_time() {
+            LockTime::Height(height) => height.0,
+            LockTime::Time(time) => time.timestamp() as u32,
+        },
+        expiryheight: transaction.expiry_height().map(|height| height.0),
+        vin: transaction
+            .inputs()
+            .map(input_json)
+            .collect::<Result<_, _>>()?,
+        vout: transaction
+            .outputs()
+            .enumerate()
+            .map(|(n, output)| output_json(n, output))
+            .collect(),
+        vjoinsplit,
+        value_balance: None,
+        value_balance_zat: None,
+        v_shielded_spend: None,
+        v_shielded_output: None,
+        binding_sig: None,
+    };
+
+    if let Transaction::V4 {
+        value_balance,
+        shielded_data,
+        ..
+    } = transaction
+    {
+        json.value_balance = Some(zec(*value_balance));
+        json.value_balance_zat = Some(i64::from(*value_balance));
+        json.v_shielded_spend = Some(
+            shielded_data
+                .iter()
+                .flat_map(|data| data.spends())
+                .map(spend_json)
+                .collect::<Result<_, _>>()?,
+        );
+        json.v_shielded_output = Some(
+            shielded_data
+                .iter()
+                .flat_map(|data| data.outputs())
+                .map(output_description_json)
+                .collect::<Result<_, _>>()?,
+        );
+        json.binding_sig = shielded_data
+            .as_ref()
+            .map(|data| hex::encode(&<[u8; 64]>::from(data.binding_sig)[..]));
+    }
+
+    Ok(json)
+}
+
+fn input_json(input: &TransparentInput) -> Result<Value, RpcError> {
+    match input {
+        TransparentInput::Coinbase { sequence, .. } => {
+            // The coinbase script is the BIP34 height, followed by the
+            // coinbase data, so we extract it from the serialized input
+            let bytes = input.zcash_serialize_to_vec().map_err(RpcError::state)?;
+            let mut script = &bytes[36..bytes.len() - 4];
+            script.read_compactsize().map_err(RpcError::state)?;
+
+            Ok(json!({
+                "coinbase": hex::encode(script),
+                "sequence": sequence,
+            }))
+        }
+        TransparentInput::PrevOut {
+            outpoint,
+            script,
+            sequence,
+        } => Ok(json!({
+            "txid": display_hex(&outpoint.hash.0),
+            "vout": outpoint.index,
+            "scriptSig": { "hex": hex::encode(&script.0) },
+            "sequence": sequence,
+        })),
+    }
+}
+
+fn output_json(n: usize, output: &TransparentOutput) -> Value {
+    json!({
+        "value": zec(output.value),
+        "valueZat": i64::from(output.value),
+        "n": n,
+        "scriptPubKey": { "hex": hex::encode(&output.pk_script.0) },
+    })
+}
+
+fn joinsplit_json<P: ZkSnarkProof>(joinsplit: &JoinSplit<P>) -> Result<Value, RpcError> {
+    Ok(json!({
+        "vpub_old": zec(joinsplit.vpub_old),
+        "vpub_oldZat": i64::from(joinsplit.vpub_old),
+        "vpub_new": zec(joinsplit.vpub_new),
+        "vpub_newZat": i64::from(joinsplit.vpub_new),
+        "anchor": display_hex(&joinsplit.anchor),
+        "nullifiers": [
+            display_hex(&hash_bytes(&joinsplit.nullifiers[0])?),
+            display_hex(&hash_bytes(&joinsplit.nullifiers[1])?),
+        ],
+        "commitments": [
+            display_hex(&joinsplit.commitments[0]),
+            display_hex(&joinsplit.commitments[1]),
+        ],
+        "onetimePubKey": display_hex(joinsplit.ephemeral_key.as_bytes()),
+        "randomSeed": display_hex(&joinsplit.random_seed),
+        "macs": [
+            display_hex(&hash_bytes(&joinsplit.vmacs[0])?),
+            display_hex(&hash_bytes(&joinsplit.vmacs[1])?),
+        ],
+        "proof": serialized_hex(&joinsplit.zkproof)?,
+        "ciphertexts": [
+            serialized_hex(&joinsplit.enc_ciphertexts[0])?,
+            serialized_hex(&joinsplit.enc_ciphertexts[1])?,
+        ],
+    }))
+}
+
+fn spend_json(spend: &Spend) -> Result<Value, RpcError> {
+    Ok(json!({
+        "cv": display_hex(&spend.cv),
+        "anchor": display_hex(&spend.anchor.0),
+        "nullifier": display_hex(&hash_bytes(&spend.nullifier)?),
+        "rk": display_hex(&<[u8; 32]>::from(spend.rk)),
+        "proof": serialized_hex(&spend.zkproof)?,
+        "spendAuthSig": hex::encode(&<[u8; 64]>::from(spend.spend_auth_sig)[..]),
+    }))
+}
+
+fn output_description_json(output: &Output) -> Result<Value, RpcError> {
+    Ok(json!({
+        "cv": display_hex(&output.cv),
+        "cmu": display_hex(&output.cmu),
+        "ephemeralKey": display_hex(&output.ephemeral_key.to_bytes()),
+        "encCiphertext": serialized_hex(&output.enc_ciphertext)?,
+        "outCiphertext": serialized_hex(&output.out_ciphertext)?,
+        "proof": serialized_hex(&output.zkproof)?,
+    }))
+}
+
+/// Returns `amount` in ZEC.
+fn zec<C>(amount: Amount<C>) -> f64 {
+    i64::from(amount) as f64 / COIN as f64
+}
+
+/// Returns the serialized bytes of a 32-byte value.
+fn hash_bytes(value: &impl ZcashSerialize) -> Result<[u8; 32], RpcError> {
+    let bytes = value.zcash_serialize_to_vec().map_err(RpcError::state)?;
+    let mut hash = [0; 32];
+    hash.copy_from_slice(&bytes);
+
+    Ok(hash)
+}
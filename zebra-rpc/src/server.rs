@@ -0,0 +1,329 @@
+//! The JSON-RPC HTTP server.
+
+use std::{
+    error,
+    future::Future,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use hyper::{
+    body::HttpBody,
+    header::{HeaderValue, AUTHORIZATION, CONTENT_LENGTH, WWW_AUTHENTICATE},
+    server::conn::AddrStream,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use serde_json::Value;
+
+use zebra_chain::Network;
+
+use crate::{
+    auth::{Auth, AuthError, Permission},
+    events::{self, Filter, EVENTS_PATH},
+    json::{self, codes, RpcError},
+    methods::{self, Methods, Peers},
+    state::ChainState,
+};
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// The delay before responding to a request with the wrong credentials, which
+/// slows down password guessing.
+const UNAUTHORIZED_DELAY: Duration = Duration::from_millis(250);
+
+/// The maximum size of a request body, in bytes.
+///
+/// This is the same as `zcashd`'s HTTP server limit, so clients can't make
+/// the server buffer unbounded amounts of data.
+const MAX_REQUEST_SIZE: usize = 0x0200_0000;
+
+/// A zcashd-compatible JSON-RPC server.
+///
+/// JSON-RPC requests are `POST`ed to any path. Every JSON-RPC response has
+/// HTTP status 200, including errors. Requests that fail authentication get
+/// an HTTP error status instead.
+///
+/// Clients can also subscribe to chain events by sending a `GET` request to
+/// `/events`.
+#[derive(Clone)]
+pub struct RpcServer {
+    methods: Methods,
+    state: ChainState,
+    auth: Option<Arc<Auth>>,
+}
+
+impl RpcServer {
+    /// Create a server that answers RPC requests using `state`, which
+    /// contains a `network` chain.
+    pub fn new(network: Network, state: ChainState) -> Self {
+        Self {
+            methods: Methods::new(network, state.clone()),
+            state,
+            auth: None,
+        }
+    }
+
+    /// Answer the peer and network RPC methods using `peers`.
+    ///
+    /// Without peers, those methods return a `P2P_DISABLED` error.
+    pub fn with_peers(self, peers: Peers) -> Self {
+        Self {
+            methods: self.methods.with_peers(peers),
+            ..self
+        }
+    }
+
+    /// Only answer requests that are allowed by `auth`.
+    ///
+    /// Without authentication, every client can call every method, so the
+    /// server should only be reachable by trusted clients.
+    pub fn with_auth(self, auth: Auth) -> Self {
+        Self {
+            auth: Some(Arc::new(auth)),
+            ..self
+        }
+    }
+
+    /// Listen for RPC requests on `addr`.
+    ///
+    /// Returns the bound address, and a future that serves requests until it
+    /// fails, or is dropped. Must be called from within a tokio runtime.
+    pub fn bind(
+        self,
+        addr: &SocketAddr,
+    ) -> Result<(SocketAddr, impl Future<Output = Result<(), Error>>), Error> {
+        let service = make_service_fn(move |conn: &AddrStream| {
+            let server = self.clone();
+            let client = conn.remote_addr().ip();
+            async move {
+                Ok::<_, hyper::Error>(service_fn(move |request| {
+                    let server = server.clone();
+                    async move { Ok::<_, hyper::Error>(server.respond(client, request).await) }
+                }))
+            }
+        });
+
+        let server = Server::try_bind(addr)?.serve(service);
+        let local_addr = server.local_addr();
+        tracing::info!(?local_addr, "serving RPC requests");
+
+        Ok((local_addr, async move { Ok(server.await?) }))
+    }
+
+    /// Returns the HTTP response to `request`, which was sent by `client`.
+    async fn respond(&self, client: IpAddr, request: Request<Body>) -> Response<Body> {
+        let permission = match &self.auth {
+            Some(auth) => auth.authorize(client, request.headers().get(AUTHORIZATION)),
+            None => Ok(Permission::Control),
+        };
+        let permission = match permission {
+            Ok(permission) => permission,
+            Err(AuthError::ForbiddenAddress) => {
+                tracing::info!(?client, "rejected RPC request from forbidden address");
+                return error_response(StatusCode::FORBIDDEN, "client address is not allowed");
+            }
+            Err(AuthError::Unauthorized) => {
+                tracing::info!(?client, "rejected RPC request with wrong credentials");
+                tokio::time::delay_for(UNAUTHORIZED_DELAY).await;
+                let mut response =
+                    error_response(StatusCode::UNAUTHORIZED, "incorrect user or password");
+                response.headers_mut().insert(
+                    WWW_AUTHENTICATE,
+                    HeaderValue::from_static("Basic realm=\"jsonrpc\""),
+                );
+                return response;
+            }
+        };
+
+        if request.method() == Method::GET && request.uri().path() == EVENTS_PATH {
+            return self.events(request.uri().query()).await;
+        }
+        if request.method() != Method::POST {
+            return error_response(
+                StatusCode::METHOD_NOT_ALLOWED,
+                "JSON-RPC requests must use POST",
+            );
+        }
+
+        let body = match read_body(request).await {
+            Ok(body) => body,
+            Err(response) => return response,
+        };
+        let body = serde_json::from_slice::<Value>(&body);
+
+        // Like bitcoind's RPC whitelists, reject the whole batch if any of its
+        // methods are forbidden
+        if let Ok(body) = &body {
+            if let Some(method) =
+                requested_methods(body).find(|method| methods::permission(method) > permission)
+            {
+                tracing::info!(?client, %method, "rejected forbidden RPC method");
+                return error_response(
+                    StatusCode::FORBIDDEN,
+                    format!("{} requires control permission", method),
+                );
+            }
+        }
+
+        let response = match body {
+            Ok(Value::Array(batch)) if !batch.is_empty() => {
+                let mut responses = Vec::with_capacity(batch.len());
+                for request in batch {
+                    responses.push(self.call(request).await);
+                }
+                serde_json::to_string(&responses)
+            }
+            Ok(request) => serde_json::to_string(&self.call(request).await),
+            Err(e) => serde_json::to_string(&json::Response::new(
+                Value::Null,
+                Err(RpcError::new(codes::PARSE_ERROR, e.to_string())),
+            )),
+        };
+
+        Response::builder()
+            .status(StatusCode::OK)
+            .header("content-type", "application/json")
+            .body(Body::from(response.expect("responses serialize to JSON")))
+            .expect("response with known status code cannot fail")
+    }
+
+    /// Returns a streaming response, containing the chain events that match
+    /// the filter in `query`.
+    async fn events(&self, query: Option<&str>) -> Response<Body> {
+        let filter = match Filter::from_query(query) {
+            Ok(filter) => filter,
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, e),
+        };
+        if filter.needs_blocks() && !self.state.stores_blocks() {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                "Blocks not available (this node only stores block headers)",
+            );
+        }
+
+        match events::subscribe(self.state.clone(), filter).await {
+            Ok(events) => Response::builder()
+                .status(StatusCode::OK)
+                .header("content-type", "application/x-ndjson")
+                .body(Body::wrap_stream(events))
+                .expect("response with known status code cannot fail"),
+            Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        }
+    }
+
+    /// Returns the JSON-RPC response to a single `request`.
+    async fn call(&self, request: Value) -> json::Response {
+        let request: json::Request = match serde_json::from_value(request) {
+            Ok(request) => request,
+            Err(e) => {
+                return json::Response::new(
+                    Value::Null,
+                    Err(RpcError::new(codes::INVALID_REQUEST, e.to_string())),
+                )
+            }
+        };
+
+        tracing::debug!(method = %request.method, "RPC requested");
+        let result = self.methods.call(&request.method, request.params).await;
+
+        json::Response::new(request.id, result)
+    }
+}
+
+/// Returns the body of `request`, or an HTTP error response if the body can't
+/// be read, or it is larger than `MAX_REQUEST_SIZE`.
+///
+/// Requests with a larger `Content-Length` are rejected before reading the
+/// body. Other requests are rejected as soon as the body exceeds the limit.
+async fn read_body(request: Request<Body>) -> Result<Vec<u8>, Response<Body>> {
+    let too_large = || {
+        error_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("request body is larger than {} bytes", MAX_REQUEST_SIZE),
+        )
+    };
+
+    let content_length = request
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|length| length.to_str().ok())
+        .and_then(|length| length.parse::<u64>().ok());
+    if content_length.map_or(false, |length| length > MAX_REQUEST_SIZE as u64) {
+        return Err(too_large());
+    }
+
+    let mut body = request.into_body();
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|e| error_response(StatusCode::BAD_REQUEST, e.to_string()))?;
+        if bytes.len() + chunk.len() > MAX_REQUEST_SIZE {
+            return Err(too_large());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    Ok(bytes)
+}
+
+/// Returns an HTTP error response with `status` and `message`.
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::from(message.into()))
+        .expect("response with known status code cannot fail")
+}
+
+/// Returns the method names of a request, or a batch of requests.
+fn requested_methods(body: &Value) -> impl Iterator<Item = &str> {
+    let requests = match body {
+        Value::Array(batch) => batch.as_slice(),
+        request => std::slice::from_ref(request),
+    };
+
+    requests
+        .iter()
+        .filter_map(|request| request.get("method").and_then(Value::as_str))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a request with a body of `chunks` chunks, each with
+    /// `chunk_size` bytes.
+    ///
+    /// Streamed bodies don't have a `Content-Length`.
+    fn streamed_request(chunks: usize, chunk_size: usize) -> Request<Body> {
+        let chunks = (0..chunks).map(move |_| Ok::<_, Error>(vec![b' '; chunk_size]));
+        Request::builder()
+            .method(Method::POST)
+            .body(Body::wrap_stream(futures::stream::iter(chunks)))
+            .expect("request is valid")
+    }
+
+    #[tokio::test]
+    async fn request_size_limit() {
+        let body = read_body(streamed_request(32, MAX_REQUEST_SIZE / 32))
+            .await
+            .expect("requests at the limit should be read");
+        assert_eq!(body.len(), MAX_REQUEST_SIZE);
+
+        let response = read_body(streamed_request(33, MAX_REQUEST_SIZE / 32))
+            .await
+            .expect_err("streamed requests over the limit should be rejected");
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // Large requests are rejected without reading the body
+        let request = Request::builder()
+            .method(Method::POST)
+            .header(CONTENT_LENGTH, MAX_REQUEST_SIZE + 1)
+            .body(Body::from("{}"))
+            .expect("request is valid");
+        let response = read_body(request)
+            .await
+            .expect_err("requests with a large Content-Length should be rejected");
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
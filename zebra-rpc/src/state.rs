@@ -0,0 +1,174 @@
+//! The chain state queries used by RPC methods.
+//!
+//! Full nodes and headers-only nodes have different state request types, so
+//! the RPC server wraps either state in a `ChainState`, and converts each
+//! query into the matching state request.
+
+use std::{error, sync::Arc};
+
+use tower::{buffer::Buffer, util::BoxService, Service, ServiceExt};
+use zebra_chain::{
+    block::{Block, BlockHeader, BlockHeaderHash},
+    transaction::{Transaction, TransactionHash},
+    types::BlockHeight,
+};
+use zebra_state::{ChainEventStream, QueryType, RequestBlock, RequestBlockHeader, Response};
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// The block state of a full node.
+pub type BlockState = Buffer<BoxService<RequestBlock, Response, Error>, RequestBlock>;
+
+/// The block header state of a headers-only node.
+pub type HeaderState = Buffer<BoxService<RequestBlockHeader, Response, Error>, RequestBlockHeader>;
+
+/// The state service that answers RPC queries.
+#[derive(Clone)]
+pub enum ChainState {
+    /// A full block state
+    Blocks(BlockState),
+    /// A block header state, which can't answer queries about transactions
+    Headers(HeaderState),
+}
+
+impl ChainState {
+    /// Returns true if this state stores full blocks.
+    pub fn stores_blocks(&self) -> bool {
+        match self {
+            ChainState::Blocks(_) => true,
+            ChainState::Headers(_) => false,
+        }
+    }
+
+    /// Returns the hash and height of the best chain tip.
+    pub(crate) async fn tip(&self) -> Result<(BlockHeaderHash, BlockHeight), Error> {
+        let response = match self.clone() {
+            ChainState::Blocks(state) => call(state, RequestBlock::GetTip).await?,
+            ChainState::Headers(state) => call(state, RequestBlockHeader::GetTip).await?,
+        };
+
+        match response {
+            Response::Tip { hash, height } => Ok((hash, height)),
+            _ => Err("unexpected response to a tip request".into()),
+        }
+    }
+
+    /// Returns the number of blocks above `hash` in the best chain, or `None`
+    /// if `hash` is not in the best chain.
+    pub(crate) async fn depth(&self, hash: BlockHeaderHash) -> Result<Option<u32>, Error> {
+        let response = match self.clone() {
+            ChainState::Blocks(state) => call(state, RequestBlock::GetDepth { hash }).await?,
+            ChainState::Headers(state) => {
+                call(state, RequestBlockHeader::GetDepth { hash }).await?
+            }
+        };
+
+        match response {
+            Response::Depth(depth) => Ok(depth),
+            _ => Err("unexpected response to a depth request".into()),
+        }
+    }
+
+    /// Returns true if the state contains `hash`, in the best chain or a
+    /// side chain.
+    pub(crate) async fn contains(&self, hash: BlockHeaderHash) -> Result<bool, Error> {
+        match self.clone() {
+            // The block state only stores the best chain
+            ChainState::Blocks(_) => Ok(self.depth(hash).await?.is_some()),
+            ChainState::Headers(state) => {
+                match call(state, RequestBlockHeader::Contains { hash }).await? {
+                    Response::Contains(contains) => Ok(contains),
+                    _ => Err("unexpected response to a contains request".into()),
+                }
+            }
+        }
+    }
+
+    /// Returns a stream of the chain events that the state publishes after
+    /// this call.
+    pub(crate) async fn subscribe(&self) -> Result<ChainEventStream, Error> {
+        let response = match self.clone() {
+            ChainState::Blocks(state) => call(state, RequestBlock::Subscribe).await?,
+            ChainState::Headers(state) => call(state, RequestBlockHeader::Subscribe).await?,
+        };
+
+        match response {
+            Response::Subscription { chain_events } => Ok(chain_events.subscribe()),
+            _ => Err("unexpected response to a subscribe request".into()),
+        }
+    }
+
+    /// Returns the block header and height for `query`.
+    ///
+    /// Fails if the block header is not in the state.
+    pub(crate) async fn header(
+        &self,
+        query: QueryType,
+    ) -> Result<(Arc<BlockHeader>, BlockHeight), Error> {
+        match self.clone() {
+            ChainState::Blocks(_) => {
+                let block = self.block(query).await?;
+                let height = block
+                    .coinbase_height()
+                    .ok_or("stored block has no coinbase height")?;
+
+                Ok((Arc::new(block.header), height))
+            }
+            ChainState::Headers(state) => {
+                match call(state, RequestBlockHeader::GetBlockHeader { query }).await? {
+                    Response::BlockHeader {
+                        block_header,
+                        block_height,
+                    } => Ok((block_header, block_height)),
+                    _ => Err("unexpected response to a block header request".into()),
+                }
+            }
+        }
+    }
+
+    /// Returns the block for `query`.
+    ///
+    /// Fails if the block is not in the state, or if this is a block header
+    /// state.
+    pub(crate) async fn block(&self, query: QueryType) -> Result<Arc<Block>, Error> {
+        let state = match self.clone() {
+            ChainState::Blocks(state) => state,
+            ChainState::Headers(_) => return Err("headers-only nodes don't store blocks".into()),
+        };
+
+        match call(state, RequestBlock::GetBlock { query }).await? {
+            Response::Block { block } => Ok(block),
+            _ => Err("unexpected response to a block request".into()),
+        }
+    }
+
+    /// Returns the transaction with `hash`, and the hash and height of the
+    /// best chain block that contains it, or `None` if it is not in the best
+    /// chain.
+    ///
+    /// Fails if this is a block header state.
+    pub(crate) async fn transaction(
+        &self,
+        hash: TransactionHash,
+    ) -> Result<Option<(Arc<Transaction>, BlockHeaderHash, BlockHeight)>, Error> {
+        let state = match self.clone() {
+            ChainState::Blocks(state) => state,
+            ChainState::Headers(_) => {
+                return Err("headers-only nodes don't store transactions".into())
+            }
+        };
+
+        match call(state, RequestBlock::GetTransaction { hash }).await? {
+            Response::Transaction(transaction) => Ok(transaction),
+            _ => Err("unexpected response to a transaction request".into()),
+        }
+    }
+}
+
+/// Send `request` to `state` when it is ready, and return the response.
+async fn call<S, R>(mut state: S, request: R) -> Result<Response, Error>
+where
+    S: Service<R, Response = Response, Error = Error>,
+{
+    state.ready_and().await?.call(request).await
+}
@@ -0,0 +1,185 @@
+use color_eyre::eyre::{eyre, Report};
+use hyper::{body, Body, Client, Method, Request, StatusCode};
+use serde_json::{json, Value};
+use std::{fs, net::SocketAddr, path::Path};
+use tempdir::TempDir;
+
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use tower::{buffer::Buffer, util::BoxService};
+use zebra_chain::Network;
+
+use zebra_rpc::{codes, cookie_path, Auth, ChainState, Config, Permission, RpcServer};
+use zebra_state::in_memory;
+
+/// Starts an RPC server with `auth`, and returns its address.
+fn start_server(auth: Auth) -> Result<SocketAddr, Report> {
+    let state = ChainState::Blocks(Buffer::new(BoxService::new(in_memory::init()), 1));
+    let (addr, server) = RpcServer::new(Network::Mainnet, state)
+        .with_auth(auth)
+        .bind(&"127.0.0.1:0".parse()?)
+        .map_err(|e| eyre!(e))?;
+    tokio::spawn(server);
+
+    Ok(addr)
+}
+
+/// Returns the `user:password` in the cookie file in `dir`.
+fn read_cookie(dir: &Path) -> Result<String, Report> {
+    Ok(fs::read_to_string(cookie_path(dir))?)
+}
+
+/// Posts `body` to the RPC server at `addr`, authenticating with
+/// `credentials`, and returns the HTTP status and body.
+async fn post(
+    addr: SocketAddr,
+    credentials: Option<&str>,
+    body: Value,
+) -> Result<(StatusCode, Vec<u8>), Report> {
+    let mut request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("http://{}/", addr));
+    if let Some(credentials) = credentials {
+        request = request.header(
+            "authorization",
+            format!("Basic {}", base64::encode(credentials)),
+        );
+    }
+
+    let response = Client::new()
+        .request(request.body(Body::from(body.to_string()))?)
+        .await?;
+    let status = response.status();
+    let body = body::to_bytes(response.into_body()).await?;
+
+    Ok((status, body.to_vec()))
+}
+
+/// Returns a request for `method`, without any parameters.
+fn request(method: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": [] })
+}
+
+#[tokio::test]
+async fn cookie_authentication() -> Result<(), Report> {
+    zebra_test::init();
+
+    let dir = TempDir::new("zebra_rpc_cookie")?;
+    let addr = start_server(Auth::new(&Config::default(), dir.path()).map_err(|e| eyre!(e))?)?;
+    let cookie = read_cookie(dir.path())?;
+    assert!(cookie.starts_with("__cookie__:"));
+
+    let (status, _) = post(addr, None, request("getblockcount")).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = post(addr, Some("__cookie__:wrong"), request("getblockcount")).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    // The empty state has no tip, so the method returns a JSON-RPC error
+    let (status, body) = post(addr, Some(&cookie), request("getblockcount")).await?;
+    assert_eq!(status, StatusCode::OK);
+    let response: Value = serde_json::from_slice(&body)?;
+    assert_eq!(response["id"], 1);
+
+    // The cookie can call control methods
+    let (status, body) = post(addr, Some(&cookie), request("addnode")).await?;
+    assert_eq!(status, StatusCode::OK);
+    let response: Value = serde_json::from_slice(&body)?;
+    assert_eq!(response["error"]["code"], codes::INVALID_PARAMS);
+
+    // Each startup writes a new cookie
+    let _new_auth = Auth::new(&Config::default(), dir.path()).map_err(|e| eyre!(e))?;
+    assert_ne!(read_cookie(dir.path())?, cookie);
+    let (status, _) = post(addr, Some(&cookie), request("getblockcount")).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn user_permissions() -> Result<(), Report> {
+    zebra_test::init();
+
+    let dir = TempDir::new("zebra_rpc_cookie")?;
+    let config = Config {
+        user: Some("reader".to_owned()),
+        password: Some("secret".to_owned()),
+        user_permission: Permission::ReadOnly,
+        ..Config::default()
+    };
+    let addr = start_server(Auth::new(&config, dir.path()).map_err(|e| eyre!(e))?)?;
+    let cookie = read_cookie(dir.path())?;
+
+    let (status, _) = post(addr, Some("reader:wrong"), request("getblockcount")).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _) = post(addr, Some("writer:secret"), request("getblockcount")).await?;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, _) = post(addr, Some("reader:secret"), request("getblockcount")).await?;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = post(addr, Some("reader:secret"), request("getpeerinfo")).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    for &method in &["addnode", "disconnectnode", "sendrawtransaction"] {
+        let (status, _) = post(addr, Some("reader:secret"), request(method)).await?;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{}", method);
+
+        let (status, _) = post(addr, Some(&cookie), request(method)).await?;
+        assert_eq!(status, StatusCode::OK, "{}", method);
+    }
+
+    // A forbidden method rejects the whole batch
+    let batch = json!([request("getblockcount"), request("addnode")]);
+    let (status, _) = post(addr, Some("reader:secret"), batch).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let batch = json!([request("getblockcount"), request("getpeerinfo")]);
+    let (status, body) = post(addr, Some("reader:secret"), batch).await?;
+    assert_eq!(status, StatusCode::OK);
+    let responses: Value = serde_json::from_slice(&body)?;
+    assert_eq!(responses[1]["error"]["code"], codes::P2P_DISABLED);
+
+    Ok(())
+}
+
+#[test]
+fn cookie_file_lifetime() -> Result<(), Report> {
+    zebra_test::init();
+
+    let dir = TempDir::new("zebra_rpc_cookie")?;
+    let path = cookie_path(dir.path());
+
+    // An existing cookie file is only readable by the current user after it
+    // is replaced
+    fs::write(&path, "__cookie__:old")?;
+    #[cfg(unix)]
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644))?;
+    let auth = Auth::new(&Config::default(), dir.path()).map_err(|e| eyre!(e))?;
+    #[cfg(unix)]
+    assert_eq!(fs::metadata(&path)?.permissions().mode() & 0o777, 0o600);
+
+    // Dropping an old server doesn't delete a newer server's cookie
+    let new_auth = Auth::new(&Config::default(), dir.path()).map_err(|e| eyre!(e))?;
+    let cookie = read_cookie(dir.path())?;
+    drop(auth);
+    assert_eq!(read_cookie(dir.path())?, cookie);
+
+    drop(new_auth);
+    assert!(!path.exists());
+
+    Ok(())
+}
+
+#[test]
+fn user_requires_password() -> Result<(), Report> {
+    zebra_test::init();
+
+    let dir = TempDir::new("zebra_rpc_cookie")?;
+    let config = Config {
+        user: Some("reader".to_owned()),
+        ..Config::default()
+    };
+    assert!(Auth::new(&config, dir.path()).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,451 @@
+use color_eyre::eyre::{eyre, Report};
+use futures::StreamExt;
+use hyper::{body, Body, Client, Method, Request, StatusCode};
+use serde_json::{json, Value};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tower::{buffer::Buffer, util::BoxService, Service, ServiceExt};
+use zebra_chain::{
+    block::Block,
+    serialization::{ZcashDeserialize, ZcashSerialize},
+    Network,
+};
+
+use zebra_rpc::{codes, ChainState, RpcServer};
+use zebra_state::{in_memory, in_memory_headersonly, RequestBlock, RequestBlockHeader};
+
+/// The mainnet genesis block hash, in zcashd's byte-reversed display order.
+const GENESIS_HASH: &str = "00040fe8ec8471911baa1db1266ea15dd06b4a8a5c453883c000b031973dce08";
+
+/// Returns the mainnet genesis block and block 1.
+fn blocks() -> Result<Vec<Arc<Block>>, Report> {
+    Ok(vec![
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?.into(),
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])?.into(),
+    ])
+}
+
+/// Returns an ephemeral in-memory block state containing `blocks`.
+async fn block_state(blocks: &[Arc<Block>]) -> Result<ChainState, Report> {
+    let mut state = Buffer::new(BoxService::new(in_memory::init()), 1);
+
+    for block in blocks {
+        state
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(RequestBlock::AddBlock {
+                block: block.clone(),
+            })
+            .await
+            .map_err(|e| eyre!(e))?;
+    }
+
+    Ok(ChainState::Blocks(state))
+}
+
+/// Returns an ephemeral in-memory block header state containing the headers
+/// of `blocks`.
+async fn header_state(blocks: &[Arc<Block>]) -> Result<ChainState, Report> {
+    let mut state = Buffer::new(BoxService::new(in_memory_headersonly::init()), 1);
+
+    for block in blocks {
+        state
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(RequestBlockHeader::AddBlockHeader {
+                block_header: Arc::new(block.header),
+            })
+            .await
+            .map_err(|e| eyre!(e))?;
+    }
+
+    Ok(ChainState::Headers(state))
+}
+
+/// Starts an RPC server for `state`, and returns its address.
+fn start_server(state: ChainState) -> Result<SocketAddr, Report> {
+    let (addr, server) = RpcServer::new(Network::Mainnet, state)
+        .bind(&"127.0.0.1:0".parse()?)
+        .map_err(|e| eyre!(e))?;
+    tokio::spawn(server);
+
+    Ok(addr)
+}
+
+/// Posts `body` to the RPC server at `addr`, and returns the JSON response.
+async fn post(addr: SocketAddr, body: String) -> Result<Value, Report> {
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(format!("http://{}/", addr))
+        .body(Body::from(body))?;
+    let response = Client::new().request(request).await?;
+    let body = body::to_bytes(response.into_body()).await?;
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Calls `method` with `params` on the RPC server at `addr`, and returns the
+/// JSON-RPC response.
+async fn rpc(addr: SocketAddr, method: &str, params: Value) -> Result<Value, Report> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": method,
+        "method": method,
+        "params": params,
+    });
+    let response = post(addr, request.to_string()).await?;
+    assert_eq!(response["jsonrpc"], "2.0");
+    assert_eq!(response["id"], method);
+
+    Ok(response)
+}
+
+#[tokio::test]
+async fn chain_methods() -> Result<(), Report> {
+    zebra_test::init();
+
+    let blocks = blocks()?;
+    let addr = start_server(block_state(&blocks).await?)?;
+
+    let count = rpc(addr, "getblockcount", json!([])).await?;
+    assert_eq!(count["result"], 1);
+
+    let best = rpc(addr, "getbestblockhash", Value::Null).await?;
+    let block_1_hash = best["result"].as_str().expect("hashes are strings");
+
+    let genesis = rpc(addr, "getblockhash", json!([0])).await?;
+    assert_eq!(genesis["result"], GENESIS_HASH);
+    let block_1 = rpc(addr, "getblockhash", json!([1])).await?;
+    assert_eq!(block_1["result"], block_1_hash);
+
+    let header = rpc(addr, "getblockheader", json!([GENESIS_HASH])).await?;
+    let header = &header["result"];
+    assert_eq!(header["hash"], GENESIS_HASH);
+    assert_eq!(header["height"], 0);
+    assert_eq!(header["confirmations"], 2);
+    assert_eq!(header["bits"], "1f07ffff");
+    assert_eq!(header["difficulty"], 1.0);
+    assert_eq!(header["nextblockhash"], block_1_hash);
+    assert!(header.get("previousblockhash").is_none());
+
+    let header = rpc(addr, "getblockheader", json!([block_1_hash, false])).await?;
+    let header_hex = header["result"].as_str().expect("hex is a string");
+    assert!(hex::encode(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..]).starts_with(header_hex));
+
+    let block = rpc(addr, "getblock", json!([block_1_hash, 0])).await?;
+    assert_eq!(
+        block["result"],
+        hex::encode(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])
+    );
+
+    let block = rpc(addr, "getblock", json!(["1"])).await?;
+    let block = &block["result"];
+    assert_eq!(block["hash"], block_1_hash);
+    assert_eq!(block["previousblockhash"], GENESIS_HASH);
+    assert_eq!(block["confirmations"], 1);
+    assert!(block.get("nextblockhash").is_none());
+    assert_eq!(block["size"], zebra_test::vectors::BLOCK_MAINNET_1_BYTES.len());
+    // Block 1 only has a coinbase transaction, so its Merkle root is the
+    // coinbase transaction hash
+    assert_eq!(block["tx"], json!([block["merkleroot"]]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn headers_only_methods() -> Result<(), Report> {
+    zebra_test::init();
+
+    let addr = start_server(header_state(&blocks()?).await?)?;
+
+    let count = rpc(addr, "getblockcount", json!([])).await?;
+    assert_eq!(count["result"], 1);
+
+    let header = rpc(addr, "getblockheader", json!([GENESIS_HASH, true])).await?;
+    assert_eq!(header["result"]["confirmations"], 2);
+
+    let block = rpc(addr, "getblock", json!([GENESIS_HASH])).await?;
+    assert_eq!(block["error"]["code"], codes::MISC_ERROR);
+    assert!(block.get("result").is_none());
+
+    let transaction = rpc(addr, "getrawtransaction", json!(["00".repeat(32)])).await?;
+    assert_eq!(transaction["error"]["code"], codes::MISC_ERROR);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn invalid_requests() -> Result<(), Report> {
+    zebra_test::init();
+
+    let addr = start_server(block_state(&blocks()?).await?)?;
+
+    let response = rpc(addr, "getblockhash", json!([2])).await?;
+    assert_eq!(response["error"]["code"], codes::INVALID_PARAMETER);
+
+    let response = rpc(addr, "getblockhash", json!(["zero"])).await?;
+    assert_eq!(response["error"]["code"], codes::INVALID_PARAMS);
+
+    let response = rpc(addr, "getblockcount", json!([1])).await?;
+    assert_eq!(response["error"]["code"], codes::INVALID_PARAMS);
+
+    let missing_hash = "00".repeat(32);
+    let response = rpc(addr, "getblockheader", json!([missing_hash])).await?;
+    assert_eq!(response["error"]["code"], codes::INVALID_ADDRESS_OR_KEY);
+
+    let response = rpc(addr, "getblock", json!([GENESIS_HASH, 2])).await?;
+    assert_eq!(response["error"]["code"], codes::INVALID_PARAMETER);
+
+    let response = rpc(addr, "stop", json!([])).await?;
+    assert_eq!(response["error"]["code"], codes::METHOD_NOT_FOUND);
+
+    let response = post(addr, "{".to_owned()).await?;
+    assert_eq!(response["error"]["code"], codes::PARSE_ERROR);
+
+    let response = post(addr, "[]".to_owned()).await?;
+    assert_eq!(response["error"]["code"], codes::INVALID_REQUEST);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn batch_requests() -> Result<(), Report> {
+    zebra_test::init();
+
+    let addr = start_server(block_state(&blocks()?).await?)?;
+
+    let batch = json!([
+        { "jsonrpc": "2.0", "id": 1, "method": "getblockhash", "params": [0] },
+        { "jsonrpc": "2.0", "id": 2, "method": "getblockcount" },
+        { "jsonrpc": "2.0", "id": 3, "method": "stop" },
+    ]);
+    let responses = post(addr, batch.to_string()).await?;
+
+    assert_eq!(responses[0]["id"], 1);
+    assert_eq!(responses[0]["result"], GENESIS_HASH);
+    assert_eq!(responses[1]["id"], 2);
+    assert_eq!(responses[1]["result"], 1);
+    assert_eq!(responses[2]["id"], 3);
+    assert_eq!(responses[2]["error"]["code"], codes::METHOD_NOT_FOUND);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn peer_methods_without_network() -> Result<(), Report> {
+    zebra_test::init();
+
+    let addr = start_server(block_state(&blocks()?).await?)?;
+
+    for &(method, ref params) in &[
+        ("getpeerinfo", json!([])),
+        ("getconnectioncount", json!([])),
+        ("getnetworkinfo", json!([])),
+        ("addnode", json!(["127.0.0.1:8233", "onetry"])),
+        ("disconnectnode", json!(["127.0.0.1:8233"])),
+    ] {
+        let response = rpc(addr, method, params.clone()).await?;
+        assert_eq!(response["error"]["code"], codes::P2P_DISABLED);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn raw_transaction_methods() -> Result<(), Report> {
+    zebra_test::init();
+
+    let addr = start_server(block_state(&blocks()?).await?)?;
+
+    let block_1 = rpc(addr, "getblock", json!([1])).await?;
+    let block_1 = &block_1["result"];
+    let coinbase_txid = block_1["tx"][0].clone();
+
+    let raw = rpc(addr, "getrawtransaction", json!([coinbase_txid])).await?;
+    let raw = raw["result"]
+        .as_str()
+        .expect("raw transactions are strings");
+
+    let decoded = rpc(addr, "decoderawtransaction", json!([raw])).await?;
+    let decoded = &decoded["result"];
+    assert_eq!(decoded["txid"], coinbase_txid);
+    assert_eq!(decoded["version"], 1);
+    assert_eq!(decoded["overwintered"], false);
+    assert!(decoded["vin"][0]["coinbase"].is_string());
+    assert_eq!(decoded["vout"][0]["n"], 0);
+
+    let verbose = rpc(addr, "getrawtransaction", json!([coinbase_txid, 1])).await?;
+    let verbose = &verbose["result"];
+    assert_eq!(verbose["txid"], coinbase_txid);
+    assert_eq!(verbose["hex"], raw);
+    assert_eq!(verbose["blockhash"], block_1["hash"]);
+    assert_eq!(verbose["height"], 1);
+    assert_eq!(verbose["confirmations"], 1);
+
+    let missing_txid = "00".repeat(32);
+    let response = rpc(addr, "getrawtransaction", json!([missing_txid])).await?;
+    assert_eq!(response["error"]["code"], codes::INVALID_ADDRESS_OR_KEY);
+
+    let response = rpc(addr, "decoderawtransaction", json!(["00"])).await?;
+    assert_eq!(response["error"]["code"], codes::DESERIALIZATION_ERROR);
+
+    let response = rpc(addr, "decoderawtransaction", json!([format!("{}00", raw)])).await?;
+    assert_eq!(response["error"]["code"], codes::DESERIALIZATION_ERROR);
+
+    let response = rpc(addr, "sendrawtransaction", json!([raw])).await?;
+    assert_eq!(response["error"]["code"], codes::P2P_DISABLED);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn decode_shielded_transactions() -> Result<(), Report> {
+    zebra_test::init();
+
+    let addr = start_server(block_state(&blocks()?).await?)?;
+
+    let overwinter_block =
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])?;
+    let sapling_block =
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_434873_BYTES[..])?;
+
+    for transaction in &overwinter_block.transactions {
+        let raw = hex::encode(transaction.zcash_serialize_to_vec()?);
+        let decoded = rpc(addr, "decoderawtransaction", json!([raw])).await?;
+        let decoded = &decoded["result"];
+        assert_eq!(decoded["version"], 3);
+        assert_eq!(decoded["versiongroupid"], "03c48270");
+        assert!(decoded["expiryheight"].is_number());
+        assert!(decoded.get("valueBalance").is_none());
+    }
+
+    for transaction in &sapling_block.transactions {
+        let raw = hex::encode(transaction.zcash_serialize_to_vec()?);
+        let decoded = rpc(addr, "decoderawtransaction", json!([raw])).await?;
+        let decoded = &decoded["result"];
+        assert_eq!(decoded["version"], 4);
+        assert_eq!(decoded["versiongroupid"], "892f2085");
+        assert!(decoded["valueBalanceZat"].is_number());
+        assert!(decoded["vShieldedSpend"].is_array());
+        assert!(decoded["vShieldedOutput"].is_array());
+    }
+
+    Ok(())
+}
+
+/// Reads newline-delimited JSON from an HTTP response body.
+struct JsonLines {
+    body: Body,
+    buffer: Vec<u8>,
+}
+
+impl JsonLines {
+    /// Returns the next line of JSON, or an error if the body ends, or the
+    /// next line takes too long.
+    async fn next(&mut self) -> Result<Value, Report> {
+        loop {
+            if let Some(end) = self.buffer.iter().position(|&byte| byte == b'\n') {
+                let line: Vec<u8> = self.buffer.drain(..=end).collect();
+                return Ok(serde_json::from_slice(&line)?);
+            }
+
+            let chunk = tokio::time::timeout(Duration::from_secs(10), self.body.next())
+                .await?
+                .ok_or_else(|| eyre!("event stream ended"))??;
+            self.buffer.extend_from_slice(&chunk);
+        }
+    }
+}
+
+/// Subscribes to the chain events matching `filter` on the RPC server at
+/// `addr`, and returns the HTTP status and event stream.
+async fn subscribe(addr: SocketAddr, filter: &str) -> Result<(StatusCode, JsonLines), Report> {
+    let uri = format!("http://{}/events?filter={}", addr, filter).parse()?;
+    let response = Client::new().get(uri).await?;
+
+    Ok((
+        response.status(),
+        JsonLines {
+            body: response.into_body(),
+            buffer: Vec::new(),
+        },
+    ))
+}
+
+#[tokio::test]
+async fn chain_event_stream() -> Result<(), Report> {
+    zebra_test::init();
+
+    let blocks = blocks()?;
+    let state = block_state(&blocks[..1]).await?;
+    let addr = start_server(state.clone())?;
+
+    let (status, mut events) = subscribe(addr, "tips,headers,blocks").await?;
+    assert_eq!(status, StatusCode::OK);
+    // The stream starts with the current tip
+    assert_eq!(
+        events.next().await?,
+        json!({ "type": "tip", "hash": GENESIS_HASH, "height": 0 })
+    );
+
+    let (status, mut tips) = subscribe(addr, "tips").await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tips.next().await?["height"], 0);
+
+    match state {
+        ChainState::Blocks(mut state) => {
+            state
+                .ready_and()
+                .await
+                .map_err(|e| eyre!(e))?
+                .call(RequestBlock::AddBlock {
+                    block: blocks[1].clone(),
+                })
+                .await
+                .map_err(|e| eyre!(e))?;
+        }
+        ChainState::Headers(_) => unreachable!("test state stores blocks"),
+    }
+
+    let connected = events.next().await?;
+    assert_eq!(connected["type"], "connected");
+    assert_eq!(connected["height"], 1);
+    assert_eq!(
+        connected["header"],
+        hex::encode(blocks[1].header.zcash_serialize_to_vec()?)
+    );
+    assert_eq!(
+        connected["block"],
+        hex::encode(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])
+    );
+
+    let tip = events.next().await?;
+    assert_eq!(tip["type"], "tip");
+    assert_eq!(tip["hash"], connected["hash"]);
+    assert_eq!(tip["height"], 1);
+
+    // Clients without the headers or blocks filters only get the tip
+    assert_eq!(tips.next().await?, tip);
+
+    let (status, _) = subscribe(addr, "mempool").await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn headers_only_chain_event_stream() -> Result<(), Report> {
+    zebra_test::init();
+
+    let addr = start_server(header_state(&blocks()?).await?)?;
+
+    let (status, mut events) = subscribe(addr, "tips,headers,disconnected").await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(events.next().await?["height"], 1);
+
+    let (status, _) = subscribe(addr, "blocks").await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
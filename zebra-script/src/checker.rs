@@ -0,0 +1,253 @@
+//! Checks signatures and lock times against a spending transaction.
+
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use secp256k1::{Message, PublicKey, Secp256k1, Signature, VerifyOnly};
+
+use zebra_chain::{
+    parameters::ConsensusBranchId,
+    transaction::{HashType, Transaction, TransparentInput},
+    types::{
+        amount::{Amount, NonNegative},
+        LockTime, Script,
+    },
+};
+
+use crate::SignatureChecker;
+
+/// Lock times below this threshold are block heights, and lock times at or
+/// above it are Unix times.
+const LOCK_TIME_THRESHOLD: i64 = 500_000_000;
+
+/// The sequence number of a final input.
+///
+/// Lock times don't apply to transactions where every input is final, so
+/// `OP_CHECKLOCKTIMEVERIFY` fails on final inputs.
+const SEQUENCE_FINAL: u32 = 0xffff_ffff;
+
+/// A shared secp256k1 context, because contexts are expensive to create.
+static SECP256K1: Lazy<Secp256k1<VerifyOnly>> = Lazy::new(Secp256k1::verification_only);
+
+/// A `SignatureChecker` for one transparent input of a transaction.
+///
+/// Signatures are secp256k1 ECDSA signatures of the transaction's signature
+/// hash. Like `zcashd`, signatures are parsed using lax DER rules, and
+/// normalized to a low S value before verification, so the interpreter's
+/// flags decide which encodings are valid.
+pub struct TransactionSignatureChecker {
+    /// The spending transaction.
+    transaction: Arc<Transaction>,
+    /// The index of the spending input in `transaction`.
+    input_index: usize,
+    /// The value of the output that the input spends.
+    value: Amount<NonNegative>,
+    /// The consensus branch id of the network upgrade that the transaction is
+    /// mined in.
+    branch_id: ConsensusBranchId,
+}
+
+impl TransactionSignatureChecker {
+    /// Returns a checker for input `input_index` of `transaction`, which
+    /// spends an output with `value`, under the network upgrade with
+    /// consensus `branch_id`.
+    pub fn new(
+        transaction: Arc<Transaction>,
+        input_index: usize,
+        value: Amount<NonNegative>,
+        branch_id: ConsensusBranchId,
+    ) -> Self {
+        TransactionSignatureChecker {
+            transaction,
+            input_index,
+            value,
+            branch_id,
+        }
+    }
+}
+
+impl SignatureChecker for TransactionSignatureChecker {
+    fn check_signature(&self, signature: &[u8], public_key: &[u8], script_code: &Script) -> bool {
+        // The hash type is the last byte of the signature
+        let (hash_type, signature) = match signature.split_last() {
+            Some((hash_type, signature)) => (HashType(u32::from(*hash_type)), signature),
+            None => return false,
+        };
+
+        let public_key = match PublicKey::from_slice(public_key) {
+            Ok(public_key) => public_key,
+            Err(_) => return false,
+        };
+        let mut signature = match Signature::from_der_lax(signature) {
+            Ok(signature) => signature,
+            Err(_) => return false,
+        };
+        signature.normalize_s();
+
+        let sighash = match self.transaction.sighash(
+            self.branch_id,
+            hash_type,
+            Some((self.input_index, script_code, self.value)),
+        ) {
+            Some(sighash) => sighash,
+            None => return false,
+        };
+        let message =
+            Message::from_slice(sighash.as_ref()).expect("signature hashes are 32 bytes long");
+
+        SECP256K1.verify(&message, &signature, &public_key).is_ok()
+    }
+
+    fn check_lock_time(&self, lock_time: i64) -> bool {
+        let transaction_lock_time = match self.transaction.lock_time() {
+            LockTime::Height(height) => i64::from(height.0),
+            LockTime::Time(time) => time.timestamp(),
+        };
+
+        // Heights and times can't be compared
+        if (lock_time < LOCK_TIME_THRESHOLD) != (transaction_lock_time < LOCK_TIME_THRESHOLD) {
+            return false;
+        }
+
+        if lock_time > transaction_lock_time {
+            return false;
+        }
+
+        let sequence = match self.transaction.inputs().nth(self.input_index) {
+            Some(TransparentInput::PrevOut { sequence, .. }) => *sequence,
+            Some(TransparentInput::Coinbase { sequence, .. }) => *sequence,
+            None => return false,
+        };
+
+        sequence != SEQUENCE_FINAL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    use zebra_chain::{block::Block, serialization::ZcashDeserialize};
+
+    use crate::{verify_script, Flags, ScriptError};
+
+    /// The consensus branch id of the Sapling network upgrade.
+    const SAPLING_BRANCH_ID: ConsensusBranchId = ConsensusBranchId(0x76b8_09bb);
+
+    /// Returns the transactions in mainnet block 434873.
+    ///
+    /// Transaction 8 spends output 1 of transaction 7, and transaction 11
+    /// has a lock time and non-final inputs.
+    fn transactions() -> Vec<Arc<Transaction>> {
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_434873_BYTES[..])
+            .expect("block should deserialize")
+            .transactions
+    }
+
+    /// Verifies input 0 of `transaction`, which spends `spent_transaction`'s
+    /// output 1.
+    fn verify(
+        transaction: &Arc<Transaction>,
+        spent_transaction: &Transaction,
+        value: Amount<NonNegative>,
+        branch_id: ConsensusBranchId,
+    ) -> Result<(), ScriptError> {
+        let script_sig = match transaction.inputs().next() {
+            Some(TransparentInput::PrevOut { script, .. }) => script,
+            _ => panic!("transaction should spend a transparent output"),
+        };
+        let script_pubkey = &spent_transaction
+            .outputs()
+            .nth(1)
+            .expect("spent output should exist")
+            .pk_script;
+        let checker = TransactionSignatureChecker::new(transaction.clone(), 0, value, branch_id);
+
+        verify_script(script_sig, script_pubkey, Flags::STANDARD, &checker)
+    }
+
+    #[test]
+    fn mainnet_signatures() {
+        let transactions = transactions();
+        let value = Amount::try_from(1_215_626_430).expect("value is valid");
+
+        verify(&transactions[8], &transactions[7], value, SAPLING_BRANCH_ID)
+            .expect("mainnet signatures should be valid");
+
+        // Signature hashes commit to the spent value and the branch id
+        let wrong_value = Amount::try_from(1_215_626_431).expect("value is valid");
+        assert_eq!(
+            verify(
+                &transactions[8],
+                &transactions[7],
+                wrong_value,
+                SAPLING_BRANCH_ID
+            ),
+            Err(ScriptError::EvalFalse)
+        );
+        assert_eq!(
+            verify(
+                &transactions[8],
+                &transactions[7],
+                value,
+                ConsensusBranchId(0x5ba8_1b19)
+            ),
+            Err(ScriptError::EvalFalse)
+        );
+
+        // The signature doesn't match the public key hash of another output
+        assert_eq!(
+            verify(&transactions[8], &transactions[5], value, SAPLING_BRANCH_ID),
+            Err(ScriptError::EqualVerify)
+        );
+    }
+
+    #[test]
+    fn invalid_signatures() {
+        let transactions = transactions();
+        let value = Amount::try_from(1_215_626_430).expect("value is valid");
+        let checker =
+            TransactionSignatureChecker::new(transactions[8].clone(), 0, value, SAPLING_BRANCH_ID);
+        let script_code = Script(Vec::new());
+
+        assert!(!checker.check_signature(&[], &[], &script_code));
+        assert!(!checker.check_signature(&[0x30, 0x01], &[0x02; 33], &script_code));
+
+        // Inputs that don't exist can't be signed
+        let checker =
+            TransactionSignatureChecker::new(transactions[8].clone(), 1, value, SAPLING_BRANCH_ID);
+        let script_sig = match transactions[8].inputs().next() {
+            Some(TransparentInput::PrevOut { script, .. }) => script.0.clone(),
+            _ => panic!("transaction should spend a transparent output"),
+        };
+        let signature = &script_sig[1..1 + usize::from(script_sig[0])];
+        let public_key = &script_sig[2 + usize::from(script_sig[0])..];
+        assert!(!checker.check_signature(signature, public_key, &script_code));
+    }
+
+    #[test]
+    fn lock_times() {
+        let transactions = transactions();
+        let value = Amount::try_from(0).expect("value is valid");
+
+        // Transaction 11 has a lock time of 434862, and non-final inputs
+        let checker =
+            TransactionSignatureChecker::new(transactions[11].clone(), 0, value, SAPLING_BRANCH_ID);
+        assert!(checker.check_lock_time(0));
+        assert!(checker.check_lock_time(434_862));
+        assert!(!checker.check_lock_time(434_863));
+        assert!(!checker.check_lock_time(LOCK_TIME_THRESHOLD));
+
+        // Lock times don't apply to final inputs
+        let checker =
+            TransactionSignatureChecker::new(transactions[8].clone(), 0, value, SAPLING_BRANCH_ID);
+        assert!(!checker.check_lock_time(0));
+
+        // Inputs that don't exist don't satisfy any lock time
+        let checker =
+            TransactionSignatureChecker::new(transactions[11].clone(), 3, value, SAPLING_BRANCH_ID);
+        assert!(!checker.check_lock_time(0));
+    }
+}
@@ -0,0 +1,78 @@
+//! Script verification errors.
+
+/// The reason a script failed verification.
+///
+/// The variants match the script errors in `zcashd`.
+#[derive(thiserror::Error, Debug, displaydoc::Display, Copy, Clone, Eq, PartialEq)]
+pub enum ScriptError {
+    /// script evaluated without error but finished with a false or empty top stack element
+    EvalFalse,
+    /// OP_RETURN was encountered
+    OpReturn,
+
+    // Limits
+    /// script is larger than the maximum size
+    ScriptSize,
+    /// attempted to push a value larger than the maximum element size
+    PushSize,
+    /// script has more than the maximum number of operations
+    OpCount,
+    /// stack size limit exceeded
+    StackSize,
+    /// signature count is negative or greater than the public key count
+    SigCount,
+    /// public key count is negative or greater than the maximum
+    PubkeyCount,
+
+    // Failed verify operations
+    /// script failed an OP_VERIFY operation
+    Verify,
+    /// script failed an OP_EQUALVERIFY operation
+    EqualVerify,
+    /// script failed an OP_CHECKMULTISIGVERIFY operation
+    CheckMultisigVerify,
+    /// script failed an OP_CHECKSIGVERIFY operation
+    CheckSigVerify,
+    /// script failed an OP_NUMEQUALVERIFY operation
+    NumEqualVerify,
+
+    // Logical and format errors
+    /// opcode missing or not understood
+    BadOpcode,
+    /// attempted to use a disabled opcode
+    DisabledOpcode,
+    /// operation not valid with the current stack size
+    InvalidStackOperation,
+    /// operation not valid with the current altstack size
+    InvalidAltstackOperation,
+    /// invalid OP_IF construction
+    UnbalancedConditional,
+    /// script number overflow
+    NumOverflow,
+    /// NOPx reserved for soft-fork upgrades
+    DiscourageUpgradableNops,
+
+    // OP_CHECKLOCKTIMEVERIFY
+    /// negative locktime
+    NegativeLockTime,
+    /// locktime requirement not satisfied
+    UnsatisfiedLockTime,
+
+    // Malleability
+    /// signature hash type missing or not understood
+    SigHashType,
+    /// non-canonical DER signature
+    SigDer,
+    /// data push or number larger than necessary
+    MinimalData,
+    /// non-canonical signature: S value is unnecessarily high
+    SigHighS,
+    /// dummy CHECKMULTISIG argument must be zero
+    SigNullDummy,
+    /// only push operators allowed in signatures
+    SigPushOnly,
+    /// public key is neither compressed or uncompressed
+    PubKeyType,
+    /// extra items left on stack after execution
+    CleanStack,
+}
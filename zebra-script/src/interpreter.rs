@@ -0,0 +1,902 @@
+//! The transparent script interpreter.
+//!
+//! This interpreter follows `zcashd`'s `EvalScript` and `VerifyScript`, which
+//! are based on Bitcoin Core 0.11. Rules that aren't part of consensus are
+//! enabled by `Flags`, so the same interpreter can check blocks and mempool
+//! transactions.
+
+use bitflags::bitflags;
+use ripemd160::Ripemd160;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use zebra_chain::types::Script;
+
+use crate::{
+    num::{ScriptNum, DEFAULT_MAX_NUM_SIZE, LOCK_TIME_MAX_NUM_SIZE},
+    opcodes::*,
+    signature, ScriptError,
+};
+
+/// The maximum size of a script, in bytes.
+pub const MAX_SCRIPT_SIZE: usize = 10_000;
+
+/// The maximum size of a value pushed to the stack, in bytes.
+pub const MAX_SCRIPT_ELEMENT_SIZE: usize = 520;
+
+/// The maximum number of non-push operations in a script.
+pub const MAX_OPS_PER_SCRIPT: usize = 201;
+
+/// The maximum number of public keys in an `OP_CHECKMULTISIG`.
+pub const MAX_PUBKEYS_PER_MULTISIG: i32 = 20;
+
+/// The maximum number of elements on the stack and altstack, combined.
+pub const MAX_STACK_SIZE: usize = 1000;
+
+bitflags! {
+    /// The rules that the interpreter checks, in addition to the rules that
+    /// every script follows.
+    pub struct Flags: u32 {
+        /// Evaluate pay-to-script-hash subscripts, as specified in BIP 16.
+        const P2SH = 1 << 0;
+        /// Require signatures to be strict DER with a defined hash type, and
+        /// public keys to be compressed or uncompressed secp256k1 keys.
+        const STRICT_ENC = 1 << 1;
+        /// Require signatures to be strict DER, as specified in BIP 66.
+        const STRICT_DER = 1 << 2;
+        /// Require signatures to have a low S value, as specified in BIP 62.
+        const LOW_S = 1 << 3;
+        /// Require `OP_CHECKMULTISIG`'s extra dummy argument to be empty, as
+        /// specified in BIP 62.
+        const NULL_DUMMY = 1 << 4;
+        /// Require pushes and number operands to use their shortest encoding,
+        /// as specified in BIP 62.
+        const MINIMAL_DATA = 1 << 6;
+        /// Reject the `OP_NOP` opcodes that are reserved for soft-fork
+        /// upgrades.
+        const DISCOURAGE_UPGRADABLE_NOPS = 1 << 7;
+        /// Require exactly one stack element after evaluation, as specified
+        /// in BIP 62. Only checked with `P2SH`.
+        const CLEAN_STACK = 1 << 8;
+        /// Evaluate `OP_CHECKLOCKTIMEVERIFY`, as specified in BIP 65.
+        /// Otherwise, it is `OP_NOP2`.
+        const CHECK_LOCK_TIME_VERIFY = 1 << 9;
+
+        /// The rules that every Zcash block follows.
+        const CONSENSUS = Self::P2SH.bits
+            | Self::STRICT_DER.bits
+            | Self::CHECK_LOCK_TIME_VERIFY.bits;
+        /// The rules that `zcashd` applies to mempool transactions, which are
+        /// its `STANDARD_SCRIPT_VERIFY_FLAGS`.
+        const STANDARD = Self::CONSENSUS.bits
+            | Self::STRICT_ENC.bits
+            | Self::LOW_S.bits
+            | Self::NULL_DUMMY.bits
+            | Self::MINIMAL_DATA.bits
+            | Self::DISCOURAGE_UPGRADABLE_NOPS.bits
+            | Self::CLEAN_STACK.bits;
+    }
+}
+
+/// Checks signatures and lock times against the spending transaction.
+pub trait SignatureChecker {
+    /// Returns true if `signature` is a valid signature by `public_key` of the
+    /// spending transaction, with `script_code` as the script being spent.
+    ///
+    /// `signature` is the raw stack element: a DER signature followed by the
+    /// hash type byte. It may be empty, or have any encoding that the
+    /// interpreter's flags allow.
+    fn check_signature(&self, signature: &[u8], public_key: &[u8], script_code: &Script) -> bool;
+
+    /// Returns true if the spending transaction satisfies `lock_time`, as
+    /// specified in BIP 65.
+    ///
+    /// `lock_time` is never negative.
+    fn check_lock_time(&self, lock_time: i64) -> bool;
+}
+
+/// The stack of a script evaluation.
+pub type Stack = Vec<Vec<u8>>;
+
+/// Verifies that `script_sig` satisfies `script_pubkey`, using `flags` and
+/// `checker`.
+pub fn verify_script(
+    script_sig: &Script,
+    script_pubkey: &Script,
+    flags: Flags,
+    checker: &dyn SignatureChecker,
+) -> Result<(), ScriptError> {
+    let mut stack = Stack::new();
+    eval_script(&mut stack, script_sig, flags, checker)?;
+
+    let p2sh_stack = if flags.contains(Flags::P2SH) {
+        Some(stack.clone())
+    } else {
+        None
+    };
+
+    eval_script(&mut stack, script_pubkey, flags, checker)?;
+    check_result(&stack)?;
+
+    if let Some(mut p2sh_stack) = p2sh_stack {
+        if is_pay_to_script_hash(script_pubkey) {
+            if !is_push_only(script_sig) {
+                return Err(ScriptError::SigPushOnly);
+            }
+
+            // The script_pubkey has already checked that the redeem script is
+            // on the stack
+            let redeem_script = Script(p2sh_stack.pop().ok_or(ScriptError::EvalFalse)?);
+            eval_script(&mut p2sh_stack, &redeem_script, flags, checker)?;
+            check_result(&p2sh_stack)?;
+            stack = p2sh_stack;
+        }
+
+        if flags.contains(Flags::CLEAN_STACK) && stack.len() != 1 {
+            return Err(ScriptError::CleanStack);
+        }
+    }
+
+    Ok(())
+}
+
+/// Evaluates `script` on `stack`, using `flags` and `checker`.
+pub fn eval_script(
+    stack: &mut Stack,
+    script: &Script,
+    flags: Flags,
+    checker: &dyn SignatureChecker,
+) -> Result<(), ScriptError> {
+    if script.0.len() > MAX_SCRIPT_SIZE {
+        return Err(ScriptError::ScriptSize);
+    }
+
+    let mut altstack = Stack::new();
+    // Whether each enclosing conditional branch is executed
+    let mut exec_stack: Vec<bool> = Vec::new();
+    let mut op_count = 0;
+    // The start of the script code for signature checks
+    let mut code_start = 0;
+
+    let mut instructions = Instructions::new(&script.0);
+    while let Some(instruction) = instructions.next() {
+        let (opcode, push_value) = instruction?;
+        let executing = exec_stack.iter().all(|&branch| branch);
+
+        if push_value.len() > MAX_SCRIPT_ELEMENT_SIZE {
+            return Err(ScriptError::PushSize);
+        }
+
+        if opcode > MAX_FREE_OPCODE {
+            op_count += 1;
+            if op_count > MAX_OPS_PER_SCRIPT {
+                return Err(ScriptError::OpCount);
+            }
+        }
+
+        if is_disabled(opcode) {
+            return Err(ScriptError::DisabledOpcode);
+        }
+
+        if opcode <= OP_PUSHDATA4 {
+            if executing {
+                if flags.contains(Flags::MINIMAL_DATA) && !is_minimal_push(opcode, push_value) {
+                    return Err(ScriptError::MinimalData);
+                }
+                stack.push(push_value.to_vec());
+            }
+        } else if executing || (OP_IF..=OP_ENDIF).contains(&opcode) {
+            match opcode {
+                // Push values
+                OP_1NEGATE | OP_1..=OP_16 => {
+                    let value = i64::from(opcode) - i64::from(OP_1 - 1);
+                    stack.push(ScriptNum(value).encode());
+                }
+
+                // Control
+                OP_NOP => {}
+                OP_NOP1 | OP_NOP3..=OP_NOP10 => {
+                    if flags.contains(Flags::DISCOURAGE_UPGRADABLE_NOPS) {
+                        return Err(ScriptError::DiscourageUpgradableNops);
+                    }
+                }
+                OP_CHECKLOCKTIMEVERIFY => {
+                    if flags.contains(Flags::CHECK_LOCK_TIME_VERIFY) {
+                        // Lock times are 5 byte numbers, and the lock time
+                        // stays on the stack
+                        let lock_time = decode_num(top(stack, 1)?, LOCK_TIME_MAX_NUM_SIZE, flags)?;
+                        if lock_time.0 < 0 {
+                            return Err(ScriptError::NegativeLockTime);
+                        }
+                        if !checker.check_lock_time(lock_time.0) {
+                            return Err(ScriptError::UnsatisfiedLockTime);
+                        }
+                    } else if flags.contains(Flags::DISCOURAGE_UPGRADABLE_NOPS) {
+                        return Err(ScriptError::DiscourageUpgradableNops);
+                    }
+                }
+                OP_IF | OP_NOTIF => {
+                    let mut value = false;
+                    if executing {
+                        let condition = stack.pop().ok_or(ScriptError::UnbalancedConditional)?;
+                        value = cast_to_bool(&condition) == (opcode == OP_IF);
+                    }
+                    exec_stack.push(value);
+                }
+                OP_ELSE => {
+                    let branch = exec_stack
+                        .last_mut()
+                        .ok_or(ScriptError::UnbalancedConditional)?;
+                    *branch = !*branch;
+                }
+                OP_ENDIF => {
+                    exec_stack.pop().ok_or(ScriptError::UnbalancedConditional)?;
+                }
+                OP_VERIFY => {
+                    if cast_to_bool(top(stack, 1)?) {
+                        stack.pop();
+                    } else {
+                        return Err(ScriptError::Verify);
+                    }
+                }
+                OP_RETURN => return Err(ScriptError::OpReturn),
+
+                // Stack operations
+                OP_TOALTSTACK => {
+                    let value = stack.pop().ok_or(ScriptError::InvalidStackOperation)?;
+                    altstack.push(value);
+                }
+                OP_FROMALTSTACK => {
+                    let value = altstack
+                        .pop()
+                        .ok_or(ScriptError::InvalidAltstackOperation)?;
+                    stack.push(value);
+                }
+                OP_2DROP => {
+                    require(stack, 2)?;
+                    stack.truncate(stack.len() - 2);
+                }
+                OP_2DUP => copy(stack, 2, 2)?,
+                OP_3DUP => copy(stack, 3, 3)?,
+                OP_2OVER => copy(stack, 4, 2)?,
+                OP_2ROT => {
+                    require(stack, 6)?;
+                    let start = stack.len() - 6;
+                    stack[start..].rotate_left(2);
+                }
+                OP_2SWAP => {
+                    require(stack, 4)?;
+                    let start = stack.len() - 4;
+                    stack[start..].rotate_left(2);
+                }
+                OP_IFDUP => {
+                    let value = top(stack, 1)?;
+                    if cast_to_bool(value) {
+                        let value = value.clone();
+                        stack.push(value);
+                    }
+                }
+                OP_DEPTH => {
+                    let depth = ScriptNum(stack.len() as i64);
+                    stack.push(depth.encode());
+                }
+                OP_DROP => {
+                    stack.pop().ok_or(ScriptError::InvalidStackOperation)?;
+                }
+                OP_DUP => copy(stack, 1, 1)?,
+                OP_NIP => {
+                    require(stack, 2)?;
+                    stack.remove(stack.len() - 2);
+                }
+                OP_OVER => copy(stack, 2, 1)?,
+                OP_PICK | OP_ROLL => {
+                    require(stack, 2)?;
+                    let n = pop_num(stack, flags)?.to_i32();
+                    if n < 0 || n as usize >= stack.len() {
+                        return Err(ScriptError::InvalidStackOperation);
+                    }
+                    let index = stack.len() - 1 - n as usize;
+                    let value = if opcode == OP_ROLL {
+                        stack.remove(index)
+                    } else {
+                        stack[index].clone()
+                    };
+                    stack.push(value);
+                }
+                OP_ROT => {
+                    require(stack, 3)?;
+                    let start = stack.len() - 3;
+                    stack[start..].rotate_left(1);
+                }
+                OP_SWAP => {
+                    require(stack, 2)?;
+                    let start = stack.len() - 2;
+                    stack.swap(start, start + 1);
+                }
+                OP_TUCK => {
+                    require(stack, 2)?;
+                    let value = top(stack, 1)?.clone();
+                    stack.insert(stack.len() - 2, value);
+                }
+
+                // Splice operations
+                OP_SIZE => {
+                    let size = ScriptNum(top(stack, 1)?.len() as i64);
+                    stack.push(size.encode());
+                }
+
+                // Bitwise logic
+                OP_EQUAL | OP_EQUALVERIFY => {
+                    require(stack, 2)?;
+                    let (b, a) = (stack.pop().unwrap(), stack.pop().unwrap());
+                    let equal = a == b;
+                    if opcode == OP_EQUALVERIFY {
+                        if !equal {
+                            return Err(ScriptError::EqualVerify);
+                        }
+                    } else {
+                        stack.push(bool_value(equal));
+                    }
+                }
+
+                // Numeric
+                OP_1ADD | OP_1SUB | OP_NEGATE | OP_ABS | OP_NOT | OP_0NOTEQUAL => {
+                    let a = pop_num(stack, flags)?.0;
+                    let result = match opcode {
+                        OP_1ADD => a + 1,
+                        OP_1SUB => a - 1,
+                        OP_NEGATE => -a,
+                        OP_ABS => a.abs(),
+                        OP_NOT => (a == 0) as i64,
+                        OP_0NOTEQUAL => (a != 0) as i64,
+                        _ => unreachable!("unary numeric opcodes are matched above"),
+                    };
+                    stack.push(ScriptNum(result).encode());
+                }
+                OP_ADD
+                | OP_SUB
+                | OP_BOOLAND
+                | OP_BOOLOR
+                | OP_NUMEQUAL
+                | OP_NUMEQUALVERIFY
+                | OP_NUMNOTEQUAL
+                | OP_LESSTHAN
+                | OP_GREATERTHAN
+                | OP_LESSTHANOREQUAL
+                | OP_GREATERTHANOREQUAL
+                | OP_MIN
+                | OP_MAX => {
+                    require(stack, 2)?;
+                    let b = pop_num(stack, flags)?.0;
+                    let a = pop_num(stack, flags)?.0;
+                    let result = match opcode {
+                        OP_ADD => a + b,
+                        OP_SUB => a - b,
+                        OP_BOOLAND => (a != 0 && b != 0) as i64,
+                        OP_BOOLOR => (a != 0 || b != 0) as i64,
+                        OP_NUMEQUAL | OP_NUMEQUALVERIFY => (a == b) as i64,
+                        OP_NUMNOTEQUAL => (a != b) as i64,
+                        OP_LESSTHAN => (a < b) as i64,
+                        OP_GREATERTHAN => (a > b) as i64,
+                        OP_LESSTHANOREQUAL => (a <= b) as i64,
+                        OP_GREATERTHANOREQUAL => (a >= b) as i64,
+                        OP_MIN => a.min(b),
+                        OP_MAX => a.max(b),
+                        _ => unreachable!("binary numeric opcodes are matched above"),
+                    };
+                    if opcode == OP_NUMEQUALVERIFY {
+                        if result == 0 {
+                            return Err(ScriptError::NumEqualVerify);
+                        }
+                    } else {
+                        stack.push(ScriptNum(result).encode());
+                    }
+                }
+                OP_WITHIN => {
+                    require(stack, 3)?;
+                    let max = pop_num(stack, flags)?.0;
+                    let min = pop_num(stack, flags)?.0;
+                    let x = pop_num(stack, flags)?.0;
+                    stack.push(bool_value(min <= x && x < max));
+                }
+
+                // Crypto
+                OP_RIPEMD160 | OP_SHA1 | OP_SHA256 | OP_HASH160 | OP_HASH256 => {
+                    let value = stack.pop().ok_or(ScriptError::InvalidStackOperation)?;
+                    let hash = match opcode {
+                        OP_RIPEMD160 => Ripemd160::digest(&value).to_vec(),
+                        OP_SHA1 => Sha1::digest(&value).to_vec(),
+                        OP_SHA256 => Sha256::digest(&value).to_vec(),
+                        OP_HASH160 => Ripemd160::digest(&Sha256::digest(&value)).to_vec(),
+                        OP_HASH256 => Sha256::digest(&Sha256::digest(&value)).to_vec(),
+                        _ => unreachable!("hash opcodes are matched above"),
+                    };
+                    stack.push(hash);
+                }
+                OP_CODESEPARATOR => code_start = instructions.position(),
+                OP_CHECKSIG | OP_CHECKSIGVERIFY => {
+                    require(stack, 2)?;
+                    let public_key = stack.pop().unwrap();
+                    let signature = stack.pop().unwrap();
+
+                    // The signature can't sign itself
+                    let mut script_code = script.0[code_start..].to_vec();
+                    find_and_delete(&mut script_code, &push_data(&signature));
+                    let script_code = Script(script_code);
+
+                    check_signature_encoding(&signature, flags)?;
+                    signature::check_public_key_encoding(&public_key, flags)?;
+                    let success = checker.check_signature(&signature, &public_key, &script_code);
+
+                    if opcode == OP_CHECKSIGVERIFY {
+                        if !success {
+                            return Err(ScriptError::CheckSigVerify);
+                        }
+                    } else {
+                        stack.push(bool_value(success));
+                    }
+                }
+                OP_CHECKMULTISIG | OP_CHECKMULTISIGVERIFY => {
+                    let success = check_multisig(
+                        stack,
+                        &script.0[code_start..],
+                        flags,
+                        checker,
+                        &mut op_count,
+                    )?;
+
+                    if opcode == OP_CHECKMULTISIGVERIFY {
+                        if !success {
+                            return Err(ScriptError::CheckMultisigVerify);
+                        }
+                    } else {
+                        stack.push(bool_value(success));
+                    }
+                }
+
+                // OP_RESERVED, OP_VER, OP_VERIF, OP_VERNOTIF, OP_RESERVED1,
+                // OP_RESERVED2, and undefined opcodes
+                _ => return Err(ScriptError::BadOpcode),
+            }
+        }
+
+        if stack.len() + altstack.len() > MAX_STACK_SIZE {
+            return Err(ScriptError::StackSize);
+        }
+    }
+
+    if !exec_stack.is_empty() {
+        return Err(ScriptError::UnbalancedConditional);
+    }
+
+    Ok(())
+}
+
+/// Evaluates an `OP_CHECKMULTISIG`, and returns true if the signatures are
+/// valid.
+///
+/// The stack is `<dummy> <signature>... <signature count> <public key>...
+/// <public key count>`. Each signature must match a public key, in the same
+/// order.
+fn check_multisig(
+    stack: &mut Stack,
+    script_code: &[u8],
+    flags: Flags,
+    checker: &dyn SignatureChecker,
+    op_count: &mut usize,
+) -> Result<bool, ScriptError> {
+    let key_count = decode_num(top(stack, 1)?, DEFAULT_MAX_NUM_SIZE, flags)?.to_i32();
+    if !(0..=MAX_PUBKEYS_PER_MULTISIG).contains(&key_count) {
+        return Err(ScriptError::PubkeyCount);
+    }
+    let key_count = key_count as usize;
+
+    // Each public key counts as an operation
+    *op_count += key_count;
+    if *op_count > MAX_OPS_PER_SCRIPT {
+        return Err(ScriptError::OpCount);
+    }
+
+    let sig_count = decode_num(top(stack, key_count + 2)?, DEFAULT_MAX_NUM_SIZE, flags)?.to_i32();
+    if sig_count < 0 || sig_count as usize > key_count {
+        return Err(ScriptError::SigCount);
+    }
+    let sig_count = sig_count as usize;
+
+    // The counts, the keys, and the signatures, which are checked along with
+    // the dummy value before any signatures are evaluated
+    let arg_count = key_count + sig_count + 2;
+    require(stack, arg_count + 1)?;
+    let args = stack.split_off(stack.len() - arg_count);
+    let signatures = &args[..sig_count];
+    let public_keys = &args[sig_count + 1..sig_count + 1 + key_count];
+
+    // The signatures can't sign themselves
+    let mut script_code = script_code.to_vec();
+    for signature in signatures {
+        find_and_delete(&mut script_code, &push_data(signature));
+    }
+    let script_code = Script(script_code);
+
+    // Check the signatures against the public keys, from the top of the stack
+    // down, until there aren't enough keys left for the remaining signatures
+    let mut signatures = signatures.iter().rev().peekable();
+    let mut public_keys = public_keys.iter().rev();
+    let mut success = true;
+    while let Some(&signature) = signatures.peek() {
+        if signatures.len() > public_keys.len() {
+            success = false;
+            break;
+        }
+        let public_key = public_keys.next().expect("there are enough public keys");
+
+        check_signature_encoding(signature, flags)?;
+        signature::check_public_key_encoding(public_key, flags)?;
+        if checker.check_signature(signature, public_key, &script_code) {
+            signatures.next();
+        }
+    }
+
+    // A bug in the original Bitcoin implementation pops an extra value.
+    // Standard transactions must leave it empty, so it can't be used to
+    // malleate them.
+    let dummy = stack.pop().expect("the dummy value is on the stack");
+    if flags.contains(Flags::NULL_DUMMY) && !dummy.is_empty() {
+        return Err(ScriptError::SigNullDummy);
+    }
+
+    Ok(success)
+}
+
+/// Checks the encoding of `signature`, using the signature rules in `flags`.
+///
+/// Empty signatures are always valid, but they never verify.
+fn check_signature_encoding(signature: &[u8], flags: Flags) -> Result<(), ScriptError> {
+    if signature.is_empty() {
+        return Ok(());
+    }
+
+    signature::check_encoding(signature, flags)
+}
+
+/// Returns an error if `stack` has fewer than `count` elements.
+fn require(stack: &Stack, count: usize) -> Result<(), ScriptError> {
+    if stack.len() < count {
+        Err(ScriptError::InvalidStackOperation)
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns the `depth`th element from the top of `stack`, starting at 1.
+fn top(stack: &Stack, depth: usize) -> Result<&Vec<u8>, ScriptError> {
+    require(stack, depth)?;
+    Ok(&stack[stack.len() - depth])
+}
+
+/// Pushes copies of `count` elements, starting at the `depth`th element from
+/// the top of `stack`.
+fn copy(stack: &mut Stack, depth: usize, count: usize) -> Result<(), ScriptError> {
+    require(stack, depth)?;
+    let start = stack.len() - depth;
+    let values = stack[start..start + count].to_vec();
+    stack.extend(values);
+    Ok(())
+}
+
+/// Decodes a number operand of at most `max_size` bytes, using the number
+/// rules in `flags`.
+fn decode_num(bytes: &[u8], max_size: usize, flags: Flags) -> Result<ScriptNum, ScriptError> {
+    let value = ScriptNum::decode(bytes, max_size)?;
+    if flags.contains(Flags::MINIMAL_DATA) && !ScriptNum::is_minimal_encoding(bytes) {
+        return Err(ScriptError::MinimalData);
+    }
+    Ok(value)
+}
+
+/// Pops a number operand from `stack`, using the number rules in `flags`.
+fn pop_num(stack: &mut Stack, flags: Flags) -> Result<ScriptNum, ScriptError> {
+    let value = decode_num(top(stack, 1)?, DEFAULT_MAX_NUM_SIZE, flags)?;
+    stack.pop();
+    Ok(value)
+}
+
+/// Returns the stack value for `value`.
+fn bool_value(value: bool) -> Vec<u8> {
+    if value {
+        vec![1]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Returns true if `value` is true.
+///
+/// Zero and negative zero are false, with any number of zero bytes.
+pub fn cast_to_bool(value: &[u8]) -> bool {
+    match value.split_last() {
+        Some((&last, rest)) => rest.iter().any(|&byte| byte != 0) || (last & 0x7f) != 0,
+        None => false,
+    }
+}
+
+/// Returns an error unless the evaluation that produced `stack` succeeded.
+fn check_result(stack: &Stack) -> Result<(), ScriptError> {
+    match stack.last() {
+        Some(value) if cast_to_bool(value) => Ok(()),
+        _ => Err(ScriptError::EvalFalse),
+    }
+}
+
+/// Returns true if `script` is a pay-to-script-hash script:
+/// `OP_HASH160 <20 byte hash> OP_EQUAL`.
+pub fn is_pay_to_script_hash(script: &Script) -> bool {
+    match script.0.as_slice() {
+        [OP_HASH160, 0x14, .., OP_EQUAL] => script.0.len() == 23,
+        _ => false,
+    }
+}
+
+/// Returns true if `script` only contains pushes.
+///
+/// `OP_1NEGATE`, `OP_RESERVED`, and the small integers count as pushes.
+pub fn is_push_only(script: &Script) -> bool {
+    Instructions::new(&script.0).all(|instruction| match instruction {
+        Ok((opcode, _)) => opcode <= OP_16,
+        Err(_) => false,
+    })
+}
+
+/// Returns true if `opcode` is the shortest push of `data`.
+fn is_minimal_push(opcode: u8, data: &[u8]) -> bool {
+    match data {
+        [] => opcode == OP_0,
+        [value @ 1..=16] => opcode == OP_1 + value - 1,
+        [0x81] => opcode == OP_1NEGATE,
+        _ if data.len() < usize::from(OP_PUSHDATA1) => usize::from(opcode) == data.len(),
+        _ if data.len() <= 0xff => opcode == OP_PUSHDATA1,
+        _ if data.len() <= 0xffff => opcode == OP_PUSHDATA2,
+        _ => true,
+    }
+}
+
+/// Returns a script that pushes `data`.
+fn push_data(data: &[u8]) -> Vec<u8> {
+    let len = data.len();
+    let mut script = Vec::with_capacity(len + 5);
+
+    if len < usize::from(OP_PUSHDATA1) {
+        script.push(len as u8);
+    } else if len <= 0xff {
+        script.push(OP_PUSHDATA1);
+        script.push(len as u8);
+    } else if len <= 0xffff {
+        script.push(OP_PUSHDATA2);
+        script.extend_from_slice(&(len as u16).to_le_bytes());
+    } else {
+        script.push(OP_PUSHDATA4);
+        script.extend_from_slice(&(len as u32).to_le_bytes());
+    }
+    script.extend_from_slice(data);
+
+    script
+}
+
+/// Removes each instance of `pattern` that starts at an instruction boundary
+/// in `script`.
+///
+/// Unlike Bitcoin's `FindAndDelete`, this function doesn't return the number
+/// of deletions, because Zcash doesn't use it.
+fn find_and_delete(script: &mut Vec<u8>, pattern: &[u8]) {
+    if pattern.is_empty() {
+        return;
+    }
+
+    let mut result = Vec::with_capacity(script.len());
+    let mut instructions = Instructions::new(script);
+    let mut start = 0;
+    loop {
+        let mut position = instructions.position();
+        result.extend_from_slice(&script[start..position]);
+        while script[position..].starts_with(pattern) {
+            position += pattern.len();
+        }
+        start = position;
+        instructions.seek(position);
+
+        match instructions.next() {
+            Some(Ok(_)) => {}
+            _ => break,
+        }
+    }
+    result.extend_from_slice(&script[start..]);
+
+    *script = result;
+}
+
+/// An iterator over the opcodes and push values in a script.
+///
+/// Yields `Err(ScriptError::BadOpcode)` and stops if a push runs past the end
+/// of the script.
+struct Instructions<'a> {
+    script: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Instructions<'a> {
+    fn new(script: &'a [u8]) -> Self {
+        Instructions {
+            script,
+            position: 0,
+        }
+    }
+
+    /// Returns the position of the next instruction.
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Moves to the instruction at `position`.
+    fn seek(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    /// Returns the next `len` bytes, and moves past them.
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.position.checked_add(len)?;
+        let bytes = self.script.get(self.position..end)?;
+        self.position = end;
+        Some(bytes)
+    }
+
+    /// Returns the opcode and push value of the next instruction.
+    fn read_instruction(&mut self) -> Option<(u8, &'a [u8])> {
+        let opcode = self.take(1)?[0];
+        let len = match opcode {
+            OP_PUSHDATA1 => usize::from(self.take(1)?[0]),
+            OP_PUSHDATA2 => {
+                let bytes = self.take(2)?;
+                usize::from(u16::from_le_bytes([bytes[0], bytes[1]]))
+            }
+            OP_PUSHDATA4 => {
+                let bytes = self.take(4)?;
+                u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize
+            }
+            _ if opcode < OP_PUSHDATA1 => usize::from(opcode),
+            _ => 0,
+        };
+        Some((opcode, self.take(len)?))
+    }
+}
+
+impl<'a> Iterator for Instructions<'a> {
+    type Item = Result<(u8, &'a [u8]), ScriptError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.script.len() {
+            return None;
+        }
+
+        match self.read_instruction() {
+            Some(instruction) => Some(Ok(instruction)),
+            None => {
+                // Stop after a truncated push
+                self.position = self.script.len();
+                Some(Err(ScriptError::BadOpcode))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    /// A checker that accepts signatures that are equal to their public key,
+    /// and records the script code of each signature check.
+    #[derive(Default)]
+    struct EqualChecker {
+        script_codes: RefCell<Vec<Script>>,
+    }
+
+    impl SignatureChecker for EqualChecker {
+        fn check_signature(
+            &self,
+            signature: &[u8],
+            public_key: &[u8],
+            script_code: &Script,
+        ) -> bool {
+            self.script_codes.borrow_mut().push(script_code.clone());
+            signature == public_key
+        }
+
+        fn check_lock_time(&self, _lock_time: i64) -> bool {
+            false
+        }
+    }
+
+    fn verify(
+        script_sig: &str,
+        script_pubkey: &str,
+        checker: &EqualChecker,
+    ) -> Result<(), ScriptError> {
+        verify_script(
+            &Script(hex::decode(script_sig).unwrap()),
+            &Script(hex::decode(script_pubkey).unwrap()),
+            Flags::P2SH,
+            checker,
+        )
+    }
+
+    #[test]
+    fn multisig_signature_order() {
+        // 2 'a' 'b' 'c' 3 CHECKMULTISIG
+        let script_pubkey = "5201610162016353ae";
+        let checker = EqualChecker::default();
+
+        // Signatures must be in the same order as their keys
+        assert_eq!(verify("0001610163", &script_pubkey, &checker), Ok(()));
+        assert_eq!(verify("0001620163", &script_pubkey, &checker), Ok(()));
+        assert_eq!(
+            verify("0001630161", &script_pubkey, &checker),
+            Err(ScriptError::EvalFalse)
+        );
+        assert_eq!(
+            verify("0001620162", &script_pubkey, &checker),
+            Err(ScriptError::EvalFalse)
+        );
+    }
+
+    #[test]
+    fn script_code() {
+        let checker = EqualChecker::default();
+
+        // 'a', then NOP CODESEPARATOR 'b' 'a' DROP CHECKSIG
+        assert_eq!(
+            verify("0161", "61ab0162016175ac", &checker),
+            Err(ScriptError::EvalFalse)
+        );
+
+        // The script code starts after the CODESEPARATOR, and doesn't
+        // include the signature
+        assert_eq!(
+            checker.script_codes.into_inner(),
+            vec![Script(hex::decode("016275ac").unwrap())]
+        );
+    }
+
+    #[test]
+    fn find_and_delete_at_boundaries() {
+        for &(script, pattern, expected) in &[
+            ("0302ff03", "0302ff03", ""),
+            ("0302ff030302ff03", "0302ff03", ""),
+            ("0302ff030302ff03", "02", "0302ff030302ff03"),
+            // The pattern starts inside a push
+            ("0302ff030302ff03", "ff", "0302ff030302ff03"),
+            ("02feed5169", "feed51", "02feed5169"),
+            ("02feed5169", "02feed51", "69"),
+            // Consecutive matches in the middle of the script
+            ("516902feed5169", "feed51", "516902feed5169"),
+            ("516902feed5169", "02feed51", "516969"),
+            ("0003feed", "03feed", "00"),
+            ("0003feed", "00", "03feed"),
+            // A truncated push stops the search
+            ("00004d", "00", "4d"),
+            ("4d00", "00", "4d00"),
+        ] {
+            let mut result = hex::decode(script).unwrap();
+            find_and_delete(&mut result, &hex::decode(pattern).unwrap());
+            assert_eq!(hex::encode(result), expected, "{} {}", script, pattern);
+        }
+    }
+
+    #[test]
+    fn push_data_sizes() {
+        assert_eq!(push_data(&[]), [OP_0]);
+        assert_eq!(push_data(&[0x51]), [0x01, 0x51]);
+        assert_eq!(push_data(&[0; 0x4c])[..2], [OP_PUSHDATA1, 0x4c]);
+        assert_eq!(push_data(&[0; 0x100])[..3], [OP_PUSHDATA2, 0x00, 0x01]);
+    }
+}
@@ -1,10 +1,32 @@
+//! Transparent script verification for Zebra.
+//!
+//! Zcash's transparent scripts use Bitcoin's script language, without the
+//! SegWit and Taproot changes. This crate is a pure-Rust interpreter for
+//! that language, which checks the consensus and standard rules enforced by
+//! `zcashd`.
+//!
+//! Use `verify_script` to check that a `scriptSig` satisfies the
+//! `scriptPubKey` of the output it spends. Signatures and lock times are
+//! checked against the spending transaction by a `SignatureChecker`, which is
+//! usually a `TransactionSignatureChecker`.
+
 #![doc(html_logo_url = "https://www.zfnd.org/images/zebra-icon.png")]
 #![doc(html_root_url = "https://doc.zebra.zfnd.org/zebra_script")]
+#![deny(missing_docs)]
+
+mod checker;
+mod error;
+mod interpreter;
+mod num;
+pub mod opcodes;
+mod signature;
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
-    }
-}
+pub use checker::TransactionSignatureChecker;
+pub use error::ScriptError;
+pub use interpreter::{
+    cast_to_bool, eval_script, is_pay_to_script_hash, is_push_only, verify_script, Flags,
+    SignatureChecker, Stack, MAX_OPS_PER_SCRIPT, MAX_PUBKEYS_PER_MULTISIG, MAX_SCRIPT_ELEMENT_SIZE,
+    MAX_SCRIPT_SIZE, MAX_STACK_SIZE,
+};
+pub use num::ScriptNum;
+pub use signature::{is_low_s, is_valid_der};
@@ -0,0 +1,162 @@
+//! Numbers on the script stack.
+
+use crate::ScriptError;
+
+/// The default maximum size of a number operand, in bytes.
+pub const DEFAULT_MAX_NUM_SIZE: usize = 4;
+
+/// The maximum size of the `OP_CHECKLOCKTIMEVERIFY` operand, in bytes.
+///
+/// Lock times can be up to `2^32 - 1`, which needs 5 bytes as a signed
+/// number.
+pub const LOCK_TIME_MAX_NUM_SIZE: usize = 5;
+
+/// A number operand or result, in the script's little-endian sign-magnitude
+/// encoding.
+///
+/// Operands are limited to `DEFAULT_MAX_NUM_SIZE` bytes, but results can
+/// overflow that range. Overflowed results can be pushed to the stack, but
+/// they can't be used as operands.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct ScriptNum(pub i64);
+
+impl ScriptNum {
+    /// Decodes `bytes` as a number of at most `max_size` bytes.
+    ///
+    /// Consensus rules don't require numbers to use their shortest encoding,
+    /// so negative zero and zero-padded numbers are valid operands. Use
+    /// `is_minimal_encoding` to check for the shortest encoding.
+    pub fn decode(bytes: &[u8], max_size: usize) -> Result<ScriptNum, ScriptError> {
+        if bytes.len() > max_size {
+            return Err(ScriptError::NumOverflow);
+        }
+
+        let last = match bytes.last() {
+            Some(&last) => last,
+            None => return Ok(ScriptNum(0)),
+        };
+
+        let mut value = bytes
+            .iter()
+            .rev()
+            .fold(0i64, |value, &byte| (value << 8) | i64::from(byte));
+
+        // The high bit of the last byte is the sign bit
+        if last & 0x80 != 0 {
+            value &= !(0x80i64 << (8 * (bytes.len() - 1)));
+            value = -value;
+        }
+
+        Ok(ScriptNum(value))
+    }
+
+    /// Returns true if `bytes` is the shortest encoding of a number.
+    ///
+    /// The last byte can only be `0x00` or `0x80` if it is needed for the
+    /// sign bit.
+    pub fn is_minimal_encoding(bytes: &[u8]) -> bool {
+        match bytes {
+            [] => true,
+            [.., last] if last & 0x7f != 0 => true,
+            [.., second_last, _] => second_last & 0x80 != 0,
+            [_] => false,
+        }
+    }
+
+    /// Returns the minimal encoding of this number.
+    pub fn encode(self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let negative = self.0 < 0;
+        let mut magnitude = if negative {
+            (self.0 as u64).wrapping_neg()
+        } else {
+            self.0 as u64
+        };
+
+        while magnitude > 0 {
+            bytes.push(magnitude as u8);
+            magnitude >>= 8;
+        }
+
+        // If the high bit of the last byte is already set, add an extra byte
+        // for the sign. Otherwise, set the sign bit on the last byte.
+        if let Some(last) = bytes.last_mut() {
+            if *last & 0x80 != 0 {
+                bytes.push(if negative { 0x80 } else { 0x00 });
+            } else if negative {
+                *last |= 0x80;
+            }
+        }
+
+        bytes
+    }
+
+    /// Returns this number, clamped to the `i32` range.
+    pub fn to_i32(self) -> i32 {
+        if self.0 > i64::from(i32::MAX) {
+            i32::MAX
+        } else if self.0 < i64::from(i32::MIN) {
+            i32::MIN
+        } else {
+            self.0 as i32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        for &(value, bytes) in &[
+            (0, &[][..]),
+            (1, &[0x01][..]),
+            (-1, &[0x81][..]),
+            (127, &[0x7f][..]),
+            (128, &[0x80, 0x00][..]),
+            (-128, &[0x80, 0x80][..]),
+            (255, &[0xff, 0x00][..]),
+            (256, &[0x00, 0x01][..]),
+            (-32768, &[0x00, 0x80, 0x80][..]),
+            (2_147_483_647, &[0xff, 0xff, 0xff, 0x7f][..]),
+            (-2_147_483_647, &[0xff, 0xff, 0xff, 0xff][..]),
+            (4_294_967_295, &[0xff, 0xff, 0xff, 0xff, 0x00][..]),
+        ] {
+            assert_eq!(ScriptNum(value).encode(), bytes, "{}", value);
+            assert!(ScriptNum::is_minimal_encoding(bytes), "{}", value);
+            assert_eq!(
+                ScriptNum::decode(bytes, LOCK_TIME_MAX_NUM_SIZE),
+                Ok(ScriptNum(value))
+            );
+        }
+    }
+
+    #[test]
+    fn non_minimal() {
+        for &(value, bytes) in &[
+            (0, &[0x00][..]),
+            (0, &[0x80][..]),
+            (0, &[0x00, 0x00, 0x00, 0x80][..]),
+            (1, &[0x01, 0x00][..]),
+            (-1, &[0x01, 0x80][..]),
+        ] {
+            assert_eq!(
+                ScriptNum::decode(bytes, DEFAULT_MAX_NUM_SIZE),
+                Ok(ScriptNum(value))
+            );
+            assert!(!ScriptNum::is_minimal_encoding(bytes), "{:?}", bytes);
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        let bytes = [0xff, 0xff, 0xff, 0xff, 0x00];
+        assert_eq!(
+            ScriptNum::decode(&bytes, DEFAULT_MAX_NUM_SIZE),
+            Err(ScriptError::NumOverflow)
+        );
+        assert_eq!(ScriptNum(i64::from(i32::MAX) + 1).to_i32(), i32::MAX);
+        assert_eq!(ScriptNum(i64::from(i32::MIN) - 1).to_i32(), i32::MIN);
+    }
+}
@@ -0,0 +1,423 @@
+//! Transparent script opcodes.
+//!
+//! Zcash uses Bitcoin's opcode set, as of Bitcoin Core 0.11, with
+//! `OP_CHECKLOCKTIMEVERIFY` in the place of `OP_NOP2`. It does not have the
+//! SegWit, `OP_CHECKSEQUENCEVERIFY`, or Taproot changes.
+
+// Push values
+/// Pushes an empty byte vector.
+pub const OP_0: u8 = 0x00;
+/// An alias for `OP_0`.
+pub const OP_FALSE: u8 = OP_0;
+/// Pushes the next `n` bytes, where `n` is the next byte.
+pub const OP_PUSHDATA1: u8 = 0x4c;
+/// Pushes the next `n` bytes, where `n` is the next 2 bytes, little-endian.
+pub const OP_PUSHDATA2: u8 = 0x4d;
+/// Pushes the next `n` bytes, where `n` is the next 4 bytes, little-endian.
+pub const OP_PUSHDATA4: u8 = 0x4e;
+/// Pushes the number -1.
+pub const OP_1NEGATE: u8 = 0x4f;
+/// Fails the script, if it is executed.
+pub const OP_RESERVED: u8 = 0x50;
+/// Pushes the number 1.
+pub const OP_1: u8 = 0x51;
+/// An alias for `OP_1`.
+pub const OP_TRUE: u8 = OP_1;
+/// Pushes the number 2.
+pub const OP_2: u8 = 0x52;
+/// Pushes the number 3.
+pub const OP_3: u8 = 0x53;
+/// Pushes the number 4.
+pub const OP_4: u8 = 0x54;
+/// Pushes the number 5.
+pub const OP_5: u8 = 0x55;
+/// Pushes the number 6.
+pub const OP_6: u8 = 0x56;
+/// Pushes the number 7.
+pub const OP_7: u8 = 0x57;
+/// Pushes the number 8.
+pub const OP_8: u8 = 0x58;
+/// Pushes the number 9.
+pub const OP_9: u8 = 0x59;
+/// Pushes the number 10.
+pub const OP_10: u8 = 0x5a;
+/// Pushes the number 11.
+pub const OP_11: u8 = 0x5b;
+/// Pushes the number 12.
+pub const OP_12: u8 = 0x5c;
+/// Pushes the number 13.
+pub const OP_13: u8 = 0x5d;
+/// Pushes the number 14.
+pub const OP_14: u8 = 0x5e;
+/// Pushes the number 15.
+pub const OP_15: u8 = 0x5f;
+/// Pushes the number 16.
+pub const OP_16: u8 = 0x60;
+
+// Control
+/// Does nothing.
+pub const OP_NOP: u8 = 0x61;
+/// Fails the script, if it is executed.
+pub const OP_VER: u8 = 0x62;
+/// Executes the following statements if the top stack item is true.
+pub const OP_IF: u8 = 0x63;
+/// Executes the following statements if the top stack item is false.
+pub const OP_NOTIF: u8 = 0x64;
+/// Fails the script, even if it is not executed.
+pub const OP_VERIF: u8 = 0x65;
+/// Fails the script, even if it is not executed.
+pub const OP_VERNOTIF: u8 = 0x66;
+/// Executes the following statements if the previous branch was not executed.
+pub const OP_ELSE: u8 = 0x67;
+/// Ends an `OP_IF` or `OP_NOTIF` block.
+pub const OP_ENDIF: u8 = 0x68;
+/// Fails the script if the top stack item is false, and removes it otherwise.
+pub const OP_VERIFY: u8 = 0x69;
+/// Fails the script.
+pub const OP_RETURN: u8 = 0x6a;
+
+// Stack operations
+/// Moves the top stack item to the alt stack.
+pub const OP_TOALTSTACK: u8 = 0x6b;
+/// Moves the top alt stack item to the stack.
+pub const OP_FROMALTSTACK: u8 = 0x6c;
+/// Removes the top two stack items.
+pub const OP_2DROP: u8 = 0x6d;
+/// Duplicates the top two stack items.
+pub const OP_2DUP: u8 = 0x6e;
+/// Duplicates the top three stack items.
+pub const OP_3DUP: u8 = 0x6f;
+/// Copies the third and fourth stack items to the top.
+pub const OP_2OVER: u8 = 0x70;
+/// Moves the fifth and sixth stack items to the top.
+pub const OP_2ROT: u8 = 0x71;
+/// Swaps the top two pairs of stack items.
+pub const OP_2SWAP: u8 = 0x72;
+/// Duplicates the top stack item if it is true.
+pub const OP_IFDUP: u8 = 0x73;
+/// Pushes the number of stack items.
+pub const OP_DEPTH: u8 = 0x74;
+/// Removes the top stack item.
+pub const OP_DROP: u8 = 0x75;
+/// Duplicates the top stack item.
+pub const OP_DUP: u8 = 0x76;
+/// Removes the second stack item.
+pub const OP_NIP: u8 = 0x77;
+/// Copies the second stack item to the top.
+pub const OP_OVER: u8 = 0x78;
+/// Copies the `n`th stack item to the top.
+pub const OP_PICK: u8 = 0x79;
+/// Moves the `n`th stack item to the top.
+pub const OP_ROLL: u8 = 0x7a;
+/// Moves the third stack item to the top.
+pub const OP_ROT: u8 = 0x7b;
+/// Swaps the top two stack items.
+pub const OP_SWAP: u8 = 0x7c;
+/// Copies the top stack item below the second item.
+pub const OP_TUCK: u8 = 0x7d;
+
+// Splice operations
+/// Disabled: concatenates two byte vectors.
+pub const OP_CAT: u8 = 0x7e;
+/// Disabled: returns part of a byte vector.
+pub const OP_SUBSTR: u8 = 0x7f;
+/// Disabled: returns the start of a byte vector.
+pub const OP_LEFT: u8 = 0x80;
+/// Disabled: returns the end of a byte vector.
+pub const OP_RIGHT: u8 = 0x81;
+/// Pushes the length of the top stack item.
+pub const OP_SIZE: u8 = 0x82;
+
+// Bitwise logic
+/// Disabled: flips every bit.
+pub const OP_INVERT: u8 = 0x83;
+/// Disabled: bitwise and.
+pub const OP_AND: u8 = 0x84;
+/// Disabled: bitwise or.
+pub const OP_OR: u8 = 0x85;
+/// Disabled: bitwise exclusive or.
+pub const OP_XOR: u8 = 0x86;
+/// Pushes true if the top two stack items are equal, and false otherwise.
+pub const OP_EQUAL: u8 = 0x87;
+/// `OP_EQUAL`, followed by `OP_VERIFY`.
+pub const OP_EQUALVERIFY: u8 = 0x88;
+/// Fails the script, if it is executed.
+pub const OP_RESERVED1: u8 = 0x89;
+/// Fails the script, if it is executed.
+pub const OP_RESERVED2: u8 = 0x8a;
+
+// Numeric
+/// Adds 1 to the top stack item.
+pub const OP_1ADD: u8 = 0x8b;
+/// Subtracts 1 from the top stack item.
+pub const OP_1SUB: u8 = 0x8c;
+/// Disabled: multiplies the top stack item by 2.
+pub const OP_2MUL: u8 = 0x8d;
+/// Disabled: divides the top stack item by 2.
+pub const OP_2DIV: u8 = 0x8e;
+/// Negates the top stack item.
+pub const OP_NEGATE: u8 = 0x8f;
+/// Replaces the top stack item with its absolute value.
+pub const OP_ABS: u8 = 0x90;
+/// Pushes 1 if the top stack item is 0, and 0 otherwise.
+pub const OP_NOT: u8 = 0x91;
+/// Pushes 0 if the top stack item is 0, and 1 otherwise.
+pub const OP_0NOTEQUAL: u8 = 0x92;
+/// Adds the top two stack items.
+pub const OP_ADD: u8 = 0x93;
+/// Subtracts the top stack item from the second item.
+pub const OP_SUB: u8 = 0x94;
+/// Disabled: multiplies the top two stack items.
+pub const OP_MUL: u8 = 0x95;
+/// Disabled: divides the second stack item by the top item.
+pub const OP_DIV: u8 = 0x96;
+/// Disabled: the remainder of dividing the second stack item by the top item.
+pub const OP_MOD: u8 = 0x97;
+/// Disabled: shifts the second stack item left.
+pub const OP_LSHIFT: u8 = 0x98;
+/// Disabled: shifts the second stack item right.
+pub const OP_RSHIFT: u8 = 0x99;
+/// Pushes 1 if both of the top two stack items are nonzero.
+pub const OP_BOOLAND: u8 = 0x9a;
+/// Pushes 1 if either of the top two stack items is nonzero.
+pub const OP_BOOLOR: u8 = 0x9b;
+/// Pushes 1 if the top two stack items are equal numbers.
+pub const OP_NUMEQUAL: u8 = 0x9c;
+/// `OP_NUMEQUAL`, followed by `OP_VERIFY`.
+pub const OP_NUMEQUALVERIFY: u8 = 0x9d;
+/// Pushes 1 if the top two stack items are different numbers.
+pub const OP_NUMNOTEQUAL: u8 = 0x9e;
+/// Pushes 1 if the second stack item is less than the top item.
+pub const OP_LESSTHAN: u8 = 0x9f;
+/// Pushes 1 if the second stack item is greater than the top item.
+pub const OP_GREATERTHAN: u8 = 0xa0;
+/// Pushes 1 if the second stack item is at most the top item.
+pub const OP_LESSTHANOREQUAL: u8 = 0xa1;
+/// Pushes 1 if the second stack item is at least the top item.
+pub const OP_GREATERTHANOREQUAL: u8 = 0xa2;
+/// Pushes the smaller of the top two stack items.
+pub const OP_MIN: u8 = 0xa3;
+/// Pushes the larger of the top two stack items.
+pub const OP_MAX: u8 = 0xa4;
+/// Pushes 1 if the third stack item is in the range given by the top two items.
+pub const OP_WITHIN: u8 = 0xa5;
+
+// Crypto
+/// Replaces the top stack item with its RIPEMD-160 hash.
+pub const OP_RIPEMD160: u8 = 0xa6;
+/// Replaces the top stack item with its SHA-1 hash.
+pub const OP_SHA1: u8 = 0xa7;
+/// Replaces the top stack item with its SHA-256 hash.
+pub const OP_SHA256: u8 = 0xa8;
+/// Replaces the top stack item with its SHA-256, then RIPEMD-160 hash.
+pub const OP_HASH160: u8 = 0xa9;
+/// Replaces the top stack item with its double SHA-256 hash.
+pub const OP_HASH256: u8 = 0xaa;
+/// Starts the part of the script that is signed by later signature checks.
+pub const OP_CODESEPARATOR: u8 = 0xab;
+/// Pushes true if the signature is valid for the public key and transaction.
+pub const OP_CHECKSIG: u8 = 0xac;
+/// `OP_CHECKSIG`, followed by `OP_VERIFY`.
+pub const OP_CHECKSIGVERIFY: u8 = 0xad;
+/// Pushes true if the signatures are valid for `m` of the `n` public keys.
+pub const OP_CHECKMULTISIG: u8 = 0xae;
+/// `OP_CHECKMULTISIG`, followed by `OP_VERIFY`.
+pub const OP_CHECKMULTISIGVERIFY: u8 = 0xaf;
+
+// Expansion
+/// Does nothing, but is reserved for upgrades.
+pub const OP_NOP1: u8 = 0xb0;
+/// Fails the script if the transaction's lock time is before the top stack item.
+pub const OP_CHECKLOCKTIMEVERIFY: u8 = 0xb1;
+/// The name of `OP_CHECKLOCKTIMEVERIFY` before BIP 65.
+pub const OP_NOP2: u8 = OP_CHECKLOCKTIMEVERIFY;
+/// Does nothing, but is reserved for upgrades.
+pub const OP_NOP3: u8 = 0xb2;
+/// Does nothing, but is reserved for upgrades.
+pub const OP_NOP4: u8 = 0xb3;
+/// Does nothing, but is reserved for upgrades.
+pub const OP_NOP5: u8 = 0xb4;
+/// Does nothing, but is reserved for upgrades.
+pub const OP_NOP6: u8 = 0xb5;
+/// Does nothing, but is reserved for upgrades.
+pub const OP_NOP7: u8 = 0xb6;
+/// Does nothing, but is reserved for upgrades.
+pub const OP_NOP8: u8 = 0xb7;
+/// Does nothing, but is reserved for upgrades.
+pub const OP_NOP9: u8 = 0xb8;
+/// Does nothing, but is reserved for upgrades.
+pub const OP_NOP10: u8 = 0xb9;
+
+/// Not an opcode: used to represent invalid opcodes.
+pub const OP_INVALIDOPCODE: u8 = 0xff;
+
+/// The highest opcode that is counted towards the script's opcode limit.
+/// Pushes, `OP_1NEGATE`, `OP_RESERVED`, and the small integers are free.
+pub(crate) const MAX_FREE_OPCODE: u8 = OP_16;
+
+/// Returns true if `opcode` is disabled.
+///
+/// Disabled opcodes make a script fail, even if they are in a branch that is
+/// not executed.
+pub fn is_disabled(opcode: u8) -> bool {
+    matches!(
+        opcode,
+        OP_CAT
+            | OP_SUBSTR
+            | OP_LEFT
+            | OP_RIGHT
+            | OP_INVERT
+            | OP_AND
+            | OP_OR
+            | OP_XOR
+            | OP_2MUL
+            | OP_2DIV
+            | OP_MUL
+            | OP_DIV
+            | OP_MOD
+            | OP_LSHIFT
+            | OP_RSHIFT
+    )
+}
+
+/// Returns the name of `opcode`, without the `OP_` prefix, or `None` if
+/// `opcode` is not a defined opcode.
+///
+/// Direct pushes (`0x01..=0x4b`) don't have names.
+pub fn name(opcode: u8) -> Option<&'static str> {
+    Some(match opcode {
+        OP_0 => "0",
+        OP_PUSHDATA1 => "PUSHDATA1",
+        OP_PUSHDATA2 => "PUSHDATA2",
+        OP_PUSHDATA4 => "PUSHDATA4",
+        OP_1NEGATE => "1NEGATE",
+        OP_RESERVED => "RESERVED",
+        OP_1 => "1",
+        OP_2 => "2",
+        OP_3 => "3",
+        OP_4 => "4",
+        OP_5 => "5",
+        OP_6 => "6",
+        OP_7 => "7",
+        OP_8 => "8",
+        OP_9 => "9",
+        OP_10 => "10",
+        OP_11 => "11",
+        OP_12 => "12",
+        OP_13 => "13",
+        OP_14 => "14",
+        OP_15 => "15",
+        OP_16 => "16",
+        OP_NOP => "NOP",
+        OP_VER => "VER",
+        OP_IF => "IF",
+        OP_NOTIF => "NOTIF",
+        OP_VERIF => "VERIF",
+        OP_VERNOTIF => "VERNOTIF",
+        OP_ELSE => "ELSE",
+        OP_ENDIF => "ENDIF",
+        OP_VERIFY => "VERIFY",
+        OP_RETURN => "RETURN",
+        OP_TOALTSTACK => "TOALTSTACK",
+        OP_FROMALTSTACK => "FROMALTSTACK",
+        OP_2DROP => "2DROP",
+        OP_2DUP => "2DUP",
+        OP_3DUP => "3DUP",
+        OP_2OVER => "2OVER",
+        OP_2ROT => "2ROT",
+        OP_2SWAP => "2SWAP",
+        OP_IFDUP => "IFDUP",
+        OP_DEPTH => "DEPTH",
+        OP_DROP => "DROP",
+        OP_DUP => "DUP",
+        OP_NIP => "NIP",
+        OP_OVER => "OVER",
+        OP_PICK => "PICK",
+        OP_ROLL => "ROLL",
+        OP_ROT => "ROT",
+        OP_SWAP => "SWAP",
+        OP_TUCK => "TUCK",
+        OP_CAT => "CAT",
+        OP_SUBSTR => "SUBSTR",
+        OP_LEFT => "LEFT",
+        OP_RIGHT => "RIGHT",
+        OP_SIZE => "SIZE",
+        OP_INVERT => "INVERT",
+        OP_AND => "AND",
+        OP_OR => "OR",
+        OP_XOR => "XOR",
+        OP_EQUAL => "EQUAL",
+        OP_EQUALVERIFY => "EQUALVERIFY",
+        OP_RESERVED1 => "RESERVED1",
+        OP_RESERVED2 => "RESERVED2",
+        OP_1ADD => "1ADD",
+        OP_1SUB => "1SUB",
+        OP_2MUL => "2MUL",
+        OP_2DIV => "2DIV",
+        OP_NEGATE => "NEGATE",
+        OP_ABS => "ABS",
+        OP_NOT => "NOT",
+        OP_0NOTEQUAL => "0NOTEQUAL",
+        OP_ADD => "ADD",
+        OP_SUB => "SUB",
+        OP_MUL => "MUL",
+        OP_DIV => "DIV",
+        OP_MOD => "MOD",
+        OP_LSHIFT => "LSHIFT",
+        OP_RSHIFT => "RSHIFT",
+        OP_BOOLAND => "BOOLAND",
+        OP_BOOLOR => "BOOLOR",
+        OP_NUMEQUAL => "NUMEQUAL",
+        OP_NUMEQUALVERIFY => "NUMEQUALVERIFY",
+        OP_NUMNOTEQUAL => "NUMNOTEQUAL",
+        OP_LESSTHAN => "LESSTHAN",
+        OP_GREATERTHAN => "GREATERTHAN",
+        OP_LESSTHANOREQUAL => "LESSTHANOREQUAL",
+        OP_GREATERTHANOREQUAL => "GREATERTHANOREQUAL",
+        OP_MIN => "MIN",
+        OP_MAX => "MAX",
+        OP_WITHIN => "WITHIN",
+        OP_RIPEMD160 => "RIPEMD160",
+        OP_SHA1 => "SHA1",
+        OP_SHA256 => "SHA256",
+        OP_HASH160 => "HASH160",
+        OP_HASH256 => "HASH256",
+        OP_CODESEPARATOR => "CODESEPARATOR",
+        OP_CHECKSIG => "CHECKSIG",
+        OP_CHECKSIGVERIFY => "CHECKSIGVERIFY",
+        OP_CHECKMULTISIG => "CHECKMULTISIG",
+        OP_CHECKMULTISIGVERIFY => "CHECKMULTISIGVERIFY",
+        OP_NOP1 => "NOP1",
+        OP_CHECKLOCKTIMEVERIFY => "CHECKLOCKTIMEVERIFY",
+        OP_NOP3 => "NOP3",
+        OP_NOP4 => "NOP4",
+        OP_NOP5 => "NOP5",
+        OP_NOP6 => "NOP6",
+        OP_NOP7 => "NOP7",
+        OP_NOP8 => "NOP8",
+        OP_NOP9 => "NOP9",
+        OP_NOP10 => "NOP10",
+        OP_INVALIDOPCODE => "INVALIDOPCODE",
+        _ => return None,
+    })
+}
+
+/// Returns the opcode called `name`, with or without the `OP_` prefix.
+///
+/// Also accepts the aliases `FALSE`, `TRUE`, and `NOP2`.
+pub fn from_name(name: &str) -> Option<u8> {
+    let name = if name.starts_with("OP_") {
+        &name[3..]
+    } else {
+        name
+    };
+
+    match name {
+        "FALSE" => return Some(OP_FALSE),
+        "TRUE" => return Some(OP_TRUE),
+        "NOP2" => return Some(OP_NOP2),
+        _ => {}
+    }
+
+    (0..=u8::MAX).find(|&opcode| self::name(opcode) == Some(name))
+}
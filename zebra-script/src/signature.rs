@@ -0,0 +1,226 @@
+//! Signature and public key encoding checks.
+//!
+//! Transparent signatures are DER-encoded ECDSA signatures, followed by a
+//! single hash type byte.
+
+use crate::{Flags, ScriptError};
+
+/// The hash type flag that only signs the input being spent.
+const SIGHASH_ANYONECANPAY: u8 = 0x80;
+
+/// The lowest defined hash type, which signs every output.
+const SIGHASH_ALL: u8 = 0x01;
+
+/// The highest defined hash type, which signs the output with the same index
+/// as the input.
+const SIGHASH_SINGLE: u8 = 0x03;
+
+/// Half of the order of the secp256k1 group, as a big-endian integer.
+///
+/// Signatures with an S value above this are malleable, because `(R, S)` and
+/// `(R, n - S)` are both valid.
+const HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Returns true if `signature` is a strict DER signature, followed by a hash
+/// type byte, as specified in BIP 66.
+///
+/// The format is:
+/// `0x30 [total-length] 0x02 [R-length] [R] 0x02 [S-length] [S] [hash-type]`
+pub fn is_valid_der(signature: &[u8]) -> bool {
+    // Minimum and maximum size constraints
+    if signature.len() < 9 || signature.len() > 73 {
+        return false;
+    }
+
+    // A signature is of type 0x30 (compound), and its length covers the
+    // entire signature, except for the hash type
+    if signature[0] != 0x30 || usize::from(signature[1]) != signature.len() - 3 {
+        return false;
+    }
+
+    // S must be inside the signature
+    let len_r = usize::from(signature[3]);
+    if 5 + len_r >= signature.len() {
+        return false;
+    }
+
+    // The lengths of R and S must add up to the length of the signature
+    let len_s = usize::from(signature[5 + len_r]);
+    if len_r + len_s + 7 != signature.len() {
+        return false;
+    }
+
+    is_valid_integer(&signature[2..], len_r) && is_valid_integer(&signature[4 + len_r..], len_s)
+}
+
+/// Returns true if `element` starts with a valid DER integer of `len` bytes.
+///
+/// DER integers are tagged with `0x02`, can't be empty or negative, and can't
+/// have unnecessary leading zeroes.
+fn is_valid_integer(element: &[u8], len: usize) -> bool {
+    element[0] == 0x02
+        && len != 0
+        && element[2] & 0x80 == 0
+        && !(len > 1 && element[2] == 0x00 && element[3] & 0x80 == 0)
+}
+
+/// Returns true if `signature` has an S value that is at most half the group
+/// order.
+///
+/// `signature` must be a valid DER signature.
+pub fn is_low_s(signature: &[u8]) -> bool {
+    let len_r = usize::from(signature[3]);
+    let len_s = usize::from(signature[5 + len_r]);
+    let s = &signature[6 + len_r..6 + len_r + len_s];
+
+    // Remove any leading zero, then compare as big-endian integers
+    let s = if s[0] == 0x00 { &s[1..] } else { s };
+    s.len() < HALF_ORDER.len() || (s.len() == HALF_ORDER.len() && s <= &HALF_ORDER[..])
+}
+
+/// Returns true if the hash type of `signature` is `SIGHASH_ALL`,
+/// `SIGHASH_NONE`, or `SIGHASH_SINGLE`, with or without
+/// `SIGHASH_ANYONECANPAY`.
+fn is_defined_hash_type(signature: &[u8]) -> bool {
+    match signature.last() {
+        Some(&hash_type) => {
+            (SIGHASH_ALL..=SIGHASH_SINGLE).contains(&(hash_type & !SIGHASH_ANYONECANPAY))
+        }
+        None => false,
+    }
+}
+
+/// Returns true if `public_key` is a compressed or uncompressed secp256k1
+/// public key.
+///
+/// Only the length and prefix are checked, not whether the key is on the
+/// curve.
+fn is_compressed_or_uncompressed(public_key: &[u8]) -> bool {
+    match public_key.first() {
+        Some(0x02) | Some(0x03) => public_key.len() == 33,
+        Some(0x04) => public_key.len() == 65,
+        _ => false,
+    }
+}
+
+/// Checks the encoding of a non-empty `signature`, using the signature rules
+/// in `flags`.
+///
+/// `STRICT_ENC` and `LOW_S` also require strict DER signatures.
+pub(crate) fn check_encoding(signature: &[u8], flags: Flags) -> Result<(), ScriptError> {
+    let strict_der = flags.intersects(Flags::STRICT_DER | Flags::LOW_S | Flags::STRICT_ENC);
+    if strict_der && !is_valid_der(signature) {
+        return Err(ScriptError::SigDer);
+    }
+    if flags.contains(Flags::LOW_S) && !is_low_s(signature) {
+        return Err(ScriptError::SigHighS);
+    }
+    if flags.contains(Flags::STRICT_ENC) && !is_defined_hash_type(signature) {
+        return Err(ScriptError::SigHashType);
+    }
+    Ok(())
+}
+
+/// Checks the encoding of `public_key`, using the public key rules in
+/// `flags`.
+pub(crate) fn check_public_key_encoding(
+    public_key: &[u8],
+    flags: Flags,
+) -> Result<(), ScriptError> {
+    if flags.contains(Flags::STRICT_ENC) && !is_compressed_or_uncompressed(public_key) {
+        return Err(ScriptError::PubKeyType);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A low-S signature with a 33-byte R and a `SIGHASH_ALL` hash type.
+    const SIGNATURE: &str = "3045022100cd8e2b5f2cf8cb1d87ee5ac8efd3f1b8cf9b8ee2d3f0de3bf5d1d1d8ea7a0e4f02203b1e3a4dcb1c7ce5bf6c1b3c8e9a47e5b6f5e8d4f72e3c9a5d0f8a5c9b7d3e2f01";
+
+    #[test]
+    fn der() {
+        let signature = hex::decode(SIGNATURE).unwrap();
+        assert!(is_valid_der(&signature));
+        assert!(is_low_s(&signature));
+
+        // Wrong total length
+        let mut bad = signature.clone();
+        bad[1] += 1;
+        assert!(!is_valid_der(&bad));
+
+        // Negative S
+        let mut bad = signature.clone();
+        bad[39] = 0x80;
+        assert!(!is_valid_der(&bad));
+
+        // Unnecessary leading zero in R
+        let mut bad = signature.clone();
+        bad[5] = 0x4d;
+        assert!(!is_valid_der(&bad));
+
+        // Missing hash type
+        assert!(!is_valid_der(&signature[..signature.len() - 1]));
+    }
+
+    #[test]
+    fn high_s() {
+        let mut signature = hex::decode(SIGNATURE).unwrap();
+        signature[39] = 0x7f;
+        for byte in &mut signature[40..=55] {
+            *byte = 0xff;
+        }
+        assert!(is_valid_der(&signature));
+        assert!(!is_low_s(&signature));
+        assert_eq!(check_encoding(&signature, Flags::STRICT_DER), Ok(()));
+        assert_eq!(
+            check_encoding(&signature, Flags::STRICT_DER | Flags::LOW_S),
+            Err(ScriptError::SigHighS)
+        );
+    }
+
+    #[test]
+    fn hash_types() {
+        let mut signature = hex::decode(SIGNATURE).unwrap();
+        for &(hash_type, defined) in &[
+            (0x00, false),
+            (0x01, true),
+            (0x03, true),
+            (0x04, false),
+            (0x81, true),
+            (0x84, false),
+        ] {
+            *signature.last_mut().unwrap() = hash_type;
+            assert_eq!(is_defined_hash_type(&signature), defined, "{}", hash_type);
+            assert_eq!(check_encoding(&signature, Flags::STRICT_DER), Ok(()));
+        }
+
+        *signature.last_mut().unwrap() = 0x04;
+        assert_eq!(
+            check_encoding(&signature, Flags::STRICT_ENC),
+            Err(ScriptError::SigHashType)
+        );
+    }
+
+    #[test]
+    fn public_keys() {
+        let compressed = [&[0x02][..], &[0x11; 32][..]].concat();
+        let uncompressed = [&[0x04][..], &[0x11; 64][..]].concat();
+        assert!(is_compressed_or_uncompressed(&compressed));
+        assert!(is_compressed_or_uncompressed(&uncompressed));
+        assert!(!is_compressed_or_uncompressed(&compressed[..32]));
+        assert!(!is_compressed_or_uncompressed(&uncompressed[1..]));
+        assert!(!is_compressed_or_uncompressed(&[]));
+
+        assert_eq!(check_public_key_encoding(&[], Flags::empty()), Ok(()));
+        assert_eq!(
+            check_public_key_encoding(&[], Flags::STRICT_ENC),
+            Err(ScriptError::PubKeyType)
+        );
+    }
+}
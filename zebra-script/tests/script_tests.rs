@@ -0,0 +1,167 @@
+//! Tests for the script interpreter, using a corpus in the format of Bitcoin's
+//! `script_tests.json`.
+
+use color_eyre::eyre::{bail, eyre, Report};
+use serde_json::Value;
+
+use zebra_chain::types::Script;
+use zebra_script::{opcodes, verify_script, Flags, ScriptNum, SignatureChecker};
+
+const SCRIPT_TESTS: &str = include_str!("data/script_tests.json");
+
+/// Lock times below this threshold are block heights, and lock times at or
+/// above it are Unix times.
+const LOCK_TIME_THRESHOLD: i64 = 500_000_000;
+
+/// A signature checker for a spending transaction with a lock time of
+/// `lock_time`, which doesn't verify any signatures.
+struct TestChecker {
+    lock_time: i64,
+}
+
+impl SignatureChecker for TestChecker {
+    fn check_signature(
+        &self,
+        _signature: &[u8],
+        _public_key: &[u8],
+        _script_code: &Script,
+    ) -> bool {
+        false
+    }
+
+    fn check_lock_time(&self, lock_time: i64) -> bool {
+        (lock_time < LOCK_TIME_THRESHOLD) == (self.lock_time < LOCK_TIME_THRESHOLD)
+            && lock_time <= self.lock_time
+    }
+}
+
+/// Parses a script in the corpus' human-readable format.
+///
+/// Scripts are whitespace-separated:
+///   * decimal numbers, which are pushed as script numbers,
+///   * `0x`-prefixed hex, which is copied to the script as raw bytes,
+///   * `'quoted'` strings, which are pushed as data, and
+///   * opcode names, with or without the `OP_` prefix.
+fn parse_script(text: &str) -> Result<Script, Report> {
+    let mut script = Vec::new();
+
+    for word in text.split_whitespace() {
+        let is_number = {
+            let digits = if word.starts_with('-') {
+                &word[1..]
+            } else {
+                word
+            };
+            !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit())
+        };
+
+        if is_number {
+            let n: i64 = word.parse()?;
+            match n {
+                0 => script.push(opcodes::OP_0),
+                -1 | 1..=16 => script.push((i64::from(opcodes::OP_1) - 1 + n) as u8),
+                _ => push(&mut script, &ScriptNum(n).encode()),
+            }
+        } else if word.starts_with("0x") && word.len() > 2 {
+            script.extend(hex::decode(&word[2..])?);
+        } else if word.len() >= 2 && word.starts_with('\'') && word.ends_with('\'') {
+            push(&mut script, word[1..word.len() - 1].as_bytes());
+        } else if let Some(opcode) = opcodes::from_name(word) {
+            script.push(opcode);
+        } else {
+            bail!("unknown script word {:?}", word);
+        }
+    }
+
+    Ok(Script(script))
+}
+
+/// Appends a push of `data` to `script`.
+fn push(script: &mut Vec<u8>, data: &[u8]) {
+    let len = data.len();
+    if len < usize::from(opcodes::OP_PUSHDATA1) {
+        script.push(len as u8);
+    } else if len <= 0xff {
+        script.extend(&[opcodes::OP_PUSHDATA1, len as u8]);
+    } else {
+        script.push(opcodes::OP_PUSHDATA2);
+        script.extend(&(len as u16).to_le_bytes());
+    }
+    script.extend(data);
+}
+
+/// Parses a comma-separated list of `Flags` names.
+fn parse_flags(text: &str) -> Result<Flags, Report> {
+    let mut flags = Flags::empty();
+
+    for name in text.split(',').filter(|name| !name.is_empty()) {
+        flags |= match name {
+            "P2SH" => Flags::P2SH,
+            "STRICT_ENC" => Flags::STRICT_ENC,
+            "STRICT_DER" => Flags::STRICT_DER,
+            "LOW_S" => Flags::LOW_S,
+            "NULL_DUMMY" => Flags::NULL_DUMMY,
+            "MINIMAL_DATA" => Flags::MINIMAL_DATA,
+            "DISCOURAGE_UPGRADABLE_NOPS" => Flags::DISCOURAGE_UPGRADABLE_NOPS,
+            "CLEAN_STACK" => Flags::CLEAN_STACK,
+            "CHECK_LOCK_TIME_VERIFY" => Flags::CHECK_LOCK_TIME_VERIFY,
+            "CONSENSUS" => Flags::CONSENSUS,
+            "STANDARD" => Flags::STANDARD,
+            _ => bail!("unknown flag {:?}", name),
+        };
+    }
+
+    Ok(flags)
+}
+
+#[test]
+fn script_tests() -> Result<(), Report> {
+    zebra_test::init();
+
+    let tests: Vec<Vec<Value>> = serde_json::from_str(SCRIPT_TESTS)?;
+    let checker = TestChecker { lock_time: 1000 };
+
+    for test in tests.iter().filter(|test| test.len() > 1) {
+        let fields = test
+            .iter()
+            .take(4)
+            .map(|field| {
+                field
+                    .as_str()
+                    .ok_or_else(|| eyre!("invalid test {:?}", test))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let script_sig = parse_script(fields[0])?;
+        let script_pubkey = parse_script(fields[1])?;
+        let flags = parse_flags(fields[2])?;
+
+        let result = match verify_script(&script_sig, &script_pubkey, flags, &checker) {
+            Ok(()) => "OK".to_owned(),
+            Err(error) => format!("{:?}", error),
+        };
+        assert_eq!(result, fields[3], "{}", serde_json::to_string(test)?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn parse_scripts() -> Result<(), Report> {
+    zebra_test::init();
+
+    for &(text, expected) in &[
+        ("0 1 16 -1", "0051604f"),
+        ("17 -2 1000", "0111018202e803"),
+        ("'Az' 0x4c 0x01 0x07", "02417a4c0107"),
+        ("DUP OP_HASH160 EQUALVERIFY OP_CHECKSIG", "76a988ac"),
+        ("NOP2 CHECKLOCKTIMEVERIFY", "b1b1"),
+    ] {
+        assert_eq!(hex::encode(parse_script(text)?.0), expected, "{}", text);
+    }
+
+    assert!(parse_script("NOT_AN_OPCODE").is_err());
+    assert!(parse_script("0x").is_err());
+
+    Ok(())
+}
@@ -0,0 +1,440 @@
+//! Fork-aware chain selection for the block header states.
+//!
+//! The header states store every header that connects to the genesis block,
+//! but only index the best chain by height. Headers that aren't on the best
+//! chain are kept in a non-finalized side chain set, until they are pruned.
+//!
+//! The best chain is the chain with the most cumulative work. When a side
+//! chain gets more work than the best chain, the state reorganises: it
+//! disconnects the best chain headers above the fork point, and connects the
+//! side chain headers instead.
+//!
+//! This module plans each insert using a read-only `ChainView` of the state.
+//! The in-memory and on-disk states then apply the plan to their own storage.
+use std::error;
+use std::iter;
+use std::sync::Arc;
+
+use zebra_chain::{
+    block::{BlockHeader, BlockHeaderHash, Work},
+    parameters::GENESIS_PREVIOUS_BLOCK_HASH,
+    types::BlockHeight,
+};
+
+use crate::{
+    events::ChainEvent,
+    locator::{self, MAX_FIND_BLOCK_HASHES_RESULTS, MAX_FIND_BLOCK_HEADERS_RESULTS},
+    QueryType, Response,
+};
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// The maximum number of best chain block headers that a reorganisation can
+/// disconnect.
+///
+/// Side chains that fork further below the tip are stored, but never become
+/// the best chain. This matches `MAX_REORG_LENGTH` in zcashd.
+pub const MAX_REORG_DEPTH: u32 = 99;
+
+/// Side chain block headers more than this many blocks below the best tip are
+/// pruned from the state.
+pub const SIDE_CHAIN_PRUNE_DEPTH: u32 = 288;
+
+/// A read-only view of the block headers in a state.
+pub(crate) trait ChainView {
+    /// Returns the block header with `hash`, on any chain.
+    fn header(&self, hash: BlockHeaderHash) -> Result<Option<Arc<BlockHeader>>, Error>;
+
+    /// Returns the height of the block header with `hash`, on any chain.
+    fn height(&self, hash: BlockHeaderHash) -> Result<Option<BlockHeight>, Error>;
+
+    /// Returns the cumulative work of the chain ending at `hash`, on any chain.
+    fn work(&self, hash: BlockHeaderHash) -> Result<Option<Work>, Error>;
+
+    /// Returns the hash of the best chain block header at `height`.
+    fn best_hash(&self, height: BlockHeight) -> Result<Option<BlockHeaderHash>, Error>;
+
+    /// Returns the hash and height of the best chain tip.
+    fn best_tip(&self) -> Result<Option<(BlockHeaderHash, BlockHeight)>, Error>;
+
+    /// Returns the side chain block headers below `height`.
+    fn side_chain_below(
+        &self,
+        height: BlockHeight,
+    ) -> Result<Vec<(BlockHeight, BlockHeaderHash)>, Error>;
+
+    /// Returns the height of `hash`, if it is on the best chain.
+    fn best_height(&self, hash: BlockHeaderHash) -> Result<Option<BlockHeight>, Error> {
+        match self.height(hash)? {
+            Some(height) if self.best_hash(height)? == Some(hash) => Ok(Some(height)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the depth of `hash` below the best tip, if it is on the best
+    /// chain.
+    fn best_depth(&self, hash: BlockHeaderHash) -> Result<Option<u32>, Error> {
+        let height = match self.best_height(hash)? {
+            Some(height) => height,
+            None => return Ok(None),
+        };
+
+        let (_tip_hash, tip_height) = self
+            .best_tip()?
+            .expect("the state must have a tip if it contains a best chain header");
+
+        Ok(Some(tip_height.0 - height.0))
+    }
+
+    /// Returns the block header for `query`, its height, and its depth below
+    /// the best tip, if it is on the best chain.
+    ///
+    /// Hash queries can return side chain block headers, but height queries
+    /// only return best chain block headers.
+    fn header_with_depth(
+        &self,
+        query: QueryType,
+    ) -> Result<Option<(Arc<BlockHeader>, BlockHeight, Option<u32>)>, Error> {
+        let hash = match query {
+            QueryType::ByHash(hash) => hash,
+            QueryType::ByHeight(height) => match self.best_hash(height)? {
+                Some(hash) => hash,
+                None => return Ok(None),
+            },
+        };
+
+        let block_header = match self.header(hash)? {
+            Some(block_header) => block_header,
+            None => return Ok(None),
+        };
+        let height = self
+            .height(hash)?
+            .ok_or_else(|| format!("block height {:?} could not be found", hash))?;
+
+        Ok(Some((block_header, height, self.best_depth(hash)?)))
+    }
+
+    /// Returns a block locator for the best chain, or an empty list if the
+    /// state is empty.
+    fn block_locator(&self) -> Result<Vec<BlockHeaderHash>, Error> {
+        let tip_height = match self.best_tip()? {
+            Some((_tip_hash, tip_height)) => tip_height,
+            None => return Ok(Vec::new()),
+        };
+
+        locator::block_locator_heights(tip_height)
+            .into_iter()
+            .map(|height| {
+                self.best_hash(height)?.ok_or_else(|| {
+                    format!("best chain block header at {:?} could not be found", height).into()
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()
+    }
+
+    /// Returns the hashes of the best chain block headers that follow
+    /// `known_blocks`, up to `stop` or `MAX_FIND_BLOCK_HASHES_RESULTS`.
+    fn find_block_hashes(
+        &self,
+        known_blocks: &[BlockHeaderHash],
+        stop: Option<BlockHeaderHash>,
+    ) -> Result<Vec<BlockHeaderHash>, Error> {
+        let successors = locator::find_successors(
+            known_blocks,
+            stop,
+            MAX_FIND_BLOCK_HASHES_RESULTS,
+            self.best_tip()?.map(|(_tip_hash, tip_height)| tip_height),
+            |hash| self.best_height(hash),
+            |height| self.best_hash(height),
+        )?;
+
+        Ok(successors.into_iter().map(|(_height, hash)| hash).collect())
+    }
+
+    /// Returns the best chain block headers that follow `known_blocks`, up to
+    /// `stop` or `MAX_FIND_BLOCK_HEADERS_RESULTS`.
+    fn find_block_headers(
+        &self,
+        known_blocks: &[BlockHeaderHash],
+        stop: Option<BlockHeaderHash>,
+    ) -> Result<Vec<Arc<BlockHeader>>, Error> {
+        let successors = locator::find_successors(
+            known_blocks,
+            stop,
+            MAX_FIND_BLOCK_HEADERS_RESULTS,
+            self.best_tip()?.map(|(_tip_hash, tip_height)| tip_height),
+            |hash| self.best_height(hash),
+            |height| self.best_hash(height),
+        )?;
+
+        successors
+            .into_iter()
+            .map(|(_height, hash)| {
+                self.header(hash)?.ok_or_else(|| {
+                    format!("best chain block header {:?} could not be found", hash).into()
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()
+    }
+}
+
+/// A planned block header insert.
+#[derive(Debug)]
+pub(crate) struct Insertion {
+    /// The new block header.
+    pub block_header: Arc<BlockHeader>,
+    /// The hash of the new block header.
+    pub hash: BlockHeaderHash,
+    /// The height of the new block header, derived from its parent.
+    pub height: BlockHeight,
+    /// The cumulative work of the chain ending at the new block header.
+    pub work: Work,
+    /// The changes to the best chain.
+    pub update: ChainUpdate,
+    /// The side chain block headers to remove from the state.
+    pub pruned: Vec<(BlockHeight, BlockHeaderHash)>,
+}
+
+/// The best chain changes for a block header insert.
+#[derive(Debug)]
+pub(crate) enum ChainUpdate {
+    /// The new block header extends the best chain.
+    Extend,
+    /// The new block header is on a side chain.
+    SideChain,
+    /// The new block header's side chain becomes the best chain.
+    Reorganize {
+        /// The best chain headers above the fork point, in ascending height
+        /// order. They become side chain headers.
+        disconnected: Vec<(BlockHeight, BlockHeaderHash)>,
+        /// The side chain headers above the fork point, in ascending height
+        /// order, ending with the new block header. They become best chain
+        /// headers.
+        connected: Vec<(BlockHeight, BlockHeaderHash, Arc<BlockHeader>)>,
+    },
+}
+
+impl Insertion {
+    /// Returns the state response for this insert.
+    pub fn response(&self) -> Response {
+        match &self.update {
+            ChainUpdate::Extend | ChainUpdate::SideChain => Response::Added {
+                hash: self.hash,
+                height: self.height,
+            },
+            ChainUpdate::Reorganize {
+                disconnected,
+                connected,
+            } => Response::Reorganized {
+                hash: self.hash,
+                height: self.height,
+                disconnected: disconnected.iter().map(|(_, hash)| *hash).collect(),
+                connected: connected.iter().map(|(_, hash, _)| *hash).collect(),
+            },
+        }
+    }
+
+    /// Returns the chain events for this insert.
+    ///
+    /// Side chain inserts don't change the best chain, so they don't have any
+    /// events.
+    pub fn chain_events(&self) -> Vec<ChainEvent> {
+        let new_tip = ChainEvent::NewTip {
+            hash: self.hash,
+            height: self.height,
+        };
+
+        match &self.update {
+            ChainUpdate::Extend => vec![
+                ChainEvent::Connected {
+                    hash: self.hash,
+                    height: self.height,
+                },
+                new_tip,
+            ],
+            ChainUpdate::SideChain => Vec::new(),
+            ChainUpdate::Reorganize {
+                disconnected,
+                connected,
+            } => disconnected
+                .iter()
+                .rev()
+                .map(|(height, hash)| ChainEvent::Disconnected {
+                    hash: *hash,
+                    height: *height,
+                })
+                .chain(
+                    connected
+                        .iter()
+                        .map(|(height, hash, _)| ChainEvent::Connected {
+                            hash: *hash,
+                            height: *height,
+                        }),
+                )
+                .chain(iter::once(new_tip))
+                .collect(),
+        }
+    }
+}
+
+/// Plan the insert of `block_header` into the state in `view`.
+///
+/// The height of `block_header` is derived from its parent, which must already
+/// be in the state. Headers without a parent are only accepted as the first
+/// header in an empty state.
+pub(crate) fn plan_insert(
+    view: &impl ChainView,
+    block_header: Arc<BlockHeader>,
+) -> Result<Insertion, Error> {
+    let hash: BlockHeaderHash = block_header.as_ref().into();
+    if view.height(hash)?.is_some() {
+        Err(format!(
+            "Entry (block header) with this hash {:?} already exist",
+            hash
+        ))?
+    }
+
+    let header_work = block_header.bits.to_work().ok_or_else(|| {
+        format!(
+            "block header {:?} has an invalid difficulty threshold {:?}",
+            hash, block_header.bits
+        )
+    })?;
+    let tip = view.best_tip()?;
+
+    let (height, work) = if block_header.previous_block_hash == GENESIS_PREVIOUS_BLOCK_HASH {
+        if tip.is_some() {
+            Err(format!(
+                "a genesis block header is already in the state, rejecting {:?}",
+                hash
+            ))?
+        }
+        (BlockHeight(0), header_work)
+    } else {
+        let parent_hash = block_header.previous_block_hash;
+        let parent_height = view
+            .height(parent_hash)?
+            .ok_or_else(|| format!("parent block header {:?} could not be found", parent_hash))?;
+        let parent_work = view.work(parent_hash)?.ok_or_else(|| {
+            format!(
+                "parent block header {:?} work could not be found",
+                parent_hash
+            )
+        })?;
+        let work = parent_work
+            .checked_add(header_work)
+            .ok_or("cumulative chain work overflowed")?;
+
+        (BlockHeight(parent_height.0 + 1), work)
+    };
+
+    let update = match tip {
+        None => ChainUpdate::Extend,
+        Some((tip_hash, _)) if tip_hash == block_header.previous_block_hash => ChainUpdate::Extend,
+        Some((tip_hash, tip_height)) => {
+            let tip_work = view
+                .work(tip_hash)?
+                .ok_or("best tip work could not be found")?;
+
+            // Ties go to the chain that was seen first, like zcashd
+            let update = if work > tip_work {
+                plan_reorganize(view, &block_header, hash, height, tip_height)?
+            } else {
+                ChainUpdate::SideChain
+            };
+
+            if let ChainUpdate::SideChain = update {
+                if height.0 + SIDE_CHAIN_PRUNE_DEPTH < tip_height.0 {
+                    Err(format!(
+                        "side chain block header {:?} at {:?} is too far below the best tip {:?}",
+                        hash, height, tip_height
+                    ))?
+                }
+            }
+
+            update
+        }
+    };
+
+    let new_tip_height = match (&update, tip) {
+        (ChainUpdate::SideChain, Some((_, tip_height))) => tip_height,
+        _ => height,
+    };
+    let pruned = match new_tip_height.0.checked_sub(SIDE_CHAIN_PRUNE_DEPTH) {
+        Some(prune_height) => view.side_chain_below(BlockHeight(prune_height))?,
+        None => Vec::new(),
+    };
+
+    Ok(Insertion {
+        block_header,
+        hash,
+        height,
+        work,
+        update,
+        pruned,
+    })
+}
+
+/// Plan a reorganisation to the side chain ending at `block_header`, which has
+/// more work than the best chain ending at `tip_height`.
+///
+/// Returns `ChainUpdate::SideChain` if the reorganisation would disconnect more
+/// than `MAX_REORG_DEPTH` best chain headers.
+fn plan_reorganize(
+    view: &impl ChainView,
+    block_header: &Arc<BlockHeader>,
+    hash: BlockHeaderHash,
+    height: BlockHeight,
+    tip_height: BlockHeight,
+) -> Result<ChainUpdate, Error> {
+    let mut connected = vec![(height, hash, block_header.clone())];
+    let mut ancestor_hash = block_header.previous_block_hash;
+    let mut ancestor_height = BlockHeight(height.0 - 1);
+
+    // Walk back until we reach the best chain
+    while view.best_hash(ancestor_height)? != Some(ancestor_hash) {
+        if ancestor_height.0 + MAX_REORG_DEPTH < tip_height.0 {
+            return Ok(ChainUpdate::SideChain);
+        }
+        if ancestor_height == BlockHeight(0) {
+            Err(format!(
+                "side chain block header {:?} doesn't connect to the best chain genesis block",
+                hash
+            ))?
+        }
+
+        let ancestor = view.header(ancestor_hash)?.ok_or_else(|| {
+            format!(
+                "side chain block header {:?} could not be found",
+                ancestor_hash
+            )
+        })?;
+        connected.push((ancestor_height, ancestor_hash, ancestor.clone()));
+
+        ancestor_hash = ancestor.previous_block_hash;
+        ancestor_height = BlockHeight(ancestor_height.0 - 1);
+    }
+
+    let fork_height = ancestor_height;
+    if tip_height.0 - fork_height.0 > MAX_REORG_DEPTH {
+        return Ok(ChainUpdate::SideChain);
+    }
+
+    let disconnected = (fork_height.0 + 1..=tip_height.0)
+        .map(|height| {
+            let height = BlockHeight(height);
+            view.best_hash(height)?
+                .map(|hash| (height, hash))
+                .ok_or_else(|| {
+                    format!("best chain block header at {:?} could not be found", height).into()
+                })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    connected.reverse();
+
+    Ok(ChainUpdate::Reorganize {
+        disconnected,
+        connected,
+    })
+}
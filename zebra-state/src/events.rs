@@ -0,0 +1,163 @@
+//! Chain events, which notify subscribers when the best chain changes.
+//!
+//! Each state publishes events after an insert has been written. Subscribers
+//! get a `Stream` of the events that are published after they subscribe.
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use futures::channel::mpsc;
+use zebra_chain::{block::BlockHeaderHash, types::BlockHeight};
+
+/// The maximum number of unread events for each subscriber.
+///
+/// Subscribers that fall further behind are disconnected, so slow subscribers
+/// can't make the state buffer an unbounded number of events. Disconnected
+/// subscribers can subscribe again, then catch up using `GetTip` and
+/// `GetBlockHeaderRange`.
+pub const MAX_UNREAD_CHAIN_EVENTS: usize = 1000;
+
+/// A change to the best chain of a state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChainEvent {
+    /// A block or block header was removed from the best chain by a
+    /// reorganisation
+    ///
+    /// Disconnected blocks are published in descending height order.
+    Disconnected {
+        /// The hash of the disconnected block
+        hash: BlockHeaderHash,
+        /// The height of the disconnected block
+        height: BlockHeight,
+    },
+    /// A block or block header was added to the best chain
+    ///
+    /// Connected blocks are published in ascending height order.
+    Connected {
+        /// The hash of the connected block
+        hash: BlockHeaderHash,
+        /// The height of the connected block
+        height: BlockHeight,
+    },
+    /// The best chain has a new tip
+    ///
+    /// Published after the `Disconnected` and `Connected` events for the
+    /// same insert.
+    NewTip {
+        /// The hash of the new tip
+        hash: BlockHeaderHash,
+        /// The height of the new tip
+        height: BlockHeight,
+    },
+}
+
+/// A stream of the chain events published by a state.
+///
+/// The stream ends if the state is dropped, or the subscriber has more than
+/// `MAX_UNREAD_CHAIN_EVENTS` unread events.
+pub type ChainEventStream = mpsc::Receiver<ChainEvent>;
+
+/// A handle for subscribing to the chain events of a state.
+///
+/// Returned by the `Subscribe` request.
+#[derive(Clone, Default)]
+pub struct ChainEvents {
+    subscribers: Arc<Mutex<Vec<mpsc::Sender<ChainEvent>>>>,
+}
+
+impl ChainEvents {
+    /// Returns a stream of the chain events published after this call.
+    pub fn subscribe(&self) -> ChainEventStream {
+        let (sender, receiver) = mpsc::channel(MAX_UNREAD_CHAIN_EVENTS);
+        self.subscribers
+            .lock()
+            .expect("chain event subscribers lock was poisoned")
+            .push(sender);
+
+        receiver
+    }
+
+    /// Send `events` to each subscriber, and drop any subscribers that are
+    /// closed or too far behind.
+    pub(crate) fn publish(&self, events: Vec<ChainEvent>) {
+        if events.is_empty() {
+            return;
+        }
+
+        let mut subscribers = self
+            .subscribers
+            .lock()
+            .expect("chain event subscribers lock was poisoned");
+
+        let mut connected = Vec::with_capacity(subscribers.len());
+        'subscribers: for mut sender in subscribers.drain(..) {
+            for event in &events {
+                if let Err(e) = sender.try_send(event.clone()) {
+                    if e.is_full() {
+                        tracing::warn!("disconnecting a chain event subscriber that fell behind");
+                    }
+                    continue 'subscribers;
+                }
+            }
+
+            connected.push(sender);
+        }
+
+        *subscribers = connected;
+    }
+}
+
+impl fmt::Debug for ChainEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let subscribers = self
+            .subscribers
+            .lock()
+            .map(|subscribers| subscribers.len())
+            .unwrap_or_default();
+
+        f.debug_struct("ChainEvents")
+            .field("subscribers", &subscribers)
+            .finish()
+    }
+}
+
+/// Chain event handles are equal if they publish the events of the same state.
+impl PartialEq for ChainEvents {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.subscribers, &other.subscribers)
+    }
+}
+
+impl Eq for ChainEvents {}
+
+/// Returns the chain events for adding the block `hash` at `height` to a
+/// block state.
+///
+/// Block states index blocks by height, so a new block replaces any `replaced`
+/// block at the same height. `tip_height` is the height of the tip before the
+/// insert.
+pub(crate) fn block_events(
+    hash: BlockHeaderHash,
+    height: BlockHeight,
+    replaced: Option<BlockHeaderHash>,
+    tip_height: Option<BlockHeight>,
+) -> Vec<ChainEvent> {
+    let mut events = Vec::new();
+
+    match replaced {
+        Some(replaced) if replaced == hash => return events,
+        Some(replaced) => events.push(ChainEvent::Disconnected {
+            hash: replaced,
+            height,
+        }),
+        None => {}
+    }
+
+    events.push(ChainEvent::Connected { hash, height });
+    if tip_height.map_or(true, |tip_height| height >= tip_height) {
+        events.push(ChainEvent::NewTip { hash, height });
+    }
+
+    events
+}
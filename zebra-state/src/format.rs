@@ -0,0 +1,223 @@
+//! On-disk database format versions and migrations.
+//!
+//! Each on-disk state has a `metadata` tree, which records the database format
+//! version and the network of the stored chain.
+//!
+//! Databases are stored in a subdirectory of `cache_dir` for each network,
+//! for example `cache_dir/testnet/headers`.
+//!
+//! ## Format version 2
+//!
+//! The block database (`blocks`) has a transaction index:
+//!   * `tx_by_hash`: `TransactionHash` -> `BlockHeaderHash`, followed by the
+//!     transaction's index in the block, as a big-endian `u32`
+//!
+//! The block header database has the same layout as version 1.
+//!
+//! ## Format version 1
+//!
+//! Heights are big-endian `u32`s, and block headers and blocks are stored in
+//! their zcash serialization.
+//!
+//! The block header database (`headers`) has these trees:
+//!   * `by_hash`: `BlockHeaderHash` -> `BlockHeader`
+//!   * `by_height`: best chain `BlockHeight` -> `BlockHeader`
+//!   * `hash_height`: `BlockHeaderHash` -> `BlockHeight`
+//!   * `hash_work`: `BlockHeaderHash` -> cumulative `Work`, as a big-endian
+//!     `u128`
+//!   * `side_chain`: `BlockHeight` followed by `BlockHeaderHash` -> empty
+//!
+//! The block database (`blocks`) has these trees:
+//!   * `by_hash`: `BlockHeaderHash` -> `Block`
+//!   * `by_height`: `BlockHeight` -> `Block`
+//!
+//! ## Format version 0
+//!
+//! Databases without a version were written before versions were recorded.
+//! Block header databases didn't have the `hash_work` and `side_chain` trees.
+//! Inserts weren't transactional, so version 0 databases can contain
+//! partially written inserts.
+//!
+//! ## Consistency checks
+//!
+//! Every time a state is opened, it checks that its trees are consistent,
+//! and repairs any partially written inserts.
+use std::{error, fs};
+
+use zebra_chain::Network;
+
+use crate::Config;
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// The current on-disk database format version.
+///
+/// Increment this version whenever the layout of an on-disk tree changes, and
+/// add a `Migration` that upgrades existing databases.
+pub const DATABASE_FORMAT_VERSION: u32 = 2;
+
+/// The name of the tree that stores database metadata.
+const METADATA_TREE: &[u8] = b"metadata";
+
+/// The `metadata` key for the database format version, a big-endian `u32`.
+const VERSION_KEY: &[u8] = b"version";
+
+/// The `metadata` key for the network name.
+const NETWORK_KEY: &[u8] = b"network";
+
+/// An in-place upgrade of an on-disk database.
+pub(crate) struct Migration {
+    /// The database format version after this migration.
+    pub version: u32,
+    /// A short description of the layout change.
+    pub description: &'static str,
+    /// Upgrades a database from the previous format version.
+    pub migrate: fn(&sled::Db) -> Result<(), Error>,
+}
+
+/// Open the on-disk database in `path_dir`, and upgrade it to the current
+/// format version.
+///
+/// Returns an error if the database is for a different network, or has a
+/// newer format version.
+pub(crate) fn open(
+    config: &Config,
+    path_dir: &str,
+    network: Network,
+    migrations: &[Migration],
+) -> Result<sled::Db, Error> {
+    if !config.ephemeral {
+        move_legacy_db(config, network, path_dir)?;
+    }
+
+    let db = config.sled_config(network, path_dir).open()?;
+    upgrade(&db, network, migrations)?;
+
+    Ok(db)
+}
+
+/// Move a database from `cache_dir` to its network subdirectory.
+///
+/// Databases used to be stored directly in `cache_dir`. Those databases were
+/// always mainnet chains, because the syncers only accepted the mainnet
+/// genesis block.
+fn move_legacy_db(config: &Config, network: Network, path_dir: &str) -> Result<(), Error> {
+    let legacy_path = config.cache_dir.join(path_dir);
+    let path = config.db_path(network, path_dir);
+
+    if network != Network::Mainnet || !legacy_path.exists() || path.exists() {
+        return Ok(());
+    }
+
+    tracing::info!(
+        ?legacy_path,
+        ?path,
+        "moving the state database to its network directory"
+    );
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(legacy_path, path)?;
+
+    Ok(())
+}
+
+/// Check the format version and network of `db`, then run any `migrations`
+/// that are newer than its format version.
+///
+/// Unversioned databases don't record their network, so they are assumed to
+/// be for `network`.
+fn upgrade(db: &sled::Db, network: Network, migrations: &[Migration]) -> Result<(), Error> {
+    let metadata = db.open_tree(METADATA_TREE)?;
+
+    let version = match metadata.get(VERSION_KEY)? {
+        Some(bytes) => version_from_bytes(&bytes)?,
+        None if is_empty(db)? => DATABASE_FORMAT_VERSION,
+        None => 0,
+    };
+
+    if version > DATABASE_FORMAT_VERSION {
+        Err(format!(
+            "the state database has format version {}, but this node only supports versions up to {}",
+            version, DATABASE_FORMAT_VERSION
+        ))?;
+    }
+
+    if let Some(name) = metadata.get(NETWORK_KEY)? {
+        let db_network = network_from_name(&name)?;
+        if db_network != network {
+            Err(format!(
+                "the state database contains a {:?} chain, but the node is configured for {:?}",
+                db_network, network
+            ))?;
+        }
+    }
+
+    for migration in migrations
+        .iter()
+        .filter(|migration| migration.version > version)
+    {
+        tracing::info!(
+            version = migration.version,
+            description = migration.description,
+            "upgrading the state database format"
+        );
+
+        (migration.migrate)(db)?;
+        metadata.insert(VERSION_KEY, &migration.version.to_be_bytes())?;
+        db.flush()?;
+    }
+
+    metadata.insert(VERSION_KEY, &DATABASE_FORMAT_VERSION.to_be_bytes())?;
+    metadata.insert(NETWORK_KEY, network_name(network))?;
+    db.flush()?;
+
+    Ok(())
+}
+
+/// Returns true if every tree in `db`, other than the metadata tree, is empty.
+fn is_empty(db: &sled::Db) -> Result<bool, Error> {
+    for name in db.tree_names() {
+        if &*name == METADATA_TREE {
+            continue;
+        }
+
+        if !db.open_tree(&name)?.is_empty() {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn version_from_bytes(bytes: &[u8]) -> Result<u32, Error> {
+    if bytes.len() != 4 {
+        Err(format!(
+            "the state database format version has an invalid length: {}",
+            bytes.len()
+        ))?;
+    }
+
+    let mut version = [0u8; 4];
+    version.clone_from_slice(bytes);
+    Ok(u32::from_be_bytes(version))
+}
+
+fn network_name(network: Network) -> &'static str {
+    match network {
+        Network::Mainnet => "Mainnet",
+        Network::Testnet => "Testnet",
+    }
+}
+
+fn network_from_name(name: &[u8]) -> Result<Network, Error> {
+    match name {
+        b"Mainnet" => Ok(Network::Mainnet),
+        b"Testnet" => Ok(Network::Testnet),
+        _ => Err(format!(
+            "the state database has an unknown network: {:?}",
+            String::from_utf8_lossy(name)
+        ))?,
+    }
+}
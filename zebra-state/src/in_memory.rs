@@ -3,7 +3,10 @@
 //! This service is provided as an independent implementation of the
 //! zebra-state service to use in verifying the correctness of `on_disk`'s
 //! `Service` implementation.
-use super::{RequestBlock, Response, QueryType};
+use super::{
+    RequestBlock, Response, QueryType, MAX_FIND_BLOCK_HASHES_RESULTS,
+    MAX_FIND_BLOCK_HEADERS_RESULTS,
+};
 use futures::prelude::*;
 use std::{
     collections::{BTreeMap, HashMap},
@@ -19,6 +22,8 @@ use zebra_chain::{
     types::BlockHeight,
 };
 
+use crate::events::ChainEvents;
+
 mod block_index;
 
 type Error = Box<dyn error::Error + Send + Sync + 'static>;
@@ -26,6 +31,7 @@ type Error = Box<dyn error::Error + Send + Sync + 'static>;
 #[derive(Default)]
 struct InMemoryState<T> {
     index: block_index::BlockIndex<T>,
+    events: ChainEvents,
 }
 
 impl Service<RequestBlock> for InMemoryState<Block> {
@@ -43,7 +49,7 @@ impl Service<RequestBlock> for InMemoryState<Block> {
             RequestBlock::AddBlock { block } => {
                 let result = self
                     .index
-                    .insert(block)
+                    .insert(block, &self.events)
                     .map(|(hash, height)| Response::Added { hash, height });
 
                 async move { result }.boxed()
@@ -103,6 +109,47 @@ impl Service<RequestBlock> for InMemoryState<Block> {
                 }
                 .boxed()
             }
+            RequestBlock::GetTransaction { hash } => {
+                let result = self
+                    .index
+                    .get_transaction(hash)
+                    .map(Response::Transaction);
+
+                async move { result }.boxed()
+            }
+            RequestBlock::GetBlockLocator => {
+                let result = self
+                    .index
+                    .block_locator()
+                    .map(|block_locator| Response::BlockLocator { block_locator });
+
+                async move { result }.boxed()
+            }
+            RequestBlock::FindBlockHeaders { known_blocks, stop } => {
+                let result = self
+                    .index
+                    .find_successors(&known_blocks, stop, MAX_FIND_BLOCK_HEADERS_RESULTS)
+                    .map(|blocks| Response::BlockHeaders {
+                        block_headers: blocks.iter().map(|block| block.header.into()).collect(),
+                    });
+
+                async move { result }.boxed()
+            }
+            RequestBlock::FindBlockHashes { known_blocks, stop } => {
+                let result = self
+                    .index
+                    .find_successors(&known_blocks, stop, MAX_FIND_BLOCK_HASHES_RESULTS)
+                    .map(|blocks| Response::BlockHashes {
+                        block_hashes: blocks.iter().map(|block| block.as_ref().into()).collect(),
+                    });
+
+                async move { result }.boxed()
+            }
+            RequestBlock::Subscribe => {
+                let chain_events = self.events.clone();
+
+                async move { Ok(Response::Subscription { chain_events }) }.boxed()
+            }
         }
     }
 }
@@ -122,6 +169,8 @@ pub fn init() -> impl Service<
         index: block_index::BlockIndex::<Block>{
             by_hash: HashMap::<BlockHeaderHash, Arc<Block>>::default(),
             by_height: BTreeMap::<BlockHeight, Arc<Block>>::default(),
+            by_tx: HashMap::default(),
         },
+        events: ChainEvents::default(),
     }, 1)
 }
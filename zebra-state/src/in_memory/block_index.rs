@@ -1,20 +1,20 @@
 use std::{
-    collections::{
-        btree_map::Entry as BTreeMapEntry,
-        hash_map::Entry as HashMapEntry,
-        BTreeMap,
-        HashMap
-    },
+    collections::{BTreeMap, HashMap},
     error,
     sync::Arc,
 };
 
 use zebra_chain::{
     block::{Block, BlockHeaderHash},
+    transaction::{Transaction, TransactionHash},
     types::BlockHeight,
 };
 
 use super::QueryType;
+use crate::{
+    events::{block_events, ChainEvents},
+    locator,
+};
 
 type Error = Box<dyn error::Error + Send + Sync + 'static>;
 
@@ -22,63 +22,46 @@ type Error = Box<dyn error::Error + Send + Sync + 'static>;
 pub(super) struct BlockIndex<T> {
     pub by_hash: HashMap<BlockHeaderHash, Arc<T>>,
     pub by_height: BTreeMap<BlockHeight, Arc<T>>,
-}
-
-#[derive(Copy, Clone)]
-enum Either<Hash, Height, E> {
-    Hash(Hash),
-    Height(Height),
-    Error(E),
+    /// The block hash and transaction index of each transaction
+    pub by_tx: HashMap<TransactionHash, (BlockHeaderHash, usize)>,
 }
 
 impl BlockIndex<Block> {
+    /// Insert `block`, and publish the best chain changes to `events`.
+    ///
+    /// Like the on-disk state, a new block replaces any block at the same
+    /// height. The replaced block's transactions are removed from the
+    /// transaction index, because they are no longer in the best chain.
     pub fn insert(
         &mut self,
         block: impl Into<Arc<Block>>,
+        events: &ChainEvents,
     ) -> Result<(BlockHeaderHash, BlockHeight), Error> {
         let block = block.into();
         let hash: BlockHeaderHash = block.as_ref().into();
         let height = block.coinbase_height().unwrap();
+        let tip_height = self.get_tip()?.and_then(|tip| tip.coinbase_height());
 
-        let hash_result = match self.by_hash.entry(hash) {
-            HashMapEntry::Vacant(entry) => {
-                let _ = entry.insert(block.clone());
-            //  let _ = self.by_hash.insert(hash, block);
-                Either::<BlockHeaderHash, BlockHeight, _>::Hash(hash)
-            }
-            HashMapEntry::Occupied(_) => Either::Error(format!("Entry (block) with this hash {:?} already exist", hash))
-        };
+        if self.by_hash.contains_key(&hash) {
+            Err(format!("Entry (block) with this hash {:?} already exist", hash))?
+        }
 
-        let height_result = match self.by_height.entry(height) {
-            BTreeMapEntry::Vacant(entry) => {
-                let _ = entry.insert(block.clone());
-            //  let _ = self.by_height.insert(height, block);
-                Either::<BlockHeaderHash, BlockHeight, _>::Height(height)
+        let replaced = self.by_height.insert(height, block.clone());
+        if let Some(replaced) = &replaced {
+            for transaction in replaced.transactions.iter() {
+                let _ = self.by_tx.remove(&transaction.as_ref().into());
             }
-            BTreeMapEntry::Occupied(_) => Either::Error(format!("Entry (block) with this height {:?} already exist", height))
-        };
+        }
 
-        match (&hash_result, &height_result) {
-            (Either::Hash(hash), Either::Height(height)) => Ok((hash.clone(), height.clone())),
-            (Either::Error(_hash_error), Either::Error(_height_error)) => Err(format!("Entry (block) with this hash {:?} & height {:?} already exist", hash, height))?,
-            _ => {
-                let mut error_result: String = String::from("");
-//              for error in [hash_result, height_result].iter() {
-//                  match error {
-//                      Either::Error(e) => error_result = format!(" {:?} {:?};", error_result, e),
-//                      _ => error_result = format!(" {:?};", error_result),
-//                  }
-//              };
-                for error in &[hash_result, height_result] {
-                    if let Either::Error(e) = error {
-                        error_result = format!(" {:?} {:?};", error_result, e)
-                    } else {
-                        error_result = format!(" {:?};", error_result)
-                    }
-                };
-                Err(format!("Error: {:?}", error_result))?
-            },
+        let _ = self.by_hash.insert(hash, block.clone());
+        for (index, transaction) in block.transactions.iter().enumerate() {
+            let _ = self.by_tx.insert(transaction.as_ref().into(), (hash, index));
         }
+
+        let replaced = replaced.map(|replaced| replaced.as_ref().into());
+        events.publish(block_events(hash, height, replaced, tip_height));
+
+        Ok((hash, height))
     }
 
     pub fn get(&self, query: impl Into<QueryType>) -> Result<Option<Arc<Block>>, Error> {
@@ -94,6 +77,32 @@ impl BlockIndex<Block> {
         }
     }
 
+    /// Returns the transaction with `hash`, and the hash and height of the
+    /// block that contains it.
+    pub fn get_transaction(
+        &self,
+        hash: TransactionHash,
+    ) -> Result<Option<(Arc<Transaction>, BlockHeaderHash, BlockHeight)>, Error> {
+        let (block_hash, index) = match self.by_tx.get(&hash) {
+            Some(location) => *location,
+            None => return Ok(None),
+        };
+        let block = self
+            .by_hash
+            .get(&block_hash)
+            .ok_or("indexed transaction is missing its block")?;
+        let transaction = block
+            .transactions
+            .get(index)
+            .ok_or("indexed transaction is missing from its block")?
+            .clone();
+        let height = block
+            .coinbase_height()
+            .ok_or("indexed block is missing its coinbase height")?;
+
+        Ok(Some((transaction, block_hash, height)))
+    }
+
     pub fn get_tip(&self) -> Result<Option<Arc<Block>>, Error> {
         let last_entry = self.by_height
             .iter()
@@ -111,6 +120,58 @@ impl BlockIndex<Block> {
         let key = &hash;
         Ok(self.by_hash.contains_key(key))
     }
+
+    /// Returns a block locator for the best chain, or an empty list if the
+    /// state is empty.
+    ///
+    /// Heights without a stored block are skipped.
+    pub fn block_locator(&self) -> Result<Vec<BlockHeaderHash>, Error> {
+        let tip_height = match self.get_tip()? {
+            Some(tip) => tip
+                .coinbase_height()
+                .ok_or("tip block is missing its coinbase height")?,
+            None => return Ok(Vec::new()),
+        };
+
+        Ok(locator::block_locator_heights(tip_height)
+            .into_iter()
+            .filter_map(|height| self.by_height.get(&height))
+            .map(|block| block.as_ref().into())
+            .collect())
+    }
+
+    /// Returns the best chain blocks that follow `known_blocks`, up to `stop`
+    /// or `max_len` blocks.
+    pub fn find_successors(
+        &self,
+        known_blocks: &[BlockHeaderHash],
+        stop: Option<BlockHeaderHash>,
+        max_len: u32,
+    ) -> Result<Vec<Arc<Block>>, Error> {
+        let tip_height = self.get_tip()?.and_then(|tip| tip.coinbase_height());
+        let best_hash = |height: BlockHeight| -> Result<Option<BlockHeaderHash>, Error> {
+            Ok(self
+                .by_height
+                .get(&height)
+                .map(|block| block.as_ref().into()))
+        };
+        let best_height = |hash: BlockHeaderHash| -> Result<Option<BlockHeight>, Error> {
+            let height = self.by_hash.get(&hash).and_then(|block| block.coinbase_height());
+            match height {
+                Some(height) if best_hash(height)? == Some(hash) => Ok(Some(height)),
+                _ => Ok(None),
+            }
+        };
+
+        locator::find_successors(known_blocks, stop, max_len, tip_height, best_height, best_hash)?
+            .into_iter()
+            .map(|(height, _hash)| {
+                self.by_height.get(&height).cloned().ok_or_else(|| {
+                    format!("best chain block at {:?} could not be found", height).into()
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()
+    }
 }
 
 /*
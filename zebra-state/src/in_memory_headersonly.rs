@@ -6,7 +6,7 @@
 use super::{RequestBlockHeader, Response, QueryType};
 use futures::prelude::*;
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
     error,
     sync::Arc,
     future::Future,
@@ -15,10 +15,12 @@ use std::{
 };
 use tower::{buffer::Buffer, Service};
 use zebra_chain::{
-    block::{BlockHeader, BlockHeaderHash},
+    block::{BlockHeader, BlockHeaderHash, Work},
     types::BlockHeight,
 };
 
+use crate::{chain::ChainView, events::ChainEvents, range};
+
 mod block_index;
 
 type Error = Box<dyn error::Error + Send + Sync + 'static>;
@@ -26,6 +28,7 @@ type Error = Box<dyn error::Error + Send + Sync + 'static>;
 #[derive(Default)]
 struct InMemoryState<T> {
     index: block_index::BlockIndex<T>,
+    events: ChainEvents,
 }
 
 impl Service<RequestBlockHeader> for InMemoryState<BlockHeader> {
@@ -40,23 +43,22 @@ impl Service<RequestBlockHeader> for InMemoryState<BlockHeader> {
 
     fn call(&mut self, req: RequestBlockHeader) -> Self::Future {
         match req {
-            RequestBlockHeader::AddBlockHeader { block_header, block_height } => {
-                let result = self
-                    .index
-                    .insert(block_header, block_height)
-                    .map(|(hash, height)| Response::Added { hash, height });
+            RequestBlockHeader::AddBlockHeader { block_header } => {
+                let result = self.index.insert(block_header, &self.events);
 
                 async move { result }.boxed()
             }
             RequestBlockHeader::GetBlockHeader { query } => {
                 let storage = self.index.clone();
                 async move {
-                    let block_header = storage.get(query)?; //? .unwrap() .unwrap_or(default: T) .expect("GetBlockHeader - block header could not be found")
-                    let hash: BlockHeaderHash = block_header.clone().unwrap().as_ref().into();
-                    let height = storage.get_height(hash)?.unwrap();
-                    block_header
-                        .map(|block_header| Response::BlockHeader { block_header: block_header, block_height: height })
-                        .ok_or_else(|| "GetBlockHeader - block header could not be found".into())
+                    let block_header = storage
+                        .get(query)?
+                        .ok_or("GetBlockHeader - block header could not be found")?;
+                    let hash: BlockHeaderHash = block_header.as_ref().into();
+                    let block_height = storage
+                        .get_height(hash)?
+                        .ok_or("GetBlockHeader - block height could not be found")?;
+                    Ok(Response::BlockHeader { block_header, block_height })
                 }
                 .boxed()
             }
@@ -81,27 +83,71 @@ impl Service<RequestBlockHeader> for InMemoryState<BlockHeader> {
                 .boxed()
             }
             RequestBlockHeader::GetDepth { hash } => {
-                let storage = self.index.clone();
+                // Side chain block headers aren't part of the best chain, so
+                // they don't have a depth
+                let result = self.index.best_depth(hash).map(Response::Depth);
 
-                async move {
-                    if !storage.contains(&hash)? {
-                        return Ok(Response::Depth(None));
-                    }
+                async move { result }.boxed()
+            }
+            RequestBlockHeader::GetBlockHeaderWithDepth { query } => {
+                let result = self
+                    .index
+                    .header_with_depth(query)
+                    .map(Response::BlockHeaderWithDepth);
 
-                    let block_header_height = storage
-                        .get_height(hash)?
-                        .expect("GetDepth - block header must be present if contains() returned true");
+                async move { result }.boxed()
+            }
+            RequestBlockHeader::Contains { hash } => {
+                let result = self.index.contains(&hash).map(Response::Contains);
 
-                    let tip = storage
-                        .get_tip()?
-                        .map(|(_header, _hash, height)| height)
-                        .expect("GetDepth - storage must have a tip if it contains() the previous block header");
+                async move { result }.boxed()
+            }
+            RequestBlockHeader::GetBlockLocator => {
+                let result = self
+                    .index
+                    .block_locator()
+                    .map(|block_locator| Response::BlockLocator { block_locator });
 
-                    let depth = tip.0 - block_header_height.0;
+                async move { result }.boxed()
+            }
+            RequestBlockHeader::FindBlockHeaders { known_blocks, stop } => {
+                let result = self
+                    .index
+                    .find_block_headers(&known_blocks, stop)
+                    .map(|block_headers| Response::BlockHeaders { block_headers });
 
-                    Ok(Response::Depth(Some(depth)))
-                }
-                .boxed()
+                async move { result }.boxed()
+            }
+            RequestBlockHeader::FindBlockHashes { known_blocks, stop } => {
+                let result = self
+                    .index
+                    .find_block_hashes(&known_blocks, stop)
+                    .map(|block_hashes| Response::BlockHashes { block_hashes });
+
+                async move { result }.boxed()
+            }
+            RequestBlockHeader::GetBlockHeaderRange { start, end } => {
+                let result = self
+                    .index
+                    .get_range(start, end)
+                    .map(|block_headers| Response::BlockHeadersWithHeights { block_headers });
+
+                async move { result }.boxed()
+            }
+            RequestBlockHeader::GetBlockHeaders(queries) => {
+                let result = range::get_batch(
+                    queries,
+                    |query| self.index.get(query),
+                    |hash| self.index.get_height(hash),
+                )
+                .map(|block_headers| Response::BlockHeadersWithHeights { block_headers });
+
+                async move { result }.boxed()
+            }
+            RequestBlockHeader::Subscribe => {
+                let chain_events = self.events.clone();
+
+                async move { Ok(Response::Subscription { chain_events }) }.boxed()
             }
          /* RequestBlockHeader::GetDepth { hash: _ } => {
                 async move { Ok(Response::Depth(None)) }.boxed()
@@ -126,6 +172,9 @@ pub fn init() -> impl Service<
             by_hash: HashMap::<BlockHeaderHash, Arc<BlockHeader>>::default(),
             by_height: BTreeMap::<BlockHeight, Arc<BlockHeader>>::default(),
             hash_height: HashMap::<BlockHeaderHash, BlockHeight>::default(),
+            hash_work: HashMap::<BlockHeaderHash, Work>::default(),
+            side_chain: BTreeMap::<BlockHeight, HashSet<BlockHeaderHash>>::default(),
         },
+        events: ChainEvents::default(),
     }, 1)
 }
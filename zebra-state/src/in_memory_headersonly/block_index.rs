@@ -1,95 +1,97 @@
 use std::{
-    collections::{
-        btree_map::Entry as BTreeMapEntry,
-        hash_map::Entry as HashMapEntry,
-        BTreeMap,
-        HashMap
-    },
+    collections::{BTreeMap, HashMap, HashSet},
     error,
     sync::Arc,
 };
 
 use zebra_chain::{
-    block::{BlockHeader, BlockHeaderHash},
+    block::{BlockHeader, BlockHeaderHash, Work},
     types::BlockHeight,
 };
 
 use super::QueryType;
+use crate::{
+    chain::{self, ChainUpdate, ChainView},
+    events::ChainEvents,
+    range,
+};
 
 type Error = Box<dyn error::Error + Send + Sync + 'static>;
 
 #[derive(Default, Clone)]
 pub(super) struct BlockIndex<T> {
+    /// Every stored block header, on the best chain or a side chain
     pub by_hash: HashMap<BlockHeaderHash, Arc<T>>,
+    /// The best chain block headers
     pub by_height: BTreeMap<BlockHeight, Arc<T>>,
+    /// The heights of every stored block header
     pub hash_height: HashMap<BlockHeaderHash, BlockHeight>,
-}
-
-#[derive(Copy, Clone)]
-enum Either<Hash, Height, E> {
-    Hash(Hash),
-    Height(Height),
-    HashHeight(Hash, Height),
-    Error(E),
+    /// The cumulative chain work of every stored block header
+    pub hash_work: HashMap<BlockHeaderHash, Work>,
+    /// The non-finalized side chain block headers, by height
+    pub side_chain: BTreeMap<BlockHeight, HashSet<BlockHeaderHash>>,
 }
 
 impl BlockIndex<BlockHeader> {
+    /// Insert `block_header`, updating the best chain if its chain has the
+    /// most work, and publish the best chain changes to `events`.
+    ///
+    /// Returns the state response for the insert.
     pub fn insert(
         &mut self,
         block_header: impl Into<Arc<BlockHeader>>,
-        block_height: BlockHeight,
-    ) -> Result<(BlockHeaderHash, BlockHeight), Error> {
-        let block_header = block_header.into();
-        let hash: BlockHeaderHash = block_header.as_ref().into();
-//      let height = block.coinbase_height().unwrap(); // didn't applicable for block height handling
-        let height = block_height;
-
-        let hash_result = match self.by_hash.entry(hash) {
-            HashMapEntry::Vacant(entry) => {
-                let _ = entry.insert(block_header.clone());
-            //  let _ = self.by_hash.insert(hash, block_header);
-                Either::<BlockHeaderHash, BlockHeight, _>::Hash(hash)
+        events: &ChainEvents,
+    ) -> Result<crate::Response, Error> {
+        let insertion = chain::plan_insert(self, block_header.into())?;
+        let response = insertion.response();
+
+        let hash = insertion.hash;
+        let height = insertion.height;
+        let _ = self.by_hash.insert(hash, insertion.block_header.clone());
+        let _ = self.hash_height.insert(hash, height);
+        let _ = self.hash_work.insert(hash, insertion.work);
+
+        match insertion.update {
+            ChainUpdate::Extend => {
+                let _ = self.by_height.insert(height, insertion.block_header);
             }
-            HashMapEntry::Occupied(_) => Either::Error(format!("Entry (block header) with this hash {:?} already exist", hash)),
-        };
-
-        let height_result = match self.by_height.entry(height) {
-            BTreeMapEntry::Vacant(entry) => {
-                let _ = entry.insert(block_header.clone());
-            //  let _ = self.by_height.insert(height, block_header);
-                Either::<BlockHeaderHash, BlockHeight, _>::Height(height)
+            ChainUpdate::SideChain => {
+                let _ = self.side_chain.entry(height).or_default().insert(hash);
             }
-            BTreeMapEntry::Occupied(_) => Either::Error(format!("Entry (block header) with this height {:?} already exist", height)),
-        };
-
-        let hash_height_result = match self.hash_height.entry(hash) {
-            HashMapEntry::Vacant(entry) => {
-                let _ = entry.insert(height.clone());
-            //  let _ = self.hash_height.insert(hash, height);
-                Either::<BlockHeaderHash, BlockHeight, _>::HashHeight(hash, height)
+            ChainUpdate::Reorganize {
+                disconnected,
+                connected,
+            } => {
+                for (height, hash) in disconnected {
+                    let _ = self.by_height.remove(&height);
+                    let _ = self.side_chain.entry(height).or_default().insert(hash);
+                }
+                for (height, hash, block_header) in connected {
+                    self.remove_side_chain_entry(height, hash);
+                    let _ = self.by_height.insert(height, block_header);
+                }
+            }
+        }
+
+        for (height, hash) in insertion.pruned {
+            self.remove_side_chain_entry(height, hash);
+            let _ = self.by_hash.remove(&hash);
+            let _ = self.hash_height.remove(&hash);
+            let _ = self.hash_work.remove(&hash);
+        }
+
+        events.publish(insertion.chain_events());
+
+        Ok(response)
+    }
+
+    /// Remove `hash` at `height` from the side chain set.
+    fn remove_side_chain_entry(&mut self, height: BlockHeight, hash: BlockHeaderHash) {
+        if let Some(hashes) = self.side_chain.get_mut(&height) {
+            let _ = hashes.remove(&hash);
+            if hashes.is_empty() {
+                let _ = self.side_chain.remove(&height);
             }
-            HashMapEntry::Occupied(_) => Either::Error(format!("Entry (block header hash) with these hash {:?} & height {:?} already exist", hash, height)),
-        };
-
-        match (&hash_result, &height_result, &hash_height_result) {
-            (Either::Hash(hash), Either::Height(height), Either::HashHeight(_header_hash, _header_height)) => Ok((hash.clone(), height.clone())),
-            _ => {
-                let mut error_result: String = String::from("");
-//              for error in [hash_result, height_result, hash_height_result].iter() {
-//                  match error {
-//                      Either::Error(e) => error_result = format!(" {:?} {:?};", error_result, e),
-//                      _ => error_result = format!(" {:?};", error_result),
-//                  }
-//              };
-                for error in &[hash_result, height_result, hash_height_result] {
-                    if let Either::Error(e) = error {
-                        error_result = format!(" {:?} {:?};", error_result, e)
-                    } else {
-                        error_result = format!(" {:?};", error_result)
-                    }
-                };
-                Err(format!("Error: {:?}", error_result))?
-            },
         }
     }
 
@@ -131,12 +133,63 @@ impl BlockIndex<BlockHeader> {
         }
     }
 
+    /// Returns the best chain block headers from `start` to `end`, inclusive.
+    pub fn get_range(
+        &self,
+        start: BlockHeight,
+        end: BlockHeight,
+    ) -> Result<Vec<(Arc<BlockHeader>, BlockHeight)>, Error> {
+        range::check_range(start, end)?;
+
+        Ok(self
+            .by_height
+            .range(start..=end)
+            .map(|(block_height, block_header)| (block_header.clone(), *block_height))
+            .collect())
+    }
+
     pub fn contains(&self, hash: &BlockHeaderHash) -> Result<bool, Error> {
         let key = &hash;
         Ok(self.by_hash.contains_key(key))
     }
 }
 
+impl ChainView for BlockIndex<BlockHeader> {
+    fn header(&self, hash: BlockHeaderHash) -> Result<Option<Arc<BlockHeader>>, Error> {
+        self.get(hash)
+    }
+
+    fn height(&self, hash: BlockHeaderHash) -> Result<Option<BlockHeight>, Error> {
+        self.get_height(hash)
+    }
+
+    fn work(&self, hash: BlockHeaderHash) -> Result<Option<Work>, Error> {
+        Ok(self.hash_work.get(&hash).cloned())
+    }
+
+    fn best_hash(&self, height: BlockHeight) -> Result<Option<BlockHeaderHash>, Error> {
+        Ok(self
+            .by_height
+            .get(&height)
+            .map(|block_header| block_header.as_ref().into()))
+    }
+
+    fn best_tip(&self) -> Result<Option<(BlockHeaderHash, BlockHeight)>, Error> {
+        Ok(self.get_tip()?.map(|(_header, hash, height)| (hash, height)))
+    }
+
+    fn side_chain_below(
+        &self,
+        height: BlockHeight,
+    ) -> Result<Vec<(BlockHeight, BlockHeaderHash)>, Error> {
+        Ok(self
+            .side_chain
+            .range(..height)
+            .flat_map(|(height, hashes)| hashes.iter().map(move |hash| (*height, *hash)))
+            .collect())
+    }
+}
+
 /*
 pub(super) enum BlockQuery {
     ByHash(BlockHeaderHash),
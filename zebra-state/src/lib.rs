@@ -8,6 +8,10 @@
 //! * BlockHeight -> Block
 //!
 //! Inserting a block into the service will create a mapping in each tree for that block.
+//!
+//! Block states also index the transactions in each block:
+//!
+//! * TransactionHash -> BlockHeaderHash and transaction index
 #![doc(html_logo_url = "https://www.zfnd.org/images/zebra-icon.png")]
 #![doc(html_root_url = "https://doc.zebra.zfnd.org/zebra_state")]
 #![warn(missing_docs)]
@@ -24,19 +28,34 @@ use zebra_chain::{
         BlockHeader,
         BlockHeaderHash,
     },
+    transaction::{Transaction, TransactionHash},
     types::BlockHeight,
+    Network,
 };
 
+mod chain;
+mod events;
+mod format;
+mod locator;
+mod range;
 pub mod on_disk;
 pub mod on_disk_headersonly;
 pub mod in_memory;
 pub mod in_memory_headersonly;
 
+pub use chain::{MAX_REORG_DEPTH, SIDE_CHAIN_PRUNE_DEPTH};
+pub use events::{ChainEvent, ChainEventStream, ChainEvents, MAX_UNREAD_CHAIN_EVENTS};
+pub use format::DATABASE_FORMAT_VERSION;
+pub use locator::{MAX_FIND_BLOCK_HASHES_RESULTS, MAX_FIND_BLOCK_HEADERS_RESULTS};
+pub use range::MAX_BLOCK_HEADER_RANGE_RESULTS;
+
 /// Configuration for the state service.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields, default)]
 pub struct Config {
     /// The root directory for storing cached data into the state storage.
+    ///
+    /// Each network's state is stored in its own subdirectory.
     pub cache_dir: PathBuf,
     /// The maximum number of bytes to use caching data in memory.
     pub memory_cache_bytes: u64,
@@ -47,19 +66,43 @@ pub struct Config {
 }
 
 impl Config {
-    pub(crate) fn sled_config(&self, path_dir: &str) -> sled::Config {
+    /// Returns the directory for the `path_dir` database on `network`.
+    ///
+    /// Each network is stored in its own subdirectory of `cache_dir`, so
+    /// chains from different networks are never mixed.
+    pub(crate) fn db_path(&self, network: Network, path_dir: &str) -> PathBuf {
+        let network_dir = match network {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+        };
+
+        self.cache_dir.join(network_dir).join(path_dir)
+    }
+
+    pub(crate) fn sled_config(&self, network: Network, path_dir: &str) -> sled::Config {
         let config = sled::Config::default()
             .cache_capacity(self.memory_cache_bytes)
             .mode(sled::Mode::LowSpace);
         if self.ephemeral {
             config.temporary(self.ephemeral)
         } else {
-            let path = self.cache_dir.join(path_dir.to_owned());
-            config.path(path)
+            config.path(self.db_path(network, path_dir))
         }
     }
 }
 
+/// Converts a sled transaction error into a state error.
+///
+/// State transactions never abort, so any error is a storage error.
+pub(crate) fn transaction_error(
+    error: sled::TransactionError<()>,
+) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+    match error {
+        sled::TransactionError::Abort(()) => "unexpected state transaction abort".into(),
+        sled::TransactionError::Storage(error) => error.into(),
+    }
+}
+
 impl Default for Config {
     fn default() -> Config {
         Config {
@@ -96,17 +139,48 @@ pub enum RequestBlock {
         /// The hash to check against the current chain
         hash: BlockHeaderHash,
     },
+    /// Get a transaction, and the block that contains it, from the zebra-state
+    GetTransaction {
+        /// The hash used to identify the transaction
+        hash: TransactionHash,
+    },
+    /// Get a block locator for the current best chain
+    GetBlockLocator,
+    /// Get the block headers that follow a block locator in the current best
+    /// chain
+    ///
+    /// Returns up to `MAX_FIND_BLOCK_HEADERS_RESULTS` headers.
+    FindBlockHeaders {
+        /// Hashes of known blocks, ordered from highest height to lowest height
+        known_blocks: Vec<BlockHeaderHash>,
+        /// Optionally, the last block header to return
+        stop: Option<BlockHeaderHash>,
+    },
+    /// Get the hashes of the blocks that follow a block locator in the
+    /// current best chain
+    ///
+    /// Returns up to `MAX_FIND_BLOCK_HASHES_RESULTS` hashes.
+    FindBlockHashes {
+        /// Hashes of known blocks, ordered from highest height to lowest height
+        known_blocks: Vec<BlockHeaderHash>,
+        /// Optionally, the last block hash to return
+        stop: Option<BlockHeaderHash>,
+    },
+    /// Get a handle for subscribing to best chain changes
+    Subscribe,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 /// A state request, used to manipulate the zebra-state on disk or in memory
 pub enum RequestBlockHeader {
     /// Add a block header to the zebra-state
+    ///
+    /// The height of the block header is derived from its parent, which must
+    /// already be in the state. Headers without a parent are only accepted as
+    /// the genesis block, at height 0.
     AddBlockHeader {
-        /// The block header & block height to be added to the state
+        /// The block header to be added to the state
         block_header: Arc<BlockHeader>,
-        /// The block header & block height to be added to the state
-        block_height: BlockHeight,
     },
     /// Get a block header from the zebra-state
     GetBlockHeader {
@@ -125,6 +199,61 @@ pub enum RequestBlockHeader {
         /// The hash to check against the current chain
         hash: BlockHeaderHash,
     },
+    /// Get a block header, its height, and its depth in the current best chain
+    ///
+    /// The block header and its depth are looked up together, so the depth is
+    /// always for the returned block header, even if the best chain changes.
+    GetBlockHeaderWithDepth {
+        /// The hash or height used to identify the block header
+        query: QueryType,
+    },
+    /// Ask the state if the given hash is in the best chain or a side chain
+    Contains {
+        /// The hash to check against the stored block headers
+        hash: BlockHeaderHash,
+    },
+    /// Get a block locator for the current best chain
+    GetBlockLocator,
+    /// Get the block headers that follow a block locator in the current best
+    /// chain
+    ///
+    /// Returns up to `MAX_FIND_BLOCK_HEADERS_RESULTS` headers.
+    FindBlockHeaders {
+        /// Hashes of known blocks, ordered from highest height to lowest height
+        known_blocks: Vec<BlockHeaderHash>,
+        /// Optionally, the last block header to return
+        stop: Option<BlockHeaderHash>,
+    },
+    /// Get the hashes of the block headers that follow a block locator in the
+    /// current best chain
+    ///
+    /// Returns up to `MAX_FIND_BLOCK_HASHES_RESULTS` hashes.
+    FindBlockHashes {
+        /// Hashes of known blocks, ordered from highest height to lowest height
+        known_blocks: Vec<BlockHeaderHash>,
+        /// Optionally, the last block hash to return
+        stop: Option<BlockHeaderHash>,
+    },
+    /// Get the best chain block headers from `start` to `end`, inclusive
+    ///
+    /// Heights above the tip are skipped. Ranges of more than
+    /// `MAX_BLOCK_HEADER_RANGE_RESULTS` heights are rejected.
+    GetBlockHeaderRange {
+        /// The height of the first block header
+        start: BlockHeight,
+        /// The height of the last block header
+        end: BlockHeight,
+    },
+    /// Get a block header for each hash or height query, in query order
+    ///
+    /// Fails if any block header could not be found. Batches of more than
+    /// `MAX_BLOCK_HEADER_RANGE_RESULTS` queries are rejected.
+    GetBlockHeaders(
+        /// The hashes or heights used to identify the block headers
+        Vec<QueryType>,
+    ),
+    /// Get a handle for subscribing to best chain changes
+    Subscribe,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -138,6 +267,21 @@ pub enum Response {
         /// The hash and height of the block that was added
         height: BlockHeight,
     },
+    /// The response to an `AddBlockHeader` request, indicating that the block
+    /// header was added to the state, and that the best chain was switched to
+    /// the side chain that ends with that header
+    Reorganized {
+        /// The hash of the block header that was added, which is the new tip
+        hash: BlockHeaderHash,
+        /// The height of the block header that was added
+        height: BlockHeight,
+        /// The block headers removed from the best chain, in ascending height
+        /// order
+        disconnected: Vec<BlockHeaderHash>,
+        /// The block headers added to the best chain, in ascending height
+        /// order, ending with `hash`
+        connected: Vec<BlockHeaderHash>,
+    },
     /// The response to a `GetBlock` request by hash or height
     Block {
         /// The block that was requested
@@ -168,6 +312,53 @@ pub enum Response {
         /// The number of blocks above the given block in the current best chain
         Option<u32>,
     ),
+    /// The response to a `GetTransaction` request
+    Transaction(
+        /// The transaction, and the hash and height of the block that contains
+        /// it, or `None` if the transaction is not in the state
+        Option<(Arc<Transaction>, BlockHeaderHash, BlockHeight)>,
+    ),
+    /// The response to a `GetBlockHeaderWithDepth` request
+    BlockHeaderWithDepth(
+        /// The block header, its height, and its depth in the current best
+        /// chain, or `None` if the block header is not in the state
+        ///
+        /// The depth is `None` if the block header is on a side chain.
+        Option<(Arc<BlockHeader>, BlockHeight, Option<u32>)>,
+    ),
+    /// The response to a `Contains` request
+    Contains(
+        /// Whether the given hash is in the best chain or a side chain
+        bool,
+    ),
+    /// The response to a `GetBlockLocator` request
+    BlockLocator {
+        /// The best chain hashes, starting at the tip, then getting
+        /// exponentially further apart, and ending with the genesis block.
+        ///
+        /// Empty if the state is empty.
+        block_locator: Vec<BlockHeaderHash>,
+    },
+    /// The response to a `FindBlockHeaders` request
+    BlockHeaders {
+        /// The block headers, in ascending height order
+        block_headers: Vec<Arc<BlockHeader>>,
+    },
+    /// The response to a `FindBlockHashes` request
+    BlockHashes {
+        /// The block hashes, in ascending height order
+        block_hashes: Vec<BlockHeaderHash>,
+    },
+    /// The response to a `GetBlockHeaderRange` or `GetBlockHeaders` request
+    BlockHeadersWithHeights {
+        /// The block headers and their heights, in request order
+        block_headers: Vec<(Arc<BlockHeader>, BlockHeight)>,
+    },
+    /// The response to a `Subscribe` request
+    Subscription {
+        /// The handle for subscribing to the state's chain events
+        chain_events: ChainEvents,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -0,0 +1,99 @@
+//! Block locators, and finding the best chain blocks that follow them.
+//!
+//! Peers send us block locators in `getblocks` and `getheaders` messages. We
+//! answer with the best chain blocks after the first locator hash that is in
+//! our best chain.
+use std::{error, iter};
+
+use zebra_chain::{block::BlockHeaderHash, types::BlockHeight};
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// The maximum number of block headers returned by a `FindBlockHeaders`
+/// request.
+///
+/// This matches the `MAX_HEADERS_RESULTS` limit on `headers` messages in
+/// zcashd.
+pub const MAX_FIND_BLOCK_HEADERS_RESULTS: u32 = 160;
+
+/// The maximum number of block hashes returned by a `FindBlockHashes` request.
+///
+/// zcashd sends at most 500 hashes in response to a `getblocks` message.
+pub const MAX_FIND_BLOCK_HASHES_RESULTS: u32 = 500;
+
+/// Get the heights of the blocks for constructing a block_locator list
+///
+/// The list starts at the tip, then goes back 1, 2, 4, 8, ... blocks, and
+/// ends with the genesis block.
+pub(crate) fn block_locator_heights(tip_height: BlockHeight) -> Vec<BlockHeight> {
+    let below_tip = iter::successors(Some(1u32), |h| h.checked_mul(2))
+        .flat_map(move |step| tip_height.0.checked_sub(step))
+        .filter(|height| *height > 0);
+
+    iter::once(tip_height.0)
+        .chain(below_tip)
+        .chain(iter::once(0).filter(|_| tip_height.0 > 0))
+        .map(BlockHeight)
+        .collect()
+}
+
+/// Returns the best chain blocks that follow the block locator `known_blocks`,
+/// up to `stop` or `max_len` blocks, whichever comes first.
+///
+/// The first hash in `known_blocks` that is in the best chain is the fork
+/// point. If none of them are in the best chain, the fork point is the
+/// genesis block. If `known_blocks` is empty, only the `stop` block is
+/// returned.
+///
+/// `best_height` returns the height of a hash, if it is in the best chain, and
+/// `best_hash` returns the best chain hash at a height.
+pub(crate) fn find_successors(
+    known_blocks: &[BlockHeaderHash],
+    stop: Option<BlockHeaderHash>,
+    max_len: u32,
+    tip_height: Option<BlockHeight>,
+    best_height: impl Fn(BlockHeaderHash) -> Result<Option<BlockHeight>, Error>,
+    best_hash: impl Fn(BlockHeight) -> Result<Option<BlockHeaderHash>, Error>,
+) -> Result<Vec<(BlockHeight, BlockHeaderHash)>, Error> {
+    let tip_height = match tip_height {
+        Some(tip_height) => tip_height,
+        None => return Ok(Vec::new()),
+    };
+
+    if known_blocks.is_empty() {
+        return match stop {
+            Some(stop) => Ok(best_height(stop)?
+                .map(|height| (height, stop))
+                .into_iter()
+                .collect()),
+            None => Ok(Vec::new()),
+        };
+    }
+
+    let mut fork_height = BlockHeight(0);
+    for hash in known_blocks {
+        if let Some(height) = best_height(*hash)? {
+            fork_height = height;
+            break;
+        }
+    }
+
+    let mut successors = Vec::new();
+    let mut height = fork_height.0 + 1;
+    while height <= tip_height.0 && successors.len() < max_len as usize {
+        let hash = best_hash(BlockHeight(height))?.ok_or_else(|| {
+            format!(
+                "best chain block at {:?} could not be found",
+                BlockHeight(height)
+            )
+        })?;
+        successors.push((BlockHeight(height), hash));
+
+        if Some(hash) == stop {
+            break;
+        }
+        height += 1;
+    }
+
+    Ok(successors)
+}
@@ -1,8 +1,14 @@
 //! The primary implementation of the `zebra_state::Service` built upon sled
 use super::{RequestBlock, Response, QueryType};
-use crate::Config;
+use crate::{
+    events::{block_events, ChainEvents},
+    format::{self, Migration},
+    locator::{self, MAX_FIND_BLOCK_HASHES_RESULTS, MAX_FIND_BLOCK_HEADERS_RESULTS},
+    Config,
+};
 // use std::path::{Path, PathBuf};
 use futures::prelude::*;
+use sled::{ConflictableTransactionResult, Transactional};
 use std::sync::Arc;
 use std::{
     error,
@@ -13,23 +19,48 @@ use std::{
 use tower::{buffer::Buffer, Service};
 use zebra_chain::serialization::{ZcashDeserialize, ZcashSerialize};
 use zebra_chain::{
-    block::{Block, BlockHeaderHash},
+    block::{Block, BlockHeader, BlockHeaderHash},
+    transaction::{Transaction, TransactionHash},
     types::BlockHeight,
+    Network,
 };
 
+/// Upgrades for block databases with older formats.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "repair partially written blocks, which is now done on every open",
+        migrate: |_| Ok(()),
+    },
+    Migration {
+        version: 2,
+        description: "add the transaction index",
+        migrate: add_transaction_index,
+    },
+];
+
 type Error = Box<dyn error::Error + Send + Sync + 'static>;
 
 #[derive(Clone)]
 struct SledState {
     storage: sled::Db,
+    events: ChainEvents,
 }
 
 impl SledState {
-    pub(crate) fn new(config: &Config) -> Self {
-        let config = config.sled_config("blocks");
-        Self {
-            storage: config.open().unwrap(),
-        }
+    pub(crate) fn new(config: &Config, network: Network) -> Self {
+        let storage = format::open(config, "blocks", network, MIGRATIONS)
+            .unwrap_or_else(|e| panic!("the block state could not be opened: {}", e));
+
+        let state = Self {
+            storage,
+            events: ChainEvents::default(),
+        };
+        state
+            .check_consistency()
+            .unwrap_or_else(|e| panic!("the block state could not be repaired: {}", e));
+
+        state
     }
 
     pub(super) fn insert(
@@ -42,13 +73,40 @@ impl SledState {
 
         let by_height = self.storage.open_tree(b"by_height")?;
         let by_hash = self.storage.open_tree(b"by_hash")?;
+        let tx_by_hash = self.storage.open_tree(b"tx_by_hash")?;
 
         let mut bytes = Vec::new();
         block.zcash_serialize(&mut bytes)?;
+        let transactions = transaction_locations(&block, hash);
 
-        // TODO(jlusby): make this transactional
-        by_height.insert(&height.0.to_be_bytes(), bytes.as_slice())?;
-        by_hash.insert(&hash.0, bytes)?;
+        // A new block replaces any block at the same height, so the replaced
+        // block's transactions are no longer in the best chain.
+        let replaced_block = self.get(height)?;
+        let replaced = replaced_block.as_ref().map(|block| block.as_ref().into());
+        let replaced_transactions: Vec<TransactionHash> = replaced_block
+            .iter()
+            .flat_map(|block| block.transactions.iter())
+            .map(|transaction| transaction.as_ref().into())
+            .collect();
+        let tip_height = self.get_tip()?.and_then(|tip| tip.coinbase_height());
+
+        (&by_height, &by_hash, &tx_by_hash)
+            .transaction(
+                |(by_height, by_hash, tx_by_hash)| -> ConflictableTransactionResult<()> {
+                    by_height.insert(&height.0.to_be_bytes()[..], bytes.as_slice())?;
+                    by_hash.insert(&hash.0[..], bytes.as_slice())?;
+                    for transaction_hash in &replaced_transactions {
+                        tx_by_hash.remove(&transaction_hash.0[..])?;
+                    }
+                    for (transaction_hash, location) in &transactions {
+                        tx_by_hash.insert(&transaction_hash.0[..], &location[..])?;
+                    }
+                    Ok(())
+                },
+            )
+            .map_err(crate::transaction_error)?;
+
+        self.events.publish(block_events(hash, height, replaced, tip_height));
 
         Ok((hash, height))
     }
@@ -77,6 +135,33 @@ impl SledState {
         }
     }
 
+    /// Returns the transaction with `hash`, and the hash and height of the
+    /// block that contains it.
+    fn get_transaction(
+        &self,
+        hash: TransactionHash,
+    ) -> Result<Option<(Arc<Transaction>, BlockHeaderHash, BlockHeight)>, Error> {
+        let tx_by_hash = self.storage.open_tree(b"tx_by_hash")?;
+        let (block_hash, index) = match tx_by_hash.get(&hash.0)? {
+            Some(location) => location_from_bytes(&location)?,
+            None => return Ok(None),
+        };
+
+        let block = self
+            .get(block_hash)?
+            .ok_or("indexed transaction is missing its block")?;
+        let transaction = block
+            .transactions
+            .get(index as usize)
+            .ok_or("indexed transaction is missing from its block")?
+            .clone();
+        let height = block
+            .coinbase_height()
+            .ok_or("indexed block is missing its coinbase height")?;
+
+        Ok(Some((transaction, block_hash, height)))
+    }
+
     pub(super) fn get_tip(&self) -> Result<Option<Arc<Block>>, Error> {
         let tree = self.storage.open_tree(b"by_height")?;
         let last_entry = tree.iter().values().next_back();
@@ -94,12 +179,140 @@ impl SledState {
 
         Ok(by_hash.contains_key(key)?)
     }
+
+    /// Returns a block locator for the best chain, or an empty list if the
+    /// state is empty.
+    ///
+    /// Heights without a stored block are skipped.
+    fn block_locator(&self) -> Result<Vec<BlockHeaderHash>, Error> {
+        let tip_height = match self.get_tip()? {
+            Some(tip) => tip
+                .coinbase_height()
+                .ok_or("tip block is missing its coinbase height")?,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut block_locator = Vec::new();
+        for height in locator::block_locator_heights(tip_height) {
+            if let Some(block) = self.get(height)? {
+                block_locator.push(block.as_ref().into());
+            }
+        }
+
+        Ok(block_locator)
+    }
+
+    /// Returns the best chain blocks that follow `known_blocks`, up to `stop`
+    /// or `max_len` blocks.
+    fn find_successors(
+        &self,
+        known_blocks: &[BlockHeaderHash],
+        stop: Option<BlockHeaderHash>,
+        max_len: u32,
+    ) -> Result<Vec<Arc<Block>>, Error> {
+        let tip_height = self.get_tip()?.and_then(|tip| tip.coinbase_height());
+        let best_hash = |height: BlockHeight| -> Result<Option<BlockHeaderHash>, Error> {
+            Ok(self
+                .get(height)?
+                .map(|block| block.as_ref().into()))
+        };
+        let best_height = |hash: BlockHeaderHash| -> Result<Option<BlockHeight>, Error> {
+            let height = self.get(hash)?.and_then(|block| block.coinbase_height());
+            match height {
+                Some(height) if best_hash(height)? == Some(hash) => Ok(Some(height)),
+                _ => Ok(None),
+            }
+        };
+
+        locator::find_successors(known_blocks, stop, max_len, tip_height, best_height, best_hash)?
+            .into_iter()
+            .map(|(height, _hash)| {
+                self.get(height)?.ok_or_else(|| {
+                    format!("best chain block at {:?} could not be found", height).into()
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()
+    }
+
+    /// Check that the trees are consistent with each other, and repair any
+    /// partially written blocks.
+    ///
+    /// Runs every time the state is opened. Unversioned databases were
+    /// written before inserts were transactional, so they can contain blocks
+    /// that were only written to `by_height`. Those entries contain the whole
+    /// block, so the missing `by_hash` entries are restored from them.
+    /// Transaction index entries for blocks that aren't in `by_hash` are
+    /// removed.
+    fn check_consistency(&self) -> Result<(), Error> {
+        let by_height = self.storage.open_tree(b"by_height")?;
+        let by_hash = self.storage.open_tree(b"by_hash")?;
+        let tx_by_hash = self.storage.open_tree(b"tx_by_hash")?;
+
+        let mut missing = Vec::new();
+        for bytes in by_height.iter().values() {
+            let bytes = bytes?;
+            // Blocks start with their header, so the rest of the block
+            // doesn't need to be deserialized
+            let header: BlockHeader = ZcashDeserialize::zcash_deserialize(bytes.as_ref())?;
+            let hash: BlockHeaderHash = (&header).into();
+
+            if !by_hash.contains_key(&hash.0)? {
+                missing.push((hash, bytes));
+            }
+        }
+
+        let mut dangling = Vec::new();
+        for entry in tx_by_hash.iter() {
+            let (key, location) = entry?;
+            let in_by_hash = match location_from_bytes(&location) {
+                Ok((hash, _)) => {
+                    by_hash.contains_key(&hash.0)?
+                        || missing
+                            .iter()
+                            .any(|(missing_hash, _)| *missing_hash == hash)
+                }
+                Err(_) => false,
+            };
+
+            if !in_by_hash {
+                dangling.push(key);
+            }
+        }
+
+        if missing.is_empty() && dangling.is_empty() {
+            return Ok(());
+        }
+
+        tracing::warn!(
+            missing_blocks = missing.len(),
+            dangling_transactions = dangling.len(),
+            "repairing partially written blocks in the state"
+        );
+
+        (&by_hash, &tx_by_hash)
+            .transaction(
+                |(by_hash, tx_by_hash)| -> ConflictableTransactionResult<()> {
+                    for (hash, bytes) in &missing {
+                        by_hash.insert(&hash.0[..], bytes.clone())?;
+                    }
+                    for key in &dangling {
+                        tx_by_hash.remove(key)?;
+                    }
+
+                    Ok(())
+                },
+            )
+            .map_err(crate::transaction_error)?;
+        self.storage.flush()?;
+
+        Ok(())
+    }
 }
 
 impl Default for SledState {
     fn default() -> Self {
         let config = crate::Config::default();
-        Self::new(&config)
+        Self::new(&config, Network::default())
     }
 }
 
@@ -179,8 +392,114 @@ impl Service<RequestBlock> for SledState {
                 }
                 .boxed()
             }
+            RequestBlock::GetTransaction { hash } => {
+                let storage = self.clone();
+
+                async move { storage.get_transaction(hash).map(Response::Transaction) }.boxed()
+            }
+            RequestBlock::GetBlockLocator => {
+                let storage = self.clone();
+
+                async move {
+                    storage
+                        .block_locator()
+                        .map(|block_locator| Response::BlockLocator { block_locator })
+                }
+                .boxed()
+            }
+            RequestBlock::FindBlockHeaders { known_blocks, stop } => {
+                let storage = self.clone();
+
+                async move {
+                    let blocks = storage.find_successors(
+                        &known_blocks,
+                        stop,
+                        MAX_FIND_BLOCK_HEADERS_RESULTS,
+                    )?;
+                    let block_headers = blocks.iter().map(|block| block.header.into()).collect();
+
+                    Ok(Response::BlockHeaders { block_headers })
+                }
+                .boxed()
+            }
+            RequestBlock::FindBlockHashes { known_blocks, stop } => {
+                let storage = self.clone();
+
+                async move {
+                    let blocks = storage.find_successors(
+                        &known_blocks,
+                        stop,
+                        MAX_FIND_BLOCK_HASHES_RESULTS,
+                    )?;
+                    let block_hashes = blocks.iter().map(|block| block.as_ref().into()).collect();
+
+                    Ok(Response::BlockHashes { block_hashes })
+                }
+                .boxed()
+            }
+            RequestBlock::Subscribe => {
+                let chain_events = self.events.clone();
+
+                async move { Ok(Response::Subscription { chain_events }) }.boxed()
+            }
+        }
+    }
+}
+
+/// Returns the `tx_by_hash` entries for the transactions in `block`, which
+/// has `hash`.
+///
+/// Each entry maps a transaction hash to the block hash, followed by the
+/// transaction's index in the block, as a big-endian `u32`.
+fn transaction_locations(
+    block: &Block,
+    hash: BlockHeaderHash,
+) -> Vec<(TransactionHash, [u8; 36])> {
+    block
+        .transactions
+        .iter()
+        .enumerate()
+        .map(|(index, transaction)| {
+            let mut location = [0; 36];
+            location[..32].copy_from_slice(&hash.0);
+            location[32..].copy_from_slice(&(index as u32).to_be_bytes());
+            (transaction.as_ref().into(), location)
+        })
+        .collect()
+}
+
+/// Returns the block hash and transaction index in a `tx_by_hash` entry.
+fn location_from_bytes(bytes: &[u8]) -> Result<(BlockHeaderHash, u32), Error> {
+    if bytes.len() != 36 {
+        Err(format!(
+            "the transaction index has an entry with an invalid length: {}",
+            bytes.len()
+        ))?;
+    }
+
+    let mut hash = [0; 32];
+    hash.copy_from_slice(&bytes[..32]);
+    let mut index = [0; 4];
+    index.copy_from_slice(&bytes[32..]);
+
+    Ok((BlockHeaderHash(hash), u32::from_be_bytes(index)))
+}
+
+/// Index the transactions in the best chain blocks of a version 1 database.
+fn add_transaction_index(db: &sled::Db) -> Result<(), Error> {
+    let by_height = db.open_tree(b"by_height")?;
+    let tx_by_hash = db.open_tree(b"tx_by_hash")?;
+
+    for bytes in by_height.iter().values() {
+        let block: Block = ZcashDeserialize::zcash_deserialize(bytes?.as_ref())?;
+        let hash: BlockHeaderHash = (&block).into();
+
+        for (transaction_hash, location) in transaction_locations(&block, hash) {
+            tx_by_hash.insert(&transaction_hash.0[..], &location[..])?;
         }
     }
+
+    Ok(())
 }
 
 /// An alternate repr for `BlockHeight` that implements `AsRef<[u8]>` for usage
@@ -220,8 +539,12 @@ impl From<BlockHeight> for BlockQuery {
 */
 
 /// Return's a type that implement's the `zebra_state::Service` using `sled`
+///
+/// Panics if the database can't be opened or repaired, or it is for a
+/// different `network`.
 pub fn init(
     config: Config,
+    network: Network,
 ) -> impl Service<
     RequestBlock,
     Response = Response,
@@ -231,5 +554,5 @@ pub fn init(
   + Send
   + Clone
   + 'static {
-    Buffer::new(SledState::new(&config), 1)
+    Buffer::new(SledState::new(&config, network), 1)
 }
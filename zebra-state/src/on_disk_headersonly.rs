@@ -3,8 +3,10 @@ use super::{RequestBlockHeader, Response, QueryType};
 use crate::Config;
 // use std::path::{Path, PathBuf};
 use futures::prelude::*;
+use sled::{ConflictableTransactionResult, Transactional};
 use std::sync::Arc;
 use std::{
+    collections::HashSet,
     error,
     future::Future,
     pin::Pin,
@@ -13,48 +15,126 @@ use std::{
 use tower::{buffer::Buffer, Service};
 use zebra_chain::serialization::{ZcashDeserialize, ZcashSerialize};
 use zebra_chain::{
-    block::{BlockHeader, BlockHeaderHash},
+    block::{BlockHeader, BlockHeaderHash, Work},
     types::BlockHeight,
+    Network,
 };
 
+use crate::chain::{self, ChainUpdate, ChainView};
+use crate::events::ChainEvents;
+use crate::format::{self, Migration};
+use crate::range;
+
+/// Upgrades for block header databases with older formats.
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "add the cumulative work index",
+    migrate: add_work_index,
+}];
+
 type Error = Box<dyn error::Error + Send + Sync + 'static>;
 
 #[derive(Clone)]
 struct SledState {
     storage: sled::Db,
+    events: ChainEvents,
 }
 
 impl SledState {
-    pub(crate) fn new(config: &Config) -> Self {
-        let config = config.sled_config("headers");
-        Self {
-            storage: config.open().unwrap(),
-        }
+    pub(crate) fn new(config: &Config, network: Network) -> Self {
+        let storage = format::open(config, "headers", network, MIGRATIONS)
+            .unwrap_or_else(|e| panic!("the block header state could not be opened: {}", e));
+
+        let state = Self {
+            storage,
+            events: ChainEvents::default(),
+        };
+        state
+            .check_consistency()
+            .unwrap_or_else(|e| panic!("the block header state could not be repaired: {}", e));
+
+        state
     }
 
+    /// Insert `block_header`, updating the best chain if its chain has the
+    /// most work.
+    ///
+    /// Returns the state response for the insert.
+    ///
+    /// The insert is planned using reads outside the sled transaction, so
+    /// inserts must not run concurrently.
     pub(super) fn insert(
         &mut self,
         block_header: impl Into<Arc<BlockHeader>>,
-        block_height: BlockHeight,
-    ) -> Result<(BlockHeaderHash, BlockHeight), Error> {
-        let block_header = block_header.into();
-        let hash: BlockHeaderHash = block_header.as_ref().into();
-//      let height = block.coinbase_height().unwrap(); // didn't applicable for block height handling
-        let height = block_height;
+    ) -> Result<Response, Error> {
+        let insertion = chain::plan_insert(self, block_header.into())?;
+        let response = insertion.response();
 
         let by_hash = self.storage.open_tree(b"by_hash")?;
         let by_height = self.storage.open_tree(b"by_height")?;
         let hash_height = self.storage.open_tree(b"hash_height")?;
+        let hash_work = self.storage.open_tree(b"hash_work")?;
+        let side_chain = self.storage.open_tree(b"side_chain")?;
+
+        let hash = insertion.hash;
+        let height = insertion.height;
+        let bytes = header_bytes(&insertion.block_header)?;
+
+        // Serialize connected headers before the transaction, because
+        // serialization errors can't be returned from the transaction closure
+        let connected = match &insertion.update {
+            ChainUpdate::Reorganize { connected, .. } => connected
+                .iter()
+                .map(|(height, hash, block_header)| {
+                    Ok((*height, *hash, header_bytes(block_header)?))
+                })
+                .collect::<Result<Vec<_>, Error>>()?,
+            _ => Vec::new(),
+        };
+
+        // Sled transactions are atomic across trees, so a crash can't leave
+        // partially written indexes
+        (&by_hash, &by_height, &hash_height, &hash_work, &side_chain)
+            .transaction(
+                |(by_hash, by_height, hash_height, hash_work, side_chain)| -> ConflictableTransactionResult<()> {
+                    by_hash.insert(&hash.0[..], bytes.as_slice())?;
+                    hash_height.insert(&hash.0[..], &height.0.to_be_bytes()[..])?;
+                    hash_work.insert(&hash.0[..], &insertion.work.to_be_bytes()[..])?;
+
+                    match &insertion.update {
+                        ChainUpdate::Extend => {
+                            by_height.insert(&height.0.to_be_bytes()[..], bytes.as_slice())?;
+                        }
+                        ChainUpdate::SideChain => {
+                            side_chain.insert(side_chain_key(height, hash), Vec::new())?;
+                        }
+                        ChainUpdate::Reorganize { disconnected, .. } => {
+                            for (height, hash) in disconnected {
+                                by_height.remove(&height.0.to_be_bytes()[..])?;
+                                side_chain.insert(side_chain_key(*height, *hash), Vec::new())?;
+                            }
+                            for (height, hash, bytes) in &connected {
+                                side_chain.remove(side_chain_key(*height, *hash))?;
+                                by_height.insert(&height.0.to_be_bytes()[..], bytes.as_slice())?;
+                            }
+                        }
+                    }
+
+                    for (height, hash) in &insertion.pruned {
+                        side_chain.remove(side_chain_key(*height, *hash))?;
+                        by_hash.remove(&hash.0[..])?;
+                        hash_height.remove(&hash.0[..])?;
+                        hash_work.remove(&hash.0[..])?;
+                    }
 
-        let mut bytes = Vec::new();
-        block_header.zcash_serialize(&mut bytes)?;
+                    Ok(())
+                },
+            )
+            .map_err(crate::transaction_error)?;
 
-        // TODO(jlusby): make this transactional
-        by_hash.insert(&hash.0, bytes.as_slice())?;
-        by_height.insert(&height.0.to_be_bytes(), bytes.as_slice())?;
-        hash_height.insert(&hash.0, &height.0.to_be_bytes())?;
+        self.events.publish(insertion.chain_events());
 
-        Ok((hash, height))
+        Ok(response)
     }
 
     pub(super) fn get(&self, query: impl Into<QueryType>) -> Result<Option<Arc<BlockHeader>>, Error> {
@@ -142,18 +222,303 @@ impl SledState {
         }
     }
 
+    /// Returns the best chain block headers from `start` to `end`, inclusive.
+    fn get_range(
+        &self,
+        start: BlockHeight,
+        end: BlockHeight,
+    ) -> Result<Vec<(Arc<BlockHeader>, BlockHeight)>, Error> {
+        range::check_range(start, end)?;
+
+        let by_height = self.storage.open_tree(b"by_height")?;
+        by_height
+            .range(start.0.to_be_bytes()..=end.0.to_be_bytes())
+            .map(|entry| {
+                let (key, bytes) = entry?;
+                let block_header: BlockHeader = ZcashDeserialize::zcash_deserialize(bytes.as_ref())?;
+                Ok((Arc::new(block_header), height_from_key(&key)))
+            })
+            .collect()
+    }
+
     fn contains(&self, hash: &BlockHeaderHash) -> Result<bool, Error> {
         let by_hash = self.storage.open_tree(b"by_hash")?;
         let key = &hash.0;
 
         Ok(by_hash.contains_key(key)?)
     }
+
+    /// Check that the trees are consistent with each other, and remove any
+    /// partially written block headers.
+    ///
+    /// Runs every time the state is opened. Unversioned databases were
+    /// written before inserts were transactional, so they can contain block
+    /// headers that were only written to some of the trees. Versioned
+    /// databases can be damaged by disk errors or other programs. Repairs
+    /// keep:
+    ///   * headers that are in `by_hash`, `hash_height` and `hash_work`,
+    ///   * the best chain up to the first missing or unlinked header, and
+    ///   * side chain headers that connect to the remaining headers.
+    ///
+    /// All other headers and index entries are removed. The syncer downloads
+    /// the removed headers again.
+    fn check_consistency(&self) -> Result<(), Error> {
+        let by_hash = self.storage.open_tree(b"by_hash")?;
+        let by_height = self.storage.open_tree(b"by_height")?;
+        let hash_height = self.storage.open_tree(b"hash_height")?;
+        let hash_work = self.storage.open_tree(b"hash_work")?;
+        let side_chain = self.storage.open_tree(b"side_chain")?;
+
+        // Headers must be in every hash-keyed tree
+        let mut incomplete = HashSet::new();
+        let hash_trees = [&by_hash, &hash_height, &hash_work];
+        for tree in hash_trees.iter() {
+            for key in tree.iter().keys() {
+                let key = key?;
+                for other in hash_trees.iter() {
+                    if !other.contains_key(&key)? {
+                        incomplete.insert(hash_from_key(&key));
+                        break;
+                    }
+                }
+            }
+        }
+
+        // The best chain must be a linked chain of complete headers, starting
+        // at genesis
+        let mut best_chain = HashSet::new();
+        let mut truncated = Vec::new();
+        let mut previous_hash = None;
+        for entry in by_height.iter() {
+            let (key, bytes) = entry?;
+            let height = height_from_key(&key);
+
+            if truncated.is_empty() {
+                // Headers that don't deserialize are treated like missing
+                // headers
+                let block_header: BlockHeader =
+                    match ZcashDeserialize::zcash_deserialize(bytes.as_ref()) {
+                        Ok(block_header) => block_header,
+                        Err(_) => {
+                            truncated.push(key);
+                            continue;
+                        }
+                    };
+                let hash: BlockHeaderHash = (&block_header).into();
+                let linked = match previous_hash {
+                    Some(previous_hash) => block_header.previous_block_hash == previous_hash,
+                    None => height == BlockHeight(0),
+                };
+
+                if linked
+                    && height.0 == best_chain.len() as u32
+                    && !incomplete.contains(&hash)
+                    && self.get_height(hash)? == Some(height)
+                {
+                    best_chain.insert(hash);
+                    previous_hash = Some(hash);
+                    continue;
+                }
+            }
+
+            truncated.push(key);
+        }
+
+        // Side chain headers must connect to the best chain, directly or
+        // through other side chain headers. Keys are in height order, so
+        // parents are checked before their children.
+        let mut side_chain_hashes = HashSet::new();
+        let mut side_chain_removed = Vec::new();
+        for key in side_chain.iter().keys() {
+            let key = key?;
+            let (height, hash) = side_chain_entry(&key);
+
+            let connected = match self.get(hash) {
+                Ok(Some(block_header)) if !incomplete.contains(&hash) => {
+                    let parent_hash = block_header.previous_block_hash;
+                    !best_chain.contains(&hash)
+                        && self.get_height(hash)? == Some(height)
+                        && (best_chain.contains(&parent_hash)
+                            || side_chain_hashes.contains(&parent_hash))
+                }
+                _ => false,
+            };
+
+            if connected {
+                side_chain_hashes.insert(hash);
+            } else {
+                side_chain_removed.push(key);
+            }
+        }
+
+        // Every other header is unreachable, including headers that were
+        // truncated from the best chain
+        let mut removed = incomplete;
+        for key in by_hash.iter().keys() {
+            let hash = hash_from_key(&key?);
+            if !best_chain.contains(&hash) && !side_chain_hashes.contains(&hash) {
+                removed.insert(hash);
+            }
+        }
+
+        if removed.is_empty() && truncated.is_empty() && side_chain_removed.is_empty() {
+            return Ok(());
+        }
+
+        tracing::warn!(
+            removed_headers = removed.len(),
+            truncated_best_chain = truncated.len(),
+            removed_side_chain = side_chain_removed.len(),
+            "repairing partially written block headers in the state"
+        );
+
+        (&by_hash, &by_height, &hash_height, &hash_work, &side_chain)
+            .transaction(
+                |(by_hash, by_height, hash_height, hash_work, side_chain)| -> ConflictableTransactionResult<()> {
+                    for hash in &removed {
+                        by_hash.remove(&hash.0[..])?;
+                        hash_height.remove(&hash.0[..])?;
+                        hash_work.remove(&hash.0[..])?;
+                    }
+                    for key in &truncated {
+                        by_height.remove(key)?;
+                    }
+                    for key in &side_chain_removed {
+                        side_chain.remove(key)?;
+                    }
+
+                    Ok(())
+                },
+            )
+            .map_err(crate::transaction_error)?;
+        self.storage.flush()?;
+
+        Ok(())
+    }
+}
+
+impl ChainView for SledState {
+    fn header(&self, hash: BlockHeaderHash) -> Result<Option<Arc<BlockHeader>>, Error> {
+        self.get(hash)
+    }
+
+    fn height(&self, hash: BlockHeaderHash) -> Result<Option<BlockHeight>, Error> {
+        self.get_height(hash)
+    }
+
+    fn work(&self, hash: BlockHeaderHash) -> Result<Option<Work>, Error> {
+        let hash_work = self.storage.open_tree(b"hash_work")?;
+
+        match hash_work.get(&hash.0)? {
+            Some(vec) => {
+                let mut bytes = [0u8; 16];
+                bytes.clone_from_slice(&vec);
+                Ok(Some(Work::from_be_bytes(bytes)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn best_hash(&self, height: BlockHeight) -> Result<Option<BlockHeaderHash>, Error> {
+        Ok(self
+            .get(height)?
+            .map(|block_header| block_header.as_ref().into()))
+    }
+
+    fn best_tip(&self) -> Result<Option<(BlockHeaderHash, BlockHeight)>, Error> {
+        let by_height = self.storage.open_tree(b"by_height")?;
+        if by_height.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(self.get_tip()?.map(|(_header, hash, height)| (hash, height)))
+    }
+
+    fn side_chain_below(
+        &self,
+        height: BlockHeight,
+    ) -> Result<Vec<(BlockHeight, BlockHeaderHash)>, Error> {
+        let side_chain = self.storage.open_tree(b"side_chain")?;
+
+        side_chain
+            .range(..height.0.to_be_bytes())
+            .keys()
+            .map(|key| Ok(side_chain_entry(&key?)))
+            .collect()
+    }
+}
+
+/// Returns the serialized bytes of `block_header`.
+fn header_bytes(block_header: &BlockHeader) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    block_header.zcash_serialize(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Returns the `side_chain` tree key for the block header `hash` at `height`.
+///
+/// Keys start with the big-endian height, so side chain headers can be pruned
+/// using a range scan.
+fn side_chain_key(height: BlockHeight, hash: BlockHeaderHash) -> Vec<u8> {
+    let mut key = height.0.to_be_bytes().to_vec();
+    key.extend_from_slice(&hash.0);
+    key
+}
+
+/// Upgrades an unversioned block header database by adding the `hash_work`
+/// tree.
+///
+/// Unversioned databases only stored the best chain, so the cumulative work
+/// of each best chain header is summed from genesis.
+fn add_work_index(db: &sled::Db) -> Result<(), Error> {
+    let by_height = db.open_tree(b"by_height")?;
+    let hash_work = db.open_tree(b"hash_work")?;
+
+    let mut work: Option<Work> = None;
+    for bytes in by_height.iter().values() {
+        let block_header: BlockHeader = ZcashDeserialize::zcash_deserialize(bytes?.as_ref())?;
+        let hash: BlockHeaderHash = (&block_header).into();
+        let header_work = block_header
+            .bits
+            .to_work()
+            .ok_or("block header difficulty threshold is invalid")?;
+
+        let cumulative_work = match work {
+            Some(work) => work
+                .checked_add(header_work)
+                .ok_or("cumulative chain work overflowed")?,
+            None => header_work,
+        };
+        hash_work.insert(&hash.0, &cumulative_work.to_be_bytes())?;
+        work = Some(cumulative_work);
+    }
+
+    Ok(())
+}
+
+/// Returns the height and hash in a `side_chain` tree key.
+fn side_chain_entry(key: &[u8]) -> (BlockHeight, BlockHeaderHash) {
+    (height_from_key(&key[..4]), hash_from_key(&key[4..]))
+}
+
+/// Returns the block header hash in a hash-keyed tree key.
+fn hash_from_key(key: &[u8]) -> BlockHeaderHash {
+    let mut bytes = [0u8; 32];
+    bytes.clone_from_slice(key);
+    BlockHeaderHash(bytes)
+}
+
+/// Returns the height in a `by_height` tree key.
+fn height_from_key(key: &[u8]) -> BlockHeight {
+    let mut bytes = [0u8; 4];
+    bytes.clone_from_slice(key);
+    BlockHeight(u32::from_be_bytes(bytes))
 }
 
 impl Default for SledState {
     fn default() -> Self {
         let config = crate::Config::default();
-        Self::new(&config)
+        Self::new(&config, Network::default())
     }
 }
 
@@ -169,24 +534,25 @@ impl Service<RequestBlockHeader> for SledState {
 
     fn call(&mut self, req: RequestBlockHeader) -> Self::Future {
         match req {
-            RequestBlockHeader::AddBlockHeader { block_header, block_height } => {
-                let mut storage = self.clone();
-                async move {
-                    storage
-                        .insert(block_header, block_height)
-                        .map(|(hash, height)| Response::Added { hash, height })
-                }
-                .boxed()
+            RequestBlockHeader::AddBlockHeader { block_header } => {
+                // Inserts plan against the current tip, so they run in `call`,
+                // rather than the returned future. The state is only called
+                // by its `Buffer` worker, so inserts never run concurrently.
+                let result = self.insert(block_header);
+
+                async move { result }.boxed()
             }
             RequestBlockHeader::GetBlockHeader { query } => {
                 let storage = self.clone();
                 async move {
-                    let block_header = storage.get(query)?;
-                    let hash: BlockHeaderHash = block_header.clone().unwrap().as_ref().into();
-                    let height = storage.get_height(hash)?.unwrap();
-                    block_header
-                        .map(|block_header| Response::BlockHeader { block_header: block_header, block_height: height })
-                        .ok_or_else(|| "GetBlockHeader - block header could not be found".into())
+                    let block_header = storage
+                        .get(query)?
+                        .ok_or("GetBlockHeader - block header could not be found")?;
+                    let hash: BlockHeaderHash = block_header.as_ref().into();
+                    let block_height = storage
+                        .get_height(hash)?
+                        .ok_or("GetBlockHeader - block height could not be found")?;
+                    Ok(Response::BlockHeader { block_header, block_height })
                 }
                 .boxed()
             }
@@ -213,26 +579,82 @@ impl Service<RequestBlockHeader> for SledState {
             RequestBlockHeader::GetDepth { hash } => {
                 let storage = self.clone();
 
+                // Side chain block headers aren't part of the best chain, so
+                // they don't have a depth
+                async move { storage.best_depth(hash).map(Response::Depth) }.boxed()
+            }
+            RequestBlockHeader::GetBlockHeaderWithDepth { query } => {
+                // Like inserts, this lookup runs in `call`, so the best chain
+                // can't change between the block header and depth lookups
+                let result = self
+                    .header_with_depth(query)
+                    .map(Response::BlockHeaderWithDepth);
+
+                async move { result }.boxed()
+            }
+            RequestBlockHeader::Contains { hash } => {
+                let storage = self.clone();
+
+                async move { storage.contains(&hash).map(Response::Contains) }.boxed()
+            }
+            RequestBlockHeader::GetBlockLocator => {
+                let storage = self.clone();
+
                 async move {
-                    if !storage.contains(&hash)? {
-                        return Ok(Response::Depth(None));
-                    }
+                    storage
+                        .block_locator()
+                        .map(|block_locator| Response::BlockLocator { block_locator })
+                }
+                .boxed()
+            }
+            RequestBlockHeader::FindBlockHeaders { known_blocks, stop } => {
+                let storage = self.clone();
 
-                    let block_header_height = storage
-                        .get_height(hash)?
-                        .expect("GetDepth - block header must be present if contains() returned true");
+                async move {
+                    storage
+                        .find_block_headers(&known_blocks, stop)
+                        .map(|block_headers| Response::BlockHeaders { block_headers })
+                }
+                .boxed()
+            }
+            RequestBlockHeader::FindBlockHashes { known_blocks, stop } => {
+                let storage = self.clone();
 
-                    let tip = storage
-                        .get_tip()?
-                        .map(|(_header, _hash, height)| height)
-                        .expect("GetDepth - storage must have a tip if it contains() the previous block header");
+                async move {
+                    storage
+                        .find_block_hashes(&known_blocks, stop)
+                        .map(|block_hashes| Response::BlockHashes { block_hashes })
+                }
+                .boxed()
+            }
+            RequestBlockHeader::GetBlockHeaderRange { start, end } => {
+                let storage = self.clone();
 
-                    let depth = tip.0 - block_header_height.0;
+                async move {
+                    storage
+                        .get_range(start, end)
+                        .map(|block_headers| Response::BlockHeadersWithHeights { block_headers })
+                }
+                .boxed()
+            }
+            RequestBlockHeader::GetBlockHeaders(queries) => {
+                let storage = self.clone();
 
-                    Ok(Response::Depth(Some(depth)))
+                async move {
+                    range::get_batch(
+                        queries,
+                        |query| storage.get(query),
+                        |hash| storage.get_height(hash),
+                    )
+                    .map(|block_headers| Response::BlockHeadersWithHeights { block_headers })
                 }
                 .boxed()
             }
+            RequestBlockHeader::Subscribe => {
+                let chain_events = self.events.clone();
+
+                async move { Ok(Response::Subscription { chain_events }) }.boxed()
+            }
          /* RequestBlockHeader::GetDepth { hash: _ } => {
                 async move { Ok(Response::Depth(None)) }.boxed()
             } */
@@ -336,8 +758,12 @@ impl From<BlockHeight> for BlockQuery {
 */
 
 /// Return's a type that implement's the `zebra_state::Service` using `sled`
+///
+/// Panics if the database can't be opened or repaired, or it is for a
+/// different `network`.
 pub fn init(
     config: Config,
+    network: Network,
 ) -> impl Service<
     RequestBlockHeader,
     Response = Response,
@@ -347,5 +773,28 @@ pub fn init(
   + Send
   + Clone
   + 'static {
-    Buffer::new(SledState::new(&config), 1)
+    Buffer::new(SledState::new(&config, network), 1)
+}
+
+/// Like `init`, but returns the state's worker task instead of spawning it.
+///
+/// The worker owns the database. Dropping the worker closes the database,
+/// even if there are still handles to the state, so applications can reopen
+/// the same `cache_dir` after they stop it.
+pub fn init_with_worker(
+    config: Config,
+    network: Network,
+) -> (
+    impl Service<
+            RequestBlockHeader,
+            Response = Response,
+            Error = Error,
+            Future = impl Future<Output = Result<Response, Error>>,
+        > + Sync
+        + Send
+        + Clone
+        + 'static,
+    impl Future<Output = ()> + Send + 'static,
+) {
+    Buffer::pair(SledState::new(&config, network), 1)
 }
@@ -0,0 +1,74 @@
+//! Block header range and batch queries.
+//!
+//! Clients like IBC relayers fetch many consecutive block headers at a time.
+//! Range queries scan the best chain height index, rather than looking up
+//! each height separately.
+use std::{error, sync::Arc};
+
+use zebra_chain::{
+    block::{BlockHeader, BlockHeaderHash},
+    types::BlockHeight,
+};
+
+use crate::QueryType;
+
+type Error = Box<dyn error::Error + Send + Sync + 'static>;
+
+/// The maximum number of block headers returned by a `GetBlockHeaderRange`
+/// or `GetBlockHeaders` request.
+///
+/// Larger requests are rejected, so a single request can't make the state
+/// read and serialize an unbounded number of headers.
+pub const MAX_BLOCK_HEADER_RANGE_RESULTS: u32 = 2000;
+
+/// Check that the inclusive height range `start..=end` is valid, and contains
+/// at most `MAX_BLOCK_HEADER_RANGE_RESULTS` heights.
+pub(crate) fn check_range(start: BlockHeight, end: BlockHeight) -> Result<(), Error> {
+    if end < start {
+        Err(format!(
+            "invalid block header range: end {:?} is below start {:?}",
+            end, start
+        ))?;
+    }
+
+    if end.0 - start.0 >= MAX_BLOCK_HEADER_RANGE_RESULTS {
+        Err(format!(
+            "block header range {:?}..={:?} is longer than the limit of {} headers",
+            start, end, MAX_BLOCK_HEADER_RANGE_RESULTS
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the block header and height for each query in `queries`, in query
+/// order.
+///
+/// `get` returns the block header for a hash or best chain height, and
+/// `get_height` returns the height of a hash.
+pub(crate) fn get_batch(
+    queries: Vec<QueryType>,
+    get: impl Fn(QueryType) -> Result<Option<Arc<BlockHeader>>, Error>,
+    get_height: impl Fn(BlockHeaderHash) -> Result<Option<BlockHeight>, Error>,
+) -> Result<Vec<(Arc<BlockHeader>, BlockHeight)>, Error> {
+    if queries.len() > MAX_BLOCK_HEADER_RANGE_RESULTS as usize {
+        Err(format!(
+            "{} block header queries is more than the limit of {} headers",
+            queries.len(),
+            MAX_BLOCK_HEADER_RANGE_RESULTS
+        ))?;
+    }
+
+    queries
+        .into_iter()
+        .map(|query| {
+            let block_header = get(query.clone())?
+                .ok_or_else(|| format!("block header {:?} could not be found", query))?;
+            let hash: BlockHeaderHash = block_header.as_ref().into();
+            let block_height = get_height(hash)?
+                .ok_or_else(|| format!("block height {:?} could not be found", hash))?;
+
+            Ok((block_header, block_height))
+        })
+        .collect()
+}
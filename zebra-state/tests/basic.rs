@@ -1,8 +1,10 @@
 use color_eyre::eyre::Report;
+use futures::stream::StreamExt;
 use once_cell::sync::Lazy;
 use std::sync::Arc;
 use tempdir::TempDir;
-use zebra_chain::{block::Block, serialization::ZcashDeserialize};
+use tower::{Service, ServiceExt};
+use zebra_chain::{block::Block, serialization::ZcashDeserialize, types::BlockHeight, Network};
 use zebra_test::transcript::Transcript;
 
 use zebra_state::*;
@@ -49,24 +51,218 @@ static GET_TIP_TRANSCRIPT: Lazy<Vec<(RequestBlock, Response)>> = Lazy::new(|| {
             Response::Added { hash: hash0, height: height0 },
         ),
         (RequestBlock::GetTip, Response::Tip { hash: hash1, height: height1 }),
+        (
+            RequestBlock::GetBlockLocator,
+            Response::BlockLocator { block_locator: vec![hash1, hash0] },
+        ),
+    ]
+});
+
+static GET_TRANSACTION_TRANSCRIPT: Lazy<Vec<(RequestBlock, Response)>> = Lazy::new(|| {
+    let block: Arc<_> =
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])
+            .unwrap()
+            .into();
+    let hash = block.as_ref().into();
+    let height = block.coinbase_height().unwrap();
+    let transaction = block.transactions.last().unwrap().clone();
+    let transaction_hash = transaction.as_ref().into();
+    vec![
+        (
+            RequestBlock::GetTransaction { hash: transaction_hash },
+            Response::Transaction(None),
+        ),
+        (
+            RequestBlock::AddBlock {
+                block: block.clone(),
+            },
+            Response::Added { hash: hash, height: height },
+        ),
+        (
+            RequestBlock::GetTransaction { hash: transaction_hash },
+            Response::Transaction(Some((transaction, hash, height))),
+        ),
     ]
 });
 
+static REORG_TRANSACTION_TRANSCRIPT: Lazy<Vec<(RequestBlock, Response)>> = Lazy::new(|| {
+    let block1 = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..]).unwrap();
+    let block415000 =
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..]).unwrap();
+    let coinbase = block1.transactions[0].clone();
+    let coinbase_hash = coinbase.as_ref().into();
+    let transaction = block415000.transactions.last().unwrap().clone();
+    let transaction_hash = transaction.as_ref().into();
+
+    // The original block contains an extra transaction, but its replacement
+    // at the same height does not
+    let mut original = block1.clone();
+    original.transactions.push(transaction.clone());
+    let original: Arc<_> = original.into();
+    let mut replacement = block1;
+    replacement.header.nonce[0] ^= 0xff;
+    let replacement: Arc<_> = replacement.into();
+
+    let original_hash = original.as_ref().into();
+    let replacement_hash = replacement.as_ref().into();
+    let height = replacement.coinbase_height().unwrap();
+    vec![
+        (
+            RequestBlock::AddBlock { block: original.clone() },
+            Response::Added { hash: original_hash, height },
+        ),
+        (
+            RequestBlock::GetTransaction { hash: transaction_hash },
+            Response::Transaction(Some((transaction.clone(), original_hash, height))),
+        ),
+        (
+            RequestBlock::AddBlock { block: replacement.clone() },
+            Response::Added { hash: replacement_hash, height },
+        ),
+        (
+            RequestBlock::GetTransaction { hash: transaction_hash },
+            Response::Transaction(None),
+        ),
+        (
+            RequestBlock::GetTransaction { hash: coinbase_hash },
+            Response::Transaction(Some((coinbase, replacement_hash, height))),
+        ),
+        (
+            RequestBlock::GetBlock { query: QueryType::ByHeight(height) },
+            Response::Block { block: replacement },
+        ),
+    ]
+});
+
+static ADD_BLOCK_HEADER_TRANSCRIPT: Lazy<Vec<(RequestBlockHeader, Response)>> = Lazy::new(|| {
+    let block0 =
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..]).unwrap();
+    let block1 = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..]).unwrap();
+    let header0: Arc<_> = block0.header.into();
+    let header1: Arc<_> = block1.header.into();
+    let hash0 = header0.as_ref().into();
+    let hash1 = header1.as_ref().into();
+    // The heights are derived from the parent headers, not supplied by the caller
+    let height0 = block0.coinbase_height().unwrap();
+    let height1 = block1.coinbase_height().unwrap();
+    vec![
+        (
+            RequestBlockHeader::AddBlockHeader { block_header: header0.clone() },
+            Response::Added { hash: hash0, height: height0 },
+        ),
+        (
+            RequestBlockHeader::AddBlockHeader { block_header: header1.clone() },
+            Response::Added { hash: hash1, height: height1 },
+        ),
+        (
+            RequestBlockHeader::GetBlockHeader { query: QueryType::ByHeight(height1) },
+            Response::BlockHeader { block_header: header1, block_height: height1 },
+        ),
+        (RequestBlockHeader::GetTip, Response::Tip { hash: hash1, height: height1 }),
+        (
+            RequestBlockHeader::GetBlockLocator,
+            Response::BlockLocator { block_locator: vec![hash1, hash0] },
+        ),
+    ]
+});
+
+#[tokio::test]
+async fn check_header_transcripts() -> Result<(), Report> {
+    zebra_test::init();
+
+    let service = in_memory_headersonly::init();
+    let transcript = Transcript::from(ADD_BLOCK_HEADER_TRANSCRIPT.iter().cloned());
+    transcript.check(service).await?;
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    let service = on_disk_headersonly::init(
+        Config {
+            cache_dir: storage_guard.path().to_owned(),
+            memory_cache_bytes: 1024 * 1024 * 1024,
+            ephemeral: false,
+        },
+        Network::Mainnet,
+    );
+    let transcript = Transcript::from(ADD_BLOCK_HEADER_TRANSCRIPT.iter().cloned());
+    transcript.check(service).await?;
+
+    Ok(())
+}
+
+/// Check that `service` rejects block 1's header, because its parent is
+/// missing.
+async fn check_orphan_header_rejected<S>(mut service: S) -> Result<(), Report>
+where
+    S: Service<RequestBlockHeader, Response = Response>,
+    S::Error: std::fmt::Debug,
+{
+    let block1 = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])?;
+    let header1: Arc<_> = block1.header.into();
+    let hash1 = header1.as_ref().into();
+
+    service
+        .ready_and()
+        .await
+        .unwrap()
+        .call(RequestBlockHeader::AddBlockHeader { block_header: header1 })
+        .await
+        .expect_err("headers without a parent should be rejected");
+
+    let response = service
+        .ready_and()
+        .await
+        .unwrap()
+        .call(RequestBlockHeader::GetDepth { hash: hash1 })
+        .await
+        .unwrap();
+    assert_eq!(response, Response::Depth(None));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reject_orphan_headers() -> Result<(), Report> {
+    zebra_test::init();
+
+    check_orphan_header_rejected(in_memory_headersonly::init()).await?;
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    let service = on_disk_headersonly::init(
+        Config {
+            cache_dir: storage_guard.path().to_owned(),
+            memory_cache_bytes: 1024 * 1024 * 1024,
+            ephemeral: false,
+        },
+        Network::Mainnet,
+    );
+    check_orphan_header_rejected(service).await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn check_transcripts() -> Result<(), Report> {
     zebra_test::init();
 
-    for transcript_data in &[&ADD_BLOCK_TRANSCRIPT, &GET_TIP_TRANSCRIPT] {
+    for transcript_data in &[
+        &ADD_BLOCK_TRANSCRIPT,
+        &GET_TIP_TRANSCRIPT,
+        &GET_TRANSACTION_TRANSCRIPT,
+        &REORG_TRANSACTION_TRANSCRIPT,
+    ] {
         let service = in_memory::init();
         let transcript = Transcript::from(transcript_data.iter().cloned());
         transcript.check(service).await?;
 
         let storage_guard = TempDir::new("./.tmp-state")?;
-        let service = on_disk::init(Config {
-            cache_dir: storage_guard.path().to_owned(),
-            memory_cache_bytes: 1024 * 1024 * 1024,
-            ephemeral: false,
-        });
+        let service = on_disk::init(
+            Config {
+                cache_dir: storage_guard.path().to_owned(),
+                memory_cache_bytes: 1024 * 1024 * 1024,
+                ephemeral: false,
+            },
+            Network::Mainnet,
+        );
         let transcript = Transcript::from(transcript_data.iter().cloned());
         transcript.check(service).await?;
         // Delete the contents of the temp directory before going to the next case.
@@ -75,3 +271,80 @@ async fn check_transcripts() -> Result<(), Report> {
 
     Ok(())
 }
+
+/// Check that `service` publishes the blocks it adds to subscribers.
+async fn check_block_chain_events<S>(mut service: S) -> Result<(), Report>
+where
+    S: Service<RequestBlock, Response = Response>,
+    S::Error: std::fmt::Debug,
+{
+    let block0: Arc<_> =
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?.into();
+    let block1: Arc<_> =
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])?.into();
+    let hash0 = block0.as_ref().into();
+    let hash1 = block1.as_ref().into();
+
+    let events = match service
+        .ready_and()
+        .await
+        .unwrap()
+        .call(RequestBlock::Subscribe)
+        .await
+        .unwrap()
+    {
+        Response::Subscription { chain_events } => chain_events.subscribe(),
+        r => panic!("unexpected response {:?}", r),
+    };
+
+    // Blocks below the tip are connected, but don't change the tip
+    for block in vec![block1, block0] {
+        service
+            .ready_and()
+            .await
+            .unwrap()
+            .call(RequestBlock::AddBlock { block })
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(
+        events.take(3).collect::<Vec<_>>().await,
+        vec![
+            ChainEvent::Connected {
+                hash: hash1,
+                height: BlockHeight(1),
+            },
+            ChainEvent::NewTip {
+                hash: hash1,
+                height: BlockHeight(1),
+            },
+            ChainEvent::Connected {
+                hash: hash0,
+                height: BlockHeight(0),
+            },
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn block_chain_events() -> Result<(), Report> {
+    zebra_test::init();
+
+    check_block_chain_events(in_memory::init()).await?;
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    check_block_chain_events(on_disk::init(
+        Config {
+            cache_dir: storage_guard.path().to_owned(),
+            memory_cache_bytes: 1024 * 1024 * 1024,
+            ephemeral: false,
+        },
+        Network::Mainnet,
+    ))
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,606 @@
+//! Check that the on-disk states upgrade older database formats, refuse
+//! incompatible databases, and repair partially written inserts.
+//!
+//! Inserts are transactional, so usually only unversioned databases contain
+//! partial inserts. These tests simulate an interrupted insert by removing
+//! entries from some of the trees, and the database format version, then
+//! reopening the state. The states check their trees every time they are
+//! opened, so they also repair damaged versioned databases.
+use color_eyre::eyre::{eyre, Report};
+use std::{future::Future, path::Path, sync::Arc};
+use tempdir::TempDir;
+use tokio::runtime;
+use tower::{Service, ServiceExt};
+use zebra_chain::{
+    block::{Block, BlockHeader, BlockHeaderHash},
+    serialization::{ZcashDeserialize, ZcashSerialize},
+    transaction::TransactionHash,
+    types::BlockHeight,
+    Network,
+};
+
+use zebra_state::*;
+
+/// Returns the mainnet genesis block header, followed by `len` block headers
+/// that extend it.
+fn headers(len: u32) -> Result<Vec<Arc<BlockHeader>>, Report> {
+    let genesis = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?;
+    let mut previous_block_hash: BlockHeaderHash = (&genesis.header).into();
+    let mut headers = vec![Arc::new(genesis.header)];
+
+    for i in 0..len {
+        let mut header = genesis.header;
+        header.previous_block_hash = previous_block_hash;
+        header.nonce[0..4].copy_from_slice(&i.to_be_bytes());
+
+        previous_block_hash = (&header).into();
+        headers.push(Arc::new(header));
+    }
+
+    Ok(headers)
+}
+
+fn config(storage_guard: &TempDir) -> Config {
+    Config {
+        cache_dir: storage_guard.path().to_owned(),
+        memory_cache_bytes: 1024 * 1024 * 1024,
+        ephemeral: false,
+    }
+}
+
+/// Run `future` on a new runtime.
+///
+/// The runtime is dropped before returning, which also drops the state
+/// service, and closes its database.
+fn run<F>(future: F) -> Result<(), Report>
+where
+    F: Future<Output = Result<(), Report>>,
+{
+    let mut runtime = runtime::Builder::new()
+        .basic_scheduler()
+        .enable_all()
+        .build()?;
+    let result = runtime.block_on(future);
+    drop(runtime);
+
+    result
+}
+
+/// Remove `keys` from the `tree` in the database at `path`, simulating an
+/// insert that was interrupted before it wrote them.
+fn remove_keys(path: &Path, tree: &str, keys: &[&[u8]]) -> Result<(), Report> {
+    let db = sled::Config::default().path(path).open()?;
+    let tree = db.open_tree(tree)?;
+
+    for key in keys {
+        tree.remove(*key)?;
+    }
+    db.flush()?;
+
+    Ok(())
+}
+
+/// Remove the format version from the database at `path`, so it is upgraded
+/// like an unversioned database when it is reopened.
+fn remove_version(path: &Path) -> Result<(), Report> {
+    remove_keys(path, "metadata", &[b"version"])
+}
+
+/// Insert `entries` into the `tree` in the database at `path`.
+fn insert_entries(path: &Path, tree: &str, entries: &[(&[u8], &[u8])]) -> Result<(), Report> {
+    let db = sled::Config::default().path(path).open()?;
+    let tree = db.open_tree(tree)?;
+
+    for (key, value) in entries {
+        tree.insert(*key, *value)?;
+    }
+    db.flush()?;
+
+    Ok(())
+}
+
+/// Returns the value of `key` in the `tree` in the database at `path`.
+fn get_value(path: &Path, tree: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Report> {
+    let db = sled::Config::default().path(path).open()?;
+    let value = db.open_tree(tree)?.get(key)?;
+
+    Ok(value.map(|value| value.to_vec()))
+}
+
+async fn call<S, R>(service: &mut S, request: R) -> Result<Response, Report>
+where
+    S: Service<R, Response = Response>,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    service
+        .ready_and()
+        .await
+        .map_err(|e| eyre!(e.into()))?
+        .call(request)
+        .await
+        .map_err(|e| eyre!(e.into()))
+}
+
+async fn add_headers(config: Config, headers: Vec<Arc<BlockHeader>>) -> Result<(), Report> {
+    let mut state = on_disk_headersonly::init(config, Network::Mainnet);
+
+    for block_header in headers {
+        call(
+            &mut state,
+            RequestBlockHeader::AddBlockHeader { block_header },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn interrupted_header_insert() -> Result<(), Report> {
+    zebra_test::init();
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    let config = config(&storage_guard);
+    let path = config.cache_dir.join("mainnet").join("headers");
+    let headers = headers(3)?;
+    let hash2: BlockHeaderHash = headers[2].as_ref().into();
+    let hash3: BlockHeaderHash = headers[3].as_ref().into();
+
+    run(add_headers(config.clone(), headers.clone()))?;
+
+    // The last header was written to `by_hash` and `hash_height`, but not to
+    // `hash_work` or `by_height`
+    remove_keys(&path, "hash_work", &[&hash3.0[..]])?;
+    remove_keys(&path, "by_height", &[&3u32.to_be_bytes()[..]])?;
+    remove_version(&path)?;
+
+    run(async move {
+        let mut state = on_disk_headersonly::init(config, Network::Mainnet);
+
+        assert_eq!(
+            call(&mut state, RequestBlockHeader::GetTip).await?,
+            Response::Tip {
+                hash: hash2,
+                height: BlockHeight(2),
+            }
+        );
+        assert_eq!(
+            call(&mut state, RequestBlockHeader::Contains { hash: hash3 }).await?,
+            Response::Contains(false)
+        );
+
+        // The header can be added again
+        assert_eq!(
+            call(
+                &mut state,
+                RequestBlockHeader::AddBlockHeader {
+                    block_header: headers[3].clone(),
+                }
+            )
+            .await?,
+            Response::Added {
+                hash: hash3,
+                height: BlockHeight(3),
+            }
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn dangling_best_chain_header() -> Result<(), Report> {
+    zebra_test::init();
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    let config = config(&storage_guard);
+    let path = config.cache_dir.join("mainnet").join("headers");
+    let headers = headers(3)?;
+    let hash1: BlockHeaderHash = headers[1].as_ref().into();
+    let hash2: BlockHeaderHash = headers[2].as_ref().into();
+    let hash3: BlockHeaderHash = headers[3].as_ref().into();
+
+    run(add_headers(config.clone(), headers.clone()))?;
+
+    // A header in the middle of the best chain was only written to
+    // `by_height`, so its hash lookups fail
+    remove_keys(&path, "by_hash", &[&hash2.0[..]])?;
+    remove_keys(&path, "hash_height", &[&hash2.0[..]])?;
+    remove_version(&path)?;
+
+    run(async move {
+        let mut state = on_disk_headersonly::init(config, Network::Mainnet);
+
+        // The best chain is truncated below the missing header
+        assert_eq!(
+            call(&mut state, RequestBlockHeader::GetTip).await?,
+            Response::Tip {
+                hash: hash1,
+                height: BlockHeight(1),
+            }
+        );
+        for hash in &[hash2, hash3] {
+            assert_eq!(
+                call(&mut state, RequestBlockHeader::Contains { hash: *hash }).await?,
+                Response::Contains(false)
+            );
+        }
+        assert_eq!(
+            call(&mut state, RequestBlockHeader::GetBlockLocator).await?,
+            Response::BlockLocator {
+                block_locator: vec![hash1, headers[0].as_ref().into()],
+            }
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn interrupted_block_insert() -> Result<(), Report> {
+    zebra_test::init();
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    let config = config(&storage_guard);
+    let path = config.cache_dir.join("mainnet").join("blocks");
+    let block0: Arc<Block> =
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?.into();
+    let block1: Arc<Block> =
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])?.into();
+    let hash1: BlockHeaderHash = block1.as_ref().into();
+
+    let add_config = config.clone();
+    let blocks = vec![block0, block1.clone()];
+    run(async move {
+        let mut state = on_disk::init(add_config, Network::Mainnet);
+        for block in blocks {
+            call(&mut state, RequestBlock::AddBlock { block }).await?;
+        }
+
+        Ok(())
+    })?;
+
+    // The last block was written to `by_height`, but not to `by_hash`
+    remove_keys(&path, "by_hash", &[&hash1.0[..]])?;
+    remove_version(&path)?;
+
+    run(async move {
+        let mut state = on_disk::init(config, Network::Mainnet);
+
+        assert_eq!(
+            call(
+                &mut state,
+                RequestBlock::GetBlock {
+                    query: hash1.into(),
+                }
+            )
+            .await?,
+            Response::Block { block: block1 }
+        );
+        assert_eq!(
+            call(&mut state, RequestBlock::GetDepth { hash: hash1 }).await?,
+            Response::Depth(Some(0))
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn corrupted_versioned_header_database() -> Result<(), Report> {
+    zebra_test::init();
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    let config = config(&storage_guard);
+    let path = config.cache_dir.join("mainnet").join("headers");
+    let headers = headers(3)?;
+    let hash1: BlockHeaderHash = headers[1].as_ref().into();
+    let hash2: BlockHeaderHash = headers[2].as_ref().into();
+    let hash3: BlockHeaderHash = headers[3].as_ref().into();
+
+    run(add_headers(config.clone(), headers.clone()))?;
+
+    // The database keeps its format version, but a best chain header is
+    // garbage, and another header is missing its cumulative work
+    insert_entries(
+        &path,
+        "by_height",
+        &[(&2u32.to_be_bytes()[..], &[0xff; 8][..])],
+    )?;
+    remove_keys(&path, "hash_work", &[&hash3.0[..]])?;
+
+    run(async move {
+        let mut state = on_disk_headersonly::init(config, Network::Mainnet);
+
+        assert_eq!(
+            call(&mut state, RequestBlockHeader::GetTip).await?,
+            Response::Tip {
+                hash: hash1,
+                height: BlockHeight(1),
+            }
+        );
+        for hash in &[hash2, hash3] {
+            assert_eq!(
+                call(&mut state, RequestBlockHeader::Contains { hash: *hash }).await?,
+                Response::Contains(false)
+            );
+        }
+
+        // The removed headers can be added again
+        for block_header in headers[2..].iter().cloned() {
+            call(
+                &mut state,
+                RequestBlockHeader::AddBlockHeader { block_header },
+            )
+            .await?;
+        }
+        assert_eq!(
+            call(&mut state, RequestBlockHeader::GetTip).await?,
+            Response::Tip {
+                hash: hash3,
+                height: BlockHeight(3),
+            }
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn corrupted_versioned_block_database() -> Result<(), Report> {
+    zebra_test::init();
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    let config = config(&storage_guard);
+    let path = config.cache_dir.join("mainnet").join("blocks");
+    let block0: Arc<Block> =
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?.into();
+    let block1: Arc<Block> =
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])?.into();
+    let hash1: BlockHeaderHash = block1.as_ref().into();
+    let missing_transaction = TransactionHash([0xaa; 32]);
+
+    let add_config = config.clone();
+    let blocks = vec![block0, block1.clone()];
+    run(async move {
+        let mut state = on_disk::init(add_config, Network::Mainnet);
+        for block in blocks {
+            call(&mut state, RequestBlock::AddBlock { block }).await?;
+        }
+
+        Ok(())
+    })?;
+
+    // The database keeps its format version, but a block is missing from
+    // `by_hash`, and the transaction index refers to a block that isn't in
+    // the state
+    remove_keys(&path, "by_hash", &[&hash1.0[..]])?;
+    let mut location = vec![0xbb; 32];
+    location.extend_from_slice(&0u32.to_be_bytes());
+    insert_entries(
+        &path,
+        "tx_by_hash",
+        &[(&missing_transaction.0[..], &location[..])],
+    )?;
+
+    run(async move {
+        let mut state = on_disk::init(config, Network::Mainnet);
+
+        assert_eq!(
+            call(
+                &mut state,
+                RequestBlock::GetBlock {
+                    query: hash1.into(),
+                }
+            )
+            .await?,
+            Response::Block { block: block1 }
+        );
+        assert_eq!(
+            call(
+                &mut state,
+                RequestBlock::GetTransaction {
+                    hash: missing_transaction,
+                }
+            )
+            .await?,
+            Response::Transaction(None)
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn unversioned_header_database_upgrade() -> Result<(), Report> {
+    zebra_test::init();
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    let config = config(&storage_guard);
+    let legacy_path = config.cache_dir.join("headers");
+    let path = config.cache_dir.join("mainnet").join("headers");
+    let headers = headers(3)?;
+    let hash2: BlockHeaderHash = headers[2].as_ref().into();
+    let hash3: BlockHeaderHash = headers[3].as_ref().into();
+
+    // Unversioned databases were stored directly in the cache directory, and
+    // only have the `by_hash`, `by_height` and
+    // `hash_height` trees
+    for (height, header) in headers[..3].iter().enumerate() {
+        let hash: BlockHeaderHash = header.as_ref().into();
+        let height = (height as u32).to_be_bytes();
+        let mut bytes = Vec::new();
+        header.zcash_serialize(&mut bytes)?;
+
+        insert_entries(&legacy_path, "by_hash", &[(&hash.0[..], &bytes[..])])?;
+        insert_entries(&legacy_path, "by_height", &[(&height[..], &bytes[..])])?;
+        insert_entries(&legacy_path, "hash_height", &[(&hash.0[..], &height[..])])?;
+    }
+
+    let add_config = config.clone();
+    run(async move {
+        let mut state = on_disk_headersonly::init(add_config, Network::Mainnet);
+
+        assert_eq!(
+            call(&mut state, RequestBlockHeader::GetTip).await?,
+            Response::Tip {
+                hash: hash2,
+                height: BlockHeight(2),
+            }
+        );
+
+        // Adding a header needs the cumulative work of its parent
+        assert_eq!(
+            call(
+                &mut state,
+                RequestBlockHeader::AddBlockHeader {
+                    block_header: headers[3].clone(),
+                }
+            )
+            .await?,
+            Response::Added {
+                hash: hash3,
+                height: BlockHeight(3),
+            }
+        );
+
+        Ok(())
+    })?;
+
+    assert!(!legacy_path.exists());
+    assert_eq!(
+        get_value(&path, "metadata", b"version")?,
+        Some(DATABASE_FORMAT_VERSION.to_be_bytes().to_vec())
+    );
+    assert_eq!(
+        get_value(&path, "metadata", b"network")?,
+        Some(b"Mainnet".to_vec())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn networks_are_separate() -> Result<(), Report> {
+    zebra_test::init();
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    let config = config(&storage_guard);
+
+    run(add_headers(config.clone(), headers(1)?))?;
+
+    run(async move {
+        let mut state = on_disk_headersonly::init(config, Network::Testnet);
+
+        assert_eq!(
+            call(&mut state, RequestBlockHeader::GetBlockLocator).await?,
+            Response::BlockLocator {
+                block_locator: Vec::new(),
+            }
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(expected = "configured for Mainnet")]
+fn wrong_network_rejected() {
+    zebra_test::init();
+
+    let storage_guard = TempDir::new("./.tmp-state").unwrap();
+    let config = config(&storage_guard);
+    let path = config.cache_dir.join("mainnet").join("headers");
+
+    // A testnet database was copied into the mainnet directory
+    insert_entries(&path, "metadata", &[(&b"network"[..], &b"Testnet"[..])]).unwrap();
+
+    let _ = run(async move {
+        let _state = on_disk_headersonly::init(config, Network::Mainnet);
+        Ok(())
+    });
+}
+
+#[test]
+#[should_panic(expected = "only supports versions up to")]
+fn newer_format_version_rejected() {
+    zebra_test::init();
+
+    let storage_guard = TempDir::new("./.tmp-state").unwrap();
+    let config = config(&storage_guard);
+    let path = config.cache_dir.join("mainnet").join("blocks");
+    let newer_version = (DATABASE_FORMAT_VERSION + 1).to_be_bytes();
+
+    insert_entries(&path, "metadata", &[(&b"version"[..], &newer_version[..])]).unwrap();
+
+    let _ = run(async move {
+        let _state = on_disk::init(config, Network::Mainnet);
+        Ok(())
+    });
+}
+
+#[test]
+fn transaction_index_upgrade() -> Result<(), Report> {
+    zebra_test::init();
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    let config = config(&storage_guard);
+    let path = config.cache_dir.join("mainnet").join("blocks");
+    let block: Arc<Block> =
+        Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_415000_BYTES[..])?.into();
+    let hash: BlockHeaderHash = block.as_ref().into();
+    let height = block.coinbase_height().expect("test blocks have heights");
+    let transaction = block
+        .transactions
+        .last()
+        .expect("blocks have transactions")
+        .clone();
+    let transaction_hash: TransactionHash = transaction.as_ref().into();
+
+    let add_config = config.clone();
+    let add_block = block.clone();
+    run(async move {
+        let mut state = on_disk::init(add_config, Network::Mainnet);
+        call(&mut state, RequestBlock::AddBlock { block: add_block }).await?;
+
+        Ok(())
+    })?;
+
+    // Version 1 block databases don't have a transaction index
+    let hashes: Vec<TransactionHash> = block
+        .transactions
+        .iter()
+        .map(|transaction| transaction.as_ref().into())
+        .collect();
+    let keys: Vec<&[u8]> = hashes.iter().map(|hash| &hash.0[..]).collect();
+    remove_keys(&path, "tx_by_hash", &keys)?;
+    insert_entries(
+        &path,
+        "metadata",
+        &[(&b"version"[..], &1u32.to_be_bytes()[..])],
+    )?;
+
+    run(async move {
+        let mut state = on_disk::init(config, Network::Mainnet);
+
+        assert_eq!(
+            call(
+                &mut state,
+                RequestBlock::GetTransaction {
+                    hash: transaction_hash,
+                }
+            )
+            .await?,
+            Response::Transaction(Some((transaction, hash, height)))
+        );
+
+        Ok(())
+    })?;
+
+    assert_eq!(
+        get_value(&path, "metadata", b"version")?,
+        Some(DATABASE_FORMAT_VERSION.to_be_bytes().to_vec())
+    );
+
+    Ok(())
+}
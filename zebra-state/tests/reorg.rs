@@ -0,0 +1,702 @@
+use color_eyre::eyre::{eyre, Report};
+use futures::stream::StreamExt;
+use std::sync::Arc;
+use tempdir::TempDir;
+use tower::{Service, ServiceExt};
+use zebra_chain::{
+    block::{Block, BlockHeader, BlockHeaderHash, CompactDifficulty},
+    serialization::ZcashDeserialize,
+    types::BlockHeight,
+    Network,
+};
+
+use zebra_state::*;
+
+/// A harder difficulty threshold than the genesis block, so headers with these
+/// bits have more work than any short chain of genesis difficulty headers.
+const HARD_BITS: CompactDifficulty = CompactDifficulty(0x1e07_ffff);
+
+/// Returns the mainnet genesis block header.
+fn genesis() -> Result<Arc<BlockHeader>, Report> {
+    let block = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?;
+    Ok(block.header.into())
+}
+
+/// Returns `len` block headers that extend `parent`.
+///
+/// The state doesn't check proof of work, so these headers are made by
+/// changing the genesis header's fields. `tag` is used to give each chain
+/// different hashes.
+fn chain(
+    parent: &BlockHeader,
+    len: u32,
+    bits: CompactDifficulty,
+    tag: u8,
+) -> Vec<Arc<BlockHeader>> {
+    let mut previous_block_hash: BlockHeaderHash = parent.into();
+    let mut headers = Vec::new();
+
+    for i in 0..len {
+        let mut header = *parent;
+        header.previous_block_hash = previous_block_hash;
+        header.bits = bits;
+        header.nonce[0] = tag;
+        header.nonce[1..5].copy_from_slice(&i.to_be_bytes());
+
+        previous_block_hash = (&header).into();
+        headers.push(Arc::new(header));
+    }
+
+    headers
+}
+
+fn hashes(headers: &[Arc<BlockHeader>]) -> Vec<BlockHeaderHash> {
+    headers
+        .iter()
+        .map(|header| header.as_ref().into())
+        .collect()
+}
+
+async fn call<S>(service: &mut S, request: RequestBlockHeader) -> Result<Response, Report>
+where
+    S: Service<RequestBlockHeader, Response = Response>,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    service
+        .ready_and()
+        .await
+        .map_err(|e| eyre!(e.into()))?
+        .call(request)
+        .await
+        .map_err(|e| eyre!(e.into()))
+}
+
+async fn add_all<S>(service: &mut S, headers: &[Arc<BlockHeader>]) -> Result<(), Report>
+where
+    S: Service<RequestBlockHeader, Response = Response>,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    for block_header in headers.iter().cloned() {
+        call(service, RequestBlockHeader::AddBlockHeader { block_header }).await?;
+    }
+
+    Ok(())
+}
+
+async fn check_tip<S>(service: &mut S, header: &BlockHeader, height: u32) -> Result<(), Report>
+where
+    S: Service<RequestBlockHeader, Response = Response>,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    assert_eq!(
+        call(service, RequestBlockHeader::GetTip).await?,
+        Response::Tip {
+            hash: header.into(),
+            height: BlockHeight(height),
+        }
+    );
+
+    Ok(())
+}
+
+/// Check that `service` switches to a side chain once it has more work than
+/// the best chain.
+async fn check_reorganize<S>(mut service: S) -> Result<(), Report>
+where
+    S: Service<RequestBlockHeader, Response = Response>,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    let genesis = genesis()?;
+    let best = chain(&genesis, 4, genesis.bits, 1);
+    let side = chain(&genesis, 4, genesis.bits, 2);
+
+    add_all(&mut service, &[genesis.clone()]).await?;
+    add_all(&mut service, &best[..3]).await?;
+
+    // A side chain with equal work doesn't change the best chain
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::AddBlockHeader {
+                block_header: side[0].clone()
+            }
+        )
+        .await?,
+        Response::Added {
+            hash: side[0].as_ref().into(),
+            height: BlockHeight(1),
+        }
+    );
+    add_all(&mut service, &side[1..3]).await?;
+    check_tip(&mut service, &best[2], 3).await?;
+
+    let side_hash = side[1].as_ref().into();
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::GetDepth { hash: side_hash }
+        )
+        .await?,
+        Response::Depth(None)
+    );
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::Contains { hash: side_hash }
+        )
+        .await?,
+        Response::Contains(true)
+    );
+
+    // The side chain has more work, so it becomes the best chain
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::AddBlockHeader {
+                block_header: side[3].clone()
+            }
+        )
+        .await?,
+        Response::Reorganized {
+            hash: side[3].as_ref().into(),
+            height: BlockHeight(4),
+            disconnected: hashes(&best[..3]),
+            connected: hashes(&side),
+        }
+    );
+    check_tip(&mut service, &side[3], 4).await?;
+
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::GetBlockHeader {
+                query: BlockHeight(2).into()
+            }
+        )
+        .await?,
+        Response::BlockHeader {
+            block_header: side[1].clone(),
+            block_height: BlockHeight(2),
+        }
+    );
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::GetDepth { hash: side_hash }
+        )
+        .await?,
+        Response::Depth(Some(2))
+    );
+
+    let old_best_hash = best[1].as_ref().into();
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::GetDepth {
+                hash: old_best_hash
+            }
+        )
+        .await?,
+        Response::Depth(None)
+    );
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::Contains {
+                hash: old_best_hash
+            }
+        )
+        .await?,
+        Response::Contains(true)
+    );
+
+    // Block locators follow the new best chain
+    let block_locator = vec![
+        side[3].as_ref().into(),
+        side[2].as_ref().into(),
+        side[1].as_ref().into(),
+        genesis.as_ref().into(),
+    ];
+    assert_eq!(
+        call(&mut service, RequestBlockHeader::GetBlockLocator).await?,
+        Response::BlockLocator { block_locator }
+    );
+
+    // Peers with a locator on the old best chain are sent the new best chain
+    // from the fork point
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::FindBlockHashes {
+                known_blocks: vec![best[2].as_ref().into(), genesis.as_ref().into()],
+                stop: None,
+            }
+        )
+        .await?,
+        Response::BlockHashes {
+            block_hashes: hashes(&side),
+        }
+    );
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::FindBlockHeaders {
+                known_blocks: vec![side_hash],
+                stop: Some(side[2].as_ref().into()),
+            }
+        )
+        .await?,
+        Response::BlockHeaders {
+            block_headers: vec![side[2].clone()],
+        }
+    );
+
+    // Ties go to the chain that was seen first
+    add_all(&mut service, &best[3..]).await?;
+    check_tip(&mut service, &side[3], 4).await?;
+
+    Ok(())
+}
+
+/// Check that `service` doesn't reorganize more than `MAX_REORG_DEPTH` blocks.
+async fn check_reorganize_depth_limit<S>(mut service: S) -> Result<(), Report>
+where
+    S: Service<RequestBlockHeader, Response = Response>,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    let genesis = genesis()?;
+    let best = chain(&genesis, MAX_REORG_DEPTH + 1, genesis.bits, 1);
+
+    add_all(&mut service, &[genesis.clone()]).await?;
+    add_all(&mut service, &best).await?;
+
+    // Block locators are exponentially spaced, and end at genesis
+    let block_locator = [100, 99, 98, 96, 92, 84, 68, 36]
+        .iter()
+        .map(|height: &usize| best[height - 1].as_ref().into())
+        .chain(std::iter::once(genesis.as_ref().into()))
+        .collect();
+    assert_eq!(
+        call(&mut service, RequestBlockHeader::GetBlockLocator).await?,
+        Response::BlockLocator { block_locator }
+    );
+
+    // Forking at genesis would disconnect one block too many
+    let too_deep = chain(&genesis, 1, HARD_BITS, 2);
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::AddBlockHeader {
+                block_header: too_deep[0].clone()
+            }
+        )
+        .await?,
+        Response::Added {
+            hash: too_deep[0].as_ref().into(),
+            height: BlockHeight(1),
+        }
+    );
+    check_tip(&mut service, &best[best.len() - 1], MAX_REORG_DEPTH + 1).await?;
+
+    // Forking at height 1 disconnects exactly `MAX_REORG_DEPTH` blocks
+    let deepest = chain(&best[0], 1, HARD_BITS, 3);
+    match call(
+        &mut service,
+        RequestBlockHeader::AddBlockHeader {
+            block_header: deepest[0].clone(),
+        },
+    )
+    .await?
+    {
+        Response::Reorganized { disconnected, .. } => {
+            assert_eq!(disconnected, hashes(&best[1..]))
+        }
+        response => panic!("expected a reorganization, got {:?}", response),
+    }
+    check_tip(&mut service, &deepest[0], 2).await?;
+
+    Ok(())
+}
+
+/// Check that `service` prunes side chains that are more than
+/// `SIDE_CHAIN_PRUNE_DEPTH` blocks below the tip.
+async fn check_side_chain_pruning<S>(mut service: S) -> Result<(), Report>
+where
+    S: Service<RequestBlockHeader, Response = Response>,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    let genesis = genesis()?;
+    let best = chain(&genesis, SIDE_CHAIN_PRUNE_DEPTH + 2, genesis.bits, 1);
+    let side = chain(&genesis, 2, genesis.bits, 2);
+    let side_hash = side[0].as_ref().into();
+
+    add_all(&mut service, &[genesis.clone()]).await?;
+    add_all(&mut service, &best[..1]).await?;
+    add_all(&mut service, &side[..1]).await?;
+
+    // The side chain header is still within the prune depth
+    add_all(&mut service, &best[1..SIDE_CHAIN_PRUNE_DEPTH as usize + 1]).await?;
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::Contains { hash: side_hash }
+        )
+        .await?,
+        Response::Contains(true)
+    );
+
+    add_all(&mut service, &best[SIDE_CHAIN_PRUNE_DEPTH as usize + 1..]).await?;
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::Contains { hash: side_hash }
+        )
+        .await?,
+        Response::Contains(false)
+    );
+
+    // Pruned side chains can't be extended, and new headers that deep are
+    // rejected
+    call(
+        &mut service,
+        RequestBlockHeader::AddBlockHeader {
+            block_header: side[1].clone(),
+        },
+    )
+    .await
+    .expect_err("headers on pruned side chains should be rejected");
+    call(
+        &mut service,
+        RequestBlockHeader::AddBlockHeader {
+            block_header: side[0].clone(),
+        },
+    )
+    .await
+    .expect_err("side chain headers below the prune depth should be rejected");
+
+    // Long chains are sent to peers in batches
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::FindBlockHeaders {
+                known_blocks: vec![genesis.as_ref().into()],
+                stop: None,
+            }
+        )
+        .await?,
+        Response::BlockHeaders {
+            block_headers: best[..MAX_FIND_BLOCK_HEADERS_RESULTS as usize].to_vec(),
+        }
+    );
+
+    Ok(())
+}
+
+/// Check that `service` returns best chain header ranges and header batches.
+async fn check_header_ranges<S>(mut service: S) -> Result<(), Report>
+where
+    S: Service<RequestBlockHeader, Response = Response>,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    let genesis = genesis()?;
+    let best = chain(&genesis, 4, genesis.bits, 1);
+    let side = chain(&genesis, 1, genesis.bits, 2);
+    let side_hash: BlockHeaderHash = side[0].as_ref().into();
+
+    add_all(&mut service, &[genesis.clone()]).await?;
+    add_all(&mut service, &best).await?;
+    add_all(&mut service, &side).await?;
+
+    // Ranges only contain best chain headers, and stop at the tip
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::GetBlockHeaderRange {
+                start: BlockHeight(1),
+                end: BlockHeight(10),
+            }
+        )
+        .await?,
+        Response::BlockHeadersWithHeights {
+            block_headers: best.iter().cloned().zip((1..).map(BlockHeight)).collect(),
+        }
+    );
+
+    // Batches can mix heights and hashes, including side chain hashes
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::GetBlockHeaders(vec![
+                BlockHeight(2).into(),
+                side_hash.into(),
+                BlockHeaderHash::from(genesis.as_ref()).into(),
+            ])
+        )
+        .await?,
+        Response::BlockHeadersWithHeights {
+            block_headers: vec![
+                (best[1].clone(), BlockHeight(2)),
+                (side[0].clone(), BlockHeight(1)),
+                (genesis.clone(), BlockHeight(0)),
+            ],
+        }
+    );
+
+    call(
+        &mut service,
+        RequestBlockHeader::GetBlockHeaders(vec![BlockHeight(1).into(), BlockHeight(5).into()]),
+    )
+    .await
+    .expect_err("batches with missing headers should be rejected");
+    call(
+        &mut service,
+        RequestBlockHeader::GetBlockHeaderRange {
+            start: BlockHeight(2),
+            end: BlockHeight(1),
+        },
+    )
+    .await
+    .expect_err("ranges that end below their start should be rejected");
+    call(
+        &mut service,
+        RequestBlockHeader::GetBlockHeaderRange {
+            start: BlockHeight(0),
+            end: BlockHeight(MAX_BLOCK_HEADER_RANGE_RESULTS),
+        },
+    )
+    .await
+    .expect_err("ranges longer than the limit should be rejected");
+    call(
+        &mut service,
+        RequestBlockHeader::GetBlockHeaders(vec![
+            BlockHeight(0).into();
+            MAX_BLOCK_HEADER_RANGE_RESULTS as usize + 1
+        ]),
+    )
+    .await
+    .expect_err("batches larger than the limit should be rejected");
+
+    Ok(())
+}
+
+/// Check that `service` publishes best chain changes to subscribers.
+async fn check_chain_events<S>(mut service: S) -> Result<(), Report>
+where
+    S: Service<RequestBlockHeader, Response = Response>,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    let genesis = genesis()?;
+    let best = chain(&genesis, 2, genesis.bits, 1);
+    let side = chain(&genesis, 3, genesis.bits, 2);
+
+    let mut events = match call(&mut service, RequestBlockHeader::Subscribe).await? {
+        Response::Subscription { chain_events } => chain_events.subscribe(),
+        r => panic!("unexpected response {:?}", r),
+    };
+
+    let connected = |header: &Arc<BlockHeader>, height| ChainEvent::Connected {
+        hash: header.as_ref().into(),
+        height: BlockHeight(height),
+    };
+    let disconnected = |header: &Arc<BlockHeader>, height| ChainEvent::Disconnected {
+        hash: header.as_ref().into(),
+        height: BlockHeight(height),
+    };
+    let new_tip = |header: &Arc<BlockHeader>, height| ChainEvent::NewTip {
+        hash: header.as_ref().into(),
+        height: BlockHeight(height),
+    };
+
+    add_all(&mut service, &[genesis.clone()]).await?;
+    add_all(&mut service, &best).await?;
+    assert_eq!(
+        events.by_ref().take(6).collect::<Vec<_>>().await,
+        vec![
+            connected(&genesis, 0),
+            new_tip(&genesis, 0),
+            connected(&best[0], 1),
+            new_tip(&best[0], 1),
+            connected(&best[1], 2),
+            new_tip(&best[1], 2),
+        ]
+    );
+
+    // Side chain headers don't have any events until the side chain has the
+    // most work
+    add_all(&mut service, &side).await?;
+    assert_eq!(
+        events.by_ref().take(6).collect::<Vec<_>>().await,
+        vec![
+            disconnected(&best[1], 2),
+            disconnected(&best[0], 1),
+            connected(&side[0], 1),
+            connected(&side[1], 2),
+            connected(&side[2], 3),
+            new_tip(&side[2], 3),
+        ]
+    );
+
+    Ok(())
+}
+
+/// Check that `service` keeps a consistent best chain when competing headers
+/// are added concurrently.
+async fn check_concurrent_inserts<S>(mut service: S) -> Result<(), Report>
+where
+    S: Service<RequestBlockHeader, Response = Response> + Clone,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+    let genesis = genesis()?;
+    add_all(&mut service, &[genesis.clone()]).await?;
+
+    // Competing children of genesis, with the same work
+    let siblings: Vec<Arc<BlockHeader>> = (1..=8)
+        .map(|tag| chain(&genesis, 1, genesis.bits, tag).remove(0))
+        .collect();
+    let inserts = siblings.iter().cloned().map(|block_header| {
+        let mut service = service.clone();
+        async move {
+            call(
+                &mut service,
+                RequestBlockHeader::AddBlockHeader { block_header },
+            )
+            .await
+        }
+    });
+    for response in futures::future::join_all(inserts).await {
+        response?;
+    }
+
+    // Exactly one sibling is in the best chain, and extending any other
+    // sibling reorganizes onto it
+    let tip = match call(&mut service, RequestBlockHeader::GetTip).await? {
+        Response::Tip { hash, height } => {
+            assert_eq!(height, BlockHeight(1));
+            hash
+        }
+        response => panic!("unexpected response to GetTip: {:?}", response),
+    };
+    let side = siblings
+        .iter()
+        .find(|header| BlockHeaderHash::from(header.as_ref()) != tip)
+        .expect("there are other siblings")
+        .clone();
+    let extension = chain(&side, 1, genesis.bits, 9);
+    assert_eq!(
+        call(
+            &mut service,
+            RequestBlockHeader::AddBlockHeader {
+                block_header: extension[0].clone(),
+            }
+        )
+        .await?,
+        Response::Reorganized {
+            hash: extension[0].as_ref().into(),
+            height: BlockHeight(2),
+            disconnected: vec![tip],
+            connected: hashes(&[side.clone(), extension[0].clone()]),
+        }
+    );
+    check_tip(&mut service, &extension[0], 2).await?;
+    assert_eq!(
+        call(&mut service, RequestBlockHeader::GetBlockLocator).await?,
+        Response::BlockLocator {
+            block_locator: vec![
+                extension[0].as_ref().into(),
+                side.as_ref().into(),
+                genesis.as_ref().into(),
+            ],
+        }
+    );
+
+    Ok(())
+}
+
+fn on_disk_service(
+    storage_guard: &TempDir,
+) -> impl Service<
+    RequestBlockHeader,
+    Response = Response,
+    Error = Box<dyn std::error::Error + Send + Sync + 'static>,
+> {
+    on_disk_headersonly::init(
+        Config {
+            cache_dir: storage_guard.path().to_owned(),
+            memory_cache_bytes: 1024 * 1024 * 1024,
+            ephemeral: false,
+        },
+        Network::Mainnet,
+    )
+}
+
+#[tokio::test]
+async fn reorganize() -> Result<(), Report> {
+    zebra_test::init();
+
+    check_reorganize(in_memory_headersonly::init()).await?;
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    check_reorganize(on_disk_service(&storage_guard)).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reorganize_depth_limit() -> Result<(), Report> {
+    zebra_test::init();
+
+    check_reorganize_depth_limit(in_memory_headersonly::init()).await?;
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    check_reorganize_depth_limit(on_disk_service(&storage_guard)).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn side_chain_pruning() -> Result<(), Report> {
+    zebra_test::init();
+
+    check_side_chain_pruning(in_memory_headersonly::init()).await?;
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    check_side_chain_pruning(on_disk_service(&storage_guard)).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn header_ranges() -> Result<(), Report> {
+    zebra_test::init();
+
+    check_header_ranges(in_memory_headersonly::init()).await?;
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    check_header_ranges(on_disk_service(&storage_guard)).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn chain_events() -> Result<(), Report> {
+    zebra_test::init();
+
+    check_chain_events(in_memory_headersonly::init()).await?;
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    check_chain_events(on_disk_service(&storage_guard)).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn concurrent_inserts() -> Result<(), Report> {
+    zebra_test::init();
+
+    check_concurrent_inserts(in_memory_headersonly::init()).await?;
+
+    let storage_guard = TempDir::new("./.tmp-state")?;
+    check_concurrent_inserts(on_disk_service(&storage_guard)).await?;
+
+    Ok(())
+}
@@ -255,3 +255,76 @@ lazy_static! {
     // https://github.com/zcash/zips/blob/master/zip-0243.rst#test-vector-3
     pub static ref ZIP243_3 : Vec<u8> = <Vec<u8>>::from_hex("0400008085202f8901a8c685478265f4c14dada651969c45a65e1aeb8cd6791f2f5bb6a1d9952104d9010000006b483045022100a61e5d557568c2ddc1d9b03a7173c6ce7c996c4daecab007ac8f34bee01e6b9702204d38fdc0bcf2728a69fde78462a10fb45a9baa27873e6a5fc45fb5c76764202a01210365ffea3efa3908918a8b8627724af852fc9b86d7375b103ab0543cf418bcaa7ffeffffff02005a6202000000001976a9148132712c3ff19f3a151234616777420a6d7ef22688ac8b959800000000001976a9145453e4698f02a38abdaa521cd1ff2dee6fac187188ac29b0040048b004000000000000000000000000").expect("Transaction bytes are in valid hex representation");
 }
+
+/// A signature hash test vector from ZIP-143 or ZIP-243.
+pub struct SigHashVector {
+    /// The transaction bytes.
+    pub tx: Vec<u8>,
+    /// The script code of the signed input.
+    pub script_code: Vec<u8>,
+    /// The index of the signed input, or `None` if the signature doesn't sign
+    /// an input.
+    pub input: Option<usize>,
+    /// The signature hash type.
+    pub hash_type: u32,
+    /// The value of the output spent by the signed input, in zatoshis.
+    pub amount: u64,
+    /// The expected signature hash.
+    pub sighash: [u8; 32],
+}
+
+fn sighash_vector(
+    tx: &str,
+    script_code: &str,
+    input: Option<usize>,
+    hash_type: u32,
+    amount: u64,
+    sighash: &str,
+) -> SigHashVector {
+    SigHashVector {
+        tx: <Vec<u8>>::from_hex(tx).expect("Transaction bytes are in valid hex representation"),
+        script_code: <Vec<u8>>::from_hex(script_code)
+            .expect("Script bytes are in valid hex representation"),
+        input,
+        hash_type,
+        amount,
+        sighash: <[u8; 32]>::from_hex(sighash)
+            .expect("Signature hash bytes are in valid hex representation"),
+    }
+}
+
+lazy_static! {
+    // The full ZIP-143 test vector set, from `zip_0143.py` in
+    // https://github.com/zcash-hackworks/zcash-test-vectors
+    //
+    // The first two vectors are ZIP-143's test vectors 1 and 2.
+    pub static ref ZIP143_SIGHASHES: Vec<SigHashVector> = vec![
+        sighash_vector("030000807082c4030002e7719811893e0000095200ac6551ac636565b2835a0805750200025151481cdd86b3cc431800", "6a00000063ac53", None, 0x01, 1672704339313879, "a1f1a4e5cd9bd522322d661edd2af1bf2a7019cfab94ece18f4ba935b0a19073"),
+        sighash_vector("030000807082c403024201cfb1cd8dbf69b8250c18ef41294ca97993db546c1fe01f7e9c8e36d6a5e29d4e30a703ac6a0098421c69378af1e40f64e125946f62c2fa7b2fecbcb64b6968912a6381ce3dc166d56a1d62f5a8d7056363635353e8c7203d02d2da86387ae60100080063656a63ac5200a7622997f4ff0400075151005353656597b0e4e4c705fc05020000000000000000000000000000000076495c222f7fba1e31defa3d5a57efc2e1e9b01a035587d5fb1a38e01d94903d3c3e0ad3360c1d3710acd20b183e31d49f25c9a138f49b1a537edcf04be34a9851a7af9db6990ed83dd64af3597c04323ea51b0052ad8084a8b9da948d320dadd64f5431e61ddf658d24ae67c22c8d1309131fc00fe7f235734276d38d47f1e191e00c7a1d48af046827591e9733a97fa6b679f3dc601d008285edcbdae69ce8fc1be4aac00ff2711ebd931de518856878f73476f21a482ec9378365c8f7393c94e2885315eb4671098b79535e790fe53e29fef2b3766697ac32b4f473f468a008e72389fc03880d780cb07fcfaabe3f1a84b27db59a4a153d882d2b2103596555ed9494c6ac893c49723833ec8926c1039586a7afcf4a0d9c731e985d99589c03b838e8aaf745533ed9e8ae3a1cd074a51a20da8aba18d1dbebbc862ded42435e02476930d069896cff30eb414f727b89e001afa2fb8dc3436d75a4a6f26572504b0b2232ecb9f0c02411e52596bc5e90457e745939ffedbd12863ce71a02af117d417adb3d15cc54dcb1fce467500c6b8fb86b12b56da9c382857deecc40a98d5f2903395ee4762dd21afdbb5d47fa9a6dd984d567db2857b927b7fae2db587105415d0242789d38f50b8dbcc129cab3d17d19f3355bcf73cecb8cb8a5da01307152f13902a270572670dc82d39026c6cb4cd4b0f7f5aa2a4f5a5341ec5dd715406f2fdd2a02733f5f641c8c21862a1bafce2609d9eecfa158cfb5cd79f88008e315dc7d8388036c1782fd2795d18a763624c25fa959cc97489ce75745824b77868c53239cfbdf73caec65604037314faaceb56218c6bd30f8374ac13386793f21a9fb80ad03bc0cda4a44946c00e1b1a1df0e5b87b5bece477a709649e950060591394812951e1fe3895b8cc3d14d2cf6556df6ed4b4ddd3d9a69f53357d7767f4f5ccbdbc596631277f8fecd08cb056b95e3025b9792fff7f244fc716269b926d62e9596fa825c6bf21aff9e68625a192440ea06828123d97884806f15fa08da52754a1095e3ff1abd5ce4fddfccfc3a6128aef784a64610a89d1a7099216d0814d3a2d452431c32d411ac1cce82ad0229407bbc48985675e3f874a4533f1d63a84dfa3e0f460fe2f57e34fbc75423c3737f5b2a0615f5722db041a3ef66fa483afd3c2e19e59444a64add6df1d963f5dd5b5010d3d025f0287c4cf19c75f33d51ddddba5d657b43ee8da645443814cc7329f3e9b4e54c236c29af3923101756d9fa4bd0f7d2ddaacb6b0f86a2658e0a07a05ac5b950051cd24c47a88d13d659ba2a46ca1830816d09cd7646f76f716abec5de07fe9b523410806ea6f288f8736c23357c85f45791e1708029d9824d90704607f387a03e49bf9836574431345a7877efaa8a08e73081ef8d62cb780ab6883a50a0d470190dfba10a857f82842d3825b3d6da0573d316eb160dc0b716c48fbd467f75b780149ae8808f4e68f50c0536acddf6f1aeab016b6bc1ec144b4e553acfd670f77e755fc88e0677e31ba459b44e307768958fe3789d41c2b1ff434cb30e15914f01bc6bc2307b488d2556d7b7380ea4ffd712f6b02fe806b94569cd4059f396bf29b99d0a40e5e1711ca944f72d436a102fca4b97693da0b086fe9d2e7162470d02e0f05d4bec9512bfb3f38327296efaa74328b118c27402c70c3a90b49ad4bbc68e37c0aa7d9b3fe17799d73b841e751713a02943905aae0803fd69442eb7681ec2a05600054e92eed555028f21b6a155268a2dd6640a69301a52a38d4d9f9f957ae35af7167118141ce4c9be0a6a492fe79f1581a155fa3a2b9dafd82e650b386ad3a08cb6b83131ac300b0846354a7eef9c410e4b62c47c5426907dfc6685c5c99b7141ac626ab4761fd3f41e728e1a28f89db89ffdeca364dd2f0f0739f0534556483199c71f189341ac9b78a269164206a0ea1ce73bfb2a942e7370b247c046f8e75ef8e3f8bd821cf577491864e20e6d08fd2e32b555c92c661f19588b72a89599710a88061253ca285b6304b37da2b5294f5cb354a894322848ccbdc7c2545b7da568afac87ffa005c312241c2d57f4b45d6419f0d2e2c5af33ae243785b325cdab95404fc7aed70525cddb41872cfcc214b13232edc78609753dbff930eb0dc156612b9cb434bc4b693392deb87c530435312edcedc6a961133338d786c4a3e103f60110a16b1337129704bf4754ff6ba9fbe65951e610620f71cda8fc877625f2c5bb04cbe1228b1e886f4050afd8fe94e97d2e9e85c6bb748c0042d3249abb1342bb0eebf62058bf3de080d94611a3750915b5dc6c0b3899d41222bace760ee9c8818ded599e34c56d7372af1eb86852f2a732104bdb750739de6c2c6e0f9eb7cb17f1942bfc9f4fd6ebb6b4cdd4da2bca26fac4578e9f543405acc7d86ff59158bd0cba3aef6f4a8472d144d99f8b8d1dedaa9077d4f01d4bb27bbe31d88fbefac3dcd4797563a26b1d61fcd9a464ab21ed550fe6fa09695ba0b2f10e00000000000000000000000000000000ea6468cc6e20a66f826e3d14c5006f0563887f5e1289be1b2004caca8d3f34d6e84bf59c1e04619a7c23a996941d889e4622a9b9b1d59d5e319094318cd405ba27b7e2c084762d31453ec4549a4d97729d033460fcf89d6494f2ffd789e98082ea5ce9534b3acd60fe49e37e4f666931677319ed89f85588741b3128901a93bd78e4be0225a9e2692c77c969ed0176bdf9555948cbd5a332d045de6ba6bf4490adfe7444cd467a09075417fcc0062e49f008c51ad4227439c1b4476ccd8e97862dab7be1e8d399c05ef27c6e22ee273e15786e394c8f1be31682a30147963ac8da8d41d804258426a3f70289b8ad19d8de13be4eebe3bd4c8a6f55d6e0c373d456851879f5fbc282db9e134806bff71e11bc33ab75dd6ca067fb73a043b646a70339cab4928386786d2f24141ee120fdc34d6764eafc66880ee0204f53cc1167ed02b43a52dea3ca7cff8ef35cd8e6d7c111a68ef44bcd0c1513ad47ca61c659cc5d0a5b440f6b9f59aff66879bb6688fd2859362b182f207b3175961f6411a493bffd048e7d0d87d82fe6f990a2b0a25f5aa0111a6e68f37bf6f3ac2d26b84686e569038d99c1383597fad81193c4c1b16e6a90e2d507cdfe6fbdaa86163e9cf5de310003ca7e8da047b090db9f37952fbfee76af61668190bd52ed490e677b515d0143840307219c7c0ee7fc7bfc79f325644e4df4c0d7db08e9f0bd024943c705abff899403a605cfbc7ed746a7d3f7c37d9e8bdc433b7d79e08a12f738a8f0dbddfef2f26502f3e47d1b0fd11e6a13311fb799c79c641d9da43b33e7ad012e28255398789262275f1175be8462c01491c4d842406d0ec4282c9526174a09878fe8fdde33a29604e5e5e7b2a025d6650b97dbb52befb59b1d30a57433b0a351474444099daa371046613260cf3354cfcdada663ece824ffd7e44393886a86165ddddf2b4c41773554c86995269408b11e6737a4c447586f69173446d8e48bf84cbc000a807899973eb93c5e819aad669413f8387933ad1584aa35e43f4ecd1e2d0407c0b1b89920ffdfdb9bea51ac95b557af71b89f903f5d9848f14fcbeb1837570f544d6359eb23faf38a0822da36ce426c4a2fbeffeb0a8a2e297a9d19ba15024590e3329d9fa9261f9938a4032dd34606c9cf9f3dd33e576f05cd1dd6811c6298757d77d9e810abdb226afcaa4346a6560f8932b3181fd355d5d391976183f8d99388839632d6354f666d09d3e5629ea19737388613d38a34fd0f6e50ee5a0cc9677177f50028c141378187bd2819403fc534f80076e9380cb4964d3b6b45819d3b8e9caf54f051852d671bf8c1ffde2d1510756418cb4810936aa57e6965d6fb656a760b7f19adf96c173488552193b147ee58858033dac7cd0eb204c06490bbdedf5f7571acb2ebe76acef3f2a01ee987486dfe6c3f0a5e234c127258f97a28fb5d164a8176be946b8097d0e317287f33bf9c16f9a545409ce29b1f4273725fc0df02a04ebae178b3414fb0a82d50deb09fcf4e6ee9d180ff4f56ff3bc1d3601fc2dc90d814c3256f4967d3a8d64c83fea339c51f5a8e5801fbb97835581b602465dee04b5922c2761b54245bec0c9eef2db97d22b2b3556cc969fbb13d06509765a52b3fac54b93f421bf08e18d52ddd52cc1c8ca8adfaccab7e5cc2f4573fbbf8239bb0b8aedbf8dad16282da5c9125dba1c059d0df8abf621078f02d6c4bc86d40845ac1d59710c45f07d585eb48b32fc0167ba256e73ca3b9311c62d109497957d8dbe10aa3e866b40c0baa2bc492c19ad1e6372d9622bf163fbffeaeee796a3cd9b6fbbfa4d792f34d7fd6e763cd5859dd26833d21d9bc5452bd19515dff9f4995b35bc0c1f876e6ad11f2452dc9ae85aec01fc56f8cbfda75a7727b75ebbd6bbffb43b63a3b1b671e40feb0db002974a3c3b1a788567231bf6399ff89236981149d423802d2341a3bedb9ddcbac1fe7b6435e1479c72e7089d029e7fbbaf3cf37e9b9a6b776791e4c5e6fda57e8d5f14c8c35a2d270846b9dbe005cda16af4408f3ab06a916eeeb9c9594b70424a4c1d171295b6763b22f47f80b53ccbb904bd68fd65fbd3fbdea1035e98c21a7dbc91a9b5bc7690f05ec317c97f8764eb48e911d428ec8d861b708e8298acb62155145155ae95f0a1d1501034753146e22d05f586d7f6b4fe12dad9a17f5db70b1db96b8d9a83edadc966c8a5466b61fc998c31f1070d9a5c9a6d268d304fe6b8fd3b4010348611abdcbd49fe4f85b623c7828c71382e1034ea67bc8ae97404b0c50b2a04f559e49950afcb0ef462a2ae024b0f0224dfd73684b88c7fbe92d02b68f759c4752663cd7b97a14943649305521326bde085630864629291bae25ff8822a14c4b666a9259ad0dc42a8290ac7bc7f53a16f379f758e5de750f04fd7cad47701c8597f97888bea6fa0bf2999956fbfd0ee68ec36e4688809ae231eb8bc4369f5fe1573f57e099d9c09901bf39caac48dc11956a8ae905ead86954547c448ae43d315e669c4242da565938f417bf43ce7b2b30b1cd4018388e1a910f0fc41fb0877a5925e466819d375b0a912d4fe843b76ef6f223f0f7c894f38f7ab780dfd75f669c8c06cffa43eb47565a50e3b1fa45ad61ce9a1c4727b7aaa53562f523e73952", "53", Some(1), 0x03, 365293780364847, "23652e76cb13b85a0e3363bb5fca061fa791c40c533eccee899364e6e60bb4f7"),
+        sighash_vector("030000807082c4030299a69fdf1c5ac7732146ee5e1d6b6ca9b9180f964cc9d0878ae1373524d7d510e58227df095153636a006aac516ab0f1856e28d5c8afb095ef6184fed651589022eeaea4c0ce1fa6f085092b04979489172b3ef8194a0163f5724d6b02dee136f3a9aa0200035252ac17b73f8d383e000006ac630053ac5104b47556af73b6080100000000000000000000000000000000e2321d146071789d2335934a680614e83562f82dfd405b54a45eb32c165448d4d5d61ca2859585369f53f1a137e9e82b67b8fdaf01bda54a317311896ae10280a032440c420a421e944d1e952b70d5826cd3b08b7db9630fe4fd5f22125de840fcc40b98038af11d55be25432597b4b65b9ec1c7a8bbfd052cbf7e1c1785314934b262d5853754f1f17771cfb7503072655753fa3f54ecc587e9f83b581916092df26e63e18994cb0db91a0bbdc7b6119b32222adf5e61d8d8ae89dae4954b54813bb33f08d562ba513fee1b09c0fcd516055419474dd7fda038a89c84ea7b9468287f0eb0c10c4b132520194d3d8d5351fc10d09c15c8cc101aa1663bbf17b84111f38bb439f07353bdea3596d15e713e1e2e7d3f1c383135b47fa7f81f46df02902a404699ec912f5656c35b85763e4de583aecaa1dfd5d2677d9c8ffee877f60340a5ca0d67f6e554124739f805af876aeede53aa8b0f8e5604a73c30cbd09dad0a3d6f8a5dcc40def40797342113ba206fae8ebe4f3bc3caf69259e462eff9ba8b3f4bfaa1300c26925a8729cd32915bfc966086f0d5560bbe32a598c22adfb48c0372ba5d4287c0cefbacfd8ce195b4963c34a94bba7a175dae4bbe3ef4863d53700315090f47a068e227433f9e49d3aa09e356d8d66d0c0121e91a3c4aa3f27fa1b60396e2b41db908fdab8b18cc7304e94e970568f9421c0dbbbaf84598d972b0534f02a5e52670436aaa776ed2482ad703430201e53443c36dcfd34a0cb6637876105e03bf3bd58ec148cb64970e3223a91f71dfcfd5a04b667fbaf3d4b3b908b9828820dfecdd753750b5f9d2216e56c615272f854464c0ca4b1e85aedd038292c4e1a57744ebba010b9ebfbb011bd6f0b78805025d27f3c17746bae116c15d9f471f0f6288a150647b2afe9df7cccf01f5cde5f04680bbfed87f6cf429fb27ad6babe791766611cf5bc20e48bef119259b9b8a0e39c3df28cb9582ea338601cdc481b32fb82adeebb3dade25d1a3df20c37e712506b5d996c49a9f0f30ddcb91fe9004e1e83294a6c9203d94e8dc2cbb449de4155032604e47997016b304fd437d8235045e255a19b743a0a9f2e336b44cae307bb3987bd3e4e777fbb34c0ab8cc3d67466c0a88dd4ccad18a07a8d1068df5b629e5718d0f6df5c957cf71bb00a5178f175caca944e635c5159f738e2402a2d21aa081e10e456afb00b9f62416c8b9c0f7228f510729e0be3f305313d77f7379dc2af24869c6c74ee4471498861d192f0ff0f508285dab6b6a36ccf7d12256cc76b95503720ac672d08268d2cf7773b6ba2a5f664847bf707f2fc10c98f2f006ec22ccb5a8c8b7c40c7c2d49a6639b9f2ce33c25c04bc461e744dfa536b00d94baddf4f4d14044c695a33881477df124f0fcf206a9fb2e65e304cdbf0c4d2390170c130ab849c2f22b5cdd3921640c8cf1976ae1010b0dfd9cb2543e45f99749cc4d61f2e8aabfe98bd905fa39951b33ea769c45ab9531c57209862ad12fd76ba4807e65417b6cd12fa8ec916f013ebb8706a96effeda06c4be24b04846392e9d1e6930eae01fa21fbd700583fb598b92c8f4eb8a61aa6235db60f2841cf3a1c6ab54c67066844711d091eb931a1bd6281aedf2a0e8fab18817202a9be06402ed9cc720c16bfe881e4df4255e87afb7fc62f38116bbe03cd8a3cb11a27d568414782f47b1a44c97c680467694bc9709d32916c97e8006cbb07ba0e4180a3738038c374c4cce8f32959afb25f303f5815c4533124acf9d18940e77522ac5dc4b9570aae8f47b7f57fd8767bea1a24ae7bed65b4afdc8f1278c30e2db98fd172730ac6bbed4f1127cd32b04a95b205526cfcb4c4e1cc955175b3e8de1f5d81b18669692350aaa1a1d797617582e54d7a5b57a683b32fb1098062dad7b0c2eb518f6862e83db25e3dbaf7aed504de932acb99d735992ce62bae9ef893ff6acc0ffcf8e3483e146b9d49dd8c7835f43a37dca0787e3ec9f6605223d5ba7ae0ab9025b73bc03f7fac36c009a56d4d95d1e81d3b3ebca7e54cc1a12d127b57c8138976e791013b015f06a624f521b6ee04ec980893c7e5e01a336203594094f82833d7445fe2d09130f63511da54832de9136b39f4599f5aa5dfbb45da60cdceab7eefde89be63f3f7c0d2324847cce1405def7c469b0e272494e5df54f568656cb9c8818d92b72b8bc34db7bb3112487e746eefe4e808bbb287d99bf07d00dabededc5e5f074ffeae0cba7da3a516c173be1c513323e119f635e8209a074b216b7023fadc2d25949c90037e71e3e550726d210a2c688342e52440635e9cc14afe10102621a9c9accb782e9e4a5fa87f0a956f5b85509960285c22627c59483a5a4c28cce4b156e551406a7ee8355656a21e43e38ce129fdadb759eddfa08f00fc8e567cef93c6792d01df05e6d580f4d5d48df042451a33590d3e8cf49b2627218f0c292fa66ada945fa55bb23548e33a83a562957a3149a993cc472362298736a8b778d97ce423013d64b32cd172efa551bf7f368f04bdaec6091a3004a757598b801dcf675cb83e43a53ae8b254d333bcda20d4817d3477abfba25bb83df5949c126f149b1d99341e4e6f9120f4d41e629185002c72c012c414d2382a6d47c7b3deaba7", "ac00", Some(1), 0x03, 711752082734717, "b69d7929a3401f6443aa19938af1c812c77321b4f206379b86b3a68aa7964676"),
+        sighash_vector("030000807082c40300020d386ae30dd30100070065516553536ad50942f76967020005ac656365ac61a7b8b9f699d60102000000000000000000000000000000002849232f329fef95c7af370098ffe4918e0ca1df47f275867b739e0a514d3209325e217045927b479c1ce2e5d54f25488cad1513e3f44a21266cfd841633327dee6cf810fbf7393e317d9e53d1be1d5ae7839b66b943b9ed18f2c530e975422332c3439cce49a29f2a336a4851263c5e9bd13d731109e844b7f8c392a5c1dcaa2ae5f50ff63fab9765e016702c35a67cd7364d3fab552fb349e35c15c50250453fd18f7b855992632e2c76c0fbf1ef963ea80e3223de3277bc559251725829ec03f213ba8955cab2822ff21a9b0a4904d668fcd77224bde3dd01f6ffc4828f6b64230b35c6a049873494276ea1d7ed5e92cb4f90ba83a9e49601b194042f2900d99d312d7b70508cf176066d154dbe96ef9d4367e4c840e4a17b5e5122e8ebe2038a3c5f4cbae21ea3fa1ae6c25a9462ebcbb0fd5f14554bc97747c33e34da90c802d8d0d50bfe37618c5812891484fa259322c15092d4155d8696d6f12f24fd36440ab3be0871ca3dd9625348a614b59bde45885649bae36de34def8fcec85343475d976ae1e9b27829ce2ac5efd0b399a8b448be6504294ee6b3c1c6a5342d7c01ae038ad3070c2b1a91573af5e0c5e4cbbf4acdc6b54c9272200d9970250c17c1036f02085c41858ed3a0c48150bc697e4a695fef335f7ad07e1a46dc767ff822db70e6029080b9816b2232c81a4c66cc586abfe1eaa8ca6cf41fc3c3e6c7b886fb6dac9f0222b4fc6fff9d0513d61a21c80a377671d135a668a0ae2bb934c82c4142da69d102a7de9a7df706400ec79878d868e17e8f71ea31495af819a016cc419e07c501aa8309b2e6c85b79b2763733a37bbc0420d42537b871b4294a65d3e055ff718dd9dc8c75e7e5b2efe442637371b7c48f6ee99e3ea38a4b0f2f67fc2b908cda657eae754e037e262e9a9f9bd7ec4267ed8e96930e1084783c37d6f9dd15fd29f4cc477e66f130d630430dcc0104899b4f9f46eb090ef7fc90b479abf61f93955ee00e6a1848f1ab14ad334f2b68035808cdf1bb9e9d9a816baf728a955b960b7701fa626687dc3c9cba646337b53e29816e9482ddf5578a8768aae477fce410ac2d5de6095861c111d7feb3e6bb4fbb5a54955495972798350a253f05f66c2ecfcbc0ed43f5ec2e6d8dba15a51254d97b1821107c07dd9a16ef8406f943e282b95d4b362530c913d6ba421df6027de5af1e4745d5868106954be6c1962780a2941072e95131b1679df0637625042c37d48ffb152e5ebc185c8a2b7d4385f1c95af937df78dfd8757fab434968b0b57c66574468f160b447ac8221e5060676a842a1c6b7172dd3340f764070ab1fe091c5c74c95a5dc043390723a4c127da14cdde1dc2675a62340b3e6afd0522a31de26e7d1ec3a9c8a091ffdc75b7ecfdc7c12995a5e37ce3488bd29f8629d68f696492448dd526697476dc061346ebe3f677217ff9c60efce943af28dfd3f9e59692598a6047c23c4c01400f1ab5730eac0ae8d5843d5051c376240172af218d7a1ecfe65b4f75100638983c14de4974755dade8018c9b8f4543fb095961513e67c61dbc59c607f9b51f8d09bdcad28bcfb9e5d2744ea8848b2623ac07f8ef61a81a35910b8a1baf39a919a7b60bc604d63185f759221d847cc54a22765a4c33475b5791e9af3271fc8d9350667090d8184ec50522d804f23c4fb44ffa481bc92ae408d1b9f2b131904f9705c59e2f4bde7a3b2c085d93fd2abc5e14d163001a12f51938d021afa92239b873dc6c357eaa8af4ee6d00540657fe32914103b5d98f68bd3e2b5359f08ccd88d0c811e4c31fbb49f3a90bbd05dce62f344e7077593159ae35050b04c9e6b86bc432dc8b048c73c0018ca5b69411297732a4e1aa99a928c71e7a24fd277856aa42501e51b012aea9446a2104e93f815a0b3a29b458314f3d8be2b9823d342f46213e942a7e19a46e970b5c506708430317b1bb3b35df68ae33a4926a03e6bfeb5510416fcbb0524c9ca5074156cc5a5d6fe1c995edc60a2f550411aa41e3da3bdcf64bcf04a0510571b936d47e55cec0330ee8dfe73563404f047d7f3a8a3d7743bc554955210f1eb0d08599ea77d5f974d87176d37d98b9c0ad440407209ed6a9f08464d565593e1a63b938536b49244e97d880173b640f2ddb74d068ecb46cf289b7d891307bba37054cf91b31fc82f74d5fcc000942ede911825f53fe609686f463223b1e9bc03bde895d1238fad04a3bfce68a075e8a37c0e87bf46dd015545f9b4fb0eec645ffcbbe0ca5f8c561b257d52d602d8c94c502873a01d9251d8c860c041525b3bf4e3a2eb9272815c7586768428b4c2b25e3745f009c5dce20b69d5d7c43ceb736b6831e8c110f16cfdb3a467e9414c00ecf13731500894555678c497faba9a95d01cc464390fc4a76bfa8b0e1c68a525d706d6604b2330b6b3485215f606f1883a751588c7efa506c3e8d0c60192e8476bd1175d9562087bdb818e66216286bafe47ff4dbcced51444480a9a5673ece7fac73a000000000000000000000000000000000ed41ab0051753a7caa89be3139afd9793b3e02f27f040046595acd47bf13fd0da27f09eda48036d3ee437f2ee8f8606ea97343c33584657f46dba99db5cfe6ca176fab7b0f3bfa0ab61e340c34eb9f17c7ec2be03b180f0bb6f434c2a6542e00e84373f4f4649cda32bf686666143f622aa480460b5afac518607cd9af8bcd6b58c30127316b25d5ea7bf6b0cab8542ff69d9b2f180be12ed75344a395aa10f852f083ad64ef40e9c0309e9bba54b8cb33c95498a69538d3ae5b25e247098306fa8c74a8ee5bca941531d61aac27aab3dc5617d5606c9577a2a8346e8d85b32b8505775108dc85e2ade2eac1e636e1af4054c8b6f57632df269c3723b320872e4c57b218358dc7e9905bb04edf92edf0df635f3bf361e57a13296e1447af5080272d636e27518a9876e15eb01f5e8ded81892511cc2851b00b832712a6d3ba5660317bcd3567621a7cf8445589653262020c33bf78031b8ee0707de072068c170570b27e6d9f5c6ddc335402efc548862f5a07094fd428a7bbc15d7b38d05362c9ca985f58a76647d2be4c2cd6b3d17d6870971d7a098baf72c6f6f1214cf1faae488037de259d3415c2f0ddec7457004f35708d1eccccc0df65a04943ad5cbc13f295f020fe056c40b2d88f27dc34cfeb803be3483a9ebf9b5a9026057725d63ead2c0c0031fe26ac1e7bdfcd6fad875842d194f331750462c06b8d7982d67995ed5d3ae96025ae0067f4eb1c7c93231bd39773cbe0a9d66b0c9aa8cff6a376e1f372eac6ac4026cc0942245d4c2dcf02d7640ffcc5a6ac3a87f5c411551bcc2f26cb94961d53f95ddb19ae930c8d70f031b29a5df99ff36695e802cbcb6b58c1ba7ed5eacfa76414a41ad4a44f71f1b580d34c3a952920b254a145fea517f5b42b2f65ecd0f82595478d80ae5c8ceea12a161ccbb5eac09990fc619a46080436dbd08d74784af002d58e06faf7f3ceae7d3419b1fca265a5559cf9e2d3b60978d81a678b9ed8e4486b4d14609d6c127c0c2fbffe30a60f7bff1d9fb8300ed009253ba9b996fa05241b10f5ac9a8408e925b626bb21a471fe3bede52bba097b2a99a9ba5a86658c3fd9ec55bfa9b328567254ab36d2c7f44d2c7e13eb54beb70ea8fa94b6c6e012d79e3f53689c2b1a18eaf2d471d13c1ab39d9194ae843ab1d28ffa8f69dc7e15cc38b12e8fcd79255b7216056d9edb7482fb98aa033b65e51c1a08b8a11d84d0409b734f452aaf0d6b18f50258683d3f9a76d399fd047eee288bb4585851dc93eccc62322924cd13b5dd4eed66ed8d9972d772629ea64742e54733981b006c062468e4bd8f7dd9af698f52ae814634e81d7f3e0c420317caca9ae4811c6af06fe80a8c02ab7a00e18e4a6aa1ea1b76945d2615d43ac118b56c2f2960fe93a025f13ec91ffc6d2c353699abb092dedc065db8fa214dbc46466f897b88c58b30152133aa3831af37c74d99e9e36ff7011d3238305691508a2c3a43e755dc081b511d6482a7db65fa9699ea87ff47099ed3637dbb0a3d0ef79796a8ef1e4d94d42b4bc2b4a038ae6e46b24cfc84153d31eaf895063a5ca959be63f37f2ba0d432366736d8632fce072b6ae5b6f3fd59d3faff638275a992fefc87e60d44c2cadc2b5c494e3e72eb4597c96b40167799a9001a2ed3676a8b403ae25ffd772f7081e9a32bcc1c5e2edd4e2a6576b783cce3aae11fa432262548856183ee682d5dc31beb38f061cbdeca7021a444e2dd417df26dcd220f2b731772b439e96d614e1facb486c7a7d5171b1de359f6ad3a96f649c969102a1964fb4b4a1a4279c68e6c372e42187d754e804a61653092069fb9b6d25266890808b015df28c801065da6febdc1a56bfd002625acfaa5373fde149c1cfc3649b4869696d44ecb12479c5ebef995f10029f8b530eeb3fdc2e50e8757fc0bb9e263023db82f878d9ac7ffb0bd4391df1d879899a3ef57bfd0d1f7755648edd85bb052a6edf71cd2628c987429f36dc505ccc43f30e7a869c9e255e2af9fcf30c121796d190000960cb6fe2f1bf246118b498f3247f9d484c73cf09393039e45326b8ffffb3e7e6159c46699f100792d4672950348a90552e45943beeacf03f3216f94e274d63d637d9f190e8a266cdeef153530bee5cb8355260505c2c2e5d990fffdc34ec0ff7f1af81b24ced0efa6213da6c7c60c487f5f7b03f8160a057f46d05bf8218b3add9c06893bd02db9b61191dfb133bfabe4858e47a4cc32e416ec08b8ac7915a43733f4406e9d967c560f344d7e904a28045d99f3af8c82e97e1b9c1b205e585fbebb48faf58f1b65dca2497e09a70aad4865f85715a280e186f3fc1740d8184d33e8322169521cdc132212939c84a108964e2de74b6ea55b4cb8f6f9bee98b10d415109455f48b776082dc30b4bc73477075511700308158ce2f2f9bf0f691b2ce53e61142cb740c15b7b623cf48b3f7bfefa31bcdc665c6d7123e95350811375947b055a43db07e03f33627df5c638bfad956ddc1ea7d7620a20f2792f63817a1cf32580d04274234af2a51b56bb68a29e43a954142ba4ca6823bde9053d72fdadbc61ad5936c53fdd7579446d11c44607f41630e4c08915e631771550e9ce1fca2c63fe06b7989d584fa7d782a88c1e7d64b6fbf55e35", "6a535363", None, 0x01, 379068098637835, "92e7b48f328187712687af4dc17a73fe0a70ac078d24cdcdd458a3d68661ec0a"),
+        sighash_vector("030000807082c40302249df05701dab031c4bac1ea267a2996a2028d1e6a0f80a3847c531dba96ee65a24189bd0952ac656365ac006500b2a4f951ef8f497dfff2f2f271eab89c628e18b5fcb43882537eaf6ad2a6b1754633caa86bf2c76f0753636a6a656a53a2210c2701ea6c542cc8c7060000e01129f03a1e9c090100000000000000000000000000000000be0762c0b1c65855deba8422ca4b88abeea6a4382cf16ccd6dc7c37c44e549c4534819acd8bb0a02a5fa7a1c1d3806fbc3407fd7da93fd0de6400d3ab8977485cddfbed5932f507b79947adb2fad37615aa717db5f298099f20f263b359a1151a6b75c01365eb154ae42140d6e10342f14f34dc33e07ff0e4d1a6be375b32f84b92e5d81ebb639c4f27e715aa42cc75707d4ebd1bbfbe8f90fc7c953e7a9715e65af8267373d3451674ff084efd92ccf3bcc7aca1467b6327e4f9522b2cc579a7a8fff7ca7cf145dfc13eafc34153b2c3e8afbe53444d0c73b3bd5bc870b01cd457911e356313fd1dafb4c8151634a01aff7cf116d433c3d2b3adda9cebe18f7d172443e5e7b5ac9abe8db2256d7ebe2ff28020939503870597b9a955892c7380250a2d42ec92be723fedf2f2ede5a472aa1e74f33ad41901544edbbe3ac464cf403196015f4f22ac2b8fc01496beab4d45907f479812a259431a2cbc93d4f3b84e40b366020273a6752e501af6ff1b78ddc817e6ea351d6006becf8d2ffb03990f67774a81e05b7f4bbad8577fa27c9de64e1b11dcf384f5956443748755a9fc6f2a00310c3657ebac03bfc0b587bef2f45ec8acdaa51c143b0cb25b9142c61bd790a8003c23f90cc03495b51e4d2843e557f9e2545108c6c6fae359f645c276891c0dcab03af187700c082dc477740fb3f2cd7bb59fb358554e94c7e678ce01aebf94e515e03722967995aea858d64e7789ff306369577228180326a5b0af475e27a54b207b40392e376170e3fb005028261c99c2dbd0eedee871c1c0f48b8e9b8e4be77d1b737fe21f0fa5a18ebb52755b5a6cf6130fb56944cfab87527c250d113b29bcac9aaa10c2e7de415edb0806c6da03020a134ca7ecdc8da1bd57a37f55a46940b45b241b1c16ee100927d1bd860d445a9de50d4c384d6e1d00108026c0ea5ebbf0b72fbf5c370bce18d3acbc46599099baae1d802f77333494a7ae130fe86e8f818f9261a2dadb4125229ba0ffc0e7090324430b521a90d224ab7a1024e1d893e7404fedb348e4d5e2235c59a7876a0fc60145c6a009687684460271ee133a437fe52fb6cfba97fcec161df515dde905a24da6d37bdc34044a955e682b47471ca1e8c78c51ed377cd4afa894bd9bd12e707156da0726f7cf5729fabe372160463fe0429244d067489ba5d09472ecd9bcdc4d5e4df101e189db8463eb538307b587deff78de9c73af28080b2fd05003e11d3e1b3299dc9521f8b513badb010e91bfeb91b0b2a6cb129c2e825a597b8fb75bc562d654d62104640dd74e56cd14baaba565b84b845e163d1caef2533c3981637204f96a59c8e8024d9041b2029e94c15245f1a958840ba3f380a4d20f1184e77827de3ff8f3d73459afe241f723c084823230e003d3d21e53501ec0499b083a7dad685c57127f4de64733a880c2db28fdaabf1b542d205f664a35135712711dcccd931a50b9c5661882360d4cac0047681bc2e2b3bf6c99760d7cfb4fa21394377a4551c76d1f75ac03c262054dffd79a9ded05e888958199eea4501e2990a53a5cd2a46a401576588fd7d058a26f28438e5782f45ac1d07f6f6f5ed73741d5785837a6b844b474775718c29dd99084e9f88ef153a8329f532a69017dc3a97ed754367723098e5765840b022897244745fbbbb30a7cb54fa0511166e9544122000610bd2aacbd82325a59b95154ecd82c88d23abd1e20770ffb8aabf83fc0734964ccd411d1c935714e24aab566f4f08424014c4eca91b590f082b473f361c87415d37bd20d70fd0b52b6ddf1865f766702e32b05b3cf1630ee8597aae19633f3516a8555ac5be32c675be1817efbffd9369041a089c283f19649968c2498cde56f500434f280d77a9c62e43cbd3f136a4c6a00a43e6ed530cb2e8ae838860adc88aacc7bd6a00ae0c19ff4533a485efde082b5f4d1f7a8ebe7ed82b7b05a8cfe1e373459f1bdcbf9525747e8c9508a555facb798740e0bdf994d9739bbe5538a0ae0f076c582c0f5ba878b99b8249db1d7e95056c98af083d98cb0ed9e3f7436e1c7643766f966b83e999206ebd1393b9b2a7f414480fa017480069f85c7749c435ae2fba2ddc1038d547d84854817ef39635c29827aad86726c9ade3b265b9086c8b5b75ef56fe4bd8b4d62893895b3fd2734fdac464156d7e5ebc7ecf1d83b86f659637e3b142c164963b8cdcf4ba4f4035dffc5a789458847781918ac72fc18bbbf5110032e66d75b3171ef4b513290164a77b42b0a4cfb89639ab23845e1aa2a452f3731c8cb65082a622a7c2e0013ea47d0bdd42d6990466649a905c684c3251716d61f760d53de6e3f790fba7f5f1f4de267113bdfcd7422822330b32d58e6777765f22a4116344eeb65b2ec516393ab3751b5356d2b0c9500c0f3e469181035bc3660f0b8f9fbe6e40b5e89cb79b063714ca75e72e2e100a10d63bf784df0820ef25f8ef40fe5f05fb95683f9105ff3cb2d219ab76605a064f69219f1dc0d00b3b48642f970dc00cca4b8b43308be18286ec5a4288d600a3785cb622d468a4c6969b3792f2485027d0ad9aa4a9c2cc972f9ee5190a95b1eb058dddd8c08e7d753f5e011b2bcfee1d52c1c4f2cacda30bdb6930653c0cc4486e60e89fa849b3", "5352", Some(1), 0x03, 1437866676382615, "982b6ff680fd2f1e14392b39b45b3c9b1bad0141901f1087648715f9fde6771c"),
+        sighash_vector("030000807082c4030187daa731f570a7a4060af0ce700d31bca7e74b3e3ba3d0e8a6392a062b8e86d9d7d00b21026553062e06b1013011ff08f083050009636a52635163006aac9abcef2a9908731900", "63", None, 0x01, 1993227025071196, "2b62ff0c8dec4df18b9956615b574dda3942fe452d9178b0bbb2eaee4de44a8c"),
+        sighash_vector("030000807082c403027c82977c0ff797093e2c1f3ae855f65aea91e1312fc6b8a4351a2ec03e02e5d02f53354b056a5352636a82cd1f55ebca57b6337c85938a79813d2021d6094c68b375e984f68393300871e348fc5236cca63305ac63655163418701ff0186d26fee28ca060001ac5aa727ab7985da0e00", "655351", None, 0x01, 573431699906543, "898742d2f2ca36aab2269e434974dc69bb0e314212e12b4078326f173f9b5b38"),
+        sighash_vector("030000807082c403029150c84bf890789942f55c200b773ecdd7992cff3eca24de3e0984e10e68ae387534b96c066a5251636a6a68787d370ca8c4c4074dc5d601ae90495437c3c2d48a3d966683ac05160b7a84eaa7aab74009e57a076a6a0052ac52000f829c5402cd11ea8eaf6d060007ac63656a516353dfbea7a8e6360200055100630051eab483f65b03f5000200000000000000000000000000000000a15232cfadec75ab1866e4c0155a9c74a7a57ccf34c483ac7da1588a1b6b9941f11040f94cf78fad89bf11fed69aa0d83105adacdd4e5f04a62424023c9b9e33c4fb7f12bdf21f07f265c537d51c6551f4617b915d21991839c3d0d36393d646e0a8a41509217d0e7d2ca1a0a0d677a3eaca23edeb07b74e652a0bc50c6c083a55d6c7306e74086f4768933aa24873681867a7893d77cb7f29b8c847c583f2d071a686616e206719f761ae39c110442e06163d2b84590360695d4e19849e634f24d9ad396c19ff83ce74f46e645f932e141a41195936c85d514414f112e60b1a2537c38d6dc6c4638305c9bd6c62e366bc63123e3e6dd36eedd3136fce8deeca2aa09a3298a39d83859efc9b2b69cf9a7dee08a98e4be558ac7912fdcb42209003420260f7cad0f2c01f2afe33073f26249d944f7a50dd84839bc3ea7fdee4ed71029cf07533d26e1e27a3efb032c3a3b34bd3092622d2062ae536ef5149c49b5bc90b5eafab6e675761008b0daddeecaa604470bbe0fada255d290e92b190c2c2d8c2dee5455d1fa9a9f3db7779b584643464aa8014ba66994de25517f83980e66ee4022314ae6dbef452d5d38b0a16f3991f36d8a8b39ddc0d5595eed98762878cdf3f022edc5cda77d5fe4faf63a15f568a540da57dd9beb6fb1a977ccb91b4d79cb39b02911a29e7bf028ac6103796dfb6b20967239ad373c38c53f6df1823d4950a0283039b9c06ab2966667c9df677716b0caded818df9e449c072e22f9d98bb0f9b03bd03d013fcef3ed6a49aeb98720254087ef728e31947ffe8f766e63ee46ff20816d5fa8ff55a26398961490ab9ae366fc5a2d1996ed693ccca82356f600ab099f6eca8bfe645270d3f95edba5b0de7a32819233bcc754a5ce2e5ea07842e5ff2cebe62ad76e8eff8d15ea4c24a5f207868319a5af6b035be3f44f434094f6e525be614dac920a330bdfb26d75fe7b4b365d094459250aaa5544489fb1d992581800a77b8912157fc9713aaac25b4c26eb03f716646619af02456ae695962fe5e931a63b5c79052ecd333e18412db91e15f7cbc70b4cd7e8e3c951f358572e37767e7d52704a6721b30efc41017ae4d231558c5c82cc7dd7e3356c09dc24906f0438dfcc300856ac2ced8f77fa8015736c661e80248aeeb774874aa79d290b8f5027a0a509537fc7c689b7ad86116cfec2647ccaae1c74b416f3e6ae8f7cc60eaaf7b6a590d51544138e1732945603a53462c60e1f6cb0c9ca0390c488224c313269fcd59fcb611fb2d9b4c8fa601bb1cb8d07d797bf5de52bceeb02301c8962ac1fc0491dc81affd6c1ebf89a13d6f290eda5d5cef382215c5e951d71305ef33d9737126d0e662905f1250926f6a229990e38f69ad9a9192b302f26bdda465d90b94b12c57fa3fd6930083f184438d8a889d3f5ecea2c6d23d6736f2a0f18e26f4fa45d1be8f3dc4a707137e95d2ad594f6c03d24923067ae47fd6425efb9c1d504e6fd5575340945601fe806f5756acb562f13c0ca1d803a195c2ebb2ef02ac33e6a88dea075ba996d3c336648e8694d3a19d3dca531beb50d4327c5c0c23cb7cfdb08ca7cf2cac6bc139d0741473d376029cb4ab6bf054557ce294c728a4687d57ec8909ff51a4d02f9dcd11193d7d1c9fdae6a17396a1bf57a994934f5e7a59f045debeaff62ef326b947f2a8b49555e4d99b3bf5c81ff9fe314e047af152508f57015ca402c67d925c99acea3ee8cc4b008c5cb43966e714ef480fd05e07c7b2dda9aa3966113eaa293d3f622b309d64803ce1e6378b6aac4fab527c43cd45ed0a3c1a4b9fb18dcccfcdb6ac0c2421639cda0075a20dc5111b8d3d3199495bd9133dbab94541410e4fba92c7b606a5cb122f140cf1a3596f2788f3c8b92660f14cb65af5dd23dfdbac1371ecf4b33712fed2292c44f70834cf96c05d58827e69bfc2e696fa0874869c02f3dca11c3b90cb214e68bc1cae039d7a146cdc1d609d7a6b3fd5d461b0951c82cfb3e763fad2d1bc7678cdf82779f8fd5a1ce22a8d3c4547abd959838a46fb80afe01f8ecc9931513b1962ec540856cb189387cfbfcc0f7c68223cba47fb0c9b486e4d99171941f7675a8b46328a3bc109bf07c66d5ede771cc4c74ce80333829191eedc493508a644530a6144f22dcf97525a4cdca1ad71073b080b73ea4549f5401bff4318268e6ad637363157a19a53f123a0b0e16d0b77f02028da464100fde76d83dd0bb224f7b57a00c02f68ae648fdc529957a10490dce1fddbb0904f0d518bb387544019983b616975a78e74d854fddc49b255167b55ef4bee465668b20ea4118ca569ae480e0f6e5e043a357b36d3ab36c861f2278301dce57674d5073b3a6f5103a0793af1b7d46f957e22d8d2583bf181836c3be9930bac8fa460e968aa7109870bbed17df5f888c8ca1467ae17dbbcde31c1105cb5bda88ac6c627002ce21c02140ffe81ec58bf1e6d1bb7aaada41fba0bb588778a7f65202ad811ea73d26c7455030000000000000000000000000000000095aff75325107c9b3f9ae9dcdcd86ed081a2e7424719a3d185b7e0a43a472e298ac0afdc5287d7ad124cd9405a62cd1ca08b282efef7f928df76e2821a418413eb7ceaa5ff1290b03ec91ce6dd28130c3ab0b23b602bd5be5dc26003aae04b33d7bd2590e90c8c388ea7955122dbaca67b30395a928b57b8575123205ae19152e41e002931b45746198e5dd9571a56a7e0d423ff27989d3eb417ecd3c3093fb82c5658e29624c53219a60cd0a8c4da367e29a71779a73032985a3d1fd03dd4d06e05566f3b84367cf0faee9bc3bd7a3a606a9fdb849c5d82d0a61923c2e5d8aa63a8a50c38bd038772c4143d8b7acfd74e72c04d89248dff20fe8dc5ec2149054ea24164e85f6744ad0cacf1a8b70126f482c092ed9f6127d2050d12e878a79603a1e84daec3ebe62d5f6c4abe5ce90a7fe2e52a8d7846e8edf2f2bce05a037c820322caad1261467dcfb7d6b6133dc21e8096c7e9f8e9e10c1e3fac4058b682c68e0afacae0f9c2dd4d64d9046152b4762332939f17e6aaf7d8b9d358e2218d4e0d69a4f119e1c64eec4c8b532809707131f01f55c7ad04cfb63f7c4a3d0a2b0ffb0b03a6be055b8c94ca80bb0a1d13cd4cd69ab98304ae2515d5f7699d4abee5c20be603d873511012f234bd85a7eff5fb634cff2658ba6516048563095ecefb3015ee3f03ca52a177f261ecdc26bc089d34c6404846e9c647fcfe98cc6acdbb464f64278a02ce9d1ae0d415bc0c05245fddaf4ebc8dc703a85cb270f796ad2d937e2ac0d5e0034821758000aa59c9d4652485294ee0ab29696b21430fa54dcfbf2b9c49d142064209eeeed4d471ffc017d4e20a796b0927804c061b9f4a7091fe015ada68fd8442e01825c88dfe55cf5de38936f7ce25311b902ba97a3c12a95cfa1c3a591b818f60832709d9e4839e410fb36b84f3ac4f070fc35e161978259e5b8edc744d90919aa770bb36215128e582b59641e23852e958eb8fc3c0aa96152ba4f77f138d6a6712a3ae3226015883f81db23e583c869c4c71143a6fffd65e8dfdc50c99a2f1f314cdcc71359e235f1d7dc2b5f38ef7b970843163c03f9dd40a8015efdc8791956a3f3cedd9ea64f8efa7a0815a70381d71467817bd04ca529aede07ff60d176aed0f855a2eaea89eaeaca89358c081826a0812a5bca28be1373f086dbdba7e43e203212c9fed21474ba19a055ffcc179412e893a744832298c5fe24cc6b18667f49b34dfb12379267419a9cb9403d8167d8d1e91d2811a043b29243b069b37587847dc6fcddb1831bd1cc2567ca033ac40f74ab6955f683b12e4e8254e4ea760d38b3f46791c5c4cb12bc7ccb0ed1865f25d601c303f81fb1fa1db48533d3d6b288e4d9a4dff8ec21c96f578399710c825fe7e32f93a8c0743f9ebd54cc151c7610337aebf7e9b915720a54351d49ab8c22fa34998dcf583d4387361ef3ff86f50ec53f49249e4ad349603066fc9c661d69f911dfa7241c8d5792d43c457d5de96523a53d667ec5c4ef9d502a16f1522475896d79bc57833e977171c324dce2a1ea1e4304f49e43ae065e3fb196f76d9b879c7200862ead18dea5fb6a17acea33386eb4ca1b51486a9148fbdf9a95332aa605c5d5483ce4ba8ece01a8ff2b7ef82d05c0b6e861b915f13ca0eb3ea13d5070807a2cb6680a249ea9c7224392cbc8ab82501b26f112ac789a12a31ad1314e2ede08fad3143af30c27f403bc866c755177852afd0abb90ade1d682726f42008b46ad7f8abdb18117f72641390f086b6e1498be69548527e6ada2b38b9fe121ef670af7437d32536d5cf5c4ab19dd99771582d038104b7e039a376f7acbbeadb34f945beb9d7ca0e4e3d5c5e4eb1d8526ebd13dacb1ba35735c6d04a4555acf4bf117626500d77b38189dd4888041225acbe3874a4c0f607fe6745f9355b3fa188f1d65c09f389af1b9d6232aa79447919c550f6f31fec35481cb922de2db5b4da2f81948617028e321706a3a778c1938c443bb00e5b0ff06ad8ab9b1ab0c11477673f85df9561dbea45d5f9781ebe317a0710ae5461e34fe6f1b1aa9b4e67b14910984802c2a7e38193bc7bdc8ba3e4e3d1d933bfb580f5b3e87a2a06517051410fe1b4ff1ea0ade824f338515456a57c7a916a74388ee8f1281f9ade0ae2a2613a0612c469df792b8df4cae4fc25c1cadba95a807ce61e5a5303faaf9e14653996b5a8adc34fd475ef1499094babaf1f3f07da9a390b1d9fc9a08327987adfe9564863fbdfa8f6b46a8841583099afb7870118face76347e40b6fd8cd15582ae8e23be9a0219bc3e4e4546a30d3bbbbd1686086876be0e4c859be71fb58f4fab3d28c0b4f7e75ad1edb7f88946fb40cfa5786a0fcba1303c8347ecee93d46d140bb5f69531d666548b109ce764bead7c87bd4c876494de82db6e5073a6c94f7c099a40d7a31c4a04b69c9fccf3c7dd56f5544776c53b4df7953981d55a96a6dcff9904a90842e5bafec8840c2d255bf5ad61c460f98feb82a10fa1c099f62776798236c5ca7f1e46ebdb2b144d8713e56c772f2c3b860ea5b03a8854bc6e6590d63cc0ea54f10b73ba241bf74b635551a2aaca9687ac5269fd368b26d70a737f267685998a3f7d2637914909c746495d24c498635ef97ac66a400894c09f", "00656a6a53", Some(0), 0x82, 985279270393606, "ee92a7a6d46a564a356af23190c86018f95357a913e44cbc4557233296f805b2"),
+        sighash_vector("030000807082c4030165c29936e7fa48d77e89ee0962f58c051d11d055fce204a562de68088a1b2648b8174cbc065353ac006a515afde184020bc7c892cc74030004635352653164584e3879030002535145e8eb2213d4df02020000000000000000000000000000000050e4d5217c6617008c78f4fb1112f4028a704fc5a9382c6b03e7d8085e906cf84ca2c1207c87a2bce2080a9891668d69b044beced6cda32c229c9117917aa07ddffcd377395cba616d63c0b69c01fcc45391fd5b8763fb96d7ca333a12de3cefa91c6c98f9473b8e104a71293e46374705baf65fa41384ba5c8e0c88a3eb07e0be34daddfabb7b65543b5f39cb2023d46789eb7d989af779e5b8d28385a85b0da2abe07f0c2bb4255fcea03188527a307d409159e90166fac6a070ba05b3e4dbfd3a2bfcc9ee6ed016c0f665be8133b7dc1d86044db0f9db40fb0e9f8bc2e4db5382a8b4f815b4e8434ad0dfbc51a5e9b145e1596cbf4670b7e05dfdafbb0cf3ddee28d76a82428e8aba4364e84bac379298df2932e69bb5d045516efc33ae6c03947ceb09ed371667212a831b5485eafce8488188ea4e27d0cdf7ddd348abff77034a13bbc716b6a5944ee727965690e209b49eb962c039975f939ed5c6e4c400d80b759433d3ad716da0cb446113c7727a64b58c3f8a0f81189f98005233a81366aee73cec85228ebcfd5ee3c3fb44db76ba243f2842b7b5fc746ae51b0bc4bd4fc903833565ea852b92b224f6990318ad8c7d9437e20e2a1f20e818f9057c5abaaa2e0215b94945cd424c28a5fa385dadfe4907b274d842707db3697a5ae6c8f542e5ec027fe47350d1014670212efe81fb7c73e8450df814ef6232f7490f63ccf07480f802a66eaffc28fea448d7b401cdae10e7c0c7f9a7b15331969fc8cb36396773de190331c750f6ce5caaf29768ebb27dacc738056a8125b4772bf87ae10a8a309b9bd655043cfc3159494368c5ab8cadb7f671e9626bd263e31181a604b506a03b439a7ffe4355892477e2bdf338c62c3922f7d3c9a56c7103d911948a84b5ae2dbb16a3761add053a0f967e6b5bc94211b6547153267c6ee1cad0d974a71088583735e4f63d33156dadd54c2faf89114a127b97b94cc2a22ef303f459d04fc0b53ace5918d47ff33a558bd71a75f355fbd06bbccf4e02c3c0a4b63d0cc949801d63a64cb2d32373b2c7b274ab2db4682142c8b21d84c481f5ef21e4b5e3603451bf94774d0ef47f63fa6abb78d21c193cbe65b695fe67423c1e2d312e2776fa24ece84683e74876c55ea0369e4ea0e86494e00dde236a1689731f0a5d8203afde5c423640b81e4f631c981c11a2e1d184c67c528df92d53aec44a40a4ea2a131b4733cfe45c6b0012c3e9e20975baaecb0232df880bd7d1de13e1349462ec8d5df3e780ffa72eba8a8df7fcf398ec230513ca9d6123f8b9d8178560daf975111955a2bca3423eeefc527be3a8543eb90a5ec02f35a7c64b7dd59a72da0074634e01d2abf3637add77c7350f12b011b294168ec75576e47d169e3938bf6ae2aa8ff7cfba7cacb1f92b6e4c2497bffa9f17cad242fa9c3179c1a3aa81f7361649572c715c25a1f6cd5ace82c00ab2342b9c3cb4fffdda160ca5ab9e9baf2139ef9afbe1b1f309462afce462a79bb9698e22c957c590a753a76b87e009121e06f6a1bf62a08bf435d92e2fffe86e2a9cbba9133a68e4aebf33c38436f2545fc2d52832d165af415b244adc5f57377deedf460aa3beb43419c6b082e835ce84ca13b6908a8813c021de9fa9a44e4c18dcb3d21faabdb41931b2fd497644dc3a1507fa5ac7c76beebbdbd1d49299a55bd49927e9d7f4884e6ed3fd5e4b7cb835b83308964e3c46873fd613317b91d29236ea90e365d162cc051c846d242176daf6d28618ae31fbaae999a93f175c6938e631a081f2c1f3fd782549d3f3245759606d9f92d5548acfeadbaf9caa6b93dc08828d74f6d5fdd83331f0969145955297e69f00fd2987f2da2b94b995fecbe622a735ef7f1207f671629489202bea0b475e51681aa16778b39bd923c98dc6ff8373c79bb17030417bc200c8f0b855acfec179f7674cec2721a10fca693d83cfe5b8cdcc18f81ad617fa26f0dfb83655b8a29a7f834232425e8c474588f18dd326aa396c3e4775e00205fc9e45f7b7d2e6d55dcb90e23ff6b508459aa699bfcbd56f10997764d087408986e73d6e284fea9a23c39311782f86cabff9455e4cf699e5f5d4bc0b3905a4e3bd01c54df8643443be0f889032ea325bf07107fd41d673eebae6fa637b70cc0ed3f00958dfb8dcf00e85a1d0a6a8908140c2f434c2e260efb0bca2003504c99993a9e1c0ff9cefe6a665d791428690e47ef8c131a8e9bfb4c3080235032d731b0d3841225f1c11e2c28ee84d35f9226100565972eb269d278ef64979bf6515ed4a6840b0883a9e6ef64a0efcae1cf21dfe74854e84c2749fac03825275c9b6302184c72df4c4bb2862e4e8a7d9a4a282866f9a7b2cfc9a56313da0c47a34b7b9cda3ace8185f07df36e448a76aa477f224d87a074f43af5d5f79b3ab1128f08191447fa646bfdde5b51e233ca6155d101585bc2c40158ac2106e66a26e46423370636876b434a74f8ce8060050b082a79b61bb5d344eb5a1158326ced900000000000000000000000000000000a9d9f54fb2fe8f9f05cd111ee46c4710f6f63a62694557ef1b12c88006b67872505f4e883b585907929a2f3fdb0d8f7914c42dde2d2000f5ae02d41821c8e1ee0138ebcb728d7c6c3c80027e437594c670fd6f3908222ee7a1b917f8271abe660e39e051aaa6fca1862276e2baa0fe0b162aebcfe3d9349c8d154bb7ee28212c1baa705d82070d7032f2695d1796809fab41246926af992b6eee95a9a06bc4562c5f2f1b19549500372e7ad579a6d6d78b33153130fb448fb79e8a669db8a0f35cdf9ae5d32d732fc79418e23b451ddc95a22ababb056ec6b5e8ba4f524dfafe875262dd7be41cbbc62420d4ad6df5c9b713604f656088a4485e93be1907d27ac6ec3c57259bd6981d42c1b78a29ad9685e63c494d4129623ea1a7ffec85fa29031073edb2978ef4e469ddd5cda986189995f88d6ab366db019001f5b25288cf8603d998ee573c8cc48aa9efcf9b617e043c329cd1aa1a0ed3a402fb96e336c719e60b3cb691aa0db52736626ed1978875888ec76c846bc227272a585317dff0b1148d92d6f5fb7d95336770a7d16fac1add860776cb480221f8fb33d7e4e9b07902d20386fdac72096234aed48de892ff7355073bbf0615f67b1100cc2ea3ba3d6c1a1a0287b119baeebfa62bc9f0ec479d99c1a3b158b514d1629db3993f11672a26708e02d816b547ab7e827d071ba7842b3e90305383896ec4905f70c78b694e6a5a3e4302cd8208132b840f05c714523ca819720ae227fd1acba714fa4fc45fc5398857b403c14879856f354ba4d2581d0cda54b638ba9d76f9b52d17c8f88ee63f5845b5dcefa4c3479bce9acad18b4aeae03c0eae225d42848bdeaa536d7d8dd3bc979f06586673bc6ff1c5d3b320f349a5b3a8b355592296aaf61c5b7252f73ec0a9466a1b85764fb0831b4a1a36890e224c01acfce48ee3ed93877398e0726d02936d0d032e18e3288b2670e1362c32d6e4733b9dd2d5f26e1fe306f73c007fddcae9d9c0aaf187d7428b1e9d479c18237b9828bca8b98c9d9bec7d8270b5d8eec3cc4f43fa0188521bc61b21dd04e37a83ece68ca7a2fa6c8f9e34a6290335aa1fbd83d54aaf441e319ea47a862ad0293cedf5dd9edadeee33cb522cd0118bbd811ace9a23bda39aba72f1566fc1688497d2a7928c36701525678bc97214b31b37bab46b88f27f0448decb31622d0f0f87a855ba54000332031f73abffd46591da0b88723504edb2337230dad2acc0d8bb68bc837a2ff930bff06fde74eb90aae4f60dbb6eb827ea99884acd6285a98892802cf59d5d60d01663387b3ed2723bd6489e9c2c106d4aa2de23ced16c720429c7753a7738ec7d9db8624229edd217b80d74875a14cae4863f139e9c0b131b2a4c28071a38ec61f66801aa5956fcb2a46b9587665b7571aa03481fd8d9d5698f836fc8635e69e3bde42f4ac071328b5409f6e42d790aedd73bc1a2354723b3b819d0637a6fa4663946a30ac5afdd30ce830f6791b4575270a1720f91866e2b86f4788894c8da62d8b91faf520e3bedbc1206a5a5e6efd3dfde0843c3b06757643fc006008838ca473087f8977918cc1b81c9e68e3b888fe6f7c630f1bc7ae188f512842041cada1e05f866d2562dbe09c4b43068f754dad34df0fcfc181f31801a7992d2f16be0211b4a22f62aab64701bf4a4e6d666fc304a5c79c609acc43b00b4864893d37d5007f0c329a4755052577570dd38fac043cd91c12ee34e9cfae392a78bdabd4ee31dc0deb02fe7b1d8b0178ac9513105fcc7e30ba8e016aa36a6b5df5e5a1909f63aba095d9877a8f2dc53f46f6c9b07addf146f4ffa501f9dd3cff924e3010faf504e2b8aca7357acbffec73ac34c1a73160f2cea1e0510f84d2fe2f73b6e921907a1b7b3751213241b2cfaa55a5ea4dd517e7b49d2de8c090843730d2408a2a304aa1e2e1370a6bf6c2bc73ff00d893bc1285efca82599d181f12351f939a94ea8b975c065a91ff257cac7a92385fc8fa921b106ba8660c60ac8ba5ece45606f04f36a3a90bb3838c42abf62dd2d84babef3e188e9171aff9bc116669009d887130ac9f7396a627a8474c1811b696f99552b14c484dfe42c24d57c3a9c3fea1376cdcb63421c314a622a9aef0bc057cb11bc5e3066e33a3b9b31df2575cd5185a4f3fc4e4c3d402ed42046f81f974816d279b1513ab81d3f0a3c7f7fcf2fbb4e26321993a513ad3d7f4afe6c1bbdc657585080bb5a0f25973d63eb20ada0166bbd8a39ff93246f2789732ad05587f8db7bc87c242cfd36ce685a4b656986c39fd7fcb23c91913e4611191edcc88b78f145ea29d271b940c69941e4c3fd2d71f3b190690ee16f5d14ac2224e6fc89597654527dabe72e75d2d2a13a9fbaa6378e8a264321087a1900efe3cad14a579686aa3636bd375bd3136bee0bdaabcfac881bc701812721e6fb75aa072d2d187e62258d65a192157cdf2ec321407f682f5eec6a3297ab20b7061c62245716a44f71fbfc34c79b44e09e4212ac2653f6c403643e1c5b9ad134d89c680b707283af54326fc4f84d6a5829a0ad4830806c05758492cd6ac46ba01a2b3722b5e4cdafbb3f36785f424af044dac5db5f7df839eb63c0c17d8b0c79db8630942015be13f79af6f43e5ab0778114798f44", "0065ac53", None, 0x01, 1300854057285044, "d0284ded56920e5847be0ae48276840d820966cbaded4a53bee8d74765857295"),
+        sighash_vector("030000807082c4030120177366f424b0a54b0b60f4fb1358c20aa41dc502e1dd8a1633f3d8e3276b59e7d2c4e60665636563ac6a247e36480013b20442220894110100000000000000000000000000000000eba02fc4142b4297ebb5683db8d24319706ad26aafd81c53b740f34543a6b3e9f5bb7d5c49e8c37f614921254f3212394c797d1cee7899b7b4b65b59b7342f92531c1d59e17970b7317414438cd80bd0f9a67c9b9e552f013c115a954f35e0616c68d43163d334dac3827033e5ad8488bfd9c4bbbe8f5935c6c5ea04c3ad49c747a9e7231bcd7d16215e6e80737d6b54fec8b88402f0475245e174a745b831f8fe03a76fb9ceca4d22b783c328c6915c43405064ae56bc89e64d1578e4d3a34bb95591eaf1d3da02a4549fa80db0ff7cb03993b68ae15a30e87949aa080e94abde68898c3392a217d649616bbe739b13d14df03ff27671489be0b4bebaafa7d1e639d5b3e994ffb6b7a209f6adfe8d1e5ccf010c19168aeb18aa9d687e24adc003135c70c970e0903af6e17081d5818e88b14e4f601b8c063e3f4387ffa2322a5103909f0980d689de7f8e6a5c62a777d175002a137de85b8888929198117aa5d6190be1dcf75876dca609f9d28471f997fa11f99d423f9cf1734be8a5ff997d451eb3cf4b3dfdd9d4545c35b2b5a7dc17a836b12b43befc0be0a1bd369772338078b4037d8e2d979a3441e1c8f5afe47b1e7da56cf00602d01b110c05cf48fda3e6cce302044000f45c6d1e696d245cbd312bdc3a3a21c992d0ebc8cc8fa6306d7e130a2b022018fe596949fd82267bcc59dd4626efc3ea7438d05c91b0f8e092550d2d39a002b45ee8f7d09b038d8383e19bc30e6403828cdb652a556b12040931402aa6ac340219fdc06e2e7787f5b77b045fd098c031bdbd46277609d842f48424eda31e3cf2cdd64385bad3118858d142d906eadb7590c94136da6a063514d6a25f7b37d7664f9b9709433e6e702118a4ab9e7a7a3e6259129937d29d0db26070523e8b0643130abefe943b401298ae01a3ab00abbc60d7db933c7f07a8bf0f7ce1660bccb45e042b451b935002cece27f36aba5647ac28d8186cdd1fb95dc135d48992f68da12ad61ac756680dd7f8d0774abd6cfda2f032af3be139a633d6733c75d1aba89018c8572b99cd30c537067941df1c4bc1fd570f7b4ddc97518623e3ae4a87bdb966c94d861e80de88c292aee9387194e256c6700752301c73fc9565a40480d8126e9d085879e24b16e9c485d8f0d618ca0dd121b51a7cab230c5b45672bdb8ea3a040f7aaa098ba26025d2eab7948693dd5f6d3096501e9e07125d7eb293b3abad57fd5f01164702dae64bdba8c924fb0799679d77f98d303919fb4a7ff26a96f137a5e5cb95bc4c6ff9993526bda1503168ab48cbd45153927d30430423dbdf06605f5b54b808feb22b208b064581847b2f64ca64837007216de6ecaffeb4b69e63347f84abcad8f2e757d5861ce77ee46513da7416837dcb23d33ea72af23d0ad8c9307d0b5858da95b77fff9027b8859e11dcbd598350eee50939481708ea708eb9f664388b9c64d6af0f96690342400348e929e07460253f38390f87bd6c05308c3bde25228e0fa0880b08ef34a5a9cc0ea0a67ca65b6ffd005572909f1c42dd745eeee9dd6b4439c9f3f98a118fe16698e9ceff558f16066975fe39583e9b5853b1311391580019fe55d59d1c828d3feb6a3b9ce92d089ae4b408e23d6a437d4989b519b7a9eb08ae6d448a7a16e8aed26a2ecd0cad80844fd0650d8c4e4d2af90656748d8099a0c756fc16cca06a334430702ae1961665b4845acd1a8e34101e68bb644ac034dc63e6e344c3d63762a7a5bf59f13095410981d6b6b16bcd4c9fa68af6e5365e94ecbe7ab8b8043dfbacb23c84d71a8fe5d9ac5502ce9f73f408e14376db86ef57cc37d09896fa906972e557180a4ab5ad09d8846dd6da748765436e0160240f8d41c0ac783f939f2d0ed262ce859c131ebc93ff2e6e407d4e243e1e931d53a4543b6e26d82596fc53b52312c776d12eb2b659b4fb098df87d683cf9e5412ee56c3fe9841d73fd070dfa51f5bafedf206f13c524e5c50cac9906efa393290042e3bc59f960b7d240ae443fc49269ce00061e65c6d74812a30dd5f5fe74eff61e0cbab3cec75d0aef95083189452dd3d9edf4487bc734c8b24f21296e4e9ef117d7fb977e3b0e6406e63085906331a93033d1cb8360fe6fea61a6826df36255789f92e40bafcb2ebcb9e556f6c0ccadc6af08e31ec4ad5288034e16d155cfdcada7bab599c2fa4ad2e6293f9fe097169148276b6a9eaa72f148b0c9565c3c2dd63125e0fa530861a710df8e481f2712920f8787e0aedfe618aff50a3b56213884d6262c11debf2ba7e8ad6692cb17078331418da4be064ff5270073934abcd2ab0469ecaf7275b4bd72bc6ed34478ea4089b736a16dd906d49f25c33827c571ce0b5d72177aa3508804bc0f8faa947122231402d2f5cc9a0eb0e09d427b427288d937d9d72b77456f886594cd8c6a462f77fd83076469cc0ecba3cc40cad69e5b54112eab33396aecfbc211f1f79cf33108e93d95378bae6958274b31088fbd8b3a3a0d154a789735b0349c4d51c889d08952ddd5488be95560594e673fa051bf9b614a15e100b60a0fe9a7e12a9b256df589b3e48e5b80fb8cff03e86f60cc070fb23c97d4c14fa3a7346ff556bc6855a5f83e3dcd9f6eab3dabcd47750e34e7c0938f64d451e3950", "006a", None, 0x01, 634714385711621, "498bb78737b3b0cc06f5cf7bf073d91a86b34882c9f02e13e6348defc589a424"),
+    ];
+
+    // The full ZIP-243 test vector set, from `zip_0243.py` in
+    // https://github.com/zcash-hackworks/zcash-test-vectors
+    //
+    // The first two vectors are ZIP-243's test vectors 1 and 2.
+    pub static ref ZIP243_SIGHASHES: Vec<SigHashVector> = vec![
+        sighash_vector("0400008085202f890002e7719811893e0000095200ac6551ac636565b2835a0805750200025151481cdd86b3cc4318442117623ceb0500031b3d1a027c2c40590958b7eb13d742a997738c46a458965baf276ba92f272c721fe01f7e9c8e36d6a5e29d4e30a73594bf5098421c69378af1e40f64e125946f62c2fa7b2fecbcb64b6968912a6381ce3dc166d56a1d62f5a8d7551db5fd931325c9a138f49b1a537edcf04be34a9851a7af9db6990ed83dd64af3597c04323ea51b0052ad8084a8b9da948d320dadd64f5431e61ddf658d24ae67c22c8d1309131fc00fe7f235734276d38d47f1e191e00c7a1d48af046827591e9733a97fa6b679f3dc601d008285edcbdae69ce8fc1be4aac00ff2711ebd931de518856878f73476f21a482ec9378365c8f7393c94e2885315eb4671098b79535e790fe53e29fef2b3766697ac32b4f473f468a008e72389fc03880d780cb07fcfaabe3f1a84b27db59a4a153d882d2b2103596555ed9494c6ac893c49723833ec8926c1039586a7afcf4a0d9c731e985d99589c8bb838e8aaf745533ed9e8ae3a1cd074a51a20da8aba18d1dbebbc862ded42435e92476930d069896cff30eb414f727b895a4b7be1769367e1fe8ad18de11e58d88a0ad5511d3525122b7b0a6f25d28b16457e745939ffedbd12863ce71a02af117d417adb3d15cc54dcb1fce467500c6b8fb86b12b56da9c382857deecc40a98d5f2935395ee4762dd21afdbb5d47fa9a6dd984d567db2857b927b7fae2db587105415d4642789d38f50b8dbcc129cab3d17d19f3355bcf73cecb8cb8a5da01307152f13936a270572670dc82d39026c6cb4cd4b0f7f5aa2a4f5a5341ec5dd715406f2fdd2afa733f5f641c8c21862a1bafce2609d9eecfa158cfb5cd79f88008e315dc7d8388e76c1782fd2795d18a763624c25fa959cc97489ce75745824b77868c53239cfbdf73caec65604037314faaceb56218c6bd30f8374ac13386793f21a9fb80ad03bc0cda4a44946c00e1b1a1df0e5b87b5bece477a709649e950060591394812951e1fe3895b8cc3d14d2cf6556df6ed4b4ddd3d9a69f53357d7767f4f5ccbdbc596631277f8fecd08cb056b95e3025b9792fff7f244fc716269b926d62e9596fa825c6bf21aff9e68625a6b4cbc4b700a364fa76bd8298bc3ec608d4cf7f3566658d5588714ec9448b0f0396128aef884a646114c9f1a6df56319033c3199cc7a09e9e9567482c92695390229407bbc48985675e3f874a4533f1d63a84dfa3e0f460fe2f57e34fbc75423b6883a50a0d470190dfba10a857f82842d3825b3d6da0573d316eb160dc0b716c48fbd467f75b780149ae8808f4e68f50c0536acddf6f1aeab016b6bc1ec144b4e553acfd670f77e755fc88e0677e31ba459b44e307768958fe3789d41c2b1ff434cb30e15914f01bc6bc2307b488d2556d7b7380ea4ffd712f6b02fe806b94569cd4059f396bf29b99d0a40e5e1711ca944f72d436a102fca4b97693da0b086fe9d2e7162470d02e0f05d4bec9512bfb3f38327296efaa74328b118c27402c70c3a90b49ad4bbc68e37c0aa7d9b3fe17799d73b841e751713a02943905aae0803fd69442eb7681ec2a05600054e92eed555028f21b6a155268a2dd6640a69301a52a38d4d9f9f957ae35af7167118141ce4c9be0a6a492fe79f1581a155fa3a034999c538f7a758bb5b1d28fd218fba1938744bdb77b4a4dfa7a5fae96e8cd49b26907dfc6685c5c99b7141ac626ab4761fd3f41e728e1a28f89db89ffdeca364e4b22d81d9968d0119e4c7a189adf22ad96830a54e40dc73eaba6b2aaf14f7ca942e7370b247c046f8e75ef8e3f8bd821cf577491864e20e6d08fd2e32b555c92c661f19588b72a89599710a88061253ca285b6304b37da2b5294f5cb354a894322848ccbdc7c2545b7da568afac87ffa005c312241c2d57f4b45d6419f0d2e2c5af33ae243785b325cdab95404fc7aed70525cddb41872cfcc214b13232edc78609753dbff930eb0dc156612b9cb434bc4b693392deb87c530435312edcedc6a961133338d786c4a3e103f60110a16b1337129704bf4754ff6ba9fbe65951e610620f71cda8fc877625f2c5bb04cbe1228b1e886f4050afd8fe94e97d2e9e85c6bb748c0042d3249abb1342bb0eebf62058bf3de080d94611a3750915b5dc6c0b3899d41222bace760ee9c8818ded599e34c56d7372af1eb86852f2a732104bdb750739de6c2c6e0f9eb7cb17f1942bfc9f4fd6ebb6b4cdd4da2bca26fac4578e9f543405acc7d86ff59158bd0cba3aef6f4a8472d144d99f8b8d1dedaa9077d4f01d4bb27bbe31d88fbefac3dcd4797563a26b1d61fcd9a464ab21ed550fe6fa09695ba0b2f10eea6468cc6e20a66f826e3d14c5006f0563887f5e1289be1b2004caca8d3f34d6e84bf59c1e04619a7c23a996941d889e4622a9b9b1d59d5e319094318cd405ba27b7e2c084762d31453ec4549a4d97729d033460fcf89d6494f2ffd789e98082ea5ce9534b3acd60fe49e37e4f666931677319ed89f85588741b3128901a93bd78e4be0225a9e2692c77c969ed0176bdf9555948cbd5a332d045de6ba6bf4490adfe7444cd467a09075417fcc0062e49f008c51ad4227439c1b4476ccd8e97862dab7be1e8d399c05ef27c6e22ee273e15786e394c8f1be31682a30147963ac8da8d41d804258426a3f70289b8ad19d8de13be4eebe3bd4c8a6f55d6e0c373d456851879f5fbc282db9e134806bff71e11bc33ab75dd6ca067fb73a043b646a7cf39cab4928386786d2f24141ee120fdc34d6764eafc66880ee0204f53cc1167ed20b43a52dea3ca7cff8ef35cd8e6d7c111a68ef44bcd0c1513ad47ca61c659cc5d325b440f6b9f59aff66879bb6688fd2859362b182f207b3175961f6411a493bffd048e7d0d87d82fe6f990a2b0a25f5aa0111a6e68f37bf6f3ac2d26b84686e569d58d99c1383597fad81193c4c1b16e6a90e2d507cdfe6fbdaa86163e9cf5de3100fbca7e8da047b09079362d7792deb3ca9dc1561b87c82e3cb99eb5837319582216a3226774efa90efb7bfc79f425644e4e98c2d7d8642b9db82aa739bf2d71cc4117227db227cf0a05ad9a95832e23c94f271ca0e4694fac6322282ebac6986b8fdc8ad863084ff10fd11e6a13311fb799c79c641d9da43b33e7ad012e28255398789262275f1175be8462c01491c4d842406d0ec4282c9526174a09878fe8fdde33a29604e5e5e7b2a025d6650b97dbb52befb59b1d30a57433b0a351474444099daa371046613260cf3354cfcdada663ece824ffd7e44393886a86165ddddf2b4c41773554c86995269408b11e6737a4c447586f69173446d8e48bf84cbc000a807899973eb93c5e819aad669413f8387933ad1584aa35e43f4ecd1e2d0407c0b1b89920ffdfdb9bea51ac95b557af71b89f903f5d9848f14fcbeb1837570f544d6359eb23faf38a0822da36ce426c4a2fbeffeb0a8a2e297a9d19ba15024590e3329d9fa9261f9938a4032dd34606c9cf9f3dd33e576f05cd1dd6811c6298757d77d9e810abdb226afcaa4346a6560f8932b3181fd355d5d391976183f8d99388839632d6354f666d09d3e5629ea19737388613d38a34fd0f6e50ee5a0cc9677177f50028c141378187bd2819403fc534f80076e9380cb4964d3b6b45819d3b8e9caf54f051852d671bf8c1ffde2d1510756418cb4810936aa57e6965d6fb656a760b7f19adf96c173488552193b147ee58858033dac7cd0eb204c06490bbdedf5f7571acb2ebe76acef3f2a01ee987486dfe6c3f0a5e234c127258f97a28fb5d164a8176be946b8097d0e317287f33bf9c16f9a545409ce29b1f4273725fc0df02a04ebae178b3414fb0a82d50deb09fcf4e6ee9d180ff4f56ff3bc1d3601fc2dc90d814c3256f4967d3a8d64c83fea339c51f5a8e5801fbb97835581b602465dee04b5922c2761b54245bec0c9eef2db97d22b2b3556cc969fbb13d06509765a52b3fac54b93f421bf08e18d52ddd52cc1c8ca8adfaccab7e5cc2f4573fbbf8239bb0b8aedbf8dad16282da5c9125dba1c059d0df8abf621078f02d6c4bc86d40845ac1d59710c45f07d585eb48b32fc0167ba256e73ca3b9311c62d109497957d8dbe10aa3e866b40c0baa2bc492c19ad1e6372d9622bf163fbffeaeee796a3cd9b6fbbfa4d792f34d7fd6e763cd5859dd26833d21d9bc5452bd19515dff9f4995b35bc0c1f876e6ad11f2452dc9ae85aec01fc56f8cbfda75a7727b75ebbd6bbffb43b63a3b1b671e40feb0db002974a3c3b1a788567231bf6399ff89236981149d423802d2341a3bedb9ddcbac1fe7b6435e1479c72e7089b51bfe2ff345857da9b545e88e3221f3f5f72d1e069c9a85dd2236d390989587be005cda16af4408f3ab06a916eeeb9c9594b70424a4c1d171295b6763b22f4712ba7beff0ff27883afaff26034b895735709cf937bd2231891e70eb2771e9927c97f8764eb48e911d428ec8d861b708e8298acb62155145155ae95f0a1d1501034753146e22d05f586d7f6b4fe12dad9a17f5db70b1db96b8d9a83edadc966c8a5466b61fc998c31f1070d9a5c9a6d268d304fe6b8fd3b4010348611abdcbd49fe4f85b623c7828c71382e1034ea67bc8ae97404b0c50b2a04f559e49950afcb0ef462a2ae024b0f0224dfd73684b88c7fbe92d02b68f759c4752663cd7b97a14943649305521326bde085630864629291bae25ff8822a14c4b666a9259ad0dc42a8290ac7bc7f53a16f379f758e5de750f04fd7cad47701c8597f97888bea6fa0bf2999956fbfd0ee68ec36e4688809ae231eb8bc4369f5fe1573f57e099d9c09901bf39caac48dc11956a8ae905ead86954547c448ae43d315e669c4242da565938f417bf43ce7b2b30b1cd4018388e1a910f0fc41fb0877a5925e466819d375b0a912d4fe843b76ef6f223f0f7c894f38f7ab780dfd75f669c8c06cffa43eb47565a50e3b1fa45ad61ce9a1c4727b7aaa53562f523e73952bbf33d8a4104078ade3eaaa49699a69fdf1c5ac7732146ee5e1d6b6ca9b9180f964cc9d0878ae1373524d7d510e58227df6de9d30d271867640177b0f1856e28d5c8afb095ef6184fed651589022eeaea4c0ce1fa6f085092b04979489172b3ef8194a798df5724d6b05f1ae000013a08d612bca8a8c31443c10346dbf61de8475c0bbec5104b47556af3d514458e2321d146071789d2335934a680614e83562f82dfd405b54a45eb32c165448d4d5d61ca2859585369f53f1a137e9e82b67b8fdaf01bda54a317311896ae10280a032440c420a421e944d1e952b70d5826cd3b08b7db9630fe4fd5f22125de840fcc40b98038af11d55be25432597b4b65b9ec1c7a8bbfd052cbf7e1c1785314934b262d5853754f1f17771cfb7503072655753fa3f54ecc587e9f83b581916092df26e63e18994cb0db91a0bbdc7b6119b32222adf5e61d8d8ae89dae4954b54813bb33f08d562ba513fee1b09c0fcd516055419474dd7fda038a89c84ea7b9468287f0eb0c10c4b132520194d3d8d5351fc10d09c15c8cc101aa1663bbf17b84111f38bb439f07353bdea3596d15e713e1e2e7d3f1c383135b47fa7f81f46df7a902a404699ec912f5656c35b85763e4de583aecaa1dfd5d2677d9c8ffee877f63f40a5ca0d67f6e554124700f805af876aeede53aa8b0f8e5604a73c30cbd09dad963d6f8a5dcc40def40797342113ba206fae8ebe4f3bc3caf69259e462eff9ba8b3f4bfaa1300c26925a87", "63", None, 0x01, 1969273897303781, "63d18534de5f2d1c9e169b73f9c783718adbef5c8a7d55b5e7a37affa1dd3ff3"),
+        sighash_vector("0400008085202f89020bbe32a598c22adfb48cef72ba5d4287c0cefbacfd8ce195b4963c34a94bba7a175dae4b0465ac656353708915090f47a068e227433f9e49d3aa09e356d8d66d0c0121e91a3c4aa3f27fa1b63396e2b41d090063535300ac53ac514e97056802da071b970d4807000152a844550bdc2002000752526a65520052d7034302011b9a076620edc067ff0200000353e3b8a71face1c9f37745ed36883529304bfd5a390b37bc5a3445241f03f64a818820dfeddd75375159fbd21eca9872104f8d7b3c8c869703a1e7848a5c941e45a9c7943446d0dc9627cb31f80e7aa596d4821dc99a7d777cd57e194842a023471f0f6288a150647b2afe9df7cccf01f5cde5f04680bbfed87f6cf429fb27ad6babe791766611cf5bc20e48bef119259b9b8a0e39c3df28cb9582ea338601cdc481b32fb82adeebb3dade25d1a3df20c37e712506b5d996c49a9f0f30ddcb91fe9004e1e83294a6c9203d94e8dc2cbb449de4155032604e47997016b304fd437d8235045e255a19b743a0a9f2e336b44cae307bb3987bd3e4e777fbb34c0ab8cc3d67466c0a88dd4ccad18a07a8d1068df5b629e5718d0f6df5c957cf71bb00a5178f175caca944e635c5159f738e2402a2d21aa081e10e456afb00b9f62416c8b9c0f7228f510729e0be3f305313d77f7379dc2af24869c6c74ee4471498861d192f0ff0f508285dab6b6a36ccf7d12256cc76b95503720ac672d08268d2cf7773b6ba2a5f664847bf707f2fc10c98f2f006ec22ccb5a8c8b7c40c7c2d49a6639b9f2ce33c25c04bc461e744dfa536b00d94baddf4f4d14044c695a33881477df124f0fcf206a9fb2e65e304cdbf0c4d2390170c130ab849c2f22b5cdd3921640c8cf1976ae1010b0dfd9cb2543e45f99749cc4d61f2e8aabfe98bd905fa39951b33ea769c45ab9531c57209862ad12fd76ba4807e65417b6cd12fa8ec916f013ebb8706a96effeda06c4be24b04846392e9d1e6930eae01fa21fbd700583fb598b92c8f4eb8a61aa6235db60f2841cf3a1c6ab54c67066844711d091eb931a1bd6281aedf2a0e8fab18817202a9be06402ed9cc720c16bfe881e4df4255e87afb7fc62f38116bbe03cd8a3cb11a27d568414782f47b1a44c97c680467694bc9709d32916c97e8006cbb07ba0e4180a3738038c374c4cce8f32959afb25f303f5815c4533124acf9d18940e77522ac5dc4b9570aae8f47b7f57fd8767bea1a24ae7bed65b4afdc8f1278c30e2db98fd172730ac6bbed4f1127cd32b04a95b205526cfcb4c4e1cc955175b3e8de1f5d81b18669692350aaa1a1d797617582e54d7a5b57a683b32fb1098062dad7b0c2eb518f6862e83db25e3dbaf7aed504de932acb99d735992ce62bae9ef893ff6acc0ffcf8e3483e146b9d49dd8c7835f43a37dca0787e3ec9f6605223d5ba7ae0ab9025b73bc03f7fac36c009a56d4d95d1e81d3b3ebca7e54cc1a12d127b57c8138976e791013b015f06a624f521b6ee04ec980893c7e5e01a336203594094f82833d74427880084d35863c8e7ebb5c9eed98e72572ec40c79b26623b58022f489b0893d88be63f3f8c0d23249ebcde13db9312941c36c1d1cbcabac0c78cb3b1912db0dcbfe1893d9b51be4af1d000bac1ad0a3ae2ce1e73225fb114d05af4cefc06e875f074ffeae0cba7da3a516c173be1c513323e119f635e8209a074b216b7023fadc2d25949c90037e71e3e550726d210a2c688342e52440635e9cc14afe10102621a9c9accb782e9e4a5fa87f0a956f5b85509960285c22627c59483a5a4c28cce4b156e551406a7ee8355656a21e43e38ce129fdadb759eddfa08f00fc8e567cef93c6792d01df05e6d580f4d5d48df042451a33590d3e8cf49b2627218f0c292fa66ada945fa55bb23548e33a83a562957a3149a993cc472362298736a8b778d97ce423013d64b32cd172efa551bf7f368f04bdaec6091a3004a757598b801dcf675cb83e43a53ae8b254d333bcda20d4817d3477abfba25bb83df5949c126f149b1d99341e4e6f9120f4d41e629185002c72c012c414d2382a6d47c7b3deaba770c400ca96b2814f6b26c3ef17429f1a98c85d83db20efad48be8996fb1bff591efff360fe1199056c56e5feec61a7b8b9f699d6012c2849232f329fef95c7af370098ffe4918e0ca1df47f275867b739e0a514d3209325e217045927b479c1ce2e5d54f25488cad1513e3f44a21266cfd841633327dee6cf810fbf7393e317d9e53d1be1d5ae7839b66b943b9ed18f2c530e975422332c3439cce49a29f2a336a4851263c5e9bd13d731109e844b7f8c392a5c1dcaa2ae5f50ff63fab9765e016702c35a67cd7364d3fab552fb349e35c15c50250453fd18f7b855992632e2c76c0fbf1ef963ea80e3223de3277bc559251725829ec03f213ba8955cab2822ff21a9b0a4904d668fcd77224bde3dd01f6ffc4828f6b64230b35c6a049873494276ea1d7ed5e92cb4f90ba83a9e49601b194042f2900d99d312d7b70508cf176066d154dbe96ef9d4367e4c840e4a17b5e5122e8ebe2158a3c5f4cbae21ea3fa1ae6c25a9462ebcbb0fd5f14554bc97747c33e34da90c816d8d0d50bfe37618c5812891484fa259322c15092d4155d8696d6f12f24fd364496b3be0871ca3dd9625348a614b59bde45885649bae36de34def8fcec85343475d976ae1e9b27829ce2ac5efd0b399a8b448be6504294ee6b3c1c6a5342d7c01ae9d8ad3070c2b1a91573af5e0c5e4cbbf4acdc6b54c9272200d9970250c17c1036f06085c41858ed3a0c48150bc697e4a695fef335f7ad07e1a46dc767ff822db70e6669080b9816b2232c81a4c66cc586abfe1eaa8ca6cf41fc30eb8dc57c37a3c39c59c94232df9d388dbfa35c2cd5c75f328e9fea78f65568f2bb934c82c4142da69d12ca7de9a7df706400ec79878d868e17e8f71ea31495a8bae7bdc2e48b5118771c2fca078cca1fce0d7ef0af3478cf36f69e85a41dd29b4294a65d3e055ff718dd9dc8c75e7e5b2efe442637371b7c48f6ee99e3ea38a4b0f2f67fc2b908cda657eae754e037e262e9a9f9bd7ec4267ed8e96930e1084783c37d6f9dd15fd29f4cc477e66f130d630430dcc0104899b4f9f46eb090ef7fc90b479abf61f93955ee00e6a1848f1ab14ad334f2b68035808cdf1bb9e9d9a816baf728a955b960b7701fa626687dc3c9cba646337b53e29816e9482ddf5578a8768aae477fce410ac2d5de6095861c111d7feb3e6bb4fbb5a54955495972798350a253f05f66c2ecfcbc0ed43f5ec2e6d8dba15a51254d97b1821107c07dd9a16ef8406f943e282b95d4b362530c913d6ba421df6027de5af1e4745d5868106954be6c1962780a2941072e95131b1679df0637625042c37d48ffb152e5ebc185c8a2b7d4385f1c95af937df78dfd8757fab434968b0b57c66574468f160b447ac8221e5060676a842a1c6b7172dd3340f764070ab1fe091c5c74c95a5dc043390723a4c127da14cdde1dc2675a62340b3e6afd0522a31de26e7d1ec3a9c8a091ffdc75b7ecfdc7c12995a5e37ce3488bd29f8629d68f696492448dd526697476dc061346ebe3f677217ff9c60efce943af28dfd3f9e59692598a6047c23c4c01400f1ab5730eac0ae8d5843d5051c376240172af218d7a1ecfe65b4f75100638983c14de4974755dade8018c9b8f4543fb095961513e67c61dbc59c607f9b51f8d09bdcad28bcfb9e5d2744ea8848b2623ac07f8ef61a81a35910b8a1baf39a919a7b60bc604d63185f759221d847cc54a22765a4c33475b5791e9af3271fc8d9350667090d8184ec50522d804f23c4fb44ffa481bc92ae408d1b9f2b131904f9705c59e2f4bde7a3b2c085d93fd2abc5e14d163001a12f51938d021afa92239b873dc6c357eaa8af4ee6d00540657fe32914103b5d98f68bd3e2b5359f08ccd88d0c811e4c31fbb49f3a90bbd05dce62f344e7077593159ae35050b04c9e6b86bc432dc8b048c73c0018ca5b69411297732a4e1aa99a928c71e7a24fd277856aa42501e51b012aea9446a2104e93f815a0b3a29b458314f3d8be2b9823d342f46213e942a7e19a46e970b5c506708430317b1bb3b35df68ae33a4926a03e6bfeb5510416fcbb0524c9ca5074156cc5a5d6fe1c995edc60a2f550411aa41e3da3bdcf64bcf04a0510571b936d47e55cec0330008dfe73563404f047d7f3a8a3d7743bc554955210f1eb0d08599ea77d5f974d87176d37d98b9c0ad440407209ed6a9f08464d565593e1a63b938536b49244e97d", "", Some(1), 0x02, 652655344020909, "bbe6d84f57c56b29b914c694baaccb891297e961de3eb46c68e3c89c47b1a1db"),
+        sighash_vector("0400008085202f890146cf289b7d891307bba37054cf91b31fc82f74d5fcc000942ede911825f53fe609686f460023b1e9bc00bde895d1238fad04aba98899667d010004ea427176098413905918ee213d4ec12794742d19f67d6f86cef7e6982e8841712873a01d9251d8c860c041525b3bf4e3a2eb9272815c7586768428b4c2b25e3745f009c5dce20b69d5d7c43ceb736b6831e8c110f16cfdb3a467e9414c00ecf13731500894555678c497faba9a95d01cc464390fc4a76bfa8b0e1c68a525d706d6604b2330b6b3485215f606f1883a751588c7efa506c3e8d0c60192e8476bd1175d9562087bdb818e66216286bafe47ff4dbcced51444480a9a5673ece7fac73a0ed41ab0051753a7caa89be3139afd9793b3e02f27f040046595acd47bf13fd0da27f09eda48036d3ee437f2ee8f8606ea97343c33584657f46dba99db5cfe6ca176fab7b0f3bfa0ab61e340c34eb9f17c7ec2be03b180f0bb6f434c2a6542e00e84373f4f4649cda32bf686666143f622aa480460b5afac518607cd9af8bcd6b58c30127316b25d5ea7bf6b0cab8542ff69d9b2f180be12ed75344a395aa10f852f083ad64ef40e9c0309e9bba54b8cb33c95498a69538d3ae5b25e247098e1117c918aaaae9cb6ef77abd1e01cc743d0ddd02275951b92499565ce831f3032b8505775108dc85e2ade2eac1e636e1af4054c8b6f57632df269c3723b320872e4c57b218358dc7e9905bb04edf92edf0df635f3bf361e57a13296e1447af5a5666517bcd3567621a7cf8445589653262020c33bf78031b8ee0707de072068c170570327e6d9f5c6ddc335402efc548862f5a07094fd428a7bbc15d7b38d05362c9ca985f58a76647d2be4c2cd6b3d17d6870971d7a098baf72c6f6f1214cf1faae488bd7de259d3415c2f0ddec7457004f35708d1eccccc0df65a04943ad5cbc13f295f000fe056c40b2d88f27dc34cfeb803be3483a9ebf9b5a9026057725d63ead2c0c0ff1fe26ac1e7bdfcd6fad875842d194f331750462c06b8d7982d67995ed5d3ae96a05ae0067f4eb1c7c93231bd39773cbe0a9d66b0c9aa8cff6a376e1f372eac6ac4e46cc0942245d4c2dcf02d7640ffcc5a6ac3a87f5c411551bcc2f26cb94961d53f95ddb19ae930c8d70f031b29a5df99ff36695e802cbcb6b1cf7dc97abb3b252764c01a622dfb2ecb49ce71f7386e23895b5afe166198b67f5b42b2f65ecd0f82595478d80ae5c8ceea12a161ccbb5eac09990fc619a46080436dbd08d74784af002d58e06faf7f3ceae7d3419b1fca265a5559cf9e2d3b97b2a99a9ba5a86658c3fd9ec55bfa9b328567254ab36d2c7f44d2c7e13eb54beb70ea8fa94b6c6e012d79e3f53689c2b1a18eaf2d471d13c1ab39d9194ae843ab1d28ffa8f69dc7e15cc38b12e8fcd79255b7216056d9edb7482fb98aa033b65e51c1a08b8a11d84d0409b734f452aaf0d6b18f50258683d3f9a76d399fd047eee288bb4585851dc93eccc62322924cd13b5dd4eed66ed8d9972d772629ea64742e54733981b006c062468e4bd8f7dd9af698f52ae814634e81d7f3e0c420317caca9ae4811c6af06fe80a8c02ab7a00e18e4a6aa1ea1b76945d2615d43ac118b56c2f2960fe93a025f13ec91ffc6d2c353699abb092dedc065db8fa214dbc46466f897b88c58b30152133aa3831af37c74d99e9e36ff7011d3238305691508d0f1f6aaaaa4251230c6ccc46668bbcf35e5a5ef2f86e665d8cfac7476ecb24378796a8ef2e4d94d4358be2b475fcc92df9382c5c06919a0d631ec2610fedc219be63f37f2ba0d432366736d8632fce072b6ae5b6f3fd59d3faff638275a992fefc87e60d44c2cadc2b5c494e3e72eb4597c96b40167799a9001a2ed3676a8b403ae25ffd772f7081e9a32bcc1c5e2edd4e2a6576b783cce3aae11fa432262548856183ee682d5dc31beb38f061cbdeca7021a444e2dd417df26dcd220f2b731772b439e96d614e1facb486c7a7d5171b1de359f6ad3a96f649c969102a1964fb4b4a1a4279c68e6c372e42187d754e804a61653092069fb9b6d25266890808b015df28c801065da6febdc1a56bfd002625acfaa5373fde149c1cfc3649b4869696d44ecb12479c5ebef995f10029f8b530eeb3fdc2e50e8757fc0bb9e263023db82f878d9ac7ffb0bd4391df1d879899a3ef57bfd0d1f7755648edd85bb052a6edf71cd2628c987429f36dc505ccc43f30e7a869c9e255e2af9fcf30c12179604ae1757553b5c94607e00d032fbbed23c4cbabf741d68aab30e0b8f15f444d586b3e7e6159c46699f100792d4672950348a90552e45943beeacf03f3216f94e27906edc632319ad8d37447f5c59ccde354f99ff6c7a7623f6d41525a809ce2f41ec0ff7f1af81b24ced0efa6213da6c7c60c487f5f7b03f8160a057f46d05bf8218b3add9c06893bd02db9b61191dfb133bfabe4858e47a4cc32e416ec08b8ac7915a43733f4406e9d967c560f344d7e904a28045d99f3af8c82e97e1b9c1b205e585fbebb48faf58f1b65dca2497e09a70aad4865f85715a280e186f3fc1740d8184d33e8322169521cdc132212939c84a108964e2de74b6ea55b4cb8f6f9bee98b10d415109455f48b776082dc30b4bc73477075511700308158ce2f2f9bf0f691b2ce53e61142cb740c15b7b623cf48b3f7bfefa31bcdc665c6d7123e95350811375947b055a43db07e03f33627df5c638bfad956ddc1ea7d7620a20f2792f63817a1cf32580d04274234af2a51b56bb68a29e43a954142ba4ca6823bde9053d72fdadbc61ad5936c53fdd7579446d11c44607f41630e4c08915e631771550e9ce1fca2c63fe06b7989d584fa7d782a88c1e7d64b6fbf55e3596af9bcb7585f8c7d3aa5c2082b265249df05701dab031c4bac1ea267a2996a2028d1e6a0f80a3847c531dba96ee65a24189bd2712e40e959664981e58b2a4f951ef8f497dfff2f2f271eab89c628e18b5fcb43882537eaf6ad2a6b1754633caa86bf2c76f3993154fc73e6fbba2210c2743f530a427849a301e00e01129f03a4607f87cbe0762c0b1c65855deba8422ca4b88abeea6a4382cf16ccd6dc7c37c44e549c4534819acd8bb0a02a5fa7a1c1d3806fbc3407fd7da93fd0de6400d3ab8977485cddfbed5932f507b79947adb2fad37615aa717db5f298099f20f263b359a1151a6b75c01365eb154ae42140d6e10342f14f34dc33e07ff0e4d1a6be375b32f84b92e5d81ebb639c4f27e715aa42cc75707d4ebd1bbfbe8f90fc7c953e7a9715e65af8267373d3451674ff084efd92ccf3bcc7aca1467b6327e4f9522b2cc579a7a8fff7ca7cf145dfc13eafc34153b2c3e8afbe53444d0c73b3bd5bc870b01cd457911e356313fd1dafb4c8151634a01aff7cf116d433c3d2b3adda9cebe18f7d172443e5e7b5ac9abe8db2256d7ebe2ff28020939503870597b9a955892c7389650a2d42ec92be723fedf2f2ede5a472aa1e74f33ad41901544edbbe3ac464cf439196015f4f22ac2b8fc01496beab4d45907f479812a259431a2cbc93d4f3b84e4dd366020273a6752e501af6ff1b78ddc817e6ea3e5375ca3fe2fb1d1a93715dff92993ccc6504f64fcbf221730d9eff4f327e0ad374f5956453748755b43c8f29d67526f60a618b73324d2243372921b99e3df36850c60d5d9343a4870a0e7528c6513c27c56439093f933681d93a4d4bceeac2b108c6c6fae359f645c276891c0dcab3faf187700c082dc477740fb3f2cd7bb59fb358554e94c7e678ce01aebf94e515e49722967995aea858d64e7789ff306369577228180326a5b0af475e27a54b207b41f92e376170e3fb005028261c99c2dbd0eedee871c1c0f48b8e9b8e4be77d1b737fe21f0fa5a18ebb52755b5a6cf6130fb56944cfab87527c250d113b29bcac9aaa10c2e7de415edb0806c6da03020a134ca7ecdc8da1bd57a37f55a46940b45b241b1c16ee100927d1bd860d445a9de50d4c384d6e1d00108026c0ea5ebbf0b72fbf5c370bce18d3acbc46599099baae1d802f77333494a7ae130fe86e8f818f9261a2dadb4125229ba0ffc0e7090324430b521a90d224ab7a1024e1d893e7404fedb348e4d5e2235c59a7876a0fc60145c6a009687684460271ee133a437fe52fb6cfba97fcec161df515dde905a24da6d37bdc34044a955e682b47471ca1e8c78c51ed377cd4afa894bd9bd12e707156da0726f7cf5729fabe372160463fe0429244d067489ba5d09472ecd9bcdc4d5e4df101e189db8463eb538307b587deff78de9c73af28080b2fd05003e11d3e1b3299dc9521f8b513badb010e91bfeb91b0b2a6cb129c2e825a597b8fb75bc562d654d62104640dd74e56cd14baaba565b84b845e163d1caef2533c3981637204f96a59c8e8024d9041b2029e94c15245f1a958840ba3f380a4d20f1184e77827de3ff8f3d73459afe241f723c084823230e003d3d21e53501ec0499b083a7dad685c57127f4de64733a880c2db28fdaabf1b542d205f664a35135712711dcccd931a50b9c5661882360d4cac0047681bc2e2b3bf6c99760d7cfb4fa21394377a4551c76d1f75ac03c262054dffd79a9ded05e888958199eea4501e2990a53a5cd2a46a401576588fd7d058a26f28438e5782f45ac1d07f6f6f5ed73741d5785837a6b844b474775718c29dd99084e9f88ef153a8329f532a69017dc3a97ed754367723098e5765840b022897244745fbbbb30a7cb54fa0511166e9544122000610bd2aacbd82325a59b95154ecd82c88d23abd1e20770ffb8aabf83fc0734964ccd411d1c935714e24aab566f4f08424014c4eca91b590f082b473f361c87415d37bd20d70fd0b52b6ddf1865f766702e32b05b3cf1630ee8597aae19633f3516a8555ac5be32c675be1817efbffd936904bd734d97233aaa383b41ba6d27f642847d1edacb4bf822e67080867518ae5a8f0a43e6ed530cb2e8ae838860adc88aacc7bd6a00ae0c19ff4533a485efde082bc52140182b234d1a0d0eebdfb9877598e0347fb1001e15b5d4446e766cde25ef798740e0bdf994d9739bbe5538a0ae0f076c582c0f5ba878b99b8249db1d7e95056c98af083d98cb0ed9e3f7436e1c7643766f966b83e999206ebd1393b9b2a7f414480fa017480069f85c7749c435ae2fba2ddc1038d547d84854817ef39635c29827aad86726c9ade3b265b9086c8b5b75ef56fe4bd8b4d62893895b3fd2734fdac464156d7e5ebc7ecf1d83b86f659637e3b142c164963b8cdcf4ba4f4035dffc5a789458847781918ac72fc18bbbf5110032e66d75b3171ef4b513290164a77b42b0a4cfb89639ab23845e1aa2a452f3731c8cb65082a622a7c2e0013ea47d0bdd42d6990466649a905c684c3251716d61f760d53de6e3f790fba7f5f1f4de267113bdfcd7422822330b32d58e6777765f22a4116344eeb65b2ec516393ab3751b5356d2b0c9500c0f3e469181035bc3660f0b8f9fbe6e40b5e89cb79b063714ca75e72e2e100a10d63bf784df0820ef25f8ef40fe5f05fb95683f9105ff3cb2d219ab76605a064f69219f1dc0d00b3b48642f970dc00cca4b8b43308be18286ec5a4288d600a3785cb622d468a4c6969b3792f2485027d0ad9aa4a9c2cc972f9ee5190a95b1eb058dddd8c08e7d753f5e011b2bcfee1d52c1c4f2cacda30bdb6930653c0cc4486e60e89fa849b32083ba9db453fb8df683cd68754c87daa731f570a7a4060af0ce700d31bca7e74b3e3ba3d0e8a6392a062b8e86d9d7d00b21701e7b062e06b1bcd82a01d375626fbf872d27fa4511f5f8cf8c9abcef2a990176ae339325d5a588da5796faae5bab7c82977c0ff797093e2c1f3ae855f65aea91e1312fc6b8a4351a2ec03e02e5d02f53354b052fd3da0dff82cd1f55ebca57b6337c85938a79813d2021d6094c68b375e984f68393300871e348fc5236cca6330544e54639b5418701ff4cc45a31f62edd843dbbdc5aa727ab79b442683c4956bbb195a4fa66dc9cd542c76b9150c84bf890789942f55c200b773ecdd7992cff3eca24de3e0984e10e68ae387534b96cde3792f135bf5f68787d370ca8c4c4074dc5d601ae90495437c3c2d48a3d966683ac05160b7a84eaa7aab74009e57a85f7bf68a2e482000f829c545073a15d5cd0fcc57439a4350eaf098dfb82a085ea8a4af6fa8381f0658819eab483f65b325d5aeda15232cfadec75ab1866e4c0155a9c74a7a57ccf34c483ac7da1588a1b6bed28a8c1ed3a24da16f2ae7c4d4a855e8dea0596fc83d3d369947bc3065a88de9d33c4fb8012bdf220abf365c29317c95f79525873b923ee509b9b997d2c761fb15648e1dcbe89be6be7429962cf0e4fc5cde1c931f57386599a07ef2415dc0f083a55d6c7306e74086f4768933aa24873681867a7893d77cb7f29b8c847c583f2d071a686616e206719f761ae39c110442e06163d2b84590360695d4e19849e634f24d9ad396c19ff83ce74f46e645f932e141a41195936c85d514414f112e60b1a2537c38d6dc6c4638305c9bd6c62e366bc63123e3e6dd36eedd3136fce8deeca2aa09a3298a39d83859efc9b2b69cf9a7dee08a98e4be558ac7912fdcb42209075420260f7cad0f2c01f2afe33073f26249d944f7a50dd84839bc3ea7fdee4ed71449cf07533d26e1e27a3efb032c3a3b34bd3092622d2062ae536ef5149c49b5bc9475eafab6e675761008b0daddeecaa604470bbe0fada255d290e92b190c2c2d8c2dee5455d1fa9a9f3db7779b584643464aa8014ba66994de25517f83980e66ee4f62314ae6dbef452d5d38b0a16f3991f36d8a8b39ddc0d5595eed98762878cdf3f4a2edc5cda77d5fe4faf63a15f568a540da57dd9beb6fb1a977ccb91b4d79cb39b28911a29e7bf028ac6103796dfb6b20967239ad373c38c53f6df1823d4950a0283e99b9c06ab2966667c9df677716b0caded818df9e449c072e22f9d98bb0f9b03bd5fd013fcef3ed6a49aeb98720254087ef728e31947ffe8f766e63ee46ff20816d5fa8ff55a26398961490ab9ae366fc5a2d1996ed693ccca82356f600ab099f6eca8bfe645270d3f95edba5b0de7a32819233bcc754a5ce2e5ea07842e5ff2cebe62ad76e8eff8d15ea4c24a5f207868319a5af6b035be3f44f434094f6e525be614dac920a330bdfb26d75fe7b4b365d094459250aaa5544489fb1d992581800a77b8912157fc9713aaac25b4c26eb03f716646619af02456ae695962fe5e931a63b5c79052ecd333e18412db91e15f7cbc70b4cd7e8e3c951f358572e37767e7d52704a6721b30efc41017ae4d231558c5c82cc7dd7e3356c09dc24906f0438dfcc300856ac2ced8f77fa8015736c661e80248aeeb774874aa79d290b8f5027a0a509537fc7c689b7ad86116cfec2647ccaae1c74b416f3e6ae8f7cc60eaaf7b6a590d51544138e1732945603a53462c60e1f6cb0c9ca0390c488224c313269fcd59fcb611fb2d9b4c8fa601bb1cb8d07d797bf5de52bceeb02301c8962ac1fc0491dc81affd6c1ebf89a13d6f290eda5d5cef382215c5e951d71305ef33d9737126d0e662905f125092006a229990e38f69ad9a9192b302f26bdda465d90b94b12c57fa3fd6930083f184438d8a889d3f5ecea2c6d23d6736f2a0f18e26f4fa45d1be8f3dc4a707137e95", "63516aac", None, 0x01, 323658898943250, "5e9974c1562194cb3d97794bae800e96c1c768d4c8f1501d446fa660e42fa2a4"),
+        sighash_vector("0400008085202f8900013651c9b87a5906000300ac6501fe806f57f8db02cedbcb674af2060004dab54e2b07f7b86457335d42317cf32edc17a9dac752a1a47d5cb0c5d13eb167317c5c0c24cb7cfdb130a9cf2908ae6d34f8d20a6bfb3ccf53370e429dad6708e294c728a4687d57ec8909ff51a4d02f9dcd11193d7d1c9fdae6a17396a1bf57314e047af152508f57015ca402c67d925c99acea3ee8cc4b008c5cb43966e714ef480fd05e07c7b2dda9aa3966113eaa293d3f622b309d64803ce1e6378b6aac4fab527c43cd45ed0a3c1a4b9fb18dcccfcdb6ac0c2421639cda0075a20dc5111b8d3d3199495bd9133dbab94541410e4fba92c7b606a5cb122f140cf1a3596f2788f3c8b92660f14cb65af5dd23dfdbac1371ecf4b33712fed2292c44f70834cf96c05d58827e69bfc2e696fa0874869c02f3dca11c3b90cb214e68bc1cae039d7a146cdc1d609d7a6b3fd5d461b0951c82cfb3e763fad2d1bc7678cdf82779f8fd5a1ce22a8d3c4547abd959838a46fb80afe01f8ecc9931513b1962ec540856cb189387cfbfcc0f7c68223cba47fb0c9b486e4d99171941f7675a8b46328af2220085005f936761b34b0bc2a5e10adefb792bf845aefad8a4284f491d34342dcf97525a4cdca1ad71073b080b73ea4549f5401bff4318268e6ad637363157a19a53f123a0b0e16d0b77f02028da464100fde76d83dd0bb224f7b57a00c02f8e74d854fddc49b255167b55ef4bee465668b20ea4118ca569ae480e0f6e5e043a357b36d3ab36c861f2278301dce57674d5073b3a6f5103a0793af1b7d46f957e22d8d2583bf181836c3be9930bac8fa460e968aa7109870bbed17df5f888c8ca1467ae17dbbcde31c1105cb5bda88ac6c627002ce21c02140ffe81ec58bf1e6d1bb7aaada41fba0bb588778a7f65202ad811ea73d26c74550395aff75325107c9b3f9ae9dcdcd86ed081a2e7424719a3d185b7e0a43a472e298ac0afdc5287d7ad124cd9405a62cd1ca08b282efef7f928df76e2821a418413eb7ceaa5ff1290b03ec91ce6dd28130c3ab0b23b602bd5be5dc26003aae04b33d7bd2590e90c8c388ea7955122dbaca67b30395a928b57b8575123205ae19152e41e002931b4a0336aef85b933e636a891b55e1935d36ec9b1dd25602b0eaaa4c0a34b94a8eac43219a60dd0a8c4dbda7f29a473bb532b5af6503547960a8c53d1db02c84d10367cf0faee9bc3bd7a3a606a9fdb849c5d82d0a61923c2e5d8aa63a8a50c38bdebe62d5f6c4abe5ce90a7fe2e52a8d7846e8edf2f2bce05a037c826f22caad1261467dcfb7d6b6133dc21e8096c7e9f8e9e10c1e3fac4058b682c68e54facae0f9c2dd4d64d9046152b4762332939f17e6aaf7d8b9d358e2218d4e0d69a4f119e1c64eec4c8b532809707131f01f55c7ad04cfb63f7c4a3d0a2b0ffb0b05a6be055b8c94ca80bb0a1d13cd4cd69ab98304ae2515d5f7699d4abee5c20be609d873511012f234bd85a7eff5fb634cff2658ba6516048563095ecefb3015ee3f03ca52a177f261ecdc26bc089d34c6404846e9c647fcfe98cc6acdbb464f64278ad8ce9d1ae0d415bc0c05245fddaf4ebc8dc703a85cb270f796ad2d937e2ac0d5e0a34821758000aa59c9d4652485294ee0ab29696b21430fa54dcfbf2b9c49d1b556d76567606fd822811a39b49db35508e2138bf8a090f4380ee03616ee2da5fd8442e01825c88dfe55cf5de38936f7ce25311b902ba97a3c12a95cfa1c3a591b818f60832709d9e4839e410fb36b84f3ac4f070fc35e161978259e5b8edc74138d6a6712a3ae3226015883f81db23e583c869c4c71143a6fffd65e8dfdc50c99a2f1f314cdcc71359e235f1d7dc2b5f38ef7b970843163c03f9dd40a8015efdc8791956a3f3cedd9ea64f8efa7a0815a70381d71467817bd04ca529aede07ff60d176aed0f855a2eaea89eaeaca89358c081826a0812a5bca28be1373f086dbdba7e43e203212c9fed21474ba19a055ffcc179412e893a744832298c5fe24cc6b18667f49b34dfb12379267419a9cb9403d8167d8d1e91d2811a043b29243b069b37587847dc6fcddb1831bd1cc2567ca033ac40f74ab6955f683b12e4e8254e4ea760d38b3f46791c5c4cb12bc7ccb0ed1865f25d601c303f81fb1fa1db48533d3d6b288e4d9a4dff8ec21c96f578399710c825fe7e32f93a8c0743f9ebd501773baa8b0dd17a346dea5057f321499f5d9b1b9fb5773b676d9224c50cb054c93ef86f50ed53f4924a88af34935f481bc4eebfcc97b9e3c62ac42aac26865550f0f3e2eb79247a34f84dcb3f06551087b1eaa9bf10f79c9048a9b0fc878f13994dce2a1ea1e4304f49e43ae065e3fb196f76d9b879c7200862ead18dea5fb6a17acea33386eb4ca1b51486a9148fbdf9a95332aa605c5d5483ce4ba8ece01a8ff2b7ef82d05c0b6e861b915f13ca0eb3ea13d5070807a2cb6680a249ea9c7224392cbc8ab82501b26f112ac789a12a31ad1314e2ede08fad3143af30c27f403bc866c755177852afd0abb90ade1d682726f42008b46ad7f8abdb18117f72641390f086b6e1498be69548527e6ada2b38b9fe121ef670af7437d32536d5cf5c4ab19dd99771582d038104b7e039a376f7acbbeadb34f945beb9d7ca0e4e3d5c5e4eb1d8526ebd13dacb1ba35735c6d04a4555acf4bf117626500d77b38189dd4888041225acbe3874a4c0f607fe6745f9355b3fa188f1d65c09f389af1b9d6232aa79447919c550f6f31fec35481cb922de2db5b4da2f81948617028e321706a3a778c1938c443bb00e5b0ff06ad8ab9b1ab0c11477673f85df9561dbea45d5f9781ebe317a0710ae5461e34fe6f1b1aa9b4e67b14910984802c2a7e38193bc7bdc8ba3e4e3d1d933bfb580f5b3e87a2a06517051410fe1b4ff1ea0ade824f338515456a57c7a916a74388ee8f1281f9ade0ae2a2613a0612c469df792b8df4cae4fc25c1cadba95a807ce61e5a5303faaf9e14653996b5a8adc34fd475ef1499094babaf1f3f07da9a390b1d9fc9a08327987adfe9564863fbdfa8f6b46a8841583099afb7870118face76347e40b6fd8cd15582ae8e23be9a0219bc3e4e4546a30d3bbbbd1686086876be0e4c859be71fb58f4fab3d28c0b4f7e75ad1edb7f88946fb40cfa5786a0fcba1303c8347ecee93d46d140bb5f69531d666548b109ce764bead7c87bd4c876494de82db6e5073a6c94f7c099a40d7a31c4a04b69c9fccf3c7dd56f5544776c53b4df7953981d55a96a6dcff9904a90842e5bafec8840c2d255bf5ad61c460f98feb82a10fa1c099f62776798236c5ca7f1e46ebdb2b144d8713e56c772f2c3b860ea5b03a8854bc6e6590d63cc0ea54f10b73ba241bf74b635551a2aaca9687ac5269fd368b26d70a737f267685998a3f7d2637914909c746495d24c498635ef97ac66a400894c09f73488eb7cf33f6dad1666a05f91ad7757965c29936e7fa48d77e89ee0962f58c051d11d055fce204a562de68088a1b2648b8174cbcfc8b5b5cd077115afde1840502000000000000000000000000000000004e5da9a04310342c5d3b526e0b02c5ca1722badeee23d145e8eb2213fc4af1e450e4d5217c6617008c78f4fb1112f4028a704fc5a9382c6b03e7d8085e906cf84ca2c1207c87a2bce2080a9891668d69b044beced6cda32c229c9117917aa07ddffcd377395cba616d63c0b69c01fcc45391fd5b8763fb96d7ca333a12de3cefa91c6c98f9473b8e104a71293e46374705baf65fa41384ba5c8e0c88a3eb07e0be34daddfabb7b65543b5f39cb2023d46789eb7d989af779e5b8d28385a85b0da2abe07f0c2bb4255fcea03188527a307d409159e90166fac6a070ba05b3e4dbfd3a2bfcc9ee6ed016c0f665be8133b7dc1d86044db0f9db40fb0e9f8bc2e4db5382a8b4f815b4e8434ad0dfbc51a5e9b145e1596cbf4670b7e05dfdafbb0cf3ddee28d76a82428e8aba4364e84bac379298df2932e69bb5d045516efc33ae6cc3947ceb09ed371667212a831b5485eafce8488188ea4e27d0cdf7ddd348abff777f4a13bbc716b6a5944ee727965690e209b49eb962c039975f939ed5c6e4c400d887759433d3ad716da0cb446113c7727a64b58c3f8a0f81189f98005233a81366aee73cec85228ebcfd5ee3c3fb44db76ba243f2842b7b5fc746ae51b0bc4bd4fc9fd833565ea852b92b224f6990318ad8c7d9437e20e2a1f20e818f9057c5abaaa2e5c15b94945cd424c28a5fa385dadfe4907b274d842707db3697a5ae6c8f542e5ecc07fe47350d1014670212efe81fb7c73e8450df814ef6232f7490f63ccf07480f884a66eaffc28fea448d7b401cdae10e7c0c7f9a7b15331969fc8cb36396773de191931c750f6ce5caaf29768ebb27dacc738056a8125b4772bf87ae10a8a309b9bd655043cfc3159494368c5ab8cadb7f671e9626bd263e31181a604b506a03b439a7ffe4355892477e2bdf338c62c3922f7d3c9a56c7103d911948a84b5ae2dbb16a3761add053a0f967e6b5bc94211b6547153267c6ee1cad0d974a71088583735e4f63d33156dadd54c2faf89114a127b97b94cc2a22ef303f459d04fc0b53ace5918d47ff33a558bd71a75f355fbd06bbccf4e02c3c0a4b63d0cc949801d63a64cb2d32373b2c7b274ab2db4682142c8b21d84c481f5ef21e4b5e3603451bf94774d0ef47f63fa6abb78d21c193cbe65b695fe67423c1e2d312e2776fa24ece84683e74876c55ea0369e4ea0e86494e00dde236a1689731f0a5d8203afde5c423640b81e4f631c981c11a2e1d184c67c528df92d53aec44a40a4ea2a131b4733cfe45c6b0012c3e9e20975baaecb0232df880bd7d1de13e1349462ec8d5df3e780ffa72eba8a8df7fcf398ec230513ca9d6123f8b9d8178560daf975111955a2bca3423eeefc527be3a8543eb90a5ec02f35a7c64b7dd59a72da0074634e01d2abf3637add77c7350f12b011b294168ec75576e47d169e3938bf6ae2aa8ff7cfba7cacb1f92b6e4c2497bffa9f17cad242fa9c3179c1a3aa81f7361649572c715c25a1f6cd5ace82c00ab2342b9c3cb4fffdda160ca5ab9e9baf2139ef9afbe1b1f309462afce462a79bb9698e22c957c590a753a76b87e009121e06f6a1bf62a08bf435d92e2fffe86e2a9cbba9133a68e4aebf33c38436f2545fc2d52832d165af415b244adc5f57377deedf460aa3beb43419c6b082e835ce84ca13b6908a8813c021de9fa9a44e4c18dcb3d21faabdb41931b2fd497644dc3a1507fa5ac7c76beebbdbd1d49299a55bd49927e9d7f4884e6ed3fd5e4b7cb835b83308964e3c46873fd613317b91d29236ea90e365d162cc051c846d242176daf6d28618ae31fbaae999a93f175c6938e631a081f2c1f3fd782549d3f3245759606d9f92d5548acfeadbaf9caa6b93dc08828d74f6d5fdd83331f0969145955297e69f00fd2987f2da2b94b995fecbe622a735ef7f1207f671629489202bea0b475e51681aa16778b39bd923c98dc6ff8373c79bb17030417bc200c8f0b855acfec179f7674cec2721a10fca693d83cfe5b8cdcc18f81ad617fa26f0dfb83655b8a29a7f834232425e8c474588f18dd326aa396c3e4775e00205fc9e45f7b7d2e6d55dcb90e23ff6b508459aa699bfcbd56f10997764d087408986e73d6e284fea9a23c39311782f86cabff9455e4cf699e5f5d4bc0b3905a4e3bd01c54df8643443be0f889032ea325bf07107fd41d673eebae6fa637b70cc0ed3f00958dfb8dcf00e85a1d0a6a8908140c2f434c2e260efb0bca2003504c99993a9e1c0ff9cefe6a665d791428690e47ef8c131a8e9bfb4c3080235032d731b0d3841225f1c11e2c28ee84d35f9226100565972eb269d278ef64979bf6515ed4a6840b0883a9e6ef64a0efcae1cf21dfe74854e8400000000000000000000000000000000c2749fac03825275c9b6302184c72df4c4bb2862e4e8a7d9a4a282866f9a7b2cfc9a56313da0c47a34b7b9cda3ace8185f07df36e448a76aa477f224d87a074f43af5d5f79b3ab1128f08191447fa646bfdde5b51e233ca6155d101585bc2c40158ac2106e66a26e46423370636876b434a74f8ce8060050b082a79b61bb5d344eb5a1158326ced9a9d9f54fb2fe8f9f05cd111ee46c4710f6f63a62694557ef1b12c88006b67872505f4e883b585907929a2f3fdb0d8f7914c42dde2d2000f5ae02d41821c8e1ee0138ebcb728d7c6c3c80027e437594c670fd6f3908222ee7a1b917f8271abe660e39e051aaa6fca1862276e2baa0fe0b162aebcfe3d9349c8d154bb7ee28212c1baa705d82070d7032f2695d1796809fab41246926af992b6eee95a9a06bc4562c5f2f1b19549500372e7ad579a6d6d78b33153130fb448fb79e8a669db8a0f35cdf9ae5d32d732fc79418e23b451ddc95a22ababb056ec6b5e8ba4f524dfafe875262dd7be41cbbc62420d4ad6df5c9b713604f656088a4485e93be1907d27ac6ec3c57259bd6981d42c1b78a29ad9685e63c494d4129623ea1a7ffec85fa29411073edb2978ef4e469ddd5cda986189995f88d6ab366db019001f5b25288cf860fd998ee573c8cc48aa9efcf9b617e043c329cd1aa1a0ed3a402fb96e336c719e6253cb691aa0db52736626ed1978875888ec76c846bc227272a585317dff0b1148d92d6f5fb7d95336770a7d16fac1add860776cb480221f8fb33d7e4e9b07902d2ff86fdac72096234aed48de892ff7355073bbf0615f67b1100cc2ea3ba3d6c1a1a9087b119baeebfa62bc9f0ec479d99c1a3b158b514d1629db3993f11672a26708e5ad816b547ab7e827d071ba7842b3e90305383896ec4905f70c78b694e6a5a3e4312cd8208132b840f05c714523ca819720ae227fd1acba714fa4fc45fc5398857b40dc14879856f354ba4d2581d0cda54b638ba9d76f9b52d17c8f88ee63f5845b5dcefa4c3479bce9acad18b4aeae03c0eae225d42848bdeaa536d7d8dd3bc979f06586673bc6ff1c5d3b320f349a5b3a8b355592296aaf61c5b7252f73ec0a9466a1b85764fb0831b4a1a36890e224c01acfce48ee3ed93877398e0726d02936d0d032e18e3288b2670e1362c32d6e4733b9dd2d5f26e1fe306f73c007fddcae9d9c0aaf187d7428b1e9d479c18237b9828bca8b98c9d9bec7d8270b5d8eec3cc4f43fa0188521bc61b21dd04e37a83ece68ca7a2fa6c8f9e34a6290335aa1fbd83d54aaf441e319ea47a862ad0293cedf5dd9edadeee33cb522cd0118bbd811ace9a23bda39aba72f1566fc1688497d2a7928c36701525678bc97214b31b37bab46b88f27f0448decb31622d0f0f87a855ba54000332031f73abffd46591da0b88723504edb2337230dad2acc0d8bb68bc837a2ff930bff06fde74eb90aae4f60dbb6eb827ea99884acd6285a98892802cf59d5d60d01663387b3ed2723bd6489e9c2c106d4aa2de23ced16c720429c7753a7738ec7d9db8624229edd217b80d74875a14cae4863f139e9c0b131b2a4c28071a38ec61f66801aa5956fcb2a46b9587665b7571aa03481fd8d9d5698f836fc8635e69e3bde42f4ac071328b5409f6e42d790aedd73bc1a2354723b3b819d0637a6fa4663946a30ac5afdd30ce830f6791b4575270a1720f91866e2b86f4788894c8da62d8b91faf520e3bedbc1206a5a5e6efd3dfde0843c3b06757643fc006008838ca473087f8977918cc1b81c9e68e3b888fe6f7c630f1bc7ae188f512842041cada1e05f866d2562dbe09c4b43068f754dad34df0fcfc181f31801a7992d2f16be0211b4a22f62aab64701bf4a4e6d666fc304a5c79c609acc43b00b4864893d37d5007f0c329a4755052577570dd38fac043cd91c12ee34e9cfae392a78bdabd4ee31dc0deb02fe7b1d8b0178ac9513105fcc7e30ba8e016aa36a6b5df5e5a1909f63aba095d9877a8f2dc53f46f6c9b07addf146f4ffa501f9dd3cff924e3010faf504e2b8aca7357acbffec73ac34c1a73160f2cea1e0510f84d2fe2f73b6e921907a1b7b3751213241b2cfaa55a5ea4dd517e7b49d2de8c090843730d2408a2a304aa1e2e1370a6bf6c2bc73ff00d893bc1285efca82599d181f12351f939a94ea8b975c065a91ff257cac7a92385fc8fa921b106ba8660c60ac8ba5ece45606f04f36a3a90bb3838c42abf62dd2d84babef3e188e9171aff9bc116669009d887130ac9f7396a627a8474c1811b696f99552b14c484dfe42c24d57c3a9c3fea1376cdcb63421c314a622a9aef0bc057cb11bc5e3066e33a3b9b31df2575cd5185a4f3fc4e4c3d402ed42046f81f974816d279b1513ab81d3f0a3c7f7fcf2fbb4e26321993a513ad3d7f4afe6c1bbdc657585080bb5a0f25973d63eb20ada0166bbd8a39ff93246f2789732ad05587f8db7bc87c242cfd36ce685a4b656986c39fd7fcb23c91913e4611191edcc88b78f145ea29d271b940c69941e4c3fd2d71f3b190690ee16f5d14ac2224e6fc89597654527dabe72e75d2d2a13a9fbaa6378e8a264321087a1900efe3cad1", "6a656552", None, 0x01, 1847002024287554, "76581bfa18e980910ae177e03e77c959d16b67741e5144a38b2a0dac0ebe383d"),
+        sighash_vector("0400008085202f8902daabcfac881bc701812721e6fb75aa072d2d187e62258d65a192157cdf2ec321407f682f04ac52526aab20b7061c62245716a44f71fbfc34c79b44e09e4212ac2653f6c403643e1c5b9ad134d89c680b7004536aac526fc4f84d01cd5a3066db0100000563ac526352c46ba01a2bd951021901f000706c060001a423d7588f90538d9a847056c77c7ad1fe6b6e5013ca1ae9a34856d6cb30eb49b0778114798f442258eedc436fcc386b36b57e1917d720177366f424b0a54b0b60f4fb1358c20aa41dc502e1dd8a1633f3d8e3276b59e7d2c4e624a6f53695bc6ad26aafd81c53b740f34543a6b3e9f5bb7d5c49e8c37f614921254f3212394c797d1cee7899b7b4b65b59b7342f92531c1d59e17970b7317414438cd80bd0f9a67c9b9e552f013c115a954f35e0616c68d43163d334dac3827033e5ad8488bfd9c4bbbe8f5935c6c5ea04c3ad49c747a9e7231bcd7d16215e6e80737d6b54fec8b88402f0475245e174a745b831f8fe03a76fb9ceca4d22b783c328c6915c43405064ae56bc89e64d1578e4d3a34bb95591eaf1d3da02a4549fa80db0ff7cb03993b68ae15a30e87949aa080e94abde68898c3392a217d649616bbe739b13d14df03ff27671489be0b4bebaafa7d1e639d5b3e994ffb6b7a209f6adfe8d1e5ccf010c19168aeb18aa9d687e24adc0b1135c70c970e0903af6e17081d5818e8802bc96e0e4b62a2a947e25fb8887ce4a77ed4e57281c8fad8898f40f78f08c7468fe29137dea5b888894d99b11745d5b72883199e448c6684079fe9731cbaabc127b91680901a0d046d2f01930ade7c77add1da97481693ce1033fdd3a6269975c36b12b43befc0be0a1bd369772338078b4ff7d8e2d979a3441e1c8f5afe47b1e7da56cf00602d01b110c05cf48fda3e6cce32a044000f45c6d1e696d245cbd312bdc3a3a21c992d0ebc8cc8fa6306d7e130a2ba42018fe596949fd82267bcc59dd4626efc3ea7438d05c91b0f8e092550d2d39a01eb45ee8f7d09b038d8383e19bc30e6403828cdb652a556b12040931402aa6ac34fc19fdc06e2e7787f5b77b045fd098c031bdbd46277609d842f48424eda31e3cf2cdd64385bad3118858d142d906eadb7590c94136da6a063514d6a25f7b37d7664f9b9709433e6e702118a4ab9e7a7a3e6259129937d29d0db26070523e8b0643130abefe943b401298ae01a3ab00abbc60d7db933c7f07a8bf0f7ce1660bccb45e042b451b935002cece27f36aba5647ac28d8186cdd1fb95dc135d48992f68da12ad61ac756680dd7f8d0774abd6cfda2f032af3be139a633d6733c75d1aba89018c8572b99cd30c537067941df1c4bc1fd570f7b4ddc97518623e3ae4a87bdb966c94d861e80de88c292aee9387194e256c6700752301c73fc9565a40480d8126e9d085879e24b16e9c485d8f0d618ca0dd121b51a7cab230c5b45672bdb8ea3a040f7aaa098ba26025d2eab7948693dd5f6d3096501e9e07125d7eb293b3abad57fd5f01164702dae64bdba8c924fb0799679d77f98d303919fb4a7ff26a96f137a5e5cb95bc4c6ff9993526bda1503168ab48cbd45153927d30430423dbdf06605f5b54b808feb22b208b064581847b2f64ca64837007216de6ecaffeb4b69e63347f84abcad8f2e757d5861ce77ee46513da7416837dcb23d33ea72af23d0ad8c9307d0b5858da95b77fff9027b8859e11dcbd598350eee50939481708ea708eb9f664388b9c64d6af0f96690342400348e929e07460253f38390f87bd6c05308c3bde25228e0fa0880b08ef34a5a9cc0ea0a67ca65b6ffd005572909f1c42dd745eeee9dd6b4439c9f3f98a118fe16698e9ceff558f16066975fe39583e9b5853b1311391580019fe55d59d1c828d3feb6a3b9ce92d089ae4b408e23d6a437d4989b519b7a9eb08ae6d448a7a16e8aed26a2ecd0cad80844fd0650d8c4e4d2af90656748d8099a0c756fc16cca06a334430702ae1961665b4845acd1a8e34101e68bb644ac034dc63e6e344c3d63762a7a5bf59f13095410981d6b54658e0bb64b36e6c938c049f076943b1d654778cdea6f23626f78b4cdb464252ce9f73f408e14376db86ef57cc37d09896fa906972e557180a4ab5ad09d8846547c2c0ed4c365aeb35f9af6ed55ea706d0f71a2e2f210550d75f08c8bf21616e6e407d4e243e1e931d53a4543b6e26d82596fc53b52312c776d12eb2b659b4fb098df87d683cf9e5412ee56c3fe9841d73fd070dfa51f5bafedf206f13c524e5c50cac9906efa393290042e3bc59f960b7d240ae443fc49269ce00061e65c6d74812a30dd5f5fe74eff61e0cbab3cec75d0aef95083189452dd3d9edf4487bc734c8b24f21296e4e9ef117d7fb977e3b0e6406e63085906331a93033d1cb8360fe6fea61a6826df36255789f92e40bafcb2ebcb9e556f6c0ccadc6af08e31ec4ad5288034e16d155cfdcada7bab599c2fa4ad2e6293f9fe097169148276b6a9eaa72f148b0c9565c3c2dd63125e0fa530861a710df8e481f2712920f8787e0aedfe618aff50a3b56213884d6262c11debf2ba7e8ad6692cb17078331418da4be064ff5270073934abcd2ab0469ecaf7275b4bd72bc6ed34478ea4089b736a16dd906d49f25c33827c571ce0b5d72177aa3508804bc0f8faa947122231402d2f5cc9a0eb0e09d427b427288d937d9d72b77456f886594cd8c6a462f77fd83076469cc0ecba3cc40cad69e5b54112eab33396aecfbc211f1f79cf33108e93d95378bae6958274b31088fbd8b3a3a0d154a789735b0349c4d51c889d08952ddd5488be95560594e673fa051bf9b614a15e100b60a0fe9a7e12a9b256df589b3e48e5b80fb8cff03e86f60cc070fb23c97d4c14fa3a7346ff556bc6855a5f83e3dcd9f6eab3dabcd47750e34e7c0938f64d451e39509e902747a70755122095082ab7985919073141b6d3702091ab717280bdc55e799c01ad8641904e3b1dd29e1a964c737d3c155afb307b748e4112b48b77d5ed5700e6002b18b0fed2cffdf61fd9934b60732f4d37810a91acef1e038b81d736d98eada9cd7e0c2be27ab85032066091224edf872f79637dda3916796a5c62f57f1de37678b6dea00869933674f88e41a91808073b0f436ebe25a5f44a601033e2184b88db79e968ca6d89b74901be6c6db3636580182e658dfc686767d6d819fa923e0cdf3ea36576f852bcd4e196a71a1329f6c3ff8e42e3095abd8ec197990713ee89394c5719b276de8f818a34a7bec1f268682e9142c7d38789f676cc12b71ab66635c502e69d05b9c7ef01529775c623a48e4cc5c415c9fd565365a4163768785153887fb5f963e7acc162f2805f45f44487f85e199c1df4a0fca4d44baa0200000000000000000000000000000000da7af5ed69684112d35f3673732f5a1ac3e4f021ba5c2c32f06e6b90fae2d254cf09e7690cf4e3aa7030987448e147f943bab5cab558029a36024d2e790fc6fd667f176e0aa99dd1d72b57368f01b66c4a96c156f3f28541ab4ca4966960218208466961129490a7d8b65c1470bad8db0828ef06c1cb55700e85e24fdea94ea2b06e8d8a89fc91871f88fb1abdcd721efff12ef9d4f5b04585197c3b3cc8e857d81f21ef881fed533c92cf4cb0e18fe7d34e997c6492884fe56a8b9108980d453cb8a66ea0a0153550060acb043a40ed6f929d3e0da164b23619af1de456fdd037bf1ea7fab29a6761ef4dedc86c2f1762ad64484c08ffea775a904dec827fd87a18860d6e8a4a52b5cf44be28a62d415902093a0c365d299edeba53136c626e160acb0044ce6f2bb8dde1fdda5b474d5b3507474e3d5277241201b8261a49d491af049b39e26d1357c306926416776d7d13f840bd82aca01c831c983f1985ee0adae8db8447c0e51c09dfe3dee3880a9713ceb745abfdd9f1c7ead76308cdeea21c8b0957027c5d00e50a4388c7af2bd643cb5eae49274d1230a4cd49237ae37b3810c2c3958a7dee0234301b89a2df2a78ef0bfb4bf6b387df2c6c86e61cd10ca11f8113012607f15b2856240fdc52065a1028c8a2ddfdd15cf5265f87388ab9bf21c9a78c59038a98ab64fd671077d472c209dd729bd7f8480945fba752098a94ccb24cf3bc092d42364611a293aff3c579372c12e15090aa27232057f2edde4e1db292f7b18647226735176d90f1265b3798ccabac0b8d79b17720b2ba71d7850cc2a0872bf0f4b814367859f89948f0a1a383604b9ef07ea93dbb9871c009aa6a31d8eaf1430b7bc0ac264e2f976ad397f27f48378f8a4ed902c66e4918faee8dc0067246960db1f8cd07bf90d7537cc27bbb8c9d5b2962c47ed182a2fce05f8e03c4e25e496dd57d6ab3458facbd91ea2272ffda47b073595e78dd84b71ff88b74210288f0eaf8e71aeba44c5ec382e35933e17ba7efd66490f672032d4ebcf7cd557ae0dbb725004ecb057a5a2b157a1abfb9838708ba28e7eaa212a90422c1271753b9f30f8ff8e533a993f069bd822bf724d1b738c73d4b46e99028de1eaadf9ab089dd466ca185a80afcfd44685cf8ece558d7bfd0173920d7175130f0e4d0937441bce98cfa5b333b66190f2b447138e8c26d8412cac82086d61b5d2c8cf0bbebac5b89bfe82b58917664bab91ce2ece290b27b6052d4bf991a33f4581a633625787958897fca4b98b7e7277c5e6a1d885948c9d484dd0cefef854e8176c397dcfa772e711472e790ba8d3935d57ca31349379e6283a6aa8fc991efc7d3b7ef66b92fe09d3516270ae19a999216eeae162144acea560d177205f26c9703b54e80af1a8794d6d3f1c5eead220b119f06b200986c912132cb08a98e0fee35e7f77fc8521d38773e614eeeb8a3ead86a024832e64a4c75720cdcddf9d07709a168d01012c2e4f33430f29970c60be8c5e2c8cc8a86edcd512da70dd7bb40e27b32df3d776a4a7b00e3bd8f697f1f4e5c9fbebeb446b025fd8065b186aedc75f568872c16faf5e5a3474d8a9d45548facb7469acb2da10b7078259c507c4debe4508e0cee4fbcb0d13bf62437dcf05a631345efbe0d7bb9016166554ff38a1d77f2fda4e7eba7a78ab31f38294252a2b10fd2865b5705055dfe9b3e9e8f7ad5f4007dbe422b3aa0beb9d1c89d37460854ff6e5f03e5ff3d4f1848f4cc64218a01f2472bb055802f97f32041a792790b7c226b04a6eae85f1b71ca19a1718902b4c3a3b506d8c1b7ae728c9b6cc317e5e0dee533e2e99973d883a40c6e68f231d2cb012f60c143ccabdd4045590d9e43fba36fe4cfd97b4bdd0c4d2c93c5728b1287fd2541722c699bc1a00583dbc948d5324ac5bd7a680964673edf2c6debb1c8e1d02416e6bdb2a7681bf4299225c21b5db6a845ad104d3429cdc59e3bcacf6dbc88af0f67dcbdf3a0723e4d4bce32851bb5197a8f4330b27227f0b771d0af175e9c3f6e1f68462ee7fe1797d928406f9238a3f3fd836a2756dd0a11e1ab949d5e30894f56299525e65d950f2eb50b3a8ea7acadbc3c77eb53e7de9ba82f7dd5f613cda629fcd2f6366b2e1ec240d482c3a6f9d98dab1c864c00b8fd3646f0d596fe180f70b194842563e9f3f4dcf52b893a709e1dd4a7ca1c49ec814e8fe6e0e0de546a4fbe7d25670b2fc68a8fb2c4a63defec6fe01d8ce0f51d3c65a42890975fa1eded705620dfcd1d0cdead2abfa6dfe26d79c90c63ff96e540b7615d43a6261d57730306b6632c8ee61baa4ab4d3084d659cabcfc4064c09d24269b3031710b67d3b0b736facbc181eb1dc000000000000000000000000000000008c493f10dbe6fe45fdd4ab6022fabdd34c09f75104c385c9268341c16ebe80f8c80e8e06230603995ade5561fed45cf8d114d4cf02420c4b962dc202f8a507f3d8e8a344fba10a327ff22254f6c3ac8f3cf9700b1fd2ecbe9f4e91e43a654fff027cd9174b638e6efec4abfba187f8f3dba0459da6c3f800cb6b6133a8b4ac1ef658d111c03f072208dcc207a2223a702292432e8306fc030463e754ff0f153d97bc9ce96dff4bed2f1ea5b8ea876d2ee4e4f6e49a4a85a9cf4a33dcd93660a42543e53422390d665bdd302478b33c8d574792414c5fe5b74fe1d169525c99301a3a68a0c85f9908ed2425515d45cae5cae7ce0e98b5829ed696be2c3db459e0ad5b5df74aa17b43446542af1784401efec9f1256daf719159d8a1833fc05cdb01f688ef4981c74a7ff43de355c3c4661c36fa24ec1099a8adf4e311487820b5a776ea0642ef8ef1e28782767d9de57deadeadcb4af5193e09c9bb7473773a8ca56d76511d659920db9964d32badb61f4cf6b022d7c153931849643e8b99eae0284f8b0115b4237a7c5d81970fe87c6f84b6686c4625dbdd9d79d2c555dd4fceed2c5e5e896f631ae4597e9cc0bee7b3025f9556106a843a18227f5ab9617d7bcb1af528faa7a052ea4f52ca594557fdad33052bc82b39c6a609a070753d788b2c4a2caebbe79ff012071c07081094ad6059c28f48e556c4e8d8c5378bc293076bb497075f9ca0ba1311550fa2173d0eb1f0bdddf3b3d5c243ffeabee823cd63b43939ce9546ed4c41e60ccc7e1c543cb3e2d350e2e2e974215cf7aa969b668114acdb29f4cdcfdcec2a8ce4f595f4ff5f707e7fa4dee8bf8f3952ae32e77f34f8b3abaae96928ba4a6c0fbf5b29192dae800dfa79570caf0bb833bd37a3d4beaf091f6b3e55aae525f413ac804c347d541d2c09ec6e54035df1d830284d9b46ffd2b2eb040b6177d0a09c166034a957b18ff62e434a3ec73262e4b23fec9d290a81c5b1f73cb4cd1c472b86e534ab9e6553295db0cf34e1392aad5abcf3986416a70a9dbe59bb958ebc711c3ae08caf52eca9cb54c458be7f5e6214eca0f0a3815262200132e6145437ecd21fc8036cb00a491384c341d872dcda31b1429673d9c4f57b81a0236da5ec5502ee2963150a0026bd63ef679e8c25b8ecee06564af3b02deab10697a24de67d4f6504ae2737b8e17325c2ff150c62e3798344a1ad3cbb75b7f2a15738f601cf00f7e8bc08b689567e4c7c01058beec2903c5ca6b4c4a571f460d60587362996c6e12554e8e34e683a27f8a5ff971d5a0dc2f3efd3889987c1cc39ce5d4b6b544ce04c71ee4bfae5040d61f057e4f7701728f12004a7f7edeb3ab22609ed33b0ab5d69b12d4576577714dfc6dda71ff6017b55b3354d11e9216792e5609fc06788ec668eef645e63b37e2d0cd263040800bc8aa280156a794f62a5f693ebd9074b5d354a71c8e336de0408ac7080a2aeee366c58146f32e349a9bc657ec9e57a89a04cceee21bdf3793e49a5cf713a42d029dddb3db495092c37ce814be73ef4ec8d70e869bd2b788f1500fe5ee56c0ce704eba2c1a3a3290de6ec68ccb5ef7cd0212a3f099692cf00048de5012619e741692bfc7405ba3e875e98b7ca31e965a16fddb5b0b772a3f5d050d8ad7f607f71c5363f7b7d2c3438abe6b8cd3bb4218b4d7f55650b801380c7b5c610079e513716c46fafcf3c8c2715382783aee669a9df47177071b54398cecfd686a0bc9ad37f44b5388775875166006d25df4b5ed1c41f121b9e16fca6e015a901e1e7e2c0994e427bebd356e4176dec83e6fe80029cfc478b88b6fd38c039e08b6fd95dabcfb25f238b266206b0a2f9a2eea1c083fac808aafa036566ccd202bcfa414e71c8b48933c8ed45287e1b439b6106a5509473f57b8788af527cf9a7aba593dc9f5e5aca1a648ee488f36deb4a3fdb0ff6f5a3044a63e17f70a4303824603ab50e9bf75baeb57bfdc89bfdbc27279d1073bf7f9505fb3168d206e2bf4102bf159cff61e6d66c803750da254cd6b81aed42099794b84ece904218e6f66ec634e92eeff45f52e04b4b795a1525aaf9c51d6260fbd64e8d8ac266dc6e7df6153ad97355837928404cd581bc9cf9dcd66747dc970a9f00deb44bd634ab042e0104c1ce747f53751bc33e384c6b5576399e16f8f0cb08de35083733954587c1c24df2ae6630fffe996215efe4d2626deb20566a8f5ead2f04db5d08779c9c659ea343cd784634c99d8c8bada93be8e6da841594bacf7cb3e692c74b5ffe957873113a1ab064026f6dee8b48a384a1338318360786502784d17d400ce3d721787edc4c6b3935662510771000680d78bb49c566ef27df61c9feb92c089759448727a934e357953de1e9e90fd8dffe40b873bcd5b98208df4b2ca2897af90d8c8a23623002a9d8bc02e806254f410e3b02409cbebfce8acf65cf39426b64a6ba9374a13d7259623f65e93e10bf1f16ba7ae07da920581c70409edc7b9e214e959192824c1da65d337b7375f5032fead3b4f3284811950c7a90aec975d4e3629f52d19a164e5116ef3ad022442d1eec76b888738b53e50558a70f20", "ac636365", Some(0), 0x81, 1710494278648408, "dd0f58fbaad1e78244e554add48c9596b2873ec9733824dc7d15f950dc43d2a7"),
+        sighash_vector("0400008085202f8901155996cae7bd97f02b516d3200fb3c17397cc12bb7a19fd436e67abce66d30fec047fb27025265476f3e32013c2cde8cd91107000553ac65ac636cb8d2d70fa67507bfd208b34528050004c575303a723b6818f8a1be3dc7430b53d94cef969cce218c37daa442cb7e30c68ae2c24b86cf3d57439caf17f28eda94932eef28534e1649cef88540fcb1a63e115c5822afa440c8d79d66f9bb1f48e1140b06ec87183cbc6e95f6cd5f7ebcadb897c77b4afb367b952dbb717f751890c8ac3036dacdbd784a0d83abb8446b3f9396335fbf0b44edc99e1c67c5c3816ace7629e6e7b028d6c862749e86ebc5117e21f423e18d0976a1f51d45476da560ff231542bb21c3ded2f23b2a50e0b8225690675d1d1165d760702ef103d2236726902359be8d797352f96d2246a2ee0af80a2a2d89a58530d6e36bd33a00c1b893d6ff8f900144151bee34c7944b99ed6e7945e7f0de87263d0bba6e55ac96a96d4995129bcfa9d9da6de6dd482639153a8169a4ab464e390b7f0a96d14a73f7697f7ece3cd781d35dd22adddd2f5d345204e4bb557e88453f188cacbe922987bbe3b3d976826135c103b6ca182b63e9e67f83dc9f489333d52a7fd7688a58d6620b67e9c7b0916fef90f15d8e4eb80c66b09e22e62416863a2ea57d2b978e9d50f64dbc5f5b187fc9e76c67911eb31aff527cffc6a82d599d06a4d47832440288045679a2a095ee5522d2f20cd33b13b97f7a7632d8724b5dff67f75e2d317406a0cec289ed083b7c5819818c5047939e8f42b9b3fd6f3bc770671d9c19122fa3256d090736b6d64eb9cc0320f1eaaa271ba2861ec4b3f3f6c840b619ff388d81fc4044a0d531a4bb44c93d099db08a9bc346a0b62f168ffbdb739366bb535dde66c2c1287b3b2785aed64cc40cbc7d33cba4a9f3fcf5f83136a4392d21a7f9eb1ce4b6e17e6f4a85a579669efd0fb09878e088e322e906e80d27f8d0ca7e7915ab409659a6d80fded10aff9fb773749d792857f68c7e8cf518260a61086de32fff8239f453617a19f6fec220676065ebe2757efcaccb77fc61e59b97637e920dee5e7e7a12e9d6d228b26b2fa836f4728369adcdfcd004dcf19e27c0c08444d29a122b2309f7163c990eb9261fd415c0454a56aa3eaf9c1f9bfff604776a4d25e7d3cdc5c5f19cd2a8794a4f57167f86ff9e28f370cefd911672dee9ed6a5d4a2bf8cbb7e35dbf16b8de0727a01d486ebe46e0ab29766ae083037a6e6152e879f215a76e708d8ece86e6cd27c58231381b825640a1be48e415a1e6a27d78022a8a2ff070abf12394e3ae5a8c23e37393ea8753c991b02e61810e7461ed87b380db96abe3bead0f4b2212db658c11b83f53114785276598b0197a7f1c25627d79624dacee977d9f4e1a35ed2eaad3cb68250aa9b3ab1a8345728e7d1a78be1fe462ce8ead528f7c050f1f6e022ba8b0cedf6e297ab564ca1a1faaf4cff1e42032fbbb389d3f66d57555ef3f3e9e49c2ac4e85bb751d6266c9035b779d769d495c918a055e7767fbb4bbac3f963de99746ec4dfb642d9c2b8638e16c16e72770793b7ea1d070c4e11cbc20d8ff3bead10db9c94ae0482721e1f22cefe0df7c577aa38ec0e6c78c9ba164e9dd0055dde83e8ad240e6dfdbfbe176e4551fdde92db16727420441700658b50ebb5a1613267eac51c80b19ecb786ab3bb937f0d98e08b9c9cd4df1534efee38a8f878c9f3b61d02513325b889b19eeeea28d3c6b4ac1ad7570433c1e26b6908bca29c506bd0b5d8e0d38021d3b5f07e88c9904376d27f13e4441d5387442c5ea0af5a20a3832bc3b9c59b84bca39b52cd6b1fa2932ba9d66c412f5cd39351e1333ef85d0ee18dd63381ba2f29828836fe978dab5201b2db08c3b389ba4b6acf778c2bf9102be0c3e12d77aea6df7538e8cf362baaaad1dc56042c6f24caf46bed66abf4c402a74924ecfd0a08dedeea0efcecd352c275f13ed207603822b1ef997b7ed42f4a576b9e4c00738563f82a76285467da295c23ba1c587ebefaf13cd4d50f23ca5743c225c386d46d4ac708379ef9996744b3912044b355f927a67af1ef26a717fb5a846ac9da15ea3f18f8c36183f879bb9a3b298fff9a489646e778e6d6701f9adac7ae88209a843ba8a55d1192bbeef31d0714537f7a035b079c6add4ab50612d35897a933d49e8ef086cdf96c80d2856ccc7e45fc4efd4bf1b98ab28891b4aea7ef84cf736935c466b24974df8f5355b8ba320ac5fbc475aa2cf5ad37780bd049d6c8e545c89933ab806054d2b0e3c4f821a66b94251592e0f1a5bc0946eb33cb06792469a727dbed05591ea605732205e2605978a3a902c3cd65f948300f7378e80c00619d6ff8d23350e7f0028386518397ddb2cf9ecaa8736758be32eb630b9679927dc7e0fbaf058d93f37c72b286b02b75f3cdbfb850eed90cb23392432ebc36bd24754469c03731a7ebbed2857784981a0716705d9cb47d987f83d3421b107d155dbb661ed08f2fc2e6b4a5b09776451d873b2fc63681ce308c808f5388cb1aa5589a18773db3907a06bef62d12960aae72a2b897e26b575fd048a57222c7c680d54dc7328d0f0f2d70b43108cb20c5c31164631b0e5b3bd31b7df8f4c1fe1434fa74756706f831060a5b703df9cd42e24960e508a0436118d4a9207b6d850596ddebe30f928eeeae73598fb3d869d2d1815a9e14d1279f7b4b63f4bca0f56689bf8733b03064964a4b020b060dcf45471fa1d41e5ee03f9bd90652b5372303a3ab9bb2ee379b9afcd1f6a3cb9000bb14efc333d3d64754a2bfc0c08e19f5ab82959b5cb9649979e3ccf75a8dad05460261fcdcb007aebc15e11675c2db4a6cb7938e1feb5cddc27d6d075441e16c707f09714474c96160aa68eaa123179069cd220440626cdfeed65f9fabdaa6db1760da5d84cfd6003cffe52fdd0d2a980348f269f5a07642e89ce2627ba0e87139ec2db572d1cec8276d1a62a472f612ac9da093a9c5fcc78119c82befd7b30ff2c0059410bfd5b322ca5db693939fa89766ff098ad4bc64037a34a73128605723a241f0eb1540f5f5b555b7579980f9750469b58cb10700bdfcfc628ac85c07fb3c0420032709c0eb6ef2c14b4372b58a0de19789c91fc9931ecbcac6419ca0e5d97a3b41c76c8a196c7a3adf55bdbe60e8559264b6d8ef75d26dc720fe5ec1f59662d95d08e789e3ad1829e40119aa7897d89404dc496604668f559ca67437d2bfbb7f51f36e0a5b7228f05b6ec5789c13fc2719556155263966e81f52151e2f6e36869d8a3c4c496a513632caa8abe1f2735eb60fc1285828eaddc5441a402a3bf5bcd227cd804e3c8ca21243cdfcd53d86605f3f8af1a9cc56933155328280143fadb3a1fc33d769f07ffc01e3579e1181f1915db89d82e50bd7424087c797d9b7b3b7d2a53b8fff9f2d928ab996dce5ed2715898e4858eec6078a9488d2da6d17305d0a3471862a22238b9bec23ef2e2041d5008733e9ea5662c9fea0e4afdf3270c11323ba48b355085744097f3f6c52ee40431739c5ca8db2bda13da9b330b62000b79fd3544b13183159d174ffed2548540a52ee4b62d35aa5a5863f2baa4971a7002be26156d0945119cafae5a34bb8d3bff9e6874055dc0cb91b2aa03ec575f62d944c806b3f93c04b73a98b27343eb25a06c875360de1a1438840ad066e3ac8ffac09872175eb8106ee31321c722029228d0a52043d73d076910d02b31e63ef985f935bd7ff8c7705c89c0bbccda9e665e3b06ba879fddf35e0b2f60c2a70cb8eb9de2f5d738c05e34e50f1f2619258b89e573da5575463d2e3bce39f70eb45526cd99fad90f9792d0cd593ba86aa1aea503ddca5e3e5737e6fc7bab2785126920c447d5e56a75dbe89d688bc0daa79aa62de9ea2955f71e1a61682a6178f80bcada3b97aeec77d9c8563b069ea0132f723fbe75602dd629ac480993d3714ff02c970ebd83e6d6cbbe39086b035420e0c275628658a3ba92305cc07698f12ee1e4171370ac39df0e466dc8ecc39da5ee47b6829dbba9970f0358ed682649605c7bfee6931a295b14a3407600074edc79fa61e6806f1108d334b4a590f7a026b0eb02804d3917466e999120641ce07ebcdc99426082e0771f159c826a9be6ced72d0e9cfa5b4b8a8640ca3488a1eb2b6e374e8c2e003cdfa232103748b5c9dc11bb30f646b973d783f59914174e48bd6a84fad89dbca5c76d0ab4145abd08e4d0f2c76025fc85fc116cca8d302c8a3beb26603a1af1b59391eaf471759adf194c40c209298cc051fc7903fe40902c356f28279f2794bbb9e00b1e220e55b676a18a9cadb88b5b148d38f38090edc4f26f1490b6a17cf99f9a7c458c3b31823fdf69578c47db5b3dda86aab1ec9f58d96226c6b91dc0f03fe8d7df230f07b2fb948776601e9c83f6c1cf876fc8ed44ada0e1608f485c6d75678b3c00e967d34a9cf1028c1705fa3767f46d4bab7028b09b2038fc1b727f619e61c4fc16bffe657e99126ac5184fc87f5e5301886423b356875909ec92b32d33084253a1b97c5d2ed66c7e22d18558fe82b5ec88c6070582facf756d703238d9af9419966be462dfbd315c5bfaf044aa695a05e69d3d41e77378751d4e02c266dfb5cb6a7c4008f944888311e6de37dc7bdf65d70cab3e078ab44e232b411cafb2884e264595beedf9d49a7936bb287fe28e1c29635eaeca747d0687cf465902d25f5e5158481daacdd300b47740bc0c6277b447cc2664044243dd4811404ecbd7c7a63c9fb7d937bcd812c2345923b5902683bd2ed54c01ae0419a7f54e8a3a59c6a6dacf89c7370e79b560136a2b00ddb6074d74ffc5c5dfd06b6c519abec3596a476113be4138eead5ffde86b1e32401fa3846232d0b3c9bd5688b64a330938162a8b8929d70c1b675362f4c2a9bb6b7f91ebd47d263cf07b1c322b37353ccb15afad8017812c0b7560b0ad878e0c9756d478957a87350e49320b8a4fba0ab56dca3f4bf83eaded88f92bdd10734f1db6fd0e56cdfc8d2dbe0adde51806d6aecd7ecbebf95bd7229dc756af8c814ce53865fe7efa419adf6e4ca790ccf9fdae8079e55609272c63b549b0c85f110cc9c958680114b3117480af57cb159edfbe5cb9c62bce2cf2ab29b66711ac7aa53a749ffa83907ecb6912aa569638dea19e5441611efca32099653e8a5ca1fbbdbab1d64471ec320ec38ea488400c9b1f4e8cb5480c0e9242b086a80eeed490ae32000c8009ecb71ffa39f4f3b5749cfd1befe0d9667ab30220c2dc04393698b2cfa20492f250ce1432358158703df7b139d745ce1fc340787701fb51dd5e48b89509417d8889008063f9ba015a07d8d39bbd00762f595afad8d859eaabf0d82d4633cf8298b09bea3f222855a92a0843f52fa58db3a175c30d2abe64826490cbe6ca1488fe3a015a946dc9c45ac30925727a13e08978f7240347208a4d2538c2d56124378c22c04e23dc28b15019be776d70bfc1d2645b5e80d1fd8419df72904380e2e1fc4dd1df1ba3dfe480cc846d51514a065ed762787afd6eb90bdf8fbbad5eb3d23fdc8c54cca10fa1fe546482f5e1424bfda87aa7fb786e260f2614be0811ee16b8d29df9a0f330e9709f63c950fbd903ff7d5b0ca29fd63b0f97517769025cc36a52e0c115934a3ca258b8bab90016a401d5d8d7c3b944925b35a9349a1ac7d98521610c2fad8b5c8b319cd6e05f9bbed353f1d0c865a94aa456dcd18a39e2f585d9bea84eb5f0af8b45779498c9ae1f755d9f90a2c3273e52aad3ca34b443791b029994b1ee4c40fca005352b8d6d286983177d655b6f34c499322b65da6eb6b9e1f4d5902125b64c93da74cc1a356018b0093bb5cc8205b2692f6d3e9c1cc88541b4d983845485b450cd4b982aba8d2e91f41f22eee7f36d79cca9c0e01b26c4651118ea771514c77ed60cd52451942dc85b3fba448b2d6310f2777942832e21cf3d44874f8d04a80526c69fd3b51049e692ba45a702ee12514ac2e1894f9b83d756d0939697ca982b687c9ed7e0b23277073c1930a473d1668ef2e9ae9663cff05816626cd3c5bf771653d7785181355c05aed24a99c4b674d24a0f08f4b0cfbe90f2fdbab42482e98f13fffcd1ad33f4f4c04debc89f40b5dbf64546c520dca5d0ecf3f65d3a77d0129f600371108aac30a9eca8bee5524fab671fc08658762c8738abc9fa7693e39d39d703d5cd942b5a55fedafeccaef7021769e92cc9d3ac7b4c23b33fc22321854ba33f49eebaddca29b35640e4f034884f7c5555b53118a7ad4863ea8a6b5722be5efb244391e9d01d2b5638f9e011cf2949c412f63c4e7ba99b0bc2fb74b1974691cf158df77b556ab82412463cb6e4be99e90bde09edc1ca8cc3077dba31ed4587d7ffb4b905455f64636da1ac36bff3a1bcefc53aec30ed89110f9010978df70ce4ac6f1d602550cf20e44436063e3a15b51ecbaa4a59df2fe015cb3637f3728304ec3a724f3149275e7b634bd88278d93fab6b941668d913dbcd89213f3bacfcfd2002ea866f3f1707351264b66788f4eb7f68c5a536fa9c130d8f6da1bb031df9e220d8ca8bab46ddcf9c35fa634809a73dcd91b79f5bcb987b20544bb52aaf0d9e3aea91183b8c4812786c8dc9b93073a3052671b37150525d5924aa6ee5e036c1beb9daf6f94d05100b2ddd36b13c4df9d456f6480bb1afa62026ea809794d3b74d78017ee0fbca83cc7e5cbd527acde7465373512c07646a62c60f5c16c2ef9f418d8c7d188f7b13dd4538a55d186ad6362a589a9f52b25e616fb2a357accade6357fa5a42a798e4171311ade9ccfd15f27c8c1972179d261fb9b09bc7a036c105559b04389dfd8a7be2a3ae2bba2afbd1e9bf9005c8b366354f909be71e52c09080fba7452377e8f12c184fe7ed465b32c9f9b2819ea1d119fc267c8a753381eb51acf854c19e8d58ff4274eba8c63f0fa170a63cbfce2cf87bdcdf32b7e19804541c2c589724efc69bc465d0908e09b84d1f50412bb07f47fb9f0d4729281614cacab614ef65ceba1396b5249d2c61704fb6f348447183f9882a98ae9c71a76633e05b333a1bceeec9bd44b8876fab6cd72a5e335c977a8c56ca167b1a198e931bf285f68681fc5aca846676e89b17ee769a08f9b460fe4e4881f9b20fedb39d1fc6665d106baa5a93140d1ddacae4a7590f5ab07852c1811f1a035c3f1a60b154226c9db08ffdd0b6deee722a90076ca7c6d604fe8332868e1d59322f262bbfbe95cc5b9b1e20310b76350b4d604cd1a458661dc474fe4c587904c053475e1761b80a60cc48edd95434df023b94a58a99d62566e00f677790dca076a4f167470c43a81e6c32f0d00d23656ba74828b8e4d47538e50c0ecee2cdfe0d5943e23e3f1733829d3e1b80539330e06c6ae3d0ece738c0dd742aa5860f43b530f03dc55debf720123f8fbaf2e56859a5343d4612ee21464db2501d4f353147f3e1a5abb893850816c80af29d889248c92a729a0e2be2b66cc13ac5d996b25014666ddc638a1fd2a0afee93d98e31dc1ea858d72b84bbd32fc0c616e7d4abdaf3c18ff96013245d83b3bdf921f403f1aecfddd885fdcfc733870f760cb87ed4fc00cca9332e8e1c85623b206609f887ebdbcf9da10f3814197a9f820705eaa1283ac7931683083f22fc4dc7ff681ab846186f22d5730843de7100f03117a3bba064", "ac5253535353", None, 0x01, 616080300350029, "7c6c3b442b271a48b253b1fc35209809998896275f949c5188e4130be3da1294"),
+        sighash_vector("0400008085202f8902ecef9f596db01a87a8dadb827a1be8b5799b33c99a822b73f7e662ed6f860345a26283c1040065636379d70e7b0c0ab71e116ee2d9da27461e28122a09ca04de3876502fd24dff9209552f9113877078a0020063f8cebb4100e03a6b56f60bdc0338f1d45fdecb010001ade40a90079deeaa3c566d593ad9abe7f210776730b6c88e73f63c24726ce94a93f86a3147bd08757a4f7aa7793c97821c2b5722c9dbad20f6a1e7adf68bf2227be51204e9deca8d9eb6266f659b3355c8977eae7e9ed539d17939f0c6166b0101270324b1101037892942907c6e19509a6c5f6659baf7f4363c4915e61bda34069bd986b6377ff604ede5a7425db28886b1a261366da8a1398665beed3be9bc2e055e711b7d36ddbdd365ccdcd7fcbafe7129669508dac0ad2d55ee7fc60bce228850ba7b943a8d50ffcb2a670651d315d8719c7b57f637a37edd326abc76f0a7690c236880160107c2b4c85ecf2ad9f5dd2645626e4090f10047cc131540ca5803045a6aee91ea0b3f9b77c4434069c5320cf5b70182d9fbbf3098306011759d0d64a884141ea021cdd95efa3263a505b85229d154ecaa235e8fa10795c9da2741cd98719016a90117a76f84f00b5c3d4bced79a73bfb3a1c78ad1adea5078f2f1b00f815bc7a30ef85840077732dcb1a61e9f31763d522d04c490371aeabc04c0ebcd60f20afc5f4ccd1fdd4651099bd059119d4adda1be89ef4127756ea13aa25f94b6b41aab7d184e49c370c6fe25974f9a7c44442f5d8a0361f572d45f1675d0381ea0e9136b1ed8122d9b1013a6fa353fe24ed45783dbdaac100d3e7ce2634cd43f62ea93a46d88f2fcbc3e284084e704fb1d7d0d9acb91961e2eebe2dc9ebe365b25b56675973d0c38f47630574723cd3ec66c8f3b128221a790d92c895b94270fe94051a170e95b8be7163486ec8c0beebef65e1626b046d7e7f826372b6aa10baefb848fa1df6bb1dc439540f63c9c7a9d5f8813402962651ee9843902b6c3982dce50a6178a55a1adc01ce7dc6c8338e1a9ceefc178dc4314f6749a81a731ee3c7fc0c35d1ce363cef11328f387c401fef27a67a6292f6f72b0a1d6c389162d162ef050ae5f3ddbb55caabca9a1be89b463494d7439fb5647a918128b9625d33eaca619d52f035fe6089ce8d8b90fe3670d8c5a2e3e054969a3d97e61b5e630674fc70857f1bbf10fdc4049eff560eba5f22acc8d77dbee0b20557fa4d0333172cbb5cbcc2b135f2ccde014e63ebe4edf925e61ba2a320cd399915addfceb1ad069a9fd5b6210a4b6e50452b1f906dd16f01668f0af566a287ccefcd8947341859ae7dc3a06f6bf1574feb931f927e2d505f608599e23b05af7c323698397a801dc7f78825cc7eb9fcce6c6c4f8f68839d30ac567148e7084db2b375830a07b305fedd607a347fa65def01d4e1fd6c16b4b47f5b01b4365b77226e60fdd40f22a395aa235f0dfda8fb4d3de65b0cf4f4c220b3b4a9e32bc0db64f162c07df42a1019903a67cda693ddeb5ca39a0fe500850ec7c06bee71866b355ccbc078cd4dc036fdaa81cb2de99cc88f60a49464287f59fc7148b1afb4a2f9bb89714e1eb8c0361e5992a5b79cdbb91d9bf29eb598cbb4bda923d267feacb91ce72d61ab1ea00f56aa6766eabc47dcaa69a024bbff2f296917f17a3f8c93e1bf29c3cfc991a2be8cfa70e5de3f2dd52a7550138687aec28926fa168b181db7282bd60dad3310dfe542cebe694740025c7ec2a2043febb779f7f3789a5e242db4803ee367252c463c9a88b417b70866d9afb7a08276801f9227c6381f15cc094ac7bd154a4cef90b4847dc168a01f1e31eec74a7efceba11f50769f5d8f54d3620c23ec8993f7aef27c1d35196b102b3cf3fed8bf85d8a45f69683ecdd1a2344efb84807d90f1835b4f2f24d8ff8123047eb9f7d30623e14290d5617963b4221404ae761c86bec7a07bf81a0b9a7f7d087ac26ce3dfa9c93feeaebd10dc188c627d4b91d2a7901dc394e5239050a17ecd53320a5d71c1324efc3183c1e6acd5605280ce39b981bd70cbc85b883a6b14605cb8bddea0f3c7197563d072ae251f90ca15cbce9012754e37a0e2b3eb98e5b61e7826d6d581b48649702d909191de8aa61c762c2d035853bc92b52eda15cd4219b6c1525c00b55dc8c8af9b0f6a3ca1e07eff15811391c53f7e43b1b8116dadc016d1926c8480d4ee34e76191b79bed0ce95973a4c7cf2f057c7147edb013d205d81e2360888a2abddccf0f6f3d8f8ba111d642c52d04ebd3ce17c60d92257ea5869094501bb671268b224477a8e0141d6ff37e24ff1c765e84d264db88f00928e64c412bd59151a6571c6670916b0706b044fc5c2bd93ade3967957cdb941274cc6bdb4e036b767b950c09e4626a1d005bcf4836ef6a1de48095dcb461278b16c456890b23d40bd360410f0010a55f505fe5e2db201c752e9b5b15bf8aa9e82d649ab1173ba2a5132e0cc5051ccf74c7a6a3707ab5983f7cc275c991abe4d7cee5f289efe727eb3da86fa21a28d6b8a2affd42db98bb2a46cd8a329312fa94539d9cb35dcb604678b639064d92005df2d10681c64b9ed8ce47d7eba0f2b502b206ad4b2e92bbe4586f6d7509e57a6377feabe38b3cc6c955d5e7bdf7eb132d86bc07a3098b413e4405daaa255291d552b2c8007bed41e22f1cf7911821200555e9c4ffb09efc122381175031c38280b5326ebbeaf334fdcf0dc444e629f939551540bcbbbb1ab9c231a866b329e8524ab25f93e5e334a05272a3f826f9d05a45058dfcdf68843a8b936a0cf5e6aa8ae1b80f60161bf414f280211110921a9c85f5104a0168e8e72de4f8aa04132eb258876f19d7be5f2dd2b0b304b923b2952d91fdee7e55205dbb194ebba322fdc67b2522c926121c7fa1af17ed06c47278f96089296087a704b7d0f847d51d6cc68acc52207747341f6b98cb1cd4fafcd2bb0d05bc79bb80d7c4b8a1a11bc0a3bdeca4541869b4dc9d6b48cd7869bf763b9dc4245273c704b0d8dec4b85d16dd438ced6220fa66926663fcc228fc6c4d27e17e327834b6757914d1bcbf34b65d858ab8b5c120cb0850522f542893fddb179e87f832daaa152c831f13564009c418123533de2c67949e3af2dcb60d6bdbddada63a30b4b54cd1ce5a5a00f8e8557eba9234e81178d0fcab5610fba9669cfeb1bd08cd96533498b272c5779a9f939691de1ad881c80878d6c294215230bbb619069b4dc17b3e59dbd242cd88ecc3be3a2696bf7f2d9e5b8c152cc0d99a0a5e9a38b1b8eb1a013eb76513337a7b0dadb4e817b6f497802bd47e93a820c4fad6c65097442b9cac161b64d77b3057d1336b55a48a7b7f689ec56a77e95ff29b491cc0daa9959b2f7282857a14711e737b96202041406df26f23013d14b205fb45d067e37502e379c4aa1382ff9220c4f389ca2c414995560520799c817ff305baec16867250595641154190e5bfde13b3a0e0895345bd5df93e3fbfe6312f6380b98fb850a2a142b914adc715447c5791a1b67ae656caddd21e1b46dc9a764127bc0a301b48004a9c5276bcf08e7fe4ae52d76e431488a5b9d431fa136346e5a53ab3f6812f2d970f7b39898cf8b62f2dbf61e99a2915dfb75ae22b79f84cf2597eb34ec3d292e6b5d84ebac4d92de52e1f8bf6bfdbada634409f20ef2cc6e3c390e435f47e347238db48690840473b0a0831a5a8a58c4dcfc4eab7b418cba2a414f95577190ff88d727f73e2fff97aabd1114b764e3edbc183e603acfb7c09bf132bb01efc7178d4f9a2dbaf4924fd80fbe0e604f60390832eb980479e04e9c9a2bb2fb3684d8f80648d580783854584f62be0cc9218832385610d962365f5071fa3d368ffb671ba2c2f9a0fc68d8072219a77bef2d6b4a19f16dd53074224746bba5f1728220b196e40f937c4705429d04aa3c505c95603e05ff552ec18642d567050267b9f9929c2e138014b5ef1ba71d9a7186e3d13c8a8e408c2a9d1201a7febb8334512b44b82bb201789f635804896e3eb21b5bd8c421f0b4cfba04de92528f04fb4b526b737ee32da863f598456131983a01358fb07de6752111585a86256ce034c0d8575a42761361b11877050bc6afc3161564e96fd8cf048febeb2a9220071cff182d6ca037ce2c2ded916bd7b84de28ac0171d97fc24956c266669c1036b2b1a23dabcf34e38f3514512ae8a47b353b416699675e4d31a2fe03408e424a7829a06ade6365361d8a961257cbe25b0cde33e964877df5e57c53db2835177343e2d876d514c62fbb3b4a708ce626205ccf92f240d602cdb5d6841fd29da6308b6ca4097d8525410465452239b0451a8dbedac1e41eddd0f6be0e3d889690703a3145707e0b3f5db91b8193756e0e347b664a1cccbd7869a4022eadf3f873c10ecab9a93f2cadca7a333b81bb6104e82ea14fe741eb062080dc85acbc8cc3a9bc80c03d91ffb3c25f9e42bc25cf77d7390c3abaf2610f4ecdb019b158da2155befecb9c2296d03f823eaac0c740d2a4489b8284c7e7b3a729afb69bd5bfa5f62f9b5273797dd24a018307fc620e642aa27e7506e17b198dca4790e8de1bfb671d8dc7513910e954310721b4fb53733c918f0d189851889627322d520cacc9dd7036bb439a169ef2cdd6cdbaea5a91bc24ab3fca1574c12c931e7aa3ed3c64966c06b622d23c88db2fdd61b8229f53c5f67b610b1d0ff1ca928837027a3782258f92da9e773a484fc272db28764a1b9176cfaedb3a775c05b2f8457595373222fb3ee6061d35f531a207a77e8935bdc6617f094bae80929dd139877a45a19eed1d9cc90102c32ffb70191bff4ed29026746f09efa9dfcbcdec5a695b1f731361464ec42e3b5267eb65f556b267af35971b4149bb3e5aa03a495fbeb9015ac3ff13a5c1c2a5f8196473d5bfe7048df277f0b5cf4e6c71ca9366eca3b9cf1e6069d539e5ce43fd9aa25c211d37992c340adea8b249f28ab23493917c49deb283b4c8a649041887e66838d1c429decdb3159cb30afe4fb3168ccec44982e05f871132efa63d65a2493cdf239e8b2c80905e804a84dd76afeaa6894791d49b1e400b3fcaa82739960adda3645bb85756c63005c016f658ba6ab5257c486af13edc9b46bf62934aa714f003605965ac54d8250a5535200d1202acccaaa9e42ea982a21618edbb134c33bc84e35fc76560586a3c3438e8f2b0ce70d863171df238e1260d59f824037a7717b2e21a96e4d799b8ec4c98b8d16836c1822b2456266465986850d2331c72934bdb67154aba0ad49be0e52d8b07841117c0eb76a39549639f7ade76a90710e7983978e9b23349bee22cd0c71a1f07270e2ce8b36051b0055ba9705ab222e8e858dc45b66c1ef3fe2665503e78b3029effbd5bb139e852c3bf907132e54c3edad03f7e868f523155f9f6bcef450bc9b56310cda173e50e95a6ee5f068b25e329c3548fc2499373cde29360fbbfa5b64b5744ab03a4bd5d948c1bef8cf4e6bd94c32809b18f1189c32bb8fae2753e4851c3196f5bb1da07851b5d31f20a0fd3a7a4b4501f3185d267b1c8bb3595d85c53cae189ec9db6f1453b3c6ad4f3b93dd106a3a390db27a1a750e7ed0897ebb6198484dccdfa7a7e1d8eb2f23668d54e98f9ed3ae90fe0c275f177ecf701fd30b92f61b3c1253cc317895fe5e39c4ea03248e83202ea589a0e8fcafc43407b5719c086ac2f58c1c4e05636956b6304e317f4f65b4e2b99f25e8d7bb5328ea1f3113256a4508016a3e9d012ef819fa36a5dbce7e3aff4742c0cd3d5d9eb84044a003233940699bc27945b9ac938223c1173f34d17e7e2e7bbcad2d919d1af554940b68d7433a6d67e85cd33566b060e448b4a2a052a8b79e27578dce6e09886ef092ef096797478bb54b9abba5ae26799b07cdc88c802e6af5cbfd4124295700ac12d910a02a74c8abd24d398872dd9d3ab3c54c63a09e51bb5162540103ab0caefc6e5b880521f49c5593a7ece1efdc00ad96c382fecf0f9c1c8ecdcbc22e8907ce99df994a330a0200000000000000000000000000000000446daeecab71f00235dd70233c4317d64ef6ba3f657642baad9735e54868c197545689a0570bd4584aade41a5908b8aa33549572c7209f63ad0b804c7602f48ded668c31a07d7602d6f82429c3d2dee92f38db5b9203ac84d0fe14ba6ac19aaf9400f2e3583fb168d303ca7a8871ddd9a295041b30b81eea1e7d8224344bd268a94a111ea7c9b06ec56912452eeb01cf8887a3e26e14406ffeec4bfd7a9fd877ce5203fe6b058d231ec71af9ca18ed5c735506d7ba28ee68ee66587c998c8feca7ae068c8ed079e5a9a436728ccee10c8f126f7b2fa0d0ff91cc41ee28a196230337c61f42e9522bf6de64fc5a57e374770607630bc196ed052dff008361fc59fd9c48d262b93aee45652c787805dface83d04e524403a25a166a1f48ecc8fff844f09de67480452a6789d48b7bdbd811f0edadaa8ee8eb91617992ead6f8a8b9ef4c5adb6f25248b213f3d693f63c0d5d15ab5432880714273579373c49cbf147f94a84ade64849eb5a940440133896a24555e4015599c046dfa6f14a2870533ae47d33ff816b8e4663f070c80d8db01b43c60f5fc02c85acf5e106d3ba71ea693ba465dd61ff1d80feeea1b6d5a163d0c962431636e1ed621966fe285bc970a266bb408d4d48d55ef71704f5b79862bd806a6a33e113b18832b3d59e3a6984e14fd52ac9d2be3aeaaabf3829cbf4dfca6803afcd1fc4cd0244d7b63b4c9d4aa1a227adda806a4624a07965b9fda173a2d99a624f4a78e9c71763012b77af326c75226b7de829744b6d3972e47f6a145b81340d2716201e071e471a855e9cc36d3949971574bf3a060fc0d882d0a9865c24e094031730cbe188e6fdafcbbaf751be87af965cd98d993104ca6edd29280cda865567bdd4b4ba4737e61c3f0ad875a8dee6e6cdff26818808ff9b2d558795d65d2a95b4565619f7b24162cc47599a331306e3652ffbc3b3fd06c1460c806f4e61bec2a2a7b6c796f65dcf36a4afc6d8100935210a86389f249e2f8232737b8b336b5f55400b0679ba0c1ef020c92685a424917995ea63ad1d5e28dd63998382c7b39d26db80b43e324faf5d63604a84f2135cbff598eb50e1d3a4b99cd66c7afde67fac47f0358ec783be35954796e5973acff63198a35594187ef817000b08881e70e0b2cde2315179c0841551e8bd928eb624876e86b0b33aab0cde87eb8fd4788de9fb37eab3b47fd5dfe9b37bcbb6e1f225fd29ab07fc9ff5a08f48669e1c9968f5217ad3452dad0478390744e9d16785cd54a50398b014a07b7845997a5b116bb2c2f4c4e5646e63082c5e3fee5092ff2fa89ae32ad69907504d6885b5bd72c823d4c70d5ed45cb00c3e0405892c88837453fef2efb75137f3c2abbc3547df86ee0136b6e85f33c525583ffe27e6ff48a80d124ef801d324754e161d8bd67744df8ac5849b655acf9fa7b2ea84621d8e4dd8576da75ed1b48acb910803273e483773a99d58cb70408f3f23a3ea71d67323b8f9fd5193b8db906a1886e426d0d3216e7f0f42a9aae00fc3791220dbb1031519bc1eccf8298a22ab20927165aa95d54688834817583c6490287734ea300c3894f99baa29ee97509d1c1071f21742ba6713eda020381e6098b05ade280963b398c03bf4c4e1f19ad1adf1f0d61facbf9966bdb01fd184b200f866c5d12e3dc57ecf4fcd60c4a756191dcf50bb0f976f00e43636a68308692f40244c3915344b6f1f5ee70e51e12b28538553403be1498e0075dbda3e666d9ebd18a12721c97349ac10e8fa2d6a59b22356a7719618aab5c757f8821efc3e071b75f215b200b7d29998ed7ae0057fb2329ca9136dd2bc51a6590171dfca3bcb936b11c63c03bb7fce30a05f9b6f8ff354060450a3452da186e93d6c32da6272b89bc4d6d5e8478f299101989711a9d22097cdb70c150ed26df47b0cddee521b4f1e9896a1b6978653a4e38b0d28526e1e3a87435ac4fd3097afe321e72d40c470f3b53f5c358d2e53697caf669deaa11de77c984a730e5bf7b38ef6589a5aa75581bfd3c0078a63a392960ec3f2a05c081a484eb4f425b708360f82853cfd50a027fa9251768696f3735cd9edf79ecd4be08c5785c8aee79a13238709942f2cfd0f807daab50cc6131bab9125673627f5e9a3d53d99fa025c39fab09e2a21346dc7f860a62dd2108e0441178ef97621aefce8972810a4c7fc1b3c7eaa83d4a62bd710989611dd7e2f4bdf15d831006011b44ed959dc61d8de52745e30679cef04013ac6154ef06469823874252162263f3a4ba5657b8d0ecf0386441f8730d0f14e868a324637b0d34a9d1dd6c39f28fd9af350dc23937929e37970f8873701d3fa471010a7214068ad1b890252261dd90d8900000000000000000000000000000000c5a6f2904bc616b027d7bec879b7a178254fdcaa991b422b7a9693e764a127b172a0dccac44f1527086c488985f9235e2882b4781644eba9ed0961ca7a6845b57365d8754bdc791f81c809d012bd329b6a44bd3dfa34735ce4c738edefa42d3c74092b5cba9c358157d2ab8a6883040f40cec798a69d7e0ea3b476d993d696db0addd5433f9e7a0ffbe024261e798dad058ec8de267c9478c801ff371e41c0bc0cf46a4a84d0aca473e880de962969e9de2399a2995680dd768fd76bc6896fe02aa482f76c7252e66504e880d276bf7d557b396ade3bb47a6b0e0dcf063b1ad856694f8eef54ca7df42b41f9c6153ea7471cd54f90547cc4d4ef5fb1bfe582882259c777efc4eb8f5d75531c1b801b7212c6f14509784020cbc3b00eb531c56244368928a851ae537c7480ee6e451b297432ee1758229950cf780849326c3f28dd53d68119d296955012a26f833cdd29c6f4c716f1d337d3f4d21c7a63f854c9f4c1c4ccf181ad4316cab136467c01d96d36e2981c86c476567d83776b733735d5658a48f9897cf1e5052b37ec1c88914736d9f97c5499d73d923b4500694ffa5735c93cdb87b35d829549b1c6383e95fd1902ad2980f2a3a2483ace74b7643d8eae8d079aa0067541006b94a6f913dcff13d67cd9a8cfdf30b0c3d15aaa470b3f8956105142fa2611fedaa43facbb3f0596f67887cdee9142c5090a84e6252931ffcf61a50a4b92853060e8b87e10cea8ce00e4665e5f931f0e08dc5247be1aedc79ebb7c20162fca7bf90e5883025fc924368d42450b4fb7a7e1910edd8d295f03d4de03de6051d1fcf287f54f382441dde00cb683a4048ce54d4220905724b309c799924b854afa377b801a0352fc4450b335277adad761e48a1d1dd378936a491e286cafc700b48edf15f1c2d6edf1a24e0e51b3985564eba969cd6ee659baaef746e13aba64afad58af52f428173645757a407e1fdfd989380c02bcc3c37f4890c08eb93162cf78bc3c7453f3f992a794534c07e3968d8270aa191f67800a0bb3e7bfa54b0f6fa53ee8fb136982ce71f40864b54d00451af3f5327422421606ea10c0d6127c02f91ad3aeb9ffd6111225141448be8240c42973ac52d71b012fe8ef41f00ec196c757899ef8c00ef8df445c565469d84bd02c7fc41bfcdf98951f50e83f19a000a9e453f62167e7350f92360831bd7c5222b670616e4b6ca8a23550cad8ac0ddb7645e2b9713be7e1d768f88eeb74c5d32d8a2f427f7216c767a66ba01e6449ae435c24be7b9d288ad75712c92aa50618dfba18e84e88d46068df0b42af892925c867eaf4c639056e9951d009a939f759a8a2c049def0977f61ea11231406cd10956d165578bb29e47696769a580e0701e815af3b504e1358d037e59f6665069ec907b3cd3d75580686146bd456d6c1d8fa3eff94456b569935f5eee525831cae5d78c7eae2557cc6e8ec67a3657e80a1f6776f3c066c60be9f288cbbcc3362a29b6e047f66afe5077eef8365bb38ce94cab02833ce47d4a098657294ec10b299742222d0bf743ffaa360ecadc37b862c62ada06c8e732c7153b4291966293586afb50198cace9b40c8ea9714325c8a37050824fa7562d2c9252c34a9845027d66390e956b25e166c4495d3ded3f7accf74763899473511341298feb189b7ed34e567d72f1df4bf697f7146493fa5c23691227b90b25122c540df0a6f2ec06f9d89a3f771e9b8ed747940855106d5ea71ba89e8f20cdea69a778a59e4df7928c035562331c8e162b8fd5ebbd5e2b37bea7af069071040c37c1a1c37f0760fed7db7fa70a9489403004576a2cce90a394b5ec58b2e5d0e1af8b0296d0bf02c5597a43354144335e06a801c6e7c73297dfe0b32fcb875338171dd1eebeb123feafa32a5d8c7ce58390ea2df26c68888daf3816b7d0297a17b5f5d208de922e773972b95e6965e58fbf64fae06f0c3896e0b57890dd7f3c64c3d5cebb6a744c59338612271820804957afd67c50779903e7c53d115545f066c734167189b557050ee814a88f993d9ca56084d09b78488a3ba1d8a3d6b489afdf232d6d070a1b5060caa443d0c7ee51904547faf5395cbd0ba99480ad04ae0e1915bd77fa26d04175b00fdc81ef6f37923724927f08266b686409313dc13bc399d1977b8f6588c0e087210f051cf6e36e14e32aa23ba6ae4331f2239e705f679542fbd4ed2bf3191243681f827896b1bb1c4b78b34c487a4edfa97d36d62ee3249efe094c3878adedf9f2b17d51199804f429cd704a7c86c850ce15d3c5f01d1ad17ebb6c2883f28e815bc452a56079805a5dd6900e55f477ecac2143f02ee98c8d9b1b70393a170ba254806b4085b8df9ca04071842a3af933316830d53a7cb88d2a9823bcdfbec8f18c86ac3df894238001ba8fa313f80cfe75f7cb5d973cc77f321f1952f305018c0bf238b80e32119906066f64e645e2bcad7e4cdbef007f7e9ad8a31838b9eaec385e3f25e1604a6d46499875ac14a6cb355a3d4329145803c6bfb82e29ab0299f917a74028157358728db4992e90420d2330d69e957527f5ea7411756ea6dd81edb0e0e6f1bd7688f", "53ac006a", Some(0), 0x03, 1256716825485252, "1ae745760eb4e4dc87fa633200f9a7eec275594c485f34bf58ffc00a947ae2e7"),
+        sighash_vector("0400008085202f8901d68653b4d777daca6591af46127a4d35b009b6ddbdb237207533d5bbad3ba1a3d6b0893209516a53656352516a63012eee32026ea33e652b53000001536c23c8348f6705000553ac636a00e2691cd1ce771909780302de0778040001bf79c2579fa7d1701f87f9feac258f7c1577272d2a71f296399bc90702aeb5145c3684a8963f498cca0c1ba89c724cb475cf39f7115cc08f897871535f25df3bb601d353a64638c90847e24ce1d7a8d0afab74db88db5ca102d5e0e1aac7ccf94178aca31b13c37c9aeb7f62e2d85897ea2e2a2328ee03c97f2f3f4d20a8e73024c5508eeebd3a126731cdbf21fdadb14b4e591cbab144bec35a72acbf9484f47a10b91efc0427fecf3ffcf169d70059b40279ffa02c51067427a0daead6f94bafe4c1233a2225eb56003fc385420d5a9ff3d5915523a08c87eb2ea66917233a7325fe793f41076d64255abd1521476660e95ec1081e14793394fdc0f4dc57faffc5b331e93a82151ebabb77869c32ae6e881bbcd36f9fde0b3538b2f8ac5ff9b2171abce1117c4beca7e87e878423932089e00c06fcee78400de6ce307a7db5f00ec5af9166961bdc5d4aea19d92a1af74ba398673eed9c76e33810494718d05ddfdc007a54bcd1cc4c9740f7e53a31681d2b2c6ede79281149eac30f6ee58301034b1d8e3fb2a3e5910be697ea8cb18435cfd40d52e2ee4c4af22d20097a99c4e681b0a0effbbbf74c99399dca6902a074c83335607a0c0db01ca3ca2fa818577a979a833a48e4d260d640f1d57266db3bbef3cd891d07b797b7e81633deaa109704439327644631a7bbc1a841f365830d27c8aa4d75c80787bd10b714cb979c1b0f3f0b41ee942294248c485cf99c6bc463207af3836197835741415de61ff29fad300182714c20ca34047bccb705810ffae53a3416a53f28afc008e8bff949e43a5410e6adb665f99fa4cafac2e0f2c0f134bdba8381c2bbac43332acdcb10082ef343a35ac64f4ba16e4957c71e9a2bd9a5cd6a92258a9e588e021a06650904670da2c0e52c524f6e5ce3ee275a0a63103b5f926416c0bd5da1ae6569d3a4ee4dbc5ec08b297202c9d713abc3474de4940f59b1f3fe0e9276a1763b2dea3940b0c1f7ab5da3f455623e049682d092189cb79ecfd43c3bf10e7f2c8d4de3a736f869f08703c4e59f574f77aa861cbfddd07f77dc24a97410afc7cfbe3ce1ffd224535cf305cecc7856a4d48a6dec17a24b6d27fe2664bc2b2b711d6713906ced8a8066621840d90c23ae337730679d2cde3269ab1f42ac03ffdba032d32ca879638256565de1d2de39f56f945795d6e958e693dc8cbf6d043000cc7a4015f02d0fe397ec57f8fe292e851424e8406d38ddb8d1de9def672e927d3dc1f411dc78ada7610091bfe263cd7996d1805ee491e99591d6efdb2e3c79715741d0d472ac11db78644f3d23e58f0b01a861e08565535207cd5e710fc33eb2f8928bc7d4017e4e56c0c2eb9585d699745e3bb9618b2c1b90f2351baf276a7017b0fcfacb52ea273195a8dee1677913c786cc3acb06a9ec7a37b058980ceb3c82aab03eafc1bb88cf7ab798f1651d67bf2230d534ec55231d21317b1cb30b3c38ff8d211b7636702a25ca7ca1bff1f2c158c6ef2213ffabb9c09f5c47e73bbebbd37f3d3ebc24a665b29f10de8b9cf1942d90b4c31d89a9883bf5a027e920d1b85119f2f2f95fd55eda8575a4db626905681c29e8d8e741d420a83442a9d38af4199ef95cb30bc44e93fe4d0eb74222fc10ac8d400e10ed4e56fa39da012ac18dee4d99425c8f714c51ac0c59917ecc6665dc88569702e2adb00ae8d42d7b7657aaa363a47b55980be013b913a64115bccbbb2ecc89815521e56e07c88bbb4a55e9945d03db2da0fcae3c64e2cd3c99b628e4494329a8561d6bd92082552324609c23cb62e145575023544322be0fb249bfd3c5e7fb3837e9ff2135073ae1360dcfaf5fb678568fd84d99a51f32eb94ccf5f239025b2b97be020000000000000000000000000000000025dbb67f20c3e0d95173129c063750395213414924e0a3fdd366ffd469c9ebea79fb76af10ea45b566f1fc92af48cee211f8e1b058fb721a8b22ce430c54940e24b3308e570ab857250d10cdece105071bc866ea4d6d5c69f95928f39f7f1fcdf15acdbbec67d848f7c1b2ef577f48a70b4bf3d8a78814316b3d7fa3e3c98cdfa178b989bc78de8d24c1bbc09d207e11181e591a609abff9a200d34e1ac63a38f040053a320168b823ac766e026cbe1abf2755be0c73c8fd98625556406c14993f6a28ae4bb3a473a18dd3743d887eac548eb7ca4d46157c62b729f366a95602287c8c56335b78bc689fc5389c3979b8e75daf31bd60a9cc2a920dbcc671dde27eb4600f12dc2ab3944aa19c71a987d8713d99a4ba9b9a19a921606c5620c167d4c7f4a28a464a9d16c4b0d74e0e75df6dba0e1dfe601c04c8eb37010e13921d5b6c93b9f0c3ddd32f7becb2d77d79a1618a79f73c459b0df5297f8eabd6ed06fd2340e8600a95d72cefd12e622c57b457a4e8397593746a6bcf04c49c6dd4a33668da538d9093a450a4d82451b612ff5470738e62bfdfc79b3e31bb47fca1e98722a5983affe5f632840b923ab56b1da153d35d822324e7d56d613c73ebc6341ea03bee3ab973e84d8ffc4a7c581383e2142d292a580b6d308343dcf1ef4929a9e3e61532fcffb74d3019f4e2d6d31178575aca941299225044e1d37bab9f10e29fd96f9cf684af98ed648b83d61e525be32cdb453d2d38935feeb322ceb9d2a2e95eb7fc612d89f4cfe893228e8828b189009045629075c0c2039d5a7332fdbcd7c7b091015c4569a300532356bbad08ffa3bb167a3ebeb46266b70606494adae9149e1a64c0a0aa5daa5362d3c7a896fd527808d0a3c1cf7061ba6789398078850be4b9940e01aebb936dd81a318204281d43976f4e0fa207e4be1fb82c91bb2642f736856dcd5aeb75c50af200e14be5b78ce69a885154efe30edd09ae8c5eb53f4b8b7c7535373c0fe6cfe448a9b9f4d9e3109303d6cee9106aa22bd59ae0e027d3256a75b9c5d60709099753ce572c9e29dc92562d1c3f4a0b4d36a6fec21ba494173e44d79bc2341895bd0c7096f0974f1267fef6721d58b8c4e334f14d86c0ee3bc4598c6264f93a187b1d4dccd18f7c1e620befa58f97c12cd6d73ee45cbb3d5ae89d5de3240910c59c36ec8fe99b32491630ab35b124531d9c29e046c478e62ac0c0e97a7087ab694a63158704bf6f9723dbc4c70918e8904b224dce849577a488b7613d4c393cac2105b28fd0467a0bf023f00d1a17f653cdb6b5a83e4cf15c347b34b97fbfe6eaee13bb90153afdc9112637fad1cfe17eddcb0c819e60d35039349b69f7ca9ba64df9f5e471115cd67926bdf16e3012398dae595bfd25f3aee58acffe2f3ed748fdf93a6ed21e872d9497a9f3b7b16b7ea9ea19f2479e4f8b6d423fa15fbcdfa3c99b9a3970ee74a8d85ec2159652daa767031263bb4b49285d705e24e8192686ebc8ff8598d24b51232a9938565d0f68be7f3a53364acc6921a35bc59910bb71fb58b867373ce95f198409aaef97f401e433004b9919049f937fd776c4b631a5912a08d49fdf6528f81a6f3200093767bb7789d95a75030ac1d24c2c75bd60382552863f098d36bd4833283d3a2d215d10c7ffe9c84037231445583329261674193bdd1c6481bef9f226e1e60bb1c776a4be7dc69b4430a75a0cbd55867a6f46ff9303f9a29b6f3f7c7a9c9fbcf747b23f8645f4da3d9f72d0d876a75e548a49db375b40ebe1bbe0817a9949dec1157d62a71dbfbd9bb1d6551753dff5bb7fc93648d4eb6cad416733adfdcc8708dde8be8734d05dec9e45df3fa45adac41a6d23a224a04fdc0d967387980f95e627e6b3dce19caf0109848ca9daea2e246e62c28507d256ebabe118f1f6ef976e4a31a0e4143c4360d8b179b30e4bfa7e161b1e6c707d8eae7628715921941e7854e10d11991258c43fe6c44529f6614b5841615d3e4e77fb09a6f020e0b83228ac1755ad477116decaac517bfbcf6737f5bb99e007eb6400766b6cfdd737e20857df3c85ca16ab21177b531e5532c445ded00c1e96635e9f500ba87644b8c1d5332537abf29fccab8ae3e3882718826bdb8dbdb851a47705eb0dec2d5ee939dc7987256feee67f099028f145e20bf4889498243014351373fdf633018d217c588c52986fc524e79797ab655843c261ae7fc9cc3f47054600e4cd385c467a788a9fffc37e9ddbb5d3e8a4bd0c4e8f56e5695afa90fe50ce0a3004fed712b4de15ad5f0171ad51edfa54dbd48b1fcc5ef6ac73cf0a28e9d93e0cafad8816761b3be638398c00143338ea27a9fff22ec4731636961225ca4900000000000000000000000000000000e013a6dc802bc7fb77cad10acafefce5fa9a373563b3917a3a3739cc9780ea815073de8eb42e3f6693e852befddedd619129d0aa13c4bd838622b5e32856358e6d827878957e5d156a5f466ded003f7911876aae40eb9ecc76e38b690422abcefb384a13820d047f9f9e660ffaa77180cba26f90da007cda4057a6cea2e26bfde50c7f9079880033ee28777a1f8c9c814ee325debeebe4f9162b933471916d764160c49a9f1fcf85551aaa7fa67b0136cd119f87d8219e000297f02c0ce6e37b620f5e47fca03acdd6544a47f4deef194f959adc368b3b5d27d383fe2f2b525dae0450550635aa215818f7f5037890f053233f9aa50ae29c0556c36d67b2647e54ebe7588e1f02b3c717df0298430ec9d2bb114b3542b75d010d934e5896e1d2d10a0920119df7292c8c2847650fbf428057128a02040eb3e32db50ca7d8da7ff4c4a7a0e9cf4b652b653d428f83f4853357841b28138055b9138117790a91e28faa412fd7d0733256734485d1d6d1a98cc2d7c82b379e60725d318c1477ce496c95863108a1c7e4f0200b7a3c088de77eb4bc95a1c6c839d75fab5940d3079424d523d6d9a46be54e18f529dc9e56776c5ec451ce28079d37826aec4097ca7aeec8083ff5c429569f9153f696be62bd38a3e727a68accdfab029b0b21e6d0cd460a57d5f903da18a60786b391dd1f5be949827e0ce7dfd1e08427f0d3c2865378c73d46a73c554a129986022a4f38360c39eb9cdd050f56ec059568653c7829e0a44f2c7010adb673e8de7704e54c03a77ab78e85b63f2b91185ca5da67d32830658b54bb33587513c42e03b52ceb9a1957a9e905847237ce4456e533506826490ec5552b3912db1c880ed471b1092998dcc16fa98d5ae9e76fd29d179fd7365978c080445118801ac10dc0f5788f47866934b98aadb9c68dd88483c15d47af8ff42e6bfbb8e0e53a047e58e5ba90d1db1ea126017c656d011c687bb04f47a560ef7ced231b24387ff4019043cffd67fb9d892006c3917fd7a96fe03d7beaa217128d71f0a28a83787a86cfc93369d0dd5465327fc4294dae81c4351c42a6f0a80eefa61db6a40bb681f558f81b101eb657f65727d617691b8bee3aa7e575b411a0128a3f24753e52ee349004cf6d25fad6c4681b02a2e19614e80c958381362a91d3cd3b4e76583294310c82411129ac97f2ad5a5b9fa864a9c5d02d8c92d64244fa6c409c21694862c4427dc51aec577f6ea3380503139991e6e8890987649fa7c43ac803f689b69d70abd7efa71cf9a0f2a41df9418976a4ffa44f437592f19c09cb4931b3d3cd015931cffae171e08ac5928861fcc32e08811559764966bebc141436b917c5271b2c680cdc502cbad527ac087b34656f755dfbf0ae5aedc80985f63d0ca44a762f9b311f156de627741919998e674466c77725fa04c49eb187fbf75e5f7cee261e3075c2b2c2812fe83232c41a5f10f40b911ebcebb78c91c20b82c0050fe2ee104b3920ed0a05d17b060d99d5870198e63ccf51b15df80e87acbd30126cda2affb8f1cecb1baa6a919e0a978791396904449ade4b0b02920fb8c0bf7fc082eb749873c10d17dbd91ffea93610eeea625790ada28e3a2cf22c0d4ea2b92641f216d3922c1fc32dbc1e0e9900386cf898cb8ed56c064e5b12b026bf035dfbc4eb92ce33f82bbe48ca945f12448310d7b9db85f1b046dc9c56512f61e0a3966fa4ab71d15f4e23e4e31cb962106014c4c29ec3b910e0722dac38aa4dc81e176d72feaf2f93f9ecd504cbaf9559833009d92c9d2f81687bf589a49366cd0abae7a174a48ff76cd72f02b18af8187526d470949cb8d93efe6c5bc791ca93b110c1825b6afb045d9d8ca351f7ada328fdd52aec2977d2940e2cdcb2664d78b76ac0e06d788e57f8244f442c886a8f31137cd7f19e8221a385cbfb3f7f2a1e79504bcf1ae083b12902a5012cd5ea2fc85643ddeceef4ab959343219b0c63dd0a8b0e233efc68fc633073e66c59975f23524b6aa1ab9ae7b133d5f30cf9e1d0f9bad71f673f5b754cf400997757a6458ad3b9dc8ec0c69c6609663b42bbb0ca1a55733742811f0d7130e013fe2f88058ee89b90a75cd069daf10037254d1016d3acf7e62f183b2c551a5990e4ed73dcd894f78570fd1956cb227c650001f27f9423f4ed12560b2e1c8dbcb4c30215b2167f02efeb707af1b5c784b7f58b2e517303f3af71b1ee39a9ae06b97728e64f676ded50a3f51bc9e01707bf57956f01b7da7c23e693520657286fe73eee9eb1d583752203f3d92bd4047b83fd38f566dd25b96d11b7222b6782dadef5ee7882147cbb4fcfe70d2ca7f39a297b21d56d6610e9da9d8eefdc699e4a30068a1457cf5eaf69877821d39ea08594c2fb9eb9d8046450e41303f195bdc905e4f2583c6ae3861b8719bbced1ce58c468816d4515e6097b3e2e8182210f6c1bb3aaa62ae0f69f79fcc547baab311d997c8495d6abe3a51f5653f31c5a2eea8d319097f3045e6c3c3d8c87c9bd55b4192ebf00ff8fc7f41e18930a9972a34d9e6aa9d91d2e2817eb6de9ba389e69aa512f3fb4dff8ca1ce7c9ca396e8a9d99d496", "00", Some(0), 0x83, 2067219635092102, "96a3747baa72a220ebcb2936c55064b2c3d84fbe3d5bede40bd94839fbcba89f"),
+        sighash_vector("0400008085202f8901a379286f95d39b9581deb2c53775aeef20e7bdbc3b19d89baceea13b74e6c7f52089397d07656aac5200636ad62781a002255efb98f62207000352acacc0e174d2651a060000cf23530c68dd0b04473c61df13a804000409477c44b86e2e1724bcb0d25ad049939a649ba72461d6a9201f7e9713b73f8e3b691a3d96c4bd56f525cedb7fdcb733e767062fd8a4ef1a4b715e5edf7626144e285f2b3c4e2cb41b7db9663582ad65a5416e57f7485f39c05e8e7af96b3678fea15ee950c330aca3f1e51c69447472f26a3d6741bc67e92e00a083b6953303b3731cf2848d817ceb77f1cca71ec91391202b734d548fa3142c37e6fcac5192fca28d63981f67dddc28b31fd0b93a7f2188c1eca2c1efa461d2dd7338df0705ae701062fbcd8d50299885d8e3d4fbd6a4f2155dc8d8fd0b058f3c775083f59612ac6602d9adfa49e2602a12f2900d22b99c0b8a3268a019c0ddf3143e8af41307d9267402130859ee92434d2379e94bcbbe561de04292b532abc35dde53d2ad867f7ad94200e48e503e7d416bcf98299f82fcbae2dc42aec18a293b63795b6863f32249cd205e54d7cb7c823b0074773596c1c533921d3bae11fe1c6bfb7774e1498864f3b626d4cb144795d8f359f5c55da3d711704e742958955eafa4b7d0910aa003e7570443886dd7991e0066b45e6d1b0a842eb52f3e2de30dc445496d952f17684b7a25301b3d575deca357ab5df69a9c27aaa7bda4bed8af97db6c4846fa2cf2b7a9643ef2110d16ef7a370a5a99c0f73dd207b7bac52f367dc4ba9f521c2d4877ba6898b8c90c6da733645cfb78c6f40992c720968fe43c32bb59399ba88236064aa0a68f1a5afaaed0f539c24ef9e69d37ddba2d1586c03b524548d8207da95892c00acfee51b2424c2d1a4d7d4dd98a1c6f2a5f6b39206430f184373a96c6aa58cce2e1c504d40ee9efda588d43efb1da53c73d530fa76b112f33b4afa941cd1e205acd72ca8684ade8333d4632ab943d690f132bc99f5c5a1d3ea4e0ca8f43238cd9eb09c8bf11e918a9c7f883be94890656336667954a51a8aecdc4cbd39acac752056e71cc969155dd656d7959008c0ecf61832a5c44e2e0de68f504c177df688bee558c6f4e5ea5f9ad78267340e2c835b974dd12cdb90508876034ddde0d97eafaf970183490cd22ea57b98abd1a7f79e2cf23cf8d1b0e9b2dbf256535e93435aefaf64d4cc41e833244db1427fd7304bd4bc24118c7b64875f5ee25a6d74d128c49b40a19741c37dd34616db31eac0be7f53ffa619f45181f5a4dbe5b1b48098eba2c2ec20a0ac0443ba8e9487bcf7dc174bbb5327fd1dc41406dab9f19c8a1307a881ddf4f95789734fcc165ee1e044085b6e7a177508c29da0ce77ded750898de89d260d370050244cc75e19834525fba56900de99385442eb9ec9a5f182b875d70b5b153790a1ee79c0e86783795fad4a7d80da09845fc922992c78364fffc7c2f2868b676d9d43fb340dd0623f88a063acd03acc4b70db4176eb6414c700def088e41db7e2be3b2e21e87c465054c3f6a60e2b76e1756088b87da839f772cbd0f275c9228385a04bb50ec3cfa9ee2e15b153d4c85fe50b6006258e9e8c25299c09df8b455466ba25f7e4c8f471437472af0330516ebda3abf7b691e63e9f9a6173d4de53af0c1ec7ab6741983e3845d1c19f8c2a0d9b98c8a2652150bbf5c18e48845563ccd9edad912b9adc621278992c7369cc4a736a40f783f614aa2a66af21788d9d3f5838cdac87c6cb697300dd53631ac8830b8006793f89c3dd8bd59ef2957b2725670ff775b2a87890b1db88455bd4e9d6387318ad6719d873a454da2dabe8a0f6e5a898ff41f3ee7e250edba60695da47faafdd626ba7e9d4896e4b8a8a1a1dc215b0a25eeb04ed1befb5b3138c69fe528e7291123fcdf8a366c257d329538250a0cb7f54e1c016ce1c623b2e276a52c6e41241b2ac509373c188140e8365c94f58c63f27ff8e6e869a985afb61e97d8ceec2a7824a5c107b0baa4d6e79a6c71872a7b3b17ef918ae4e25f98a72db53ba7f26e408bd4d1f9e3474ddca5833ff5ff8d11b1bf1e2bb4d1968a823888bd91a21a76796bca4453e2892d1b6e1363ed107a9e7ed93fb1da994a9d4e7ec92e29a687f218d28a7646069bcacb4da7badf4eb1331aab212b92c6ea6476a0a09d6bd2e0f76fa87379abfd17582f3eb23b86c9669f86737048d771849b8f70bd8799013be0bfbd7b57bea1a49a4a39147912d7baf68004d415dfba473d7281cad4e0cb00010000000000000000000000000000000060064d026ddc947641ca85e3515988d95a52a6f2cb10f9623288bb76051bcfa006a6667260b1d976fb277e864ec56a0e146be989da2dcbeee33bd3e7b34bd254ceb33c898121511cab3124e14a8d545ce0f228b0e2ade63517593f7be66948c0857aece01bc272295e60b1806946c93bc8c7d2a2edc37fa37c477a69a90b59b4c6912e913a57efa9d54c7e80d5ac8a4294d0fd31a402e4b47ec7bf0331b2c9a48f44573fc7e7f102ed48c97508cbe43065a9e99fb4ce1362bb8a76b1419d95030e9c24eeba9ff8cfda957b17098cdf8c9a919e47a13a5b3346e37e827cc83b3c9aabf2d0ba17ff3d9e0d223c41c88ec2391c76622d7bd62117331e21ffec3272c1e1423982c6b63aec8dbf5ca2dd15810f5342af49fad279b7ca23ded30824799630dedc6db724bce1113621c4a6479dd555f485217cb567139eeadd7ee8dc5b2662f1066a7c60dee0093c9246de7a05e8b0f6bef0033dde2e87cba68d236ef66a23d55e7bd28d02599cca0df7a900637bb3464d622b7c9c9c8c91468974880164def799908a11a591abb3c8d8bd9c12b1f6f3cdc9ed8e16e57d2334b217797df19052feebed6cdb99ac44ea13afeac4377d0fa37ef516ddaceab0d9395bd440460e28b5f57a6efd37d268a864cb5ca34be287e1048efc1e40cdf4fcfc024cf182038b9d80ed1c07636200c819a7e7c240c3c4f7a91732e3ff13e2a56a64666610cad9841c1a934fe933b0f19fb71d061c58f21a4981ce3e68c5023903608de58302c6c8def4e5619ec0d91cf9354475972bfe0d7575602aaf0e9e885c6baf9d567b1fcb63190cb792f1d871611adb4f3d1ed328026918e28d2fd45ab9d370e7292ed754ce29fb787fd5d09e6d47cbc80021abf7d2efebdbe0add870168f51dcc40957a4a3c8e192601383b7684136dca282623f31ba7ae5366b453c6a26f68a14db6559bcb10237379a27a9502ff9d64a338320751530f1f892a6d46f50311b5e18f0336fc477215666e188933c6939989f6e6a3adba22996aae6a0fe1bddcbe1496d968de093df44a3300f7515a12c9d82226d6b4d62c46a213d5f0107106fd2a22d3b598613db491f70ccb1f03b8659669ed74434e43b771f22780710fbd8f2f20e9897df5cc23548779c6c0830839d231c3ff9ac54407dfdfcc59014bf67d9685706a5622e38f7a933c34afbb6aa8cdfd93bd2ec91ad37904ce13b8ab8ef772366fad3c3ebee8f2611ee7b6c2af7e653efbec4dc4cbf13acf37e399e2b0b05b61cb7e17b15627b62962e2100b195fefe94bc484e88139700737de1a5ec7d9cc85d533b61ecad869edaf4dc7ce672d5dfbb27e6976251ea71542dfcfe11a01f22e6cbebab7b257f579d1b5dbeae8f5388e122caeb1417533ad3cc5c3afef65ce8370db75bddea7ef8ac0f59bb0caba6d7f12aafde4f1c442aa01f5a4b896496b52a14989c8f3af4e1e3db706a34cb904ef08d9c82c55bc728c955b120bb2ec373fcffff3c46d603ab387896d49cd21b2f77ecfbbb02a5e153b171afed986c15da6f2d4cf745d1995f5136e1b3e68a67a8996fe765616d8aa11bcd9f8b591db87efcdaaffd41003ec729360542620854fb04b80cb861a636a4717d666894c32f1f2bf2247cc415de1d0c4e712b958842d6a4b276dea5db88423f2b4c664b1d2b1877baf33747343614e5ebe9b7e12ed0153f9ca7458e4da497639dff1352ff0efae01d140321c28dd0b67b069890f6130f8246ab8544717532d3a5f63639a99d7f8e9831c64851b7ef6893b3c9740f9844d18a613b5f9a6ab4bd6e6a93e8e4bea5575d2cb4330c0af8558319a909a5988a992e406343dd1c742d64cd4a17a2f3795e8db4d30ccdf4415655eda7b437e3397323896b11b1bed72d63e310aa49671d85534f6dbc181febb5bdc08ac0d123829d108cd269f3b0a396f4241e7dda72f54862bedef01c12e3c6cfdf75f676c2ddef91af7f8a8a769c25e177cd430bede74b57690519a98db1fb5c361280f7540ac827a91b2d08752decfb7156fcdb617578b053eee41f66a60e045c3a569f3f7edb7631682fde9ef91ea8811fc2c78f646af6b4710edbb8bf2328bd3273a2cb72ffcca7c217b827192dd2ea929e976d131c9d202ec506a35d93ab216f64bd73fe5d8abae4571f85beb84a7f93a3de37a451f308f7de6ccd1a6eefef24699f2158d1261fe25182b502da3e74611a6116fc3064fa723c5a81adc0a32f1ed6299157d1c11c0ad99041894696301d5b3f1bf43205d7dccfa68bbb4a1f5e242b3e690bfc97b94366a343f5dd16df67b2ed2be21c747118872b462ee20c778ced856fa980403fb24b786137d0ef0278539b00ce6e23c07ef2a07cb24c51c5b485e454edf661db4b931ab8cb494eb394fd13c1b32085f27b204a4b87ee6c806345d7584cb161006ad9848a24a22a5771e3a2ab65463f553d52cd535ef10bdd40d8877372a532e3731b0ee90c04e8e43747cc3eb96bb879bd94d7012af46a93ba177037f062744d3fdfccd36aabe0f8ccca19dcf7841b1ee2f4feb1800e75441c51e95cce94ceeecd8587fbf574308dd763631b7335783091f4c8b3c8fb3cd93970cef0ed", "5200ac630053ac52", Some(0), 0x02, 1872548396205738, "ea961b51ae03d67c7c656d2d087fe65f9928a1d4b68b0a0045a63307c0309dfb"),
+        sighash_vector("0400008085202f8900026231140f3d8b0600076a006365535351054df874099904000253655c84ced7b19f6e12e57ff83e62bb03000232632bc61e5484321d965ed82d541eea390220d65a89f19ac39a29f97e98caa5c2dc15770d33722b9c9dae946ceeac26a182dbd398d2a1be9f67e92a5c1fbe6cbabcc2ef1aa3c45b6dad63e297449ba61a00e6fb98436877b5d7ee3a79af5aafa07a8c392d917759496d1fa0540b7f4ab4d188528a5abec413abcc6378da6a13bfd5802f92fd4c5f82761fbc6809da613de2bc48f88776c896c20da4e0a8b92c06bb1cac6e6c1e7ad69e4acd9addab5b9f945f753b36b0e44372623ed49b0a08ab5f9345488ece9eab19068dd6d6d7d4d9eb6b5ab00a9dd9fc53d9e8199961cc6b003efd201406333f73dc977a1e64580b763c5021fe62d282a7a33c3eb45cc3db0d9856203da5812c8608d4b0596085e33bad28c22dcae7f204d7c06e3793f41ad07c167f24e867bf8dd54ab9691fc039f89955987bd67c2804605313b59761c7aa0a4b8a8395d1653f783484c2cc5dee4acf42ef41b0f6b023e3687a09888a0451d87488aa9e8ba628e973b789bd05786f470f4fac2a65184774bfbd6107aa3bad66ba75a7331c3ae75fd8b6a6e16f379d84fec252e842e6e454e51cff8bb03c64d28012affea5b76fedbfe596a7516b74a3c8a05d80d89bf86839595dab1eb994185299b935dcc5818d4abd4ee3f819f53e420700573ee9450226f95464458d33fef15e43f3cc4904446de6a189762011f412b53090b4da8b08ef4c74f3098d3ad00557456e1c3639e4460b0c3cb278942c362ac452701451428ca899988b7f4257b0da6adff1bb4217a59f79bb9444f269abcc3deba9ffe76499b3ed400c9f78f3adc6ffa0762a38e4dfa43c4b64df1699941caa6efdb982b08da22e987050bfce65017bc659c3fad3fe8b3ff6642ab3a4f2e67175e596484a45871f1b41222e9386a23285a200ffce1f9178af3c36d5e82b75849d07d2c0715f1bd15c52b9e9139d2a0bc4a4cb843f760c4f08aebb697edb26488dba71fa851852133b7a69e7eef4f32a4240748f4ec932d6790eb836f42e4ea1cdbdb8abaf44e001dcddeb40d2d1fb7edce30997a16076b30a1f9ee3bd0e3c9b4921c51b6866868f66820389f2c3ac7d2a743ebc1b8431313d03260e67342c2992e552aca43f0cf617b84d53c21da122c0b95d6ef3b2ac4039c96f9a0d9c89a2b62480dc82a33f71c1fd3f46aa8500e9ecc6635e6c7460979554c0c9a67fa53af98a2387117e0cda0db7e82309b614eb58f51eb08a4a289efdae182588e4d40ac2c3a3af00db4838c62903f93f7b419505e77a5c29686fde289306ecb0bf22677b19bd503c59386937ad6547fa4bdb0800c0591c56df2e094e5c1f6d1d57b20ac08bf89005a3f15d0031969072d6845dd761adb352b53eb472d290e4aa6baa157c4fc64bd79a9c1c5e9c5725b374415ce422f4c5b382bf17b5ae69465d3896577761b3bb142dbd945f5258a64c696237addcdbae014d4098d52a70b01f5f4e31993e0991f9927c05ecf418dadda60677fde9a5597b834babf9921c8b3c19fa0c92e3e86b239acb64803f8578dd90dec4107e672aa638d54a437cb8b9b72470eb385bac35466d26da98c9fc089e9ef2f2cb31f85c9e5a5d53de84017f7fdf16dd9aa1b3499ce317daa3ff0de1a2433ac8dd81765f994a69c8d2d64bdde649ebea9539e39b0a1f212d6c0e3ceb151a92e86fdb3817099b0ec2ecd5eaf053efb207cf05880b0d6672941720285cbb50534e695f4d7d2877875e5f45d3e7fe47cc75bf3909ba57101ac931b7bf2ae1bbf154930184e50e1e7bebc5eb2153bf2ab329aebe0a6809777c6e89bfe680d46591d538c496a21496dc6f8c9f1c83304a2ba7402c7f41c3746c958c3dec1f6cc161e80317384ae04b4c635c812de0b19f96d01a371508efa8993ca3c04e86f05e291131aa15e6e6c9e0209f3ee473a78250f0290068275c449389d0fccc51b3d6e0f83fd728c79410db982bc895d6d7268e0bb7238def5ef5306c8bc6df632566bb349d39e33ea80475930708c3db9413aa86a6d42678c48a8efc471484938f687c03a790228a6c0ab43f9b2887b7ed78bb282263109fe0e8d4056aaaab21ff606c258c3a8e4aa976ddb4d3ea1f5130a8f0baea56ea92dde64fca07d003d4e69fdf3984f09c3506f72d26823e21e8ab2dc115044fc85d0a7e23bf96eb33382e47dddb736b8c39649ab56ce0566eebbf2ca5fda10eb8801362b14828712a7a90345677c35a3a8e51f532027c272068b213bd644815a836a8b4e0c5c2e04f92c36991a6feac05fc69e3bc6e4a2d2cf221d20d90c5d748faf124711184000d9f9777648de14742d2a722f08fca220986011bd9ad06edfac2151ee2460211b450b34d56931994de9f27dc7182b303855462348ec58fe88076b64863fa5f581af4de9f6f1540247b467002a47c6d157475d8de6ccf9ed9d9f3bd04188e015e04a5969f9d9acbc389119efb6938f7c6d4b8c5619cd741ce65842338c887926e251ce3c20e23ab0d10e0f9d338734486158bb56762e21266e7efbfcb85d753c0955526b356124fb1a3e40dbc8524aa2e837aed54c4ac339cad0ddbbb5d8c21ffe189a3a2ca47195d2b17b797398bfaff24e3d66b4071bd797fec17eb434c6f6f8485c3be014422b7ed04797c5df318961a989a15d153b88f4d5c466f9f82278d508226934aa8bc87370555b3b054a14a0e5724c0cc63c83ad98351fee4b984be6acc7b5d7c23e1be32272263da3e779a11d7d625d74d0d22cd0e41217f5623e04b8304f49c36941a32ccce3a81b83454f508dfea7caaaf17f51b2e5629917348a1f6691ae1e2a0f9ad1b4736d8d31d453d904d6172106c774810a75db19a756bab1aeaf3371b476dc25b5a83fbef78a661601e06c943df942b02ae56042579134caf9630cdbaae12bec96bd992fcb790eea2039f4de4b5b6cb676d6279c984586db0c2bc3f762c6c6fb18007e6c16e4d4a683919784060b9b193ab8107e49161553f80ce2b6d1d6ca34df7d9410709a59551094472aa5b216f15f908a47b40cafe9b02a03e7f4fca0a23d1f7fc084a91c580c3738699605a5c246082d0845903c8d2880e21b6dc0f87548926695f735a4ac27138c73802e86edb0b4509a77eb435429cd45d4be06999b97dfa892533b3b442b0c8873205c62e71985ee1200a09a191bcbe5b3cf372c9ba3bd6dd35ba117267d117c65fb76b2f6d51eca1fcadf386525cb396ff278d11b560e8e6dd0123a9d4138bab4eacfbaa440a8093e10f10232463dc9d1da45066b5f8da852e893c99633f204716a7ec8ec96c02c3d545df98486db77978d160f122e8b921d14d06c171026ae35682420f92580177d6e47780d491b63417edfe07ff360fec4d3e1846e969ec020566448bc7f7ba1fbb2ddb78f4e946a7312d933b0843e067ac2aacce8082056d0f0a634ca0ade5fcdce76ee7864a82e441b55a8623b3d0cf46b8e6de26a5d8c83ab7f5ccd4c91caf67f96727cf94e246dfad3845b0997a193d19bf6ea29a3ef5c687bd1c3daf6b284e3c388f46bd3330de58984a87b8650fdced1a222eb93dadf0e84bf8cd55a57351ceb163f8cd56876fa29a44c4fbb19d39a02399b2c2dfabd612944659294eaad58505a713c754fe2179755552c2a90d95de22a7293df02f949efc52eacab535db6c20cac00824f1bb94626054485a75ff5d2980aebb4a0cefaa8c796e1d57c5a139eaf6bd95f2dda8736fe7eb8452332d20a0c0363da0f5891d60e140ac8ec05e2abb1cef69262be35e782d511321fd46c7a21a3b564d56ce500b1e1ed7de266235f374bec030fc3023abd2d4fd8f01f54a6f526b7e229bd34921770062d4ecff68efbb866ec298f46aae6399f7815db67d92d49d016d76f6eea5f1a21726f12880c331449271dfc07878659ffef4259736f3f7cd4a279b39a8a60b3ec27d596d99ecbd3942dc37db9e8fe62ba5b438445990b61bba2183ca40eeaa02634c5c6809b19372aaf52faac3365ad1bd62151d18b76bd3ef58c1a11b97048a19bfb74fb9f6df3870ab2d223e86598bf318d0bf5c9dcafd7b0f6ec67a89107248eca6d4fbe6b38f834d4527b1ddb1a58c003e294914a37a2028b1cb1b4c53851e86f0da0d4b26d50bd9754ef2cf207ac0699636ba277fe95e2d32c4cc443f941aad313abfc3d61beb73ed2572f96981579951a71e2fd003ae8811168c869e6f811b2a9e4651920ff5b1dcd57e9f01e056cae855dc3d3469f84efca8708958c162044933005630e3fa1fbbbf2602c8d85395be83e00905d829f87db60563a259b963c53d6caba854195a5e90a7e4f0cf91c1ea5b6deb9b810e00d3eec7b2b08b77b6d9db5446f2fc38c5f4f432513aa7a0ae82f3bccd9c19a48f3998ab620a69da6ed7db6a2d621598d4c4cbe358173fc8b27a80285de52fb3b37abc1511b1ea38b1477c8ed887d3083604461abf7a6da7583db9e6c2d917fc3ade520dcde73b57882d1ced393c484e09ac7c92c4795c5e6de3108702c8f99c20b65751e1c4d5792cac9c4eaa6a45a1fcf28348aff5035e8a1e6670c415b7e876fdf2e54a61e053dedab641e12187748e06f9e07da80fed173c7b90cf3b11fbcda71b413a7d82f6b7df6c6ce89e37165b46748c74a34454ca1e2ad2827cf361058913823b7d0fc5a6b737f377ea675d3e8bc7d4b6cfc2c7c583148db86e0836ef8385f63d738d4b030edf83a46c4f5e5fa77a3a1a4f74aa9c334734ffaca05ad4a9bb0b2979c232d9146bacd00b079541b20d98e73c3ff98f45795168bbf937c91873ca90430f3a706af9b65a7f0cfbbad299196e7edff5279fb2870d554be8ca7aa353e3ee5c56b539a7a0217dc32d5f401be5f3b13c6186da29d70de664442e10e18206f9b710af0b408ae5779221a9f8e7be6f6741b4c41c1a7d24919e96fc6e66bf9f4e4dcffd98c0c1e3c60eb065e396655ca4d835308b90097eee88be94afd541a4bd9f0227d526dcab3a7191ed19eb1c7aa180256c9f0f5e97ce93e5fe01bfc968612e6ddfa58b0d137a5cb1346f2c4aa73d7c3982340991cd05dae8b198fdc2dfe0ea3acac369033dd7b715a645af7010d5ce2b0cd474f900d17b0dbe6bd31d083ea00942c9020000000000000000000000000000000093470e1f8e4397a1099ebdf250556b0e8dc364626f033275b46baaa78f769d15a054d89f10c6a7127dec4586328044cb169d6c23b0f1e678f6a4948474158277009afba3d0e95d3178879e3c74947b5af626bdbdb3765e45524df00ded7b3cb315bd8589fdb53862b81621a64abcac4f1831c33a4d3152cfaded1261cd23b6a396e2b731dddde5b69afc2aec8d1c44318a801e43791c71c2d6b18452ac5e9b6a650d7f86215e03260de588d35c65ddee102c9b9ee3b0d8b0fcb32e6eebcbbec06eabe37ce101c8546815c3d280f8ecf6fa507c47f2fd2c4d7c0d1a3631c0719f015650baf75b0039f72bc3e127b99a6d322e509e43c9fc00fe8d9261881d0006de2376b714fb655194c094fa428c06500b2d334fa61e8ad555358391a568f6ce7cfb7fbab8cb0d829945fb2673e3fdf0c0fd1ed50a56b07fe368b82111d7e9d4b4647043f73cff82e042220057dade41617ff679004aee184dda0c34461452d5c7b5432bb1d8f2496dcd1dfb7fc341ede316c2237e9b4cddcaf634cd58895672dc0b7f1f8fd572357d6d83f41b27a01e43df8a8fb0f97b859093a978665524e3dff9a5677d1e630cce55312a78a265683fc870d0c849a4fda6a24c0f95016c9ff8adbe3d9962e3dab81ed5a9bd3c49d4a10eee1062f503760ec021d4298c14eb0a265e8c9d73e77993eeede68a41a5782b78962dbaaf3665b018e7ceb4b2b765ed56a59849fe019edc911c37fdd652d987da44e5ef19141cc2910ffd5b5eeb6d5a3268fa3a96a3a20de74321ee8c0a4812b972cfb3162d2a368e1d7ed64a4c87abe84ebfac3ed81262c31dd2908e827bc2c4cc69e5e53841dcf9478c82f6aa24b1d73bbf907f27c96302c510efa552261e9980895db1513fcf022c4fe53ea358fd7132ef4d43af0c23954a9f59e0aa7547034e3656304c7898717dd79753cb030aa82512ad533a2e6d40a3c1a115e44e7bd81d89619b0263c570dcc3b0bf6c93dd3eb82586b081f2937304ae87db26aac265eb381f8b5abbade219e9e80999576569a137e2267fe7fb8fafe264035307880105a5d4b5798cdc6439252f5725eace4388264827673840b216b863a5f64d1ccf9241bed5e1c616ada7cc5c4d8f8438257751103745cf80aaec7830d92f43b988609c6f8717d2d4a3d28f3c87b7da2c8fb3794cc30d6f10da900e3c00a34dbcb462fec7686b14ecb22f7f083dcb9f5bb959883fea20ecf835fced54635ee586743b109c461bf34b965a1cdf323aa04794e26b3ddfea446f1951c3a87c0bc37908de773508011dd27473dcca7f13494fa99bef68baa1dcb6add79506a3289c0ac7d00f6474d4473926e487c176debdf55548dfda5c9f975a33729c04dbb6f96e3ec6df70e742b1055d0d69507321e13f6625b28e35f90e55171494f3bd642de1f5efac515939ded3b70f7b8f4b644b290561ad4f63cb6167f9c9546aaed533d1b2fd0e137f6513669793df8f8254e1b24a5def2560969b705edf520e430a73f053ac2af42ac14d6ce054e4a252cb9b4a7903034288856c7f645bee71f47cc286270600401e5d8b7d446564479ad3f9c11c900c91835ca7ce41f3540084bd822e56ee39deda718fdda43ee845f41ad453026f067f843b14de0a99b396478baeaef59e35158451f3dd920800c208bab2f8129d9f073a105e00f96232f7dace22caa4779157331428e4ca7c938e7be1b2a71e6ad99c03a7195322434dfd5339b12b456f2c595be98477dd3f4e0755068d8ba2380aedc654bfba793e7d175ed61777f4b3cf8af7038a45f8897d0562eaed72a53b343f7bbbc98bb3929da9a9691cb92596aec967247084682f8700d83580420a7607604ed55114098bd6f518fc46d884ce769a2c97bfa956182e003f0b827f410339796ae0112b2a39afa91bb31a15ef9045ef144b43ec60f106b8e7879c130ec4c49cc2f0527e22feebefc49f4ba62b6357586819e42058c3dccf0680f12295ce88b9c2ed8a3dc031d33f8ff6471b6e71459f451d41568522d46590618e618a023608704f31c16a8127585ae9c6311672f62529116dfabdd8610a6a6dcd8b3762849f3df20c590000d92171c3651ae1ec5e8c31e32e668af48741b758c71f5bb1c78b82e0179f3c600f8b5a8fb5f8f21f75e5b691ed0327c92f08b3246172da8db9408e7d3e8d11c6626489934115c2816e8cefb3ce1269689735840cef5cceaf856daa8d44cae69573721e4b8f5894a98ce6a16f832263287419c86448e340c6d4c6514e07d1f8db4f9be01677f933c4b4841dc2af38fc8d4ea63ca029a7f697a3db4ceac38c4260faccde0afa6040da332e106b5de54b974a60e976d1541696ecc0f8e23663506cfad8b1e4e5856b00000000000000000000000000000000cf4db3248c5ce51007d4684a27103acf44f3d48d58d34911b45fe782befabcc3f2554f4b3e75d9de77d986f554eabe05b9aab9ab18949e6b309733c1ce5b348cafc0ce3bcbc8e732e8d4f38a3f6f53b7a9d459792e451e20b4625536858944af2632953e8bf4459e41c32c6bb0d533c9c0d74f14e36c6fa5c131fa93a33a4b0ea910520128556c35ed02887ccd48050d8ad4b78ed68b00d2dee2824586ea2944c33aafe721e89572b99be23a46c409a109b95229e04f347be9d0e7d45921d54987a123b71bc9f115098d5dac516e2ca254d8420e17174b4eef9e7fc3236ec6bc95fe0b2bf86e1ce86614a5eb125d74c75ba3cdbc256754e2baf043a946e3fdfaf0514ae81eeb71d3cbc23cb2ad5c17b7ec5b5a1b70eec16188c3ca5444abe83b58c62d418974723ab07bf5df76b55816e8f6bd329e7a8259c549723d123c14625edb54a19abe7553d980c29a66d0f17122c69a8cacdf21093dee1859161215c041066037caecf8d72107234ee25298bfb0a2acd6936e984f59e57bc1d06c595ae553384233c94c2b73e62b93e0db1f19d92df244ee7d66a860b538d5d46d3fa139c95e37312a7d0285dffdfc05062ae5b865d45b8679a83e9f4eaf5bcb4e131d98c89ddea5f807977d22dc8d73beaddc906e166bbc23124f7bd907496d1d452da4cbd165b907c09a8bfdd79ac1e01b4e96c1dd65527bf0fc41e267c29db1bb42136fcdcb98e6d1f58a45837f3b4a73f759c99501f2856a071f5495914b35f733dfc959e33eb675b154f2ad6db875e0b2c804e92f2889a9653ad6f2f253368a3b8a5122d24e6c0756b1cd5c529e09616a6b9fbb517f63001e0d71fc668ad3ad4fa52a379d5da0054cfbf7031790c5962ad423411b65af89043bf3f3e34fa41f771d07d3c7c88b509ff74301a0ac2e5197110bef4f7c8ddab17b84526762ae3cddd94063710eb00ba8207992a0fbf35cc7117dfc5329d70bf6d6ba1f6cd2b925c14a0a82b0a238c7b56f82cf2dffbb488b3798589dd3ff7eee0a7c88eb618d910f77119abf7319b55fac69fdccd3ba43cbd70bf903984bce95b55b2363b51a57c004350b8e976767c76617a38fbb7b87132a2a17bf40db704bb4870e7b39181285b5e8a804ae0c38308c4849a4dbe70235bec368021ca3fbf085e205173e0e131cef00e9d28baf028a0b297069cedb0c85c235cf6abb2a7690e99330c66447e1230d2dc2af24f176783066109c9628da5eb9483346c7e9e79048bf6063320b0b18f798027acf9488a82dff845d31a24b9066ac870dcc5b6975e32a01219f7fce67caa668c4ba9b4e43a96f887920987d2703b833db608ec1b04e516f82f83134b7b7cd4046b4b73b56cf08711b0895430cdf78860777edc55303f66db699fbf385ceaa5a2f4ca5e027ebff6fba3c5a203b57661fb9fc1be8f883ca5717005e742a499d6d547773db7562e7e77b6c83f170781b35440ef0aafbb00d21201d38e11cc261b155526da0620adc33b45b4e37f91eeee4a5a6897e784206384ab3e14e5774b324024a81b2e017515dfb3ef070e3ee719fda3ad723485f7a3716ccc6e3a358d7752bea81f5079639a51a5f936df637000e1d0146b2bdd6b8feaed4de616a38bb9a7cb31d78e75d1a0ff21234c800341b1e9471d4a6c52733f91f6ccc5250dc5747b274b322091e0db9a5741260032391af5c78dd6ba5dfa1e3088c63851b3e914b1c05ac1e55e3cc0252f76d200ab42524ce4822929e4094f0b0787531e7403d785a01f6c86757d4bbdda8a45fb7fbc14fd1b224d38549fe84121060c8335eaa9b269c6ace655caa4b078febc5f3fbcb95ad90c4b9211005c94ed19d67509795b1b74f114e072878e7c33e67af2ccd6176b0c57d7d6add76ed47345d4ac0148f22e1894df1ddcb7d5c76129ace198f2aafaa158c64b0807e34f4e5b827136b5a72e3887b71d15b1c87cf79a6d41656c3652148bb675c645ed80c8101de6651a77473bac00e7261e832aa53c3cf541fb9f38c2acc0e6c002c135f3e2a03d83e452b16e61e9bebf9d71010033d73eee830ae41da56d288b01868f27a107ff5cee774f1cec73713470257d3be97d931b515eb2d2835ca53f2c58ad4f6f89cde8f0262d8662d30d0b1b87edd98421de67fd442cb9e20e7788379190b6bca22b239ef3b6066663910267d9ba71328d2045c61ca0fb912ff549f8c65afa622d1e3139b0b0468bd1b5d943caf8d94daa116aaaaf26627104cb2f7d25d956281951a4a725fdd8aa04c4a6deebf9a5d874b2763fc2e2ac8247a3b7876c4ae17b83995ccd81692b8ad201dad32f3568ae1d037804029aac6174ed670ce96c6d73765d2bbf45e195fddb0e7eb60ed19a8303a92c466769794c076f1f671744dbdb9bdd74025f0a4e992cd95feba50f554cb87e373c69a656739107bb3eb8a08e587dc185c47f4a8025a1b355724ef40999a53df9856119f597d9b5a200cd8cea17590e5f28e9bcab0ff061257895365b25e10a907489df378e8ef59a3e20d457a03a5edfc4a3e08ec887e13402589d6654277d4fe3ec18d1c36ad1c455aca2652694045c8325c18a1e0ae0fa27dc702b20bbb4136be", "52526a6553", None, 0x01, 1786168836150446, "f4a3d2d94754a2261039cb35d1a6cc9d9b14ddaed6977cd2a3429ec947943692"),
+    ];
+}
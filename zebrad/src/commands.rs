@@ -3,6 +3,7 @@
 pub mod connect;
 pub mod connect_headersonly;
 pub mod generate;
+mod locator;
 pub mod revhex;
 pub mod seed;
 pub mod start;
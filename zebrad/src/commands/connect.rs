@@ -18,17 +18,12 @@ use zebra_chain::{
         BlockHeader,
         BlockHeaderHash
     },
+    parameters::genesis_hash,
     types::BlockHeight,
 };
 
 // use zebra_state::QueryType;
 
-// genesis
-static GENESIS: BlockHeaderHash = BlockHeaderHash([
-    8, 206, 61, 151, 49, 176, 0, 192, 131, 56, 69, 92, 138, 74, 107, 208, 93, 161, 110, 38, 177,
-    29, 170, 27, 145, 113, 132, 236, 232, 15, 4, 0,
-]);
-
 /// `connect` subcommand
 #[derive(Command, Debug, Options)]
 pub struct ConnectCmd {
@@ -95,7 +90,8 @@ impl ConnectCmd {
         config.initial_mainnet_peers.insert(self.addr.to_string());
 
         let state = zebra_state::in_memory::init();
-        let (peer_set, _address_book) = zebra_network::init(config, node).await;
+        let genesis = genesis_hash(config.network);
+        let (peer_set, _address_book, _peer_set_handle) = zebra_network::init(config, node).await;
         let retry_peer_set = tower::retry::Retry::new(zebra_network::RetryErrors, peer_set.clone());
 
         let mut downloaded_block_heights = BTreeSet::<BlockHeight>::new();
@@ -105,7 +101,7 @@ impl ConnectCmd {
             retry_peer_set,
             peer_set,
             state,
-            tip: GENESIS,
+            tip: genesis,
             block_requests: FuturesUnordered::new(),
             requested_block_heights: 0,
             downloaded_block_heights,
@@ -18,17 +18,12 @@ use zebra_chain::{
         BlockHeader,
         BlockHeaderHash
     },
+    parameters::genesis_hash,
     types::BlockHeight,
 };
 
 // use zebra_state::QueryType;
 
-// genesis
-static GENESIS: BlockHeaderHash = BlockHeaderHash([
-    8, 206, 61, 151, 49, 176, 0, 192, 131, 56, 69, 92, 138, 74, 107, 208, 93, 161, 110, 38, 177,
-    29, 170, 27, 145, 113, 132, 236, 232, 15, 4, 0,
-]);
-
 /// `connect-headers-only` subcommand
 #[derive(Command, Debug, Options)]
 pub struct ConnectHeadersOnlyCmd {
@@ -95,7 +90,8 @@ impl ConnectHeadersOnlyCmd {
         config.initial_mainnet_peers.insert(self.addr.to_string());
 
         let state = zebra_state::in_memory_headersonly::init();
-        let (peer_set, _address_book) = zebra_network::init(config, node).await;
+        let genesis = genesis_hash(config.network);
+        let (peer_set, _address_book, _peer_set_handle) = zebra_network::init(config, node).await;
         let retry_peer_set = tower::retry::Retry::new(zebra_network::RetryErrors, peer_set.clone());
 
         let mut downloaded_block_heights = BTreeSet::<BlockHeight>::new();
@@ -105,7 +101,7 @@ impl ConnectHeadersOnlyCmd {
             retry_peer_set,
             peer_set,
             state,
-            tip: GENESIS,
+            tip: genesis,
             block_requests: FuturesUnordered::new(),
             requested_block_heights: 0,
             downloaded_block_heights,
@@ -217,7 +213,11 @@ where
     async fn drain_requests(&mut self, request_goal: usize) -> Result<(), Report> {
         while self.block_requests.len() > request_goal {
             match self.block_requests.next().await {
-                Some(Ok(zebra_network::Response::Blocks(blocks))) => {
+                Some(Ok(zebra_network::Response::Blocks(mut blocks))) => {
+                    // Header heights are derived from their parents, so add
+                    // parents before children
+                    blocks.sort_by_key(|block| block.coinbase_height());
+
                     for block in blocks {
                         let header: Arc<BlockHeader> = block.header.into();
                         let hash: BlockHeaderHash = block.as_ref().into();
@@ -227,13 +227,18 @@ where
                         self.downloaded_block_heights
                             .insert(height);
 
-                        self.state
+                        // Blocks from different requests can arrive out of
+                        // order, so orphan headers are expected here
+                        if let Err(e) = self
+                            .state
                             .ready_and()
                             .await
                             .map_err(|e| eyre!(e))?
-                            .call(zebra_state::RequestBlockHeader::AddBlockHeader { block_header: header, block_height: height })
+                            .call(zebra_state::RequestBlockHeader::AddBlockHeader { block_header: header })
                             .await
-                            .map_err(|e| eyre!(e))?;
+                        {
+                            error!(%e);
+                        }
                     }
                 }
                 Some(Err(e)) => {
@@ -0,0 +1,182 @@
+//! Block locators for the block and block header syncers.
+//!
+//! Both syncers build block locators from the state, and download and verify
+//! the genesis block or block header when the state is empty. The only
+//! difference is the kind of chain item they sync, which is described by the
+//! `SyncItem` trait.
+
+use std::{iter, sync::Arc};
+
+use color_eyre::eyre::{eyre, Report};
+use tower::{Service, ServiceExt};
+
+use zebra_chain::block::{Block, BlockHeader, BlockHeaderHash};
+use zebra_network as zn;
+use zebra_state as zs;
+
+type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// A chain item that a syncer downloads, verifies, and adds to the state.
+pub(crate) trait SyncItem: Sized {
+    /// The state request type for this item.
+    type StateRequest;
+
+    /// Returns the state request for a block locator.
+    fn block_locator_request() -> Self::StateRequest;
+
+    /// Returns the network request for the genesis item.
+    fn genesis_request(genesis_hash: BlockHeaderHash) -> zn::Request;
+
+    /// Returns the genesis item in `response`.
+    fn genesis_from_response(
+        response: zn::Response,
+        genesis_hash: BlockHeaderHash,
+    ) -> Result<Self, Report>;
+}
+
+impl SyncItem for Arc<Block> {
+    type StateRequest = zs::RequestBlock;
+
+    fn block_locator_request() -> Self::StateRequest {
+        zs::RequestBlock::GetBlockLocator
+    }
+
+    fn genesis_request(genesis_hash: BlockHeaderHash) -> zn::Request {
+        zn::Request::BlocksByHash(iter::once(genesis_hash).collect())
+    }
+
+    fn genesis_from_response(
+        response: zn::Response,
+        genesis_hash: BlockHeaderHash,
+    ) -> Result<Self, Report> {
+        match response {
+            zn::Response::Blocks(blocks) => blocks
+                .into_iter()
+                .find(|block| BlockHeaderHash::from(block.as_ref()) == genesis_hash)
+                .ok_or_else(|| eyre!("peer did not send the genesis block")),
+            r => Err(eyre!(
+                "unexpected response to a genesis block request: {:?}",
+                r
+            )),
+        }
+    }
+}
+
+impl SyncItem for Arc<BlockHeader> {
+    type StateRequest = zs::RequestBlockHeader;
+
+    fn block_locator_request() -> Self::StateRequest {
+        zs::RequestBlockHeader::GetBlockLocator
+    }
+
+    /// A `getheaders` message with an empty block locator asks for the
+    /// `hash_stop` header.
+    fn genesis_request(genesis_hash: BlockHeaderHash) -> zn::Request {
+        zn::Request::FindHeaders {
+            known_blocks: Vec::new(),
+            stop: Some(genesis_hash),
+        }
+    }
+
+    fn genesis_from_response(
+        response: zn::Response,
+        genesis_hash: BlockHeaderHash,
+    ) -> Result<Self, Report> {
+        match response {
+            zn::Response::BlockHeaders(headers) => headers
+                .into_iter()
+                .find(|header| BlockHeaderHash::from(header.as_ref()) == genesis_hash)
+                .ok_or_else(|| eyre!("peer did not send the genesis block header")),
+            r => Err(eyre!(
+                "unexpected response to a genesis header request: {:?}",
+                r
+            )),
+        }
+    }
+}
+
+/// Get a block locator for the best chain in `state`.
+///
+/// Every item is verified against its parent in the state, so if the state
+/// is empty, we download the genesis item from `peer_set`, and verify it with
+/// `verifier` first. If that fails, the block locator is empty.
+#[instrument(skip(peer_set, state, verifier))]
+pub(crate) async fn block_locator<T, ZN, ZS, ZV>(
+    peer_set: &mut ZN,
+    state: &mut ZS,
+    verifier: &mut ZV,
+    genesis_hash: BlockHeaderHash,
+) -> Result<Vec<BlockHeaderHash>, Report>
+where
+    T: SyncItem,
+    ZN: Service<zn::Request, Response = zn::Response, Error = Error>,
+    ZS: Service<T::StateRequest, Response = zs::Response, Error = Error>,
+    ZV: Service<T, Error = Error>,
+{
+    let mut block_locator = state_block_locator::<T, _>(state).await?;
+
+    if block_locator.is_empty() {
+        tracing::info!("state is empty, downloading the genesis block");
+        if let Err(e) = request_genesis::<T, _, _>(peer_set, verifier, genesis_hash).await {
+            tracing::warn!(?e, "could not download the genesis block");
+        }
+        block_locator = state_block_locator::<T, _>(state).await?;
+    }
+
+    Ok(block_locator)
+}
+
+/// Query `state` for a block locator for its best chain.
+async fn state_block_locator<T, ZS>(state: &mut ZS) -> Result<Vec<BlockHeaderHash>, Report>
+where
+    T: SyncItem,
+    ZS: Service<T::StateRequest, Response = zs::Response, Error = Error>,
+{
+    let response = state
+        .ready_and()
+        .await
+        .map_err(|e| eyre!(e))?
+        .call(T::block_locator_request())
+        .await
+        .map_err(|e| eyre!(e))?;
+
+    match response {
+        zs::Response::BlockLocator { block_locator } => Ok(block_locator),
+        r => Err(eyre!(
+            "unexpected response to a block locator request: {:?}",
+            r
+        )),
+    }
+}
+
+/// Download the genesis item from `peer_set`, and verify it with `verifier`.
+async fn request_genesis<T, ZN, ZV>(
+    peer_set: &mut ZN,
+    verifier: &mut ZV,
+    genesis_hash: BlockHeaderHash,
+) -> Result<(), Report>
+where
+    T: SyncItem,
+    ZN: Service<zn::Request, Response = zn::Response, Error = Error>,
+    ZV: Service<T, Error = Error>,
+{
+    let response = peer_set
+        .ready_and()
+        .await
+        .map_err(|e| eyre!(e))?
+        .call(T::genesis_request(genesis_hash))
+        .await
+        .map_err(|e| eyre!(e))?;
+
+    let genesis = T::genesis_from_response(response, genesis_hash)?;
+
+    verifier
+        .ready_and()
+        .await
+        .map_err(|e| eyre!(e))?
+        .call(genesis)
+        .await
+        .map_err(|e| eyre!(e))?;
+
+    Ok(())
+}
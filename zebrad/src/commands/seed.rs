@@ -137,7 +137,7 @@ impl SeedCmd {
 
         let config = app_config().network.clone();
 
-        let (mut peer_set, address_book) = zebra_network::init(config, buffered_svc).await;
+        let (mut peer_set, address_book, _peer_set_handle) = zebra_network::init(config, buffered_svc).await;
 
         let _ = addressbook_tx.send(address_book);
 
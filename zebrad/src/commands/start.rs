@@ -19,14 +19,13 @@
 //!    * This task runs in the background and continuously queries the network for
 //!    new blocks to be verified and added to the local state
 use crate::config::ZebradConfig;
-use crate::{components::tokio::TokioComponent, prelude::*};
-use abscissa_core::{config, Command, FrameworkError, Options, Runnable};
-use color_eyre::eyre::Report;
-use tower::{buffer::Buffer, service_fn};
-use zebra_chain::{
-    block::BlockHeaderHash,
-//  block::{Block, BlockHeader, BlockHeaderHash},
+use crate::{
+    components::{inbound::Inbound, tokio::TokioComponent},
+    prelude::*,
 };
+use abscissa_core::{config, Command, FrameworkError, Options, Runnable};
+use color_eyre::eyre::{eyre, Report};
+use tower::{buffer::Buffer, util::BoxService};
 use std::path::{
 //  Path,
     PathBuf,
@@ -34,12 +33,6 @@ use std::path::{
 
 mod sync;
 
-// genesis
-const GENESIS: BlockHeaderHash = BlockHeaderHash([
-    8, 206, 61, 151, 49, 176, 0, 192, 131, 56, 69, 92, 138, 74, 107, 208, 93, 161, 110, 38, 177,
-    29, 170, 27, 145, 113, 132, 236, 232, 15, 4, 0,
-]);
-
 /// `start` subcommand
 #[derive(Command, Debug, Options)]
 pub struct StartCmd {
@@ -65,20 +58,27 @@ impl StartCmd {
     async fn start(&self) -> Result<(), Report> {
         info!(?self, "begin tower-based peer handling test stub");
 
-        // The service that our node uses to respond to requests by peers
-        let node = Buffer::new(
-            service_fn(|req| async move {
-                info!(?req);
-                Ok::<zebra_network::Response, Report>(zebra_network::Response::Nil)
-            }),
-            1,
-        );
         let config = app_config();
-        let state = zebra_state::on_disk::init(config.state.clone());
-        let (peer_set, _address_book) = zebra_network::init(config.network.clone(), node).await;
-        let verifier = zebra_consensus::verify::block::init(state.clone());
+        let state = zebra_state::on_disk::init(config.state.clone(), config.network.network);
+
+        // The service that our node uses to respond to requests by peers
+        let node = Buffer::new(Inbound::<_, zebra_state::RequestBlock>::new(state.clone()), 1);
+        let (peer_set, address_book, peer_set_handle) = zebra_network::init(config.network.clone(), node).await;
+        let verifier = zebra_consensus::verify::block::init(config.network.network, state.clone());
+
+        if let Some(rpc) = config.rpc.clone() {
+            let rpc_state = zebra_rpc::ChainState::Blocks(Buffer::new(BoxService::new(state.clone()), 1));
+            let peers = zebra_rpc::Peers {
+                user_agent: config.network.user_agent.clone(),
+                address_book,
+                handle: peer_set_handle,
+                peer_set: Buffer::new(BoxService::new(peer_set.clone()), 1),
+            };
+            zebra_rpc::init(rpc, config.network.network, rpc_state, peers, &config.state.cache_dir)
+                .map_err(|e| eyre!(e))?;
+        }
 
-        let mut syncer = sync::Syncer::new(peer_set, state, verifier);
+        let mut syncer = sync::Syncer::new(config.network.network, peer_set, state, verifier);
 
         syncer.sync().await
     }
@@ -1,4 +1,4 @@
-use std::{collections::HashSet, iter, sync::Arc, time::Duration};
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
 use color_eyre::eyre::{eyre, Report};
 use futures::stream::{FuturesUnordered, StreamExt};
@@ -8,12 +8,16 @@ use tracing_futures::Instrument;
 
 use zebra_chain::{
     block::{Block, BlockHeaderHash},
+    parameters::genesis_hash,
     types::BlockHeight,
+    Network,
 };
 
 use zebra_network::{self as zn, RetryLimit};
 use zebra_state::{self as zs};
 
+use crate::commands::locator;
+
 // use zebra_state::QueryType;
 
 type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -28,6 +32,7 @@ where
     pub verifier: ZV,
     pub retry_peer_set: Retry<RetryLimit, ZN>,
     pub prospective_tips: HashSet<BlockHeaderHash>,
+    pub genesis_hash: BlockHeaderHash,
     pub block_requests: FuturesUnordered<ZN::Future>,
     pub fanout: NumReq,
 }
@@ -36,7 +41,7 @@ impl<ZN, ZS, ZV> Syncer<ZN, ZS, ZV>
 where
     ZN: Service<zn::Request> + Clone,
 {
-    pub fn new(peer_set: ZN, state: ZS, verifier: ZV) -> Self {
+    pub fn new(network: Network, peer_set: ZN, state: ZS, verifier: ZV) -> Self {
         let retry_peer_set = Retry::new(RetryLimit::new(3), peer_set.clone());
         Self {
             peer_set,
@@ -46,6 +51,7 @@ where
             block_requests: FuturesUnordered::new(),
             fanout: 4,
             prospective_tips: HashSet::new(),
+            genesis_hash: genesis_hash(network),
         }
     }
 }
@@ -82,11 +88,19 @@ where
     async fn obtain_tips(&mut self) -> Result<(), Report> {
         // ObtainTips Step 1
         //
-        // Query the current state to construct the sequence of hashes: handled by
-        // the caller
-        //
-        // TODO(jlusby): get the block_locator from the state
-        let block_locator = vec![super::GENESIS];
+        // Query the current state to construct the sequence of hashes
+        let block_locator = locator::block_locator::<Arc<Block>, _, _, _>(
+            &mut self.retry_peer_set,
+            &mut self.state,
+            &mut self.verifier,
+            self.genesis_hash,
+        )
+        .await?;
+        if block_locator.is_empty() {
+            tracing::info!("state is still empty, retrying in the next sync round");
+            return Ok(());
+        }
+
         let mut tip_futs = FuturesUnordered::new();
         tracing::info!(?block_locator, "trying to obtain new chain tips");
 
@@ -141,8 +155,8 @@ where
                         "found index of first unknown hash in response"
                     );
                     if first_unknown == hashes.len() {
-                        // XXX until we fix the TODO above to construct the locator correctly,
-                        // we might hit this case, but it will be unexpected afterwards.
+                        // The peer's chain doesn't extend our best chain,
+                        // or it has only sent hashes we already have.
                         tracing::debug!("no new hashes, even though we gave our tip?");
                         continue;
                     }
@@ -219,9 +233,8 @@ where
                         // response is the genesis block; if so, discard the response.
                         // It indicates that the remote peer does not have any blocks
                         // following the prospective tip.
-                        // TODO(jlusby): reject both main and test net genesis blocks
                         match hashes.first() {
-                            Some(&super::GENESIS) => {
+                            Some(&hash) if hash == self.genesis_hash => {
                                 tracing::debug!("skipping response, peer could not extend the tip");
                                 continue;
                             }
@@ -338,12 +351,3 @@ where
         Ok(())
     }
 }
-
-/// Get the heights of the blocks for constructing a block_locator list
-#[allow(dead_code)]
-pub fn block_locator_heights(tip_height: BlockHeight) -> impl Iterator<Item = BlockHeight> {
-    iter::successors(Some(1u32), |h| h.checked_mul(2))
-        .flat_map(move |step| tip_height.0.checked_sub(step))
-        .map(BlockHeight)
-        .chain(iter::once(BlockHeight(0)))
-}
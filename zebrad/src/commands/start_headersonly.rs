@@ -19,26 +19,97 @@
 //!    * This task runs in the background and continuously queries the network for
 //!    new blocks to be verified and added to the local state
 use crate::config::ZebradConfig;
-use crate::{components::tokio::TokioComponent, prelude::*};
-use abscissa_core::{config, Command, FrameworkError, Options, Runnable};
-use color_eyre::eyre::Report;
-use tower::{buffer::Buffer, service_fn};
-use zebra_chain::{
-    block::BlockHeaderHash,
-//  block::{Block, BlockHeader, BlockHeaderHash},
+use crate::{
+    components::{inbound::Inbound, tokio::TokioComponent},
+    prelude::*,
 };
-use std::path::{
-//  Path,
-    PathBuf,
+use abscissa_core::{config, Command, FrameworkError, Options, Runnable};
+use color_eyre::eyre::{eyre, Report};
+use futures::future::{abortable, AbortHandle, Aborted, BoxFuture, FutureExt};
+use tokio::{sync::watch, task::JoinHandle};
+use tower::{buffer::Buffer, util::BoxService};
+use std::{
+    path::{
+    //  Path,
+        PathBuf,
+    },
+    sync::{Arc, Mutex},
 };
 
+use zebra_network::{AddressBook, PeerSetHandle};
+
 mod sync_headersonly;
 
-// genesis
-const GENESIS: BlockHeaderHash = BlockHeaderHash([
-    8, 206, 61, 151, 49, 176, 0, 192, 131, 56, 69, 92, 138, 74, 107, 208, 93, 161, 110, 38, 177,
-    29, 170, 27, 145, 113, 132, 236, 232, 15, 4, 0,
-]);
+pub use sync_headersonly::SyncStatus;
+
+type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// The block header state of a headers-only node.
+///
+/// The state service is boxed, so applications that embed a node can name its
+/// type.
+pub type HeadersOnlyState = Buffer<
+    BoxService<zebra_state::RequestBlockHeader, zebra_state::Response, Error>,
+    zebra_state::RequestBlockHeader,
+>;
+
+/// The services of a headers-only node, returned by `init_node`.
+pub struct HeadersOnlyNode {
+    /// The block header state
+    pub state: HeadersOnlyState,
+    /// Stops the state's worker task, which owns the state database
+    ///
+    /// Aborting the worker closes the database, even if other tasks still
+    /// have handles to the state. Those handles fail their next request.
+    pub abort_state: AbortHandle,
+    /// The state's worker task, which finishes after it is aborted
+    pub state_task: JoinHandle<Result<(), Aborted>>,
+    /// The addresses of the peers that the node knows about
+    pub address_book: Arc<Mutex<AddressBook>>,
+    /// The control channel for adding and evicting peers, and shutting down
+    /// the network
+    pub peer_set_handle: PeerSetHandle,
+    /// The peer set, for sending requests to peers
+    pub peer_set: zebra_rpc::PeerSet,
+    /// The progress of the syncer
+    pub sync_status: watch::Receiver<SyncStatus>,
+    /// The sync task, which runs until it fails
+    ///
+    /// The node only syncs while this future is polled. Dropping it stops the
+    /// syncer, and drops its handles to the network and state services.
+    pub sync: BoxFuture<'static, Result<(), Report>>,
+}
+
+/// Initialize the state, network, and verifier services of a headers-only
+/// node, using `config`.
+///
+/// Must be called from within a tokio runtime, because the network service
+/// spawns tasks for each peer.
+pub async fn init_node(config: &ZebradConfig) -> HeadersOnlyNode {
+    let (state, state_worker) = zebra_state::on_disk_headersonly::init_with_worker(config.state.clone(), config.network.network);
+    let (state_worker, abort_state) = abortable(state_worker);
+    let state_task = tokio::spawn(state_worker);
+    let state: HeadersOnlyState = Buffer::new(BoxService::new(state), 1);
+
+    // The service that our node uses to respond to requests by peers
+    let node = Buffer::new(Inbound::<_, zebra_state::RequestBlockHeader>::new(state.clone()), 1);
+    let (peer_set, address_book, peer_set_handle) = zebra_network::init(config.network.clone(), node).await;
+    let verifier = zebra_consensus::verify::header::init(config.network.network, state.clone());
+
+    let mut syncer = sync_headersonly::Syncer::new(config.network.network, peer_set.clone(), state.clone(), verifier);
+    let sync_status = syncer.status();
+
+    HeadersOnlyNode {
+        state,
+        abort_state,
+        state_task,
+        address_book,
+        peer_set_handle,
+        peer_set: Buffer::new(BoxService::new(peer_set), 1),
+        sync_status,
+        sync: async move { syncer.sync().await }.boxed(),
+    }
+}
 
 /// `start-headers-only` subcommand
 #[derive(Command, Debug, Options)]
@@ -65,23 +136,22 @@ impl StartHeadersOnlyCmd {
     async fn start(&self) -> Result<(), Report> {
         info!(?self, "begin tower-based peer handling test stub");
 
-        // The service that our node uses to respond to requests by peers
-        let node = Buffer::new(
-            service_fn(|req| async move {
-                info!(?req);
-                Ok::<zebra_network::Response, Report>(zebra_network::Response::Nil)
-            }),
-            1,
-        );
         let config = app_config();
-        let state = zebra_state::on_disk_headersonly::init(config.state.clone());
-        let _storage = ibclib::prelude::IBCStorage::new(state.clone());
-        let (peer_set, _address_book) = zebra_network::init(config.network.clone(), node).await;
-        let verifier = zebra_consensus::verify::header::init(state.clone());
+        let node = init_node(&config).await;
 
-        let mut syncer = sync_headersonly::Syncer::new(peer_set, state, verifier);
+        if let Some(rpc) = config.rpc.clone() {
+            let rpc_state = zebra_rpc::ChainState::Headers(node.state.clone());
+            let peers = zebra_rpc::Peers {
+                user_agent: config.network.user_agent.clone(),
+                address_book: node.address_book.clone(),
+                handle: node.peer_set_handle.clone(),
+                peer_set: node.peer_set.clone(),
+            };
+            zebra_rpc::init(rpc, config.network.network, rpc_state, peers, &config.state.cache_dir)
+                .map_err(|e| eyre!(e))?;
+        }
 
-        syncer.sync().await
+        node.sync.await
     }
 }
 
@@ -1,24 +1,41 @@
-use std::{collections::HashSet, iter, sync::Arc, time::Duration};
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
 use color_eyre::eyre::{eyre, Report};
 use futures::stream::{FuturesUnordered, StreamExt};
-use tokio::time::delay_for;
-use tower::{retry::Retry, Service, ServiceExt};
-use tracing_futures::Instrument;
+use tokio::{sync::watch, time::delay_for};
+use tower::{Service, ServiceExt};
 
 use zebra_chain::{
     block::{BlockHeader, BlockHeaderHash},
+    parameters::genesis_hash,
     types::BlockHeight,
+    Network,
 };
 
-use zebra_network::{self as zn, RetryLimit};
+use zebra_consensus::verify::chain::ChainError;
+use zebra_network::{self as zn};
 use zebra_state::{self as zs};
 
-// use zebra_state::QueryType;
+use crate::commands::locator;
 
 type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
 type NumReq = u32;
 
+/// The progress of the block header syncer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SyncStatus {
+    /// The height of the last block header that the syncer verified, if any
+    pub verified_height: Option<BlockHeight>,
+    /// True if the last sync round ran out of prospective tips, so the syncer
+    /// is waiting for peers to announce new block headers
+    pub is_idle: bool,
+}
+
+/// Syncs block headers using `getheaders` and `headers` messages.
+///
+/// Block bodies are never requested: each `headers` response contains up to
+/// `MAX_HEADERS_RESULTS` headers, which are verified and added to the state
+/// in order.
 pub struct Syncer<ZN, ZS, ZV>
 where
     ZN: Service<zn::Request>,
@@ -26,28 +43,46 @@ where
     pub peer_set: ZN,
     pub state: ZS,
     pub verifier: ZV,
-    pub retry_peer_set: Retry<RetryLimit, ZN>,
     pub prospective_tips: HashSet<BlockHeaderHash>,
-    pub block_requests: FuturesUnordered<ZN::Future>,
+    pub genesis_hash: BlockHeaderHash,
     pub fanout: NumReq,
+    status: SyncStatus,
+    status_sender: watch::Sender<SyncStatus>,
+    status_receiver: watch::Receiver<SyncStatus>,
 }
 
 impl<ZN, ZS, ZV> Syncer<ZN, ZS, ZV>
 where
     ZN: Service<zn::Request> + Clone,
 {
-    pub fn new(peer_set: ZN, state: ZS, verifier: ZV) -> Self {
-        let retry_peer_set = Retry::new(RetryLimit::new(3), peer_set.clone());
+    pub fn new(network: Network, peer_set: ZN, state: ZS, verifier: ZV) -> Self {
+        let (status_sender, status_receiver) = watch::channel(SyncStatus::default());
+
         Self {
             peer_set,
             state,
             verifier,
-            retry_peer_set,
-            block_requests: FuturesUnordered::new(),
             fanout: 4,
             prospective_tips: HashSet::new(),
+            genesis_hash: genesis_hash(network),
+            status: SyncStatus::default(),
+            status_sender,
+            status_receiver,
         }
     }
+
+    /// Returns a watch channel that is updated as the syncer makes progress.
+    pub fn status(&self) -> watch::Receiver<SyncStatus> {
+        self.status_receiver.clone()
+    }
+
+    /// Update the sync status, and notify any watchers.
+    fn update_status(&mut self, update: impl FnOnce(&mut SyncStatus)) {
+        update(&mut self.status);
+
+        // The syncer holds a receiver, so the channel is never closed
+        let _ = self.status_sender.broadcast(self.status);
+    }
 }
 
 impl<ZN, ZS, ZV> Syncer<ZN, ZS, ZV>
@@ -56,15 +91,16 @@ where
     ZN::Future: Send,
     ZS: Service<zs::RequestBlockHeader, Response = zs::Response, Error = Error> + Send + Clone + 'static,
     ZS::Future: Send,
-    ZV: Service<(Arc<BlockHeader>, BlockHeight), Response = (BlockHeaderHash, BlockHeight), Error = Error> + Send + Clone + 'static,
+    ZV: Service<Arc<BlockHeader>, Response = (BlockHeaderHash, BlockHeight), Error = Error> + Send + Clone + 'static,
     ZV::Future: Send,
 {
     #[instrument(skip(self))]
     pub async fn sync(&mut self) -> Result<(), Report> {
         loop {
+            self.update_status(|status| status.is_idle = false);
             self.obtain_tips().await?;
 
-            // ObtainTips Step 6
+            // ObtainTips Step 4
             //
             // If there are any prospective tips, call ExtendTips. Continue this step until there are no more prospective tips.
             while !self.prospective_tips.is_empty() {
@@ -72,31 +108,39 @@ where
                 self.extend_tips().await?;
             }
 
+            self.update_status(|status| status.is_idle = true);
             delay_for(Duration::from_secs(15)).await;
         }
     }
 
-    /// Given a block_locator list fan out request for subsequent hashes to
+    /// Given a block_locator list fan out request for subsequent headers to
     /// multiple peers
     #[instrument(skip(self))]
     async fn obtain_tips(&mut self) -> Result<(), Report> {
         // ObtainTips Step 1
         //
-        // Query the current state to construct the sequence of hashes: handled by
-        // the caller
-        //
-        // TODO(jlusby): get the block_locator from the state
-        let block_locator = vec![super::GENESIS];
+        // Query the current state to construct the sequence of hashes
+        let block_locator = locator::block_locator::<Arc<BlockHeader>, _, _, _>(
+            &mut self.peer_set,
+            &mut self.state,
+            &mut self.verifier,
+            self.genesis_hash,
+        )
+        .await?;
+        if block_locator.is_empty() {
+            tracing::info!("state is still empty, retrying in the next sync round");
+            return Ok(());
+        }
         let mut tip_futs = FuturesUnordered::new();
         tracing::info!(?block_locator, "trying to obtain new chain tips");
 
         // ObtainTips Step 2
         //
-        // Make a FindBlocksByHash request to the network F times, where F is a
+        // Make a FindHeaders request to the network F times, where F is a
         // fanout parameter, to get resp1, ..., respF
         for _ in 0..self.fanout {
             let req = self.peer_set.ready_and().await.map_err(|e| eyre!(e))?.call(
-                zn::Request::FindBlocks {
+                zn::Request::FindHeaders {
                     known_blocks: block_locator.clone(),
                     stop: None,
                 },
@@ -104,86 +148,28 @@ where
             tip_futs.push(req);
         }
 
-        let mut download_set = HashSet::new();
         while let Some(res) = tip_futs.next().await {
             match res.map_err::<Report, _>(|e| eyre!(e)) {
-                Ok(zn::Response::BlockHeaderHashes(hashes)) => {
-                    if hashes.is_empty() {
+                Ok(zn::Response::BlockHeaders(headers)) => {
+                    if headers.is_empty() {
                         tracing::debug!("skipping empty response");
                         continue;
-                    } else {
-                        tracing::debug!(hashes.len = hashes.len(), "processing response");
                     }
+                    tracing::debug!(headers.len = headers.len(), "processing response");
 
                     // ObtainTips Step 3
                     //
-                    // For each response, starting from the beginning of the
-                    // list, prune any block hashes already included in the
-                    // state, stopping at the first unknown hash to get resp1',
-                    // ..., respF'. (These lists may be empty).
-                    let mut first_unknown = 0;
-                    for (i, &hash) in hashes.iter().enumerate() {
-                        let depth = self
-                            .state
-                            .ready_and()
-                            .await
-                            .map_err(|e| eyre!(e))?
-                            .call(zebra_state::RequestBlockHeader::GetDepth { hash })
-                            .await
-                            .map_err(|e| eyre!(e))?;
-                        if let zs::Response::Depth(None) = depth {
-                            first_unknown = i;
-                            break;
-                        }
-                    }
-                    tracing::debug!(
-                        first_unknown,
-                        "found index of first unknown hash in response"
-                    );
-                    if first_unknown == hashes.len() {
-                        // XXX until we fix the TODO above to construct the locator correctly,
-                        // we might hit this case, but it will be unexpected afterwards.
-                        tracing::debug!("no new hashes, even though we gave our tip?");
-                        continue;
-                    }
-
-                    let unknown_hashes = &hashes[first_unknown..];
-                    let new_tip = *unknown_hashes
-                        .last()
-                        .expect("already checked first_unknown < hashes.len()");
-
-                    // ObtainTips Step 4:
-                    // Combine the last elements of each list into a set; this is the
-                    // set of prospective tips.
-                    if !download_set.contains(&new_tip) {
-                        tracing::debug!(?new_tip, "adding new prospective tip");
-                        self.prospective_tips.insert(new_tip);
-                    } else {
-                        tracing::debug!(?new_tip, "discarding tip already queued for download");
-                    }
-
-                    let prev_download_len = download_set.len();
-                    download_set.extend(unknown_hashes);
-                    let new_download_len = download_set.len();
-                    tracing::debug!(
-                        prev_download_len,
-                        new_download_len,
-                        new_hashes = new_download_len - prev_download_len,
-                        "added hashes to download set"
-                    );
+                    // Verify the headers in each response, skipping any
+                    // headers already included in the state. If the response
+                    // is full, the peer has more headers, so its last header
+                    // is a prospective tip.
+                    self.verify_headers(headers).await?;
                 }
                 Ok(r) => tracing::error!("unexpected response {:?}", r),
                 Err(e) => tracing::error!("{:?}", e),
             }
         }
 
-        // ObtainTips Step 5
-        //
-        // Combine all elements of each list into a set, and queue
-        // download and verification of those blocks.
-        self.request_blocks(download_set.into_iter().collect())
-            .await?;
-
         Ok(())
     }
 
@@ -195,16 +181,15 @@ where
         let tips = std::mem::take(&mut self.prospective_tips);
         tracing::debug!(?tips, "extending tip set");
 
-        let mut download_set = HashSet::new();
         for tip in tips {
             // ExtendTips Step 2
             //
-            // Create a FindBlocksByHash request consisting of just the
+            // Create a FindHeaders request consisting of just the
             // prospective tip. Send this request to the network F times
             let mut tip_futs = FuturesUnordered::new();
             for _ in 0..self.fanout {
                 tip_futs.push(self.peer_set.ready_and().await.map_err(|e| eyre!(e))?.call(
-                    zn::Request::FindBlocks {
+                    zn::Request::FindHeaders {
                         known_blocks: vec![tip],
                         stop: None,
                     },
@@ -212,16 +197,16 @@ where
             }
             while let Some(res) = tip_futs.next().await {
                 match res.map_err::<Report, _>(|e| eyre!(e)) {
-                    Ok(zn::Response::BlockHeaderHashes(mut hashes)) => {
+                    Ok(zn::Response::BlockHeaders(headers)) => {
                         // ExtendTips Step 3
                         //
-                        // For each response, check whether the first hash in the
-                        // response is the genesis block; if so, discard the response.
-                        // It indicates that the remote peer does not have any blocks
-                        // following the prospective tip.
-                        // TODO(jlusby): reject both main and test net genesis blocks
-                        match hashes.first() {
-                            Some(&super::GENESIS) => {
+                        // For each response, check whether the first header in
+                        // the response extends the prospective tip; if not,
+                        // discard the response. It indicates that the remote
+                        // peer does not have the prospective tip, so it sent
+                        // headers from its fork point instead.
+                        match headers.first() {
+                            Some(header) if header.previous_block_hash != tip => {
                                 tracing::debug!("skipping response, peer could not extend the tip");
                                 continue;
                             }
@@ -232,16 +217,11 @@ where
                             Some(_) => {}
                         }
 
-                        let new_tip = hashes.pop().expect("expected: hashes must have len > 0");
-
                         // ExtendTips Step 4
                         //
-                        // Combine the last elements of the remaining responses into
-                        // a set, and add this set to the set of prospective tips.
-                        tracing::debug!(?new_tip, hashes.len = ?hashes.len());
-                        let _ = self.prospective_tips.insert(new_tip);
-
-                        download_set.extend(hashes);
+                        // Verify the headers, and add the last header of full
+                        // responses to the set of prospective tips.
+                        self.verify_headers(headers).await?;
                     }
                     Ok(r) => tracing::error!("unexpected response {:?}", r),
                     Err(e) => tracing::error!("{:?}", e),
@@ -249,101 +229,78 @@ where
             }
         }
 
-        // ExtendTips Step ??
-        //
-        // Remove tips that are already included behind one of the other
-        // returned tips
-        self.prospective_tips
-            .retain(|tip| !download_set.contains(tip));
-
-        // ExtendTips Step 5
-        //
-        // Combine all elements of the remaining responses into a
-        // set, and queue download and verification of those blocks
-        self.request_blocks(
-            download_set
-                .into_iter()
-                .chain(self.prospective_tips.iter().cloned())
-                .collect(),
-        )
-        .await?;
-
         Ok(())
     }
 
-    /// Queue downloads for each block that isn't currently known to our node
-    #[instrument(skip(self, hashes))]
-    async fn request_blocks(&mut self, hashes: Vec<BlockHeaderHash>) -> Result<(), Report> {
-        tracing::debug!(hashes.len = hashes.len(), "requesting blocks");
-        for chunk in hashes.chunks(10usize) {
-            let set = chunk.iter().cloned().collect();
+    /// Verify `headers` in order, skipping headers that are already in the
+    /// state.
+    ///
+    /// If `headers` is a full response, and every header was added to the
+    /// state, the last header becomes a prospective tip.
+    #[instrument(skip(self, headers))]
+    async fn verify_headers(&mut self, headers: Vec<Arc<BlockHeader>>) -> Result<(), Report> {
+        let full_response = headers.len() >= zn::MAX_HEADERS_RESULTS;
+        let mut last_hash = None;
+
+        for header in headers {
+            let hash: BlockHeaderHash = header.as_ref().into();
+
+            if self.state_contains(hash).await? {
+                last_hash = Some(hash);
+                continue;
+            }
 
-            let request = self
-                .retry_peer_set
+            let result = self
+                .verifier
                 .ready_and()
                 .await
                 .map_err(|e| eyre!(e))?
-                .call(zn::Request::BlocksByHash(set));
-
-            let verifier = self.verifier.clone();
-
-            let _ = tokio::spawn(
-                async move {
-                    // XXX for some reason the tracing filter
-                    // filter = 'info,[sync]=debug'
-                    // does not pick this up, even though this future is instrumented
-                    // with the current span below.  However, fixing it immediately
-                    // isn't critical because this code needs to be changed to propagate
-                    // backpressure to the syncer.
-                    tracing::debug!("test");
-                    let result_fut = async move {
-                        let mut handles = FuturesUnordered::new();
-                        let resp = request.await?;
-
-                        if let zn::Response::Blocks(blocks) = resp {
-                            debug!(count = blocks.len(), "received blocks");
-
-                            for block in blocks {
-                                let mut verifier = verifier.clone();
-                                let handle = tokio::spawn(async move {
-                                    let hash: BlockHeaderHash = block.as_ref().into();
-                                    let hash_str = hex::encode(&hash.0);
-                                    let height = block.coinbase_height().unwrap();
-                                    tracing::info!("Block header with height {:?} and hash {:?} stored!", height, hash_str);
-                                //  entry point to storing block headers into on-disk state
-                                    verifier.ready_and().await?.call((block.header.into(), height)).await
-                                });
-                                handles.push(handle);
-                            }
-                        } else {
-                            debug!(?resp, "unexpected response");
-                        }
-
-                        while let Some(res) = handles.next().await {
-                            let (_hash, _height) = res??;
+                .call(header)
+                .await;
+
+            match result {
+                Ok((hash, height)) => {
+                    tracing::info!(?height, ?hash, "verified block header");
+                    last_hash = Some(hash);
+                    self.update_status(|status| status.verified_height = Some(height));
+                }
+                Err(e) => {
+                    match e.downcast_ref::<ChainError>() {
+                        // The rest of the response doesn't connect either, so
+                        // we wait for headers from a later sync round
+                        Some(ChainError::Orphan { .. }) => {
+                            tracing::debug!(?hash, "skipping orphan block header");
                         }
-
-                        Ok::<_, Error>(())
-                    };
-
-                    match result_fut.await {
-                        Ok(()) => {}
-                        Err(e) => error!("{:?}", e),
+                        _ => tracing::error!(?hash, %e, "block header verification failed"),
                     }
+                    return Ok(());
                 }
-                .instrument(tracing::Span::current()),
-            );
+            }
+        }
+
+        if let (true, Some(new_tip)) = (full_response, last_hash) {
+            tracing::debug!(?new_tip, "adding new prospective tip");
+            self.prospective_tips.insert(new_tip);
         }
 
         Ok(())
     }
-}
 
-/// Get the heights of the blocks for constructing a block_locator list
-#[allow(dead_code)]
-pub fn block_locator_heights(tip_height: BlockHeight) -> impl Iterator<Item = BlockHeight> {
-    iter::successors(Some(1u32), |h| h.checked_mul(2))
-        .flat_map(move |step| tip_height.0.checked_sub(step))
-        .map(BlockHeight)
-        .chain(iter::once(BlockHeight(0)))
+    /// Ask the state if it contains the block header with `hash`, on any
+    /// chain.
+    async fn state_contains(&mut self, hash: BlockHeaderHash) -> Result<bool, Report> {
+        let response = self
+            .state
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(zebra_state::RequestBlockHeader::Contains { hash })
+            .await
+            .map_err(|e| eyre!(e))?;
+
+        match response {
+            zs::Response::Contains(contains) => Ok(contains),
+            r => Err(eyre!("unexpected response to a contains request: {:?}", r)),
+        }
+    }
 }
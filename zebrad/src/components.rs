@@ -1,3 +1,4 @@
+pub mod inbound;
 pub mod metrics;
 pub mod tokio;
 pub mod tracing;
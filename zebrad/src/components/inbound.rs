@@ -0,0 +1,273 @@
+//! The inbound service, which answers requests from peers using the state.
+//!
+//! Peers use these requests to sync from our node:
+//!   * `getheaders` is answered with up to 160 block headers,
+//!   * `getblocks` is answered with an `inv` of block hashes, and
+//!   * `getdata` is answered with the requested blocks that we have.
+//!
+//! Block locators are matched against our best chain. Headers-only states
+//! don't store blocks, so their `getdata` responses are always empty.
+
+use std::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::future::FutureExt;
+use tower::{Service, ServiceExt};
+
+use zebra_chain::block::BlockHeaderHash;
+use zebra_network as zn;
+use zebra_state as zs;
+
+type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// A state request type that the inbound service can use to answer peer
+/// requests.
+pub trait InboundStateRequest: Clone + Send + 'static {
+    /// Returns a request for the best chain block headers after
+    /// `known_blocks`.
+    fn find_block_headers(
+        known_blocks: Vec<BlockHeaderHash>,
+        stop: Option<BlockHeaderHash>,
+    ) -> Self;
+
+    /// Returns a request for the best chain block hashes after
+    /// `known_blocks`.
+    fn find_block_hashes(known_blocks: Vec<BlockHeaderHash>, stop: Option<BlockHeaderHash>)
+        -> Self;
+
+    /// Returns a request for the block with `hash`, or `None` if the state
+    /// doesn't store blocks.
+    fn get_block(hash: BlockHeaderHash) -> Option<Self>;
+}
+
+impl InboundStateRequest for zs::RequestBlock {
+    fn find_block_headers(
+        known_blocks: Vec<BlockHeaderHash>,
+        stop: Option<BlockHeaderHash>,
+    ) -> Self {
+        zs::RequestBlock::FindBlockHeaders { known_blocks, stop }
+    }
+
+    fn find_block_hashes(
+        known_blocks: Vec<BlockHeaderHash>,
+        stop: Option<BlockHeaderHash>,
+    ) -> Self {
+        zs::RequestBlock::FindBlockHashes { known_blocks, stop }
+    }
+
+    fn get_block(hash: BlockHeaderHash) -> Option<Self> {
+        Some(zs::RequestBlock::GetBlock { query: hash.into() })
+    }
+}
+
+impl InboundStateRequest for zs::RequestBlockHeader {
+    fn find_block_headers(
+        known_blocks: Vec<BlockHeaderHash>,
+        stop: Option<BlockHeaderHash>,
+    ) -> Self {
+        zs::RequestBlockHeader::FindBlockHeaders { known_blocks, stop }
+    }
+
+    fn find_block_hashes(
+        known_blocks: Vec<BlockHeaderHash>,
+        stop: Option<BlockHeaderHash>,
+    ) -> Self {
+        zs::RequestBlockHeader::FindBlockHashes { known_blocks, stop }
+    }
+
+    fn get_block(_hash: BlockHeaderHash) -> Option<Self> {
+        None
+    }
+}
+
+/// The inbound service for a node with a state service of type `S`, which
+/// accepts state requests of type `R`.
+#[derive(Clone)]
+pub struct Inbound<S, R> {
+    state: S,
+    _request: PhantomData<fn(R)>,
+}
+
+impl<S, R> Inbound<S, R> {
+    /// Returns an inbound service that answers peer requests using `state`.
+    pub fn new(state: S) -> Self {
+        Self {
+            state,
+            _request: PhantomData,
+        }
+    }
+}
+
+impl<S, R> Service<zn::Request> for Inbound<S, R>
+where
+    R: InboundStateRequest,
+    S: Service<R, Response = zs::Response, Error = Error> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = zn::Response;
+    type Error = Error;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Each request uses its own clone of the state service
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: zn::Request) -> Self::Future {
+        let state = self.state.clone();
+
+        match req {
+            zn::Request::FindHeaders { known_blocks, stop } => async move {
+                match state
+                    .oneshot(R::find_block_headers(known_blocks, stop))
+                    .await?
+                {
+                    zs::Response::BlockHeaders { block_headers } => {
+                        Ok(zn::Response::BlockHeaders(block_headers))
+                    }
+                    _ => Err("unexpected response to a find block headers request".into()),
+                }
+            }
+            .boxed(),
+            zn::Request::FindBlocks { known_blocks, stop } => async move {
+                match state
+                    .oneshot(R::find_block_hashes(known_blocks, stop))
+                    .await?
+                {
+                    zs::Response::BlockHashes { block_hashes } => {
+                        Ok(zn::Response::BlockHeaderHashes(block_hashes))
+                    }
+                    _ => Err("unexpected response to a find block hashes request".into()),
+                }
+            }
+            .boxed(),
+            zn::Request::BlocksByHash(hashes) => async move {
+                let mut blocks = Vec::new();
+
+                for request in hashes.into_iter().filter_map(R::get_block) {
+                    // The state returns an error for missing blocks, so we
+                    // just skip the blocks that we don't have
+                    if let Ok(zs::Response::Block { block }) = state.clone().oneshot(request).await
+                    {
+                        blocks.push(block);
+                    }
+                }
+
+                Ok(zn::Response::Blocks(blocks))
+            }
+            .boxed(),
+            req => {
+                debug!(?req, "ignoring unsupported inbound request");
+                async { Ok(zn::Response::Nil) }.boxed()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use color_eyre::eyre::{eyre, Report};
+    use zebra_chain::{block::Block, serialization::ZcashDeserialize};
+
+    #[tokio::test]
+    async fn headers_only_inbound() -> Result<(), Report> {
+        zebra_test::init();
+
+        let block0 =
+            Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_GENESIS_BYTES[..])?;
+        let block1 = Block::zcash_deserialize(&zebra_test::vectors::BLOCK_MAINNET_1_BYTES[..])?;
+        let hash0: BlockHeaderHash = (&block0).into();
+        let hash1: BlockHeaderHash = (&block1).into();
+
+        let mut state = zs::in_memory_headersonly::init();
+        for block in &[&block0, &block1] {
+            state
+                .ready_and()
+                .await
+                .map_err(|e| eyre!(e))?
+                .call(zs::RequestBlockHeader::AddBlockHeader {
+                    block_header: Arc::new(block.header),
+                })
+                .await
+                .map_err(|e| eyre!(e))?;
+        }
+
+        let mut inbound = Inbound::<_, zs::RequestBlockHeader>::new(state);
+
+        // getheaders from genesis
+        match inbound
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(zn::Request::FindHeaders {
+                known_blocks: vec![hash0],
+                stop: None,
+            })
+            .await
+            .map_err(|e| eyre!(e))?
+        {
+            zn::Response::BlockHeaders(headers) => {
+                assert_eq!(headers, vec![Arc::new(block1.header)])
+            }
+            r => panic!("unexpected response {:?}", r),
+        }
+
+        // getblocks with an unknown locator starts after genesis
+        match inbound
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(zn::Request::FindBlocks {
+                known_blocks: vec![BlockHeaderHash([0xff; 32])],
+                stop: None,
+            })
+            .await
+            .map_err(|e| eyre!(e))?
+        {
+            zn::Response::BlockHeaderHashes(hashes) => assert_eq!(hashes, vec![hash1]),
+            r => panic!("unexpected response {:?}", r),
+        }
+
+        // getheaders without a locator returns the stop header
+        match inbound
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(zn::Request::FindHeaders {
+                known_blocks: Vec::new(),
+                stop: Some(hash0),
+            })
+            .await
+            .map_err(|e| eyre!(e))?
+        {
+            zn::Response::BlockHeaders(headers) => {
+                assert_eq!(headers, vec![Arc::new(block0.header)])
+            }
+            r => panic!("unexpected response {:?}", r),
+        }
+
+        // Headers-only states don't have any blocks
+        match inbound
+            .ready_and()
+            .await
+            .map_err(|e| eyre!(e))?
+            .call(zn::Request::BlocksByHash(vec![hash1].into_iter().collect()))
+            .await
+            .map_err(|e| eyre!(e))?
+        {
+            zn::Response::Blocks(blocks) => assert!(blocks.is_empty()),
+            r => panic!("unexpected response {:?}", r),
+        }
+
+        Ok(())
+    }
+}
@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 
 use zebra_network::Config as NetworkSection;
 
+use zebra_rpc::Config as RpcSection;
+
 use zebra_state::Config as StateSection;
 
 /// Configuration for `zebrad`.
@@ -28,6 +30,12 @@ pub struct ZebradConfig {
     pub metrics: MetricsSection,
     /// State configuration
     pub state: StateSection,
+    /// IBC configuration
+    pub ibc: IbcSection,
+    /// RPC configuration
+    ///
+    /// The RPC server only runs if this section is present.
+    pub rpc: Option<RpcSection>,
 }
 
 /// Tracing configuration section.
@@ -61,6 +69,28 @@ impl Default for MetricsSection {
     }
 }
 
+/// IBC configuration section.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct IbcSection {
+    /// The number of blocks that must be above a block header, before IBC
+    /// queries treat it as final.
+    pub finality_depth: u32,
+    /// The address for the local IBC query server, if any.
+    ///
+    /// Only used by nodes embedded using `ibcrun`.
+    pub listen_addr: Option<SocketAddr>,
+}
+
+impl Default for IbcSection {
+    fn default() -> Self {
+        Self {
+            finality_depth: ibclib::prelude::DEFAULT_FINALITY_DEPTH,
+            listen_addr: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use color_eyre::eyre::Result;